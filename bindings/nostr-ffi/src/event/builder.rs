@@ -14,6 +14,7 @@ use super::{Event, EventId};
 use crate::error::Result;
 use crate::key::Keys;
 use crate::nips::nip15::{ProductData, StallData};
+use crate::nips::nip47::Method;
 use crate::nips::nip53::LiveEvent;
 use crate::nips::nip57::ZapRequestData;
 use crate::nips::nip90::DataVendingMachineStatus;
@@ -159,12 +160,61 @@ impl EventBuilder {
         }))
     }
 
+    /// Private Direct Message rumor
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/17.md>
     #[uniffi::constructor]
-    pub fn repost(event_id: Arc<EventId>, public_key: Arc<PublicKey>) -> Arc<Self> {
+    pub fn private_msg_rumor(
+        receiver: Arc<PublicKey>,
+        message: String,
+        reply_to: Option<Arc<EventId>>,
+    ) -> Arc<Self> {
         Arc::new(Self {
-            inner: nostr::EventBuilder::repost(
-                event_id.as_ref().into(),
-                *public_key.as_ref().deref(),
+            inner: nostr::EventBuilder::private_msg_rumor(
+                *receiver.as_ref().deref(),
+                message,
+                reply_to.map(|id| id.as_ref().into()),
+            ),
+        })
+    }
+
+    /// Seal
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/59.md>
+    #[uniffi::constructor]
+    pub fn seal(
+        sender_keys: Arc<Keys>,
+        receiver_pubkey: Arc<PublicKey>,
+        rumor: Arc<UnsignedEvent>,
+    ) -> Result<Arc<Self>> {
+        Ok(Arc::new(Self {
+            inner: nostr::EventBuilder::seal(
+                sender_keys.deref(),
+                receiver_pubkey.as_ref().deref(),
+                rumor.as_ref().deref().clone(),
+            )?,
+        }))
+    }
+
+    /// Repost event
+    #[uniffi::constructor]
+    pub fn repost(event: Arc<Event>, relay_url: Option<String>) -> Arc<Self> {
+        Arc::new(Self {
+            inner: nostr::EventBuilder::repost_embedded(
+                event.as_ref().deref(),
+                relay_url.map(UncheckedUrl::from),
+            ),
+        })
+    }
+
+    /// Quote repost
+    #[uniffi::constructor]
+    pub fn quote(content: String, quoted: Arc<Event>, relay_url: Option<String>) -> Arc<Self> {
+        Arc::new(Self {
+            inner: nostr::EventBuilder::quote(
+                content,
+                quoted.as_ref().deref(),
+                relay_url.map(UncheckedUrl::from),
             ),
         })
     }
@@ -273,6 +323,15 @@ impl EventBuilder {
         }))
     }
 
+    #[uniffi::constructor]
+    pub fn nostr_wallet_connect_info(methods: Vec<Method>) -> Arc<Self> {
+        Arc::new(Self {
+            inner: nostr::EventBuilder::nostr_wallet_connect_info(
+                methods.into_iter().map(|m| m.into()),
+            ),
+        })
+    }
+
     #[uniffi::constructor]
     pub fn live_event(live_event: LiveEvent) -> Arc<Self> {
         Arc::new(Self {
@@ -167,6 +167,20 @@ impl Event {
             .collect()
     }
 
+    /// Extract quoted event IDs from tags (`q` tag)
+    pub fn quoted_events(&self) -> Vec<Arc<EventId>> {
+        self.inner
+            .quoted_events()
+            .copied()
+            .map(|p| Arc::new(p.into()))
+            .collect()
+    }
+
+    /// Check if it's a quote repost (text note with at least one `q` tag)
+    pub fn is_quote(&self) -> bool {
+        self.inner.is_quote()
+    }
+
     /// Extract coordinates from tags (`a` tag)
     pub fn coordinates(&self) -> Vec<Arc<Coordinate>> {
         self.inner
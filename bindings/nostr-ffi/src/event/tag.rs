@@ -58,6 +58,8 @@ impl From<tag::Marker> for Marker {
 pub enum Report {
     /// Depictions of nudity, porn, etc
     Nudity,
+    /// Virus, trojan horse, worm, robot, spyware, adware, back door, ransomware, rootkit, kidnapper, etc.
+    Malware,
     /// Profanity, hateful speech, etc.
     Profanity,
     /// Something which may be illegal in some jurisdiction
@@ -68,16 +70,20 @@ pub enum Report {
     Spam,
     /// Someone pretending to be someone else
     Impersonation,
+    /// Reports that don't fit in the above categories
+    Other,
 }
 
 impl From<Report> for tag::Report {
     fn from(value: Report) -> Self {
         match value {
             Report::Nudity => Self::Nudity,
+            Report::Malware => Self::Malware,
             Report::Profanity => Self::Profanity,
             Report::Illegal => Self::Illegal,
             Report::Spam => Self::Spam,
             Report::Impersonation => Self::Impersonation,
+            Report::Other => Self::Other,
         }
     }
 }
@@ -86,10 +92,12 @@ impl From<tag::Report> for Report {
     fn from(value: tag::Report) -> Self {
         match value {
             tag::Report::Nudity => Self::Nudity,
+            tag::Report::Malware => Self::Malware,
             tag::Report::Profanity => Self::Profanity,
             tag::Report::Illegal => Self::Illegal,
             tag::Report::Spam => Self::Spam,
             tag::Report::Impersonation => Self::Impersonation,
+            tag::Report::Other => Self::Other,
         }
     }
 }
@@ -176,6 +184,8 @@ pub enum TagKind {
     U,
     /// SHA256
     X,
+    /// Quote
+    Q,
     /// Relay
     RelayUrl,
     /// Nonce
@@ -268,6 +278,7 @@ impl From<tag::TagKind> for TagKind {
             tag::TagKind::M => Self::M,
             tag::TagKind::U => Self::U,
             tag::TagKind::X => Self::X,
+            tag::TagKind::Q => Self::Q,
             tag::TagKind::Relay => Self::RelayUrl,
             tag::TagKind::Nonce => Self::Nonce,
             tag::TagKind::Delegation => Self::Delegation,
@@ -326,6 +337,7 @@ impl From<TagKind> for tag::TagKind {
             TagKind::M => Self::M,
             TagKind::U => Self::U,
             TagKind::X => Self::X,
+            TagKind::Q => Self::Q,
             TagKind::RelayUrl => Self::Relay,
             TagKind::Nonce => Self::Nonce,
             TagKind::Delegation => Self::Delegation,
@@ -426,6 +438,10 @@ pub enum TagEnum {
         identifier: String,
         relay_url: Option<String>,
     },
+    Quote {
+        event_id: Arc<EventId>,
+        relay_url: Option<String>,
+    },
     RelayUrl {
         relay_url: String,
     },
@@ -630,6 +646,13 @@ impl From<tag::Tag> for TagEnum {
                 identifier,
                 relay_url: relay_url.map(|u| u.to_string()),
             },
+            tag::Tag::Quote {
+                event_id,
+                relay_url,
+            } => Self::Quote {
+                event_id: Arc::new(event_id.into()),
+                relay_url: relay_url.map(|u| u.to_string()),
+            },
             tag::Tag::ExternalIdentity(identity) => Self::ExternalIdentityTag {
                 identity: identity.into(),
             },
@@ -804,6 +827,13 @@ impl TryFrom<TagEnum> for tag::Tag {
                 identifier,
                 relay_url: relay_url.map(UncheckedUrl::from),
             }),
+            TagEnum::Quote {
+                event_id,
+                relay_url,
+            } => Ok(Self::Quote {
+                event_id: **event_id,
+                relay_url: relay_url.map(UncheckedUrl::from),
+            }),
             TagEnum::RelayUrl { relay_url } => Ok(Self::Relay(UncheckedUrl::from(relay_url))),
             TagEnum::POW { nonce, difficulty } => Ok(Self::POW {
                 nonce: nonce.parse()?,
@@ -25,6 +25,13 @@ impl From<nostr::UnsignedEvent> for UnsignedEvent {
     }
 }
 
+impl Deref for UnsignedEvent {
+    type Target = nostr::UnsignedEvent;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
 #[uniffi::export]
 impl UnsignedEvent {
     pub fn id(&self) -> Arc<EventId> {
@@ -17,6 +17,7 @@ pub mod nip47;
 pub mod nip48;
 pub mod nip53;
 pub mod nip57;
+pub mod nip59;
 pub mod nip65;
 pub mod nip90;
 pub mod nip94;
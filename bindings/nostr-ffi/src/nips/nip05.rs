@@ -35,5 +35,5 @@ pub fn get_nip05_profile(nip05: String, proxy: Option<String>) -> Result<Arc<Nip
         Some(proxy) => Some(proxy.parse()?),
         None => None,
     };
-    Ok(Arc::new(nip05::get_profile_blocking(nip05, proxy)?.into()))
+    Ok(Arc::new(nip05::profile_blocking(nip05, proxy)?.into()))
 }
@@ -57,3 +57,21 @@ pub fn nip44_decrypt(
         payload,
     )?)
 }
+
+/// Encrypt the same content to many recipients (v2)
+///
+/// Return the ciphertexts in the same order of `recipients`
+#[uniffi::export]
+pub fn nip44_encrypt_to_many(
+    secret_key: Arc<SecretKey>,
+    recipients: Vec<Arc<PublicKey>>,
+    content: String,
+) -> Result<Vec<String>> {
+    let recipients: Vec<_> = recipients.into_iter().map(|p| **p).collect();
+    Ok(
+        nip44::encrypt_to_many(secret_key.as_ref().deref(), &recipients, content)?
+            .into_iter()
+            .map(|(_, ciphertext)| ciphertext)
+            .collect(),
+    )
+}
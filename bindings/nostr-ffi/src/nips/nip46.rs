@@ -60,6 +60,50 @@ impl NostrConnectURI {
     }
 }
 
+#[derive(Object)]
+pub struct BunkerURI {
+    inner: nip46::BunkerURI,
+}
+
+impl From<nip46::BunkerURI> for BunkerURI {
+    fn from(inner: nip46::BunkerURI) -> Self {
+        Self { inner }
+    }
+}
+
+impl Deref for BunkerURI {
+    type Target = nip46::BunkerURI;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[uniffi::export]
+impl BunkerURI {
+    #[uniffi::constructor]
+    pub fn from_string(uri: String) -> Result<Arc<Self>> {
+        Ok(Arc::new(Self {
+            inner: nip46::BunkerURI::from_str(&uri)?,
+        }))
+    }
+
+    pub fn signer_public_key(&self) -> Arc<PublicKey> {
+        Arc::new(self.inner.signer_public_key.into())
+    }
+
+    pub fn relays(&self) -> Vec<String> {
+        self.inner.relays.iter().map(|u| u.to_string()).collect()
+    }
+
+    pub fn secret(&self) -> Option<String> {
+        self.inner.secret.clone()
+    }
+
+    pub fn to_uri(&self) -> String {
+        self.inner.to_string()
+    }
+}
+
 #[derive(Enum)]
 pub enum NostrConnectMessage {
     Request {
@@ -0,0 +1,62 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+use std::ops::Deref;
+use std::sync::Arc;
+
+use nostr::nips::nip59;
+use uniffi::Object;
+
+use crate::error::Result;
+use crate::{Event, Keys, PublicKey, UnsignedEvent};
+
+#[derive(Object)]
+pub struct UnwrappedGift {
+    inner: nip59::UnwrappedGift,
+}
+
+impl From<nip59::UnwrappedGift> for UnwrappedGift {
+    fn from(inner: nip59::UnwrappedGift) -> Self {
+        Self { inner }
+    }
+}
+
+#[uniffi::export]
+impl UnwrappedGift {
+    /// Extract the rumor from a gift wrap addressed to `receiver_keys`
+    #[uniffi::constructor]
+    pub fn from_gift_wrap(receiver_keys: Arc<Keys>, gift_wrap: Arc<Event>) -> Result<Arc<Self>> {
+        Ok(Arc::new(Self {
+            inner: nip59::extract_rumor(
+                receiver_keys.as_ref().deref(),
+                gift_wrap.as_ref().deref(),
+            )?,
+        }))
+    }
+
+    pub fn sender(&self) -> Arc<PublicKey> {
+        Arc::new(self.inner.sender.into())
+    }
+
+    pub fn rumor(&self) -> Arc<UnsignedEvent> {
+        Arc::new(self.inner.rumor.clone().into())
+    }
+}
+
+/// Seal the `rumor` and gift wrap it for the `receiver_pubkey`
+#[uniffi::export]
+pub fn gift_wrap(
+    sender_keys: Arc<Keys>,
+    receiver_pubkey: Arc<PublicKey>,
+    rumor: Arc<UnsignedEvent>,
+) -> Result<Arc<Event>> {
+    Ok(Arc::new(
+        nostr::EventBuilder::gift_wrap(
+            sender_keys.as_ref().deref(),
+            receiver_pubkey.as_ref().deref(),
+            rumor.as_ref().deref().clone(),
+        )?
+        .into(),
+    ))
+}
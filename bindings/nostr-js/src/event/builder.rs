@@ -119,9 +119,12 @@ impl JsEventBuilder {
     }
 
     #[wasm_bindgen]
-    pub fn repost(event_id: &JsEventId, public_key: &JsPublicKey) -> Self {
+    pub fn repost(event: &JsEvent, relay_url: Option<String>) -> Self {
         Self {
-            builder: EventBuilder::repost(event_id.into(), public_key.into()),
+            builder: EventBuilder::repost_embedded(
+                event.deref(),
+                relay_url.map(UncheckedUrl::from),
+            ),
         }
     }
 
@@ -10,7 +10,7 @@ use std::time::Duration;
 
 use nostr_ffi::{
     ClientMessage, Event, EventBuilder, EventId, FileMetadata, Filter, Metadata, PublicKey,
-    RelayMessage,
+    RelayMessage, UnwrappedGift,
 };
 use nostr_sdk::client::blocking::Client as ClientSdk;
 use nostr_sdk::relay::RelayPoolNotification as RelayPoolNotificationSdk;
@@ -25,7 +25,7 @@ pub use self::builder::ClientBuilder;
 pub use self::options::Options;
 pub use self::signer::ClientSigner;
 use crate::error::Result;
-use crate::{NostrDatabase, Relay};
+use crate::{NostrDatabase, NotificationHub, Relay, SeenTracker};
 
 #[derive(Object)]
 pub struct Client {
@@ -74,6 +74,14 @@ impl Client {
         self.inner.start();
     }
 
+    pub fn notification_hub(&self) -> Arc<NotificationHub> {
+        Arc::new(self.inner.notification_hub().into())
+    }
+
+    pub fn seen_tracker(&self) -> Arc<SeenTracker> {
+        Arc::new(self.inner.seen_tracker().into())
+    }
+
     pub fn stop(&self) -> Result<()> {
         Ok(self.inner.stop()?)
     }
@@ -119,7 +127,7 @@ impl Client {
     }
 
     pub fn connect(&self) {
-        self.inner.connect()
+        self.inner.connect();
     }
 
     pub fn disconnect(&self) -> Result<()> {
@@ -240,6 +248,27 @@ impl Client {
         ))
     }
 
+    pub fn send_private_msg(
+        &self,
+        receiver: Arc<PublicKey>,
+        message: String,
+        reply_to: Option<Arc<EventId>>,
+    ) -> Result<Arc<EventId>> {
+        Ok(Arc::new(
+            self.inner
+                .send_private_msg(**receiver, message, reply_to.map(|r| **r))?
+                .into(),
+        ))
+    }
+
+    pub fn unwrap_gift_wrap(&self, gift_wrap: Arc<Event>) -> Result<Arc<UnwrappedGift>> {
+        Ok(Arc::new(
+            self.inner
+                .unwrap_gift_wrap(gift_wrap.as_ref().deref())?
+                .into(),
+        ))
+    }
+
     pub fn file_metadata(
         &self,
         description: String,
@@ -264,12 +293,12 @@ impl Client {
             tracing::debug!("Client Thread Started");
             Ok(self.inner.handle_notifications(|notification| {
                 match notification {
-                    RelayPoolNotificationSdk::Message { relay_url, message } => {
-                        handler.handle_msg(relay_url.to_string(), message.into())
-                    }
-                    RelayPoolNotificationSdk::Event { relay_url, event } => {
-                        handler.handle(relay_url.to_string(), Arc::new(event.into()))
-                    }
+                    RelayPoolNotificationSdk::Message {
+                        relay_url, message, ..
+                    } => handler.handle_msg(relay_url.to_string(), message.into()),
+                    RelayPoolNotificationSdk::Event {
+                        relay_url, event, ..
+                    } => handler.handle(relay_url.to_string(), Arc::new(event.into())),
                     _ => (),
                 }
 
@@ -9,6 +9,9 @@ use std::time::Duration;
 use nostr_ffi::helper::unwrap_or_clone_arc;
 use uniffi::Object;
 
+use super::ClientSigner;
+use crate::{NotificationHub, SeenTracker};
+
 #[derive(Clone, Object)]
 pub struct Options {
     inner: nostr_sdk::Options,
@@ -89,4 +92,46 @@ impl Options {
         builder.inner = builder.inner.nip46_timeout(nip46_timeout);
         Arc::new(builder)
     }
+
+    /// Set signer used for NIP42 authentication
+    pub fn auth_signer(self: Arc<Self>, signer: Arc<ClientSigner>) -> Arc<Self> {
+        let mut builder = unwrap_or_clone_arc(self);
+        builder.inner = builder.inner.auth_signer(signer.as_ref().deref().clone());
+        Arc::new(builder)
+    }
+
+    /// Share the seen events tracker with other pools
+    pub fn seen_tracker(self: Arc<Self>, tracker: Arc<SeenTracker>) -> Arc<Self> {
+        let mut builder = unwrap_or_clone_arc(self);
+        builder.inner.pool = builder
+            .inner
+            .pool
+            .clone()
+            .seen_tracker(tracker.as_ref().deref().clone());
+        Arc::new(builder)
+    }
+
+    /// Send the notifications to a shared hub
+    pub fn notification_hub(self: Arc<Self>, hub: Arc<NotificationHub>) -> Arc<Self> {
+        let mut builder = unwrap_or_clone_arc(self);
+        builder.inner.pool = builder
+            .inner
+            .pool
+            .clone()
+            .notification_hub(hub.as_ref().deref().clone());
+        Arc::new(builder)
+    }
+
+    /// Automatically authenticate to relays that send an `AUTH` challenge
+    pub fn automatic_authentication(self: Arc<Self>, enable: bool) -> Arc<Self> {
+        let mut builder = unwrap_or_clone_arc(self);
+        builder.inner = builder.inner.automatic_authentication(enable);
+        Arc::new(builder)
+    }
+
+    pub fn unwrap_gift_wraps(self: Arc<Self>, enable: bool) -> Arc<Self> {
+        let mut builder = unwrap_or_clone_arc(self);
+        builder.inner = builder.inner.unwrap_gift_wraps(enable);
+        Arc::new(builder)
+    }
 }
@@ -5,7 +5,7 @@
 use std::ops::Deref;
 use std::sync::Arc;
 
-use nostr_ffi::{Keys, PublicKey};
+use nostr_ffi::{BunkerURI, Keys, PublicKey};
 use nostr_sdk::{block_on, client, Url};
 use uniffi::Object;
 
@@ -48,11 +48,27 @@ impl Nip46Signer {
         })
     }
 
+    /// New NIP46 remote signer from bunker URI
+    #[uniffi::constructor]
+    pub fn from_bunker_uri(uri: Arc<BunkerURI>, app_keys: Arc<Keys>) -> Result<Self> {
+        Ok(Self {
+            inner: client::Nip46Signer::from_bunker_uri(
+                uri.as_ref().deref().clone(),
+                app_keys.as_ref().deref().clone(),
+            )?,
+        })
+    }
+
     /// Get signer relay [`Url`]
     pub fn relay_url(&self) -> String {
         self.inner.relay_url().to_string()
     }
 
+    /// Get signer relays
+    pub fn relays(&self) -> Vec<String> {
+        self.inner.relays().iter().map(|u| u.to_string()).collect()
+    }
+
     /// Get signer [`XOnlyPublicKey`]
     pub fn signer_public_key(&self) -> Option<Arc<PublicKey>> {
         block_on(async move {
@@ -22,6 +22,8 @@ pub use crate::client::{Client, ClientBuilder, HandleNotification, Options};
 pub use crate::database::NostrDatabase;
 pub use crate::error::NostrSdkError;
 pub use crate::logger::{init_logger, LogLevel};
-pub use crate::relay::{ActiveSubscription, Relay, RelayConnectionStats, RelayStatus};
+pub use crate::relay::{
+    ActiveSubscription, NotificationHub, Relay, RelayConnectionStats, RelayStatus, SeenTracker,
+};
 
 uniffi::setup_scaffolding!("nostr_sdk");
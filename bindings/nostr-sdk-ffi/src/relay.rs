@@ -6,7 +6,7 @@ use std::sync::Arc;
 use std::time::Duration;
 use std::{collections::HashMap, ops::Deref};
 
-use nostr_ffi::{ClientMessage, Event, Filter, RelayInformationDocument, Timestamp};
+use nostr_ffi::{ClientMessage, Event, EventId, Filter, RelayInformationDocument, Timestamp};
 use nostr_sdk::{block_on, relay, FilterOptions};
 use uniffi::{Enum, Object};
 
@@ -231,3 +231,93 @@ impl Relay {
             .req_events_of(filters, timeout, FilterOptions::ExitOnEOSE);
     }
 }
+
+/// Track the IDs of the events already seen
+///
+/// Share it between the client and the Rust components consuming the same relays.
+#[derive(Object)]
+pub struct SeenTracker {
+    inner: relay::SeenTracker,
+}
+
+impl Deref for SeenTracker {
+    type Target = relay::SeenTracker;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl From<relay::SeenTracker> for SeenTracker {
+    fn from(inner: relay::SeenTracker) -> Self {
+        Self { inner }
+    }
+}
+
+#[uniffi::export]
+impl SeenTracker {
+    #[uniffi::constructor]
+    pub fn new(max: u64) -> Self {
+        Self {
+            inner: relay::SeenTracker::new(max as usize),
+        }
+    }
+
+    pub fn contains(&self, id: Arc<EventId>) -> bool {
+        self.inner.contains(id.as_ref().deref())
+    }
+
+    /// Mark the ID as seen
+    ///
+    /// Return `false` if the ID was already seen.
+    pub fn insert(&self, id: Arc<EventId>) -> bool {
+        self.inner.insert(**id)
+    }
+
+    pub fn len(&self) -> u64 {
+        self.inner.len() as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn clear(&self) {
+        self.inner.clear();
+    }
+}
+
+/// Broadcast channel of the relay pool notifications
+///
+/// Share it between the client and the Rust components consuming the same relays.
+#[derive(Object)]
+pub struct NotificationHub {
+    inner: relay::NotificationHub,
+}
+
+impl Deref for NotificationHub {
+    type Target = relay::NotificationHub;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl From<relay::NotificationHub> for NotificationHub {
+    fn from(inner: relay::NotificationHub) -> Self {
+        Self { inner }
+    }
+}
+
+#[uniffi::export]
+impl NotificationHub {
+    #[uniffi::constructor]
+    pub fn new(capacity: u64) -> Self {
+        Self {
+            inner: relay::NotificationHub::new(capacity as usize),
+        }
+    }
+
+    /// Number of active listeners
+    pub fn listeners(&self) -> u64 {
+        self.inner.listeners() as u64
+    }
+}
@@ -529,14 +529,14 @@ impl JsClient {
             inner
             .handle_notifications(|notification| async {
                 match notification {
-                    RelayPoolNotification::Message { relay_url, message } => {
+                    RelayPoolNotification::Message { relay_url, message, .. } => {
                         let message: JsRelayMessage = message.into();
                         if callback.handle_msg(relay_url.to_string(), message).await.as_bool().unwrap_or_default() {
                             tracing::info!("Received `true` in `handlemsg`: exiting from `handleNotifications`");
                             return Ok(true);
                         }
                     }
-                    RelayPoolNotification::Event { relay_url, event } => {
+                    RelayPoolNotification::Event { relay_url, event, .. } => {
                         let event: JsEvent = event.into();
                         if callback.handle_event(relay_url.to_string(), event).await.as_bool().unwrap_or_default() {
                             tracing::info!("Received `true` in `handleEvent`: exiting from `handleNotifications`");
@@ -0,0 +1,145 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Import events from relay exports
+//!
+//! Supported formats (one entry per line):
+//! * plain JSONL events (i.e. `strfry export`, `ndb export`)
+//! * relay messages (`["EVENT", <subscription_id>, <event>]` or `["EVENT", <event>]`)
+//! * arrays of events (`[<event>, <event>, ...]`)
+
+use nostr::serde_json::{self, Value};
+use nostr::Event;
+
+/// Import options
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImportOptions {
+    /// Verify event ID and signature before saving (default: true)
+    pub verify: bool,
+    /// Report progress every `n` lines (default: 1000)
+    pub progress_interval: usize,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            verify: true,
+            progress_interval: 1000,
+        }
+    }
+}
+
+impl ImportOptions {
+    /// New default [`ImportOptions`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verify event ID and signature before saving (default: true)
+    ///
+    /// Disable only for trusted archives.
+    pub fn verify(self, verify: bool) -> Self {
+        Self { verify, ..self }
+    }
+
+    /// Report progress every `n` lines (default: 1000)
+    pub fn progress_interval(self, interval: usize) -> Self {
+        Self {
+            progress_interval: interval.max(1),
+            ..self
+        }
+    }
+}
+
+/// Import progress
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportProgress {
+    /// Processed lines
+    pub lines: usize,
+    /// Saved events
+    pub imported: usize,
+    /// Events already in the database
+    pub duplicates: usize,
+    /// Events refused by the database (i.e. replaced or deleted)
+    pub skipped: usize,
+    /// Malformed lines or events that failed verification
+    pub invalid: usize,
+}
+
+/// Extract the events contained in a line of an export
+///
+/// Return `None` if the line is malformed.
+pub(crate) fn parse_line(line: &str) -> Option<Vec<Event>> {
+    match serde_json::from_str(line).ok()? {
+        value @ Value::Object(_) => Some(vec![Event::from_value(value).ok()?]),
+        Value::Array(mut values) => {
+            if values.first().and_then(Value::as_str) == Some("EVENT") {
+                let value: Value = values.pop()?;
+                Some(vec![Event::from_value(value).ok()?])
+            } else {
+                values
+                    .into_iter()
+                    .map(|value| Event::from_value(value).ok())
+                    .collect()
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nostr::{EventBuilder, JsonUtil, Keys};
+
+    use super::*;
+    use crate::{MemoryDatabase, NostrDatabaseExt};
+
+    #[test]
+    fn test_parse_line() {
+        let keys = Keys::generate();
+        let event = EventBuilder::new_text_note("Hello", [])
+            .to_event(&keys)
+            .unwrap();
+        let json = event.as_json();
+
+        assert_eq!(parse_line(&json), Some(vec![event.clone()]));
+        assert_eq!(
+            parse_line(&format!(r#"["EVENT","sub",{json}]"#)),
+            Some(vec![event.clone()])
+        );
+        assert_eq!(
+            parse_line(&format!(r#"["EVENT",{json}]"#)),
+            Some(vec![event.clone()])
+        );
+        assert_eq!(
+            parse_line(&format!("[{json},{json}]")),
+            Some(vec![event.clone(), event])
+        );
+        assert_eq!(parse_line("not json"), None);
+        assert_eq!(parse_line(r#"["NOTICE","hello"]"#), None);
+    }
+
+    #[tokio::test]
+    async fn test_import_zero_progress_interval() {
+        let keys = Keys::generate();
+        let event = EventBuilder::new_text_note("Hello", [])
+            .to_event(&keys)
+            .unwrap();
+        let export: String = format!("{}\nnot json\n", event.as_json());
+
+        let opts = ImportOptions {
+            verify: true,
+            progress_interval: 0,
+        };
+        let database = MemoryDatabase::default();
+        let mut calls: usize = 0;
+        let status = database
+            .import_events(export.as_bytes(), opts, |_| calls += 1)
+            .await
+            .unwrap();
+        assert_eq!(status.lines, 2);
+        assert_eq!(status.invalid, 1);
+        assert_eq!(calls, 3);
+    }
+}
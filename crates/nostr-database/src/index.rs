@@ -10,6 +10,7 @@ use std::sync::Arc;
 
 use nostr::event::id;
 use nostr::nips::nip01::Coordinate;
+use nostr::nips::nip13;
 use nostr::secp256k1::XOnlyPublicKey;
 use nostr::{Alphabet, Event, EventId, Filter, GenericTagValue, Kind, Timestamp};
 use thiserror::Error;
@@ -47,6 +48,8 @@ struct EventIndex {
     kind: Kind,
     /// Tag indexes
     tags: ArcTagIndexes,
+    /// Committed POW difficulty
+    pow: u8,
 }
 
 impl PartialOrd for EventIndex {
@@ -73,6 +76,7 @@ impl TryFrom<RawEvent> for EventIndex {
             event_id: Arc::new(EventId::from_slice(&raw.id)?),
             pubkey: PublicKeyPrefix::from(raw.pubkey),
             kind: raw.kind,
+            pow: nip13::get_committed_difficulty(raw.id, pow_target(&raw.tags)),
             tags: Arc::new(TagIndexes::from(raw.tags.into_iter())),
         })
     }
@@ -86,10 +90,19 @@ impl From<&Event> for EventIndex {
             pubkey: PublicKeyPrefix::from(e.pubkey),
             kind: e.kind,
             tags: Arc::new(TagIndexes::from(e.tags.iter().map(|t| t.as_vec()))),
+            pow: e.pow_difficulty(),
         }
     }
 }
 
+/// Get the POW target difficulty from the `nonce` tag of a raw event
+fn pow_target(tags: &[Vec<String>]) -> Option<u8> {
+    tags.iter().find_map(|tag| match tag.as_slice() {
+        [kind, _, target, ..] if kind == "nonce" => target.parse().ok(),
+        _ => None,
+    })
+}
+
 /// Public Key prefix
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct PublicKeyPrefix([u8; PUBLIC_KEY_PREFIX_SIZE]);
@@ -123,6 +136,7 @@ struct FilterIndex {
     since: Option<Timestamp>,
     until: Option<Timestamp>,
     generic_tags: HashMap<Alphabet, HashSet<GenericTagValue>>,
+    pow: Option<u8>,
 }
 
 impl FilterIndex {
@@ -189,6 +203,7 @@ impl FilterIndex {
             && self.kind_match(&event.kind)
             && self.authors_match(event)
             && self.tag_match(event)
+            && self.pow.map_or(true, |difficulty| event.pow >= difficulty)
     }
 }
 
@@ -205,6 +220,7 @@ impl From<Filter> for FilterIndex {
             since: value.since,
             until: value.until,
             generic_tags: value.generic_tags,
+            pow: value.pow,
         }
     }
 }
@@ -259,6 +275,14 @@ impl<'a> EventOrRawEvent<'a> {
         }
     }
 
+    fn pow(&self) -> u8 {
+        match self {
+            Self::Event(e) => e.pow_difficulty(),
+            Self::EventOwned(e) => e.pow_difficulty(),
+            Self::Raw(r) => nip13::get_committed_difficulty(r.id, pow_target(&r.tags)),
+        }
+    }
+
     fn tags(self) -> TagIndexes {
         match self {
             Self::Event(e) => TagIndexes::from(e.tags.iter().map(|t| t.as_vec())),
@@ -531,6 +555,7 @@ impl DatabaseIndexes {
                 event_id: event_id.clone(),
                 pubkey: pubkey_prefix,
                 kind,
+                pow: event.pow(),
                 tags: Arc::new(event.tags()),
             });
 
@@ -598,6 +623,7 @@ impl DatabaseIndexes {
             kinds,
             since,
             until,
+            pow,
             ..
         } = filter.into();
 
@@ -626,6 +652,12 @@ impl DatabaseIndexes {
             }
         }
 
+        if let Some(pow) = pow {
+            if ev.pow < pow {
+                return None;
+            }
+        }
+
         Some(ev)
     }
 
@@ -645,6 +677,7 @@ impl DatabaseIndexes {
             since,
             until,
             generic_tags,
+            pow,
             ..
         } = filter.into();
 
@@ -683,6 +716,12 @@ impl DatabaseIndexes {
             }
         }
 
+        if let Some(pow) = pow {
+            if ev.pow < pow {
+                return None;
+            }
+        }
+
         Some(ev)
     }
 
@@ -847,7 +886,7 @@ impl DatabaseIndexes {
 #[cfg(test)]
 mod tests {
     use nostr::secp256k1::SecretKey;
-    use nostr::{FromBech32, JsonUtil, Keys};
+    use nostr::{EventBuilder, FromBech32, JsonUtil, Keys};
 
     use super::*;
 
@@ -1018,4 +1057,32 @@ mod tests {
             ]
         );
     }
+
+    #[tokio::test]
+    async fn test_database_indexes_pow() {
+        let keys = Keys::new(SecretKey::from_bech32(SECRET_KEY_A).unwrap());
+        let indexes = DatabaseIndexes::new();
+
+        let note = EventBuilder::new_text_note("No POW", [])
+            .to_event(&keys)
+            .unwrap();
+        let pow_note = EventBuilder::new_text_note("POW", [])
+            .to_pow_event(&keys, 8)
+            .unwrap();
+        indexes.index_event(&note).await;
+
+        // Raw events
+        let mut events: BTreeSet<RawEvent> = BTreeSet::new();
+        events.insert(pow_note.clone().into());
+        indexes.bulk_index(events).await;
+
+        assert_eq!(indexes.count([Filter::new()]).await, 2);
+        assert_eq!(
+            indexes
+                .query([Filter::new().kind(Kind::TextNote).pow(8)], Order::Desc)
+                .await,
+            vec![pow_note.id]
+        );
+        assert_eq!(indexes.count([Filter::new().pow(9)]).await, 0);
+    }
 }
@@ -8,7 +8,8 @@
 #![warn(rustdoc::bare_urls)]
 
 use core::fmt;
-use std::collections::{BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::io::BufRead;
 use std::sync::Arc;
 
 pub use async_trait::async_trait;
@@ -20,21 +21,25 @@ use nostr::{Event, EventId, Filter, JsonUtil, Kind, Metadata, Timestamp, Url};
 mod error;
 #[cfg(feature = "flatbuf")]
 pub mod flatbuffers;
+pub mod import;
 pub mod index;
 pub mod memory;
 mod options;
 pub mod profile;
 mod raw;
+pub mod stats;
 mod tag_indexes;
 
 pub use self::error::DatabaseError;
 #[cfg(feature = "flatbuf")]
 pub use self::flatbuffers::{FlatBufferBuilder, FlatBufferDecode, FlatBufferEncode};
+pub use self::import::{ImportOptions, ImportProgress};
 pub use self::index::{DatabaseIndexes, EventIndexResult};
 pub use self::memory::MemoryDatabase;
-pub use self::options::DatabaseOptions;
+pub use self::options::{DatabaseOptions, Redaction};
 pub use self::profile::Profile;
 pub use self::raw::RawEvent;
+pub use self::stats::TimeRange;
 
 /// Backend
 pub enum Backend {
@@ -148,6 +153,22 @@ pub trait NostrDatabase: AsyncTraitDeps {
         event_id: EventId,
     ) -> Result<Option<HashSet<Url>>, Self::Err>;
 
+    /// Mark (or unmark) the [`EventId`] as not yet published to any relay
+    ///
+    /// Used by the outbox of the client to resume publishing after a restart.
+    /// Not supported by default.
+    async fn set_event_pending(&self, event_id: EventId, pending: bool) -> Result<(), Self::Err> {
+        let _ = (event_id, pending);
+        Err(DatabaseError::NotSupported.into())
+    }
+
+    /// Get the [`EventId`]s marked as not yet published to any relay
+    ///
+    /// Not supported by default.
+    async fn pending_event_ids(&self) -> Result<BTreeSet<EventId>, Self::Err> {
+        Err(DatabaseError::NotSupported.into())
+    }
+
     /// Get [`Event`] by [`EventId`]
     async fn event_by_id(&self, event_id: EventId) -> Result<Event, Self::Err>;
 
@@ -250,6 +271,97 @@ pub trait NostrDatabaseExt: NostrDatabase {
             None => Ok(BTreeSet::new()),
         }
     }
+
+    /// Import events from a relay export
+    ///
+    /// Duplicated events are skipped, malformed lines and events that fail verification are counted as invalid.
+    /// `progress` is called every [`ImportOptions::progress_interval`] lines and when done.
+    ///
+    /// See [`import`] module for the supported formats.
+    #[tracing::instrument(skip_all, level = "trace")]
+    async fn import_events<R, F>(
+        &self,
+        reader: R,
+        opts: ImportOptions,
+        mut progress: F,
+    ) -> Result<ImportProgress, Self::Err>
+    where
+        R: BufRead + SendOutsideWasm,
+        F: FnMut(ImportProgress) + SendOutsideWasm,
+    {
+        let mut status = ImportProgress::default();
+
+        for line in reader.lines() {
+            let line: String = line.map_err(DatabaseError::backend)?;
+            let line: &str = line.trim();
+            status.lines += 1;
+
+            if !line.is_empty() {
+                match import::parse_line(line) {
+                    Some(events) => {
+                        for event in events.into_iter() {
+                            if opts.verify && event.verify().is_err() {
+                                status.invalid += 1;
+                            } else if self.has_event_already_been_saved(&event.id).await? {
+                                status.duplicates += 1;
+                            } else if self.save_event(&event).await? {
+                                status.imported += 1;
+                            } else {
+                                status.skipped += 1;
+                            }
+                        }
+                    }
+                    None => status.invalid += 1,
+                }
+            }
+
+            if status.lines % opts.progress_interval.max(1) == 0 {
+                progress(status);
+            }
+        }
+
+        progress(status);
+
+        Ok(status)
+    }
+
+    /// Count events per [`Kind`] created in the time range
+    #[tracing::instrument(skip_all, level = "trace")]
+    async fn count_by_kind(&self, range: TimeRange) -> Result<BTreeMap<Kind, usize>, Self::Err> {
+        let events: Vec<Event> = self.query(vec![range.filter()], Order::Desc).await?;
+        Ok(stats::count_by_kind(&events))
+    }
+
+    /// Get the `n` most active authors in the time range, with their number of events
+    #[tracing::instrument(skip_all, level = "trace")]
+    async fn top_authors(
+        &self,
+        range: TimeRange,
+        n: usize,
+    ) -> Result<Vec<(XOnlyPublicKey, usize)>, Self::Err> {
+        let events: Vec<Event> = self.query(vec![range.filter()], Order::Desc).await?;
+        Ok(stats::top_authors(&events, n))
+    }
+
+    /// Count events matching `filter` per time bucket of `bucket` seconds
+    ///
+    /// Buckets are keyed by their start and aligned to the UNIX epoch: use [`stats::DAY`] for daily counts.
+    /// `since` and `until` of `filter` are overwritten by `range`.
+    #[tracing::instrument(skip_all, level = "trace")]
+    async fn activity_histogram(
+        &self,
+        filter: Filter,
+        range: TimeRange,
+        bucket: u64,
+    ) -> Result<BTreeMap<Timestamp, usize>, Self::Err> {
+        let filter = Filter {
+            since: range.since,
+            until: range.until,
+            ..filter
+        };
+        let events: Vec<Event> = self.query(vec![filter], Order::Desc).await?;
+        Ok(stats::histogram(&events, bucket))
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -331,6 +443,17 @@ impl<T: NostrDatabase> NostrDatabase for EraseNostrDatabaseError<T> {
             .map_err(Into::into)
     }
 
+    async fn set_event_pending(&self, event_id: EventId, pending: bool) -> Result<(), Self::Err> {
+        self.0
+            .set_event_pending(event_id, pending)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn pending_event_ids(&self) -> Result<BTreeSet<EventId>, Self::Err> {
+        self.0.pending_event_ids().await.map_err(Into::into)
+    }
+
     async fn event_by_id(&self, event_id: EventId) -> Result<Event, Self::Err> {
         self.0.event_by_id(event_id).await.map_err(Into::into)
     }
@@ -4,6 +4,7 @@
 
 //! Memory (RAM) Storage backend for Nostr apps
 
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
@@ -30,7 +31,10 @@ pub struct MemoryDatabase {
 
 impl Default for MemoryDatabase {
     fn default() -> Self {
-        Self::new(DatabaseOptions { events: false })
+        Self::new(DatabaseOptions {
+            events: false,
+            ..Default::default()
+        })
     }
 }
 
@@ -74,11 +78,17 @@ impl NostrDatabase for MemoryDatabase {
     }
 
     fn opts(&self) -> DatabaseOptions {
-        self.opts
+        self.opts.clone()
     }
 
     async fn save_event(&self, event: &Event) -> Result<bool, Self::Err> {
         if self.opts.events {
+            let event: Cow<Event> = match self.opts.redact_event(event) {
+                Some(event) => event,
+                None => return Ok(false),
+            };
+            let event: &Event = event.as_ref();
+
             let EventIndexResult {
                 to_store,
                 to_discard,
@@ -4,16 +4,37 @@
 
 //! Nostr Database options
 
-/// Database options
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use nostr::{Event, Kind};
+
+/// How to persist events of a sensitive [`Kind`]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Redaction {
+    /// Persist the event without `content`
+    ///
+    /// The stored event can't be verified anymore.
+    Content,
+    /// Never persist the event
+    Event,
+}
+
+/// Database options
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DatabaseOptions {
     /// Store events (?)
     pub events: bool,
+    /// Kinds to redact before saving events
+    pub redactions: BTreeMap<Kind, Redaction>,
 }
 
 impl Default for DatabaseOptions {
     fn default() -> Self {
-        Self { events: true }
+        Self {
+            events: true,
+            redactions: BTreeMap::new(),
+        }
     }
 }
 
@@ -22,4 +43,62 @@ impl DatabaseOptions {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Redact events of `kind` (i.e. [`Kind::EncryptedDirectMessage`] or [`Kind::NostrConnect`])
+    pub fn redact(mut self, kind: Kind, redaction: Redaction) -> Self {
+        self.redactions.insert(kind, redaction);
+        self
+    }
+
+    /// Get the [`Redaction`] of `kind`
+    pub fn redaction(&self, kind: &Kind) -> Option<Redaction> {
+        self.redactions.get(kind).copied()
+    }
+
+    /// Apply the [`Redaction`] of the event kind
+    ///
+    /// Return `None` if the event must not be persisted.
+    pub fn redact_event<'a>(&self, event: &'a Event) -> Option<Cow<'a, Event>> {
+        match self.redaction(&event.kind) {
+            Some(Redaction::Event) => None,
+            Some(Redaction::Content) => {
+                let mut event: Event = event.clone();
+                event.content = String::new();
+                Some(Cow::Owned(event))
+            }
+            None => Some(Cow::Borrowed(event)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nostr::{EventBuilder, Keys};
+
+    use super::*;
+
+    #[test]
+    fn test_redact_event() {
+        let keys = Keys::generate();
+        let opts = DatabaseOptions::new()
+            .redact(Kind::EncryptedDirectMessage, Redaction::Content)
+            .redact(Kind::NostrConnect, Redaction::Event);
+
+        let note = EventBuilder::new_text_note("hello", [])
+            .to_event(&keys)
+            .unwrap();
+        assert!(matches!(opts.redact_event(&note), Some(Cow::Borrowed(_))));
+
+        let dm = EventBuilder::new(Kind::EncryptedDirectMessage, "secret", [])
+            .to_event(&keys)
+            .unwrap();
+        let redacted = opts.redact_event(&dm).unwrap();
+        assert_eq!(redacted.id, dm.id);
+        assert!(redacted.content.is_empty());
+
+        let nostr_connect = EventBuilder::new(Kind::NostrConnect, "secret", [])
+            .to_event(&keys)
+            .unwrap();
+        assert!(opts.redact_event(&nostr_connect).is_none());
+    }
 }
@@ -0,0 +1,127 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Event statistics
+
+use std::collections::{BTreeMap, HashMap};
+
+use nostr::secp256k1::XOnlyPublicKey;
+use nostr::{Event, Filter, Kind, Timestamp};
+
+/// Seconds in a day
+pub const DAY: u64 = 86_400;
+
+/// Time range
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeRange {
+    /// Lower bound (inclusive)
+    pub since: Option<Timestamp>,
+    /// Upper bound (inclusive)
+    pub until: Option<Timestamp>,
+}
+
+impl TimeRange {
+    /// Unbounded time range
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set lower bound
+    pub fn since(self, since: Timestamp) -> Self {
+        Self {
+            since: Some(since),
+            ..self
+        }
+    }
+
+    /// Set upper bound
+    pub fn until(self, until: Timestamp) -> Self {
+        Self {
+            until: Some(until),
+            ..self
+        }
+    }
+
+    /// Last `n` days (until now)
+    pub fn last_days(n: u64) -> Self {
+        Self::new().since(Timestamp::now() - n * DAY)
+    }
+
+    pub(crate) fn filter(&self) -> Filter {
+        let mut filter = Filter::new();
+        if let Some(since) = self.since {
+            filter = filter.since(since);
+        }
+        if let Some(until) = self.until {
+            filter = filter.until(until);
+        }
+        filter
+    }
+}
+
+pub(crate) fn count_by_kind(events: &[Event]) -> BTreeMap<Kind, usize> {
+    let mut counts: BTreeMap<Kind, usize> = BTreeMap::new();
+    for event in events.iter() {
+        *counts.entry(event.kind).or_default() += 1;
+    }
+    counts
+}
+
+pub(crate) fn top_authors(events: &[Event], n: usize) -> Vec<(XOnlyPublicKey, usize)> {
+    let mut counts: HashMap<XOnlyPublicKey, usize> = HashMap::new();
+    for event in events.iter() {
+        *counts.entry(event.pubkey).or_default() += 1;
+    }
+    let mut authors: Vec<(XOnlyPublicKey, usize)> = counts.into_iter().collect();
+    authors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    authors.truncate(n);
+    authors
+}
+
+pub(crate) fn histogram(events: &[Event], bucket: u64) -> BTreeMap<Timestamp, usize> {
+    let bucket: u64 = bucket.max(1);
+    let mut counts: BTreeMap<Timestamp, usize> = BTreeMap::new();
+    for event in events.iter() {
+        let created_at: u64 = event.created_at.as_u64();
+        let start = Timestamp::from(created_at - created_at % bucket);
+        *counts.entry(start).or_default() += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use nostr::{EventBuilder, Keys};
+
+    use super::*;
+
+    fn event(keys: &Keys, kind: Kind, created_at: u64) -> Event {
+        EventBuilder::new(kind, "", [])
+            .custom_created_at(Timestamp::from(created_at))
+            .to_event(keys)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_stats() {
+        let alice = Keys::generate();
+        let bob = Keys::generate();
+        let events = vec![
+            event(&alice, Kind::TextNote, DAY),
+            event(&alice, Kind::TextNote, DAY + 10),
+            event(&alice, Kind::Reaction, 2 * DAY + 10),
+            event(&bob, Kind::TextNote, 2 * DAY + 20),
+        ];
+
+        let counts = count_by_kind(&events);
+        assert_eq!(counts.get(&Kind::TextNote), Some(&3));
+        assert_eq!(counts.get(&Kind::Reaction), Some(&1));
+
+        assert_eq!(top_authors(&events, 1), vec![(alice.public_key(), 3)]);
+
+        let daily = histogram(&events, DAY);
+        assert_eq!(daily.get(&Timestamp::from(DAY)), Some(&2));
+        assert_eq!(daily.get(&Timestamp::from(2 * DAY)), Some(&2));
+    }
+}
@@ -10,6 +10,7 @@
 #![allow(unknown_lints, clippy::arc_with_non_send_sync)]
 #![cfg_attr(not(target_arch = "wasm32"), allow(unused))]
 
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::future::IntoFuture;
@@ -60,6 +61,7 @@ pub struct OngoingMigration {
 pub struct WebDatabase {
     db: Arc<IdbDatabase>,
     indexes: DatabaseIndexes,
+    opts: DatabaseOptions,
     fbb: Arc<Mutex<FlatBufferBuilder<'static>>>,
 }
 
@@ -74,12 +76,21 @@ impl fmt::Debug for WebDatabase {
 impl WebDatabase {
     /// Open IndexedDB store
     pub async fn open<S>(name: S) -> Result<Self, IndexedDBError>
+    where
+        S: AsRef<str>,
+    {
+        Self::open_with_opts(name, DatabaseOptions::default()).await
+    }
+
+    /// Open IndexedDB store with [`DatabaseOptions`]
+    pub async fn open_with_opts<S>(name: S, opts: DatabaseOptions) -> Result<Self, IndexedDBError>
     where
         S: AsRef<str>,
     {
         let mut this = Self {
             db: Arc::new(IdbDatabase::open(name.as_ref())?.into_future().await?),
             indexes: DatabaseIndexes::new(),
+            opts,
             fbb: Arc::new(Mutex::new(FlatBufferBuilder::with_capacity(70_000))),
         };
 
@@ -251,11 +262,17 @@ impl_nostr_database!({
     }
 
     fn opts(&self) -> DatabaseOptions {
-        DatabaseOptions::default()
+        self.opts.clone()
     }
 
     #[tracing::instrument(skip_all, level = "trace")]
     async fn save_event(&self, event: &Event) -> Result<bool, IndexedDBError> {
+        let event: Cow<Event> = match self.opts.redact_event(event) {
+            Some(event) => event,
+            None => return Ok(false),
+        };
+        let event: &Event = event.as_ref();
+
         // Index event
         let EventIndexResult {
             to_store,
@@ -8,6 +8,7 @@
 #![warn(missing_docs)]
 #![warn(rustdoc::bare_urls)]
 
+use std::borrow::Cow;
 use std::collections::HashSet;
 use std::path::Path;
 use std::sync::Arc;
@@ -37,6 +38,7 @@ const EVENTS_SEEN_BY_RELAYS_CF: &str = "event-seen-by-relays";
 pub struct RocksDatabase {
     db: Arc<OptimisticTransactionDB>,
     indexes: DatabaseIndexes,
+    opts: DatabaseOptions,
     fbb: Arc<RwLock<FlatBufferBuilder<'static>>>,
 }
 
@@ -70,6 +72,14 @@ fn column_families() -> Vec<ColumnFamilyDescriptor> {
 impl RocksDatabase {
     /// Open RocksDB store
     pub async fn open<P>(path: P) -> Result<Self, DatabaseError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::open_with_opts(path, DatabaseOptions::default()).await
+    }
+
+    /// Open RocksDB store with [`DatabaseOptions`]
+    pub async fn open_with_opts<P>(path: P, opts: DatabaseOptions) -> Result<Self, DatabaseError>
     where
         P: AsRef<Path>,
     {
@@ -98,6 +108,7 @@ impl RocksDatabase {
         let this = Self {
             db: Arc::new(db),
             indexes: DatabaseIndexes::new(),
+            opts,
             fbb: Arc::new(RwLock::new(FlatBufferBuilder::with_capacity(70_000))),
         };
 
@@ -150,11 +161,17 @@ impl NostrDatabase for RocksDatabase {
     }
 
     fn opts(&self) -> DatabaseOptions {
-        DatabaseOptions::default()
+        self.opts.clone()
     }
 
     #[tracing::instrument(skip_all, level = "trace")]
     async fn save_event(&self, event: &Event) -> Result<bool, Self::Err> {
+        let event: Cow<Event> = match self.opts.redact_event(event) {
+            Some(event) => event,
+            None => return Ok(false),
+        };
+        let event: &Event = event.as_ref();
+
         // Index event
         let EventIndexResult {
             to_store,
@@ -0,0 +1,89 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Connection cache
+
+use std::collections::HashMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use tokio_rustls::rustls::{ClientConfig, OwnedTrustAnchor, RootCertStore};
+
+/// Resolved addresses and TLS sessions kept between connections
+///
+/// Reusing the same cache for the reconnections skips the DNS lookup
+/// and resumes the previous TLS session (the session tickets are stored in the TLS config).
+///
+/// Cloning the cache share the same addresses and sessions.
+#[derive(Clone)]
+pub struct ConnectionCache {
+    addrs: Arc<Mutex<HashMap<String, Vec<SocketAddr>>>>,
+    tls: Arc<ClientConfig>,
+}
+
+impl fmt::Debug for ConnectionCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let addrs = self.addrs.lock().unwrap_or_else(|e| e.into_inner());
+        f.debug_struct("ConnectionCache")
+            .field("addrs", &*addrs)
+            .finish()
+    }
+}
+
+impl Default for ConnectionCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConnectionCache {
+    /// New empty cache
+    pub fn new() -> Self {
+        let mut root_cert_store = RootCertStore::empty();
+        root_cert_store.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+            OwnedTrustAnchor::from_subject_spki_name_constraints(
+                ta.subject,
+                ta.spki,
+                ta.name_constraints,
+            )
+        }));
+        let config = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(root_cert_store)
+            .with_no_client_auth();
+        Self {
+            addrs: Arc::new(Mutex::new(HashMap::new())),
+            tls: Arc::new(config),
+        }
+    }
+
+    /// Get the cached addresses of `host:port`
+    pub fn addrs(&self, host: &str) -> Option<Vec<SocketAddr>> {
+        let addrs = self.addrs.lock().unwrap_or_else(|e| e.into_inner());
+        addrs.get(host).cloned()
+    }
+
+    pub(crate) fn set_addrs(&self, host: String, list: Vec<SocketAddr>) {
+        let mut addrs = self.addrs.lock().unwrap_or_else(|e| e.into_inner());
+        addrs.insert(host, list);
+    }
+
+    pub(crate) fn remove_addrs(&self, host: &str) {
+        let mut addrs = self.addrs.lock().unwrap_or_else(|e| e.into_inner());
+        addrs.remove(host);
+    }
+
+    pub(crate) fn tls_config(&self) -> Arc<ClientConfig> {
+        self.tls.clone()
+    }
+
+    /// Forget the resolved addresses
+    ///
+    /// The TLS sessions are kept: they are bound to the server name, not to the address.
+    pub fn clear(&self) {
+        let mut addrs = self.addrs.lock().unwrap_or_else(|e| e.into_inner());
+        addrs.clear();
+    }
+}
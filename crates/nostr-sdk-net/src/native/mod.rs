@@ -5,7 +5,6 @@
 //! Native Network
 
 use std::net::SocketAddr;
-use std::sync::Arc;
 use std::time::Duration;
 
 use futures_util::stream::{SplitSink, SplitStream};
@@ -13,19 +12,25 @@ use futures_util::StreamExt;
 use thiserror::Error;
 use tokio::net::TcpStream;
 use tokio_rustls::client::TlsStream;
-use tokio_rustls::rustls::{ClientConfig, OwnedTrustAnchor, RootCertStore, ServerName};
+use tokio_rustls::rustls::ServerName;
 use tokio_rustls::TlsConnector;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::handshake::client::Request;
+use tokio_tungstenite::tungstenite::http::header::{HeaderValue, InvalidHeaderValue, USER_AGENT};
+pub use tokio_tungstenite::tungstenite::http::HeaderMap;
 use tokio_tungstenite::tungstenite::Error as WsError;
 pub use tokio_tungstenite::tungstenite::Message;
-use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use tokio_tungstenite::{Connector, MaybeTlsStream, WebSocketStream};
 use url_fork::{ParseError, Url};
 
 type WebSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
 type Sink = SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>;
 type Stream = SplitStream<WebSocketStream<MaybeTlsStream<TcpStream>>>;
 
+mod cache;
 mod socks;
 
+pub use self::cache::ConnectionCache;
 use self::socks::TpcSocks5Stream;
 
 #[derive(Debug, Error)]
@@ -47,6 +52,18 @@ pub enum Error {
     /// Url parse error
     #[error("impossible to parse URL: {0}")]
     Url(#[from] ParseError),
+    /// Invalid header value
+    #[error("invalid header value: {0}")]
+    InvalidHeaderValue(#[from] InvalidHeaderValue),
+}
+
+/// WebSocket handshake options
+#[derive(Debug, Clone, Default)]
+pub struct HandshakeOptions {
+    /// Additional headers of the upgrade request
+    pub headers: HeaderMap,
+    /// `User-Agent` header (override the one in `headers`, if any)
+    pub user_agent: Option<String>,
 }
 
 pub async fn connect(
@@ -54,38 +71,115 @@ pub async fn connect(
     proxy: Option<SocketAddr>,
     timeout: Option<Duration>,
 ) -> Result<(Sink, Stream), Error> {
+    connect_with_handshake(url, proxy, timeout, &HandshakeOptions::default()).await
+}
+
+/// Connect using custom handshake headers
+pub async fn connect_with_handshake(
+    url: &Url,
+    proxy: Option<SocketAddr>,
+    timeout: Option<Duration>,
+    handshake: &HandshakeOptions,
+) -> Result<(Sink, Stream), Error> {
+    connect_with_cache(url, proxy, timeout, handshake, &ConnectionCache::new()).await
+}
+
+/// Connect using custom handshake headers and the [`ConnectionCache`] of the previous connections
+pub async fn connect_with_cache(
+    url: &Url,
+    proxy: Option<SocketAddr>,
+    timeout: Option<Duration>,
+    handshake: &HandshakeOptions,
+    cache: &ConnectionCache,
+) -> Result<(Sink, Stream), Error> {
+    let timeout = timeout.unwrap_or(Duration::from_secs(60));
     let stream = match proxy {
-        Some(proxy) => connect_proxy(url, proxy, timeout).await?,
-        None => connect_direct(url, timeout).await?,
+        Some(proxy) => connect_proxy(url, handshake, proxy, timeout, cache).await?,
+        None => connect_direct(url, handshake, timeout, cache).await?,
     };
     Ok(stream.split())
 }
 
-async fn connect_direct(url: &Url, timeout: Option<Duration>) -> Result<WebSocket, Error> {
-    let timeout = timeout.unwrap_or(Duration::from_secs(60));
+fn set_handshake_headers(
+    request: &mut Request,
+    handshake: &HandshakeOptions,
+) -> Result<(), InvalidHeaderValue> {
+    let headers: &mut HeaderMap = request.headers_mut();
+    for (name, value) in handshake.headers.iter() {
+        headers.insert(name, value.clone());
+    }
+    if let Some(user_agent) = &handshake.user_agent {
+        headers.insert(USER_AGENT, HeaderValue::from_str(user_agent)?);
+    }
+    Ok(())
+}
+
+fn host_port(url: &Url) -> Result<String, ParseError> {
+    match url.host_str() {
+        Some(host) => match url.port_or_known_default() {
+            Some(port) => Ok(format!("{host}:{port}")),
+            None => Err(ParseError::EmptyHost),
+        },
+        None => Err(ParseError::InvalidPort),
+    }
+}
+
+async fn connect_direct(
+    url: &Url,
+    handshake: &HandshakeOptions,
+    timeout: Duration,
+    cache: &ConnectionCache,
+) -> Result<WebSocket, Error> {
+    let addr: String = host_port(url)?;
+    let future = async {
+        // Skip the DNS lookup, if already resolved
+        if let Some(addrs) = cache.addrs(&addr) {
+            match connect_to_addrs(url, handshake, &addrs, cache).await {
+                Ok(stream) => return Ok(stream),
+                // The relay may have moved: resolve again
+                Err(_) => cache.remove_addrs(&addr),
+            }
+        }
+
+        let addrs: Vec<SocketAddr> = tokio::net::lookup_host(addr.as_str()).await?.collect();
+        let stream = connect_to_addrs(url, handshake, &addrs, cache).await?;
+        cache.set_addrs(addr.clone(), addrs);
+        Ok(stream)
+    };
+    tokio::time::timeout(timeout, future)
+        .await
+        .map_err(|_| Error::Timeout)?
+}
+
+async fn connect_to_addrs(
+    url: &Url,
+    handshake: &HandshakeOptions,
+    addrs: &[SocketAddr],
+    cache: &ConnectionCache,
+) -> Result<WebSocket, Error> {
+    let mut request: Request = url.as_str().into_client_request()?;
+    set_handshake_headers(&mut request, handshake)?;
+    let conn = TcpStream::connect(addrs).await?;
+    let connector = Connector::Rustls(cache.tls_config());
     let (stream, _) =
-        tokio::time::timeout(timeout, tokio_tungstenite::connect_async(url.to_string()))
-            .await
-            .map_err(|_| Error::Timeout)??;
+        tokio_tungstenite::client_async_tls_with_config(request, conn, None, Some(connector))
+            .await?;
     Ok(stream)
 }
 
 async fn connect_proxy(
     url: &Url,
+    handshake: &HandshakeOptions,
     proxy: SocketAddr,
-    timeout: Option<Duration>,
+    timeout: Duration,
+    cache: &ConnectionCache,
 ) -> Result<WebSocket, Error> {
-    let timeout = timeout.unwrap_or(Duration::from_secs(60));
-    let addr: String = match url.host_str() {
-        Some(host) => match url.port_or_known_default() {
-            Some(port) => format!("{host}:{port}"),
-            None => return Err(Error::Url(ParseError::EmptyHost)),
-        },
-        None => return Err(Error::Url(ParseError::InvalidPort)),
-    };
+    let mut request: Request = url.as_str().into_client_request()?;
+    set_handshake_headers(&mut request, handshake)?;
+    let addr: String = host_port(url)?;
 
     let conn = TpcSocks5Stream::connect(proxy, addr.clone()).await?;
-    let conn = match connect_with_tls(conn, url).await {
+    let conn = match connect_with_tls(conn, url, cache).await {
         Ok(stream) => MaybeTlsStream::Rustls(stream),
         Err(_) => {
             let conn = TpcSocks5Stream::connect(proxy, addr).await?;
@@ -93,29 +187,18 @@ async fn connect_proxy(
         }
     };
 
-    let (stream, _) = tokio::time::timeout(
-        timeout,
-        tokio_tungstenite::client_async(url.to_string(), conn),
-    )
-    .await
-    .map_err(|_| Error::Timeout)??;
+    let (stream, _) = tokio::time::timeout(timeout, tokio_tungstenite::client_async(request, conn))
+        .await
+        .map_err(|_| Error::Timeout)??;
     Ok(stream)
 }
 
-async fn connect_with_tls(stream: TcpStream, url: &Url) -> Result<TlsStream<TcpStream>, Error> {
-    let mut root_cert_store = RootCertStore::empty();
-    root_cert_store.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
-        OwnedTrustAnchor::from_subject_spki_name_constraints(
-            ta.subject,
-            ta.spki,
-            ta.name_constraints,
-        )
-    }));
-    let config = ClientConfig::builder()
-        .with_safe_defaults()
-        .with_root_certificates(root_cert_store)
-        .with_no_client_auth();
-    let connector = TlsConnector::from(Arc::new(config));
+async fn connect_with_tls(
+    stream: TcpStream,
+    url: &Url,
+    cache: &ConnectionCache,
+) -> Result<TlsStream<TcpStream>, Error> {
+    let connector = TlsConnector::from(cache.tls_config());
     let domain = url.domain().ok_or(Error::InvalidDNSName)?;
     let domain = ServerName::try_from(domain).map_err(|_| Error::InvalidDNSName)?;
     Ok(connector.connect(domain, stream).await?)
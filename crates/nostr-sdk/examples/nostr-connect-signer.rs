@@ -0,0 +1,39 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+use nostr_sdk::prelude::*;
+
+const SIGNER_SECRET_KEY: &str = "nsec1ufnus6pju578ste3v90xd5m2decpuzpql2295m3sknqcjzyys9ls0qlc85";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let secret_key = SecretKey::from_bech32(SIGNER_SECRET_KEY)?;
+    let keys = Keys::new(secret_key);
+
+    // Serve `connect` and `get_public_key` without asking, approve the other requests only for text notes
+    let policy = AutoApprovePolicy::new()
+        .method("connect")
+        .method("get_public_key");
+    let signer = NostrConnectRemoteSigner::new(keys)
+        .secret("nostr-sdk")
+        .policy(policy)
+        .approval(|app: XOnlyPublicKey, req: &nip46::Request| match req {
+            nip46::Request::SignEvent(unsigned) => {
+                println!("{app} requested to sign a kind {} event", unsigned.kind);
+                unsigned.kind == Kind::TextNote
+            }
+            _ => false,
+        });
+    signer.add_relay("wss://relay.damus.io").await?;
+
+    println!("\n###############################################\n");
+    println!("Bunker URI: {}", signer.bunker_uri().await);
+    println!("\n###############################################\n");
+
+    signer.run().await?;
+
+    Ok(())
+}
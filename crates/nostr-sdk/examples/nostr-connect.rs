@@ -2,6 +2,7 @@
 // Copyright (c) 2023-2024 Rust Nostr Developers
 // Distributed under the MIT software license
 
+use std::str::FromStr;
 use std::time::Duration;
 
 use nostr_sdk::prelude::*;
@@ -15,20 +16,30 @@ async fn main() -> Result<()> {
     let secret_key = SecretKey::from_bech32(APP_SECRET_KEY)?;
     let app_keys = Keys::new(secret_key);
     let relay_url = Url::parse("wss://relay.damus.io")?;
-    let signer = Nip46Signer::new(relay_url.clone(), app_keys, None);
 
-    let metadata = NostrConnectMetadata::new("Nostr SDK").url(Url::parse("https://example.com")?);
-    let nostr_connect_uri: NostrConnectURI = signer.nostr_connect_uri(metadata);
+    // Pass a `bunker://` URI as first argument to connect to a bunker
+    let signer = match std::env::args().nth(1) {
+        Some(uri) => Nip46Signer::from_bunker_uri(BunkerURI::from_str(&uri)?, app_keys)?,
+        None => {
+            let signer = Nip46Signer::new(relay_url.clone(), app_keys, None);
 
-    println!("\n###############################################\n");
-    println!("Nostr Connect URI: {nostr_connect_uri}");
-    println!("\n###############################################\n");
+            let metadata =
+                NostrConnectMetadata::new("Nostr SDK").url(Url::parse("https://example.com")?);
+            let nostr_connect_uri: NostrConnectURI = signer.nostr_connect_uri(metadata);
+
+            println!("\n###############################################\n");
+            println!("Nostr Connect URI: {nostr_connect_uri}");
+            println!("\n###############################################\n");
+
+            signer
+        }
+    };
 
     let client = Client::new(signer);
     client.add_relay(relay_url).await?;
     client.connect().await;
 
-    // Request signer public key since we not added in Client::with_remote_signer
+    // Connect to the signer (optional: done automatically before the first request)
     client
         .req_signer_public_key(Some(Duration::from_secs(180)))
         .await?;
@@ -0,0 +1,87 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Accounts
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use nostr::key::XOnlyPublicKey;
+use tokio::sync::RwLock;
+
+use super::signer::ClientSigner;
+
+/// Label of the relays of an account (see [`Client::add_account_relay`](super::Client::add_account_relay))
+pub fn account_label(public_key: &XOnlyPublicKey) -> String {
+    format!("account:{public_key}")
+}
+
+/// Signers of the accounts managed by the [`Client`](super::Client)
+///
+/// The active account is the client signer (see [`Client::switch_account`](super::Client::switch_account)).
+#[derive(Debug, Clone, Default)]
+pub struct Accounts {
+    signers: Arc<RwLock<BTreeMap<XOnlyPublicKey, ClientSigner>>>,
+}
+
+impl Accounts {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the public keys of the accounts
+    pub async fn public_keys(&self) -> Vec<XOnlyPublicKey> {
+        self.signers.read().await.keys().copied().collect()
+    }
+
+    /// Check if the account exists
+    pub async fn contains(&self, public_key: &XOnlyPublicKey) -> bool {
+        self.signers.read().await.contains_key(public_key)
+    }
+
+    pub(crate) async fn get(&self, public_key: &XOnlyPublicKey) -> Option<ClientSigner> {
+        self.signers.read().await.get(public_key).cloned()
+    }
+
+    pub(crate) async fn insert(&self, public_key: XOnlyPublicKey, signer: ClientSigner) {
+        let mut signers = self.signers.write().await;
+        signers.insert(public_key, signer);
+    }
+
+    pub(crate) async fn remove(&self, public_key: &XOnlyPublicKey) -> Option<ClientSigner> {
+        let mut signers = self.signers.write().await;
+        signers.remove(public_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nostr::Keys;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_accounts() {
+        let accounts = Accounts::new();
+        let keys = Keys::generate();
+        let public_key = keys.public_key();
+        assert!(!accounts.contains(&public_key).await);
+        assert!(accounts.get(&public_key).await.is_none());
+
+        accounts.insert(public_key, ClientSigner::from(keys)).await;
+        assert!(accounts.contains(&public_key).await);
+        assert_eq!(accounts.public_keys().await, vec![public_key]);
+        assert!(accounts.get(&public_key).await.is_some());
+
+        assert!(accounts.remove(&public_key).await.is_some());
+        assert!(accounts.remove(&public_key).await.is_none());
+        assert!(accounts.public_keys().await.is_empty());
+    }
+
+    #[test]
+    fn test_account_label() {
+        let public_key = Keys::generate().public_key();
+        assert_eq!(account_label(&public_key), format!("account:{public_key}"));
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Application handlers (NIP89)
+
+use std::collections::{HashMap, HashSet};
+
+use nostr::key::XOnlyPublicKey;
+use nostr::nips::nip89::{HandlerInformation, HandlerRecommendation};
+use nostr::{Event, Kind};
+
+/// Application able to handle an event kind, recommended by the follows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppHandler {
+    /// Author of the handler information
+    pub public_key: XOnlyPublicKey,
+    /// Handler information
+    pub info: HandlerInformation,
+    /// Follows recommending the handler
+    pub recommended_by: Vec<XOnlyPublicKey>,
+}
+
+/// Rank the handlers of `kind` by number of recommendations
+///
+/// Only the latest recommendation of each author is considered.
+/// The recommended handlers not found in `handlers`, or not supporting `kind`, are skipped.
+pub(crate) fn rank(kind: Kind, recommendations: &[Event], handlers: &[Event]) -> Vec<AppHandler> {
+    let mut latest: HashMap<XOnlyPublicKey, &Event> = HashMap::new();
+    for event in recommendations.iter() {
+        match latest.get(&event.pubkey) {
+            Some(current) if current.created_at >= event.created_at => (),
+            _ => {
+                latest.insert(event.pubkey, event);
+            }
+        }
+    }
+
+    let mut recommended: HashMap<(XOnlyPublicKey, String), HashSet<XOnlyPublicKey>> =
+        HashMap::new();
+    for (author, event) in latest.into_iter() {
+        let recommendation = match HandlerRecommendation::from_event(event) {
+            Ok(recommendation) if recommendation.kind == kind => recommendation,
+            _ => continue,
+        };
+        for handler in recommendation.handlers.into_iter() {
+            if handler.coordinate.kind == Kind::HandlerInformation {
+                recommended
+                    .entry((handler.coordinate.pubkey, handler.coordinate.identifier))
+                    .or_default()
+                    .insert(author);
+            }
+        }
+    }
+
+    let mut apps: Vec<AppHandler> = recommended
+        .into_iter()
+        .filter_map(|((public_key, identifier), recommended_by)| {
+            let event: &Event = handlers
+                .iter()
+                .filter(|event| {
+                    event.kind == Kind::HandlerInformation
+                        && event.pubkey == public_key
+                        && event.identifier() == Some(identifier.as_str())
+                })
+                .max_by_key(|event| event.created_at)?;
+            let info = HandlerInformation::from_event(event).ok()?;
+            if !info.handles(kind) {
+                return None;
+            }
+            let mut recommended_by: Vec<XOnlyPublicKey> = recommended_by.into_iter().collect();
+            recommended_by.sort();
+            Some(AppHandler {
+                public_key,
+                info,
+                recommended_by,
+            })
+        })
+        .collect();
+
+    apps.sort_by(|a, b| {
+        b.recommended_by
+            .len()
+            .cmp(&a.recommended_by.len())
+            .then_with(|| a.info.identifier.cmp(&b.info.identifier))
+            .then_with(|| a.public_key.cmp(&b.public_key))
+    });
+    apps
+}
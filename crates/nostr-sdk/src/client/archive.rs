@@ -0,0 +1,145 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Archive
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use nostr::key::XOnlyPublicKey;
+use nostr::{EventId, Filter, Timestamp, Url};
+use nostr_database::{DynNostrDatabase, Order};
+
+use crate::relay::{Error, FilterOptions, Relay};
+
+const PAGE_SIZE: usize = 500;
+
+/// Archive coverage report
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchiveReport {
+    /// Number of unique events found
+    pub events: usize,
+    /// Oldest event found
+    pub oldest: Option<Timestamp>,
+    /// Coverage per relay
+    pub relays: HashMap<Url, RelayArchiveCoverage>,
+}
+
+/// Archive coverage of a single relay
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RelayArchiveCoverage {
+    /// Number of events found
+    pub events: usize,
+    /// Oldest event found
+    pub oldest: Option<Timestamp>,
+    /// `true` if the relay history has been crawled until the end
+    pub complete: bool,
+    /// Error that interrupted the crawl
+    pub error: Option<String>,
+}
+
+/// Get the events matching `filter` from `relay`
+///
+/// Events already saved into the database are not re-emitted by the pool, so the result
+/// is completed with the stored events seen by the same relay.
+async fn fetch(
+    relay: &Relay,
+    database: &Arc<DynNostrDatabase>,
+    filter: Filter,
+    timeout: Duration,
+) -> Result<HashMap<EventId, Timestamp>, Error> {
+    let url: Url = relay.url();
+    let mut events: HashMap<EventId, Timestamp> = relay
+        .get_events_of(vec![filter.clone()], timeout, FilterOptions::ExitOnEOSE)
+        .await?
+        .into_iter()
+        .map(|e| (e.id, e.created_at))
+        .collect();
+
+    match database.query(vec![filter], Order::Desc).await {
+        Ok(stored) => {
+            for event in stored.into_iter() {
+                if let Ok(Some(relays)) = database.event_seen_on_relays(event.id).await {
+                    if relays.contains(&url) {
+                        events.insert(event.id, event.created_at);
+                    }
+                }
+            }
+        }
+        Err(e) => tracing::warn!("Impossible to query stored events: {e}"),
+    }
+
+    Ok(events)
+}
+
+/// Page backwards the history of `public_key` on `relay`
+///
+/// When a page is full, all the events sharing the timestamp of its oldest event are fetched
+/// (without limit) before continuing from the second before, so that none is skipped.
+pub(crate) async fn crawl_relay(
+    relay: Relay,
+    database: Arc<DynNostrDatabase>,
+    public_key: XOnlyPublicKey,
+    timeout: Duration,
+) -> (RelayArchiveCoverage, HashSet<EventId>) {
+    let url: Url = relay.url();
+    let mut coverage = RelayArchiveCoverage::default();
+    let mut ids: HashSet<EventId> = HashSet::new();
+    let filter: Filter = Filter::new().author(public_key);
+    let mut until: Timestamp = Timestamp::now();
+
+    loop {
+        let page_filter: Filter = filter.clone().until(until).limit(PAGE_SIZE);
+        let page: HashMap<EventId, Timestamp> =
+            match fetch(&relay, &database, page_filter, timeout).await {
+                Ok(page) => page,
+                Err(e) => {
+                    coverage.error = Some(e.to_string());
+                    break;
+                }
+            };
+
+        let len: usize = page.len();
+        let oldest: Option<Timestamp> = page.values().min().copied();
+        ids.extend(page.into_keys());
+
+        let oldest: Timestamp = match oldest {
+            Some(oldest) => {
+                coverage.oldest = Some(coverage.oldest.map_or(oldest, |o| o.min(oldest)));
+                oldest
+            }
+            None => {
+                coverage.complete = true;
+                break;
+            }
+        };
+
+        if len < PAGE_SIZE {
+            coverage.complete = true;
+            break;
+        }
+
+        // The page may end in the middle of a second shared by more events
+        let second_filter: Filter = filter.clone().since(oldest).until(oldest);
+        match fetch(&relay, &database, second_filter, timeout).await {
+            Ok(second) => ids.extend(second.into_keys()),
+            Err(e) => {
+                coverage.error = Some(e.to_string());
+                break;
+            }
+        }
+
+        until = oldest - 1u64;
+
+        tracing::debug!(
+            "Archived {} events from {url}, continue until {until}",
+            ids.len()
+        );
+    }
+
+    coverage.events = ids.len();
+
+    (coverage, ids)
+}
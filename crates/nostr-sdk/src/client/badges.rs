@@ -0,0 +1,108 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Badges (NIP58)
+
+use nostr::key::XOnlyPublicKey;
+use nostr::nips::nip01::Coordinate;
+use nostr::nips::nip58::Error as Nip58Error;
+use nostr::{Event, EventId, Kind, Tag};
+
+/// Badge accepted by a user, from its profile badges
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Badge {
+    /// Badge definition ([`Kind::BadgeDefinition`])
+    pub definition: Event,
+    /// Badge award ([`Kind::BadgeAward`])
+    pub award: Event,
+}
+
+/// Compose the `a` and `e` tags of a badge award accepted by `public_key`
+pub(crate) fn profile_badge_tags(
+    award: &Event,
+    public_key: &XOnlyPublicKey,
+) -> Result<[Tag; 2], Nip58Error> {
+    if award.kind != Kind::BadgeAward {
+        return Err(Nip58Error::InvalidKind);
+    }
+
+    let relay_url = award
+        .tags
+        .iter()
+        .find_map(|tag| match tag {
+            Tag::PublicKey {
+                public_key: p,
+                relay_url,
+                ..
+            } if p == public_key => Some(relay_url.clone()),
+            _ => None,
+        })
+        .ok_or(Nip58Error::BadgeAwardsLackAwardedPublicKey)?;
+
+    let a_tag: Tag = award
+        .tags
+        .iter()
+        .find(|tag| {
+            matches!(
+                tag,
+                Tag::A {
+                    kind: Kind::BadgeDefinition,
+                    ..
+                }
+            )
+        })
+        .cloned()
+        .ok_or(Nip58Error::BadgeAwardMissingATag)?;
+
+    Ok([
+        a_tag,
+        Tag::Event {
+            event_id: award.id,
+            relay_url,
+            marker: None,
+        },
+    ])
+}
+
+/// Match the `(definition, award)` pairs of the profile badges of `public_key` with the fetched `events`
+///
+/// The pairs with a missing definition or an award not issued by the badge author
+/// (or not awarded to `public_key`) are skipped.
+pub(crate) fn collect(
+    public_key: &XOnlyPublicKey,
+    pairs: Vec<(Coordinate, EventId)>,
+    events: &[Event],
+) -> Vec<Badge> {
+    pairs
+        .into_iter()
+        .filter_map(|(coordinate, award_id)| {
+            let award: &Event = events.iter().find(|event| {
+                event.id == award_id
+                    && event.pubkey == coordinate.pubkey
+                    && match profile_badge_tags(event, public_key) {
+                        Ok(
+                            [Tag::A {
+                                public_key,
+                                identifier,
+                                ..
+                            }, ..],
+                        ) => public_key == coordinate.pubkey && identifier == coordinate.identifier,
+                        _ => false,
+                    }
+            })?;
+            let definition: &Event = events
+                .iter()
+                .filter(|event| {
+                    event.kind == Kind::BadgeDefinition
+                        && event.pubkey == coordinate.pubkey
+                        && event.identifier() == Some(coordinate.identifier.as_str())
+                })
+                .max_by_key(|event| event.created_at)?;
+            Some(Badge {
+                definition: definition.clone(),
+                award: award.clone(),
+            })
+        })
+        .collect()
+}
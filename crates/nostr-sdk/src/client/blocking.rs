@@ -4,22 +4,48 @@
 
 #![allow(missing_docs)]
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 
 use nostr::key::XOnlyPublicKey;
+use nostr::nips::nip01::Coordinate;
+use nostr::nips::nip23::ArticleBuilder;
+use nostr::nips::nip25::{Reaction, Reactions};
+use nostr::nips::nip38::{StatusType, UserStatus};
+#[cfg(feature = "nip47")]
+use nostr::nips::nip47::Method as NostrWalletConnectMethod;
+use nostr::nips::nip51::{List, ListItems};
+use nostr::nips::nip56::ReportTarget;
+#[cfg(feature = "nip59")]
+use nostr::nips::nip59::UnwrappedGift;
+use nostr::nips::nip84::HighlightSource;
 use nostr::nips::nip94::FileMetadata;
 use nostr::url::Url;
+use nostr::util::EventIdOrCoordinate;
 use nostr::{
-    ClientMessage, Contact, Event, EventBuilder, EventId, Filter, Keys, Metadata, Result, Tag,
+    ClientMessage, Contact, Event, EventBuilder, EventId, Filter, Keys, Kind, Metadata, PageCursor,
+    RelayMetadata, Report, Result, Tag, Timestamp, UncheckedUrl,
 };
 use nostr_database::DynNostrDatabase;
+use nostr_sdk_net::futures_util::StreamExt;
 use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
 
 use super::signer::ClientSigner;
-use super::{Error, Options, TryIntoUrl};
-use crate::relay::{pool, Relay, RelayOptions, RelayPoolNotification};
+#[cfg(feature = "nip44")]
+use super::Draft;
+use super::{
+    Accounts, AppHandler, ArchiveReport, Badge, Channel, Conversations, Error, Feed, MuteList,
+    NotificationHandler, Options, Outbox, RelayRebroadcast, ScheduledEvent, SearchResult,
+    SendAndWaitOptions, Thread, TryIntoUrl,
+};
+#[cfg(feature = "nip57")]
+use super::{ZapEntity, Zaps};
+use crate::relay::{
+    pool, ConnectReport, CountReport, Middleware, Moderation, NotificationHub, Quarantine,
+    QueuedMessage, Relay, RelayOptions, RelayPoolNotification, SeenTracker, ShutdownReport,
+};
 use crate::{ClientBuilder, NegentropyOptions, RUNTIME};
 
 #[derive(Debug, Clone)]
@@ -75,6 +101,16 @@ impl Client {
         RUNTIME.block_on(async { self.client.set_signer(signer).await })
     }
 
+    /// Get signer used for NIP42 authentication
+    pub fn auth_signer(&self) -> Result<ClientSigner, Error> {
+        RUNTIME.block_on(async { self.client.auth_signer().await })
+    }
+
+    /// Set signer used for NIP42 authentication
+    pub fn set_auth_signer(&self, signer: Option<ClientSigner>) {
+        RUNTIME.block_on(async { self.client.set_auth_signer(signer).await })
+    }
+
     /// Get current [`Keys`]
     #[deprecated(since = "0.27.0", note = "Use `client.signer()` instead.")]
     pub fn keys(&self) -> Keys {
@@ -89,6 +125,66 @@ impl Client {
         RUNTIME.block_on(async { self.client.set_keys(keys).await })
     }
 
+    pub fn accounts(&self) -> Accounts {
+        self.client.accounts()
+    }
+
+    pub fn add_account<S>(&self, signer: S) -> Result<XOnlyPublicKey, Error>
+    where
+        S: Into<ClientSigner>,
+    {
+        RUNTIME.block_on(async { self.client.add_account(signer).await })
+    }
+
+    pub fn remove_account(&self, public_key: &XOnlyPublicKey) -> bool {
+        RUNTIME.block_on(async { self.client.remove_account(public_key).await })
+    }
+
+    pub fn switch_account(&self, public_key: &XOnlyPublicKey) -> Result<(), Error> {
+        RUNTIME.block_on(async { self.client.switch_account(public_key).await })
+    }
+
+    pub fn active_account(&self) -> Option<XOnlyPublicKey> {
+        RUNTIME.block_on(async { self.client.active_account().await })
+    }
+
+    pub fn add_account_relay<U>(&self, public_key: &XOnlyPublicKey, url: U) -> Result<(), Error>
+    where
+        U: TryIntoUrl,
+        pool::Error: From<<U as TryIntoUrl>::Err>,
+    {
+        RUNTIME.block_on(async { self.client.add_account_relay(public_key, url).await })
+    }
+
+    pub fn subscribe_as(
+        &self,
+        public_key: &XOnlyPublicKey,
+        filters: Vec<Filter>,
+    ) -> Result<(), Error> {
+        RUNTIME.block_on(async { self.client.subscribe_as(public_key, filters).await })
+    }
+
+    pub fn send_event_builder_as(
+        &self,
+        public_key: &XOnlyPublicKey,
+        builder: EventBuilder,
+    ) -> Result<EventId, Error> {
+        RUNTIME.block_on(async { self.client.send_event_builder_as(public_key, builder).await })
+    }
+
+    pub fn get_events_of_as(
+        &self,
+        public_key: &XOnlyPublicKey,
+        filters: Vec<Filter>,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<Event>, Error> {
+        RUNTIME.block_on(async {
+            self.client
+                .get_events_of_as(public_key, filters, timeout)
+                .await
+        })
+    }
+
     /// Get database
     pub fn database(&self) -> Arc<DynNostrDatabase> {
         self.client.database()
@@ -114,16 +210,61 @@ impl Client {
         RUNTIME.block_on(async { self.client.shutdown().await })
     }
 
+    pub fn shutdown_with_timeout(self, timeout: Duration) -> Result<ShutdownReport, Error> {
+        RUNTIME.block_on(async { self.client.shutdown_with_timeout(timeout).await })
+    }
+
     pub fn notifications(&self) -> broadcast::Receiver<RelayPoolNotification> {
         self.client.notifications()
     }
 
+    pub fn notification_hub(&self) -> NotificationHub {
+        self.client.notification_hub()
+    }
+
+    pub fn seen_tracker(&self) -> SeenTracker {
+        self.client.seen_tracker()
+    }
+
+    pub fn quarantine(&self) -> Quarantine {
+        self.client.quarantine()
+    }
+
+    pub fn moderation(&self) -> Moderation {
+        self.client.moderation()
+    }
+
+    /// Add a [`Middleware`]
+    pub fn add_middleware<M>(&self, middleware: M)
+    where
+        M: Middleware + 'static,
+    {
+        RUNTIME.block_on(async { self.client.add_middleware(middleware).await })
+    }
+
+    /// Remove all the middlewares
+    pub fn clear_middlewares(&self) {
+        RUNTIME.block_on(async { self.client.clear_middlewares().await })
+    }
+
     /// Get relays
     pub fn relays(&self) -> HashMap<Url, Relay> {
         RUNTIME.block_on(async { self.client.relays().await })
     }
 
     /// Get [`Relay`]
+    pub fn relays_with_label(&self, label: &str) -> HashMap<Url, Relay> {
+        RUNTIME.block_on(async { self.client.relays_with_label(label).await })
+    }
+
+    pub fn queued_messages(&self) -> HashMap<Url, Vec<QueuedMessage>> {
+        RUNTIME.block_on(async { self.client.queued_messages().await })
+    }
+
+    pub fn cancel_queued_event(&self, event_id: EventId) -> HashSet<Url> {
+        RUNTIME.block_on(async { self.client.cancel_queued_event(event_id).await })
+    }
+
     pub fn relay<U>(&self, url: U) -> Result<Relay, Error>
     where
         U: TryIntoUrl,
@@ -166,6 +307,19 @@ impl Client {
         RUNTIME.block_on(async { self.client.remove_relay(url).await })
     }
 
+    pub fn remove_all_relays(&self) -> Result<(), Error> {
+        RUNTIME.block_on(async { self.client.remove_all_relays().await })
+    }
+
+    pub fn set_relays<I, U>(&self, relays: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = U>,
+        U: TryIntoUrl,
+        pool::Error: From<<U as TryIntoUrl>::Err>,
+    {
+        RUNTIME.block_on(async { self.client.set_relays(relays).await })
+    }
+
     pub fn connect_relay<U>(&self, url: U) -> Result<(), Error>
     where
         U: TryIntoUrl,
@@ -182,10 +336,12 @@ impl Client {
         RUNTIME.block_on(async { self.client.disconnect_relay(url).await })
     }
 
-    pub fn connect(&self) {
-        RUNTIME.block_on(async {
-            self.client.connect().await;
-        })
+    pub fn connect(&self) -> ConnectReport {
+        RUNTIME.block_on(async { self.client.connect().await })
+    }
+
+    pub fn connect_with_timeout(&self, timeout: Duration) -> ConnectReport {
+        RUNTIME.block_on(async { self.client.connect_with_timeout(timeout).await })
     }
 
     pub fn disconnect(&self) -> Result<(), Error> {
@@ -204,6 +360,30 @@ impl Client {
         })
     }
 
+    pub fn subscribe_to_label(&self, label: &str, filters: Vec<Filter>) -> Result<(), Error> {
+        RUNTIME.block_on(async { self.client.subscribe_to_label(label, filters).await })
+    }
+
+    pub fn unsubscribe_from_label(&self, label: &str) {
+        RUNTIME.block_on(async { self.client.unsubscribe_from_label(label).await })
+    }
+
+    pub fn subscribe_to<U>(&self, url: U, filters: Vec<Filter>) -> Result<(), Error>
+    where
+        U: TryIntoUrl,
+        pool::Error: From<<U as TryIntoUrl>::Err>,
+    {
+        RUNTIME.block_on(async { self.client.subscribe_to(url, filters).await })
+    }
+
+    pub fn unsubscribe_from<U>(&self, url: U) -> Result<(), Error>
+    where
+        U: TryIntoUrl,
+        pool::Error: From<<U as TryIntoUrl>::Err>,
+    {
+        RUNTIME.block_on(async { self.client.unsubscribe_from(url).await })
+    }
+
     pub fn get_events_of(
         &self,
         filters: Vec<Filter>,
@@ -212,6 +392,80 @@ impl Client {
         RUNTIME.block_on(async { self.client.get_events_of(filters, timeout).await })
     }
 
+    pub fn get_events_from_label(
+        &self,
+        label: &str,
+        filters: Vec<Filter>,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<Event>, Error> {
+        RUNTIME.block_on(async {
+            self.client
+                .get_events_from_label(label, filters, timeout)
+                .await
+        })
+    }
+
+    pub fn fetch_latest_replaceable(
+        &self,
+        coordinate: Coordinate,
+        timeout: Option<Duration>,
+    ) -> Result<Option<Event>, Error> {
+        RUNTIME.block_on(async {
+            self.client
+                .fetch_latest_replaceable(coordinate, timeout)
+                .await
+        })
+    }
+
+    pub fn get_feed(&self, feed: &Feed, timeout: Option<Duration>) -> Result<Vec<Event>, Error> {
+        RUNTIME.block_on(async { self.client.get_feed(feed, timeout).await })
+    }
+
+    pub fn get_events_page(
+        &self,
+        filters: Vec<Filter>,
+        cursor: Option<PageCursor>,
+        limit: usize,
+        timeout: Option<Duration>,
+    ) -> Result<(Vec<Event>, Option<PageCursor>), Error> {
+        RUNTIME.block_on(async {
+            self.client
+                .get_events_page(filters, cursor, limit, timeout)
+                .await
+        })
+    }
+
+    /// Get all the events of filters, in pages of max `page_size` events (see [`super::Client::get_events_paged`])
+    pub fn get_events_paged(
+        &self,
+        filters: Vec<Filter>,
+        page_size: usize,
+        timeout: Option<Duration>,
+    ) -> impl Iterator<Item = Result<Vec<Event>, Error>> + '_ {
+        let mut pages = Box::pin(self.client.get_events_paged(filters, page_size, timeout));
+        std::iter::from_fn(move || RUNTIME.block_on(pages.next()))
+    }
+
+    pub fn search<S>(
+        &self,
+        query: S,
+        filter: Filter,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<SearchResult>, Error>
+    where
+        S: Into<String>,
+    {
+        RUNTIME.block_on(async { self.client.search(query, filter, timeout).await })
+    }
+
+    pub fn count_events_of(
+        &self,
+        filters: Vec<Filter>,
+        timeout: Option<Duration>,
+    ) -> Result<CountReport, Error> {
+        RUNTIME.block_on(async { self.client.count_events_of(filters, timeout).await })
+    }
+
     pub fn req_events_of(&self, filters: Vec<Filter>, timeout: Option<Duration>) {
         RUNTIME.block_on(async {
             self.client.req_events_of(filters, timeout).await;
@@ -230,6 +484,19 @@ impl Client {
         RUNTIME.block_on(async { self.client.send_msg_to(url, msg).await })
     }
 
+    pub fn send_msg_to_many<I, U>(
+        &self,
+        urls: I,
+        msg: ClientMessage,
+    ) -> Result<HashMap<Url, Result<(), pool::Error>>, Error>
+    where
+        I: IntoIterator<Item = U>,
+        U: TryIntoUrl,
+        pool::Error: From<<U as TryIntoUrl>::Err>,
+    {
+        RUNTIME.block_on(async { self.client.send_msg_to_many(urls, msg).await })
+    }
+
     /// Send event
     pub fn send_event(&self, event: Event) -> Result<EventId, Error> {
         RUNTIME.block_on(async { self.client.send_event(event).await })
@@ -243,6 +510,43 @@ impl Client {
         RUNTIME.block_on(async { self.client.send_event_to(url, event).await })
     }
 
+    pub fn send_msg_to_label(
+        &self,
+        label: &str,
+        msg: ClientMessage,
+    ) -> Result<HashMap<Url, Result<(), pool::Error>>, Error> {
+        RUNTIME.block_on(async { self.client.send_msg_to_label(label, msg).await })
+    }
+
+    pub fn send_event_to_label(
+        &self,
+        label: &str,
+        event: Event,
+    ) -> Result<HashMap<Url, Result<EventId, pool::Error>>, Error> {
+        RUNTIME.block_on(async { self.client.send_event_to_label(label, event).await })
+    }
+
+    pub fn send_event_to_many<I, U>(
+        &self,
+        urls: I,
+        event: Event,
+    ) -> Result<HashMap<Url, Result<EventId, pool::Error>>, Error>
+    where
+        I: IntoIterator<Item = U>,
+        U: TryIntoUrl,
+        pool::Error: From<<U as TryIntoUrl>::Err>,
+    {
+        RUNTIME.block_on(async { self.client.send_event_to_many(urls, event).await })
+    }
+
+    pub fn send_event_and_wait(
+        &self,
+        event: Event,
+        opts: SendAndWaitOptions,
+    ) -> Result<EventId, Error> {
+        RUNTIME.block_on(async { self.client.send_event_and_wait(event, opts).await })
+    }
+
     pub fn send_event_builder(&self, builder: EventBuilder) -> Result<EventId, Error> {
         RUNTIME.block_on(async { self.client.send_event_builder(builder).await })
     }
@@ -279,6 +583,67 @@ impl Client {
         RUNTIME.block_on(async { self.client.set_contact_list(list).await })
     }
 
+    pub fn follow<S>(
+        &self,
+        public_key: XOnlyPublicKey,
+        relay_url: Option<UncheckedUrl>,
+        alias: Option<S>,
+    ) -> Result<EventId, Error>
+    where
+        S: Into<String>,
+    {
+        RUNTIME.block_on(async { self.client.follow(public_key, relay_url, alias).await })
+    }
+
+    pub fn unfollow(&self, public_key: XOnlyPublicKey) -> Result<EventId, Error> {
+        RUNTIME.block_on(async { self.client.unfollow(public_key).await })
+    }
+
+    pub fn sync_mute_list(&self, timeout: Option<Duration>) -> Result<MuteList, Error> {
+        RUNTIME.block_on(async { self.client.sync_mute_list(timeout).await })
+    }
+
+    pub fn mute_list(&self) -> MuteList {
+        RUNTIME.block_on(async { self.client.mute_list().await })
+    }
+
+    pub fn is_muted(&self, event: &Event) -> bool {
+        RUNTIME.block_on(async { self.client.is_muted(event).await })
+    }
+
+    pub fn get_list(&self, list: List, timeout: Option<Duration>) -> Result<ListItems, Error> {
+        RUNTIME.block_on(async { self.client.get_list(list, timeout).await })
+    }
+
+    pub fn add_to_list<I>(&self, list: List, items: I, private: bool) -> Result<EventId, Error>
+    where
+        I: IntoIterator<Item = Tag>,
+    {
+        RUNTIME.block_on(async { self.client.add_to_list(list, items, private).await })
+    }
+
+    pub fn remove_from_list<I>(&self, list: List, items: I) -> Result<EventId, Error>
+    where
+        I: IntoIterator<Item = Tag>,
+    {
+        RUNTIME.block_on(async { self.client.remove_from_list(list, items).await })
+    }
+
+    pub fn set_list(&self, list: List, items: ListItems) -> Result<EventId, Error> {
+        RUNTIME.block_on(async { self.client.set_list(list, items).await })
+    }
+
+    pub fn publish_relay_list<I>(&self, list: I) -> Result<EventId, Error>
+    where
+        I: IntoIterator<Item = (UncheckedUrl, Option<RelayMetadata>)>,
+    {
+        RUNTIME.block_on(async { self.client.publish_relay_list(list).await })
+    }
+
+    pub fn sync_relay_list(&self, timeout: Option<Duration>) -> Result<bool, Error> {
+        RUNTIME.block_on(async { self.client.sync_relay_list(timeout).await })
+    }
+
     pub fn get_contact_list(&self, timeout: Option<Duration>) -> Result<Vec<Contact>, Error> {
         RUNTIME.block_on(async { self.client.get_contact_list(timeout).await })
     }
@@ -297,6 +662,33 @@ impl Client {
         RUNTIME.block_on(async { self.client.get_contact_list_metadata(timeout).await })
     }
 
+    pub fn fetch_thread(
+        &self,
+        event_id: EventId,
+        timeout: Option<Duration>,
+    ) -> Result<Thread, Error> {
+        RUNTIME.block_on(async { self.client.fetch_thread(event_id, timeout).await })
+    }
+
+    pub fn fetch_metadata(
+        &self,
+        public_key: XOnlyPublicKey,
+        timeout: Option<Duration>,
+    ) -> Result<Option<Metadata>, Error> {
+        RUNTIME.block_on(async { self.client.fetch_metadata(public_key, timeout).await })
+    }
+
+    pub fn fetch_metadatas<I>(
+        &self,
+        public_keys: I,
+        timeout: Option<Duration>,
+    ) -> Result<HashMap<XOnlyPublicKey, Metadata>, Error>
+    where
+        I: IntoIterator<Item = XOnlyPublicKey>,
+    {
+        RUNTIME.block_on(async { self.client.fetch_metadatas(public_keys, timeout).await })
+    }
+
     #[cfg(feature = "nip04")]
     pub fn send_direct_msg<S>(
         &self,
@@ -310,6 +702,39 @@ impl Client {
         RUNTIME.block_on(async { self.client.send_direct_msg(receiver, msg, reply).await })
     }
 
+    #[cfg(feature = "nip59")]
+    pub fn send_private_msg<S>(
+        &self,
+        receiver: XOnlyPublicKey,
+        message: S,
+        reply_to: Option<EventId>,
+    ) -> Result<EventId, Error>
+    where
+        S: Into<String>,
+    {
+        RUNTIME.block_on(async {
+            self.client
+                .send_private_msg(receiver, message, reply_to)
+                .await
+        })
+    }
+
+    #[cfg(feature = "nip59")]
+    pub fn unwrap_gift_wrap(&self, gift_wrap: &Event) -> Result<UnwrappedGift, Error> {
+        RUNTIME.block_on(async { self.client.unwrap_gift_wrap(gift_wrap).await })
+    }
+
+    pub fn repost(&self, event: &Event, relay_url: Option<UncheckedUrl>) -> Result<EventId, Error> {
+        RUNTIME.block_on(async { self.client.repost(event, relay_url).await })
+    }
+
+    pub fn quote<S>(&self, event: &Event, comment: S) -> Result<EventId, Error>
+    where
+        S: Into<String>,
+    {
+        RUNTIME.block_on(async { self.client.quote(event, comment).await })
+    }
+
     pub fn repost_event(
         &self,
         event_id: EventId,
@@ -322,6 +747,26 @@ impl Client {
         RUNTIME.block_on(async { self.client.delete_event(event_id).await })
     }
 
+    pub fn delete_events<I, T, S>(&self, ids: I, reason: Option<S>) -> Result<EventId, Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<EventIdOrCoordinate>,
+        S: Into<String>,
+    {
+        RUNTIME.block_on(async { self.client.delete_events(ids, reason).await })
+    }
+
+    pub fn delete_by_coordinate<S>(
+        &self,
+        coordinate: Coordinate,
+        reason: Option<S>,
+    ) -> Result<EventId, Error>
+    where
+        S: Into<String>,
+    {
+        RUNTIME.block_on(async { self.client.delete_by_coordinate(coordinate, reason).await })
+    }
+
     pub fn like(&self, event_id: EventId, public_key: XOnlyPublicKey) -> Result<EventId, Error> {
         RUNTIME.block_on(async { self.client.like(event_id, public_key).await })
     }
@@ -342,6 +787,104 @@ impl Client {
         RUNTIME.block_on(async { self.client.reaction(event_id, public_key, content).await })
     }
 
+    pub fn react<R>(&self, event: &Event, reaction: R) -> Result<EventId, Error>
+    where
+        R: Into<Reaction>,
+    {
+        RUNTIME.block_on(async { self.client.react(event, reaction).await })
+    }
+
+    pub fn reactions_of(
+        &self,
+        event_id: EventId,
+        timeout: Option<Duration>,
+    ) -> Result<Reactions, Error> {
+        RUNTIME.block_on(async { self.client.reactions_of(event_id, timeout).await })
+    }
+
+    pub fn report<T, S>(
+        &self,
+        target: T,
+        report: Report,
+        reason: S,
+        mute: bool,
+    ) -> Result<EventId, Error>
+    where
+        T: Into<ReportTarget>,
+        S: Into<String>,
+    {
+        RUNTIME.block_on(async { self.client.report(target, report, reason, mute).await })
+    }
+
+    pub fn highlight<T, S, C>(
+        &self,
+        source: T,
+        selection: S,
+        comment: Option<C>,
+    ) -> Result<EventId, Error>
+    where
+        T: Into<HighlightSource>,
+        S: Into<String>,
+        C: Into<String>,
+    {
+        RUNTIME.block_on(async { self.client.highlight(source, selection, comment).await })
+    }
+
+    pub fn award_badge<I>(&self, definition: &Event, recipients: I) -> Result<EventId, Error>
+    where
+        I: IntoIterator<Item = XOnlyPublicKey>,
+    {
+        RUNTIME.block_on(async { self.client.award_badge(definition, recipients).await })
+    }
+
+    pub fn set_profile_badges<I>(&self, awards: I) -> Result<EventId, Error>
+    where
+        I: IntoIterator<Item = Event>,
+    {
+        RUNTIME.block_on(async { self.client.set_profile_badges(awards).await })
+    }
+
+    pub fn find_handlers(
+        &self,
+        kind: Kind,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<AppHandler>, Error> {
+        RUNTIME.block_on(async { self.client.find_handlers(kind, timeout).await })
+    }
+
+    pub fn fetch_badges(
+        &self,
+        public_key: XOnlyPublicKey,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<Badge>, Error> {
+        RUNTIME.block_on(async { self.client.fetch_badges(public_key, timeout).await })
+    }
+
+    pub fn set_status<S>(
+        &self,
+        status_type: StatusType,
+        content: S,
+        expiration: Option<Timestamp>,
+        link: Option<String>,
+    ) -> Result<EventId, Error>
+    where
+        S: Into<String>,
+    {
+        RUNTIME.block_on(async {
+            self.client
+                .set_status(status_type, content, expiration, link)
+                .await
+        })
+    }
+
+    pub fn fetch_status(
+        &self,
+        public_key: XOnlyPublicKey,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<UserStatus>, Error> {
+        RUNTIME.block_on(async { self.client.fetch_status(public_key, timeout).await })
+    }
+
     pub fn new_channel(&self, metadata: &Metadata) -> Result<EventId, Error> {
         RUNTIME.block_on(async { self.client.new_channel(metadata).await })
     }
@@ -375,6 +918,31 @@ impl Client {
         })
     }
 
+    pub fn reply_channel_msg<S>(
+        &self,
+        channel_id: EventId,
+        relay_url: Url,
+        reply_to: &Event,
+        msg: S,
+    ) -> Result<EventId, Error>
+    where
+        S: Into<String>,
+    {
+        RUNTIME.block_on(async {
+            self.client
+                .reply_channel_msg(channel_id, relay_url, reply_to, msg)
+                .await
+        })
+    }
+
+    pub fn fetch_channel(
+        &self,
+        channel_id: EventId,
+        timeout: Option<Duration>,
+    ) -> Result<Channel, Error> {
+        RUNTIME.block_on(async { self.client.fetch_channel(channel_id, timeout).await })
+    }
+
     pub fn hide_channel_msg<S>(
         &self,
         message_id: EventId,
@@ -397,7 +965,7 @@ impl Client {
         RUNTIME.block_on(async { self.client.mute_channel_user(pubkey, reason).await })
     }
 
-    /// Create an auth event
+    /// Authenticate to relay
     ///
     /// <https://github.com/nostr-protocol/nips/blob/master/42.md>
     pub fn auth<S>(&self, challenge: S, relay: Url) -> Result<EventId, Error>
@@ -427,6 +995,66 @@ impl Client {
         })
     }
 
+    pub fn publish_article(&self, article: ArticleBuilder) -> Result<EventId, Error> {
+        RUNTIME.block_on(async { self.client.publish_article(article).await })
+    }
+
+    pub fn promote_article_draft(&self, draft: &Event) -> Result<EventId, Error> {
+        RUNTIME.block_on(async { self.client.promote_article_draft(draft).await })
+    }
+
+    #[cfg(feature = "nip44")]
+    pub fn save_draft(&self, builder: EventBuilder) -> Result<String, Error> {
+        RUNTIME.block_on(async { self.client.save_draft(builder).await })
+    }
+
+    #[cfg(feature = "nip44")]
+    pub fn update_draft(&self, identifier: &str, builder: EventBuilder) -> Result<EventId, Error> {
+        RUNTIME.block_on(async { self.client.update_draft(identifier, builder).await })
+    }
+
+    #[cfg(feature = "nip44")]
+    pub fn delete_draft(&self, identifier: &str, draft_kind: Kind) -> Result<EventId, Error> {
+        RUNTIME.block_on(async { self.client.delete_draft(identifier, draft_kind).await })
+    }
+
+    #[cfg(feature = "nip44")]
+    pub fn decrypt_draft(&self, event: &Event) -> Result<Draft, Error> {
+        RUNTIME.block_on(async { self.client.decrypt_draft(event).await })
+    }
+
+    #[cfg(feature = "nip44")]
+    pub fn get_drafts(&self, timeout: Option<Duration>) -> Result<Vec<Draft>, Error> {
+        RUNTIME.block_on(async { self.client.get_drafts(timeout).await })
+    }
+
+    /// Zap a profile or an event
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/57.md>
+    #[cfg(feature = "nip57")]
+    pub fn zap<T>(&self, to: T, amount_msat: u64, comment: Option<String>) -> Result<String, Error>
+    where
+        T: Into<ZapEntity>,
+    {
+        RUNTIME.block_on(async { self.client.zap(to, amount_msat, comment).await })
+    }
+
+    #[cfg(feature = "nip57")]
+    pub fn zaps_of<T>(&self, of: T, timeout: Option<Duration>) -> Result<Zaps, Error>
+    where
+        T: Into<ZapEntity>,
+    {
+        RUNTIME.block_on(async { self.client.zaps_of(of, timeout).await })
+    }
+
+    #[cfg(feature = "nip47")]
+    pub fn publish_wallet_connect_info<I>(&self, methods: I) -> Result<EventId, Error>
+    where
+        I: IntoIterator<Item = NostrWalletConnectMethod>,
+    {
+        RUNTIME.block_on(async { self.client.publish_wallet_connect_info(methods).await })
+    }
+
     /// File metadata
     ///
     /// <https://github.com/nostr-protocol/nips/blob/master/94.md>
@@ -437,23 +1065,85 @@ impl Client {
         RUNTIME.block_on(async { self.client.file_metadata(description, metadata).await })
     }
 
+    #[cfg(feature = "nip96")]
+    pub fn upload_file<S>(
+        &self,
+        server_url: Url,
+        bytes: Vec<u8>,
+        mime_type: S,
+    ) -> Result<FileMetadata, Error>
+    where
+        S: AsRef<str>,
+    {
+        RUNTIME.block_on(async { self.client.upload_file(server_url, bytes, mime_type).await })
+    }
+
     /// Negentropy reconciliation
     pub fn reconcile(&self, filter: Filter, opts: NegentropyOptions) -> Result<(), Error> {
         RUNTIME.block_on(async move { self.client.reconcile(filter, opts).await })
     }
 
+    pub fn archive_pubkey(
+        &self,
+        public_key: XOnlyPublicKey,
+        timeout: Option<Duration>,
+    ) -> Result<ArchiveReport, Error> {
+        RUNTIME.block_on(async { self.client.archive_pubkey(public_key, timeout).await })
+    }
+
+    pub fn rebroadcast(
+        &self,
+        filter: Filter,
+        timeout: Option<Duration>,
+    ) -> Result<HashMap<Url, RelayRebroadcast>, Error> {
+        RUNTIME.block_on(async { self.client.rebroadcast(filter, timeout).await })
+    }
+
     #[deprecated(since = "0.27.0")]
     pub fn get_channels(&self, timeout: Option<Duration>) -> Result<Vec<Event>, Error> {
         #[allow(deprecated)]
         RUNTIME.block_on(async { self.client.get_channels(timeout).await })
     }
 
+    pub fn outbox(&self) -> Outbox {
+        self.client.outbox()
+    }
+
+    pub fn flush_outbox(&self) -> usize {
+        RUNTIME.block_on(async { self.client.flush_outbox().await })
+    }
+
+    pub fn schedule_event(&self, event: Event, publish_at: Timestamp) -> EventId {
+        RUNTIME.block_on(async { self.client.schedule_event(event, publish_at).await })
+    }
+
+    pub fn scheduled_events(&self) -> Vec<ScheduledEvent> {
+        RUNTIME.block_on(async { self.client.scheduled_events().await })
+    }
+
+    pub fn cancel_scheduled_event(&self, event_id: &EventId) -> bool {
+        RUNTIME.block_on(async { self.client.cancel_scheduled_event(event_id).await })
+    }
+
+    pub fn conversations(&self) -> Conversations {
+        self.client.conversations()
+    }
+
+    pub fn sync_conversations(&self, timeout: Option<Duration>) -> Result<usize, Error> {
+        RUNTIME.block_on(async { self.client.sync_conversations(timeout).await })
+    }
+
     pub fn handle_notifications<F>(&self, func: F) -> Result<(), Error>
     where
         F: Fn(RelayPoolNotification) -> Result<bool>,
     {
         let mut notifications = self.client.notifications();
-        while let Ok(notification) = RUNTIME.block_on(notifications.recv()) {
+        loop {
+            let notification: RelayPoolNotification = match RUNTIME.block_on(notifications.recv()) {
+                Ok(notification) => notification,
+                Err(RecvError::Lagged(n)) => RelayPoolNotification::Lagged(n),
+                Err(RecvError::Closed) => break,
+            };
             let stop: bool = RelayPoolNotification::Stop == notification;
             let shutdown: bool = RelayPoolNotification::Shutdown == notification;
             let exit: bool = func(notification).map_err(|e| Error::Handler(e.to_string()))?;
@@ -463,4 +1153,19 @@ impl Client {
         }
         Ok(())
     }
+
+    pub fn handle_notifications_with<H>(&self, handler: &H) -> Result<(), Error>
+    where
+        H: NotificationHandler,
+    {
+        RUNTIME.block_on(async { self.client.handle_notifications_with(handler).await })
+    }
+
+    /// Register a [`NotificationHandler`]
+    pub fn add_notification_handler<H>(&self, handler: H)
+    where
+        H: NotificationHandler + 'static,
+    {
+        RUNTIME.block_on(async { self.client.add_notification_handler(handler) })
+    }
 }
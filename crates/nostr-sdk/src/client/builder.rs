@@ -10,14 +10,34 @@ use nostr_database::memory::MemoryDatabase;
 use nostr_database::{DynNostrDatabase, IntoNostrDatabase};
 
 use super::signer::ClientSigner;
-use crate::{Client, Options};
+#[cfg(all(feature = "nip47", feature = "nip57"))]
+use super::NWC;
+use crate::{Client, Options, RelayPoolOptions};
 
 /// Client builder
+///
+/// # Example
+/// ```rust,no_run
+/// use nostr_sdk::prelude::*;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let keys = Keys::generate();
+/// let client: Client = ClientBuilder::new()
+///     .signer(&keys)
+///     .opts(Options::new().wait_for_send(false))
+///     .pool_opts(RelayPoolOptions::new().notify_duplicates(true))
+///     .build();
+/// # }
+/// ```
 #[derive(Debug, Clone)]
 pub struct ClientBuilder {
     pub(super) signer: Option<ClientSigner>,
     pub(super) database: Arc<DynNostrDatabase>,
     pub(super) opts: Options,
+    pool_opts: Option<RelayPoolOptions>,
+    #[cfg(all(feature = "nip47", feature = "nip57"))]
+    nwc: Option<NWC>,
 }
 
 impl Default for ClientBuilder {
@@ -26,6 +46,9 @@ impl Default for ClientBuilder {
             signer: None,
             database: Arc::new(MemoryDatabase::default()),
             opts: Options::default(),
+            pool_opts: None,
+            #[cfg(all(feature = "nip47", feature = "nip57"))]
+            nwc: None,
         }
     }
 }
@@ -71,8 +94,32 @@ impl ClientBuilder {
         self
     }
 
+    /// Set pool options
+    ///
+    /// Override the pool options of [`ClientBuilder::opts`], regardless of the order of the calls.
+    pub fn pool_opts(mut self, opts: RelayPoolOptions) -> Self {
+        self.pool_opts = Some(opts);
+        self
+    }
+
+    /// Set the wallet used to pay the zaps
+    ///
+    /// Override the wallet of [`ClientBuilder::opts`], regardless of the order of the calls.
+    #[cfg(all(feature = "nip47", feature = "nip57"))]
+    pub fn zapper(mut self, nwc: NWC) -> Self {
+        self.nwc = Some(nwc);
+        self
+    }
+
     /// Build [`Client`]
-    pub fn build(self) -> Client {
+    pub fn build(mut self) -> Client {
+        if let Some(pool_opts) = self.pool_opts.take() {
+            self.opts = self.opts.pool(pool_opts);
+        }
+        #[cfg(all(feature = "nip47", feature = "nip57"))]
+        if let Some(nwc) = self.nwc.take() {
+            self.opts = self.opts.nostr_wallet_connect(nwc);
+        }
         Client::from_builder(self)
     }
 }
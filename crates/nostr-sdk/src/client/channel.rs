@@ -0,0 +1,65 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Public chat channels (NIP28)
+
+use nostr::key::XOnlyPublicKey;
+use nostr::types::metadata::Error as MetadataError;
+use nostr::{Event, EventId, JsonUtil, Kind, Metadata, Tag, Timestamp};
+
+/// Public chat channel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Channel {
+    /// Channel ID ([`Kind::ChannelCreation`] event ID)
+    pub id: EventId,
+    /// Channel creator
+    pub creator: XOnlyPublicKey,
+    /// Metadata, with the latest [`Kind::ChannelMetadata`] applied
+    pub metadata: Metadata,
+    /// Creation timestamp
+    pub created_at: Timestamp,
+    /// Timestamp of the applied [`Kind::ChannelMetadata`], if any
+    pub updated_at: Option<Timestamp>,
+}
+
+impl Channel {
+    /// Compose from the [`Kind::ChannelCreation`] event and its [`Kind::ChannelMetadata`] updates
+    ///
+    /// Only the updates of the channel creator are applied. Updates with an invalid content are skipped.
+    pub fn from_events<'a, I>(creation: &Event, updates: I) -> Result<Self, MetadataError>
+    where
+        I: IntoIterator<Item = &'a Event>,
+    {
+        let mut channel = Self {
+            id: creation.id,
+            creator: creation.pubkey,
+            metadata: Metadata::from_json(&creation.content)?,
+            created_at: creation.created_at,
+            updated_at: None,
+        };
+
+        let mut updates: Vec<&Event> = updates
+            .into_iter()
+            .filter(|event| {
+                event.kind == Kind::ChannelMetadata
+                    && event.pubkey == channel.creator
+                    && event.tags.iter().any(|tag| match tag {
+                        Tag::Event { event_id, .. } => event_id == &channel.id,
+                        _ => false,
+                    })
+            })
+            .collect();
+        updates.sort_by_key(|event| std::cmp::Reverse(event.created_at));
+
+        if let Some((event, metadata)) = updates
+            .into_iter()
+            .find_map(|event| Some((event, Metadata::from_json(&event.content).ok()?)))
+        {
+            channel.metadata = metadata;
+            channel.updated_at = Some(event.created_at);
+        }
+
+        Ok(channel)
+    }
+}
@@ -0,0 +1,252 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Direct message conversations (NIP04 and NIP17)
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use nostr::key::XOnlyPublicKey;
+use nostr::{Event, EventId, Kind, Tag, Timestamp, UnsignedEvent};
+use nostr_sdk_net::futures_util::stream::{self, Stream};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, RwLock};
+
+const CHANNEL_SIZE: usize = 1024;
+
+/// Decrypted direct message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectMessage {
+    /// Event ID (rumor ID for [`Kind::PrivateDirectMessage`])
+    pub id: EventId,
+    /// [`Kind::EncryptedDirectMessage`] or [`Kind::PrivateDirectMessage`]
+    pub kind: Kind,
+    /// Sender
+    pub sender: XOnlyPublicKey,
+    /// Receiver (first `p` tag)
+    pub receiver: XOnlyPublicKey,
+    /// Decrypted content
+    pub content: String,
+    /// Timestamp
+    pub created_at: Timestamp,
+    /// Replied message (first `e` tag)
+    pub reply_to: Option<EventId>,
+}
+
+impl DirectMessage {
+    /// Compose from a [`Kind::EncryptedDirectMessage`] and its decrypted `content`
+    ///
+    /// Return `None` if the event has not a `p` tag.
+    pub fn from_nip04(event: &Event, content: String) -> Option<Self> {
+        Some(Self {
+            id: event.id,
+            kind: event.kind,
+            sender: event.pubkey,
+            receiver: first_public_key(&event.tags)?,
+            content,
+            created_at: event.created_at,
+            reply_to: first_event_id(&event.tags),
+        })
+    }
+
+    /// Compose from the rumor of a gift wrap
+    ///
+    /// Return `None` if it's not a [`Kind::PrivateDirectMessage`] or if it has not a `p` tag.
+    pub fn from_rumor(rumor: &UnsignedEvent) -> Option<Self> {
+        if rumor.kind != Kind::PrivateDirectMessage {
+            return None;
+        }
+
+        Some(Self {
+            id: rumor.id,
+            kind: rumor.kind,
+            sender: rumor.pubkey,
+            receiver: first_public_key(&rumor.tags)?,
+            content: rumor.content.clone(),
+            created_at: rumor.created_at,
+            reply_to: first_event_id(&rumor.tags),
+        })
+    }
+
+    /// The other party of the conversation, from the point of view of `public_key`
+    pub fn counterparty(&self, public_key: &XOnlyPublicKey) -> XOnlyPublicKey {
+        if &self.sender == public_key {
+            self.receiver
+        } else {
+            self.sender
+        }
+    }
+}
+
+fn first_public_key(tags: &[Tag]) -> Option<XOnlyPublicKey> {
+    tags.iter().find_map(|tag| match tag {
+        Tag::PublicKey {
+            public_key,
+            uppercase: false,
+            ..
+        } => Some(*public_key),
+        _ => None,
+    })
+}
+
+fn first_event_id(tags: &[Tag]) -> Option<EventId> {
+    tags.iter().find_map(|tag| match tag {
+        Tag::Event { event_id, .. } => Some(*event_id),
+        _ => None,
+    })
+}
+
+/// Messages exchanged with a public key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conversation {
+    public_key: XOnlyPublicKey,
+    messages: Vec<DirectMessage>,
+    last_read: Option<Timestamp>,
+}
+
+impl Conversation {
+    fn new(public_key: XOnlyPublicKey) -> Self {
+        Self {
+            public_key,
+            messages: Vec::new(),
+            last_read: None,
+        }
+    }
+
+    /// Public key of the other party
+    pub fn public_key(&self) -> XOnlyPublicKey {
+        self.public_key
+    }
+
+    /// Messages (oldest first)
+    pub fn messages(&self) -> &[DirectMessage] {
+        &self.messages
+    }
+
+    /// Most recent message
+    pub fn last_message(&self) -> Option<&DirectMessage> {
+        self.messages.last()
+    }
+
+    /// Timestamp of the last read message
+    pub fn last_read(&self) -> Option<Timestamp> {
+        self.last_read
+    }
+
+    /// Number of messages received after the last read one
+    pub fn unread_count(&self) -> usize {
+        self.messages
+            .iter()
+            .filter(|m| m.sender == self.public_key)
+            .filter(|m| self.last_read.map_or(true, |t| m.created_at > t))
+            .count()
+    }
+
+    /// Insert keeping the messages ordered
+    ///
+    /// Return `false` if the message was already in the conversation.
+    fn insert(&mut self, message: DirectMessage) -> bool {
+        if self.messages.iter().any(|m| m.id == message.id) {
+            return false;
+        }
+        let index: usize = self
+            .messages
+            .partition_point(|m| (m.created_at, m.id) <= (message.created_at, message.id));
+        self.messages.insert(index, message);
+        true
+    }
+}
+
+/// Direct message conversations, grouped by counterparty
+///
+/// Filled by [`Client::sync_conversations`](super::Client::sync_conversations), by the messages sent with the client
+/// and, if [`Options::track_conversations`](super::Options::track_conversations) is enabled, by the received messages.
+/// The last-read timestamps are kept only in memory.
+#[derive(Debug, Clone)]
+pub struct Conversations {
+    conversations: Arc<RwLock<HashMap<XOnlyPublicKey, Conversation>>>,
+    sender: broadcast::Sender<DirectMessage>,
+}
+
+impl Conversations {
+    pub(crate) fn new() -> Self {
+        let (sender, ..) = broadcast::channel(CHANNEL_SIZE);
+        Self {
+            conversations: Arc::new(RwLock::new(HashMap::new())),
+            sender,
+        }
+    }
+
+    /// Add a message to the conversation with its counterparty, from the point of view of `public_key`
+    ///
+    /// Return `false` if the message was already known.
+    pub(crate) async fn insert(&self, public_key: &XOnlyPublicKey, message: DirectMessage) -> bool {
+        let counterparty: XOnlyPublicKey = message.counterparty(public_key);
+        let mut conversations = self.conversations.write().await;
+        let inserted: bool = conversations
+            .entry(counterparty)
+            .or_insert_with(|| Conversation::new(counterparty))
+            .insert(message.clone());
+        if inserted {
+            let _ = self.sender.send(message);
+        }
+        inserted
+    }
+
+    /// Remove all the conversations
+    pub(crate) async fn clear(&self) {
+        let mut conversations = self.conversations.write().await;
+        conversations.clear();
+    }
+
+    /// Get the conversations, most recent first
+    pub async fn list(&self) -> Vec<Conversation> {
+        let conversations = self.conversations.read().await;
+        let mut list: Vec<Conversation> = conversations.values().cloned().collect();
+        list.sort_by_key(|c| std::cmp::Reverse(c.last_message().map(|m| m.created_at)));
+        list
+    }
+
+    /// Get the conversation with `public_key`
+    pub async fn get(&self, public_key: &XOnlyPublicKey) -> Option<Conversation> {
+        let conversations = self.conversations.read().await;
+        conversations.get(public_key).cloned()
+    }
+
+    /// Mark the messages of the conversation with `public_key` as read
+    pub async fn mark_as_read(&self, public_key: &XOnlyPublicKey) {
+        let mut conversations = self.conversations.write().await;
+        if let Some(conversation) = conversations.get_mut(public_key) {
+            if let Some(last) = conversation.last_message().map(|m| m.created_at) {
+                conversation.last_read = Some(last);
+            }
+        }
+    }
+
+    /// Total number of unread messages
+    pub async fn unread_count(&self) -> usize {
+        let conversations = self.conversations.read().await;
+        conversations.values().map(|c| c.unread_count()).sum()
+    }
+
+    /// Stream of the new messages of the conversation with `public_key`
+    ///
+    /// Messages missed by a slow consumer are skipped: use [`Conversations::get`] to catch up.
+    pub fn stream(&self, public_key: XOnlyPublicKey) -> impl Stream<Item = DirectMessage> {
+        let receiver = self.sender.subscribe();
+        stream::unfold(receiver, move |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(message)
+                        if message.sender == public_key || message.receiver == public_key =>
+                    {
+                        return Some((message, receiver))
+                    }
+                    Ok(..) | Err(RecvError::Lagged(..)) => (),
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,27 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Drafts (NIP37)
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/37.md>
+
+use nostr::secp256k1::rand::{self, Rng};
+use nostr::{Timestamp, UnsignedEvent};
+
+/// Decrypted draft
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Draft {
+    /// Identifier (`d` tag of the draft event)
+    pub identifier: String,
+    /// Unsigned draft event
+    pub event: UnsignedEvent,
+    /// Last time the draft has been saved
+    pub updated_at: Timestamp,
+}
+
+/// Generate random draft identifier
+pub(crate) fn generate_identifier() -> String {
+    let n: u128 = rand::thread_rng().gen();
+    format!("{n:032x}")
+}
@@ -0,0 +1,111 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Feed
+
+use std::fmt;
+use std::sync::Arc;
+
+use nostr::{Event, Filter};
+
+/// Score the events of a [`Feed`]
+///
+/// Events with higher scores come first. Any `Fn(&Event) -> f64` closure is a ranker.
+pub trait EventRanker: Send + Sync {
+    /// Score of the [`Event`]
+    fn score(&self, event: &Event) -> f64;
+}
+
+impl<F> EventRanker for F
+where
+    F: Fn(&Event) -> f64 + Send + Sync,
+{
+    fn score(&self, event: &Event) -> f64 {
+        self(event)
+    }
+}
+
+/// Default [`EventRanker`]: newest events first
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChronologicalRanker;
+
+impl EventRanker for ChronologicalRanker {
+    fn score(&self, event: &Event) -> f64 {
+        event.created_at.as_u64() as f64
+    }
+}
+
+/// Events matching a set of filters, ordered by an [`EventRanker`]
+///
+/// # Example
+/// ```rust,no_run
+/// use std::collections::HashMap;
+///
+/// use nostr_sdk::prelude::*;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// # let client = Client::new(&Keys::generate());
+/// let reactions: HashMap<EventId, usize> = HashMap::new();
+/// let feed = Feed::new(vec![Filter::new().kind(Kind::TextNote).limit(100)])
+///     .ranker(move |event: &Event| *reactions.get(&event.id).unwrap_or(&0) as f64);
+/// let events = client.get_feed(&feed, None).await.unwrap();
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Feed {
+    filters: Vec<Filter>,
+    ranker: Arc<dyn EventRanker>,
+}
+
+impl fmt::Debug for Feed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Feed")
+            .field("filters", &self.filters)
+            .finish()
+    }
+}
+
+impl Feed {
+    /// New chronological feed
+    pub fn new(filters: Vec<Filter>) -> Self {
+        Self {
+            filters,
+            ranker: Arc::new(ChronologicalRanker),
+        }
+    }
+
+    /// Set a custom [`EventRanker`]
+    pub fn ranker<R>(self, ranker: R) -> Self
+    where
+        R: EventRanker + 'static,
+    {
+        Self {
+            ranker: Arc::new(ranker),
+            ..self
+        }
+    }
+
+    /// Get filters
+    pub fn filters(&self) -> Vec<Filter> {
+        self.filters.clone()
+    }
+
+    /// Order events by score (highest first)
+    ///
+    /// Events with the same score are ordered by `created_at` (newest first).
+    pub fn rank(&self, events: Vec<Event>) -> Vec<Event> {
+        let mut scored: Vec<(f64, Event)> = events
+            .into_iter()
+            .map(|event| (self.ranker.score(&event), event))
+            .collect();
+        scored.sort_by(|(a_score, a), (b_score, b)| {
+            b_score
+                .total_cmp(a_score)
+                .then_with(|| b.created_at.cmp(&a.created_at))
+                .then_with(|| b.id.cmp(&a.id))
+        });
+        scored.into_iter().map(|(_, event)| event).collect()
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Notification handler
+
+#[cfg(feature = "nip59")]
+use nostr::key::XOnlyPublicKey;
+use nostr::url::Url;
+#[cfg(feature = "nip59")]
+use nostr::UnsignedEvent;
+use nostr::{Event, RelayMessage, SubscriptionId};
+use nostr_database::{async_trait, AsyncTraitDeps};
+
+use crate::relay::{RelayPoolNotification, RelayStatus};
+
+/// Error returned by a [`NotificationHandler`]
+pub type HandlerError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Typed handler of the [`RelayPoolNotification`]s
+///
+/// Alternative to the closure of [`Client::handle_notifications`](super::Client::handle_notifications):
+/// implement only the methods of the notifications to handle.
+/// Every method (except [`NotificationHandler::on_shutdown`]) returns `true` to stop handling the notifications.
+///
+/// # Example
+/// ```rust,no_run
+/// use nostr_sdk::prelude::*;
+///
+/// #[derive(Debug)]
+/// struct Printer;
+///
+/// #[async_trait]
+/// impl NotificationHandler for Printer {
+///     async fn on_event(
+///         &self,
+///         relay_url: Url,
+///         _subscription_id: SubscriptionId,
+///         event: Event,
+///     ) -> Result<bool, HandlerError> {
+///         println!("{relay_url}: {}", event.content);
+///         Ok(false)
+///     }
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// # let client = Client::new(&Keys::generate());
+/// client.add_notification_handler(Printer);
+/// # }
+/// ```
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait NotificationHandler: AsyncTraitDeps {
+    /// Handle [`RelayPoolNotification::Event`]
+    async fn on_event(
+        &self,
+        _relay_url: Url,
+        _subscription_id: SubscriptionId,
+        _event: Event,
+    ) -> Result<bool, HandlerError> {
+        Ok(false)
+    }
+
+    /// Handle [`RelayPoolNotification::Message`]
+    async fn on_message(
+        &self,
+        _relay_url: Url,
+        _message: RelayMessage,
+    ) -> Result<bool, HandlerError> {
+        Ok(false)
+    }
+
+    /// Handle [`RelayPoolNotification::RelayStatus`]
+    async fn on_relay_status(
+        &self,
+        _relay_url: Url,
+        _status: RelayStatus,
+    ) -> Result<bool, HandlerError> {
+        Ok(false)
+    }
+
+    /// Handle [`RelayPoolNotification::PrivateMessage`]
+    #[cfg(feature = "nip59")]
+    async fn on_private_message(
+        &self,
+        _relay_url: Url,
+        _sender: XOnlyPublicKey,
+        _rumor: UnsignedEvent,
+    ) -> Result<bool, HandlerError> {
+        Ok(false)
+    }
+
+    /// Handle the other notifications (i.e. [`RelayPoolNotification::Lagged`])
+    async fn on_notification(
+        &self,
+        _notification: RelayPoolNotification,
+    ) -> Result<bool, HandlerError> {
+        Ok(false)
+    }
+
+    /// Called once, when the pool is shut down
+    async fn on_shutdown(&self) {}
+}
+
+/// Pass the notification to the matching method of the `handler`
+pub(crate) async fn dispatch<H>(
+    handler: &H,
+    notification: RelayPoolNotification,
+) -> Result<bool, HandlerError>
+where
+    H: NotificationHandler + ?Sized,
+{
+    match notification {
+        RelayPoolNotification::Event {
+            relay_url,
+            subscription_id,
+            event,
+            ..
+        } => handler.on_event(relay_url, subscription_id, event).await,
+        RelayPoolNotification::Message {
+            relay_url, message, ..
+        } => handler.on_message(relay_url, message).await,
+        RelayPoolNotification::RelayStatus { relay_url, status } => {
+            handler.on_relay_status(relay_url, status).await
+        }
+        #[cfg(feature = "nip59")]
+        RelayPoolNotification::PrivateMessage {
+            relay_url,
+            sender,
+            rumor,
+            ..
+        } => handler.on_private_message(relay_url, sender, rumor).await,
+        RelayPoolNotification::Shutdown => {
+            handler.on_shutdown().await;
+            Ok(true)
+        }
+        notification => handler.on_notification(notification).await,
+    }
+}
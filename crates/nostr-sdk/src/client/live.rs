@@ -0,0 +1,209 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Live activities (NIP53)
+
+use std::collections::VecDeque;
+
+use nostr::key::XOnlyPublicKey;
+use nostr::nips::nip01::Coordinate;
+use nostr::nips::nip53::{LiveEvent, LiveEventStatus};
+use nostr::{Alphabet, Event, Filter, Kind, Tag, Timestamp};
+use nostr_sdk_net::futures_util::stream::{self, Stream};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver;
+
+use crate::relay::pool::RelayPool;
+use crate::relay::{InternalSubscriptionId, RelayPoolNotification};
+
+/// Update of a watched live event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LiveEventUpdate {
+    /// Status changed
+    Status(LiveEventStatus),
+    /// Speakers, participants or number of participants changed
+    Participants {
+        /// Speakers
+        speakers: Vec<XOnlyPublicKey>,
+        /// Participants
+        participants: Vec<XOnlyPublicKey>,
+        /// Current number of participants
+        current_participants: Option<u64>,
+    },
+    /// Chat message ([`Kind::LiveEventMessage`])
+    Message(Event),
+}
+
+/// Watch the status and the chat of a live event
+///
+/// Created with [`Client::watch_live_event`](super::Client::watch_live_event).
+/// Call [`LiveEventWatcher::stop`] to close the subscription.
+#[derive(Debug)]
+pub struct LiveEventWatcher {
+    coordinate: Coordinate,
+    pool: RelayPool,
+    notifications: Receiver<RelayPoolNotification>,
+    live_event: Option<(Timestamp, LiveEvent)>,
+    pending: VecDeque<LiveEventUpdate>,
+}
+
+impl LiveEventWatcher {
+    pub(crate) fn new(
+        coordinate: Coordinate,
+        pool: RelayPool,
+        notifications: Receiver<RelayPoolNotification>,
+    ) -> Self {
+        Self {
+            coordinate,
+            pool,
+            notifications,
+            live_event: None,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Coordinate of the live event
+    pub fn coordinate(&self) -> &Coordinate {
+        &self.coordinate
+    }
+
+    /// Last received version of the live event
+    pub fn live_event(&self) -> Option<&LiveEvent> {
+        self.live_event.as_ref().map(|(_, e)| e)
+    }
+
+    /// Wait for the next update
+    ///
+    /// Return `None` when the pool is shutdown.
+    pub async fn next(&mut self) -> Option<LiveEventUpdate> {
+        loop {
+            if let Some(update) = self.pending.pop_front() {
+                return Some(update);
+            }
+
+            match self.notifications.recv().await {
+                Ok(RelayPoolNotification::Event { event, .. }) => self.handle_event(event),
+                Ok(RelayPoolNotification::Shutdown) | Err(RecvError::Closed) => return None,
+                Ok(..) => (),
+                Err(RecvError::Lagged(n)) => {
+                    tracing::warn!("Live event watcher missed {n} notifications")
+                }
+            }
+        }
+    }
+
+    /// Convert into a [`Stream`] of updates
+    pub fn into_stream(self) -> impl Stream<Item = LiveEventUpdate> {
+        stream::unfold(self, |mut watcher| async move {
+            let update: LiveEventUpdate = watcher.next().await?;
+            Some((update, watcher))
+        })
+    }
+
+    /// Close the subscription
+    pub async fn stop(self) {
+        let internal_id: InternalSubscriptionId = internal_id(&self.coordinate);
+        for relay in self.pool.relays().await.into_values() {
+            if let Err(e) = relay
+                .unsubscribe_with_internal_id(internal_id.clone(), None)
+                .await
+            {
+                tracing::debug!(
+                    "Impossible to unsubscribe {} from live event: {e}",
+                    relay.url()
+                );
+            }
+        }
+    }
+
+    /// Queue the updates of a live event or chat message
+    ///
+    /// Events not related to the watched live event are ignored.
+    pub(crate) fn handle_event(&mut self, event: Event) {
+        if event.kind == Kind::LiveEvent
+            && event.pubkey == self.coordinate.pubkey
+            && event.identifier() == Some(self.coordinate.identifier.as_str())
+        {
+            // Only newer versions
+            if let Some((created_at, ..)) = &self.live_event {
+                if event.created_at <= *created_at {
+                    return;
+                }
+            }
+
+            let live_event: LiveEvent = match LiveEvent::from_event(&event) {
+                Ok(live_event) => live_event,
+                Err(e) => {
+                    tracing::warn!("Invalid live event {}: {e}", event.id);
+                    return;
+                }
+            };
+
+            let previous: Option<&LiveEvent> = self.live_event.as_ref().map(|(_, e)| e);
+            if let Some(status) = &live_event.status {
+                if previous.and_then(|p| p.status.as_ref()) != Some(status) {
+                    self.pending
+                        .push_back(LiveEventUpdate::Status(status.clone()));
+                }
+            }
+
+            let update: LiveEventUpdate = participants_update(&live_event);
+            if previous.map(participants_update).as_ref() != Some(&update) {
+                self.pending.push_back(update);
+            }
+
+            self.live_event = Some((event.created_at, live_event));
+        } else if event.kind == Kind::LiveEventMessage && self.is_chat_message(&event) {
+            self.pending.push_back(LiveEventUpdate::Message(event));
+        }
+    }
+
+    fn is_chat_message(&self, event: &Event) -> bool {
+        event.tags.iter().any(|tag| match tag {
+            Tag::A {
+                kind,
+                public_key,
+                identifier,
+                ..
+            } => {
+                kind == &self.coordinate.kind
+                    && public_key == &self.coordinate.pubkey
+                    && identifier == &self.coordinate.identifier
+            }
+            _ => false,
+        })
+    }
+}
+
+fn participants_update(live_event: &LiveEvent) -> LiveEventUpdate {
+    LiveEventUpdate::Participants {
+        speakers: live_event.speakers.iter().map(|(p, ..)| *p).collect(),
+        participants: live_event.participants.iter().map(|(p, ..)| *p).collect(),
+        current_participants: live_event.current_participants,
+    }
+}
+
+/// Internal ID of the live event subscription
+pub(crate) fn internal_id(coordinate: &Coordinate) -> InternalSubscriptionId {
+    InternalSubscriptionId::Custom(format!("live:{}", a_tag_value(coordinate)))
+}
+
+/// Filters for the live event and its chat messages
+pub(crate) fn filters(coordinate: &Coordinate) -> Vec<Filter> {
+    vec![
+        Filter::from(coordinate.clone()),
+        Filter::new()
+            .kind(Kind::LiveEventMessage)
+            .custom_tag(Alphabet::A, [a_tag_value(coordinate)]),
+    ]
+}
+
+fn a_tag_value(coordinate: &Coordinate) -> String {
+    format!(
+        "{}:{}:{}",
+        coordinate.kind.as_u64(),
+        coordinate.pubkey,
+        coordinate.identifier
+    )
+}
@@ -4,45 +4,138 @@
 
 //! Client
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_utility::thread;
 use nostr::event::builder::Error as EventBuilderError;
+#[cfg(feature = "nip96")]
+use nostr::hashes::sha256::Hash as Sha256Hash;
+#[cfg(feature = "nip96")]
+use nostr::hashes::Hash;
 use nostr::key::XOnlyPublicKey;
-#[cfg(feature = "nip46")]
-use nostr::nips::nip46::{Request, Response};
+use nostr::nips::nip01::Coordinate;
+use nostr::nips::nip13;
+use nostr::nips::nip19::Nip19Event;
+use nostr::nips::nip21::{self, NostrURI};
+use nostr::nips::nip23::{self, ArticleBuilder};
+use nostr::nips::nip25::{Reaction, Reactions};
+use nostr::nips::nip38::{StatusType, UserStatus};
+#[cfg(feature = "nip47")]
+use nostr::nips::nip47::Method as NostrWalletConnectMethod;
+use nostr::nips::nip51::{self, List, ListItems};
+use nostr::nips::nip53;
+use nostr::nips::nip56::ReportTarget;
+#[cfg(feature = "nip57")]
+use nostr::nips::nip57::{self, ZapReceipt, ZapRequestData};
+use nostr::nips::nip58;
+#[cfg(feature = "nip59")]
+use nostr::nips::nip59::{self, UnwrappedGift};
+use nostr::nips::nip65;
+use nostr::nips::nip84::{Highlight, HighlightSource};
+use nostr::nips::nip89::HandlerRecommendation;
 use nostr::nips::nip94::FileMetadata;
+#[cfg(feature = "nip96")]
+use nostr::nips::nip98::HttpData;
 use nostr::types::metadata::Error as MetadataError;
+use nostr::types::time::Instant;
 use nostr::url::Url;
 use nostr::util::EventIdOrCoordinate;
+#[cfg(feature = "nip96")]
+use nostr::HttpMethod;
 use nostr::{
-    ClientMessage, Contact, Event, EventBuilder, EventId, Filter, JsonUtil, Keys, Kind, Metadata,
-    Result, Tag, Timestamp,
+    Alphabet, ClientMessage, Contact, Event, EventBuilder, EventId, Filter, GenericTagValue,
+    JsonUtil, Keys, Kind, Metadata, PageCursor, RelayMessage, RelayMetadata, Report, Result, Tag,
+    Timestamp, UncheckedUrl, UnsignedEvent,
 };
-use nostr_database::DynNostrDatabase;
+use nostr_database::{DynNostrDatabase, Order};
+use nostr_sdk_net::futures_util::future;
+use nostr_sdk_net::futures_util::stream::{self, Stream};
 use nostr_sdk_net::futures_util::Future;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::{broadcast, RwLock};
 
+pub mod accounts;
+pub mod app_handler;
+pub mod archive;
+pub mod badges;
 #[cfg(feature = "blocking")]
 pub mod blocking;
 pub mod builder;
+pub mod channel;
+pub mod conversations;
+#[cfg(feature = "nip44")]
+pub mod draft;
+pub mod feed;
+pub mod handler;
+pub mod live;
+pub mod mute;
+#[cfg(feature = "nip47")]
+pub mod nwc;
 pub mod options;
+pub mod outbox;
+pub mod rebroadcast;
+pub mod relay_list;
+pub mod reply_tree;
+pub mod scheduler;
+pub mod search;
 pub mod signer;
+#[cfg(feature = "nip96")]
+pub mod uploader;
+pub mod wait;
+#[cfg(feature = "nip57")]
+pub mod zapper;
 
+pub use self::accounts::{account_label, Accounts};
+pub use self::app_handler::AppHandler;
+pub use self::archive::{ArchiveReport, RelayArchiveCoverage};
+pub use self::badges::Badge;
 pub use self::builder::ClientBuilder;
+pub use self::channel::Channel;
+pub use self::conversations::{Conversation, Conversations, DirectMessage};
+#[cfg(feature = "nip44")]
+pub use self::draft::Draft;
+pub use self::feed::{ChronologicalRanker, EventRanker, Feed};
+pub use self::handler::{HandlerError, NotificationHandler};
+pub use self::live::{LiveEventUpdate, LiveEventWatcher};
+pub use self::mute::{DefaultMutedWordMatcher, MuteList, MutedWordMatcher};
+#[cfg(feature = "nip47")]
+pub use self::nwc::{Error as NWCError, NWC};
 pub use self::options::Options;
+pub use self::outbox::Outbox;
+pub use self::rebroadcast::RelayRebroadcast;
+use self::relay_list::RelayListSync;
+pub use self::relay_list::RELAY_LIST_LABEL;
+pub use self::reply_tree::{Thread, ThreadNode};
+pub use self::scheduler::ScheduledEvent;
+use self::scheduler::Scheduler;
+pub use self::search::SearchResult;
 #[cfg(feature = "nip46")]
-pub use self::signer::nip46::Nip46Signer;
-pub use self::signer::{ClientSigner, ClientSignerType};
+pub use self::signer::nip46::{
+    AutoApprovePolicy, Nip46Signer, NostrConnectApproval, NostrConnectRemoteSigner,
+};
+pub use self::signer::{ClientSigner, ClientSignerType, NostrSigner};
+#[cfg(feature = "nip96")]
+pub use self::uploader::Error as UploaderError;
+pub use self::wait::SendAndWaitOptions;
+#[cfg(feature = "nip57")]
+pub use self::zapper::{Error as ZapperError, ZapEntity, Zaps};
 use crate::relay::pool::{self, Error as RelayPoolError, RelayPool};
+#[cfg(feature = "nip11")]
+use crate::relay::NIP_SEARCH;
 use crate::relay::{
-    FilterOptions, NegentropyOptions, Relay, RelayOptions, RelayPoolNotification, RelaySendOptions,
+    ConnectReport, CountReport, Error as RelayError, FilterOptions, InternalSubscriptionId,
+    Middleware, MiddlewareError, Moderation, NegentropyOptions, NotificationHub, NotificationQueue,
+    Quarantine, QueuedMessage, Relay, RelayOptions, RelayPoolNotification, RelaySendOptions,
+    RelayStatus, SeenTracker, ShutdownReport,
 };
 use crate::util::TryIntoUrl;
 
+/// Max number of requests to follow the ancestors of a thread
+const MAX_THREAD_ROUNDS: usize = 8;
+
 /// [`Client`] error
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -76,6 +169,9 @@ pub enum Error {
     /// Signer not configured
     #[error("signer not configured")]
     SignerNotConfigured,
+    /// Method not supported by the signer
+    #[error("signer doesn't support `{0}`")]
+    UnsupportedSignerMethod(&'static str),
     /// Signer not configured
     #[error("wrong signer: expected={expected}, found={found}")]
     WrongSigner {
@@ -88,6 +184,10 @@ pub enum Error {
     #[cfg(feature = "nip04")]
     #[error(transparent)]
     NIP04(#[from] nostr::nips::nip04::Error),
+    /// NIP44 error
+    #[cfg(feature = "nip44")]
+    #[error(transparent)]
+    NIP44(#[from] nostr::nips::nip44::Error),
     /// NIP07 error
     #[cfg(all(feature = "nip07", target_arch = "wasm32"))]
     #[error(transparent)]
@@ -120,6 +220,68 @@ pub enum Error {
     #[cfg(feature = "nip46")]
     #[error("response not match to the request")]
     ResponseNotMatchRequest,
+    /// Not a draft event, or draft not authored by the signer
+    #[cfg(feature = "nip44")]
+    #[error("invalid draft")]
+    InvalidDraft,
+    /// NIP47 error
+    #[cfg(feature = "nip47")]
+    #[error(transparent)]
+    NIP47(#[from] nostr::nips::nip47::Error),
+    /// Nostr Wallet Connect error
+    #[cfg(feature = "nip47")]
+    #[error(transparent)]
+    NWC(#[from] NWCError),
+    /// Zapper error
+    #[cfg(feature = "nip57")]
+    #[error(transparent)]
+    Zapper(#[from] ZapperError),
+    /// Uploader error
+    #[cfg(feature = "nip96")]
+    #[error(transparent)]
+    Uploader(#[from] UploaderError),
+    /// NIP59 error
+    #[cfg(feature = "nip59")]
+    #[error(transparent)]
+    NIP59(#[from] nip59::Error),
+    /// NIP21 error
+    #[error(transparent)]
+    NIP21(#[from] nip21::Error),
+    /// NIP23 error
+    #[error(transparent)]
+    NIP23(#[from] nip23::Error),
+    /// NIP51 error
+    #[error(transparent)]
+    NIP51(#[from] nip51::Error),
+    /// NIP53 error
+    #[error(transparent)]
+    NIP53(#[from] nip53::Error),
+    /// NIP58 error
+    #[error(transparent)]
+    NIP58(#[from] nip58::Error),
+    /// Event not found
+    #[error("event not found: {0}")]
+    EventNotFound(EventId),
+    /// Event accepted but not returned by enough relays
+    #[error("event not returned by the relays: {0}")]
+    EventNotReturned(EventId),
+    /// Account not found
+    #[error("account not found: {0}")]
+    AccountNotFound(XOnlyPublicKey),
+    /// None of the relays supports search (NIP50)
+    #[error("no relays supporting search (NIP50)")]
+    SearchNotSupported,
+    /// Sending aborted by a [`Middleware`]
+    #[error("middleware error: {0}")]
+    Middleware(MiddlewareError),
+    /// A contact list newer than the last fetched one has been published
+    #[error("contact list conflict: local={local}, remote={remote}")]
+    ContactListConflict {
+        /// Creation timestamp of the last contact list fetched or published by this client
+        local: Timestamp,
+        /// Creation timestamp of the newest contact list found on relays
+        remote: Timestamp,
+    },
 }
 
 /// Nostr client
@@ -127,7 +289,16 @@ pub enum Error {
 pub struct Client {
     pool: RelayPool,
     signer: Arc<RwLock<Option<ClientSigner>>>,
+    auth_signer: Arc<RwLock<Option<ClientSigner>>>,
     opts: Options,
+    mute_list: Arc<RwLock<MuteList>>,
+    contact_list_timestamp: Arc<RwLock<Option<Timestamp>>>,
+    metadata_fetched_at: Arc<RwLock<HashMap<XOnlyPublicKey, Instant>>>,
+    relay_list_sync: RelayListSync,
+    conversations: Conversations,
+    outbox: Outbox,
+    accounts: Accounts,
+    scheduler: Scheduler,
     dropped: Arc<AtomicBool>,
 }
 
@@ -197,14 +368,280 @@ impl Client {
         ClientBuilder::new().signer(signer).opts(opts).build()
     }
 
+    /// New [`ClientBuilder`]
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// let my_keys = Keys::generate();
+    /// let client = Client::builder().signer(&my_keys).build();
+    /// ```
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
     /// Compose [`Client`] from [`ClientBuilder`]
     pub fn from_builder(builder: ClientBuilder) -> Self {
-        Self {
-            pool: RelayPool::with_database(builder.opts.pool, builder.database),
-            signer: Arc::new(RwLock::new(builder.signer)),
+        #[cfg(feature = "nip46")]
+        let signer: Option<ClientSigner> = match builder.signer {
+            Some(ClientSigner::NIP46(nip46)) => Some(ClientSigner::NIP46(
+                nip46.timeout(builder.opts.nip46_timeout),
+            )),
+            signer => signer,
+        };
+        #[cfg(not(feature = "nip46"))]
+        let signer: Option<ClientSigner> = builder.signer;
+
+        let pool = RelayPool::with_database(builder.opts.pool.clone(), builder.database);
+        let relay_list_sync = RelayListSync::new(
+            pool.clone(),
+            #[cfg(not(target_arch = "wasm32"))]
+            builder.opts.proxy,
+        );
+        let outbox = Outbox::new(pool.database());
+        let client = Self {
+            pool,
+            signer: Arc::new(RwLock::new(signer)),
+            auth_signer: Arc::new(RwLock::new(builder.opts.get_auth_signer())),
             opts: builder.opts,
+            mute_list: Arc::new(RwLock::new(MuteList::new())),
+            contact_list_timestamp: Arc::new(RwLock::new(None)),
+            metadata_fetched_at: Arc::new(RwLock::new(HashMap::new())),
+            relay_list_sync,
+            conversations: Conversations::new(),
+            outbox,
+            accounts: Accounts::new(),
+            scheduler: Scheduler::new(),
             dropped: Arc::new(AtomicBool::new(false)),
+        };
+
+        if client.opts.get_automatic_authentication() {
+            client.spawn_authenticator();
+        }
+
+        #[cfg(feature = "nip59")]
+        if client.opts.get_unwrap_gift_wraps() {
+            client.spawn_gift_unwrapper();
+        }
+
+        if client.opts.get_sync_relay_list() {
+            client.spawn_relay_list_sync();
+        }
+
+        if client.opts.get_track_conversations() {
+            client.spawn_conversation_tracker();
+        }
+
+        if client.opts.get_outbox() {
+            client.spawn_outbox_flusher();
         }
+
+        client
+    }
+
+    /// Send the pending events of the outbox to the relays that connect, until the pool is shutdown
+    fn spawn_outbox_flusher(&self) {
+        let pool: RelayPool = self.pool.clone();
+        let outbox: Outbox = self.outbox.clone();
+        let opts = RelaySendOptions::new().timeout(self.opts.send_timeout);
+        thread::spawn(async move {
+            tracing::debug!("Outbox flusher thread started");
+            let mut notifications = pool.notifications();
+            let loaded: usize = outbox.load().await;
+            if loaded > 0 {
+                tracing::info!("Loaded {loaded} pending events into the outbox");
+            }
+            loop {
+                match notifications.recv().await {
+                    Ok(RelayPoolNotification::RelayStatus {
+                        relay_url,
+                        status: RelayStatus::Connected,
+                    }) => {
+                        if !outbox.is_empty().await {
+                            outbox.flush(&pool, relay_url, opts).await;
+                        }
+                    }
+                    Ok(RelayPoolNotification::Shutdown) | Err(RecvError::Closed) => break,
+                    Ok(..) | Err(RecvError::Lagged(..)) => (),
+                }
+            }
+            tracing::debug!("Exited from outbox flusher thread");
+        });
+    }
+
+    /// Apply the relay lists published by the client signer to the pool, until the pool is shutdown
+    fn spawn_relay_list_sync(&self) {
+        let pool: RelayPool = self.pool.clone();
+        let signer = self.signer.clone();
+        let relay_list_sync: RelayListSync = self.relay_list_sync.clone();
+        thread::spawn(async move {
+            tracing::debug!("Relay list sync thread started");
+            let mut notifications = pool.notifications();
+            loop {
+                match notifications.recv().await {
+                    Ok(RelayPoolNotification::Event { event, .. })
+                        if event.kind == Kind::RelayList =>
+                    {
+                        let public_key: XOnlyPublicKey = match signer.read().await.as_ref() {
+                            Some(signer) => match signer.get_public_key().await {
+                                Ok(public_key) => public_key,
+                                Err(e) => {
+                                    tracing::error!("Impossible to get signer public key: {e}");
+                                    continue;
+                                }
+                            },
+                            None => continue,
+                        };
+                        if event.pubkey != public_key {
+                            continue;
+                        }
+
+                        if let Err(e) = relay_list_sync.apply(&event).await {
+                            tracing::error!("Impossible to apply relay list {}: {e}", event.id);
+                        }
+                    }
+                    Ok(RelayPoolNotification::Shutdown) | Err(RecvError::Closed) => break,
+                    Ok(..) | Err(RecvError::Lagged(..)) => (),
+                }
+            }
+            tracing::debug!("Exited from relay list sync thread");
+        });
+    }
+
+    /// Add the received direct messages to the conversations, until the pool is shutdown
+    fn spawn_conversation_tracker(&self) {
+        let pool: RelayPool = self.pool.clone();
+        let signer = self.signer.clone();
+        let conversations: Conversations = self.conversations.clone();
+        thread::spawn(async move {
+            tracing::debug!("Conversation tracker thread started");
+            let mut notifications = pool.notifications();
+            loop {
+                let notification: RelayPoolNotification = match notifications.recv().await {
+                    Ok(RelayPoolNotification::Shutdown) | Err(RecvError::Closed) => break,
+                    Ok(notification) => notification,
+                    Err(RecvError::Lagged(..)) => continue,
+                };
+
+                let signer: ClientSigner = match signer.read().await.clone() {
+                    Some(signer) => signer,
+                    None => continue,
+                };
+                let public_key: XOnlyPublicKey = match signer.get_public_key().await {
+                    Ok(public_key) => public_key,
+                    Err(e) => {
+                        tracing::error!("Impossible to get signer public key: {e}");
+                        continue;
+                    }
+                };
+
+                let message: Option<DirectMessage> = match notification {
+                    #[cfg(feature = "nip04")]
+                    RelayPoolNotification::Event { event, .. }
+                        if event.kind == Kind::EncryptedDirectMessage =>
+                    {
+                        match decrypt_direct_msg(&signer, public_key, &event).await {
+                            Ok(message) => message,
+                            Err(e) => {
+                                tracing::warn!(
+                                    "Impossible to decrypt direct message {}: {e}",
+                                    event.id
+                                );
+                                None
+                            }
+                        }
+                    }
+                    #[cfg(feature = "nip59")]
+                    RelayPoolNotification::PrivateMessage { rumor, .. } => {
+                        DirectMessage::from_rumor(&rumor)
+                    }
+                    _ => None,
+                };
+
+                if let Some(message) = message {
+                    conversations.insert(&public_key, message).await;
+                }
+            }
+            tracing::debug!("Exited from conversation tracker thread");
+        });
+    }
+
+    /// Reply to the `AUTH` challenges of the relays, until the pool is shutdown
+    fn spawn_authenticator(&self) {
+        let pool: RelayPool = self.pool.clone();
+        let signer = self.signer.clone();
+        let auth_signer = self.auth_signer.clone();
+        let wait: Option<Duration> = self.opts.send_timeout;
+        thread::spawn(async move {
+            tracing::debug!("Automatic authentication thread started");
+            let mut notifications = pool.notifications();
+            loop {
+                match notifications.recv().await {
+                    Ok(RelayPoolNotification::Message {
+                        relay_url,
+                        message: RelayMessage::Auth { challenge },
+                        ..
+                    }) => {
+                        authenticate(&pool, &auth_signer, &signer, relay_url, challenge, wait).await
+                    }
+                    Ok(RelayPoolNotification::Shutdown) | Err(RecvError::Closed) => break,
+                    Ok(..) | Err(RecvError::Lagged(..)) => (),
+                }
+            }
+            tracing::debug!("Exited from automatic authentication thread");
+        });
+    }
+
+    /// Unwrap the gift wraps addressed to the client signer and notify the rumors, until the pool is shutdown
+    #[cfg(feature = "nip59")]
+    fn spawn_gift_unwrapper(&self) {
+        let pool: RelayPool = self.pool.clone();
+        let signer = self.signer.clone();
+        thread::spawn(async move {
+            tracing::debug!("Gift unwrapper thread started");
+            let notifier = pool.notification_hub().sender();
+            let mut notifications = pool.notifications();
+            loop {
+                match notifications.recv().await {
+                    Ok(RelayPoolNotification::Event {
+                        relay_url, event, ..
+                    }) if event.kind == Kind::GiftWrap => {
+                        let signer: ClientSigner = match signer.read().await.clone() {
+                            Some(signer) => signer,
+                            None => continue,
+                        };
+                        let public_key: XOnlyPublicKey = match signer.get_public_key().await {
+                            Ok(public_key) => public_key,
+                            Err(e) => {
+                                tracing::error!("Impossible to get signer public key: {e}");
+                                continue;
+                            }
+                        };
+                        if !event.public_keys().any(|p| p == &public_key) {
+                            continue;
+                        }
+
+                        match unwrap_gift_wrap(&signer, &event).await {
+                            Ok(UnwrappedGift { sender, rumor }) => {
+                                let _ = notifier.send(RelayPoolNotification::PrivateMessage {
+                                    relay_url,
+                                    gift_wrap_id: event.id,
+                                    sender,
+                                    rumor,
+                                });
+                            }
+                            Err(e) => {
+                                tracing::warn!("Impossible to unwrap gift wrap {}: {e}", event.id)
+                            }
+                        }
+                    }
+                    Ok(RelayPoolNotification::Shutdown) | Err(RecvError::Closed) => break,
+                    Ok(..) | Err(RecvError::Lagged(..)) => (),
+                }
+            }
+            tracing::debug!("Exited from gift unwrapper thread");
+        });
     }
 
     /// Update default difficulty for new [`Event`]
@@ -221,8 +658,113 @@ impl Client {
     }
 
     /// Set client signer
+    ///
+    /// When the public key changes, the state of the previous identity is dropped:
+    /// * the synced mute list, contact list and relay list, and the conversations, are cleared;
+    /// * the subscriptions with the previous public key in `authors` or in the `#p` tags are updated with the new one;
+    /// * if [`Options::automatic_authentication`] is enabled and an auth signer is not set,
+    ///   the relays that sent an `AUTH` challenge are authenticated again.
     pub async fn set_signer(&self, signer: Option<ClientSigner>) {
-        let mut s = self.signer.write().await;
+        let previous: Option<ClientSigner> = {
+            let mut s = self.signer.write().await;
+            std::mem::replace(&mut *s, signer.clone())
+        };
+
+        let previous: Option<XOnlyPublicKey> = match previous {
+            Some(previous) => previous.get_public_key().await.ok(),
+            None => None,
+        };
+        let current: Option<XOnlyPublicKey> = match signer {
+            Some(signer) => signer.get_public_key().await.ok(),
+            None => None,
+        };
+        if previous == current {
+            return;
+        }
+
+        self.clear_identity_state().await;
+
+        if let (Some(previous), Some(current)) = (previous, current) {
+            self.replace_subscribed_public_key(&previous, current).await;
+        }
+
+        if current.is_some()
+            && self.opts.get_automatic_authentication()
+            && self.auth_signer.read().await.is_none()
+        {
+            let wait: Option<Duration> = self.opts.send_timeout;
+            for (relay_url, relay) in self.pool.relays().await.into_iter() {
+                if let Some(challenge) = relay.auth_challenge().await {
+                    authenticate(
+                        &self.pool,
+                        &self.auth_signer,
+                        &self.signer,
+                        relay_url,
+                        challenge,
+                        wait,
+                    )
+                    .await;
+                }
+            }
+        }
+    }
+
+    /// Clear the state synced for the signer
+    async fn clear_identity_state(&self) {
+        let mut mute_list = self.mute_list.write().await;
+        let matcher = mute_list.get_matcher();
+        *mute_list = MuteList::new();
+        mute_list.set_matcher(matcher);
+        drop(mute_list);
+
+        let mut timestamp = self.contact_list_timestamp.write().await;
+        *timestamp = None;
+        drop(timestamp);
+
+        self.relay_list_sync.reset().await;
+        self.conversations.clear().await;
+    }
+
+    /// Update the subscriptions referencing `previous`, in `authors` or in the `#p` tags
+    async fn replace_subscribed_public_key(
+        &self,
+        previous: &XOnlyPublicKey,
+        current: XOnlyPublicKey,
+    ) {
+        let mut filters: Vec<Filter> = self.pool.subscription_filters().await;
+        if replace_public_key(&mut filters, previous, current) {
+            self.pool.update_subscription_filters(filters).await;
+        }
+
+        let wait: Option<Duration> = if self.opts.get_wait_for_subscription() {
+            self.opts.send_timeout
+        } else {
+            None
+        };
+        for (relay_url, relay) in self.pool.relays().await.into_iter() {
+            for (internal_id, subscription) in relay.subscriptions().await.into_iter() {
+                let mut filters: Vec<Filter> = subscription.filters();
+                if replace_public_key(&mut filters, previous, current) {
+                    if let Err(e) = relay.update_subscription(internal_id, filters, wait).await {
+                        tracing::error!("Impossible to update subscription of {relay_url}: {e}");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Get signer used for NIP42 authentication
+    ///
+    /// Fallback to the client signer if an auth signer is not set (see [`Options::auth_signer`]).
+    pub async fn auth_signer(&self) -> Result<ClientSigner, Error> {
+        get_auth_signer(&self.auth_signer, &self.signer).await
+    }
+
+    /// Set signer used for NIP42 authentication
+    ///
+    /// Pass `None` to authenticate with the client signer.
+    pub async fn set_auth_signer(&self, signer: Option<ClientSigner>) {
+        let mut s = self.auth_signer.write().await;
         *s = signer;
     }
 
@@ -244,6 +786,157 @@ impl Client {
             .await;
     }
 
+    /// Get the [`Accounts`]
+    pub fn accounts(&self) -> Accounts {
+        self.accounts.clone()
+    }
+
+    /// Add an account
+    ///
+    /// The client signer is not changed: use [`Client::switch_account`] to make the account active.
+    /// Return the public key of the account.
+    pub async fn add_account<S>(&self, signer: S) -> Result<XOnlyPublicKey, Error>
+    where
+        S: Into<ClientSigner>,
+    {
+        let signer: ClientSigner = signer.into();
+        let public_key: XOnlyPublicKey = signer.get_public_key().await?;
+        self.accounts.insert(public_key, signer).await;
+        Ok(public_key)
+    }
+
+    /// Remove an account
+    ///
+    /// The relays of the account are unsubscribed from the filters set with [`Client::subscribe_as`] and unlabeled,
+    /// but not removed. If the account is active, the client signer is not changed.
+    ///
+    /// Return `false` if the account doesn't exist.
+    pub async fn remove_account(&self, public_key: &XOnlyPublicKey) -> bool {
+        if self.accounts.remove(public_key).await.is_none() {
+            return false;
+        }
+
+        let label: String = account_label(public_key);
+        self.unsubscribe_from_label(&label).await;
+        for relay in self.pool.relays_with_label(&label).await.into_values() {
+            relay.opts().remove_label(&label);
+        }
+        true
+    }
+
+    /// Make the account active, setting its signer as client signer (see [`Client::set_signer`])
+    pub async fn switch_account(&self, public_key: &XOnlyPublicKey) -> Result<(), Error> {
+        let signer: ClientSigner = self
+            .accounts
+            .get(public_key)
+            .await
+            .ok_or(Error::AccountNotFound(*public_key))?;
+        self.set_signer(Some(signer)).await;
+        Ok(())
+    }
+
+    /// Get the public key of the active account (the client signer)
+    pub async fn active_account(&self) -> Option<XOnlyPublicKey> {
+        match self.signer.read().await.as_ref() {
+            Some(signer) => signer.get_public_key().await.ok(),
+            None => None,
+        }
+    }
+
+    /// Add a relay to the relays of the account
+    ///
+    /// The relay is added to the pool, if missing, and labeled with [`account_label`].
+    /// This method **NOT** automatically start connection with the relay!
+    pub async fn add_account_relay<U>(
+        &self,
+        public_key: &XOnlyPublicKey,
+        url: U,
+    ) -> Result<(), Error>
+    where
+        U: TryIntoUrl,
+        pool::Error: From<<U as TryIntoUrl>::Err>,
+    {
+        if !self.accounts.contains(public_key).await {
+            return Err(Error::AccountNotFound(*public_key));
+        }
+
+        let url: Url = url.try_into_url().map_err(pool::Error::from)?;
+        self.add_labeled_relay(url, account_label(public_key)).await
+    }
+
+    async fn add_labeled_relay(&self, url: Url, label: String) -> Result<(), Error> {
+        self.add_relay(url.clone()).await?;
+        let relay: Relay = self.relay(url).await?;
+        relay.opts().add_label(label);
+        Ok(())
+    }
+
+    /// Subscribe the relays of the account to filters
+    pub async fn subscribe_as(
+        &self,
+        public_key: &XOnlyPublicKey,
+        filters: Vec<Filter>,
+    ) -> Result<(), Error> {
+        if !self.accounts.contains(public_key).await {
+            return Err(Error::AccountNotFound(*public_key));
+        }
+        self.subscribe_to_label(&account_label(public_key), filters)
+            .await
+    }
+
+    /// Take an [`EventBuilder`], sign it with the signer of the account and send it to the relays of the account
+    ///
+    /// If the account has no relays, the event is sent to all relays.
+    pub async fn send_event_builder_as(
+        &self,
+        public_key: &XOnlyPublicKey,
+        builder: EventBuilder,
+    ) -> Result<EventId, Error> {
+        let signer: ClientSigner = self
+            .accounts
+            .get(public_key)
+            .await
+            .ok_or(Error::AccountNotFound(*public_key))?;
+        let event: Event = self.sign_event_builder_with(&signer, builder).await?;
+
+        let label: String = account_label(public_key);
+        if self.pool.relays_with_label(&label).await.is_empty() {
+            return self.send_event(event).await;
+        }
+
+        let event_id: EventId = event.id;
+        let results = self.send_event_to_label(&label, event).await?;
+        if results.values().any(|res| res.is_ok()) {
+            Ok(event_id)
+        } else {
+            Err(Error::RelayPool(RelayPoolError::EventNotPublished(
+                event_id,
+            )))
+        }
+    }
+
+    /// Get events of filters from the relays of the account
+    ///
+    /// If the account has no relays, the events are fetched from all relays.
+    /// If timeout is set to `None`, the default from [`Options`] will be used.
+    pub async fn get_events_of_as(
+        &self,
+        public_key: &XOnlyPublicKey,
+        filters: Vec<Filter>,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<Event>, Error> {
+        if !self.accounts.contains(public_key).await {
+            return Err(Error::AccountNotFound(*public_key));
+        }
+
+        let label: String = account_label(public_key);
+        if self.pool.relays_with_label(&label).await.is_empty() {
+            self.get_events_of(filters, timeout).await
+        } else {
+            self.get_events_from_label(&label, filters, timeout).await
+        }
+    }
+
     /// Get [`RelayPool`]
     pub fn pool(&self) -> RelayPool {
         self.pool.clone()
@@ -277,16 +970,116 @@ impl Client {
         Ok(self.pool.clone().shutdown().await?)
     }
 
+    /// Completely shutdown [`Client`], waiting up to `timeout` for pending messages to be flushed
+    ///
+    /// Return the messages that couldn't be flushed.
+    pub async fn shutdown_with_timeout(self, timeout: Duration) -> Result<ShutdownReport, Error> {
+        Ok(self.pool.clone().shutdown_with_timeout(timeout).await?)
+    }
+
     /// Get new notification listener
     pub fn notifications(&self) -> broadcast::Receiver<RelayPoolNotification> {
         self.pool.notifications()
     }
 
+    /// Get new notification listener that never lags (see [`NotificationQueue`])
+    pub fn notification_queue(&self) -> NotificationQueue {
+        self.pool.notification_queue()
+    }
+
+    /// Get [`NotificationHub`] of the pool
+    ///
+    /// Share it with another component through [`RelayPoolOptions::notification_hub`](crate::RelayPoolOptions::notification_hub).
+    pub fn notification_hub(&self) -> NotificationHub {
+        self.pool.notification_hub()
+    }
+
+    /// Get [`SeenTracker`] of the pool
+    ///
+    /// Share it with another component through [`RelayPoolOptions::seen_tracker`](crate::RelayPoolOptions::seen_tracker),
+    /// or use it to skip the events already notified by this client.
+    pub fn seen_tracker(&self) -> SeenTracker {
+        self.pool.seen_tracker()
+    }
+
+    /// Get the [`Quarantine`] of the events failing the id or signature verification
+    ///
+    /// Enable it with [`RelayPoolOptions::quarantine`](crate::RelayPoolOptions::quarantine).
+    pub fn quarantine(&self) -> Quarantine {
+        self.pool.quarantine()
+    }
+
+    /// Get the [`Moderation`] of the received events
+    ///
+    /// The events dropped by the moderation filters are not saved into the database nor notified.
+    pub fn moderation(&self) -> Moderation {
+        self.pool.moderation()
+    }
+
+    /// Add a [`Middleware`]
+    ///
+    /// Middlewares are called in registration order: for the outgoing events,
+    /// each one receives the event edited by the previous ones.
+    pub async fn add_middleware<M>(&self, middleware: M)
+    where
+        M: Middleware + 'static,
+    {
+        self.pool.middlewares().push(Arc::new(middleware)).await;
+    }
+
+    /// Remove all the middlewares
+    pub async fn clear_middlewares(&self) {
+        self.pool.middlewares().clear().await;
+    }
+
     /// Get relays
     pub async fn relays(&self) -> HashMap<Url, Relay> {
         self.pool.relays().await
     }
 
+    /// Get relays with the `label` (see [`RelayOptions::labels`])
+    pub async fn relays_with_label(&self, label: &str) -> HashMap<Url, Relay> {
+        self.pool.relays_with_label(label).await
+    }
+
+    /// Get the messages waiting in the outgoing queue of every relay
+    pub async fn queued_messages(&self) -> HashMap<Url, Vec<QueuedMessage>> {
+        self.pool.queued_messages().await
+    }
+
+    /// Cancel the event, if not yet sent (i.e. "undo send")
+    ///
+    /// Return the relays from which the event has been removed before being sent.
+    /// To have a grace window, see [`RelayOptions::send_delay`].
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let my_keys = Keys::generate();
+    /// let client = Client::with_opts(&my_keys, Options::new().wait_for_send(false));
+    /// let opts = RelayOptions::new().send_delay(Some(Duration::from_secs(5)));
+    /// client
+    ///     .add_relay_with_opts("wss://relay.damus.io", opts)
+    ///     .await
+    ///     .unwrap();
+    /// client.connect().await;
+    ///
+    /// let event = EventBuilder::new_text_note("Oops", [])
+    ///     .to_event(&my_keys)
+    ///     .unwrap();
+    /// client.send_msg(ClientMessage::new_event(event.clone())).await.unwrap();
+    /// let _relays = client.cancel_queued_event(event.id).await;
+    /// # }
+    /// ```
+    pub async fn cancel_queued_event(&self, event_id: EventId) -> HashSet<Url> {
+        self.pool.cancel_queued_event(event_id).await
+    }
+
     /// Get a previously added [`Relay`]
     pub async fn relay<U>(&self, url: U) -> Result<Relay, Error>
     where
@@ -401,6 +1194,30 @@ impl Client {
         Ok(())
     }
 
+    /// Disconnect and remove all relays
+    pub async fn remove_all_relays(&self) -> Result<(), Error> {
+        Ok(self.pool.remove_all_relays().await?)
+    }
+
+    /// Set relays
+    ///
+    /// Relays not in the list are disconnected and removed, the missing ones are added.
+    /// Relays already in the pool keep their connection.
+    ///
+    /// This method **NOT** automatically start connection with new relays!
+    pub async fn set_relays<I, U>(&self, relays: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = U>,
+        U: TryIntoUrl,
+        pool::Error: From<<U as TryIntoUrl>::Err>,
+    {
+        #[cfg(not(target_arch = "wasm32"))]
+        let opts: RelayOptions = RelayOptions::new().proxy(self.opts.proxy);
+        #[cfg(target_arch = "wasm32")]
+        let opts: RelayOptions = RelayOptions::new();
+        Ok(self.pool.set_relays(relays, opts).await?)
+    }
+
     /// Connect to a previously added relay
     ///
     /// # Example
@@ -457,6 +1274,9 @@ impl Client {
 
     /// Connect relays
     ///
+    /// Return the [`ConnectReport`]: if [`Options::wait_for_connection`] is not enabled,
+    /// the relays are usually still pending.
+    ///
     /// # Example
     /// ```rust,no_run
     /// use nostr_sdk::prelude::*;
@@ -468,28 +1288,50 @@ impl Client {
     /// client.connect().await;
     /// # }
     /// ```
-    pub async fn connect(&self) {
-        self.pool.connect(self.opts.wait_for_connection).await;
+    pub async fn connect(&self) -> ConnectReport {
+        self.pool.connect(self.opts.wait_for_connection).await
     }
 
-    /// Disconnect from all relays
+    /// Connect relays, waiting up to `timeout` for the connections
+    ///
+    /// The relays not connected before the `timeout` keep connecting in background.
     ///
     /// # Example
     /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
     /// use nostr_sdk::prelude::*;
     ///
     /// # #[tokio::main]
     /// # async fn main() {
     /// #   let my_keys = Keys::generate();
     /// #   let client = Client::new(&my_keys);
-    /// client.disconnect().await.unwrap();
+    /// let report = client.connect_with_timeout(Duration::from_secs(10)).await;
+    /// println!("Connected to {} relays", report.connected.len());
     /// # }
     /// ```
-    pub async fn disconnect(&self) -> Result<(), Error> {
-        Ok(self.pool.disconnect().await?)
+    pub async fn connect_with_timeout(&self, timeout: Duration) -> ConnectReport {
+        self.pool.connect_with_timeout(timeout).await
     }
 
-    /// Subscribe to filters
+    /// Disconnect from all relays
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// client.disconnect().await.unwrap();
+    /// # }
+    /// ```
+    pub async fn disconnect(&self) -> Result<(), Error> {
+        Ok(self.pool.disconnect().await?)
+    }
+
+    /// Subscribe to filters
     ///
     /// # Example
     /// ```rust,no_run
@@ -535,6 +1377,99 @@ impl Client {
         self.pool.unsubscribe(wait).await;
     }
 
+    /// Subscribe a single relay to filters
+    ///
+    /// Useful to request different filters to different relays (i.e. DMs only on a private relay).
+    /// The filters are kept alongside the ones set with [`Client::subscribe`].
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// let dms = Filter::new()
+    ///     .pubkey(my_keys.public_key())
+    ///     .kind(Kind::EncryptedDirectMessage);
+    ///
+    /// client
+    ///     .subscribe_to("wss://private.relay.example", vec![dms])
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub async fn subscribe_to<U>(&self, url: U, filters: Vec<Filter>) -> Result<(), Error>
+    where
+        U: TryIntoUrl,
+        pool::Error: From<<U as TryIntoUrl>::Err>,
+    {
+        let wait: Option<Duration> = if self.opts.get_wait_for_subscription() {
+            self.opts.send_timeout
+        } else {
+            None
+        };
+        Ok(self.pool.subscribe_to(url, filters, wait).await?)
+    }
+
+    /// Unsubscribe a single relay from the filters set with [`Client::subscribe_to`]
+    pub async fn unsubscribe_from<U>(&self, url: U) -> Result<(), Error>
+    where
+        U: TryIntoUrl,
+        pool::Error: From<<U as TryIntoUrl>::Err>,
+    {
+        let wait: Option<Duration> = if self.opts.get_wait_for_subscription() {
+            self.opts.send_timeout
+        } else {
+            None
+        };
+        Ok(self.pool.unsubscribe_from(url, wait).await?)
+    }
+
+    /// Subscribe the relays with the `label` to filters (see [`RelayOptions::labels`])
+    ///
+    /// The filters are kept alongside the ones set with [`Client::subscribe`] and [`Client::subscribe_to`].
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// let opts = RelayOptions::new().labels(["dm"]);
+    /// client
+    ///     .add_relay_with_opts("wss://private.relay.example", opts)
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// let dms = Filter::new()
+    ///     .pubkey(my_keys.public_key())
+    ///     .kind(Kind::EncryptedDirectMessage);
+    /// client.subscribe_to_label("dm", vec![dms]).await.unwrap();
+    /// # }
+    /// ```
+    pub async fn subscribe_to_label(&self, label: &str, filters: Vec<Filter>) -> Result<(), Error> {
+        let wait: Option<Duration> = if self.opts.get_wait_for_subscription() {
+            self.opts.send_timeout
+        } else {
+            None
+        };
+        Ok(self.pool.subscribe_to_label(label, filters, wait).await?)
+    }
+
+    /// Unsubscribe the relays with the `label` from the filters set with [`Client::subscribe_to_label`]
+    pub async fn unsubscribe_from_label(&self, label: &str) {
+        let wait: Option<Duration> = if self.opts.get_wait_for_subscription() {
+            self.opts.send_timeout
+        } else {
+            None
+        };
+        self.pool.unsubscribe_from_label(label, wait).await;
+    }
+
     /// Get events of filters
     ///
     /// If timeout is set to `None`, the default from [`Options`] will be used.
@@ -585,6 +1520,237 @@ impl Client {
         Ok(self.pool.get_events_of(filters, timeout, opts).await?)
     }
 
+    /// Get events of filters from the relays with the `label`
+    ///
+    /// If timeout is set to `None`, the default from [`Options`] will be used.
+    pub async fn get_events_from_label(
+        &self,
+        label: &str,
+        filters: Vec<Filter>,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<Event>, Error> {
+        let timeout: Duration = timeout.unwrap_or(self.opts.timeout);
+        Ok(self
+            .pool
+            .get_events_from_label(label, filters, timeout, FilterOptions::ExitOnEOSE)
+            .await?)
+    }
+
+    /// Fetch the newest version of a replaceable or parameterized replaceable event
+    ///
+    /// On the same timestamp, the event with the lowest ID wins (NIP01).
+    /// If timeout is set to `None`, the default from [`Options`] will be used.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// let coordinate = Coordinate::new(Kind::LongFormTextNote, my_keys.public_key()).identifier("my-article");
+    /// if let Some(event) = client.fetch_latest_replaceable(coordinate, None).await.unwrap() {
+    ///     println!("{}", event.content);
+    /// }
+    /// # }
+    /// ```
+    pub async fn fetch_latest_replaceable(
+        &self,
+        coordinate: Coordinate,
+        timeout: Option<Duration>,
+    ) -> Result<Option<Event>, Error> {
+        let identifier: String = coordinate.identifier.clone();
+        let events: Vec<Event> = self
+            .get_events_of(vec![Filter::from(coordinate)], timeout)
+            .await?;
+        Ok(events
+            .into_iter()
+            .filter(|event| event.identifier().unwrap_or_default() == identifier)
+            .max_by_key(|event| (event.created_at, std::cmp::Reverse(event.id))))
+    }
+
+    /// Get the events of a [`Feed`], ordered by its [`EventRanker`]
+    ///
+    /// If timeout is set to `None`, the default from [`Options`] will be used.
+    pub async fn get_feed(
+        &self,
+        feed: &Feed,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<Event>, Error> {
+        let events: Vec<Event> = self.get_events_of(feed.filters(), timeout).await?;
+        Ok(feed.rank(events))
+    }
+
+    /// Get a page of max `limit` events, starting from `cursor` (newest events if `None`)
+    ///
+    /// Return the events (newest first) and the [`PageCursor`] for the next page (`None` when there are no more events).
+    /// The events sharing a timestamp are never skipped, even if there are more than `limit`
+    /// (see [`PageCursor::page_filters`]).
+    ///
+    /// If timeout is set to `None`, the default from [`Options`] will be used.
+    pub async fn get_events_page(
+        &self,
+        filters: Vec<Filter>,
+        cursor: Option<PageCursor>,
+        limit: usize,
+        timeout: Option<Duration>,
+    ) -> Result<(Vec<Event>, Option<PageCursor>), Error> {
+        let page_filters: Vec<Filter> = PageCursor::page_filters(cursor, filters.clone(), limit);
+        let mut events: Vec<Event> = self.get_events_of(page_filters, timeout).await?;
+        let (page, next) = PageCursor::paginate(cursor, events.clone(), limit);
+
+        // A full page may end in the middle of a timestamp shared by more events:
+        // get all of them, so that the next page doesn't skip any
+        match page.last() {
+            Some(last)
+                if page.len() == limit
+                    && cursor.map_or(true, |c| c.created_at != last.created_at) =>
+            {
+                let second_filters: Vec<Filter> =
+                    PageCursor::second_filters(filters, last.created_at);
+                events.extend(self.get_events_of(second_filters, timeout).await?);
+                Ok(PageCursor::paginate(cursor, events, limit))
+            }
+            _ => Ok((page, next)),
+        }
+    }
+
+    /// Get all the events of filters, in pages of max `page_size` events (newest first)
+    ///
+    /// Query the relays again from the last received event (see [`Client::get_events_page`]),
+    /// until a page is empty or a request fails (the error is the last item of the stream).
+    /// Use [`StreamExt::take`](nostr_sdk_net::futures_util::StreamExt::take) to limit the number of pages.
+    ///
+    /// If timeout is set to `None`, the default from [`Options`] will be used for each page.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    /// use nostr_sdk_net::futures_util::StreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let client = Client::new(&Keys::generate());
+    /// let filter = Filter::new().kind(Kind::TextNote);
+    /// let mut pages = Box::pin(client.get_events_paged(vec![filter], 100, None).take(10));
+    /// while let Some(page) = pages.next().await {
+    ///     for event in page.unwrap() {
+    ///         println!("{}", event.content);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn get_events_paged(
+        &self,
+        filters: Vec<Filter>,
+        page_size: usize,
+        timeout: Option<Duration>,
+    ) -> impl Stream<Item = Result<Vec<Event>, Error>> + '_ {
+        // `None` when the pagination is over
+        let state: Option<Option<PageCursor>> = Some(None);
+        stream::unfold(state, move |state| {
+            let filters: Vec<Filter> = filters.clone();
+            async move {
+                let cursor: Option<PageCursor> = state?;
+                match self
+                    .get_events_page(filters, cursor, page_size, timeout)
+                    .await
+                {
+                    Ok((events, _)) if events.is_empty() => None,
+                    Ok((events, next)) => Some((Ok(events), next.map(Some))),
+                    Err(e) => Some((Err(e), None)),
+                }
+            }
+        })
+    }
+
+    /// Search events (NIP50)
+    ///
+    /// The `query` is sent, with the `filter`, only to the relays that advertise NIP50 in their information document (NIP11).
+    /// Return [`Error::SearchNotSupported`] if none of them does.
+    ///
+    /// The results returned by more relays come first.
+    ///
+    /// If timeout is set to `None`, the default from [`Options`] will be used.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/50.md>
+    pub async fn search<S>(
+        &self,
+        query: S,
+        filter: Filter,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<SearchResult>, Error>
+    where
+        S: Into<String>,
+    {
+        let timeout: Duration = timeout.unwrap_or(self.opts.timeout);
+
+        #[cfg(feature = "nip11")]
+        let urls: HashSet<Url> = {
+            let mut urls: HashSet<Url> = HashSet::new();
+            for (url, relay) in self.relays().await.into_iter() {
+                if relay.supports_nip(NIP_SEARCH).await {
+                    urls.insert(url);
+                }
+            }
+            urls
+        };
+        #[cfg(not(feature = "nip11"))]
+        let urls: HashSet<Url> = self.relays().await.into_keys().collect();
+
+        if urls.is_empty() {
+            return Err(Error::SearchNotSupported);
+        }
+
+        let filter: Filter = filter.search(query);
+        let events: Vec<Event> = self
+            .pool
+            .get_events_from(
+                urls.clone(),
+                vec![filter],
+                timeout,
+                FilterOptions::ExitOnEOSE,
+            )
+            .await?;
+
+        // Relays that returned each event
+        let database = self.database();
+        let mut results: Vec<SearchResult> = Vec::with_capacity(events.len());
+        for event in events.into_iter() {
+            let relays: HashSet<Url> = database
+                .event_seen_on_relays(event.id)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or_default()
+                .intersection(&urls)
+                .cloned()
+                .collect();
+            results.push(SearchResult { event, relays });
+        }
+
+        Ok(search::rank(results))
+    }
+
+    /// Count events of filters on all relays
+    ///
+    /// Relays may return different counts: check [`CountReport::relays_disagree`] before
+    /// showing the [`CountReport::estimate`].
+    ///
+    /// If timeout is set to `None`, the default from [`Options`] will be used.
+    pub async fn count_events_of(
+        &self,
+        filters: Vec<Filter>,
+        timeout: Option<Duration>,
+    ) -> Result<CountReport, Error> {
+        let timeout: Duration = match timeout {
+            Some(t) => t,
+            None => self.opts.timeout,
+        };
+        Ok(self.pool.count_events_of(filters, timeout).await?)
+    }
+
     /// Request events of filters
     /// All events will be received on notification listener (`client.notifications()`)
     /// until the EOSE "end of stored events" message is received from the relay.
@@ -646,16 +1812,59 @@ impl Client {
         Ok(self.pool.send_msg_to(url, msg, wait).await?)
     }
 
+    /// Send client message to multiple relays concurrently
+    ///
+    /// Return the result of each relay.
+    pub async fn send_msg_to_many<I, U>(
+        &self,
+        urls: I,
+        msg: ClientMessage,
+    ) -> Result<HashMap<Url, Result<(), pool::Error>>, Error>
+    where
+        I: IntoIterator<Item = U>,
+        U: TryIntoUrl,
+        pool::Error: From<<U as TryIntoUrl>::Err>,
+    {
+        let wait: Option<Duration> = if self.opts.get_wait_for_send() {
+            self.opts.send_timeout
+        } else {
+            None
+        };
+        Ok(self.pool.send_msg_to_many(urls, msg, wait).await?)
+    }
+
     /// Send event
     ///
     /// This method will wait for the `OK` message from the relay.
     /// If you not want to wait for the `OK` message, use `send_msg` method instead.
+    ///
+    /// If [`Options::outbox`] is enabled, the event not published to any relay is kept in the [`Outbox`]
+    /// and sent again when a relay connects. The error is returned anyway.
     pub async fn send_event(&self, event: Event) -> Result<EventId, Error> {
         let timeout: Option<Duration> = self.opts.send_timeout;
         let opts = RelaySendOptions::new()
             .skip_disconnected(self.opts.get_skip_disconnected_relays())
             .timeout(timeout);
-        Ok(self.pool.send_event(event, opts).await?)
+        let res = self.pool.send_event(event.clone(), opts).await;
+        self.keep_unsent(&event, res.map_err(Error::from)).await
+    }
+
+    /// Add the event to the [`Outbox`], if enabled, when it wasn't published to any relay
+    async fn keep_unsent(
+        &self,
+        event: &Event,
+        res: Result<EventId, Error>,
+    ) -> Result<EventId, Error> {
+        if self.opts.get_outbox() {
+            if let Err(Error::RelayPool(
+                RelayPoolError::NoRelays | RelayPoolError::EventNotPublished(..),
+            )) = &res
+            {
+                tracing::info!("Event {} added to the outbox", event.id);
+                self.outbox.push(event).await;
+            }
+        }
+        res
     }
 
     /// Send multiple [`Event`] at once
@@ -684,82 +1893,357 @@ impl Client {
         Ok(self.pool.send_event_to(url, event, opts).await?)
     }
 
-    async fn internal_sign_event_builder(&self, builder: EventBuilder) -> Result<Event, Error> {
-        match self.signer().await? {
-            ClientSigner::Keys(keys) => {
-                let difficulty: u8 = self.opts.get_difficulty();
-                if difficulty > 0 {
-                    Ok(builder.to_pow_event(&keys, difficulty)?)
-                } else {
-                    Ok(builder.to_event(&keys)?)
-                }
-            }
-            #[cfg(all(feature = "nip07", target_arch = "wasm32"))]
-            ClientSigner::NIP07(nip07) => {
-                let public_key: XOnlyPublicKey = nip07.get_public_key().await?;
-                let unsigned = {
-                    let difficulty: u8 = self.opts.get_difficulty();
-                    if difficulty > 0 {
-                        builder.to_unsigned_pow_event(public_key, difficulty)
-                    } else {
-                        builder.to_unsigned_event(public_key)
-                    }
-                };
-                Ok(nip07.sign_event(unsigned).await?)
-            }
-            #[cfg(feature = "nip46")]
-            ClientSigner::NIP46(nip46) => {
-                let signer_public_key: XOnlyPublicKey = nip46
-                    .signer_public_key()
-                    .await
-                    .ok_or(Error::SignerPublicKeyNotFound)?;
-                let unsigned = {
-                    let difficulty: u8 = self.opts.get_difficulty();
-                    if difficulty > 0 {
-                        builder.to_unsigned_pow_event(signer_public_key, difficulty)
-                    } else {
-                        builder.to_unsigned_event(signer_public_key)
-                    }
-                };
-                let res: Response = self
-                    .send_req_to_signer(Request::SignEvent(unsigned), self.opts.nip46_timeout)
-                    .await?;
-                if let Response::SignEvent(event) = res {
-                    Ok(event)
-                } else {
-                    Err(Error::ResponseNotMatchRequest)
-                }
-            }
-        }
-    }
-
-    /// Take an [`EventBuilder`], sign it by using the [`ClientSigner`] and broadcast to all relays.
+    /// Send event to multiple relays concurrently
     ///
-    /// Rise an error if the [`ClientSigner`] is not set.
-    pub async fn send_event_builder(&self, builder: EventBuilder) -> Result<EventId, Error> {
-        let event: Event = self.internal_sign_event_builder(builder).await?;
-        self.send_event(event).await
-    }
-
-    /// Take an [`EventBuilder`], sign it by using the [`ClientSigner`] and broadcast to specific relays.
+    /// This method will wait for the `OK` message from each relay and return the result of each one.
     ///
-    /// Rise an error if the [`ClientSigner`] is not set.
-    pub async fn send_event_builder_to<U>(
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// let event = EventBuilder::new_text_note("Hello", [])
+    ///     .to_event(&my_keys)
+    ///     .unwrap();
+    /// let results = client
+    ///     .send_event_to_many(["wss://relay.damus.io", "wss://nos.lol"], event)
+    ///     .await
+    ///     .unwrap();
+    /// for (url, res) in results.into_iter() {
+    ///     println!("{url}: {res:?}");
+    /// }
+    /// # }
+    /// ```
+    pub async fn send_event_to_many<I, U>(
         &self,
-        url: U,
-        builder: EventBuilder,
-    ) -> Result<EventId, Error>
+        urls: I,
+        event: Event,
+    ) -> Result<HashMap<Url, Result<EventId, pool::Error>>, Error>
     where
+        I: IntoIterator<Item = U>,
         U: TryIntoUrl,
         pool::Error: From<<U as TryIntoUrl>::Err>,
     {
-        let event: Event = self.internal_sign_event_builder(builder).await?;
-        self.send_event_to(url, event).await
+        let timeout: Option<Duration> = self.opts.send_timeout;
+        let opts = RelaySendOptions::new()
+            .skip_disconnected(self.opts.get_skip_disconnected_relays())
+            .timeout(timeout);
+        Ok(self.pool.send_event_to_many(urls, event, opts).await?)
     }
 
-    /// Update metadata
+    /// Send event and wait until it's returned by the relays that accepted it
     ///
-    /// <https://github.com/nostr-protocol/nips/blob/master/01.md>
+    /// After the `OK` messages, the event is queried from the relays that accepted it,
+    /// until at least [`RelaySendOptions::min_success`] of them return it.
+    /// Useful with replaceable events and with relays that accept events without storing them.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// let event = EventBuilder::set_metadata(&Metadata::new().name("nostr"))
+    ///     .to_event(&my_keys)
+    ///     .unwrap();
+    /// let opts = SendAndWaitOptions::new().timeout(Duration::from_secs(30));
+    /// client.send_event_and_wait(event, opts).await.unwrap();
+    /// # }
+    /// ```
+    pub async fn send_event_and_wait(
+        &self,
+        event: Event,
+        opts: SendAndWaitOptions,
+    ) -> Result<EventId, Error> {
+        let event_id: EventId = event.id;
+        let relays = self.pool.relays().await;
+        if relays.is_empty() {
+            let res = Err(Error::RelayPool(RelayPoolError::NoRelays));
+            return self.keep_unsent(&event, res).await;
+        }
+
+        let results = self
+            .pool
+            .send_event_to_many(relays.keys().cloned(), event.clone(), opts.send)
+            .await?;
+        let mut accepted: Vec<Url> = Vec::new();
+        let mut rejected: HashMap<Url, String> = HashMap::new();
+        for (url, res) in results.into_iter() {
+            match res {
+                Ok(..) => accepted.push(url),
+                Err(e) => {
+                    rejected.insert(url, e.to_string());
+                }
+            }
+        }
+
+        let min_success: usize = opts.send.min_success;
+        if accepted.len() < min_success {
+            let e = if accepted.is_empty() && min_success == 1 {
+                RelayPoolError::EventNotPublished(event_id)
+            } else {
+                RelayPoolError::QuorumNotReached {
+                    event_id,
+                    min_success,
+                    accepted,
+                    rejected,
+                }
+            };
+            return self.keep_unsent(&event, Err(Error::RelayPool(e))).await;
+        }
+
+        if !opts.wait_for_event {
+            return Ok(event_id);
+        }
+
+        let mut handles = Vec::new();
+        for url in accepted.into_iter() {
+            if let Some(relay) = relays.get(&url) {
+                let relay = relay.clone();
+                let database = self.database();
+                let handle = thread::spawn(async move {
+                    wait::wait_for_event(relay, database, event_id, opts).await
+                });
+                handles.push(handle);
+            }
+        }
+
+        let mut returned: usize = 0;
+        for handle in handles.into_iter().flatten() {
+            if handle.join().await.map_err(pool::Error::from)? {
+                returned += 1;
+            }
+        }
+
+        if returned >= min_success {
+            Ok(event_id)
+        } else {
+            Err(Error::EventNotReturned(event_id))
+        }
+    }
+
+    /// Send client message to the relays with the `label`
+    ///
+    /// Return the result of each relay.
+    pub async fn send_msg_to_label(
+        &self,
+        label: &str,
+        msg: ClientMessage,
+    ) -> Result<HashMap<Url, Result<(), pool::Error>>, Error> {
+        let wait: Option<Duration> = if self.opts.get_wait_for_send() {
+            self.opts.send_timeout
+        } else {
+            None
+        };
+        Ok(self.pool.send_msg_to_label(label, msg, wait).await?)
+    }
+
+    /// Send event to the relays with the `label`
+    ///
+    /// This method will wait for the `OK` message from each relay and return the result of each one.
+    pub async fn send_event_to_label(
+        &self,
+        label: &str,
+        event: Event,
+    ) -> Result<HashMap<Url, Result<EventId, pool::Error>>, Error> {
+        let timeout: Option<Duration> = self.opts.send_timeout;
+        let opts = RelaySendOptions::new()
+            .skip_disconnected(self.opts.get_skip_disconnected_relays())
+            .timeout(timeout);
+        Ok(self.pool.send_event_to_label(label, event, opts).await?)
+    }
+
+    async fn internal_sign_event_builder(&self, builder: EventBuilder) -> Result<Event, Error> {
+        let signer: ClientSigner = self.signer().await?;
+        self.sign_event_builder_with(&signer, builder).await
+    }
+
+    async fn sign_event_builder_with(
+        &self,
+        signer: &ClientSigner,
+        builder: EventBuilder,
+    ) -> Result<Event, Error> {
+        let public_key: XOnlyPublicKey = signer.get_public_key().await?;
+        let difficulty: u8 = self.opts.get_difficulty();
+        let middlewares = self.pool.middlewares();
+        let unsigned: UnsignedEvent = if middlewares.is_empty().await {
+            if difficulty > 0 {
+                builder.to_unsigned_pow_event(public_key, difficulty)
+            } else {
+                builder.to_unsigned_event(public_key)
+            }
+        } else {
+            let unsigned: UnsignedEvent = middlewares
+                .before_send(builder.to_unsigned_event(public_key))
+                .await
+                .map_err(Error::Middleware)?;
+
+            // The middlewares may have changed the event: compute the ID again (or mine it)
+            if difficulty > 0 {
+                EventBuilder::new(unsigned.kind, unsigned.content, unsigned.tags)
+                    .to_unsigned_pow_event(public_key, difficulty)
+            } else {
+                let id = EventId::new(
+                    &public_key,
+                    unsigned.created_at,
+                    &unsigned.kind,
+                    &unsigned.tags,
+                    &unsigned.content,
+                );
+                UnsignedEvent {
+                    id,
+                    pubkey: public_key,
+                    ..unsigned
+                }
+            }
+        };
+        signer.sign_event(unsigned).await
+    }
+
+    /// Take an [`EventBuilder`], sign it by using the [`ClientSigner`] and broadcast to all relays.
+    ///
+    /// Rise an error if the [`ClientSigner`] is not set.
+    pub async fn send_event_builder(&self, builder: EventBuilder) -> Result<EventId, Error> {
+        let event: Event = self.internal_sign_event_builder(builder).await?;
+        match self.opts.get_auto_pow() {
+            Some(max_difficulty) => {
+                let res = self
+                    .send_event_with_auto_pow(event.clone(), max_difficulty)
+                    .await;
+                self.keep_unsent(&event, res).await
+            }
+            None => self.send_event(event).await,
+        }
+    }
+
+    /// Send the event and, to the relays that require a higher POW difficulty, the event mined again
+    ///
+    /// Return the ID of the original event if accepted by at least one relay,
+    /// otherwise the ID of the first accepted mined event.
+    async fn send_event_with_auto_pow(
+        &self,
+        event: Event,
+        max_difficulty: u8,
+    ) -> Result<EventId, Error> {
+        let relays: HashMap<Url, Relay> = self.pool.relays().await;
+        if relays.is_empty() {
+            return Err(Error::RelayPool(RelayPoolError::NoRelays));
+        }
+
+        let opts = RelaySendOptions::new()
+            .skip_disconnected(self.opts.get_skip_disconnected_relays())
+            .timeout(self.opts.send_timeout);
+        let difficulty: u8 = nip13::get_leading_zero_bits(event.id.as_bytes());
+
+        // Skip the relays that already advertise a higher difficulty
+        let mut required: HashMap<Url, u8> = HashMap::new();
+        #[cfg(feature = "nip11")]
+        for (url, relay) in relays.iter() {
+            let limitation = relay.document().await.limitation;
+            if let Some(min_pow) = limitation.and_then(|l| l.min_pow_difficulty) {
+                if min_pow > i32::from(difficulty) {
+                    required.insert(url.clone(), u8::try_from(min_pow).unwrap_or(u8::MAX));
+                }
+            }
+        }
+
+        let mut accepted: Option<EventId> = None;
+        let urls: Vec<Url> = relays
+            .into_keys()
+            .filter(|url| !required.contains_key(url))
+            .collect();
+        if !urls.is_empty() {
+            let results = self
+                .pool
+                .send_event_to_many(urls, event.clone(), opts)
+                .await?;
+            for (url, res) in results.into_iter() {
+                match res {
+                    Ok(event_id) => accepted = Some(event_id),
+                    Err(RelayPoolError::Relay(RelayError::EventNotPublished(message))) => {
+                        match required_pow_difficulty(&message) {
+                            Some(min_pow) if min_pow > difficulty => {
+                                required.insert(url, min_pow);
+                            }
+                            _ => tracing::error!("Impossible to send event to {url}: {message}"),
+                        }
+                    }
+                    Err(e) => tracing::error!("Impossible to send event to {url}: {e}"),
+                }
+            }
+        }
+
+        // Mine once for each required difficulty
+        let mut by_difficulty: BTreeMap<u8, Vec<Url>> = BTreeMap::new();
+        for (url, min_pow) in required.into_iter() {
+            by_difficulty.entry(min_pow).or_default().push(url);
+        }
+        for (min_pow, urls) in by_difficulty.into_iter() {
+            if min_pow > max_difficulty {
+                tracing::warn!(
+                    "Skipping {} relays that require a POW difficulty of {min_pow} (max {max_difficulty})",
+                    urls.len()
+                );
+                continue;
+            }
+
+            let mined: Event = self.mine_event(&event, min_pow).await?;
+            let results = self.pool.send_event_to_many(urls, mined, opts).await?;
+            for (url, res) in results.into_iter() {
+                match res {
+                    Ok(event_id) => {
+                        accepted.get_or_insert(event_id);
+                    }
+                    Err(e) => tracing::error!("Impossible to send mined event to {url}: {e}"),
+                }
+            }
+        }
+
+        accepted.ok_or(Error::RelayPool(RelayPoolError::EventNotPublished(
+            event.id,
+        )))
+    }
+
+    /// Mine the event again at `difficulty`, keeping its timestamp
+    async fn mine_event(&self, event: &Event, difficulty: u8) -> Result<Event, Error> {
+        let signer: ClientSigner = self.signer().await?;
+        let tags = event
+            .tags
+            .iter()
+            .filter(|tag| !matches!(tag, Tag::POW { .. }))
+            .cloned();
+        let unsigned: UnsignedEvent = EventBuilder::new(event.kind, event.content.clone(), tags)
+            .custom_created_at(event.created_at)
+            .to_unsigned_pow_event(event.pubkey, difficulty);
+        signer.sign_event(unsigned).await
+    }
+
+    /// Take an [`EventBuilder`], sign it by using the [`ClientSigner`] and broadcast to specific relays.
+    ///
+    /// Rise an error if the [`ClientSigner`] is not set.
+    pub async fn send_event_builder_to<U>(
+        &self,
+        url: U,
+        builder: EventBuilder,
+    ) -> Result<EventId, Error>
+    where
+        U: TryIntoUrl,
+        pool::Error: From<<U as TryIntoUrl>::Err>,
+    {
+        let event: Event = self.internal_sign_event_builder(builder).await?;
+        self.send_event_to(url, event).await
+    }
+
+    /// Update metadata
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/01.md>
     ///
     /// # Example
     /// ```rust,no_run
@@ -784,6 +2268,62 @@ impl Client {
         self.send_event_builder(builder).await
     }
 
+    /// Publish relay list
+    ///
+    /// If [`Options::sync_relay_list`] is enabled, the relays of the pool are updated to match the list:
+    /// the missing relays are added (labeled with [`RELAY_LIST_LABEL`]) and connected,
+    /// the read/write roles are updated and the labeled relays not in the list anymore are removed.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/65.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// let list = [
+    ///     (UncheckedUrl::from("wss://relay.damus.io"), None),
+    ///     (
+    ///         UncheckedUrl::from("wss://nostr.wine"),
+    ///         Some(RelayMetadata::Read),
+    ///     ),
+    /// ];
+    /// client.publish_relay_list(list).await.unwrap();
+    /// # }
+    /// ```
+    pub async fn publish_relay_list<I>(&self, list: I) -> Result<EventId, Error>
+    where
+        I: IntoIterator<Item = (UncheckedUrl, Option<RelayMetadata>)>,
+    {
+        let builder = EventBuilder::relay_list(list);
+        let event: Event = self.internal_sign_event_builder(builder).await?;
+        let event_id: EventId = self.send_event(event.clone()).await?;
+        if self.opts.get_sync_relay_list() {
+            self.relay_list_sync.apply(&event).await?;
+        }
+        Ok(event_id)
+    }
+
+    /// Fetch the latest relay list of the client signer and apply it to the pool
+    ///
+    /// See [`Client::publish_relay_list`] for how the list is applied.
+    /// Return `false` if there isn't a relay list newer than the last applied one.
+    pub async fn sync_relay_list(&self, timeout: Option<Duration>) -> Result<bool, Error> {
+        let public_key: XOnlyPublicKey = self.signer_public_key().await?;
+        let filter: Filter = Filter::new()
+            .author(public_key)
+            .kind(Kind::RelayList)
+            .limit(1);
+        let events: Vec<Event> = self.get_events_of(vec![filter], timeout).await?;
+        match events.into_iter().max_by_key(|e| e.created_at) {
+            Some(event) => Ok(self.relay_list_sync.apply(&event).await?),
+            None => Ok(false),
+        }
+    }
+
     /// Publish text note
     ///
     /// <https://github.com/nostr-protocol/nips/blob/master/01.md>
@@ -841,229 +2381,317 @@ impl Client {
 
     /// Set contact list
     ///
+    /// If a contact list was already fetched (see [`Client::get_contact_list`]) or published by this client,
+    /// the newest one is fetched from relays before publishing:
+    /// if it's newer than the known one, [`Error::ContactListConflict`] is returned instead of overwriting it.
+    ///
     /// <https://github.com/nostr-protocol/nips/blob/master/02.md>
     pub async fn set_contact_list<I>(&self, list: I) -> Result<EventId, Error>
     where
         I: IntoIterator<Item = Contact>,
     {
-        let builder = EventBuilder::set_contact_list(list);
-        self.send_event_builder(builder).await
-    }
-
-    async fn get_contact_list_filters(&self) -> Result<Vec<Filter>, Error> {
-        let mut filter: Filter = Filter::new().kind(Kind::ContactList).limit(1);
-
-        match self.signer().await? {
-            ClientSigner::Keys(keys) => {
-                filter = filter.author(keys.public_key());
-            }
-            #[cfg(all(feature = "nip07", target_arch = "wasm32"))]
-            ClientSigner::NIP07(nip07) => {
-                let public_key: XOnlyPublicKey = nip07.get_public_key().await?;
-                filter = filter.author(public_key);
-            }
-            #[cfg(feature = "nip46")]
-            ClientSigner::NIP46(nip46) => {
-                let signer_public_key = nip46
-                    .signer_public_key()
-                    .await
-                    .ok_or(Error::SignerPublicKeyNotFound)?;
-
-                filter = filter.author(signer_public_key);
+        let local: Option<Timestamp> = *self.contact_list_timestamp.read().await;
+        if let Some(local) = local {
+            if let Some(remote) = self.get_contact_list_event(None).await? {
+                if remote.created_at > local {
+                    return Err(Error::ContactListConflict {
+                        local,
+                        remote: remote.created_at,
+                    });
+                }
             }
-        };
+        }
 
-        Ok(vec![filter])
+        let builder = EventBuilder::set_contact_list(list);
+        self.send_contact_list(builder).await
     }
 
-    /// Get contact list
+    /// Follow a public key
+    ///
+    /// Fetch the latest contact list, add (or update) the contact and publish the new list.
+    /// The other entries, the tags and the content of the list are preserved.
     ///
     /// <https://github.com/nostr-protocol/nips/blob/master/02.md>
     ///
     /// # Example
     /// ```rust,no_run
-    /// use std::time::Duration;
-    ///
     /// use nostr_sdk::prelude::*;
     ///
     /// # #[tokio::main]
     /// # async fn main() {
     /// #   let my_keys = Keys::generate();
     /// #   let client = Client::new(&my_keys);
-    /// let timeout = Duration::from_secs(10);
-    /// let _list = client.get_contact_list(Some(timeout)).await.unwrap();
+    /// let public_key = XOnlyPublicKey::from_bech32(
+    ///     "npub14f8usejl26twx0dhuxjh9cas7keav9vr0v8nvtwtrjqx3vycc76qqh9nsy",
+    /// )
+    /// .unwrap();
+    /// client
+    ///     .follow(public_key, Some(UncheckedUrl::from("wss://relay.damus.io")), Some("alice"))
+    ///     .await
+    ///     .unwrap();
     /// # }
     /// ```
-    pub async fn get_contact_list(&self, timeout: Option<Duration>) -> Result<Vec<Contact>, Error> {
-        let mut contact_list: Vec<Contact> = Vec::new();
-        let filters: Vec<Filter> = self.get_contact_list_filters().await?;
-        let events: Vec<Event> = self.get_events_of(filters, timeout).await?;
+    pub async fn follow<S>(
+        &self,
+        public_key: XOnlyPublicKey,
+        relay_url: Option<UncheckedUrl>,
+        alias: Option<S>,
+    ) -> Result<EventId, Error>
+    where
+        S: Into<String>,
+    {
+        let contact_tag = Tag::PublicKey {
+            public_key,
+            relay_url,
+            alias: alias.map(|a| a.into()),
+            uppercase: false,
+        };
 
-        for event in events.into_iter() {
-            for tag in event.tags.into_iter() {
-                if let Tag::PublicKey {
-                    public_key,
-                    relay_url,
-                    alias,
-                    uppercase: false,
-                } = tag
-                {
-                    contact_list.push(Contact::new(public_key, relay_url, alias))
-                }
-            }
+        let (content, mut tags) = self.get_contact_list_parts().await?;
+        match tags.iter_mut().find(|tag| is_contact_tag(tag, &public_key)) {
+            Some(tag) => *tag = contact_tag,
+            None => tags.push(contact_tag),
         }
 
-        Ok(contact_list)
+        let builder = EventBuilder::new(Kind::ContactList, content, tags);
+        self.send_contact_list(builder).await
     }
 
-    /// Get contact list public keys
+    /// Unfollow a public key
+    ///
+    /// Fetch the latest contact list, remove the contact and publish the new list.
     ///
     /// <https://github.com/nostr-protocol/nips/blob/master/02.md>
-    pub async fn get_contact_list_public_keys(
+    pub async fn unfollow(&self, public_key: XOnlyPublicKey) -> Result<EventId, Error> {
+        let (content, mut tags) = self.get_contact_list_parts().await?;
+        tags.retain(|tag| !is_contact_tag(tag, &public_key));
+
+        let builder = EventBuilder::new(Kind::ContactList, content, tags);
+        self.send_contact_list(builder).await
+    }
+
+    /// Get content and tags of the latest contact list (empty if not found)
+    async fn get_contact_list_parts(&self) -> Result<(String, Vec<Tag>), Error> {
+        match self.get_contact_list_event(None).await? {
+            Some(event) => Ok((event.content, event.tags)),
+            None => Ok((String::new(), Vec::new())),
+        }
+    }
+
+    /// Get the latest contact list and remember its creation timestamp
+    async fn get_contact_list_event(
         &self,
         timeout: Option<Duration>,
-    ) -> Result<Vec<XOnlyPublicKey>, Error> {
-        let mut pubkeys: Vec<XOnlyPublicKey> = Vec::new();
+    ) -> Result<Option<Event>, Error> {
         let filters: Vec<Filter> = self.get_contact_list_filters().await?;
         let events: Vec<Event> = self.get_events_of(filters, timeout).await?;
+        let event: Option<Event> = events.into_iter().max_by_key(|event| event.created_at);
 
-        for event in events.into_iter() {
-            pubkeys.extend(event.public_keys());
+        if let Some(event) = &event {
+            let mut timestamp = self.contact_list_timestamp.write().await;
+            if timestamp.map_or(true, |t| event.created_at > t) {
+                *timestamp = Some(event.created_at);
+            }
         }
 
-        Ok(pubkeys)
+        Ok(event)
     }
 
-    /// Get contact list [`Metadata`]
-    pub async fn get_contact_list_metadata(
-        &self,
-        timeout: Option<Duration>,
-    ) -> Result<HashMap<XOnlyPublicKey, Metadata>, Error> {
-        let public_keys = self.get_contact_list_public_keys(timeout).await?;
-        let mut contacts: HashMap<XOnlyPublicKey, Metadata> =
-            public_keys.iter().map(|p| (*p, Metadata::new())).collect();
+    async fn send_contact_list(&self, builder: EventBuilder) -> Result<EventId, Error> {
+        let event: Event = self.internal_sign_event_builder(builder).await?;
+        let created_at: Timestamp = event.created_at;
+        let event_id: EventId = self.send_event(event).await?;
+        let mut timestamp = self.contact_list_timestamp.write().await;
+        *timestamp = Some(created_at);
+        Ok(event_id)
+    }
 
-        let chunk_size: usize = self.opts.get_req_filters_chunk_size();
-        for chunk in public_keys.chunks(chunk_size) {
-            let mut filters: Vec<Filter> = Vec::new();
-            for public_key in chunk.iter() {
-                filters.push(
-                    Filter::new()
-                        .author(*public_key)
-                        .kind(Kind::Metadata)
-                        .limit(1),
-                );
-            }
-            let events: Vec<Event> = self.get_events_of(filters, timeout).await?;
-            for event in events.into_iter() {
-                let metadata = Metadata::from_json(&event.content)?;
-                if let Some(m) = contacts.get_mut(&event.pubkey) {
-                    *m = metadata
-                };
-            }
-        }
+    async fn signer_public_key(&self) -> Result<XOnlyPublicKey, Error> {
+        self.signer().await?.get_public_key().await
+    }
 
-        Ok(contacts)
+    async fn get_contact_list_filters(&self) -> Result<Vec<Filter>, Error> {
+        let public_key: XOnlyPublicKey = self.signer_public_key().await?;
+        let filter: Filter = Filter::new()
+            .kind(Kind::ContactList)
+            .author(public_key)
+            .limit(1);
+        Ok(vec![filter])
     }
 
-    /// Send encrypted direct message
+    /// Fetch the latest mute list of the signer and use it for [`Client::is_muted`]
     ///
-    /// <https://github.com/nostr-protocol/nips/blob/master/04.md>
+    /// The private entries are used only if the signer can decrypt them.
     ///
-    /// # Example
-    /// ```rust,no_run
+    /// <https://github.com/nostr-protocol/nips/blob/master/51.md>
+    pub async fn sync_mute_list(&self, timeout: Option<Duration>) -> Result<MuteList, Error> {
+        let mut list: MuteList = match self.get_latest_list_event(&List::Mute, timeout).await? {
+            Some(event) => match self.list_items_from_event(&event).await {
+                Ok(items) => MuteList::from_items(&items),
+                Err(e) => {
+                    tracing::warn!("Impossible to decrypt the private mute list entries: {e}");
+                    MuteList::from_event(&event)
+                }
+            },
+            None => MuteList::new(),
+        };
+
+        let mut mute_list = self.mute_list.write().await;
+        list.set_matcher(mute_list.get_matcher());
+        *mute_list = list.clone();
+
+        Ok(list)
+    }
+
+    /// Get the mute list synced with [`Client::sync_mute_list`]
+    pub async fn mute_list(&self) -> MuteList {
+        self.mute_list.read().await.clone()
+    }
+
+    /// Set the [`MutedWordMatcher`] used to match the muted words
+    pub async fn set_muted_word_matcher<M>(&self, matcher: M)
+    where
+        M: MutedWordMatcher + 'static,
+    {
+        let mut mute_list = self.mute_list.write().await;
+        mute_list.set_matcher(Arc::new(matcher));
+    }
+
+    /// Check if [`Event`] is muted by the synced mute list
+    pub async fn is_muted(&self, event: &Event) -> bool {
+        self.mute_list.read().await.is_muted(event)
+    }
+
+    /// Get the items of a NIP51 list of the signer, the private ones decrypted
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/51.md>
+    pub async fn get_list(
+        &self,
+        list: List,
+        timeout: Option<Duration>,
+    ) -> Result<ListItems, Error> {
+        match self.get_latest_list_event(&list, timeout).await? {
+            Some(event) => self.list_items_from_event(&event).await,
+            None => Ok(ListItems::new()),
+        }
+    }
+
+    /// Add items to a NIP51 list of the signer
+    ///
+    /// The latest list is fetched before adding the items, to not overwrite the ones added from other devices.
+    /// The items already in the list are moved to the public or private items, according to `private`.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/51.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
     /// use nostr_sdk::prelude::*;
     ///
     /// # #[tokio::main]
     /// # async fn main() {
     /// #   let my_keys = Keys::generate();
     /// #   let client = Client::new(&my_keys);
-    /// let alice_pubkey = XOnlyPublicKey::from_bech32(
-    ///     "npub14f8usejl26twx0dhuxjh9cas7keav9vr0v8nvtwtrjqx3vycc76qqh9nsy",
-    /// )
-    /// .unwrap();
+    /// # let event_id = EventId::all_zeros();
+    /// # let public_key = Keys::generate().public_key();
+    /// // Pin a note
+    /// client
+    ///     .add_to_list(List::Pin, [Tag::event(event_id)], false)
+    ///     .await
+    ///     .unwrap();
     ///
+    /// // Privately mute a user
     /// client
-    ///     .send_direct_msg(alice_pubkey, "My first DM fro Nostr SDK!", None)
+    ///     .add_to_list(List::Mute, [Tag::public_key(public_key)], true)
     ///     .await
     ///     .unwrap();
     /// # }
     /// ```
-    #[cfg(feature = "nip04")]
-    pub async fn send_direct_msg<S>(
+    pub async fn add_to_list<I>(
         &self,
-        receiver: XOnlyPublicKey,
-        msg: S,
-        reply_to: Option<EventId>,
+        list: List,
+        items: I,
+        private: bool,
     ) -> Result<EventId, Error>
     where
-        S: Into<String>,
+        I: IntoIterator<Item = Tag>,
     {
-        let builder: EventBuilder = match self.signer().await? {
-            ClientSigner::Keys(keys) => {
-                EventBuilder::new_encrypted_direct_msg(&keys, receiver, msg, reply_to)?
-            }
-            #[cfg(all(feature = "nip07", target_arch = "wasm32"))]
-            ClientSigner::NIP07(nip07) => {
-                let content: String = nip07.nip04_encrypt(receiver, msg.into()).await?;
-                EventBuilder::new(
-                    Kind::EncryptedDirectMessage,
-                    content,
-                    [Tag::public_key(receiver)],
-                )
-            }
-            #[cfg(feature = "nip46")]
-            ClientSigner::NIP46(..) => {
-                let req = Request::Nip04Encrypt {
-                    public_key: receiver,
-                    text: msg.into(),
-                };
-                let res: Response = self
-                    .send_req_to_signer(req, self.opts.nip46_timeout)
-                    .await?;
-                if let Response::Nip04Encrypt(content) = res {
-                    EventBuilder::new(
-                        Kind::EncryptedDirectMessage,
-                        content,
-                        [Tag::public_key(receiver)],
-                    )
-                } else {
-                    return Err(Error::ResponseNotMatchRequest);
-                }
-            }
+        let mut list_items: ListItems = self.get_list(list.clone(), None).await?;
+        for item in items.into_iter() {
+            list_items.add(item, private);
+        }
+        self.set_list(list, list_items).await
+    }
+
+    /// Remove items from a NIP51 list of the signer
+    ///
+    /// The latest list is fetched before removing the items, to not overwrite the ones added from other devices.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/51.md>
+    pub async fn remove_from_list<I>(&self, list: List, items: I) -> Result<EventId, Error>
+    where
+        I: IntoIterator<Item = Tag>,
+    {
+        let mut list_items: ListItems = self.get_list(list.clone(), None).await?;
+        for item in items.into_iter() {
+            list_items.remove(&item);
+        }
+        self.set_list(list, list_items).await
+    }
+
+    /// Publish a NIP51 list of the signer, replacing the previous one
+    ///
+    /// The private items are encrypted to the signer.
+    /// To edit the list without overwriting the changes made from other devices,
+    /// use [`Client::add_to_list`] and [`Client::remove_from_list`].
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/51.md>
+    pub async fn set_list(&self, list: List, items: ListItems) -> Result<EventId, Error> {
+        let private_content: String = items.private_content()?;
+        let content: String = if private_content.is_empty() {
+            private_content
+        } else {
+            let public_key: XOnlyPublicKey = self.signer_public_key().await?;
+            encrypt_list_content(&self.signer().await?, public_key, private_content).await?
         };
 
+        let mut tags: Vec<Tag> = Vec::with_capacity(items.public.len() + 1);
+        if let Some(identifier) = list.identifier() {
+            tags.push(Tag::Identifier(identifier.to_string()));
+        }
+        tags.extend(items.public);
+
+        let builder = EventBuilder::new(list.kind(), content, tags);
         self.send_event_builder(builder).await
     }
 
-    /// Repost event
-    pub async fn repost_event(
+    async fn get_latest_list_event(
         &self,
-        event_id: EventId,
-        public_key: XOnlyPublicKey,
-    ) -> Result<EventId, Error> {
-        let builder = EventBuilder::repost(event_id, public_key);
-        self.send_event_builder(builder).await
+        list: &List,
+        timeout: Option<Duration>,
+    ) -> Result<Option<Event>, Error> {
+        let public_key: XOnlyPublicKey = self.signer_public_key().await?;
+        let events: Vec<Event> = self
+            .get_events_of(vec![list.filter(public_key)], timeout)
+            .await?;
+        Ok(events.into_iter().max_by_key(|event| event.created_at))
     }
 
-    /// Delete event
-    ///
-    /// <https://github.com/nostr-protocol/nips/blob/master/09.md>
-    pub async fn delete_event<T>(&self, id: T) -> Result<EventId, Error>
-    where
-        T: Into<EventIdOrCoordinate>,
-    {
-        let builder = EventBuilder::delete([id]);
-        self.send_event_builder(builder).await
+    async fn list_items_from_event(&self, event: &Event) -> Result<ListItems, Error> {
+        let mut items: ListItems = ListItems::from_event(event);
+        if !event.content.is_empty() {
+            let decrypted: String = decrypt_list_content(&self.signer().await?, event).await?;
+            items.set_private(decrypted)?;
+        }
+        Ok(items)
     }
 
-    /// Like event
+    /// Get contact list
     ///
-    /// <https://github.com/nostr-protocol/nips/blob/master/25.md>
+    /// <https://github.com/nostr-protocol/nips/blob/master/02.md>
     ///
     /// # Example
     /// ```rust,no_run
-    /// use std::str::FromStr;
+    /// use std::time::Duration;
     ///
     /// use nostr_sdk::prelude::*;
     ///
@@ -1071,102 +2699,1202 @@ impl Client {
     /// # async fn main() {
     /// #   let my_keys = Keys::generate();
     /// #   let client = Client::new(&my_keys);
-    /// let event_id =
-    ///     EventId::from_hex("3aded8d2194dc2fedb1d7b70480b43b6c4deb0a22dcdc9c471d1958485abcf21")
-    ///         .unwrap();
-    /// let public_key = XOnlyPublicKey::from_str(
-    ///     "a8e76c3ace7829f9ee44cf9293309e21a1824bf1e57631d00685a1ed0b0bd8a2",
-    /// )
-    /// .unwrap();
-    ///
-    /// client.like(event_id, public_key).await.unwrap();
+    /// let timeout = Duration::from_secs(10);
+    /// let _list = client.get_contact_list(Some(timeout)).await.unwrap();
     /// # }
     /// ```
-    pub async fn like(
+    pub async fn get_contact_list(&self, timeout: Option<Duration>) -> Result<Vec<Contact>, Error> {
+        let mut contact_list: Vec<Contact> = Vec::new();
+
+        if let Some(event) = self.get_contact_list_event(timeout).await? {
+            for tag in event.tags.into_iter() {
+                if let Tag::PublicKey {
+                    public_key,
+                    relay_url,
+                    alias,
+                    uppercase: false,
+                } = tag
+                {
+                    contact_list.push(Contact::new(public_key, relay_url, alias))
+                }
+            }
+        }
+
+        Ok(contact_list)
+    }
+
+    /// Get contact list public keys
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/02.md>
+    pub async fn get_contact_list_public_keys(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<XOnlyPublicKey>, Error> {
+        let mut pubkeys: Vec<XOnlyPublicKey> = Vec::new();
+        let filters: Vec<Filter> = self.get_contact_list_filters().await?;
+        let events: Vec<Event> = self.get_events_of(filters, timeout).await?;
+
+        for event in events.into_iter() {
+            pubkeys.extend(event.public_keys());
+        }
+
+        Ok(pubkeys)
+    }
+
+    /// Get contact list [`Metadata`]
+    pub async fn get_contact_list_metadata(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<HashMap<XOnlyPublicKey, Metadata>, Error> {
+        let public_keys = self.get_contact_list_public_keys(timeout).await?;
+        let mut contacts: HashMap<XOnlyPublicKey, Metadata> =
+            public_keys.iter().map(|p| (*p, Metadata::new())).collect();
+
+        let chunk_size: usize = self.opts.get_req_filters_chunk_size();
+        for chunk in public_keys.chunks(chunk_size) {
+            let mut filters: Vec<Filter> = Vec::new();
+            for public_key in chunk.iter() {
+                filters.push(
+                    Filter::new()
+                        .author(*public_key)
+                        .kind(Kind::Metadata)
+                        .limit(1),
+                );
+            }
+            let events: Vec<Event> = self.get_events_of(filters, timeout).await?;
+            for event in events.into_iter() {
+                let metadata = Metadata::from_json(&event.content)?;
+                if let Some(m) = contacts.get_mut(&event.pubkey) {
+                    *m = metadata
+                };
+            }
+        }
+
+        Ok(contacts)
+    }
+
+    /// Fetch profile [`Metadata`] of `public_key`
+    ///
+    /// See [`Client::fetch_metadatas`].
+    pub async fn fetch_metadata(
         &self,
-        event_id: EventId,
         public_key: XOnlyPublicKey,
-    ) -> Result<EventId, Error> {
-        let builder = EventBuilder::new_reaction(event_id, public_key, "+");
-        self.send_event_builder(builder).await
+        timeout: Option<Duration>,
+    ) -> Result<Option<Metadata>, Error> {
+        let mut metadatas = self.fetch_metadatas([public_key], timeout).await?;
+        Ok(metadatas.remove(&public_key))
     }
 
-    /// Disike event
+    /// Fetch profile [`Metadata`] of many public keys, with a single `REQ`
     ///
-    /// <https://github.com/nostr-protocol/nips/blob/master/25.md>
+    /// The newest [`Kind::Metadata`] event of each public key is picked and cached in the database:
+    /// public keys fetched less than [`Options::metadata_cache_ttl`] ago are served from the database, without querying the relays.
+    ///
+    /// The public keys without metadata are not included in the result.
     ///
     /// # Example
     /// ```rust,no_run
-    /// use std::str::FromStr;
-    ///
     /// use nostr_sdk::prelude::*;
     ///
     /// # #[tokio::main]
     /// # async fn main() {
     /// #   let my_keys = Keys::generate();
     /// #   let client = Client::new(&my_keys);
-    /// let event_id =
-    ///     EventId::from_hex("3aded8d2194dc2fedb1d7b70480b43b6c4deb0a22dcdc9c471d1958485abcf21")
-    ///         .unwrap();
-    /// let public_key = XOnlyPublicKey::from_str(
-    ///     "a8e76c3ace7829f9ee44cf9293309e21a1824bf1e57631d00685a1ed0b0bd8a2",
-    /// )
-    /// .unwrap();
-    ///
-    /// client.dislike(event_id, public_key).await.unwrap();
+    /// #   let alice = Keys::generate().public_key();
+    /// #   let bob = Keys::generate().public_key();
+    /// let metadatas = client.fetch_metadatas([alice, bob], None).await.unwrap();
+    /// for (public_key, metadata) in metadatas.into_iter() {
+    ///     println!("{public_key}: {:?}", metadata.name);
+    /// }
     /// # }
     /// ```
-    pub async fn dislike(
+    pub async fn fetch_metadatas<I>(
         &self,
-        event_id: EventId,
-        public_key: XOnlyPublicKey,
-    ) -> Result<EventId, Error> {
-        let builder = EventBuilder::new_reaction(event_id, public_key, "-");
-        self.send_event_builder(builder).await
+        public_keys: I,
+        timeout: Option<Duration>,
+    ) -> Result<HashMap<XOnlyPublicKey, Metadata>, Error>
+    where
+        I: IntoIterator<Item = XOnlyPublicKey>,
+    {
+        let public_keys: HashSet<XOnlyPublicKey> = public_keys.into_iter().collect();
+        if public_keys.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let database = self.database();
+
+        // Without stored events the cache can't be used
+        let ttl: Duration = self.opts.get_metadata_cache_ttl();
+        let cache: bool = database.opts().events && ttl > Duration::ZERO;
+
+        let to_fetch: Vec<XOnlyPublicKey> = if cache {
+            let fetched_at = self.metadata_fetched_at.read().await;
+            public_keys
+                .iter()
+                .filter(|p| match fetched_at.get(p) {
+                    Some(instant) => instant.elapsed() >= ttl,
+                    None => true,
+                })
+                .copied()
+                .collect()
+        } else {
+            public_keys.iter().copied().collect()
+        };
+
+        let mut newest: HashMap<XOnlyPublicKey, Event> = HashMap::new();
+
+        if !to_fetch.is_empty() {
+            let filter = Filter::new()
+                .authors(to_fetch.iter().copied())
+                .kind(Kind::Metadata);
+            let events: Vec<Event> = self.get_events_of(vec![filter], timeout).await?;
+            for event in events.into_iter() {
+                keep_newest(&mut newest, event);
+            }
+
+            if cache {
+                let now = Instant::now();
+                let mut fetched_at = self.metadata_fetched_at.write().await;
+                for public_key in to_fetch.into_iter() {
+                    fetched_at.insert(public_key, now);
+                }
+            }
+        }
+
+        // Events received by subscriptions may be newer than the fetched ones
+        let filter = Filter::new()
+            .authors(public_keys.iter().copied())
+            .kind(Kind::Metadata);
+        let stored: Vec<Event> = database
+            .query(vec![filter], Order::Desc)
+            .await
+            .unwrap_or_default();
+        for event in stored.into_iter() {
+            keep_newest(&mut newest, event);
+        }
+
+        let mut metadatas: HashMap<XOnlyPublicKey, Metadata> = HashMap::new();
+        for (public_key, event) in newest.into_iter() {
+            match Metadata::from_json(&event.content) {
+                Ok(metadata) => {
+                    metadatas.insert(public_key, metadata);
+                }
+                Err(e) => tracing::warn!("Invalid metadata of {public_key}: {e}"),
+            }
+        }
+        Ok(metadatas)
     }
 
-    /// React to an [`Event`]
+    /// Fetch the reply tree of a note
     ///
-    /// <https://github.com/nostr-protocol/nips/blob/master/25.md>
+    /// Ancestors are followed up to the root, then the replies to the root and to the event are fetched.
+    /// Referenced events not found on relays are returned as placeholders (see [`ThreadNode::is_missing`]).
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/10.md>
     ///
     /// # Example
     /// ```rust,no_run
-    /// use std::str::FromStr;
-    ///
     /// use nostr_sdk::prelude::*;
     ///
     /// # #[tokio::main]
     /// # async fn main() {
     /// #   let my_keys = Keys::generate();
     /// #   let client = Client::new(&my_keys);
-    /// let event_id =
-    ///     EventId::from_hex("3aded8d2194dc2fedb1d7b70480b43b6c4deb0a22dcdc9c471d1958485abcf21")
-    ///         .unwrap();
-    /// let public_key = XOnlyPublicKey::from_str(
-    ///     "a8e76c3ace7829f9ee44cf9293309e21a1824bf1e57631d00685a1ed0b0bd8a2",
-    /// )
-    /// .unwrap();
-    ///
-    /// client.reaction(event_id, public_key, "🐻").await.unwrap();
+    /// #   let event_id = EventId::all_zeros();
+    /// let thread = client.fetch_thread(event_id, None).await.unwrap();
+    /// for id in thread.ancestors().into_iter() {
+    ///     println!("Ancestor: {id}");
+    /// }
+    /// if let Some(node) = thread.focused() {
+    ///     println!("{} direct replies", node.replies.len());
+    /// }
     /// # }
     /// ```
-    pub async fn reaction<S>(
+    pub async fn fetch_thread(
         &self,
         event_id: EventId,
-        public_key: XOnlyPublicKey,
-        content: S,
-    ) -> Result<EventId, Error>
-    where
-        S: Into<String>,
-    {
-        let builder = EventBuilder::new_reaction(event_id, public_key, content);
-        self.send_event_builder(builder).await
+        timeout: Option<Duration>,
+    ) -> Result<Thread, Error> {
+        let filter = Filter::new().id(event_id);
+        let focus: Event = self
+            .get_events_of(vec![filter], timeout)
+            .await?
+            .into_iter()
+            .find(|e| e.id == event_id)
+            .ok_or(Error::EventNotFound(event_id))?;
+        let root_id: EventId = focus.thread_root().unwrap_or(event_id);
+
+        let mut queried: HashSet<EventId> = HashSet::from([event_id]);
+        let mut to_query: HashSet<EventId> = HashSet::from([root_id]);
+        to_query.extend(focus.reply_to());
+        let mut events: HashMap<EventId, Event> = HashMap::from([(event_id, focus)]);
+
+        // Replies to the root and to the event
+        let mut filters: Vec<Filter> = vec![Filter::new()
+            .kind(Kind::TextNote)
+            .events([root_id, event_id])];
+
+        for _ in 0..MAX_THREAD_ROUNDS {
+            to_query.retain(|id| !queried.contains(id) && !events.contains_key(id));
+            if !to_query.is_empty() {
+                filters.push(Filter::new().ids(to_query.iter().copied()));
+                queried.extend(to_query.drain());
+            }
+
+            if filters.is_empty() {
+                break;
+            }
+
+            let fetched: Vec<Event> = self
+                .get_events_of(std::mem::take(&mut filters), timeout)
+                .await?;
+            for event in fetched.into_iter() {
+                events.entry(event.id).or_insert(event);
+            }
+
+            // Follow the ancestors not found yet
+            to_query = events
+                .values()
+                .filter(|e| e.thread_root() == Some(root_id))
+                .filter_map(|e| e.reply_to())
+                .collect();
+        }
+
+        Ok(Thread::from_events(
+            event_id,
+            events.into_values().collect(),
+        ))
     }
 
-    /// Create new channel
+    /// Send encrypted direct message
     ///
-    /// <https://github.com/nostr-protocol/nips/blob/master/28.md>
-    pub async fn new_channel(&self, metadata: &Metadata) -> Result<EventId, Error> {
+    /// <https://github.com/nostr-protocol/nips/blob/master/04.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// let alice_pubkey = XOnlyPublicKey::from_bech32(
+    ///     "npub14f8usejl26twx0dhuxjh9cas7keav9vr0v8nvtwtrjqx3vycc76qqh9nsy",
+    /// )
+    /// .unwrap();
+    ///
+    /// client
+    ///     .send_direct_msg(alice_pubkey, "My first DM fro Nostr SDK!", None)
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[cfg(feature = "nip04")]
+    pub async fn send_direct_msg<S>(
+        &self,
+        receiver: XOnlyPublicKey,
+        msg: S,
+        reply_to: Option<EventId>,
+    ) -> Result<EventId, Error>
+    where
+        S: Into<String>,
+    {
+        let msg: String = msg.into();
+        let content: String = self
+            .signer()
+            .await?
+            .nip04_encrypt(receiver, msg.clone())
+            .await?;
+        let mut tags: Vec<Tag> = vec![Tag::public_key(receiver)];
+        if let Some(reply_to) = reply_to {
+            tags.push(Tag::event(reply_to));
+        }
+        let builder = EventBuilder::new(Kind::EncryptedDirectMessage, content, tags);
+        let event: Event = self.internal_sign_event_builder(builder).await?;
+        let message: Option<DirectMessage> = DirectMessage::from_nip04(&event, msg);
+        let event_id: EventId = self.send_event(event.clone()).await?;
+
+        if let Some(message) = message {
+            self.conversations.insert(&event.pubkey, message).await;
+        }
+
+        Ok(event_id)
+    }
+
+    /// Send private direct message
+    ///
+    /// The message is sealed and gift wrapped for the `receiver` and for the client signer (to keep a copy of the sent messages).
+    /// The receiver copy is sent to the receiver's DM relays, if published, otherwise to the client relays.
+    /// Return the ID of the receiver gift wrap.
+    ///
+    /// To receive the private messages, see [`Options::unwrap_gift_wraps`] or [`Client::unwrap_gift_wrap`].
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/17.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// let alice_pubkey = XOnlyPublicKey::from_bech32(
+    ///     "npub14f8usejl26twx0dhuxjh9cas7keav9vr0v8nvtwtrjqx3vycc76qqh9nsy",
+    /// )
+    /// .unwrap();
+    ///
+    /// client
+    ///     .send_private_msg(alice_pubkey, "My first private message!", None)
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[cfg(feature = "nip59")]
+    pub async fn send_private_msg<S>(
+        &self,
+        receiver: XOnlyPublicKey,
+        message: S,
+        reply_to: Option<EventId>,
+    ) -> Result<EventId, Error>
+    where
+        S: Into<String>,
+    {
+        let signer: ClientSigner = self.signer().await?;
+        let public_key: XOnlyPublicKey = signer.get_public_key().await?;
+        let rumor: UnsignedEvent = EventBuilder::private_msg_rumor(receiver, message, reply_to)
+            .to_unsigned_event(public_key);
+
+        // Send to the receiver
+        let gift_wrap: Event = seal_and_wrap(
+            &signer,
+            public_key,
+            receiver,
+            &rumor,
+            self.opts.get_gift_wrap_timestamp_tweak(),
+        )
+        .await?;
+        let gift_wrap_id: EventId = gift_wrap.id;
+        let relays: Vec<Url> = self.get_dm_relays(receiver).await?;
+        if relays.is_empty() {
+            self.send_event(gift_wrap).await?;
+        } else {
+            for url in relays.iter() {
+                if self.add_relay(url.clone()).await? {
+                    let relay: Relay = self.relay(url.clone()).await?;
+                    self.pool.connect_relay(&relay, true).await;
+                }
+            }
+            let results = self.send_event_to_many(relays, gift_wrap).await?;
+            if !results.values().any(|res| res.is_ok()) {
+                return Err(Error::RelayPool(RelayPoolError::EventNotPublished(
+                    gift_wrap_id,
+                )));
+            }
+        }
+
+        // Keep a copy for self
+        let gift_wrap: Event = seal_and_wrap(
+            &signer,
+            public_key,
+            public_key,
+            &rumor,
+            self.opts.get_gift_wrap_timestamp_tweak(),
+        )
+        .await?;
+        self.send_event(gift_wrap).await?;
+
+        if let Some(message) = DirectMessage::from_rumor(&rumor) {
+            self.conversations.insert(&public_key, message).await;
+        }
+
+        Ok(gift_wrap_id)
+    }
+
+    /// Get the DM relays of `public_key`
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/17.md>
+    #[cfg(feature = "nip59")]
+    async fn get_dm_relays(&self, public_key: XOnlyPublicKey) -> Result<Vec<Url>, Error> {
+        let filter: Filter = Filter::new()
+            .author(public_key)
+            .kind(Kind::DirectMessageRelays)
+            .limit(1);
+        let relays: Vec<Url> = match self.get_event_of(filter).await? {
+            Some(event) => event
+                .tags
+                .iter()
+                .filter_map(|tag| match tag {
+                    Tag::Relay(url) => Url::try_from(url.clone()).ok(),
+                    _ => None,
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        Ok(relays)
+    }
+
+    /// Unwrap a gift wrap addressed to the client signer
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/59.md>
+    #[cfg(feature = "nip59")]
+    pub async fn unwrap_gift_wrap(&self, gift_wrap: &Event) -> Result<UnwrappedGift, Error> {
+        let signer: ClientSigner = self.signer().await?;
+        unwrap_gift_wrap(&signer, gift_wrap).await
+    }
+
+    /// Get the [`Outbox`] of the events not yet published
+    ///
+    /// Enabled with [`Options::outbox`].
+    pub fn outbox(&self) -> Outbox {
+        self.outbox.clone()
+    }
+
+    /// Send the pending events of the [`Outbox`] to the connected relays
+    ///
+    /// Return the number of events sent. The pending events are also sent automatically when a relay connects.
+    pub async fn flush_outbox(&self) -> usize {
+        let opts = RelaySendOptions::new().timeout(self.opts.send_timeout);
+        let mut sent: usize = 0;
+        for (url, relay) in self.pool.relays().await.into_iter() {
+            if self.outbox.is_empty().await {
+                break;
+            }
+            if relay.status().await == RelayStatus::Connected {
+                sent += self.outbox.flush(&self.pool, url, opts).await;
+            }
+        }
+        sent
+    }
+
+    /// Publish the signed event at `publish_at`
+    ///
+    /// Events scheduled in the past are published immediately. Scheduling again the same event
+    /// replaces the publication time. If [`Options::outbox`] is enabled, the events that couldn't be
+    /// published to any relay are added to the [`Outbox`].
+    ///
+    /// The schedule is kept in memory: to survive restarts, store the [`Client::scheduled_events`]
+    /// and schedule them again at startup.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// let publish_at = Timestamp::now() + 3600u64;
+    /// let event = EventBuilder::new_text_note("Good morning!", [])
+    ///     .custom_created_at(publish_at)
+    ///     .to_event(&my_keys)
+    ///     .unwrap();
+    /// client.schedule_event(event, publish_at).await;
+    /// # }
+    /// ```
+    pub async fn schedule_event(&self, event: Event, publish_at: Timestamp) -> EventId {
+        let event_id: EventId = event.id;
+        let opts = RelaySendOptions::new()
+            .skip_disconnected(self.opts.get_skip_disconnected_relays())
+            .timeout(self.opts.send_timeout);
+        let outbox: Option<Outbox> = if self.opts.get_outbox() {
+            Some(self.outbox.clone())
+        } else {
+            None
+        };
+        self.scheduler
+            .schedule(self.pool.clone(), outbox, event, publish_at, opts)
+            .await;
+        event_id
+    }
+
+    /// Get the events waiting to be published, ordered by publication time
+    pub async fn scheduled_events(&self) -> Vec<ScheduledEvent> {
+        self.scheduler.list().await
+    }
+
+    /// Cancel a scheduled event
+    ///
+    /// Return `false` if the event is not scheduled (or already published).
+    pub async fn cancel_scheduled_event(&self, event_id: &EventId) -> bool {
+        self.scheduler.cancel(event_id).await
+    }
+
+    /// Get the direct message [`Conversations`]
+    pub fn conversations(&self) -> Conversations {
+        self.conversations.clone()
+    }
+
+    /// Fetch the direct messages sent and received by the client signer and add them to the [`Conversations`]
+    ///
+    /// Both NIP04 messages and NIP17 private messages (gift wrapped) are fetched, according to the enabled features.
+    /// Messages that can't be decrypted are skipped.
+    /// Return the number of new messages.
+    ///
+    /// If timeout is set to `None`, the default from [`Options`] will be used.
+    #[allow(unused_variables)]
+    pub async fn sync_conversations(&self, timeout: Option<Duration>) -> Result<usize, Error> {
+        let signer: ClientSigner = self.signer().await?;
+        let public_key: XOnlyPublicKey = signer.get_public_key().await?;
+
+        let filters: Vec<Filter> = vec![
+            #[cfg(feature = "nip04")]
+            Filter::new()
+                .kind(Kind::EncryptedDirectMessage)
+                .author(public_key),
+            #[cfg(feature = "nip04")]
+            Filter::new()
+                .kind(Kind::EncryptedDirectMessage)
+                .pubkey(public_key),
+            #[cfg(feature = "nip59")]
+            Filter::new().kind(Kind::GiftWrap).pubkey(public_key),
+        ];
+
+        if filters.is_empty() {
+            return Ok(0);
+        }
+
+        let mut new: usize = 0;
+        for event in self.get_events_of(filters, timeout).await?.into_iter() {
+            let message: Option<DirectMessage> = match event.kind {
+                #[cfg(feature = "nip04")]
+                Kind::EncryptedDirectMessage => decrypt_direct_msg(&signer, public_key, &event)
+                    .await
+                    .ok()
+                    .flatten(),
+                #[cfg(feature = "nip59")]
+                Kind::GiftWrap => unwrap_gift_wrap(&signer, &event)
+                    .await
+                    .ok()
+                    .and_then(|UnwrappedGift { rumor, .. }| DirectMessage::from_rumor(&rumor)),
+                _ => None,
+            };
+            if let Some(message) = message {
+                if self.conversations.insert(&public_key, message).await {
+                    new += 1;
+                }
+            }
+        }
+
+        Ok(new)
+    }
+
+    /// Repost event by ID
+    ///
+    /// The reposted event is not embedded: use [`Client::repost`] if the event is available.
+    pub async fn repost_event(
+        &self,
+        event_id: EventId,
+        public_key: XOnlyPublicKey,
+    ) -> Result<EventId, Error> {
+        let builder = EventBuilder::new(
+            Kind::Repost,
+            "",
+            [Tag::event(event_id), Tag::public_key(public_key)],
+        );
+        self.send_event_builder(builder).await
+    }
+
+    /// Repost an [`Event`]
+    ///
+    /// Text notes are reposted with a [`Kind::Repost`], the other events with a [`Kind::GenericRepost`]
+    /// (see [`EventBuilder::repost_embedded`]). The reposted event is embedded in the `content`.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/18.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// #   let event = EventBuilder::new_text_note("GM", []).to_event(&my_keys).unwrap();
+    /// let relay_url = UncheckedUrl::from("wss://relay.damus.io");
+    /// client.repost(&event, Some(relay_url)).await.unwrap();
+    /// # }
+    /// ```
+    pub async fn repost(
+        &self,
+        event: &Event,
+        relay_url: Option<UncheckedUrl>,
+    ) -> Result<EventId, Error> {
+        let builder = EventBuilder::repost_embedded(event, relay_url);
+        self.send_event_builder(builder).await
+    }
+
+    /// Quote an [`Event`]
+    ///
+    /// Publish a text note with the `comment` followed by the `nostr:` URI of the event.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/18.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// #   let event = EventBuilder::new_text_note("GM", []).to_event(&my_keys).unwrap();
+    /// client.quote(&event, "So true").await.unwrap();
+    /// # }
+    /// ```
+    pub async fn quote<S>(&self, event: &Event, comment: S) -> Result<EventId, Error>
+    where
+        S: Into<String>,
+    {
+        let nevent = Nip19Event {
+            event_id: event.id,
+            author: Some(event.pubkey),
+            relays: Vec::new(),
+        };
+        let comment: String = comment.into();
+        let uri: String = nevent.to_nostr_uri()?;
+        let content: String = if comment.is_empty() {
+            uri
+        } else {
+            format!("{comment}\n\n{uri}")
+        };
+        let builder = EventBuilder::quote(content, event, None);
+        self.send_event_builder(builder).await
+    }
+
+    /// Delete event
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/09.md>
+    pub async fn delete_event<T>(&self, id: T) -> Result<EventId, Error>
+    where
+        T: Into<EventIdOrCoordinate>,
+    {
+        let builder = EventBuilder::delete([id]);
+        self.send_event_builder(builder).await
+    }
+
+    /// Delete many events with a single [`Kind::EventDeletion`]
+    ///
+    /// Relays delete only the events authored by the signer of the deletion.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/09.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// #   let first = EventId::all_zeros();
+    /// #   let second = EventId::all_zeros();
+    /// client
+    ///     .delete_events([first, second], Some("posted by mistake"))
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub async fn delete_events<I, T, S>(&self, ids: I, reason: Option<S>) -> Result<EventId, Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<EventIdOrCoordinate>,
+        S: Into<String>,
+    {
+        let builder = match reason {
+            Some(reason) => EventBuilder::delete_with_reason(ids, reason),
+            None => EventBuilder::delete(ids),
+        };
+        self.send_event_builder(builder).await
+    }
+
+    /// Delete all the versions of a replaceable or parameterized replaceable event
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/09.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// let coordinate = Coordinate::new(Kind::LongFormTextNote, my_keys.public_key())
+    ///     .identifier("my-article");
+    /// client
+    ///     .delete_by_coordinate(coordinate, None::<String>)
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub async fn delete_by_coordinate<S>(
+        &self,
+        coordinate: Coordinate,
+        reason: Option<S>,
+    ) -> Result<EventId, Error>
+    where
+        S: Into<String>,
+    {
+        self.delete_events([coordinate], reason).await
+    }
+
+    /// Like event
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/25.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::str::FromStr;
+    ///
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// let event_id =
+    ///     EventId::from_hex("3aded8d2194dc2fedb1d7b70480b43b6c4deb0a22dcdc9c471d1958485abcf21")
+    ///         .unwrap();
+    /// let public_key = XOnlyPublicKey::from_str(
+    ///     "a8e76c3ace7829f9ee44cf9293309e21a1824bf1e57631d00685a1ed0b0bd8a2",
+    /// )
+    /// .unwrap();
+    ///
+    /// client.like(event_id, public_key).await.unwrap();
+    /// # }
+    /// ```
+    pub async fn like(
+        &self,
+        event_id: EventId,
+        public_key: XOnlyPublicKey,
+    ) -> Result<EventId, Error> {
+        let builder = EventBuilder::new_reaction(event_id, public_key, "+");
+        self.send_event_builder(builder).await
+    }
+
+    /// Disike event
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/25.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::str::FromStr;
+    ///
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// let event_id =
+    ///     EventId::from_hex("3aded8d2194dc2fedb1d7b70480b43b6c4deb0a22dcdc9c471d1958485abcf21")
+    ///         .unwrap();
+    /// let public_key = XOnlyPublicKey::from_str(
+    ///     "a8e76c3ace7829f9ee44cf9293309e21a1824bf1e57631d00685a1ed0b0bd8a2",
+    /// )
+    /// .unwrap();
+    ///
+    /// client.dislike(event_id, public_key).await.unwrap();
+    /// # }
+    /// ```
+    pub async fn dislike(
+        &self,
+        event_id: EventId,
+        public_key: XOnlyPublicKey,
+    ) -> Result<EventId, Error> {
+        let builder = EventBuilder::new_reaction(event_id, public_key, "-");
+        self.send_event_builder(builder).await
+    }
+
+    /// React to an [`Event`]
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/25.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::str::FromStr;
+    ///
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// let event_id =
+    ///     EventId::from_hex("3aded8d2194dc2fedb1d7b70480b43b6c4deb0a22dcdc9c471d1958485abcf21")
+    ///         .unwrap();
+    /// let public_key = XOnlyPublicKey::from_str(
+    ///     "a8e76c3ace7829f9ee44cf9293309e21a1824bf1e57631d00685a1ed0b0bd8a2",
+    /// )
+    /// .unwrap();
+    ///
+    /// client.reaction(event_id, public_key, "🐻").await.unwrap();
+    /// # }
+    /// ```
+    pub async fn reaction<S>(
+        &self,
+        event_id: EventId,
+        public_key: XOnlyPublicKey,
+        content: S,
+    ) -> Result<EventId, Error>
+    where
+        S: Into<String>,
+    {
+        let builder = EventBuilder::new_reaction(event_id, public_key, content);
+        self.send_event_builder(builder).await
+    }
+
+    /// React to an [`Event`]
+    ///
+    /// The reaction can be a [`Reaction`] (i.e. a custom emoji) or the `content` (`+`, `-` or an emoji).
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/25.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// #   let event = EventBuilder::new_text_note("GM", []).to_event(&my_keys).unwrap();
+    /// client.react(&event, "🤙").await.unwrap();
+    ///
+    /// let emoji = Reaction::custom_emoji(
+    ///     "soapbox",
+    ///     UncheckedUrl::from("https://example.com/soapbox.png"),
+    /// );
+    /// client.react(&event, emoji).await.unwrap();
+    /// # }
+    /// ```
+    pub async fn react<R>(&self, event: &Event, reaction: R) -> Result<EventId, Error>
+    where
+        R: Into<Reaction>,
+    {
+        let builder = EventBuilder::react(event, reaction.into());
+        self.send_event_builder(builder).await
+    }
+
+    /// Get the reactions to an event, aggregated by content
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/25.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// #   let event_id = EventId::all_zeros();
+    /// let reactions = client.reactions_of(event_id, None).await.unwrap();
+    /// for (reaction, public_keys) in reactions.iter() {
+    ///     println!("{reaction}: {}", public_keys.len());
+    /// }
+    /// # }
+    /// ```
+    pub async fn reactions_of(
+        &self,
+        event_id: EventId,
+        timeout: Option<Duration>,
+    ) -> Result<Reactions, Error> {
+        let filter = Filter::new().kind(Kind::Reaction).event(event_id);
+        let events: Vec<Event> = self.get_events_of(vec![filter], timeout).await?;
+        Ok(Reactions::from_events(event_id, events.iter()))
+    }
+
+    /// Report a user or an event
+    ///
+    /// If `mute` is `true`, the target is also added to the private items of the mute list:
+    /// the user, or the thread of the event.
+    /// Call [`Client::sync_mute_list`] to use the updated mute list for [`Client::is_muted`].
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/56.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// #   let event = EventBuilder::new_text_note("Buy now!", []).to_event(&my_keys).unwrap();
+    /// client
+    ///     .report(&event, Report::Spam, "Scam link", true)
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub async fn report<T, S>(
+        &self,
+        target: T,
+        report: Report,
+        reason: S,
+        mute: bool,
+    ) -> Result<EventId, Error>
+    where
+        T: Into<ReportTarget>,
+        S: Into<String>,
+    {
+        let target: ReportTarget = target.into();
+        let builder = EventBuilder::report(target.tags(report), reason);
+        let event_id: EventId = self.send_event_builder(builder).await?;
+
+        if mute {
+            let item: Tag = match target {
+                ReportTarget::PublicKey(public_key) => Tag::public_key(public_key),
+                ReportTarget::Event { event_id, .. } => Tag::event(event_id),
+            };
+            self.add_to_list(List::Mute, [item], true).await?;
+        }
+
+        Ok(event_id)
+    }
+
+    /// Highlight a portion of an event, of an article or of a non-nostr content
+    ///
+    /// The `source` can be an [`Event`] (the author is tagged), an [`EventId`],
+    /// a [`Coordinate`] or a URL ([`HighlightSource::Url`]).
+    /// Use [`EventBuilder::highlight`] to add the surrounding text.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/84.md>
+    pub async fn highlight<T, S, C>(
+        &self,
+        source: T,
+        selection: S,
+        comment: Option<C>,
+    ) -> Result<EventId, Error>
+    where
+        T: Into<HighlightSource>,
+        S: Into<String>,
+        C: Into<String>,
+    {
+        let mut highlight = Highlight::new(selection, Some(source.into()));
+        if let Some(comment) = comment {
+            highlight = highlight.comment(comment);
+        }
+        let builder = EventBuilder::highlight(highlight);
+        self.send_event_builder(builder).await
+    }
+
+    /// Find the applications able to handle the events of `kind`, recommended by the follows
+    ///
+    /// The handlers are ranked by number of follows recommending them.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/89.md>
+    pub async fn find_handlers(
+        &self,
+        kind: Kind,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<AppHandler>, Error> {
+        let follows: Vec<XOnlyPublicKey> = self.get_contact_list_public_keys(timeout).await?;
+        if follows.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let filter = Filter::new()
+            .authors(follows)
+            .kind(Kind::HandlerRecommendation)
+            .identifier(kind.as_u64().to_string());
+        let recommendations: Vec<Event> = self.get_events_of(vec![filter], timeout).await?;
+
+        let coordinates: Vec<Coordinate> = recommendations
+            .iter()
+            .filter_map(|event| HandlerRecommendation::from_event(event).ok())
+            .flat_map(|recommendation| recommendation.handlers)
+            .map(|handler| handler.coordinate)
+            .filter(|coordinate| coordinate.kind == Kind::HandlerInformation)
+            .collect();
+        if coordinates.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let filter = Filter::new()
+            .authors(coordinates.iter().map(|coordinate| coordinate.pubkey))
+            .kind(Kind::HandlerInformation)
+            .identifiers(
+                coordinates
+                    .into_iter()
+                    .map(|coordinate| coordinate.identifier),
+            );
+        let handlers: Vec<Event> = self.get_events_of(vec![filter], timeout).await?;
+
+        Ok(app_handler::rank(kind, &recommendations, &handlers))
+    }
+
+    /// Award a badge to the `recipients`
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/58.md>
+    pub async fn award_badge<I>(&self, definition: &Event, recipients: I) -> Result<EventId, Error>
+    where
+        I: IntoIterator<Item = XOnlyPublicKey>,
+    {
+        let builder =
+            EventBuilder::award_badge(definition, recipients.into_iter().map(Tag::public_key))?;
+        self.send_event_builder(builder).await
+    }
+
+    /// Accept badge awards, adding them to the profile badges of the signer
+    ///
+    /// The latest profile badges are fetched before adding the awards, to keep the ones already accepted.
+    /// The new badges are appended in order; the awards already in the profile badges are skipped.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/58.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// let filter = Filter::new()
+    ///     .kind(Kind::BadgeAward)
+    ///     .pubkey(my_keys.public_key());
+    /// let awards = client
+    ///     .get_events_of(vec![filter], Some(Duration::from_secs(10)))
+    ///     .await
+    ///     .unwrap();
+    /// client.set_profile_badges(awards).await.unwrap();
+    /// # }
+    /// ```
+    pub async fn set_profile_badges<I>(&self, awards: I) -> Result<EventId, Error>
+    where
+        I: IntoIterator<Item = Event>,
+    {
+        let public_key: XOnlyPublicKey = self.signer_public_key().await?;
+        let mut tags: Vec<Tag> = match self.get_latest_profile_badges(public_key, None).await? {
+            Some(event) => event.tags,
+            None => vec![Tag::Identifier(
+                nip58::PROFILE_BADGES_IDENTIFIER.to_string(),
+            )],
+        };
+
+        for award in awards.into_iter() {
+            let [a_tag, e_tag] = badges::profile_badge_tags(&award, &public_key)?;
+            let accepted: bool = tags.iter().any(|tag| match tag {
+                Tag::Event { event_id, .. } => event_id == &award.id,
+                _ => false,
+            });
+            if !accepted {
+                tags.push(a_tag);
+                tags.push(e_tag);
+            }
+        }
+
+        let builder = EventBuilder::new(Kind::ProfileBadges, "", tags);
+        self.send_event_builder(builder).await
+    }
+
+    /// Get the badges accepted by `public_key`, in the order of its profile badges
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/58.md>
+    pub async fn fetch_badges(
+        &self,
+        public_key: XOnlyPublicKey,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<Badge>, Error> {
+        let profile_badges: Event =
+            match self.get_latest_profile_badges(public_key, timeout).await? {
+                Some(event) => event,
+                None => return Ok(Vec::new()),
+            };
+
+        let pairs: Vec<(Coordinate, EventId)> = nip58::extract_profile_badges(&profile_badges);
+        if pairs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut filters: Vec<Filter> = pairs
+            .iter()
+            .map(|(coordinate, ..)| Filter::from(coordinate.clone()))
+            .collect();
+        filters.push(
+            Filter::new()
+                .kind(Kind::BadgeAward)
+                .ids(pairs.iter().map(|(_, award_id)| *award_id)),
+        );
+        let events: Vec<Event> = self.get_events_of(filters, timeout).await?;
+        Ok(badges::collect(&public_key, pairs, &events))
+    }
+
+    async fn get_latest_profile_badges(
+        &self,
+        public_key: XOnlyPublicKey,
+        timeout: Option<Duration>,
+    ) -> Result<Option<Event>, Error> {
+        let filter = Filter::new()
+            .author(public_key)
+            .kind(Kind::ProfileBadges)
+            .identifier(nip58::PROFILE_BADGES_IDENTIFIER);
+        let events: Vec<Event> = self.get_events_of(vec![filter], timeout).await?;
+        Ok(events.into_iter().max_by_key(|event| event.created_at))
+    }
+
+    /// Set user status
+    ///
+    /// Use an empty `content` to clear the status.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/38.md>
+    pub async fn set_status<S>(
+        &self,
+        status_type: StatusType,
+        content: S,
+        expiration: Option<Timestamp>,
+        link: Option<String>,
+    ) -> Result<EventId, Error>
+    where
+        S: Into<String>,
+    {
+        let builder = EventBuilder::user_status(status_type, content, expiration, link);
+        self.send_event_builder(builder).await
+    }
+
+    /// Get the current statuses of `public_key`
+    ///
+    /// Only the latest status of each type is considered: cleared and expired statuses are skipped.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/38.md>
+    pub async fn fetch_status(
+        &self,
+        public_key: XOnlyPublicKey,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<UserStatus>, Error> {
+        let filter = Filter::new().author(public_key).kind(Kind::UserStatus);
+        let events: Vec<Event> = self.get_events_of(vec![filter], timeout).await?;
+
+        let mut latest: HashMap<StatusType, UserStatus> = HashMap::new();
+        for event in events.iter() {
+            if let Ok(status) = UserStatus::from_event(event) {
+                match latest.get(&status.status_type) {
+                    Some(current) if current.created_at >= status.created_at => (),
+                    _ => {
+                        latest.insert(status.status_type.clone(), status);
+                    }
+                }
+            }
+        }
+
+        let now = Timestamp::now();
+        let mut statuses: Vec<UserStatus> = latest
+            .into_values()
+            .filter(|status| status.is_active(now))
+            .collect();
+        statuses.sort_by(|a, b| a.status_type.cmp(&b.status_type));
+        Ok(statuses)
+    }
+
+    /// Create new channel
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/28.md>
+    pub async fn new_channel(&self, metadata: &Metadata) -> Result<EventId, Error> {
         let builder = EventBuilder::new_channel(metadata);
         self.send_event_builder(builder).await
     }
@@ -1200,6 +3928,64 @@ impl Client {
         self.send_event_builder(builder).await
     }
 
+    /// Reply to a channel message
+    ///
+    /// The message is tagged with the channel as root, the replied message as reply and its author.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/28.md>
+    pub async fn reply_channel_msg<S>(
+        &self,
+        channel_id: EventId,
+        relay_url: Url,
+        reply_to: &Event,
+        msg: S,
+    ) -> Result<EventId, Error>
+    where
+        S: Into<String>,
+    {
+        let builder = EventBuilder::channel_msg_reply(channel_id, relay_url, reply_to, msg);
+        self.send_event_builder(builder).await
+    }
+
+    /// Get a channel, with the latest metadata of its creator applied
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/28.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// # let channel_id = EventId::all_zeros();
+    /// let channel = client
+    ///     .fetch_channel(channel_id, Some(Duration::from_secs(10)))
+    ///     .await
+    ///     .unwrap();
+    /// println!("{:?}", channel.metadata.name);
+    /// # }
+    /// ```
+    pub async fn fetch_channel(
+        &self,
+        channel_id: EventId,
+        timeout: Option<Duration>,
+    ) -> Result<Channel, Error> {
+        let filters: Vec<Filter> = vec![
+            Filter::new().id(channel_id).kind(Kind::ChannelCreation),
+            Filter::new().kind(Kind::ChannelMetadata).event(channel_id),
+        ];
+        let events: Vec<Event> = self.get_events_of(filters, timeout).await?;
+        let creation: &Event = events
+            .iter()
+            .find(|event| event.id == channel_id && event.kind == Kind::ChannelCreation)
+            .ok_or(Error::EventNotFound(channel_id))?;
+        Ok(Channel::from_events(creation, events.iter())?)
+    }
+
     /// Hide channel message
     ///
     /// <https://github.com/nostr-protocol/nips/blob/master/28.md>
@@ -1217,47 +4003,436 @@ impl Client {
 
     /// Mute channel user
     ///
-    /// <https://github.com/nostr-protocol/nips/blob/master/28.md>
-    pub async fn mute_channel_user<S>(
+    /// <https://github.com/nostr-protocol/nips/blob/master/28.md>
+    pub async fn mute_channel_user<S>(
+        &self,
+        pubkey: XOnlyPublicKey,
+        reason: Option<S>,
+    ) -> Result<EventId, Error>
+    where
+        S: Into<String>,
+    {
+        let builder = EventBuilder::mute_channel_user(pubkey, reason);
+        self.send_event_builder(builder).await
+    }
+
+    /// Authenticate to relay
+    ///
+    /// The `AUTH` event is signed by the [`Client::auth_signer`] and sent only to `relay`.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/42.md>
+    pub async fn auth<S>(&self, challenge: S, relay: Url) -> Result<EventId, Error>
+    where
+        S: Into<String>,
+    {
+        let signer: ClientSigner = self.auth_signer().await?;
+        let event: Event = sign_auth_event(signer, challenge.into(), relay.clone()).await?;
+        let event_id: EventId = event.id;
+        self.send_msg_to(relay, ClientMessage::new_auth(event))
+            .await?;
+        Ok(event_id)
+    }
+
+    /// Create zap receipt event
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/57.md>
+    #[cfg(feature = "nip57")]
+    pub async fn new_zap_receipt<S>(
+        &self,
+        bolt11: S,
+        preimage: Option<S>,
+        zap_request: Event,
+    ) -> Result<EventId, Error>
+    where
+        S: Into<String>,
+    {
+        let builder = EventBuilder::new_zap_receipt(bolt11, preimage, zap_request);
+        self.send_event_builder(builder).await
+    }
+
+    /// Publish a long-form article, or a draft if [`ArticleBuilder::draft`] is set
+    ///
+    /// If not set, the first publication timestamp of a non-draft article is set to now.
+    /// To edit an article, use [`ArticleBuilder::from_event`] to keep the same identifier.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/23.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// let article = ArticleBuilder::new("hello-nostr", "# Hello\n\nMy first article")
+    ///     .title("Hello Nostr")
+    ///     .summary("First article")
+    ///     .hashtags(["nostr"]);
+    /// client.publish_article(article).await.unwrap();
+    /// # }
+    /// ```
+    pub async fn publish_article(&self, mut article: ArticleBuilder) -> Result<EventId, Error> {
+        if !article.draft && article.published_at.is_none() {
+            article.published_at = Some(Timestamp::now());
+        }
+        let builder = EventBuilder::article(article);
+        self.send_event_builder(builder).await
+    }
+
+    /// Publish a draft article and delete the draft
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/23.md>
+    pub async fn promote_article_draft(&self, draft: &Event) -> Result<EventId, Error> {
+        let article = ArticleBuilder::from_event(draft)?;
+        if !article.draft {
+            return Err(Error::NIP23(nip23::Error::WrongKind));
+        }
+
+        let public_key: XOnlyPublicKey = self.signer_public_key().await?;
+        let coordinate: Coordinate = article.coordinate(public_key);
+        let event_id: EventId = self.publish_article(article.draft(false)).await?;
+        self.delete_by_coordinate(coordinate, None::<String>)
+            .await?;
+        Ok(event_id)
+    }
+
+    /// Save a draft
+    ///
+    /// The unsigned event is NIP44 encrypted to self and published as a draft, so it can be synced
+    /// across devices. Return the identifier of the draft, to update it with [`Client::update_draft`].
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/37.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// let builder = EventBuilder::new_text_note("Work in progress...", []);
+    /// let identifier = client.save_draft(builder).await.unwrap();
+    ///
+    /// let builder = EventBuilder::new_text_note("Work in progress... done!", []);
+    /// client.update_draft(&identifier, builder).await.unwrap();
+    ///
+    /// for draft in client.get_drafts(None).await.unwrap() {
+    ///     println!("{}: {}", draft.identifier, draft.event.content);
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "nip44")]
+    pub async fn save_draft(&self, builder: EventBuilder) -> Result<String, Error> {
+        let identifier: String = draft::generate_identifier();
+        self.update_draft(&identifier, builder).await?;
+        Ok(identifier)
+    }
+
+    /// Replace the draft with `identifier`
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/37.md>
+    #[cfg(feature = "nip44")]
+    pub async fn update_draft(
+        &self,
+        identifier: &str,
+        builder: EventBuilder,
+    ) -> Result<EventId, Error> {
+        let signer: ClientSigner = self.signer().await?;
+        let public_key: XOnlyPublicKey = signer.get_public_key().await?;
+        let unsigned: UnsignedEvent = builder.to_unsigned_event(public_key);
+        let kind: Kind = unsigned.kind;
+        let content: String = signer.nip44_encrypt(public_key, unsigned.as_json()).await?;
+        let builder = EventBuilder::draft(identifier, kind, content);
+        self.send_event_builder(builder).await
+    }
+
+    /// Delete the draft with `identifier`, by blanking its content
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/37.md>
+    #[cfg(feature = "nip44")]
+    pub async fn delete_draft(&self, identifier: &str, draft_kind: Kind) -> Result<EventId, Error> {
+        let builder = EventBuilder::draft(identifier, draft_kind, "");
+        self.send_event_builder(builder).await
+    }
+
+    /// Decrypt a draft event
+    ///
+    /// The draft must be authored by the [`ClientSigner`].
+    #[cfg(feature = "nip44")]
+    pub async fn decrypt_draft(&self, event: &Event) -> Result<Draft, Error> {
+        let signer: ClientSigner = self.signer().await?;
+        let public_key: XOnlyPublicKey = signer.get_public_key().await?;
+        if event.kind != Kind::Draft || event.pubkey != public_key || event.content.is_empty() {
+            return Err(Error::InvalidDraft);
+        }
+
+        let identifier: String = event.identifier().ok_or(Error::InvalidDraft)?.to_string();
+        let json: String = signer
+            .nip44_decrypt(public_key, event.content.clone())
+            .await?;
+        let unsigned: UnsignedEvent =
+            UnsignedEvent::from_json(json).map_err(|_| Error::InvalidDraft)?;
+        Ok(Draft {
+            identifier,
+            event: unsigned,
+            updated_at: event.created_at,
+        })
+    }
+
+    /// Get the drafts of the [`ClientSigner`] from the relays, newest first
+    ///
+    /// Deleted drafts and drafts that can't be decrypted are skipped.
+    ///
+    /// If timeout is set to `None`, the default from [`Options`] will be used.
+    #[cfg(feature = "nip44")]
+    pub async fn get_drafts(&self, timeout: Option<Duration>) -> Result<Vec<Draft>, Error> {
+        let public_key: XOnlyPublicKey = self.signer_public_key().await?;
+        let filter: Filter = Filter::new().author(public_key).kind(Kind::Draft);
+        let events: Vec<Event> = self.get_events_of(vec![filter], timeout).await?;
+
+        // Keep only the newest version of each draft
+        let mut newest: HashMap<String, Event> = HashMap::new();
+        for event in events.into_iter() {
+            if let Some(identifier) = event.identifier() {
+                match newest.get(identifier) {
+                    Some(e) if e.created_at >= event.created_at => (),
+                    _ => {
+                        newest.insert(identifier.to_string(), event);
+                    }
+                }
+            }
+        }
+
+        let mut drafts: Vec<Draft> = Vec::with_capacity(newest.len());
+        for event in newest.into_values() {
+            if event.content.is_empty() {
+                continue;
+            }
+
+            match self.decrypt_draft(&event).await {
+                Ok(draft) => drafts.push(draft),
+                Err(e) => tracing::warn!("Impossible to decrypt draft {}: {e}", event.id),
+            }
+        }
+        drafts.sort_by_key(|d| std::cmp::Reverse(d.updated_at));
+        Ok(drafts)
+    }
+
+    /// Zap a profile or an event
+    ///
+    /// Resolve the LNURL of the recipient from the metadata, send the signed zap request to the LNURL server
+    /// and return the `bolt11` invoice.
+    /// If a wallet is set with [`Options::nostr_wallet_connect`], the invoice is paid before returning.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/57.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// let public_key = XOnlyPublicKey::from_bech32(
+    ///     "npub1drvpzev3syqt0kjrls50050uzf25gehpz9vgdw08hvex7e0vgfeq0eseet",
+    /// )
+    /// .unwrap();
+    /// let invoice: String = client
+    ///     .zap(public_key, 1000, Some(String::from("Great work!")))
+    ///     .await
+    ///     .unwrap();
+    /// println!("Pay {invoice}");
+    /// # }
+    /// ```
+    #[cfg(feature = "nip57")]
+    pub async fn zap<T>(
+        &self,
+        to: T,
+        amount_msat: u64,
+        comment: Option<String>,
+    ) -> Result<String, Error>
+    where
+        T: Into<ZapEntity>,
+    {
+        #[cfg(not(target_arch = "wasm32"))]
+        let proxy = self.opts.proxy;
+        #[cfg(target_arch = "wasm32")]
+        let proxy = None;
+
+        // Get recipient
+        let (public_key, event_id): (XOnlyPublicKey, Option<EventId>) = match to.into() {
+            ZapEntity::PublicKey(public_key) => (public_key, None),
+            ZapEntity::Event(event_id) => {
+                let filter: Filter = Filter::new().id(event_id);
+                let event: Event = self
+                    .get_event_of(filter)
+                    .await?
+                    .ok_or(ZapperError::EventNotFound)?;
+                (event.pubkey, Some(event_id))
+            }
+        };
+
+        let (details, lnurl) = self.fetch_lnurl_pay_details(public_key).await?;
+
+        // Compose zap request
+        let relays = self.relays().await.into_keys().map(UncheckedUrl::from);
+        let mut data = ZapRequestData::new(public_key, relays)
+            .amount(amount_msat)
+            .lnurl(lnurl.clone())
+            .message(comment.unwrap_or_default());
+        if let Some(event_id) = event_id {
+            data = data.event_id(event_id);
+        }
+        let zap_request: Event = self
+            .internal_sign_event_builder(EventBuilder::public_zap_request(data))
+            .await?;
+
+        let invoice: String =
+            zapper::fetch_invoice(&details, amount_msat, &zap_request, &lnurl, proxy).await?;
+
+        // Pay with the wallet, if set
+        #[cfg(feature = "nip47")]
+        if let Some(nwc) = self.opts.get_nostr_wallet_connect() {
+            nwc.pay_invoice(invoice.clone()).await?;
+        }
+
+        Ok(invoice)
+    }
+
+    /// Resolve the LNURL pay endpoint of `public_key` from its metadata
+    ///
+    /// Return the endpoint details and the bech32 encoded LNURL.
+    #[cfg(feature = "nip57")]
+    async fn fetch_lnurl_pay_details(
         &self,
-        pubkey: XOnlyPublicKey,
-        reason: Option<S>,
-    ) -> Result<EventId, Error>
-    where
-        S: Into<String>,
-    {
-        let builder = EventBuilder::mute_channel_user(pubkey, reason);
-        self.send_event_builder(builder).await
+        public_key: XOnlyPublicKey,
+    ) -> Result<(zapper::LnurlPayDetails, String), Error> {
+        #[cfg(not(target_arch = "wasm32"))]
+        let proxy = self.opts.proxy;
+        #[cfg(target_arch = "wasm32")]
+        let proxy = None;
+
+        let filter: Filter = Filter::new()
+            .author(public_key)
+            .kind(Kind::Metadata)
+            .limit(1);
+        let event: Event = self
+            .get_event_of(filter)
+            .await?
+            .ok_or(ZapperError::MetadataNotFound)?;
+        let metadata: Metadata = Metadata::from_json(event.content)?;
+        let lud: String = metadata
+            .lud16
+            .filter(|lud16| !lud16.is_empty())
+            .or(metadata.lud06.filter(|lud06| !lud06.is_empty()))
+            .ok_or(ZapperError::LnurlNotFound)?;
+        let pay_url: Url = nip57::lnurl_pay_url(lud).map_err(ZapperError::from)?;
+        let lnurl: String = nip57::encode_lnurl(&pay_url).map_err(ZapperError::from)?;
+        let details = zapper::fetch_pay_details(pay_url, proxy).await?;
+        Ok((details, lnurl))
     }
 
-    /// Create an auth event
+    /// Get the zaps of a profile or an event
     ///
-    /// <https://github.com/nostr-protocol/nips/blob/master/42.md>
-    pub async fn auth<S>(&self, challenge: S, relay: Url) -> Result<EventId, Error>
+    /// Every zap receipt is verified against its zap request and the `nostrPubkey` of the LNURL server
+    /// of the recipient: the forged receipts are discarded.
+    /// The zaps of a profile include the zaps of its events.
+    ///
+    /// If timeout is set to `None`, the default from [`Options`] will be used.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/57.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// let zaps = client.zaps_of(my_keys.public_key(), None).await.unwrap();
+    /// println!("Received {} sats", zaps.total_sats());
+    /// for (zapper, amount_msat) in zaps.zappers.iter() {
+    ///     println!("{zapper}: {} sats", amount_msat / 1000);
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "nip57")]
+    pub async fn zaps_of<T>(&self, of: T, timeout: Option<Duration>) -> Result<Zaps, Error>
     where
-        S: Into<String>,
+        T: Into<ZapEntity>,
     {
-        let builder = EventBuilder::auth(challenge, relay);
-        self.send_event_builder(builder).await
+        let (public_key, filter): (XOnlyPublicKey, Filter) = match of.into() {
+            ZapEntity::PublicKey(public_key) => (
+                public_key,
+                Filter::new().kind(Kind::ZapReceipt).pubkey(public_key),
+            ),
+            ZapEntity::Event(event_id) => {
+                let event: Event = self
+                    .get_event_of(Filter::new().id(event_id))
+                    .await?
+                    .ok_or(ZapperError::EventNotFound)?;
+                (
+                    event.pubkey,
+                    Filter::new().kind(Kind::ZapReceipt).event(event_id),
+                )
+            }
+        };
+
+        let (details, ..) = self.fetch_lnurl_pay_details(public_key).await?;
+        let nostr_pubkey: XOnlyPublicKey = match details.nostr_pubkey {
+            Some(nostr_pubkey) if details.allows_nostr => nostr_pubkey,
+            _ => return Err(ZapperError::ZapsNotSupported.into()),
+        };
+
+        let events: Vec<Event> = self.get_events_of(vec![filter], timeout).await?;
+        let receipts =
+            events
+                .iter()
+                .filter_map(|event| match ZapReceipt::from_event(event, &nostr_pubkey) {
+                    Ok(receipt) => Some(receipt),
+                    Err(e) => {
+                        tracing::warn!("Discarding zap receipt {}: {e}", event.id);
+                        None
+                    }
+                });
+        Ok(Zaps::new(public_key, receipts))
     }
 
-    /// Create zap receipt event
+    /// Advertise the Nostr Wallet Connect methods supported by the wallet service
     ///
-    /// <https://github.com/nostr-protocol/nips/blob/master/57.md>
-    #[cfg(feature = "nip57")]
-    pub async fn new_zap_receipt<S>(
-        &self,
-        bolt11: S,
-        preimage: Option<S>,
-        zap_request: Event,
-    ) -> Result<EventId, Error>
+    /// The client signer must be the keys of the wallet service.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/47.md>
+    #[cfg(feature = "nip47")]
+    pub async fn publish_wallet_connect_info<I>(&self, methods: I) -> Result<EventId, Error>
     where
-        S: Into<String>,
+        I: IntoIterator<Item = NostrWalletConnectMethod>,
     {
-        let builder = EventBuilder::new_zap_receipt(bolt11, preimage, zap_request);
+        let builder = EventBuilder::nostr_wallet_connect_info(methods);
         self.send_event_builder(builder).await
     }
 
+    /// Get the first event matching the `filter`, from the database or from the relays
+    #[cfg(any(feature = "nip57", feature = "nip59"))]
+    async fn get_event_of(&self, filter: Filter) -> Result<Option<Event>, Error> {
+        let stored = self
+            .database()
+            .query(vec![filter.clone()], Order::Desc)
+            .await
+            .unwrap_or_default();
+        if let Some(event) = stored.into_iter().next() {
+            return Ok(Some(event));
+        }
+
+        let mut events: Vec<Event> = self.get_events_of(vec![filter], None).await?;
+        events.sort_by_key(|e| std::cmp::Reverse(e.created_at));
+        Ok(events.into_iter().next())
+    }
+
     /// File metadata
     ///
     /// <https://github.com/nostr-protocol/nips/blob/master/94.md>
@@ -1273,6 +4448,55 @@ impl Client {
         self.send_event_builder(builder).await
     }
 
+    /// Upload a file to a NIP96 media server
+    ///
+    /// The upload URL is read from the `/.well-known/nostr/nip96.json` of `server_url`
+    /// and the request is authorized with a NIP98 event signed by the client signer.
+    /// Publish the returned [`FileMetadata`] with [`Client::file_metadata`] to share the file.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/96.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// let server_url = Url::parse("https://nostr.build").unwrap();
+    /// let bytes: Vec<u8> = std::fs::read("image.png").unwrap();
+    /// let metadata = client
+    ///     .upload_file(server_url, bytes, "image/png")
+    ///     .await
+    ///     .unwrap();
+    /// client.file_metadata("My image", metadata).await.unwrap();
+    /// # }
+    /// ```
+    #[cfg(feature = "nip96")]
+    pub async fn upload_file<S>(
+        &self,
+        server_url: Url,
+        bytes: Vec<u8>,
+        mime_type: S,
+    ) -> Result<FileMetadata, Error>
+    where
+        S: AsRef<str>,
+    {
+        #[cfg(not(target_arch = "wasm32"))]
+        let proxy = self.opts.proxy;
+        #[cfg(target_arch = "wasm32")]
+        let proxy = None;
+
+        let api_url: Url = uploader::fetch_api_url(&server_url, proxy).await?;
+        let data = HttpData::new(UncheckedUrl::from(api_url.clone()), HttpMethod::POST)
+            .payload(Sha256Hash::hash(&bytes));
+        let auth: Event = self
+            .internal_sign_event_builder(EventBuilder::http_auth(data))
+            .await?;
+        Ok(uploader::upload(api_url, &auth, bytes, mime_type.as_ref(), proxy).await?)
+    }
+
     /// Negentropy reconciliation
     pub async fn reconcile(&self, filter: Filter, opts: NegentropyOptions) -> Result<(), Error> {
         Ok(self.pool.reconcile(filter, opts).await?)
@@ -1288,6 +4512,220 @@ impl Client {
         Ok(self.pool.reconcile_with_items(filter, items, opts).await?)
     }
 
+    /// Crawl the full history of a public key and store it into the database
+    ///
+    /// Every relay is paged backwards with `until` cursors. The write relays of the public key
+    /// (NIP65) are temporarily added to the pool for the crawl, waiting up to `timeout` for them to connect.
+    ///
+    /// If timeout is set to `None`, the default from [`Options`] will be used (per page).
+    pub async fn archive_pubkey(
+        &self,
+        public_key: XOnlyPublicKey,
+        timeout: Option<Duration>,
+    ) -> Result<ArchiveReport, Error> {
+        let timeout: Duration = timeout.unwrap_or(self.opts.timeout);
+
+        // Get write relays of public key
+        let filter: Filter = Filter::new()
+            .author(public_key)
+            .kind(Kind::RelayList)
+            .limit(1);
+        let events: Vec<Event> = self.get_events_of(vec![filter], Some(timeout)).await?;
+        let mut added_relays: Vec<Url> = Vec::new();
+        let mut connecting: Vec<Relay> = Vec::new();
+        if let Some(event) = events.into_iter().max_by_key(|e| e.created_at) {
+            for (url, metadata) in nip65::extract_relay_list(&event).into_iter() {
+                if let Some(RelayMetadata::Read) = metadata {
+                    continue;
+                }
+
+                if let Ok(url) = Url::try_from(url) {
+                    if self.add_relay(url.clone()).await? {
+                        let relay: Relay = self.relay(&url).await?;
+                        self.pool.connect_relay(&relay, false).await;
+                        connecting.push(relay);
+                        added_relays.push(url);
+                    }
+                }
+            }
+        }
+
+        // Wait for the added relays to connect before crawling them
+        let connected: Vec<bool> = future::join_all(
+            connecting
+                .iter()
+                .map(|relay| relay.wait_for_connection(timeout)),
+        )
+        .await;
+        for (relay, connected) in connecting.iter().zip(connected) {
+            if !connected {
+                tracing::warn!("{} not connected in time for the archive", relay.url());
+            }
+        }
+
+        // Crawl relays
+        let mut handles = Vec::new();
+        for (url, relay) in self.relays().await.into_iter() {
+            let database = self.database();
+            let handle = thread::spawn(async move {
+                let res = archive::crawl_relay(relay, database, public_key, timeout).await;
+                (url, res)
+            });
+            handles.push(handle);
+        }
+
+        let mut report = ArchiveReport::default();
+        let mut ids: HashSet<EventId> = HashSet::new();
+        for handle in handles.into_iter().flatten() {
+            let (url, (coverage, relay_ids)) = handle.join().await.map_err(pool::Error::from)?;
+            if let Some(oldest) = coverage.oldest {
+                report.oldest = Some(report.oldest.map_or(oldest, |o| o.min(oldest)));
+            }
+            ids.extend(relay_ids);
+            report.relays.insert(url, coverage);
+        }
+        report.events = ids.len();
+
+        // Remove relays added for the crawl
+        for url in added_relays.into_iter() {
+            self.remove_relay(url).await?;
+        }
+
+        Ok(report)
+    }
+
+    /// Send the events of the client signer matching the `filter` to the relays that don't have them yet
+    ///
+    /// The events are fetched from the database and the relays. Relays with `write` disabled are skipped.
+    /// Useful after adding new relays.
+    ///
+    /// If timeout is set to `None`, the default from [`Options`] will be used.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// #   let my_keys = Keys::generate();
+    /// #   let client = Client::new(&my_keys);
+    /// client.add_relay("wss://relay.damus.io").await.unwrap();
+    /// client.connect().await;
+    ///
+    /// let filter = Filter::new().kinds([Kind::Metadata, Kind::ContactList, Kind::RelayList]);
+    /// let report = client.rebroadcast(filter, None).await.unwrap();
+    /// for (url, res) in report.into_iter() {
+    ///     println!("{url}: {} sent, {} failed", res.sent.len(), res.failed.len());
+    /// }
+    /// # }
+    /// ```
+    pub async fn rebroadcast(
+        &self,
+        filter: Filter,
+        timeout: Option<Duration>,
+    ) -> Result<HashMap<Url, RelayRebroadcast>, Error> {
+        let timeout: Duration = timeout.unwrap_or(self.opts.timeout);
+        let public_key: XOnlyPublicKey = self.signer_public_key().await?;
+        let filter: Filter = filter.author(public_key);
+        let events: Vec<Event> = self.get_events_of(vec![filter], Some(timeout)).await?;
+        if events.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let events: Arc<Vec<Event>> = Arc::new(events);
+        let opts = RelaySendOptions::new()
+            .skip_disconnected(self.opts.get_skip_disconnected_relays())
+            .timeout(self.opts.send_timeout);
+        let mut handles = Vec::new();
+        for (url, relay) in self.relays().await.into_iter() {
+            if !relay.opts().get_write() {
+                continue;
+            }
+
+            let database = self.database();
+            let events = events.clone();
+            let handle = thread::spawn(async move {
+                let res =
+                    rebroadcast::rebroadcast_to_relay(relay, database, events, timeout, opts).await;
+                (url, res)
+            });
+            handles.push(handle);
+        }
+
+        let mut report: HashMap<Url, RelayRebroadcast> = HashMap::new();
+        for handle in handles.into_iter().flatten() {
+            let (url, res) = handle.join().await.map_err(pool::Error::from)?;
+            report.insert(url, res);
+        }
+        Ok(report)
+    }
+
+    /// Watch the status, the participants and the chat of a live event (NIP53)
+    ///
+    /// The live event and the chat messages already stored into the database are the first updates.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/53.md>
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let client = Client::new(&Keys::generate());
+    /// let host = XOnlyPublicKey::from_bech32(
+    ///     "npub14f8usejl26twx0dhuxjh9cas7keav9vr0v8nvtwtrjqx3vycc76qqh9nsy",
+    /// )
+    /// .unwrap();
+    /// let coordinate = Coordinate::new(Kind::LiveEvent, host).identifier("my-stream");
+    /// let mut watcher = client.watch_live_event(coordinate).await.unwrap();
+    /// while let Some(update) = watcher.next().await {
+    ///     if let LiveEventUpdate::Message(event) = update {
+    ///         println!("{}", event.content);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn watch_live_event(
+        &self,
+        coordinate: Coordinate,
+    ) -> Result<LiveEventWatcher, Error> {
+        if coordinate.kind != Kind::LiveEvent {
+            return Err(Error::NIP53(nip53::Error::WrongKind));
+        }
+
+        let filters: Vec<Filter> = live::filters(&coordinate);
+        let mut watcher =
+            LiveEventWatcher::new(coordinate.clone(), self.pool.clone(), self.notifications());
+
+        // Stored events
+        let stored: Vec<Event> = self
+            .database()
+            .query(filters.clone(), Order::Asc)
+            .await
+            .unwrap_or_default();
+        for event in stored.into_iter() {
+            watcher.handle_event(event);
+        }
+
+        let wait: Option<Duration> = if self.opts.get_wait_for_subscription() {
+            self.opts.send_timeout
+        } else {
+            None
+        };
+        let internal_id: InternalSubscriptionId = live::internal_id(&coordinate);
+        for (url, relay) in self.relays().await.into_iter() {
+            if let Err(e) = relay
+                .subscribe_with_internal_id(internal_id.clone(), filters.clone(), wait)
+                .await
+            {
+                tracing::error!("Impossible to subscribe {url} to live event: {e}");
+            }
+        }
+
+        Ok(watcher)
+    }
+
     /// Get a list of channels
     #[deprecated(since = "0.27.0")]
     pub async fn get_channels(&self, timeout: Option<Duration>) -> Result<Vec<Event>, Error> {
@@ -1296,13 +4734,20 @@ impl Client {
     }
 
     /// Handle notifications
+    ///
+    /// If the handler is too slow, a [`RelayPoolNotification::Lagged`] notification reports how many notifications have been missed.
     pub async fn handle_notifications<F, Fut>(&self, func: F) -> Result<(), Error>
     where
         F: Fn(RelayPoolNotification) -> Fut,
         Fut: Future<Output = Result<bool>>,
     {
         let mut notifications = self.notifications();
-        while let Ok(notification) = notifications.recv().await {
+        loop {
+            let notification: RelayPoolNotification = match notifications.recv().await {
+                Ok(notification) => notification,
+                Err(RecvError::Lagged(n)) => RelayPoolNotification::Lagged(n),
+                Err(RecvError::Closed) => break,
+            };
             let stop: bool = RelayPoolNotification::Stop == notification;
             let shutdown: bool = RelayPoolNotification::Shutdown == notification;
             let exit: bool = func(notification)
@@ -1314,4 +4759,290 @@ impl Client {
         }
         Ok(())
     }
+
+    /// Handle notifications with a [`NotificationHandler`]
+    ///
+    /// Like [`Client::handle_notifications`], return when the handler asks to exit or when the pool is stopped or shut down.
+    pub async fn handle_notifications_with<H>(&self, handler: &H) -> Result<(), Error>
+    where
+        H: NotificationHandler,
+    {
+        let mut notifications = self.notifications();
+        loop {
+            let notification: RelayPoolNotification = match notifications.recv().await {
+                Ok(notification) => notification,
+                Err(RecvError::Lagged(n)) => RelayPoolNotification::Lagged(n),
+                Err(RecvError::Closed) => {
+                    handler.on_shutdown().await;
+                    break;
+                }
+            };
+            let stop: bool = RelayPoolNotification::Stop == notification;
+            let exit: bool = handler::dispatch(handler, notification)
+                .await
+                .map_err(|e| Error::Handler(e.to_string()))?;
+            if exit || stop {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Register a [`NotificationHandler`], run in background until it asks to exit or the pool is shut down
+    ///
+    /// Errors returned by the handler are logged.
+    pub fn add_notification_handler<H>(&self, handler: H)
+    where
+        H: NotificationHandler + 'static,
+    {
+        let mut notifications = self.notifications();
+        thread::spawn(async move {
+            tracing::debug!("Notification handler thread started");
+            loop {
+                let notification: RelayPoolNotification = match notifications.recv().await {
+                    Ok(notification) => notification,
+                    Err(RecvError::Lagged(n)) => RelayPoolNotification::Lagged(n),
+                    Err(RecvError::Closed) => {
+                        handler.on_shutdown().await;
+                        break;
+                    }
+                };
+                match handler::dispatch(&handler, notification).await {
+                    Ok(true) => break,
+                    Ok(false) => (),
+                    Err(e) => tracing::error!("Notification handler error: {e}"),
+                }
+            }
+            tracing::debug!("Exited from notification handler thread");
+        });
+    }
+}
+
+/// Seal the `rumor` with the `signer` and gift wrap it for the `receiver`
+#[cfg(feature = "nip59")]
+async fn seal_and_wrap(
+    signer: &ClientSigner,
+    public_key: XOnlyPublicKey,
+    receiver: XOnlyPublicKey,
+    rumor: &UnsignedEvent,
+    tweak: Option<Duration>,
+) -> Result<Event, Error> {
+    let content: String = signer.nip44_encrypt(receiver, rumor.as_json()).await?;
+    let seal: UnsignedEvent = EventBuilder::new(Kind::Seal, content, [])
+        .custom_created_at(nip59::created_at(tweak))
+        .to_unsigned_event(public_key);
+    let seal: Event = signer.sign_event(seal).await?;
+    Ok(nip59::gift_wrap_from_seal(&receiver, &seal, tweak)?)
+}
+
+#[cfg(feature = "nip59")]
+async fn unwrap_gift_wrap(
+    signer: &ClientSigner,
+    gift_wrap: &Event,
+) -> Result<UnwrappedGift, Error> {
+    let seal: String = signer
+        .nip44_decrypt(gift_wrap.pubkey, gift_wrap.content.clone())
+        .await?;
+    let seal: Event = nip59::open_gift_wrap(gift_wrap, seal)?;
+    let rumor: String = signer
+        .nip44_decrypt(seal.pubkey, seal.content.clone())
+        .await?;
+    Ok(nip59::open_seal(&seal, rumor)?)
+}
+
+/// Sign the `AUTH` event for the `challenge` and send it to `relay_url`
+async fn authenticate(
+    pool: &RelayPool,
+    auth_signer: &RwLock<Option<ClientSigner>>,
+    signer: &RwLock<Option<ClientSigner>>,
+    relay_url: Url,
+    challenge: String,
+    wait: Option<Duration>,
+) {
+    let event: Event = match get_auth_signer(auth_signer, signer).await {
+        Ok(signer) => match sign_auth_event(signer, challenge, relay_url.clone()).await {
+            Ok(event) => event,
+            Err(e) => {
+                tracing::error!("Impossible to sign AUTH event for {relay_url}: {e}");
+                return;
+            }
+        },
+        Err(e) => {
+            tracing::error!("Impossible to authenticate to {relay_url}: {e}");
+            return;
+        }
+    };
+    match pool
+        .send_msg_to(relay_url.clone(), ClientMessage::new_auth(event), wait)
+        .await
+    {
+        Ok(..) => tracing::debug!("Sent AUTH event to {relay_url}"),
+        Err(e) => tracing::error!("Impossible to send AUTH event to {relay_url}: {e}"),
+    }
+}
+
+async fn get_auth_signer(
+    auth_signer: &RwLock<Option<ClientSigner>>,
+    signer: &RwLock<Option<ClientSigner>>,
+) -> Result<ClientSigner, Error> {
+    if let Some(auth_signer) = auth_signer.read().await.clone() {
+        return Ok(auth_signer);
+    }
+    let signer = signer.read().await;
+    signer.clone().ok_or(Error::SignerNotConfigured)
+}
+
+async fn sign_auth_event(
+    signer: ClientSigner,
+    challenge: String,
+    relay: Url,
+) -> Result<Event, Error> {
+    let public_key: XOnlyPublicKey = signer.get_public_key().await?;
+    let unsigned: UnsignedEvent =
+        EventBuilder::auth(challenge, relay).to_unsigned_event(public_key);
+    signer.sign_event(unsigned).await
+}
+
+/// Decrypt a [`Kind::EncryptedDirectMessage`] sent or received by `public_key`
+#[cfg(feature = "nip04")]
+async fn decrypt_direct_msg(
+    signer: &ClientSigner,
+    public_key: XOnlyPublicKey,
+    event: &Event,
+) -> Result<Option<DirectMessage>, Error> {
+    if event.pubkey != public_key && !event.public_keys().any(|p| p == &public_key) {
+        return Ok(None);
+    }
+
+    let counterparty: XOnlyPublicKey = if event.pubkey == public_key {
+        match event.public_keys().next() {
+            Some(p) => *p,
+            None => return Ok(None),
+        }
+    } else {
+        event.pubkey
+    };
+    let content: String = signer
+        .nip04_decrypt(counterparty, event.content.clone())
+        .await?;
+    Ok(DirectMessage::from_nip04(event, content))
+}
+
+/// Decrypt the private items of a list (NIP04 or NIP44, according to the content format)
+#[allow(unused_variables)]
+async fn decrypt_list_content(signer: &ClientSigner, event: &Event) -> Result<String, Error> {
+    #[cfg(feature = "nip04")]
+    if event.content.contains("?iv=") {
+        return signer
+            .nip04_decrypt(event.pubkey, event.content.clone())
+            .await;
+    }
+
+    #[cfg(feature = "nip44")]
+    {
+        signer
+            .nip44_decrypt(event.pubkey, event.content.clone())
+            .await
+    }
+    #[cfg(not(feature = "nip44"))]
+    {
+        Err(Error::UnsupportedSignerMethod("nip44_decrypt"))
+    }
+}
+
+/// Encrypt the private items of a list to the signer (NIP04, or NIP44 if `nip04` feature is disabled)
+#[allow(unused_variables)]
+async fn encrypt_list_content(
+    signer: &ClientSigner,
+    public_key: XOnlyPublicKey,
+    content: String,
+) -> Result<String, Error> {
+    #[cfg(feature = "nip04")]
+    {
+        signer.nip04_encrypt(public_key, content).await
+    }
+    #[cfg(all(not(feature = "nip04"), feature = "nip44"))]
+    {
+        signer.nip44_encrypt(public_key, content).await
+    }
+    #[cfg(all(not(feature = "nip04"), not(feature = "nip44")))]
+    {
+        Err(Error::UnsupportedSignerMethod("nip04_encrypt"))
+    }
+}
+
+/// Parse the difficulty required by a `pow:` rejection message (i.e. `pow: difficulty 12 is less than 20`)
+///
+/// The message format is not standardized: the largest number is taken as the required difficulty.
+fn required_pow_difficulty(message: &str) -> Option<u8> {
+    message
+        .strip_prefix("pow:")?
+        .split(|c: char| !c.is_ascii_digit())
+        .filter_map(|n| n.parse::<u8>().ok())
+        .max()
+}
+
+/// Replace `previous` with `current` in `authors` and in the `#p` tags of the filters
+///
+/// Return `true` if at least one filter changed.
+fn replace_public_key(
+    filters: &mut [Filter],
+    previous: &XOnlyPublicKey,
+    current: XOnlyPublicKey,
+) -> bool {
+    let mut replaced: bool = false;
+    for filter in filters.iter_mut() {
+        if filter.authors.remove(previous) {
+            filter.authors.insert(current);
+            replaced = true;
+        }
+
+        if let Some(values) = filter.generic_tags.get_mut(&Alphabet::P) {
+            let previous_values = [
+                GenericTagValue::Pubkey(*previous),
+                GenericTagValue::String(previous.to_string()),
+            ];
+            for value in previous_values.iter() {
+                if values.remove(value) {
+                    values.insert(GenericTagValue::Pubkey(current));
+                    replaced = true;
+                }
+            }
+        }
+    }
+    replaced
+}
+
+/// Keep the newest event of each author
+fn keep_newest(events: &mut HashMap<XOnlyPublicKey, Event>, event: Event) {
+    match events.get(&event.pubkey) {
+        Some(current) if current.created_at >= event.created_at => {}
+        _ => {
+            events.insert(event.pubkey, event);
+        }
+    }
+}
+
+/// Check if `tag` is the contact list entry of `public_key`
+fn is_contact_tag(tag: &Tag, public_key: &XOnlyPublicKey) -> bool {
+    matches!(tag, Tag::PublicKey { public_key: pk, uppercase: false, .. } if pk == public_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_pow_difficulty() {
+        assert_eq!(
+            required_pow_difficulty("pow: difficulty 12 is less than 20"),
+            Some(20)
+        );
+        assert_eq!(required_pow_difficulty("pow: 28"), Some(28));
+        assert_eq!(required_pow_difficulty("pow:difficulty 8 < 16"), Some(16));
+        assert_eq!(required_pow_difficulty("pow: too low"), None);
+        assert_eq!(required_pow_difficulty("pow: difficulty 300"), None);
+        assert_eq!(required_pow_difficulty("blocked: pow: 20"), None);
+        assert_eq!(required_pow_difficulty("rate-limited: 20"), None);
+    }
 }
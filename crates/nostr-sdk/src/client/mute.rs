@@ -0,0 +1,228 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Mute list (NIP51)
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+use nostr::key::XOnlyPublicKey;
+use nostr::nips::nip51::ListItems;
+use nostr::{Event, EventId, Kind, Tag, TagKind};
+
+const WORD_TAG: &str = "word";
+
+/// Match muted words in the content of the events
+pub trait MutedWordMatcher: fmt::Debug + Send + Sync {
+    /// Check if `content` contains the muted `word`
+    fn is_match(&self, content: &str, word: &str) -> bool;
+}
+
+/// Default [`MutedWordMatcher`]
+///
+/// Case-insensitive (unicode case folding) and matching only whole words: `nostr` matches
+/// `Nostr!` but not `nostrich`.
+///
+/// Case folding is approximated by uppercasing and then lowercasing every char: it handles
+/// the one-to-many mappings (`ß` matches `SS`) but not the Turkic dotted/dotless `i`, and the
+/// text is not normalized (a precomposed `é` doesn't match `e` + combining acute accent).
+///
+/// # Example
+/// ```rust
+/// use nostr_sdk::prelude::*;
+///
+/// let matcher = DefaultMutedWordMatcher;
+/// assert!(matcher.is_match("I love Nostr!", "nostr"));
+/// assert!(matcher.is_match("ÜBER alles", "über"));
+/// assert!(matcher.is_match("STRASSE", "straße"));
+/// assert!(!matcher.is_match("Hello nostrich", "nostr"));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultMutedWordMatcher;
+
+impl MutedWordMatcher for DefaultMutedWordMatcher {
+    fn is_match(&self, content: &str, word: &str) -> bool {
+        let word: String = fold_case(word.trim());
+        if word.is_empty() {
+            return false;
+        }
+
+        let content: String = fold_case(content);
+        content.match_indices(&word).any(|(start, _)| {
+            let before: Option<char> = content[..start].chars().next_back();
+            let after: Option<char> = content[start + word.len()..].chars().next();
+            !before.map_or(false, is_word_char) && !after.map_or(false, is_word_char)
+        })
+    }
+}
+
+/// Unicode case folding, approximated char by char
+///
+/// `str::to_lowercase` is not used since it doesn't fold `ß` to `ss`
+/// and lowercases a final `Σ` as `ς` (instead of `σ`).
+fn fold_case(s: &str) -> String {
+    s.chars()
+        .flat_map(char::to_uppercase)
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Entries of a mute list (kind `10000`)
+#[derive(Debug, Clone)]
+pub struct MuteList {
+    /// Muted public keys (`p` tags)
+    pub public_keys: HashSet<XOnlyPublicKey>,
+    /// Muted hashtags (`t` tags), case folded
+    pub hashtags: HashSet<String>,
+    /// Muted words (`word` tags)
+    pub words: Vec<String>,
+    /// Muted threads (`e` tags)
+    pub event_ids: HashSet<EventId>,
+    matcher: Arc<dyn MutedWordMatcher>,
+}
+
+impl Default for MuteList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MuteList {
+    /// New empty mute list
+    pub fn new() -> Self {
+        Self {
+            public_keys: HashSet::new(),
+            hashtags: HashSet::new(),
+            words: Vec::new(),
+            event_ids: HashSet::new(),
+            matcher: Arc::new(DefaultMutedWordMatcher),
+        }
+    }
+
+    /// Parse the public tags of a mute list event
+    ///
+    /// Return an empty list if the event is not a [`Kind::MuteList`].
+    pub fn from_event(event: &Event) -> Self {
+        let mut list = Self::new();
+
+        if event.kind != Kind::MuteList {
+            return list;
+        }
+
+        list.extend(event.tags.iter());
+        list
+    }
+
+    /// Parse the public and private items of a mute list
+    pub fn from_items(items: &ListItems) -> Self {
+        let mut list = Self::new();
+        list.extend(items.iter());
+        list
+    }
+
+    fn extend<'a, I>(&mut self, tags: I)
+    where
+        I: Iterator<Item = &'a Tag>,
+    {
+        for tag in tags {
+            match tag {
+                Tag::PublicKey { public_key, .. } => {
+                    self.public_keys.insert(*public_key);
+                }
+                Tag::Hashtag(hashtag) => {
+                    self.hashtags.insert(fold_case(hashtag));
+                }
+                Tag::Event { event_id, .. } => {
+                    self.event_ids.insert(*event_id);
+                }
+                Tag::Generic(TagKind::Custom(kind), values) if kind == WORD_TAG => {
+                    if let Some(word) = values.first() {
+                        self.words.push(word.clone());
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Set a custom [`MutedWordMatcher`]
+    pub fn matcher<M>(self, matcher: M) -> Self
+    where
+        M: MutedWordMatcher + 'static,
+    {
+        Self {
+            matcher: Arc::new(matcher),
+            ..self
+        }
+    }
+
+    /// Check if `content` contains a muted word
+    pub fn has_muted_word(&self, content: &str) -> bool {
+        self.words
+            .iter()
+            .any(|word| self.matcher.is_match(content, word))
+    }
+
+    /// Check if [`Event`] is muted
+    ///
+    /// An event is muted if the author is muted, if it's (or reply to) a muted thread,
+    /// if it has a muted hashtag or if the content contains a muted word.
+    pub fn is_muted(&self, event: &Event) -> bool {
+        if self.public_keys.contains(&event.pubkey) || self.event_ids.contains(&event.id) {
+            return true;
+        }
+
+        if event.event_ids().any(|id| self.event_ids.contains(id)) {
+            return true;
+        }
+
+        let muted_hashtag: bool = event.tags.iter().any(|tag| match tag {
+            Tag::Hashtag(hashtag) => self.hashtags.contains(&fold_case(hashtag)),
+            _ => false,
+        });
+        if muted_hashtag {
+            return true;
+        }
+
+        self.has_muted_word(&event.content)
+    }
+
+    pub(crate) fn set_matcher(&mut self, matcher: Arc<dyn MutedWordMatcher>) {
+        self.matcher = matcher;
+    }
+
+    pub(crate) fn get_matcher(&self) -> Arc<dyn MutedWordMatcher> {
+        self.matcher.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_case() {
+        assert_eq!(fold_case("Nostr"), "nostr");
+        assert_eq!(fold_case("straße"), "strasse");
+        assert_eq!(fold_case("STRASSE"), "strasse");
+        assert_eq!(fold_case("ΟΔΟΣ"), fold_case("οδος"));
+        assert_eq!(fold_case("ﬁle"), "file");
+    }
+
+    #[test]
+    fn test_default_matcher() {
+        let matcher = DefaultMutedWordMatcher;
+        assert!(matcher.is_match("Hello NOSTR", "nostr"));
+        assert!(matcher.is_match("Die STRASSE ist lang", "straße"));
+        assert!(matcher.is_match("die straße", "STRASSE"));
+        assert!(matcher.is_match("ΟΔΟΣ", "οδος"));
+        assert!(!matcher.is_match("Hello nostrich", "nostr"));
+        assert!(!matcher.is_match("Hello", "  "));
+    }
+}
@@ -0,0 +1,295 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Nostr Wallet Connect client (NIP47)
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/47.md>
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_utility::time;
+use nostr::event::builder::Error as EventBuilderError;
+use nostr::nips::nip04;
+use nostr::nips::nip47::{
+    self, ListTransactionsRequestParams, LookupInvoiceRequestParams, LookupInvoiceResponseResult,
+    MakeInvoiceRequestParams, MakeInvoiceResponseResult, Method, NIP47Error, NostrWalletConnectURI,
+    PayInvoiceRequestParams, Request, RequestParams, Response, ResponseResult,
+    TransactionResponseResult,
+};
+use nostr::{
+    ClientMessage, Event, EventBuilder, EventId, Filter, JsonUtil, Keys, Kind, SubscriptionId, Tag,
+};
+use thiserror::Error;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::OnceCell;
+
+use crate::relay::pool::{Error as RelayPoolError, RelayPool};
+use crate::relay::{RelayOptions, RelayPoolNotification, RelayPoolOptions, RelaySendOptions};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// [`NWC`] error
+#[derive(Debug, Error)]
+pub enum Error {
+    /// [`RelayPool`] error
+    #[error("relay pool error: {0}")]
+    RelayPool(#[from] RelayPoolError),
+    /// Event builder error
+    #[error(transparent)]
+    EventBuilder(#[from] EventBuilderError),
+    /// NIP04 error
+    #[error(transparent)]
+    NIP04(#[from] nip04::Error),
+    /// NIP47 error
+    #[error(transparent)]
+    NIP47(#[from] nip47::Error),
+    /// Error returned by the wallet
+    #[error("wallet error: {}", .0.message)]
+    Wallet(NIP47Error),
+    /// The wallet replied with a result not matching the request
+    #[error("unexpected response")]
+    UnexpectedResponse,
+    /// Relay pool shutdown before receiving the response
+    #[error("relay pool shutdown")]
+    Shutdown,
+    /// Timeout
+    #[error("timeout")]
+    Timeout,
+}
+
+/// Nostr Wallet Connect client
+///
+/// Connect to the wallet relay with its own [`RelayPool`], on the first request.
+/// Requests are NIP04-encrypted with the secret of the [`NostrWalletConnectURI`].
+///
+/// If the relay is disconnected, it's reconnected before sending the next request.
+///
+/// # Example
+/// ```rust,no_run
+/// use std::str::FromStr;
+///
+/// use nostr_sdk::prelude::*;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let uri = NostrWalletConnectURI::from_str("nostr+walletconnect://b889ff5b1513b641e2a139f661a661364979c5beee91842f8f0ef42ab558e9d4?relay=wss%3A%2F%2Frelay.damus.io%2F&secret=71a8c14c1407c113601079c4302dab36460f0ccd0ad506f1f2dc73b5100e4f3c").unwrap();
+/// let nwc = NWC::new(uri);
+///
+/// let balance: u64 = nwc.get_balance().await.unwrap();
+/// println!("Balance: {balance} msat");
+///
+/// let preimage: String = nwc.pay_invoice("lnbc...").await.unwrap();
+/// println!("Paid: {preimage}");
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct NWC {
+    uri: NostrWalletConnectURI,
+    keys: Keys,
+    timeout: Option<Duration>,
+    pool: Arc<OnceCell<RelayPool>>,
+}
+
+impl NWC {
+    /// New Nostr Wallet Connect client
+    pub fn new(uri: NostrWalletConnectURI) -> Self {
+        Self {
+            keys: Keys::new(uri.secret),
+            uri,
+            timeout: Some(DEFAULT_TIMEOUT),
+            pool: Arc::new(OnceCell::new()),
+        }
+    }
+
+    /// Set timeout of the requests (default: 60 secs)
+    pub fn timeout(self, timeout: Option<Duration>) -> Self {
+        Self { timeout, ..self }
+    }
+
+    /// Get [`NostrWalletConnectURI`]
+    pub fn uri(&self) -> &NostrWalletConnectURI {
+        &self.uri
+    }
+
+    async fn pool(&self) -> Result<&RelayPool, Error> {
+        let pool: &RelayPool = self
+            .pool
+            .get_or_try_init(|| async {
+                let pool = RelayPool::new(RelayPoolOptions::default());
+                pool.add_relay(self.uri.relay_url.clone(), RelayOptions::default())
+                    .await?;
+                pool.connect(true).await;
+                Ok::<RelayPool, Error>(pool)
+            })
+            .await?;
+
+        // Reconnect, if the relay is disconnected
+        let mut connected: bool = false;
+        for relay in pool.relays().await.values() {
+            if relay.is_connected().await {
+                connected = true;
+                break;
+            }
+        }
+        if !connected {
+            tracing::debug!("NWC relay disconnected: reconnecting");
+            pool.connect(true).await;
+        }
+
+        Ok(pool)
+    }
+
+    /// Send NIP47 [`Request`] to the wallet and wait for the result
+    pub async fn send_request(&self, req: Request) -> Result<ResponseResult, Error> {
+        let pool: &RelayPool = self.pool().await?;
+        let method: Method = req.method;
+
+        // Build request
+        let content: String =
+            nip04::encrypt(&self.uri.secret, &self.uri.public_key, req.as_json())?;
+        let event: Event = EventBuilder::new(
+            Kind::WalletConnectRequest,
+            content,
+            [Tag::public_key(self.uri.public_key)],
+        )
+        .to_event(&self.keys)?;
+        let event_id: EventId = event.id;
+
+        let sub_id = SubscriptionId::generate();
+        let filter = Filter::new()
+            .author(self.uri.public_key)
+            .kind(Kind::WalletConnectResponse)
+            .event(event_id);
+
+        // Subscribe
+        pool.send_msg(ClientMessage::new_req(sub_id.clone(), vec![filter]), None)
+            .await?;
+
+        let mut notifications = pool.notifications();
+
+        // Send request to the wallet
+        pool.send_event(event, RelaySendOptions::new()).await?;
+
+        let future = async {
+            loop {
+                match notifications.recv().await {
+                    Ok(RelayPoolNotification::Event { event, .. })
+                        if event.kind == Kind::WalletConnectResponse
+                            && event.pubkey == self.uri.public_key
+                            && event.event_ids().any(|id| id == &event_id) =>
+                    {
+                        let msg: String =
+                            nip04::decrypt(&self.uri.secret, &self.uri.public_key, &event.content)?;
+                        return Ok(Response::from_json(msg)?);
+                    }
+                    Ok(RelayPoolNotification::Shutdown) | Err(RecvError::Closed) => break,
+                    Ok(..) | Err(RecvError::Lagged(..)) => (),
+                }
+            }
+
+            Err(Error::Shutdown)
+        };
+
+        let res: Option<Result<Response, Error>> = time::timeout(self.timeout, future).await;
+
+        // Unsubscribe, also on timeout
+        pool.send_msg(ClientMessage::close(sub_id), None).await?;
+
+        let res: Response = res.ok_or(Error::Timeout)??;
+
+        if let Some(error) = res.error {
+            return Err(Error::Wallet(error));
+        }
+
+        match res.result {
+            Some(result) if res.result_type == method => Ok(result),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Pay a bolt11 invoice
+    ///
+    /// Return the preimage.
+    pub async fn pay_invoice<S>(&self, invoice: S) -> Result<String, Error>
+    where
+        S: Into<String>,
+    {
+        let req = Request {
+            method: Method::PayInvoice,
+            params: RequestParams::PayInvoice(PayInvoiceRequestParams {
+                invoice: invoice.into(),
+            }),
+        };
+        match self.send_request(req).await? {
+            ResponseResult::PayInvoice(result) => Ok(result.preimage),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Get balance (msat)
+    pub async fn get_balance(&self) -> Result<u64, Error> {
+        let req = Request {
+            method: Method::GetBalance,
+            params: RequestParams::GetBalance,
+        };
+        match self.send_request(req).await? {
+            ResponseResult::GetBalance(result) => Ok(result.balance),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Create a new invoice
+    pub async fn make_invoice(
+        &self,
+        params: MakeInvoiceRequestParams,
+    ) -> Result<MakeInvoiceResponseResult, Error> {
+        let req = Request {
+            method: Method::MakeInvoice,
+            params: RequestParams::MakeInvoice(params),
+        };
+        match self.send_request(req).await? {
+            ResponseResult::MakeInvoice(result) => Ok(result),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Look up an invoice by payment hash or bolt11
+    pub async fn lookup_invoice(
+        &self,
+        params: LookupInvoiceRequestParams,
+    ) -> Result<LookupInvoiceResponseResult, Error> {
+        let req = Request {
+            method: Method::LookupInvoice,
+            params: RequestParams::LookupInvoice(params),
+        };
+        match self.send_request(req).await? {
+            ResponseResult::LookupInvoice(result) => Ok(result),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// List the invoices and payments of the wallet
+    pub async fn list_transactions(
+        &self,
+        params: ListTransactionsRequestParams,
+    ) -> Result<Vec<TransactionResponseResult>, Error> {
+        let req = Request {
+            method: Method::ListTransactions,
+            params: RequestParams::ListTransactions(params),
+        };
+        match self.send_request(req).await? {
+            ResponseResult::ListTransactions(result) => Ok(result.transactions),
+            _ => Err(Error::UnexpectedResponse),
+        }
+    }
+
+    /// Disconnect from the wallet relay
+    pub async fn shutdown(self) -> Result<(), Error> {
+        if let Some(pool) = self.pool.get() {
+            pool.clone().shutdown().await?;
+        }
+        Ok(())
+    }
+}
@@ -10,9 +10,16 @@ use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
+#[cfg(feature = "nip59")]
+use nostr::nips::nip59;
+
+use super::ClientSigner;
+#[cfg(all(feature = "nip47", feature = "nip57"))]
+use super::NWC;
 use crate::relay::RelayPoolOptions;
 
 pub(crate) const DEFAULT_SEND_TIMEOUT: Duration = Duration::from_secs(20);
+pub(crate) const DEFAULT_METADATA_CACHE_TTL: Duration = Duration::from_secs(600);
 
 /// Options
 #[derive(Debug, Clone)]
@@ -25,6 +32,8 @@ pub struct Options {
     wait_for_subscription: Arc<AtomicBool>,
     /// POW difficulty for all events (default: 0)
     difficulty: Arc<AtomicU8>,
+    /// Max POW difficulty mined for the relays that require it (default: none, disabled)
+    auto_pow: Option<u8>,
     /// REQ filters chunk size (default: 10)
     req_filters_chunk_size: Arc<AtomicU8>,
     /// Skip disconnected relays during send methods (default: true)
@@ -45,6 +54,27 @@ pub struct Options {
     pub proxy: Option<SocketAddr>,
     /// Shutdown on [Client](super::Client) drop
     pub shutdown_on_drop: bool,
+    /// Signer used for NIP42 authentication (default: none, use the client signer)
+    auth_signer: Option<ClientSigner>,
+    /// Automatically authenticate to relays that send an `AUTH` challenge (default: false)
+    automatic_authentication: bool,
+    /// Automatically unwrap the gift wraps addressed to the client signer (default: false)
+    #[cfg(feature = "nip59")]
+    unwrap_gift_wraps: bool,
+    /// Window of the random `created_at` of the sent seals and gift wraps (default: 2 days)
+    #[cfg(feature = "nip59")]
+    gift_wrap_timestamp_tweak: Option<Duration>,
+    /// Wallet used to pay the zaps (default: none)
+    #[cfg(all(feature = "nip47", feature = "nip57"))]
+    nostr_wallet_connect: Option<NWC>,
+    /// Keep the relays of the pool in sync with the relay list of the signer (default: false)
+    sync_relay_list: bool,
+    /// Add the received direct messages to the conversations (default: false)
+    track_conversations: bool,
+    /// Keep the events not published to any relay and send them again on reconnection (default: false)
+    outbox: bool,
+    /// How long the metadata fetched with `fetch_metadata` are considered fresh (default: 10 min)
+    metadata_cache_ttl: Duration,
     /// Pool Options
     pub pool: RelayPoolOptions,
 }
@@ -56,6 +86,7 @@ impl Default for Options {
             wait_for_send: Arc::new(AtomicBool::new(true)),
             wait_for_subscription: Arc::new(AtomicBool::new(false)),
             difficulty: Arc::new(AtomicU8::new(0)),
+            auto_pow: None,
             req_filters_chunk_size: Arc::new(AtomicU8::new(10)),
             skip_disconnected_relays: Arc::new(AtomicBool::new(true)),
             timeout: Duration::from_secs(60),
@@ -65,6 +96,18 @@ impl Default for Options {
             #[cfg(not(target_arch = "wasm32"))]
             proxy: None,
             shutdown_on_drop: false,
+            auth_signer: None,
+            automatic_authentication: false,
+            #[cfg(feature = "nip59")]
+            unwrap_gift_wraps: false,
+            #[cfg(feature = "nip59")]
+            gift_wrap_timestamp_tweak: Some(nip59::DEFAULT_TIMESTAMP_TWEAK),
+            #[cfg(all(feature = "nip47", feature = "nip57"))]
+            nostr_wallet_connect: None,
+            sync_relay_list: false,
+            track_conversations: false,
+            outbox: false,
+            metadata_cache_ttl: DEFAULT_METADATA_CACHE_TTL,
             pool: RelayPoolOptions::default(),
         }
     }
@@ -126,6 +169,22 @@ impl Options {
             .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |_| Some(difficulty));
     }
 
+    /// Mine again the events built by the client for the relays that require a higher POW difficulty (default: disabled)
+    ///
+    /// The required difficulty is read from the relay information document (NIP11) or from the `pow:` rejection message.
+    /// The event mined at the required difficulty is sent only to those relays.
+    /// Relays requiring more than `max_difficulty` are skipped.
+    pub fn auto_pow(self, max_difficulty: Option<u8>) -> Self {
+        Self {
+            auto_pow: max_difficulty,
+            ..self
+        }
+    }
+
+    pub(crate) fn get_auto_pow(&self) -> Option<u8> {
+        self.auto_pow
+    }
+
     /// Set `REQ` filters chunk size
     pub fn req_filters_chunk_size(self, size: u8) -> Self {
         Self {
@@ -189,6 +248,144 @@ impl Options {
         }
     }
 
+    /// Set signer used for NIP42 authentication
+    ///
+    /// Allow to authenticate to relays with a different identity (i.e. a device key)
+    /// than the one used to sign the published events. If not set, the client signer is used.
+    pub fn auth_signer<S>(self, signer: S) -> Self
+    where
+        S: Into<ClientSigner>,
+    {
+        Self {
+            auth_signer: Some(signer.into()),
+            ..self
+        }
+    }
+
+    pub(crate) fn get_auth_signer(&self) -> Option<ClientSigner> {
+        self.auth_signer.clone()
+    }
+
+    /// Automatically authenticate to relays that send an `AUTH` challenge (default: false)
+    ///
+    /// The `AUTH` event is signed by the [`Options::auth_signer`], if set, or by the client signer.
+    pub fn automatic_authentication(self, enable: bool) -> Self {
+        Self {
+            automatic_authentication: enable,
+            ..self
+        }
+    }
+
+    pub(crate) fn get_automatic_authentication(&self) -> bool {
+        self.automatic_authentication
+    }
+
+    /// Automatically unwrap the gift wraps addressed to the client signer (default: false)
+    ///
+    /// The rumors are notified as [`RelayPoolNotification::PrivateMessage`](crate::RelayPoolNotification::PrivateMessage).
+    /// The gift wraps must still be subscribed, with a [`Kind::GiftWrap`](nostr::Kind::GiftWrap) filter for the signer public key.
+    #[cfg(feature = "nip59")]
+    pub fn unwrap_gift_wraps(self, enable: bool) -> Self {
+        Self {
+            unwrap_gift_wraps: enable,
+            ..self
+        }
+    }
+
+    #[cfg(feature = "nip59")]
+    pub(crate) fn get_unwrap_gift_wraps(&self) -> bool {
+        self.unwrap_gift_wraps
+    }
+
+    /// Window of the random `created_at` of the sent seals and gift wraps (default: 2 days)
+    ///
+    /// The timestamps are moved in the past to not leak when the messages are sent.
+    /// Set to `None` to use the current timestamp.
+    #[cfg(feature = "nip59")]
+    pub fn gift_wrap_timestamp_tweak(self, window: Option<Duration>) -> Self {
+        Self {
+            gift_wrap_timestamp_tweak: window,
+            ..self
+        }
+    }
+
+    #[cfg(feature = "nip59")]
+    pub(crate) fn get_gift_wrap_timestamp_tweak(&self) -> Option<Duration> {
+        self.gift_wrap_timestamp_tweak
+    }
+
+    /// Set Nostr Wallet Connect (NIP47) wallet used by [`Client::zap`](super::Client::zap) to pay the invoices
+    #[cfg(all(feature = "nip47", feature = "nip57"))]
+    pub fn nostr_wallet_connect(self, nwc: NWC) -> Self {
+        Self {
+            nostr_wallet_connect: Some(nwc),
+            ..self
+        }
+    }
+
+    #[cfg(all(feature = "nip47", feature = "nip57"))]
+    pub(crate) fn get_nostr_wallet_connect(&self) -> Option<NWC> {
+        self.nostr_wallet_connect.clone()
+    }
+
+    /// Keep the relays of the pool in sync with the relay list (NIP65) of the client signer (default: false)
+    ///
+    /// The relay lists published with `publish_relay_list` or received from relays are applied to the pool.
+    pub fn sync_relay_list(self, enable: bool) -> Self {
+        Self {
+            sync_relay_list: enable,
+            ..self
+        }
+    }
+
+    pub(crate) fn get_sync_relay_list(&self) -> bool {
+        self.sync_relay_list
+    }
+
+    /// Add the direct messages received from relays to the [`Conversations`](super::Conversations) (default: false)
+    ///
+    /// Both NIP04 messages and, if [`Options::unwrap_gift_wraps`] is enabled, NIP17 private messages are tracked.
+    /// The messages must still be subscribed.
+    pub fn track_conversations(self, enable: bool) -> Self {
+        Self {
+            track_conversations: enable,
+            ..self
+        }
+    }
+
+    pub(crate) fn get_track_conversations(&self) -> bool {
+        self.track_conversations
+    }
+
+    /// Keep the events that couldn't be published to any relay in the [`Outbox`](super::Outbox) (default: false)
+    ///
+    /// The pending events are sent again when a relay connects. They are also persisted
+    /// if the database stores events and supports tracking the pending events.
+    pub fn outbox(self, enable: bool) -> Self {
+        Self {
+            outbox: enable,
+            ..self
+        }
+    }
+
+    pub(crate) fn get_outbox(&self) -> bool {
+        self.outbox
+    }
+
+    /// Set how long the fetched profile metadata are served from the cache (default: 10 min)
+    ///
+    /// Use [`Duration::ZERO`] to always query the relays.
+    pub fn metadata_cache_ttl(self, ttl: Duration) -> Self {
+        Self {
+            metadata_cache_ttl: ttl,
+            ..self
+        }
+    }
+
+    pub(crate) fn get_metadata_cache_ttl(&self) -> Duration {
+        self.metadata_cache_ttl
+    }
+
     /// Set pool options
     pub fn pool(self, opts: RelayPoolOptions) -> Self {
         Self { pool: opts, ..self }
@@ -0,0 +1,201 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Outbox of the events not yet published
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use nostr::url::Url;
+use nostr::{Event, EventId};
+use nostr_database::{DatabaseError, DynNostrDatabase};
+use tokio::sync::RwLock;
+
+use crate::relay::pool::RelayPool;
+use crate::relay::RelaySendOptions;
+
+/// Events that couldn't be published to any relay
+///
+/// The events are sent again when a relay (re)connects.
+/// Enabled with [`Options::outbox`](super::Options::outbox).
+///
+/// The pending events are kept in memory. If the database stores events and supports
+/// [`NostrDatabase::set_event_pending`](nostr_database::NostrDatabase::set_event_pending),
+/// they are also persisted and reloaded when the [`Client`](super::Client) is created.
+#[derive(Debug, Clone)]
+pub struct Outbox {
+    database: Arc<DynNostrDatabase>,
+    pending: Arc<RwLock<BTreeMap<EventId, Event>>>,
+}
+
+impl Outbox {
+    pub(crate) fn new(database: Arc<DynNostrDatabase>) -> Self {
+        Self {
+            database,
+            pending: Arc::new(RwLock::new(BTreeMap::new())),
+        }
+    }
+
+    /// Load the pending events persisted into the database
+    ///
+    /// Return the number of loaded events.
+    pub(crate) async fn load(&self) -> usize {
+        if !self.database.opts().events {
+            return 0;
+        }
+
+        let ids: BTreeSet<EventId> = match self.database.pending_event_ids().await {
+            Ok(ids) => ids,
+            Err(DatabaseError::NotSupported) => return 0,
+            Err(e) => {
+                tracing::error!("Impossible to load pending events: {e}");
+                return 0;
+            }
+        };
+
+        let mut loaded: usize = 0;
+        for id in ids.into_iter() {
+            match self.database.event_by_id(id).await {
+                Ok(event) => {
+                    let mut pending = self.pending.write().await;
+                    pending.insert(id, event);
+                    loaded += 1;
+                }
+                Err(e) => tracing::error!("Impossible to load pending event {id}: {e}"),
+            }
+        }
+        loaded
+    }
+
+    /// Get the pending events
+    pub async fn pending(&self) -> Vec<Event> {
+        self.pending.read().await.values().cloned().collect()
+    }
+
+    /// Check if there are pending events
+    pub async fn is_empty(&self) -> bool {
+        self.pending.read().await.is_empty()
+    }
+
+    /// Stop retrying the event
+    ///
+    /// Return `false` if the event was not pending.
+    pub async fn discard(&self, event_id: &EventId) -> bool {
+        let removed: bool = self.pending.write().await.remove(event_id).is_some();
+        if removed {
+            self.persist(*event_id, false).await;
+        }
+        removed
+    }
+
+    /// Add the event to the pending events
+    pub(crate) async fn push(&self, event: &Event) {
+        if self.database.opts().events {
+            match self.database.save_event(event).await {
+                Ok(..) => self.persist(event.id, true).await,
+                Err(e) => tracing::error!("Impossible to save pending event {}: {e}", event.id),
+            }
+        }
+        let mut pending = self.pending.write().await;
+        pending.insert(event.id, event.clone());
+    }
+
+    async fn persist(&self, event_id: EventId, pending: bool) {
+        if !self.database.opts().events {
+            return;
+        }
+
+        match self.database.set_event_pending(event_id, pending).await {
+            Ok(()) | Err(DatabaseError::NotSupported) => (),
+            Err(e) => tracing::error!("Impossible to persist pending event {event_id}: {e}"),
+        }
+    }
+
+    /// Send the pending events to `relay_url`
+    ///
+    /// The events accepted by the relay are removed from the outbox. Return the number of sent events.
+    pub(crate) async fn flush(
+        &self,
+        pool: &RelayPool,
+        relay_url: Url,
+        opts: RelaySendOptions,
+    ) -> usize {
+        let mut sent: usize = 0;
+        for event in self.pending().await.into_iter() {
+            let event_id: EventId = event.id;
+            match pool.send_event_to(relay_url.clone(), event, opts).await {
+                Ok(..) => {
+                    tracing::debug!("Sent pending event {event_id} to {relay_url}");
+                    self.discard(&event_id).await;
+                    sent += 1;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Impossible to send pending event {event_id} to {relay_url}: {e}"
+                    )
+                }
+            }
+        }
+        sent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nostr::{EventBuilder, Keys};
+    use nostr_database::{DatabaseOptions, IntoNostrDatabase, MemoryDatabase};
+
+    use super::*;
+    use crate::relay::RelayPoolOptions;
+
+    fn outbox() -> Outbox {
+        let database = MemoryDatabase::new(DatabaseOptions::default());
+        Outbox::new(database.into_nostr_database())
+    }
+
+    #[tokio::test]
+    async fn test_push_discard() {
+        let outbox = outbox();
+        let keys = Keys::generate();
+        let event = EventBuilder::new_text_note("pending", [])
+            .to_event(&keys)
+            .unwrap();
+        assert!(outbox.is_empty().await);
+
+        outbox.push(&event).await;
+        outbox.push(&event).await;
+        assert_eq!(outbox.pending().await, vec![event.clone()]);
+
+        // The pending event is saved into the database
+        assert_eq!(outbox.database.event_by_id(event.id).await.unwrap(), event);
+
+        assert!(outbox.discard(&event.id).await);
+        assert!(!outbox.discard(&event.id).await);
+        assert!(outbox.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn test_load_not_supported() {
+        let outbox = outbox();
+        assert_eq!(outbox.load().await, 0);
+        assert!(outbox.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn test_flush_keeps_unsent_events() {
+        let outbox = outbox();
+        let keys = Keys::generate();
+        let event = EventBuilder::new_text_note("pending", [])
+            .to_event(&keys)
+            .unwrap();
+        outbox.push(&event).await;
+
+        // The relay is not in the pool
+        let pool = RelayPool::new(RelayPoolOptions::default());
+        let url = Url::parse("wss://relay.example.com").unwrap();
+        let sent = outbox.flush(&pool, url, RelaySendOptions::new()).await;
+        assert_eq!(sent, 0);
+        assert_eq!(outbox.pending().await, vec![event]);
+    }
+}
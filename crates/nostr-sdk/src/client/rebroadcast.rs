@@ -0,0 +1,77 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Rebroadcast
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use nostr::{Event, EventId, Filter, Url};
+use nostr_database::DynNostrDatabase;
+
+use crate::relay::{FilterOptions, Relay, RelaySendOptions};
+
+/// Rebroadcast result of a single relay
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RelayRebroadcast {
+    /// Number of events the relay already had
+    pub present: usize,
+    /// Events sent to the relay
+    pub sent: Vec<EventId>,
+    /// Events rejected by the relay, with the reason
+    pub failed: HashMap<EventId, String>,
+    /// Error that prevented to check the events of the relay
+    pub error: Option<String>,
+}
+
+/// Send to `relay` the `events` that it doesn't have yet
+///
+/// An event is considered present if it was seen on the relay.
+/// The events returned by the relay are marked as seen on it by the pool, also if already stored.
+pub(crate) async fn rebroadcast_to_relay(
+    relay: Relay,
+    database: Arc<DynNostrDatabase>,
+    events: Arc<Vec<Event>>,
+    timeout: Duration,
+    opts: RelaySendOptions,
+) -> RelayRebroadcast {
+    let url: Url = relay.url();
+    let mut report = RelayRebroadcast::default();
+
+    // The returned events include the ones stored into the local database: not usable here
+    let filter: Filter = Filter::new().ids(events.iter().map(|e| e.id));
+    if let Err(e) = relay
+        .get_events_of(vec![filter], timeout, FilterOptions::ExitOnEOSE)
+        .await
+    {
+        report.error = Some(e.to_string());
+        return report;
+    }
+
+    let mut present: HashSet<EventId> = HashSet::new();
+    for event in events.iter() {
+        if let Ok(Some(relays)) = database.event_seen_on_relays(event.id).await {
+            if relays.contains(&url) {
+                present.insert(event.id);
+            }
+        }
+    }
+
+    for event in events.iter() {
+        if present.contains(&event.id) {
+            report.present += 1;
+            continue;
+        }
+
+        match relay.send_event(event.clone(), opts).await {
+            Ok(event_id) => report.sent.push(event_id),
+            Err(e) => {
+                report.failed.insert(event.id, e.to_string());
+            }
+        }
+    }
+
+    report
+}
@@ -0,0 +1,118 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Relay list sync (NIP65)
+
+use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use nostr::nips::nip65;
+use nostr::url::Url;
+use nostr::{Event, RelayMetadata, Timestamp};
+use tokio::sync::RwLock;
+
+use crate::relay::pool::{Error, RelayPool};
+use crate::relay::{Relay, RelayOptions};
+
+/// Label of the relays added to the pool from the relay list (see [`RelayOptions::labels`])
+///
+/// Only the relays with this label are removed when they are dropped from the relay list.
+pub const RELAY_LIST_LABEL: &str = "nip65";
+
+/// Keep the relays of the pool in sync with the relay list
+#[derive(Debug, Clone)]
+pub(crate) struct RelayListSync {
+    pool: RelayPool,
+    #[cfg(not(target_arch = "wasm32"))]
+    proxy: Option<SocketAddr>,
+    last_applied: Arc<RwLock<Option<Timestamp>>>,
+}
+
+impl RelayListSync {
+    pub fn new(
+        pool: RelayPool,
+        #[cfg(not(target_arch = "wasm32"))] proxy: Option<SocketAddr>,
+    ) -> Self {
+        Self {
+            pool,
+            #[cfg(not(target_arch = "wasm32"))]
+            proxy,
+            last_applied: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    fn relay_opts(&self, read: bool, write: bool) -> RelayOptions {
+        let opts = RelayOptions::new()
+            .read(read)
+            .write(write)
+            .labels([RELAY_LIST_LABEL]);
+        #[cfg(not(target_arch = "wasm32"))]
+        let opts = opts.proxy(self.proxy);
+        opts
+    }
+
+    /// Forget the last applied relay list (i.e. after a signer change)
+    pub async fn reset(&self) {
+        let mut last_applied = self.last_applied.write().await;
+        *last_applied = None;
+    }
+
+    /// Apply the relay list to the pool
+    ///
+    /// Return `false` if a newer (or the same) relay list has already been applied.
+    pub async fn apply(&self, event: &Event) -> Result<bool, Error> {
+        let mut last_applied = self.last_applied.write().await;
+        if let Some(timestamp) = *last_applied {
+            if event.created_at <= timestamp {
+                return Ok(false);
+            }
+        }
+
+        let list: HashMap<Url, Option<RelayMetadata>> = nip65::extract_relay_list(event)
+            .into_iter()
+            .filter_map(|(url, metadata)| Some((Url::try_from(url).ok()?, metadata)))
+            .collect();
+
+        let relays: HashMap<Url, Relay> = self.pool.relays().await;
+
+        // Remove the relays dropped from the list
+        for (url, relay) in relays.iter() {
+            if relay.has_label(RELAY_LIST_LABEL) && !list.contains_key(url) {
+                tracing::debug!("Removing {url}: not in the relay list anymore");
+                self.pool.remove_relay(url.clone()).await?;
+            }
+        }
+
+        for (url, metadata) in list.into_iter() {
+            let (read, write) = match metadata {
+                Some(RelayMetadata::Read) => (true, false),
+                Some(RelayMetadata::Write) => (false, true),
+                None => (true, true),
+            };
+
+            match relays.get(&url) {
+                Some(relay) => {
+                    let opts: RelayOptions = relay.opts();
+                    opts.update_read(read);
+                    opts.update_write(write);
+                }
+                None => {
+                    tracing::debug!(
+                        "Adding {url} from the relay list (read={read}, write={write})"
+                    );
+                    self.pool
+                        .add_relay(url.clone(), self.relay_opts(read, write))
+                        .await?;
+                    let relay: Relay = self.pool.relay(url).await?;
+                    self.pool.connect_relay(&relay, false).await;
+                }
+            }
+        }
+
+        *last_applied = Some(event.created_at);
+        Ok(true)
+    }
+}
@@ -0,0 +1,225 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Reply tree
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/10.md>
+
+use std::collections::{HashMap, HashSet};
+
+use nostr::{Event, EventId};
+
+/// Node of a [`Thread`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadNode {
+    /// Event ID
+    pub id: EventId,
+    /// Event (`None` if referenced by the replies but not found on relays)
+    pub event: Option<Event>,
+    /// Direct replies, oldest first
+    pub replies: Vec<ThreadNode>,
+}
+
+impl ThreadNode {
+    fn placeholder(id: EventId) -> Self {
+        Self {
+            id,
+            event: None,
+            replies: Vec::new(),
+        }
+    }
+
+    /// Check if the event of the node is missing
+    pub fn is_missing(&self) -> bool {
+        self.event.is_none()
+    }
+
+    /// Find the node of `id` in this subtree
+    pub fn find(&self, id: &EventId) -> Option<&ThreadNode> {
+        if &self.id == id {
+            return Some(self);
+        }
+        self.replies.iter().find_map(|reply| reply.find(id))
+    }
+
+    /// Number of nodes of this subtree (placeholders included)
+    pub fn count(&self) -> usize {
+        1 + self
+            .replies
+            .iter()
+            .map(|reply| reply.count())
+            .sum::<usize>()
+    }
+
+    fn collect_missing(&self, missing: &mut Vec<EventId>) {
+        if self.is_missing() {
+            missing.push(self.id);
+        }
+        for reply in self.replies.iter() {
+            reply.collect_missing(missing);
+        }
+    }
+}
+
+/// Reply tree of a note
+///
+/// The root and the parent of each event are taken from the `e` tags (see [`Event::thread_root`] and [`Event::reply_to`]).
+/// Events referenced but not found are kept as placeholders, to not detach their replies.
+///
+/// # Example
+/// ```rust
+/// use nostr_sdk::prelude::*;
+///
+/// let keys = Keys::generate();
+/// let root = EventBuilder::new_text_note("root", []).to_event(&keys).unwrap();
+/// let reply_tag = |id: EventId, marker: Marker| Tag::Event {
+///     event_id: id,
+///     relay_url: None,
+///     marker: Some(marker),
+/// };
+///
+/// // The direct reply to the root is missing
+/// let missing = EventId::all_zeros();
+/// let reply = EventBuilder::new_text_note(
+///     "reply",
+///     [reply_tag(root.id, Marker::Root), reply_tag(missing, Marker::Reply)],
+/// )
+/// .to_event(&keys)
+/// .unwrap();
+///
+/// let thread = Thread::from_events(reply.id, vec![root.clone(), reply.clone()]);
+/// assert_eq!(thread.root.id, root.id);
+/// assert_eq!(thread.missing(), vec![missing]);
+/// assert_eq!(thread.ancestors(), vec![root.id, missing]);
+/// assert!(thread.root.replies[0].replies[0].event.is_some());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Thread {
+    /// Root of the thread
+    pub root: ThreadNode,
+    /// ID of the event the thread has been requested for
+    pub focus: EventId,
+}
+
+impl Thread {
+    /// Build the thread of `focus` from the events
+    ///
+    /// Events not connected to the root of `focus` are ignored.
+    pub fn from_events(focus: EventId, events: Vec<Event>) -> Self {
+        let events: HashMap<EventId, Event> = events.into_iter().map(|e| (e.id, e)).collect();
+
+        let root_id: EventId = match events.get(&focus) {
+            Some(event) => event.thread_root().unwrap_or(focus),
+            None => focus,
+        };
+
+        // Parent -> children
+        let mut children: HashMap<EventId, Vec<EventId>> = HashMap::new();
+        let mut placeholders: HashSet<EventId> = HashSet::new();
+        for event in events.values() {
+            if event.id == root_id {
+                continue;
+            }
+
+            // Only the events of this thread
+            if event.thread_root() != Some(root_id) && event.id != focus {
+                continue;
+            }
+
+            let parent: EventId = event.reply_to().unwrap_or(root_id);
+            if parent != root_id && !events.contains_key(&parent) && placeholders.insert(parent) {
+                // The parent of the missing event is unknown: attach it to the root
+                children.entry(root_id).or_default().push(parent);
+            }
+            children.entry(parent).or_default().push(event.id);
+        }
+
+        let mut visited: HashSet<EventId> = HashSet::new();
+        let root: ThreadNode = build_node(root_id, &events, &children, &mut visited);
+        Self { root, focus }
+    }
+
+    /// Find the node of `id`
+    pub fn find(&self, id: &EventId) -> Option<&ThreadNode> {
+        self.root.find(id)
+    }
+
+    /// Node of the focused event
+    pub fn focused(&self) -> Option<&ThreadNode> {
+        self.find(&self.focus)
+    }
+
+    /// IDs of the ancestors of the focused event, from the root
+    pub fn ancestors(&self) -> Vec<EventId> {
+        let mut path: Vec<EventId> = Vec::new();
+        if find_path(&self.root, &self.focus, &mut path) {
+            path.pop();
+        }
+        path
+    }
+
+    /// IDs of the events referenced by the thread but not found
+    pub fn missing(&self) -> Vec<EventId> {
+        let mut missing: Vec<EventId> = Vec::new();
+        self.root.collect_missing(&mut missing);
+        missing
+    }
+
+    /// Number of nodes (placeholders included)
+    pub fn len(&self) -> usize {
+        self.root.count()
+    }
+
+    /// Check if the thread has only the root
+    pub fn is_empty(&self) -> bool {
+        self.root.replies.is_empty()
+    }
+}
+
+fn build_node(
+    id: EventId,
+    events: &HashMap<EventId, Event>,
+    children: &HashMap<EventId, Vec<EventId>>,
+    visited: &mut HashSet<EventId>,
+) -> ThreadNode {
+    visited.insert(id);
+
+    let mut node = match events.get(&id) {
+        Some(event) => ThreadNode {
+            id,
+            event: Some(event.clone()),
+            replies: Vec::new(),
+        },
+        None => ThreadNode::placeholder(id),
+    };
+
+    if let Some(ids) = children.get(&id) {
+        for child in ids.iter() {
+            // Malformed tags may create cycles
+            if !visited.contains(child) {
+                node.replies
+                    .push(build_node(*child, events, children, visited));
+            }
+        }
+    }
+
+    node.replies.sort_by(|a, b| {
+        let a_created_at = a.event.as_ref().map(|e| e.created_at);
+        let b_created_at = b.event.as_ref().map(|e| e.created_at);
+        a_created_at
+            .cmp(&b_created_at)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+
+    node
+}
+
+fn find_path(node: &ThreadNode, id: &EventId, path: &mut Vec<EventId>) -> bool {
+    path.push(node.id);
+    if &node.id == id || node.replies.iter().any(|reply| find_path(reply, id, path)) {
+        return true;
+    }
+    path.pop();
+    false
+}
@@ -0,0 +1,203 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Scheduled events
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_utility::thread;
+use nostr::{Event, EventId, Timestamp};
+use tokio::sync::RwLock;
+
+use super::Outbox;
+use crate::relay::pool::{Error as RelayPoolError, RelayPool};
+use crate::relay::RelaySendOptions;
+
+/// Event waiting to be published
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledEvent {
+    /// Signed event
+    pub event: Event,
+    /// When the event will be published
+    pub publish_at: Timestamp,
+}
+
+/// Events scheduled with [`Client::schedule_event`](super::Client::schedule_event)
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Scheduler {
+    entries: Arc<RwLock<BTreeMap<EventId, ScheduledEvent>>>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the scheduled events, ordered by publication time
+    pub async fn list(&self) -> Vec<ScheduledEvent> {
+        let entries = self.entries.read().await;
+        let mut list: Vec<ScheduledEvent> = entries.values().cloned().collect();
+        list.sort_by_key(|scheduled| scheduled.publish_at);
+        list
+    }
+
+    /// Remove the event from the schedule
+    pub async fn cancel(&self, event_id: &EventId) -> bool {
+        let mut entries = self.entries.write().await;
+        entries.remove(event_id).is_some()
+    }
+
+    /// Publish the event at `publish_at`
+    ///
+    /// Scheduling again the same event replaces the publication time.
+    /// If the event can't be published to any relay, it's added to the `outbox`, if any.
+    pub async fn schedule(
+        &self,
+        pool: RelayPool,
+        outbox: Option<Outbox>,
+        event: Event,
+        publish_at: Timestamp,
+        opts: RelaySendOptions,
+    ) {
+        let event_id: EventId = event.id;
+        let mut entries = self.entries.write().await;
+        entries.insert(event_id, ScheduledEvent { event, publish_at });
+        drop(entries);
+
+        let entries = self.entries.clone();
+        let delay = Duration::from_secs(
+            publish_at
+                .as_u64()
+                .saturating_sub(Timestamp::now().as_u64()),
+        );
+        thread::spawn(async move {
+            thread::sleep(delay).await;
+
+            // Skip if cancelled or rescheduled later
+            let mut entries = entries.write().await;
+            let due: bool = matches!(
+                entries.get(&event_id),
+                Some(scheduled) if scheduled.publish_at <= Timestamp::now()
+            );
+            if !due {
+                return;
+            }
+            let scheduled: ScheduledEvent = match entries.remove(&event_id) {
+                Some(scheduled) => scheduled,
+                None => return,
+            };
+            drop(entries);
+
+            match pool.send_event(scheduled.event.clone(), opts).await {
+                Ok(..) => tracing::debug!("Scheduled event {event_id} published"),
+                Err(e) => {
+                    tracing::error!("Impossible to publish scheduled event {event_id}: {e}");
+                    if let (
+                        Some(outbox),
+                        RelayPoolError::NoRelays | RelayPoolError::EventNotPublished(..),
+                    ) = (outbox, e)
+                    {
+                        outbox.push(&scheduled.event).await;
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nostr::{EventBuilder, Keys};
+    use nostr_database::{DatabaseOptions, IntoNostrDatabase, MemoryDatabase};
+
+    use super::*;
+    use crate::relay::RelayPoolOptions;
+
+    fn event(content: &str) -> Event {
+        let keys = Keys::generate();
+        EventBuilder::new_text_note(content, [])
+            .to_event(&keys)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_schedule_cancel() {
+        let scheduler = Scheduler::new();
+        let pool = RelayPool::new(RelayPoolOptions::default());
+        let later = Timestamp::now() + Duration::from_secs(3600);
+        let event1 = event("1");
+        let event2 = event("2");
+
+        scheduler
+            .schedule(
+                pool.clone(),
+                None,
+                event1.clone(),
+                later,
+                RelaySendOptions::new(),
+            )
+            .await;
+        scheduler
+            .schedule(
+                pool.clone(),
+                None,
+                event2.clone(),
+                later - 60u64,
+                RelaySendOptions::new(),
+            )
+            .await;
+
+        // Ordered by publication time
+        let list = scheduler.list().await;
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].event, event2);
+        assert_eq!(list[1].event, event1);
+
+        // Scheduling again replaces the publication time
+        let earlier = later - 120u64;
+        scheduler
+            .schedule(pool, None, event1.clone(), earlier, RelaySendOptions::new())
+            .await;
+        let list = scheduler.list().await;
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].event, event1);
+        assert_eq!(list[0].publish_at, earlier);
+
+        assert!(scheduler.cancel(&event1.id).await);
+        assert!(!scheduler.cancel(&event1.id).await);
+        assert_eq!(scheduler.list().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unpublished_event_added_to_outbox() {
+        let scheduler = Scheduler::new();
+        let database = MemoryDatabase::new(DatabaseOptions::default());
+        let outbox = Outbox::new(database.into_nostr_database());
+        let event = event("scheduled");
+
+        // No relays: the event can't be published
+        let pool = RelayPool::new(RelayPoolOptions::default());
+        scheduler
+            .schedule(
+                pool,
+                Some(outbox.clone()),
+                event.clone(),
+                Timestamp::now(),
+                RelaySendOptions::new(),
+            )
+            .await;
+
+        for _ in 0..50 {
+            if !outbox.is_empty().await {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        assert!(scheduler.list().await.is_empty());
+        assert_eq!(outbox.pending().await, vec![event]);
+    }
+}
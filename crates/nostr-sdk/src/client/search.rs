@@ -0,0 +1,31 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Search (NIP50)
+
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use nostr::{Event, Url};
+
+/// Search result
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult {
+    /// Event
+    pub event: Event,
+    /// Searched relays that returned the event
+    pub relays: HashSet<Url>,
+}
+
+/// Rank the results: returned by more relays first, then newest first
+pub(crate) fn rank(mut results: Vec<SearchResult>) -> Vec<SearchResult> {
+    results.sort_by_key(|r| {
+        (
+            Reverse(r.relays.len()),
+            Reverse(r.event.created_at),
+            r.event.id,
+        )
+    });
+    results
+}
@@ -5,19 +5,188 @@
 //! Client Signers
 
 use std::fmt;
+use std::sync::Arc;
 
 #[cfg(all(feature = "nip07", target_arch = "wasm32"))]
 use nostr::nips::nip07::Nip07Signer;
-use nostr::Keys;
+#[cfg(feature = "nip44")]
+use nostr::nips::nip44::{self, Version};
+use nostr::secp256k1::XOnlyPublicKey;
+use nostr::{Event, Keys, UnsignedEvent};
+use nostr_database::{async_trait, AsyncTraitDeps};
 
 #[cfg(feature = "nip46")]
 pub mod nip46;
 
 #[cfg(feature = "nip46")]
 use self::nip46::Nip46Signer;
-#[cfg(feature = "nip46")]
 use super::Error;
 
+/// Nostr signer
+///
+/// Implemented for [`Keys`], [`Nip46Signer`] and (on `wasm32`) the NIP07 signer.
+/// Custom implementations can be used with the [`Client`](super::Client) through [`ClientSigner::custom`].
+///
+/// Encryption methods return [`Error::UnsupportedSignerMethod`] by default.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait NostrSigner: AsyncTraitDeps {
+    /// Get signer public key
+    async fn get_public_key(&self) -> Result<XOnlyPublicKey, Error>;
+
+    /// Sign an [`UnsignedEvent`]
+    async fn sign_event(&self, unsigned: UnsignedEvent) -> Result<Event, Error>;
+
+    /// NIP04 encrypt
+    #[cfg(feature = "nip04")]
+    async fn nip04_encrypt(
+        &self,
+        _public_key: XOnlyPublicKey,
+        _content: String,
+    ) -> Result<String, Error> {
+        Err(Error::UnsupportedSignerMethod("nip04_encrypt"))
+    }
+
+    /// NIP04 decrypt
+    #[cfg(feature = "nip04")]
+    async fn nip04_decrypt(
+        &self,
+        _public_key: XOnlyPublicKey,
+        _encrypted_content: String,
+    ) -> Result<String, Error> {
+        Err(Error::UnsupportedSignerMethod("nip04_decrypt"))
+    }
+
+    /// NIP44 encrypt
+    #[cfg(feature = "nip44")]
+    async fn nip44_encrypt(
+        &self,
+        _public_key: XOnlyPublicKey,
+        _content: String,
+    ) -> Result<String, Error> {
+        Err(Error::UnsupportedSignerMethod("nip44_encrypt"))
+    }
+
+    /// NIP44 decrypt
+    #[cfg(feature = "nip44")]
+    async fn nip44_decrypt(
+        &self,
+        _public_key: XOnlyPublicKey,
+        _payload: String,
+    ) -> Result<String, Error> {
+        Err(Error::UnsupportedSignerMethod("nip44_decrypt"))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl NostrSigner for Keys {
+    async fn get_public_key(&self) -> Result<XOnlyPublicKey, Error> {
+        Ok(self.public_key())
+    }
+
+    async fn sign_event(&self, unsigned: UnsignedEvent) -> Result<Event, Error> {
+        Ok(unsigned.sign(self)?)
+    }
+
+    #[cfg(feature = "nip04")]
+    async fn nip04_encrypt(
+        &self,
+        public_key: XOnlyPublicKey,
+        content: String,
+    ) -> Result<String, Error> {
+        Ok(nostr::nips::nip04::encrypt(
+            &self.secret_key()?,
+            &public_key,
+            content,
+        )?)
+    }
+
+    #[cfg(feature = "nip04")]
+    async fn nip04_decrypt(
+        &self,
+        public_key: XOnlyPublicKey,
+        encrypted_content: String,
+    ) -> Result<String, Error> {
+        Ok(nostr::nips::nip04::decrypt(
+            &self.secret_key()?,
+            &public_key,
+            encrypted_content,
+        )?)
+    }
+
+    #[cfg(feature = "nip44")]
+    async fn nip44_encrypt(
+        &self,
+        public_key: XOnlyPublicKey,
+        content: String,
+    ) -> Result<String, Error> {
+        Ok(nip44::encrypt(
+            &self.secret_key()?,
+            &public_key,
+            content,
+            Version::V2,
+        )?)
+    }
+
+    #[cfg(feature = "nip44")]
+    async fn nip44_decrypt(
+        &self,
+        public_key: XOnlyPublicKey,
+        payload: String,
+    ) -> Result<String, Error> {
+        Ok(nip44::decrypt(&self.secret_key()?, &public_key, payload)?)
+    }
+}
+
+#[cfg(all(feature = "nip07", target_arch = "wasm32"))]
+#[async_trait(?Send)]
+impl NostrSigner for Nip07Signer {
+    async fn get_public_key(&self) -> Result<XOnlyPublicKey, Error> {
+        Ok(Nip07Signer::get_public_key(self).await?)
+    }
+
+    async fn sign_event(&self, unsigned: UnsignedEvent) -> Result<Event, Error> {
+        Ok(Nip07Signer::sign_event(self, unsigned).await?)
+    }
+
+    #[cfg(feature = "nip04")]
+    async fn nip04_encrypt(
+        &self,
+        public_key: XOnlyPublicKey,
+        content: String,
+    ) -> Result<String, Error> {
+        Ok(Nip07Signer::nip04_encrypt(self, public_key, content).await?)
+    }
+
+    #[cfg(feature = "nip04")]
+    async fn nip04_decrypt(
+        &self,
+        public_key: XOnlyPublicKey,
+        encrypted_content: String,
+    ) -> Result<String, Error> {
+        Ok(Nip07Signer::nip04_decrypt(self, public_key, encrypted_content).await?)
+    }
+
+    #[cfg(feature = "nip44")]
+    async fn nip44_encrypt(
+        &self,
+        public_key: XOnlyPublicKey,
+        content: String,
+    ) -> Result<String, Error> {
+        Ok(Nip07Signer::nip44_encrypt(self, public_key, content).await?)
+    }
+
+    #[cfg(feature = "nip44")]
+    async fn nip44_decrypt(
+        &self,
+        public_key: XOnlyPublicKey,
+        payload: String,
+    ) -> Result<String, Error> {
+        Ok(Nip07Signer::nip44_decrypt(self, public_key, payload).await?)
+    }
+}
+
 /// Client Signer Type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ClientSignerType {
@@ -29,6 +198,8 @@ pub enum ClientSignerType {
     /// NIP46
     #[cfg(feature = "nip46")]
     NIP46,
+    /// Custom
+    Custom,
 }
 
 // TODO: better display
@@ -40,6 +211,7 @@ impl fmt::Display for ClientSignerType {
             Self::NIP07 => write!(f, "NIP07"),
             #[cfg(feature = "nip46")]
             Self::NIP46 => write!(f, "NIP46"),
+            Self::Custom => write!(f, "Custom"),
         }
     }
 }
@@ -55,9 +227,57 @@ pub enum ClientSigner {
     /// NIP46 signer
     #[cfg(feature = "nip46")]
     NIP46(Nip46Signer),
+    /// Custom signer
+    Custom(Arc<dyn NostrSigner>),
 }
 
 impl ClientSigner {
+    /// Custom [`NostrSigner`]
+    ///
+    /// # Example
+    /// ```rust
+    /// use nostr_sdk::client::Error;
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// /// Sign with the keys, logging every signed event
+    /// #[derive(Debug)]
+    /// struct LoggingSigner {
+    ///     keys: Keys,
+    /// }
+    ///
+    /// #[async_trait]
+    /// impl NostrSigner for LoggingSigner {
+    ///     async fn get_public_key(&self) -> Result<XOnlyPublicKey, Error> {
+    ///         Ok(self.keys.public_key())
+    ///     }
+    ///
+    ///     async fn sign_event(&self, unsigned: UnsignedEvent) -> Result<Event, Error> {
+    ///         let event: Event = unsigned.sign(&self.keys)?;
+    ///         println!("Signed event {}", event.id);
+    ///         Ok(event)
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let signer = LoggingSigner {
+    ///     keys: Keys::generate(),
+    /// };
+    /// let client = Client::new(ClientSigner::custom(signer));
+    /// # let signer = client.signer().await.unwrap();
+    /// # let public_key = signer.get_public_key().await.unwrap();
+    /// # let unsigned = EventBuilder::new_text_note("Hello", []).to_unsigned_event(public_key);
+    /// # let event = signer.sign_event(unsigned).await.unwrap();
+    /// # assert!(event.verify().is_ok());
+    /// # }
+    /// ```
+    pub fn custom<T>(signer: T) -> Self
+    where
+        T: NostrSigner + 'static,
+    {
+        Self::Custom(Arc::new(signer))
+    }
+
     /// Get Client Signer Type
     pub fn r#type(&self) -> ClientSignerType {
         match self {
@@ -66,10 +286,72 @@ impl ClientSigner {
             Self::NIP07(..) => ClientSignerType::NIP07,
             #[cfg(feature = "nip46")]
             Self::NIP46(..) => ClientSignerType::NIP46,
+            Self::Custom(..) => ClientSignerType::Custom,
+        }
+    }
+
+    fn as_signer(&self) -> &dyn NostrSigner {
+        match self {
+            Self::Keys(keys) => keys,
+            #[cfg(all(feature = "nip07", target_arch = "wasm32"))]
+            Self::NIP07(nip07) => nip07,
+            #[cfg(feature = "nip46")]
+            Self::NIP46(nip46) => nip46,
+            Self::Custom(signer) => signer.as_ref(),
         }
     }
 }
 
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl NostrSigner for ClientSigner {
+    async fn get_public_key(&self) -> Result<XOnlyPublicKey, Error> {
+        self.as_signer().get_public_key().await
+    }
+
+    async fn sign_event(&self, unsigned: UnsignedEvent) -> Result<Event, Error> {
+        self.as_signer().sign_event(unsigned).await
+    }
+
+    #[cfg(feature = "nip04")]
+    async fn nip04_encrypt(
+        &self,
+        public_key: XOnlyPublicKey,
+        content: String,
+    ) -> Result<String, Error> {
+        self.as_signer().nip04_encrypt(public_key, content).await
+    }
+
+    #[cfg(feature = "nip04")]
+    async fn nip04_decrypt(
+        &self,
+        public_key: XOnlyPublicKey,
+        encrypted_content: String,
+    ) -> Result<String, Error> {
+        self.as_signer()
+            .nip04_decrypt(public_key, encrypted_content)
+            .await
+    }
+
+    #[cfg(feature = "nip44")]
+    async fn nip44_encrypt(
+        &self,
+        public_key: XOnlyPublicKey,
+        content: String,
+    ) -> Result<String, Error> {
+        self.as_signer().nip44_encrypt(public_key, content).await
+    }
+
+    #[cfg(feature = "nip44")]
+    async fn nip44_decrypt(
+        &self,
+        public_key: XOnlyPublicKey,
+        payload: String,
+    ) -> Result<String, Error> {
+        self.as_signer().nip44_decrypt(public_key, payload).await
+    }
+}
+
 impl From<Keys> for ClientSigner {
     fn from(keys: Keys) -> Self {
         Self::Keys(keys)
@@ -110,3 +392,9 @@ impl TryFrom<ClientSigner> for Nip46Signer {
         }
     }
 }
+
+impl From<Arc<dyn NostrSigner>> for ClientSigner {
+    fn from(signer: Arc<dyn NostrSigner>) -> Self {
+        Self::Custom(signer)
+    }
+}
@@ -0,0 +1,512 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Nostr Connect Client (NIP46)
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/46.md>
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_utility::time;
+use nostr::nips::nip04;
+use nostr::nips::nip46::{
+    self, BunkerURI, Message, NostrConnectMetadata, NostrConnectURI, Request, Response, CONNECT_ACK,
+};
+use nostr::secp256k1::XOnlyPublicKey;
+use nostr::{serde_json, Event, JsonUtil, Keys, UnsignedEvent};
+use nostr::{ClientMessage, EventBuilder, Filter, Kind, SubscriptionId, Timestamp, Url};
+use nostr_database::async_trait;
+use tokio::sync::{Mutex, OnceCell};
+
+use super::NostrSigner;
+#[cfg(feature = "blocking")]
+use crate::client::blocking::Client as BlockingClient;
+use crate::client::{Client, Error};
+use crate::relay::pool::RelayPool;
+use crate::relay::{RelayOptions, RelayPoolNotification, RelayPoolOptions, RelaySendOptions};
+#[cfg(feature = "blocking")]
+use crate::RUNTIME;
+
+pub mod remote;
+
+pub use self::remote::{AutoApprovePolicy, NostrConnectApproval, NostrConnectRemoteSigner};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// NIP46 Signer
+///
+/// Connect to the signer relays with its own [`RelayPool`], on the first request.
+///
+/// Before the first request, the connection with the signer is established automatically:
+/// * if the signer public key is unknown (`nostrconnect://` flow), wait for the `connect` request of the signer;
+/// * if the signer has been created from a [`BunkerURI`], send the `connect` request (with the optional secret) and wait for the `ack`.
+///
+/// If all the relays are disconnected, they are reconnected before sending the next request.
+#[derive(Debug, Clone)]
+pub struct Nip46Signer {
+    relays: Vec<Url>,
+    app_keys: Keys,
+    signer_public_key: Arc<Mutex<Option<XOnlyPublicKey>>>,
+    secret: Option<String>,
+    connected: Arc<AtomicBool>,
+    timeout: Option<Duration>,
+    pool: Arc<OnceCell<RelayPool>>,
+}
+
+impl Nip46Signer {
+    /// New NIP46 remote signer
+    ///
+    /// If the `signer_public_key` is known, the app is considered already connected to the signer.
+    pub fn new(relay_url: Url, app_keys: Keys, signer_public_key: Option<XOnlyPublicKey>) -> Self {
+        Self {
+            relays: vec![relay_url],
+            app_keys,
+            connected: Arc::new(AtomicBool::new(signer_public_key.is_some())),
+            signer_public_key: Arc::new(Mutex::new(signer_public_key)),
+            secret: None,
+            timeout: Some(DEFAULT_TIMEOUT),
+            pool: Arc::new(OnceCell::new()),
+        }
+    }
+
+    /// New NIP46 remote signer from [`BunkerURI`]
+    ///
+    /// The `connect` request is sent to the signer before the first request.
+    /// Return [`Error::NIP46`] if the URI has no relays.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::str::FromStr;
+    ///
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let uri = BunkerURI::from_str("bunker://79dff8f82963424e0bb02708a22e44b4980893e3a4be0fa3cb60a43b946764e3?relay=wss%3A%2F%2Frelay.nsec.app&secret=abcd").unwrap();
+    /// let signer = Nip46Signer::from_bunker_uri(uri, Keys::generate()).unwrap();
+    /// let client = Client::new(signer);
+    /// client.publish_text_note("Signed by a bunker", []).await.unwrap();
+    /// # }
+    /// ```
+    pub fn from_bunker_uri(uri: BunkerURI, app_keys: Keys) -> Result<Self, Error> {
+        if uri.relays.is_empty() {
+            return Err(Error::NIP46(nip46::Error::InvalidURI));
+        }
+
+        Ok(Self {
+            relays: uri.relays,
+            app_keys,
+            signer_public_key: Arc::new(Mutex::new(Some(uri.signer_public_key))),
+            secret: uri.secret,
+            connected: Arc::new(AtomicBool::new(false)),
+            timeout: Some(DEFAULT_TIMEOUT),
+            pool: Arc::new(OnceCell::new()),
+        })
+    }
+
+    /// Set timeout of the requests sent through [`NostrSigner`] methods (default: 180 secs)
+    ///
+    /// Overwritten by [`Options::nip46_timeout`](crate::Options::nip46_timeout) when the signer is passed to the [`Client`] constructors.
+    pub fn timeout(self, timeout: Option<Duration>) -> Self {
+        Self { timeout, ..self }
+    }
+
+    /// Get first signer relay [`Url`]
+    pub fn relay_url(&self) -> Url {
+        self.relays[0].clone()
+    }
+
+    /// Get signer relays
+    pub fn relays(&self) -> Vec<Url> {
+        self.relays.clone()
+    }
+
+    /// Get signer [`XOnlyPublicKey`]
+    pub async fn signer_public_key(&self) -> Option<XOnlyPublicKey> {
+        let pubkey = self.signer_public_key.lock().await;
+        *pubkey
+    }
+
+    pub(crate) async fn set_signer_public_key(&self, public_key: XOnlyPublicKey) {
+        let mut pubkey = self.signer_public_key.lock().await;
+        *pubkey = Some(public_key);
+    }
+
+    /// Check if the connection with the signer has been established
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    /// Compose Nostr Connect URI
+    pub fn nostr_connect_uri(&self, metadata: NostrConnectMetadata) -> NostrConnectURI {
+        NostrConnectURI::new(self.app_keys.public_key(), self.relay_url(), metadata.name)
+    }
+
+    async fn pool(&self) -> Result<&RelayPool, Error> {
+        let pool: &RelayPool = self
+            .pool
+            .get_or_try_init(|| async {
+                let pool = RelayPool::new(RelayPoolOptions::default());
+                for url in self.relays.iter() {
+                    pool.add_relay(url.clone(), RelayOptions::default()).await?;
+                }
+                pool.connect(true).await;
+                Ok::<RelayPool, Error>(pool)
+            })
+            .await?;
+
+        // Reconnect, if all the relays are disconnected
+        let mut connected: bool = false;
+        for relay in pool.relays().await.values() {
+            if relay.is_connected().await {
+                connected = true;
+                break;
+            }
+        }
+        if !connected {
+            tracing::debug!("NIP46 signer relays disconnected: reconnecting");
+            pool.connect(true).await;
+        }
+
+        Ok(pool)
+    }
+
+    /// Wait for the `Connect` request of the signer, if the signer public key is not known yet
+    pub async fn req_signer_public_key(&self, timeout: Option<Duration>) -> Result<(), Error> {
+        if self.signer_public_key().await.is_none() {
+            let pool: &RelayPool = self.pool().await?;
+            let public_key = self.app_keys.public_key();
+            let secret_key = self.app_keys.secret_key()?;
+
+            let id = SubscriptionId::generate();
+            let filter = Filter::new()
+                .pubkey(public_key)
+                .kind(Kind::NostrConnect)
+                .since(Timestamp::now());
+
+            // Subscribe
+            pool.send_msg(ClientMessage::new_req(id.clone(), vec![filter]), None)
+                .await?;
+
+            let mut notifications = pool.notifications();
+            time::timeout(timeout, async {
+                while let Ok(notification) = notifications.recv().await {
+                    if let RelayPoolNotification::Event { event, .. } = notification {
+                        if event.kind == Kind::NostrConnect {
+                            let msg: String =
+                                nip04::decrypt(&secret_key, &event.pubkey, &event.content)?;
+                            let msg = Message::from_json(msg)?;
+                            if let Ok(
+                                Request::Connect(public_key)
+                                | Request::ConnectWithSecret { public_key, .. },
+                            ) = msg.to_request()
+                            {
+                                self.set_signer_public_key(public_key).await;
+                                self.connected.store(true, Ordering::SeqCst);
+                                break;
+                            }
+                        }
+                    }
+                }
+
+                Ok::<(), Error>(())
+            })
+            .await
+            .ok_or(Error::Timeout)??;
+
+            // Unsubscribe
+            pool.send_msg(ClientMessage::close(id), None).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Send the `connect` request to the signer and wait for the `ack`
+    ///
+    /// Automatically called before the first request, if needed.
+    pub async fn connect(&self, timeout: Option<Duration>) -> Result<(), Error> {
+        let public_key = self
+            .signer_public_key()
+            .await
+            .ok_or(Error::SignerPublicKeyNotFound)?;
+        let req = Request::connect(public_key, self.secret.clone());
+        match self.send_req(req, timeout).await? {
+            Response::Connect => {
+                self.connected.store(true, Ordering::SeqCst);
+                Ok(())
+            }
+            _ => Err(Error::ResponseNotMatchRequest),
+        }
+    }
+
+    /// Establish the connection with the signer, if not done yet
+    async fn ensure_connected(&self) -> Result<(), Error> {
+        if !self.is_connected() {
+            if self.signer_public_key().await.is_none() {
+                self.req_signer_public_key(self.timeout).await?;
+            } else {
+                self.connect(self.timeout).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Send NIP46 [`Request`] to signer
+    ///
+    /// Establish the connection with the signer first, if needed.
+    pub async fn send_req_to_signer(
+        &self,
+        req: Request,
+        timeout: Option<Duration>,
+    ) -> Result<Response, Error> {
+        self.ensure_connected().await?;
+        self.send_req(req, timeout).await
+    }
+
+    async fn send_req(&self, req: Request, timeout: Option<Duration>) -> Result<Response, Error> {
+        let signer_pubkey = self
+            .signer_public_key()
+            .await
+            .ok_or(Error::SignerPublicKeyNotFound)?;
+
+        let pool: &RelayPool = self.pool().await?;
+
+        let msg = Message::request(req.clone());
+        let req_id = msg.id();
+
+        let public_key = self.app_keys.public_key();
+        let secret_key = self.app_keys.secret_key()?;
+
+        // Build request
+        let event = EventBuilder::nostr_connect(&self.app_keys, signer_pubkey, msg)?
+            .to_event(&self.app_keys)?;
+
+        let sub_id = SubscriptionId::generate();
+        let filter = Filter::new()
+            .pubkey(public_key)
+            .kind(Kind::NostrConnect)
+            .since(Timestamp::now());
+
+        // Subscribe
+        pool.send_msg(ClientMessage::new_req(sub_id.clone(), vec![filter]), None)
+            .await?;
+
+        let mut notifications = pool.notifications();
+
+        // Send request to signer
+        pool.send_event(event, RelaySendOptions::new()).await?;
+
+        let future = async {
+            while let Ok(notification) = notifications.recv().await {
+                if let RelayPoolNotification::Event { event, .. } = notification {
+                    if event.kind == Kind::NostrConnect {
+                        let msg = nip04::decrypt(&secret_key, &event.pubkey, &event.content)?;
+                        let msg = Message::from_json(msg)?;
+
+                        tracing::debug!("New message received: {msg:?}");
+
+                        if let Message::Response { id, result, error } = &msg {
+                            if &req_id == id {
+                                if let Some(result) = result {
+                                    return parse_response(&req, result.to_owned());
+                                }
+
+                                if let Some(error) = error {
+                                    return Err(Error::Response(error.to_owned()));
+                                }
+
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+
+            Err(Error::Generic)
+        };
+
+        let res: Result<Response, Error> =
+            time::timeout(timeout, future).await.ok_or(Error::Timeout)?;
+
+        // Unsubscribe
+        pool.send_msg(ClientMessage::close(sub_id), None).await?;
+
+        res
+    }
+}
+
+fn parse_response(req: &Request, result: serde_json::Value) -> Result<Response, Error> {
+    Ok(match req {
+        Request::Describe => Response::Describe(serde_json::from_value(result)?),
+        Request::GetPublicKey => Response::GetPublicKey(serde_json::from_value(result)?),
+        Request::SignEvent(_) => Response::SignEvent(serde_json::from_value(result)?),
+        Request::Delegate { .. } => Response::Delegate(serde_json::from_value(result)?),
+        Request::Nip04Encrypt { .. } => Response::Nip04Encrypt(serde_json::from_value(result)?),
+        Request::Nip04Decrypt { .. } => Response::Nip04Decrypt(serde_json::from_value(result)?),
+        Request::SignSchnorr { .. } => Response::SignSchnorr(serde_json::from_value(result)?),
+        Request::Connect(_) | Request::ConnectWithSecret { .. }
+            if result.as_str() == Some(CONNECT_ACK) =>
+        {
+            Response::Connect
+        }
+        _ => return Err(Error::ResponseNotMatchRequest),
+    })
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl NostrSigner for Nip46Signer {
+    async fn get_public_key(&self) -> Result<XOnlyPublicKey, Error> {
+        self.ensure_connected().await?;
+        self.signer_public_key()
+            .await
+            .ok_or(Error::SignerPublicKeyNotFound)
+    }
+
+    async fn sign_event(&self, unsigned: UnsignedEvent) -> Result<Event, Error> {
+        let res: Response = self
+            .send_req_to_signer(Request::SignEvent(unsigned), self.timeout)
+            .await?;
+        if let Response::SignEvent(event) = res {
+            Ok(event)
+        } else {
+            Err(Error::ResponseNotMatchRequest)
+        }
+    }
+
+    async fn nip04_encrypt(
+        &self,
+        public_key: XOnlyPublicKey,
+        content: String,
+    ) -> Result<String, Error> {
+        let req = Request::Nip04Encrypt {
+            public_key,
+            text: content,
+        };
+        let res: Response = self.send_req_to_signer(req, self.timeout).await?;
+        if let Response::Nip04Encrypt(content) = res {
+            Ok(content)
+        } else {
+            Err(Error::ResponseNotMatchRequest)
+        }
+    }
+
+    async fn nip04_decrypt(
+        &self,
+        public_key: XOnlyPublicKey,
+        encrypted_content: String,
+    ) -> Result<String, Error> {
+        let req = Request::Nip04Decrypt {
+            public_key,
+            text: encrypted_content,
+        };
+        let res: Response = self.send_req_to_signer(req, self.timeout).await?;
+        if let Response::Nip04Decrypt(content) = res {
+            Ok(content)
+        } else {
+            Err(Error::ResponseNotMatchRequest)
+        }
+    }
+}
+
+impl Client {
+    /// Request the [`XOnlyPublicKey`] of the signer (sent with `Connect` request)
+    ///
+    /// Call not required if you already added in `Client::with_remote_signer`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let app_keys = Keys::generate();
+    ///     let relay_url = Url::parse("wss://relay.example.com").unwrap();
+    ///     let signer = Nip46Signer::new(relay_url, app_keys, None);
+    ///     let client = Client::new(signer);
+    ///
+    ///     // Signer public key MUST be requested in this case
+    ///     client
+    ///         .req_signer_public_key(Some(Duration::from_secs(180)))
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use std::str::FromStr;
+    ///
+    /// use nostr_sdk::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let app_keys = Keys::generate();
+    ///     let relay_url = Url::parse("wss://relay.example.com").unwrap();
+    ///     let signer_public_key = XOnlyPublicKey::from_str(
+    ///         "b2d670de53b27691c0c3400225b65c35a26d06093bcc41f48ffc71e0907f9d4a",
+    ///     )
+    ///     .unwrap();
+    ///     let signer = Nip46Signer::new(relay_url, app_keys, Some(signer_public_key));
+    ///
+    ///     // Signer public key request isn't needed since we already added in client constructor
+    ///     let _client = Client::new(signer);
+    /// }
+    /// ```
+    pub async fn req_signer_public_key(&self, timeout: Option<Duration>) -> Result<(), Error> {
+        let signer: Nip46Signer = self.signer().await?.try_into()?;
+        signer.req_signer_public_key(timeout).await
+    }
+
+    /// Send NIP46 [`Request`] to signer
+    pub async fn send_req_to_signer(
+        &self,
+        req: Request,
+        timeout: Option<Duration>,
+    ) -> Result<Response, Error> {
+        let signer: Nip46Signer = self.signer().await?.try_into()?;
+        signer.send_req_to_signer(req, timeout).await
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl BlockingClient {
+    #[allow(missing_docs)]
+    pub fn req_signer_public_key(&self, timeout: Option<Duration>) -> Result<(), Error> {
+        RUNTIME.block_on(async { self.client.req_signer_public_key(timeout).await })
+    }
+
+    #[allow(missing_docs)]
+    pub fn send_req_to_signer(
+        &self,
+        req: Request,
+        timeout: Option<Duration>,
+    ) -> Result<Response, Error> {
+        RUNTIME.block_on(async { self.client.send_req_to_signer(req, timeout).await })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_bunker_uri_without_relays() {
+        let signer_public_key = Keys::generate().public_key();
+        let uri = BunkerURI::new(signer_public_key, []);
+        assert!(matches!(
+            Nip46Signer::from_bunker_uri(uri, Keys::generate()),
+            Err(Error::NIP46(nip46::Error::InvalidURI))
+        ));
+
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+        let uri = BunkerURI::new(signer_public_key, [relay_url.clone()]);
+        let signer = Nip46Signer::from_bunker_uri(uri, Keys::generate()).unwrap();
+        assert_eq!(signer.relay_url(), relay_url);
+    }
+}
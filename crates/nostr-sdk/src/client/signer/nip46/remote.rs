@@ -0,0 +1,377 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Nostr Connect Remote Signer (NIP46)
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/46.md>
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use nostr::nips::nip04;
+use nostr::nips::nip46::{BunkerURI, Message, NostrConnectURI, Request, Response};
+use nostr::secp256k1::XOnlyPublicKey;
+use nostr::{ClientMessage, Event, EventBuilder, Filter, JsonUtil, Keys, Kind, Timestamp, Url};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::RwLock;
+
+use crate::client::Error;
+use crate::relay::pool::RelayPool;
+use crate::relay::{RelayOptions, RelayPoolNotification, RelayPoolOptions};
+use crate::util::TryIntoUrl;
+
+/// Decide if a request not covered by the [`AutoApprovePolicy`] must be served
+///
+/// Any `Fn(XOnlyPublicKey, &Request) -> bool` closure is an approval callback.
+/// The callback is called from the [`NostrConnectRemoteSigner::run`] loop: a slow callback delays the next requests.
+pub trait NostrConnectApproval: Send + Sync {
+    /// Return `true` to serve the `req` sent by the app with `app_public_key`
+    fn approve(&self, app_public_key: XOnlyPublicKey, req: &Request) -> bool;
+}
+
+impl<F> NostrConnectApproval for F
+where
+    F: Fn(XOnlyPublicKey, &Request) -> bool + Send + Sync,
+{
+    fn approve(&self, app_public_key: XOnlyPublicKey, req: &Request) -> bool {
+        self(app_public_key, req)
+    }
+}
+
+/// Requests served without asking the [`NostrConnectApproval`] callback
+///
+/// # Example
+/// ```rust
+/// use nostr_sdk::prelude::*;
+///
+/// let app = Keys::generate().public_key();
+/// let policy = AutoApprovePolicy::new()
+///     .method("get_public_key")
+///     .app_method(app, "nip04_encrypt");
+///
+/// assert!(policy.is_approved(&Keys::generate().public_key(), &nip46::Request::GetPublicKey));
+/// let req = nip46::Request::Nip04Encrypt {
+///     public_key: app,
+///     text: String::from("hello"),
+/// };
+/// assert!(policy.is_approved(&app, &req));
+/// assert!(!policy.is_approved(&Keys::generate().public_key(), &req));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AutoApprovePolicy {
+    methods: HashSet<String>,
+    apps: HashMap<XOnlyPublicKey, Option<HashSet<String>>>,
+}
+
+impl AutoApprovePolicy {
+    /// New empty policy: nothing auto-approved
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Auto-approve `method` (i.e. `get_public_key`) for every app
+    pub fn method<S>(mut self, method: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.methods.insert(method.into());
+        self
+    }
+
+    /// Auto-approve every method for the app
+    pub fn app(mut self, app_public_key: XOnlyPublicKey) -> Self {
+        self.apps.insert(app_public_key, None);
+        self
+    }
+
+    /// Auto-approve `method` for the app
+    pub fn app_method<S>(mut self, app_public_key: XOnlyPublicKey, method: S) -> Self
+    where
+        S: Into<String>,
+    {
+        let entry = self
+            .apps
+            .entry(app_public_key)
+            .or_insert_with(|| Some(HashSet::new()));
+        if let Some(methods) = entry {
+            methods.insert(method.into());
+        }
+        self
+    }
+
+    /// Check if `req` of the app is auto-approved
+    pub fn is_approved(&self, app_public_key: &XOnlyPublicKey, req: &Request) -> bool {
+        let method: String = req.method();
+        if self.methods.contains(&method) {
+            return true;
+        }
+
+        match self.apps.get(app_public_key) {
+            Some(None) => true,
+            Some(Some(methods)) => methods.contains(&method),
+            None => false,
+        }
+    }
+}
+
+/// Nostr Connect Remote Signer
+///
+/// Listen for NIP46 requests on the relays and serve them with the local [`Keys`].
+/// Requests are served if approved by the [`AutoApprovePolicy`] or by the [`NostrConnectApproval`] callback,
+/// otherwise an error response is sent to the app.
+///
+/// # Example
+/// ```rust,no_run
+/// use nostr_sdk::prelude::*;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let keys = Keys::generate();
+/// let policy = AutoApprovePolicy::new()
+///     .method("connect")
+///     .method("get_public_key");
+/// let signer = NostrConnectRemoteSigner::new(keys)
+///     .secret("my-secret")
+///     .policy(policy)
+///     .approval(|app: XOnlyPublicKey, req: &nip46::Request| {
+///         println!("{app} requested {}: approve", req.method());
+///         true
+///     });
+/// signer.add_relay("wss://relay.nsec.app").await.unwrap();
+///
+/// // Share with the apps
+/// println!("{}", signer.bunker_uri().await);
+///
+/// signer.run().await.unwrap();
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct NostrConnectRemoteSigner {
+    keys: Keys,
+    pool: RelayPool,
+    secret: Option<String>,
+    policy: AutoApprovePolicy,
+    approval: Option<Arc<dyn NostrConnectApproval>>,
+    connected_apps: Arc<RwLock<HashSet<XOnlyPublicKey>>>,
+}
+
+impl NostrConnectRemoteSigner {
+    /// New remote signer, serving the requests with `keys`
+    pub fn new(keys: Keys) -> Self {
+        Self {
+            keys,
+            pool: RelayPool::new(RelayPoolOptions::default()),
+            secret: None,
+            policy: AutoApprovePolicy::default(),
+            approval: None,
+            connected_apps: Arc::new(RwLock::new(HashSet::new())),
+        }
+    }
+
+    /// Require `secret` in the `connect` requests
+    ///
+    /// If set, requests of apps not connected with the secret are rejected.
+    pub fn secret<S>(self, secret: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            secret: Some(secret.into()),
+            ..self
+        }
+    }
+
+    /// Set [`AutoApprovePolicy`]
+    pub fn policy(self, policy: AutoApprovePolicy) -> Self {
+        Self { policy, ..self }
+    }
+
+    /// Set [`NostrConnectApproval`] callback, for the requests not auto-approved by the policy
+    pub fn approval<A>(self, approval: A) -> Self
+    where
+        A: NostrConnectApproval + 'static,
+    {
+        Self {
+            approval: Some(Arc::new(approval)),
+            ..self
+        }
+    }
+
+    /// Get [`RelayPool`]
+    pub fn pool(&self) -> RelayPool {
+        self.pool.clone()
+    }
+
+    /// Add relay where to listen for requests
+    pub async fn add_relay<U>(&self, url: U) -> Result<bool, Error>
+    where
+        U: TryIntoUrl,
+        crate::relay::pool::Error: From<<U as TryIntoUrl>::Err>,
+    {
+        Ok(self.pool.add_relay(url, RelayOptions::default()).await?)
+    }
+
+    /// Compose [`BunkerURI`] to share with the apps
+    ///
+    /// The URI lists the relays added with [`NostrConnectRemoteSigner::add_relay`]:
+    /// add them before sharing it, since a URI without relays is rejected by [`Nip46Signer::from_bunker_uri`](super::Nip46Signer::from_bunker_uri).
+    pub async fn bunker_uri(&self) -> BunkerURI {
+        let relays: Vec<Url> = self.pool.relays().await.into_keys().collect();
+        let uri = BunkerURI::new(self.keys.public_key(), relays);
+        match &self.secret {
+            Some(secret) => uri.secret(secret.clone()),
+            None => uri,
+        }
+    }
+
+    /// Get public keys of the connected apps
+    pub async fn connected_apps(&self) -> HashSet<XOnlyPublicKey> {
+        self.connected_apps.read().await.clone()
+    }
+
+    /// Connect to an app that shared a [`NostrConnectURI`]
+    ///
+    /// Send the `connect` request to the app, through the relay of the URI.
+    pub async fn connect_to_app(&self, uri: &NostrConnectURI) -> Result<(), Error> {
+        self.pool
+            .add_relay(uri.relay_url.clone(), RelayOptions::default())
+            .await?;
+        self.pool.connect(true).await;
+
+        let req = Request::Connect(self.keys.public_key());
+        let msg = Message::request(req);
+        let event: Event =
+            EventBuilder::nostr_connect(&self.keys, uri.public_key, msg)?.to_event(&self.keys)?;
+        self.pool
+            .send_msg_to(uri.relay_url.clone(), ClientMessage::new_event(event), None)
+            .await?;
+
+        let mut connected_apps = self.connected_apps.write().await;
+        connected_apps.insert(uri.public_key);
+
+        Ok(())
+    }
+
+    /// Connect to the relays and serve the requests until [`NostrConnectRemoteSigner::shutdown`] is called
+    pub async fn run(&self) -> Result<(), Error> {
+        let mut notifications = self.pool.notifications();
+
+        self.pool.connect(false).await;
+        let filter = Filter::new()
+            .pubkey(self.keys.public_key())
+            .kind(Kind::NostrConnect)
+            .since(Timestamp::now());
+        self.pool.subscribe(vec![filter], None).await;
+
+        loop {
+            match notifications.recv().await {
+                Ok(RelayPoolNotification::Event { event, .. }) => {
+                    if event.kind != Kind::NostrConnect {
+                        continue;
+                    }
+
+                    if let Err(e) = self.handle_request(event).await {
+                        tracing::error!("Impossible to handle NIP46 request: {e}");
+                    }
+                }
+                Ok(RelayPoolNotification::Shutdown) | Err(RecvError::Closed) => break,
+                Ok(..) => (),
+                Err(RecvError::Lagged(n)) => {
+                    tracing::warn!("NIP46 remote signer lagged: {n} notifications skipped");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn handle_request(&self, event: Event) -> Result<(), Error> {
+        let app_public_key: XOnlyPublicKey = event.pubkey;
+        let secret_key = self.keys.secret_key()?;
+        let msg: String = nip04::decrypt(&secret_key, &app_public_key, &event.content)?;
+        let msg: Message = Message::from_json(msg)?;
+
+        // Skip responses
+        let req: Request = match msg.to_request() {
+            Ok(req) => req,
+            Err(..) => return Ok(()),
+        };
+
+        tracing::debug!("Received {} request from {app_public_key}", req.method());
+
+        let response: Option<Message> = match self.check_request(&app_public_key, &req).await {
+            Ok(()) => match req {
+                Request::Connect(_) | Request::ConnectWithSecret { .. } => {
+                    let mut connected_apps = self.connected_apps.write().await;
+                    connected_apps.insert(app_public_key);
+                    Some(Message::response(msg.id(), Some(Response::Connect), None))
+                }
+                Request::Disconnect => {
+                    let mut connected_apps = self.connected_apps.write().await;
+                    connected_apps.remove(&app_public_key);
+                    None
+                }
+                _ => match msg.generate_response(&self.keys) {
+                    Ok(res) => res,
+                    Err(e) => Some(msg.generate_error_response(e.to_string())?),
+                },
+            },
+            Err(error) => Some(msg.generate_error_response(error)?),
+        };
+
+        if let Some(response) = response {
+            let event: Event = EventBuilder::nostr_connect(&self.keys, app_public_key, response)?
+                .to_event(&self.keys)?;
+            self.pool
+                .send_msg(ClientMessage::new_event(event), None)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Return the error to send to the app, if the request must not be served
+    async fn check_request(
+        &self,
+        app_public_key: &XOnlyPublicKey,
+        req: &Request,
+    ) -> Result<(), String> {
+        match req {
+            Request::Connect(_) => {
+                if self.secret.is_some() {
+                    return Err(String::from("invalid secret"));
+                }
+            }
+            Request::ConnectWithSecret { secret, .. } => {
+                if self.secret.as_ref().map_or(false, |s| s != secret) {
+                    return Err(String::from("invalid secret"));
+                }
+            }
+            Request::Disconnect => return Ok(()),
+            _ => {
+                if self.secret.is_some()
+                    && !self.connected_apps.read().await.contains(app_public_key)
+                {
+                    return Err(String::from("app not connected"));
+                }
+            }
+        }
+
+        let approved: bool = self.policy.is_approved(app_public_key, req)
+            || self
+                .approval
+                .as_ref()
+                .map_or(false, |approval| approval.approve(*app_public_key, req));
+        if approved {
+            Ok(())
+        } else {
+            Err(String::from("request rejected"))
+        }
+    }
+
+    /// Disconnect from all relays and stop [`NostrConnectRemoteSigner::run`]
+    pub async fn shutdown(self) -> Result<(), Error> {
+        Ok(self.pool.shutdown().await?)
+    }
+}
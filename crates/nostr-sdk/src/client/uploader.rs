@@ -0,0 +1,190 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! File uploader (NIP96)
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/96.md>
+
+use std::net::SocketAddr;
+
+use base64::engine::{general_purpose, Engine};
+use nostr::hashes::sha256::Hash as Sha256Hash;
+use nostr::nips::nip94::FileMetadata;
+use nostr::serde_json::{self, Value};
+use nostr::{Event, JsonUtil, Tag, Url};
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::Proxy;
+use thiserror::Error;
+
+/// Uploader error
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Reqwest error
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+    /// JSON error
+    #[error(transparent)]
+    JSON(#[from] serde_json::Error),
+    /// Url error
+    #[error(transparent)]
+    Url(#[from] nostr::url::ParseError),
+    /// Error returned by the server
+    #[error("upload failed: {0}")]
+    Upload(String),
+    /// Unexpected response of the server
+    #[error("invalid server response")]
+    InvalidResponse,
+}
+
+fn http_client(_proxy: Option<SocketAddr>) -> Result<reqwest::Client, Error> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let mut builder = reqwest::Client::builder();
+        if let Some(proxy) = _proxy {
+            let proxy = format!("socks5h://{proxy}");
+            builder = builder.proxy(Proxy::all(proxy)?);
+        }
+        Ok(builder.build()?)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    Ok(reqwest::Client::new())
+}
+
+/// Get the upload URL of the server, following the delegation to another server
+pub(crate) async fn fetch_api_url(
+    server_url: &Url,
+    proxy: Option<SocketAddr>,
+) -> Result<Url, Error> {
+    let client = http_client(proxy)?;
+    let mut server_url: Url = server_url.clone();
+
+    // At most one delegation
+    for _ in 0..2 {
+        let url: Url = server_url.join("/.well-known/nostr/nip96.json")?;
+        let res = client.get(url).send().await?;
+        let json: Value = serde_json::from_str(&res.text().await?)?;
+
+        match json.get("delegated_to_url").and_then(Value::as_str) {
+            Some(delegated) if !delegated.is_empty() => server_url = Url::parse(delegated)?,
+            _ => {
+                let api_url: &str = json
+                    .get("api_url")
+                    .and_then(Value::as_str)
+                    .ok_or(Error::InvalidResponse)?;
+                return Ok(Url::parse(api_url)?);
+            }
+        }
+    }
+
+    Err(Error::InvalidResponse)
+}
+
+/// Upload the file with a `multipart/form-data` request, authorized by the NIP98 `auth` event
+pub(crate) async fn upload(
+    api_url: Url,
+    auth: &Event,
+    bytes: Vec<u8>,
+    mime_type: &str,
+    proxy: Option<SocketAddr>,
+) -> Result<FileMetadata, Error> {
+    let boundary: String = format!("nostr-sdk-{}", auth.id);
+    let size: usize = bytes.len();
+    let body: Vec<u8> = multipart_body(&boundary, bytes, mime_type);
+    let authorization: String =
+        format!("Nostr {}", general_purpose::STANDARD.encode(auth.as_json()));
+
+    let client = http_client(proxy)?;
+    let res = client
+        .post(api_url)
+        .header("Authorization", authorization)
+        .header(
+            "Content-Type",
+            format!("multipart/form-data; boundary={boundary}"),
+        )
+        .body(body)
+        .send()
+        .await?;
+    let json: Value = serde_json::from_str(&res.text().await?)?;
+
+    if json.get("status").and_then(Value::as_str) != Some("success") {
+        let message: &str = json
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        return Err(Error::Upload(message.to_string()));
+    }
+
+    let tags: Vec<Tag> = json
+        .get("nip94_event")
+        .and_then(|e| e.get("tags"))
+        .and_then(Value::as_array)
+        .ok_or(Error::InvalidResponse)?
+        .iter()
+        .filter_map(|tag| serde_json::from_value::<Vec<String>>(tag.clone()).ok())
+        .filter_map(|tag| Tag::parse(tag).ok())
+        .collect();
+    file_metadata(&tags, mime_type, size)
+}
+
+fn multipart_body(boundary: &str, bytes: Vec<u8>, mime_type: &str) -> Vec<u8> {
+    let mut body: Vec<u8> = Vec::with_capacity(bytes.len() + 512);
+    let mut field = |name: &str, value: &str| {
+        body.extend(
+            format!(
+                "--{boundary}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n"
+            )
+            .as_bytes(),
+        );
+    };
+    field("size", &bytes.len().to_string());
+    field("content_type", mime_type);
+
+    body.extend(
+        format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"file\"\r\nContent-Type: {mime_type}\r\n\r\n"
+        )
+        .as_bytes(),
+    );
+    body.extend(bytes);
+    body.extend(format!("\r\n--{boundary}--\r\n").as_bytes());
+    body
+}
+
+/// Compose the [`FileMetadata`] from the tags of the `nip94_event` returned by the server
+fn file_metadata(tags: &[Tag], mime_type: &str, size: usize) -> Result<FileMetadata, Error> {
+    let url: Url = tags
+        .iter()
+        .find_map(|tag| match tag {
+            Tag::Url(url) => Some(url.clone()),
+            _ => None,
+        })
+        .ok_or(Error::InvalidResponse)?;
+    let hash: Sha256Hash = tags
+        .iter()
+        .find_map(|tag| match tag {
+            Tag::Sha256(hash) => Some(*hash),
+            _ => None,
+        })
+        .ok_or(Error::InvalidResponse)?;
+    let mime_type: String = tags
+        .iter()
+        .find_map(|tag| match tag {
+            Tag::MimeType(mime_type) => Some(mime_type.clone()),
+            _ => None,
+        })
+        .unwrap_or_else(|| mime_type.to_string());
+
+    let mut metadata = FileMetadata::new(url, mime_type, hash).size(size);
+    for tag in tags.iter() {
+        match tag {
+            Tag::Size(size) => metadata.size = Some(*size),
+            Tag::Dim(dim) => metadata = metadata.dimensions(*dim),
+            Tag::Magnet(magnet) => metadata = metadata.magnet(magnet.clone()),
+            Tag::Blurhash(blurhash) => metadata = metadata.blurhash(blurhash.clone()),
+            _ => (),
+        }
+    }
+    Ok(metadata)
+}
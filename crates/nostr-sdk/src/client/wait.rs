@@ -0,0 +1,117 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Wait for published events
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_utility::thread;
+use nostr::types::time::Instant;
+use nostr::url::Url;
+use nostr::{EventId, Filter};
+use nostr_database::DynNostrDatabase;
+
+use crate::relay::{FilterOptions, Relay, RelaySendOptions};
+
+const DEFAULT_WAIT_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_WAIT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Options for [`Client::send_event_and_wait`](super::Client::send_event_and_wait)
+#[derive(Debug, Clone, Copy)]
+pub struct SendAndWaitOptions {
+    /// Send options
+    pub send: RelaySendOptions,
+    /// Wait for the event to be returned by the relays that accepted it (default: true)
+    pub wait_for_event: bool,
+    /// Max time to wait for the event (default: 10 secs)
+    pub timeout: Duration,
+    /// Interval between the queries for the event (default: 1 sec)
+    pub interval: Duration,
+}
+
+impl Default for SendAndWaitOptions {
+    fn default() -> Self {
+        Self {
+            send: RelaySendOptions::default(),
+            wait_for_event: true,
+            timeout: DEFAULT_WAIT_TIMEOUT,
+            interval: DEFAULT_WAIT_INTERVAL,
+        }
+    }
+}
+
+impl SendAndWaitOptions {
+    /// New default [`SendAndWaitOptions`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send options
+    pub fn send(self, opts: RelaySendOptions) -> Self {
+        Self { send: opts, ..self }
+    }
+
+    /// Wait for the event to be returned by the relays that accepted it (default: true)
+    ///
+    /// The number of relays that must return it is [`RelaySendOptions::min_success`].
+    pub fn wait_for_event(self, value: bool) -> Self {
+        Self {
+            wait_for_event: value,
+            ..self
+        }
+    }
+
+    /// Max time to wait for the event (default: 10 secs)
+    pub fn timeout(self, value: Duration) -> Self {
+        Self {
+            timeout: value,
+            ..self
+        }
+    }
+
+    /// Interval between the queries for the event (default: 1 sec)
+    pub fn interval(self, value: Duration) -> Self {
+        Self {
+            interval: value,
+            ..self
+        }
+    }
+}
+
+/// Query `relay` for the event until it returns it or the timeout expires
+///
+/// The event is already stored into the local database, so it's considered returned
+/// when the pool marks it as seen on the relay.
+pub(crate) async fn wait_for_event(
+    relay: Relay,
+    database: Arc<DynNostrDatabase>,
+    event_id: EventId,
+    opts: SendAndWaitOptions,
+) -> bool {
+    let url: Url = relay.url();
+    let deadline: Instant = Instant::now() + opts.timeout;
+    loop {
+        let filter: Filter = Filter::new().id(event_id);
+        if let Err(e) = relay
+            .get_events_of(vec![filter], opts.interval, FilterOptions::ExitOnEOSE)
+            .await
+        {
+            tracing::warn!("Impossible to query event {event_id} from {url}: {e}");
+        }
+
+        if let Ok(Some(relays)) = database.event_seen_on_relays(event_id).await {
+            if relays.contains(&url) {
+                return true;
+            }
+        }
+
+        if Instant::now() + opts.interval >= deadline {
+            tracing::warn!("Event {event_id} not returned by {url}");
+            return false;
+        }
+
+        thread::sleep(opts.interval).await;
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Zapper (NIP57)
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/57.md>
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+use nostr::key::XOnlyPublicKey;
+use nostr::nips::nip57::{self, ZapReceipt};
+use nostr::serde_json::{self, Value};
+use nostr::{Event, EventId, JsonUtil, Url};
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::Proxy;
+use thiserror::Error;
+
+/// Zapper error
+#[derive(Debug, Error)]
+pub enum Error {
+    /// NIP57 error
+    #[error(transparent)]
+    NIP57(#[from] nip57::Error),
+    /// Reqwest error
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+    /// JSON error
+    #[error(transparent)]
+    JSON(#[from] serde_json::Error),
+    /// Event to zap not found
+    #[error("event not found")]
+    EventNotFound,
+    /// Metadata of the recipient not found
+    #[error("metadata not found")]
+    MetadataNotFound,
+    /// The recipient has no `lud06` or `lud16` in the metadata
+    #[error("LNURL not found in the metadata")]
+    LnurlNotFound,
+    /// The LNURL server doesn't support zaps
+    #[error("LNURL server doesn't support zaps")]
+    ZapsNotSupported,
+    /// Amount not accepted by the LNURL server
+    #[error("amount out of range: min={min} msat, max={max} msat")]
+    AmountOutOfRange {
+        /// Min amount (msat)
+        min: u64,
+        /// Max amount (msat)
+        max: u64,
+    },
+    /// Error returned by the LNURL server
+    #[error("LNURL server error: {0}")]
+    Lnurl(String),
+    /// Unexpected response of the LNURL server
+    #[error("invalid LNURL server response")]
+    InvalidResponse,
+}
+
+/// Zap target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ZapEntity {
+    /// Zap an event (the recipient is the author)
+    Event(EventId),
+    /// Zap a profile
+    PublicKey(XOnlyPublicKey),
+}
+
+impl From<EventId> for ZapEntity {
+    fn from(id: EventId) -> Self {
+        Self::Event(id)
+    }
+}
+
+impl From<XOnlyPublicKey> for ZapEntity {
+    fn from(public_key: XOnlyPublicKey) -> Self {
+        Self::PublicKey(public_key)
+    }
+}
+
+/// Verified zaps of a profile or an event
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Zaps {
+    /// Total amount (msat)
+    pub total_msat: u64,
+    /// Amount (msat) per zapper
+    pub zappers: HashMap<XOnlyPublicKey, u64>,
+    /// Zap receipts
+    pub receipts: Vec<ZapReceipt>,
+}
+
+impl Zaps {
+    /// Aggregate the receipts of the zaps to `recipient`
+    pub(crate) fn new<I>(recipient: XOnlyPublicKey, receipts: I) -> Self
+    where
+        I: IntoIterator<Item = ZapReceipt>,
+    {
+        let mut zaps = Self::default();
+        for receipt in receipts
+            .into_iter()
+            .filter(|receipt| receipt.recipient == recipient)
+        {
+            zaps.total_msat += receipt.amount_msat;
+            *zaps.zappers.entry(receipt.zapper).or_default() += receipt.amount_msat;
+            zaps.receipts.push(receipt);
+        }
+        zaps
+    }
+
+    /// Total amount (sats)
+    pub fn total_sats(&self) -> u64 {
+        self.total_msat / 1000
+    }
+}
+
+/// LNURL pay endpoint details
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LnurlPayDetails {
+    pub callback: Url,
+    pub min_sendable: u64,
+    pub max_sendable: u64,
+    pub allows_nostr: bool,
+    pub nostr_pubkey: Option<XOnlyPublicKey>,
+}
+
+fn http_client(_proxy: Option<SocketAddr>) -> Result<reqwest::Client, Error> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let mut builder = reqwest::Client::builder();
+        if let Some(proxy) = _proxy {
+            let proxy = format!("socks5h://{proxy}");
+            builder = builder.proxy(Proxy::all(proxy)?);
+        }
+        Ok(builder.build()?)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    Ok(reqwest::Client::new())
+}
+
+async fn get_json(url: Url, proxy: Option<SocketAddr>) -> Result<Value, Error> {
+    let client = http_client(proxy)?;
+    let res = client.get(url).send().await?;
+    let json: Value = serde_json::from_str(&res.text().await?)?;
+
+    // LUD06 error response
+    if json.get("status").and_then(Value::as_str) == Some("ERROR") {
+        let reason: &str = json
+            .get("reason")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        return Err(Error::Lnurl(reason.to_string()));
+    }
+
+    Ok(json)
+}
+
+/// Get the details of the LNURL pay endpoint
+pub(crate) async fn fetch_pay_details(
+    url: Url,
+    proxy: Option<SocketAddr>,
+) -> Result<LnurlPayDetails, Error> {
+    let json: Value = get_json(url, proxy).await?;
+    let callback: &str = json
+        .get("callback")
+        .and_then(Value::as_str)
+        .ok_or(Error::InvalidResponse)?;
+    Ok(LnurlPayDetails {
+        callback: Url::parse(callback).map_err(|_| Error::InvalidResponse)?,
+        min_sendable: json
+            .get("minSendable")
+            .and_then(Value::as_u64)
+            .ok_or(Error::InvalidResponse)?,
+        max_sendable: json
+            .get("maxSendable")
+            .and_then(Value::as_u64)
+            .ok_or(Error::InvalidResponse)?,
+        allows_nostr: json
+            .get("allowsNostr")
+            .and_then(Value::as_bool)
+            .unwrap_or_default(),
+        nostr_pubkey: json
+            .get("nostrPubkey")
+            .and_then(Value::as_str)
+            .and_then(|pk| pk.parse().ok()),
+    })
+}
+
+/// Request the invoice for the signed zap request to the LNURL callback
+pub(crate) async fn fetch_invoice(
+    details: &LnurlPayDetails,
+    amount_msat: u64,
+    zap_request: &Event,
+    lnurl: &str,
+    proxy: Option<SocketAddr>,
+) -> Result<String, Error> {
+    if !details.allows_nostr || details.nostr_pubkey.is_none() {
+        return Err(Error::ZapsNotSupported);
+    }
+
+    if amount_msat < details.min_sendable || amount_msat > details.max_sendable {
+        return Err(Error::AmountOutOfRange {
+            min: details.min_sendable,
+            max: details.max_sendable,
+        });
+    }
+
+    let mut url: Url = details.callback.clone();
+    url.query_pairs_mut()
+        .append_pair("amount", &amount_msat.to_string())
+        .append_pair("nostr", &zap_request.as_json())
+        .append_pair("lnurl", lnurl);
+
+    let json: Value = get_json(url, proxy).await?;
+    json.get("pr")
+        .and_then(Value::as_str)
+        .map(|pr| pr.to_string())
+        .ok_or(Error::InvalidResponse)
+}
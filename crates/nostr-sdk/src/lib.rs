@@ -40,11 +40,13 @@ pub mod util;
 
 #[cfg(feature = "blocking")]
 pub use self::client::blocking;
-pub use self::client::{Client, ClientBuilder, ClientSigner, Options};
+pub use self::client::{Client, ClientBuilder, ClientSigner, NostrSigner, Options};
 pub use self::relay::{
-    ActiveSubscription, FilterOptions, InternalSubscriptionId, NegentropyOptions, Relay,
-    RelayConnectionStats, RelayOptions, RelayPoolNotification, RelayPoolOptions, RelaySendOptions,
-    RelayStatus,
+    ActiveSubscription, ConnectReport, CountReport, FilterOptions, InternalSubscriptionId,
+    MessageRejectedReason, Moderation, ModerationFilters, ModerationReason, NegentropyOptions,
+    NotificationHub, Quarantine, QuarantineReason, QuarantinedEvent, QueuedMessage, Relay,
+    RelayConnectionStats, RelayOptions, RelayPoolNotification, RelayPoolOptions, RelayPoolStats,
+    RelaySendOptions, RelayStatus, SeenTracker, ShutdownReport,
 };
 
 #[cfg(feature = "blocking")]
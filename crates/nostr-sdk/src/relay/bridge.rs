@@ -0,0 +1,178 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Bridge
+
+use std::sync::Arc;
+
+use nostr::{ClientMessage, Event, Filter};
+
+use super::pool::{Error, RelayPool};
+use super::shared::DEFAULT_MAX_SEEN;
+use super::{RelayOptions, RelayPoolNotification, RelayPoolOptions, SeenTracker};
+use crate::util::TryIntoUrl;
+
+type FilterFn = Arc<dyn Fn(&Event) -> bool + Send + Sync>;
+type TransformFn = Arc<dyn Fn(Event) -> Option<Event> + Send + Sync>;
+
+/// Republish the events received from a set of relays (source) to another one (target)
+///
+/// # Example
+/// ```rust,no_run
+/// use nostr_sdk::prelude::*;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let bridge = Bridge::new(vec![Filter::new().kind(Kind::TextNote)])
+///     .filter(|event| !event.content.is_empty());
+/// bridge.add_source_relay("wss://relay.damus.io").await.unwrap();
+/// bridge.add_target_relay("wss://nos.lol").await.unwrap();
+/// bridge.run().await.unwrap();
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Bridge {
+    source: RelayPool,
+    target: RelayPool,
+    filters: Vec<Filter>,
+    filter: Option<FilterFn>,
+    transform: Option<TransformFn>,
+    max_seen: usize,
+}
+
+impl Bridge {
+    /// New bridge for the events matching `filters`
+    pub fn new(filters: Vec<Filter>) -> Self {
+        Self {
+            source: RelayPool::new(RelayPoolOptions::default()),
+            target: RelayPool::new(RelayPoolOptions::default()),
+            filters,
+            filter: None,
+            transform: None,
+            max_seen: DEFAULT_MAX_SEEN,
+        }
+    }
+
+    /// Republish only the events for which `filter` return `true`
+    pub fn filter<F>(self, filter: F) -> Self
+    where
+        F: Fn(&Event) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            filter: Some(Arc::new(filter)),
+            ..self
+        }
+    }
+
+    /// Transform the events before republishing them
+    ///
+    /// Return `None` to skip the event. A modified event must be signed again.
+    pub fn transform<F>(self, transform: F) -> Self
+    where
+        F: Fn(Event) -> Option<Event> + Send + Sync + 'static,
+    {
+        Self {
+            transform: Some(Arc::new(transform)),
+            ..self
+        }
+    }
+
+    /// Max number of republished event IDs to remember for loop prevention (default: 100_000)
+    pub fn max_seen(self, max: usize) -> Self {
+        Self {
+            max_seen: max,
+            ..self
+        }
+    }
+
+    /// Get source [`RelayPool`]
+    pub fn source(&self) -> RelayPool {
+        self.source.clone()
+    }
+
+    /// Get target [`RelayPool`]
+    pub fn target(&self) -> RelayPool {
+        self.target.clone()
+    }
+
+    /// Add source relay
+    pub async fn add_source_relay<U>(&self, url: U) -> Result<bool, Error>
+    where
+        U: TryIntoUrl,
+        Error: From<<U as TryIntoUrl>::Err>,
+    {
+        self.source
+            .add_relay(url, RelayOptions::new().write(false))
+            .await
+    }
+
+    /// Add target relay
+    pub async fn add_target_relay<U>(&self, url: U) -> Result<bool, Error>
+    where
+        U: TryIntoUrl,
+        Error: From<<U as TryIntoUrl>::Err>,
+    {
+        self.target
+            .add_relay(url, RelayOptions::new().read(false))
+            .await
+    }
+
+    /// Connect to the relays and republish the events until [`Bridge::shutdown`] is called
+    pub async fn run(&self) -> Result<(), Error> {
+        let mut notifications = self.source.notifications();
+
+        self.target.connect(false).await;
+        self.source.connect(false).await;
+        self.source.subscribe(self.filters.clone(), None).await;
+
+        let seen = SeenTracker::new(self.max_seen);
+
+        while let Ok(notification) = notifications.recv().await {
+            match notification {
+                RelayPoolNotification::Event { event, .. } => {
+                    // Skip events republished by this bridge (i.e. target relay also used as source)
+                    if seen.contains(&event.id) {
+                        continue;
+                    }
+
+                    if let Some(filter) = &self.filter {
+                        if !filter(&event) {
+                            continue;
+                        }
+                    }
+
+                    let event: Event = match &self.transform {
+                        Some(transform) => match transform(event) {
+                            Some(event) => event,
+                            None => continue,
+                        },
+                        None => event,
+                    };
+
+                    if !seen.insert(event.id) {
+                        continue;
+                    }
+
+                    if let Err(e) = self
+                        .target
+                        .send_msg(ClientMessage::new_event(event), None)
+                        .await
+                    {
+                        tracing::error!("Impossible to republish event: {e}");
+                    }
+                }
+                RelayPoolNotification::Shutdown => break,
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Disconnect from all relays and stop [`Bridge::run`]
+    pub async fn shutdown(self) -> Result<(), Error> {
+        self.target.shutdown().await?;
+        self.source.shutdown().await
+    }
+}
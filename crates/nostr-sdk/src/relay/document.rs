@@ -0,0 +1,187 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Relay information document monitor
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_utility::thread;
+use nostr::nips::nip11::{Limitation, RelayInformationDocument};
+use nostr::Url;
+use tokio::sync::{broadcast, RwLock};
+
+use super::{Relay, RelayPoolNotification};
+
+/// Relay policy change detected in the information document (NIP11)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RelayPolicyChange {
+    /// Authentication (NIP42) now required (`true`) or no longer required (`false`)
+    AuthRequired(bool),
+    /// Payment now required (`true`) or no longer required (`false`)
+    PaymentRequired(bool),
+    /// A limit has been lowered (or set for the first time)
+    LimitLowered {
+        /// Name of the limit (i.e. `max_message_length`)
+        name: &'static str,
+        /// Previous value
+        old: Option<i32>,
+        /// New value
+        new: i32,
+    },
+}
+
+impl fmt::Display for RelayPolicyChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AuthRequired(true) => write!(f, "auth required"),
+            Self::AuthRequired(false) => write!(f, "auth no longer required"),
+            Self::PaymentRequired(true) => write!(f, "payment required"),
+            Self::PaymentRequired(false) => write!(f, "payment no longer required"),
+            Self::LimitLowered { name, old, new } => match old {
+                Some(old) => write!(f, "{name} lowered from {old} to {new}"),
+                None => write!(f, "{name} set to {new}"),
+            },
+        }
+    }
+}
+
+/// Compare two versions of a relay information document
+pub fn policy_changes(
+    old: &RelayInformationDocument,
+    new: &RelayInformationDocument,
+) -> Vec<RelayPolicyChange> {
+    let default = Limitation::default();
+    let old: &Limitation = old.limitation.as_ref().unwrap_or(&default);
+    let new: &Limitation = new.limitation.as_ref().unwrap_or(&default);
+
+    let mut changes: Vec<RelayPolicyChange> = Vec::new();
+
+    let auth_required: bool = new.auth_required.unwrap_or_default();
+    if old.auth_required.unwrap_or_default() != auth_required {
+        changes.push(RelayPolicyChange::AuthRequired(auth_required));
+    }
+
+    let payment_required: bool = new.payment_required.unwrap_or_default();
+    if old.payment_required.unwrap_or_default() != payment_required {
+        changes.push(RelayPolicyChange::PaymentRequired(payment_required));
+    }
+
+    let limits = [
+        (
+            "max_message_length",
+            old.max_message_length,
+            new.max_message_length,
+        ),
+        (
+            "max_subscriptions",
+            old.max_subscriptions,
+            new.max_subscriptions,
+        ),
+        ("max_filters", old.max_filters, new.max_filters),
+        ("max_limit", old.max_limit, new.max_limit),
+        (
+            "max_subid_length",
+            old.max_subid_length,
+            new.max_subid_length,
+        ),
+        ("max_event_tags", old.max_event_tags, new.max_event_tags),
+        (
+            "max_content_length",
+            old.max_content_length,
+            new.max_content_length,
+        ),
+    ];
+    for (name, old, new) in limits.into_iter() {
+        if let Some(new) = new {
+            if old.map_or(true, |old| new < old) {
+                changes.push(RelayPolicyChange::LimitLowered { name, old, new });
+            }
+        }
+    }
+
+    changes
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct RelayDocumentMonitor {
+    relays: Arc<RwLock<HashMap<Url, Relay>>>,
+    notification_sender: broadcast::Sender<RelayPoolNotification>,
+    interval: Duration,
+}
+
+impl RelayDocumentMonitor {
+    pub fn new(
+        relays: Arc<RwLock<HashMap<Url, Relay>>>,
+        notification_sender: broadcast::Sender<RelayPoolNotification>,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            relays,
+            notification_sender,
+            interval,
+        }
+    }
+
+    /// Periodically refresh the documents until `is_running` return `false`
+    pub fn run<F>(self, is_running: F)
+    where
+        F: Fn() -> bool + Send + 'static,
+    {
+        thread::spawn(async move {
+            tracing::debug!("Relay Document Monitor Thread Started");
+
+            // Last fetched documents
+            let mut documents: HashMap<Url, RelayInformationDocument> = HashMap::new();
+
+            loop {
+                thread::sleep(self.interval).await;
+
+                if !is_running() {
+                    break;
+                }
+
+                let relays: HashMap<Url, Relay> = self.relays.read().await.clone();
+                documents.retain(|url, _| relays.contains_key(url));
+
+                for (url, relay) in relays.into_iter() {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    let proxy = relay.proxy();
+                    #[cfg(target_arch = "wasm32")]
+                    let proxy = None;
+
+                    let document = match RelayInformationDocument::get(url.clone(), proxy).await {
+                        Ok(document) => document,
+                        Err(e) => {
+                            tracing::debug!(
+                                "Impossible to refresh information document of {url}: {e}"
+                            );
+                            continue;
+                        }
+                    };
+
+                    if let Some(old) = documents.get(&url) {
+                        let changes: Vec<RelayPolicyChange> = policy_changes(old, &document);
+                        if !changes.is_empty() {
+                            tracing::info!("Policy of {url} changed: {changes:?}");
+                            let _ = self.notification_sender.send(
+                                RelayPoolNotification::RelayPolicyChanged {
+                                    relay_url: url.clone(),
+                                    changes,
+                                },
+                            );
+                        }
+                    }
+
+                    relay.set_document(document.clone()).await;
+                    documents.insert(url, document);
+                }
+            }
+
+            tracing::debug!("Exited from Relay Document Monitor Thread");
+        });
+    }
+}
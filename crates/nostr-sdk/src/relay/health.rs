@@ -0,0 +1,186 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Relay health monitor
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_utility::futures_util::stream::AbortHandle;
+use async_utility::thread;
+use nostr::{Timestamp, Url};
+use tokio::sync::{broadcast, RwLock};
+
+use super::options::RelayHealthMonitorOptions;
+use super::{Relay, RelayPoolNotification};
+
+/// Why a relay has been demoted by the health monitor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelayDemotionReason {
+    /// Too many errors between two checks
+    TooManyErrors(usize),
+    /// Too many disconnections between two checks
+    TooManyDisconnections(usize),
+    /// Average time between `REQ` and `EOSE` too high
+    SlowEose(Duration),
+}
+
+impl fmt::Display for RelayDemotionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyErrors(errors) => write!(f, "too many errors ({errors})"),
+            Self::TooManyDisconnections(disconnections) => {
+                write!(f, "too many disconnections ({disconnections})")
+            }
+            Self::SlowEose(latency) => write!(f, "slow EOSE ({} ms)", latency.as_millis()),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct RelayHealth {
+    errors: usize,
+    disconnections: usize,
+    unhealthy_checks: usize,
+    demoted_until: Option<Timestamp>,
+}
+
+impl RelayHealth {
+    /// Take the current counters as baseline, so that only the new errors and disconnections are checked
+    fn baseline(relay: &Relay) -> Self {
+        let stats = relay.stats();
+        Self {
+            errors: stats.errors(),
+            disconnections: stats.disconnections(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct RelayHealthMonitor {
+    relays: Arc<RwLock<HashMap<Url, Relay>>>,
+    notification_sender: broadcast::Sender<RelayPoolNotification>,
+    opts: RelayHealthMonitorOptions,
+}
+
+impl RelayHealthMonitor {
+    pub fn new(
+        relays: Arc<RwLock<HashMap<Url, Relay>>>,
+        notification_sender: broadcast::Sender<RelayPoolNotification>,
+        opts: RelayHealthMonitorOptions,
+    ) -> Self {
+        Self {
+            relays,
+            notification_sender,
+            opts,
+        }
+    }
+
+    /// Periodically check relays until `is_running` return `false`
+    ///
+    /// Return the handle to abort the monitor.
+    pub fn run<F>(self, is_running: F) -> AbortHandle
+    where
+        F: Fn() -> bool + Send + 'static,
+    {
+        thread::abortable(async move {
+            tracing::debug!("Relay Health Monitor Thread Started");
+
+            let mut health: HashMap<Url, RelayHealth> = HashMap::new();
+            for (url, relay) in self.relays.read().await.iter() {
+                health.insert(url.clone(), RelayHealth::baseline(relay));
+            }
+
+            loop {
+                thread::sleep(self.opts.interval).await;
+
+                if !is_running() {
+                    break;
+                }
+
+                let relays: HashMap<Url, Relay> = self.relays.read().await.clone();
+                health.retain(|url, _| relays.contains_key(url));
+
+                for (url, relay) in relays.into_iter() {
+                    let relay_health: &mut RelayHealth = health
+                        .entry(url)
+                        .or_insert_with(|| RelayHealth::baseline(&relay));
+                    self.check(&relay, relay_health).await;
+                }
+            }
+
+            tracing::debug!("Exited from Relay Health Monitor Thread");
+        })
+    }
+
+    async fn check(&self, relay: &Relay, health: &mut RelayHealth) {
+        let stats = relay.stats();
+        let errors: usize = stats.errors();
+        let disconnections: usize = stats.disconnections();
+        let new_errors: usize = errors.saturating_sub(health.errors);
+        let new_disconnections: usize = disconnections.saturating_sub(health.disconnections);
+        health.errors = errors;
+        health.disconnections = disconnections;
+
+        // Re-probe demoted relay
+        if let Some(until) = health.demoted_until {
+            if Timestamp::now() >= until {
+                tracing::info!("Re-probing demoted relay {}", relay.url());
+                health.demoted_until = None;
+                health.unhealthy_checks = 0;
+                relay.connect(false).await;
+            }
+            return;
+        }
+
+        let reason: Option<RelayDemotionReason> = if new_errors > self.opts.max_errors {
+            Some(RelayDemotionReason::TooManyErrors(new_errors))
+        } else if new_disconnections > self.opts.max_disconnections {
+            Some(RelayDemotionReason::TooManyDisconnections(
+                new_disconnections,
+            ))
+        } else {
+            self.check_eose_latency(relay).await
+        };
+
+        match reason {
+            Some(reason) => {
+                health.unhealthy_checks += 1;
+                tracing::debug!(
+                    "{} unhealthy: {reason} [{}/{}]",
+                    relay.url(),
+                    health.unhealthy_checks,
+                    self.opts.unhealthy_checks
+                );
+
+                if health.unhealthy_checks >= self.opts.unhealthy_checks {
+                    tracing::warn!("Demoting {}: {reason}", relay.url());
+                    if let Err(e) = relay.stop().await {
+                        tracing::error!("Impossible to stop {}: {e}", relay.url());
+                    }
+                    health.demoted_until = Some(Timestamp::now() + self.opts.reprobe_after);
+                    let _ = self
+                        .notification_sender
+                        .send(RelayPoolNotification::RelayDemoted {
+                            relay_url: relay.url(),
+                            reason,
+                        });
+                }
+            }
+            None => health.unhealthy_checks = 0,
+        }
+    }
+
+    async fn check_eose_latency(&self, relay: &Relay) -> Option<RelayDemotionReason> {
+        let latency: Duration = relay.stats().eose_latency().await?;
+        if latency > self.opts.max_eose_latency {
+            Some(RelayDemotionReason::SlowEose(latency))
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Frame interceptor
+
+use std::fmt;
+
+use nostr::{RawRelayMessage, Url};
+
+/// What to do with a frame received from a relay
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrameAction {
+    /// Process the frame as usual
+    Forward,
+    /// Process this message instead of the received frame
+    Replace(RawRelayMessage),
+    /// Drop the frame (already handled by the interceptor)
+    Consume,
+}
+
+/// Intercept the frames received from a relay, before they are parsed and processed by the pool
+///
+/// Allow to piggyback custom protocols (i.e. experimental relay extensions) on the relay connection.
+/// Any `Fn(&Url, &[u8]) -> FrameAction` closure is an interceptor.
+///
+/// The interceptor is called from the relay message thread: keep it fast and non-blocking.
+///
+/// # Example
+/// ```rust,no_run
+/// use nostr_sdk::prelude::*;
+///
+/// let opts = RelayOptions::new().frame_interceptor(|url: &Url, frame: &[u8]| {
+///     if frame.starts_with(br#"["X-STATS""#) {
+///         println!("Stats from {url}: {}", String::from_utf8_lossy(frame));
+///         return FrameAction::Consume;
+///     }
+///     FrameAction::Forward
+/// });
+/// ```
+pub trait FrameInterceptor: Send + Sync {
+    /// Inspect the raw `frame` received from the relay at `relay_url`
+    fn intercept(&self, relay_url: &Url, frame: &[u8]) -> FrameAction;
+}
+
+impl<F> FrameInterceptor for F
+where
+    F: Fn(&Url, &[u8]) -> FrameAction + Send + Sync,
+{
+    fn intercept(&self, relay_url: &Url, frame: &[u8]) -> FrameAction {
+        self(relay_url, frame)
+    }
+}
+
+impl fmt::Debug for dyn FrameInterceptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameInterceptor").finish()
+    }
+}
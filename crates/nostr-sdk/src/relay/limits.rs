@@ -4,6 +4,8 @@
 
 //! Limits
 
+use nostr::Kind;
+
 /// Limits
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Limits {
@@ -17,7 +19,7 @@ impl Default for Limits {
     fn default() -> Self {
         Self {
             messages: MessagesLimits { max_size: 128_000 },
-            events: EventsLimits { max_size: 65_536 },
+            events: EventsLimits::default(),
         }
     }
 }
@@ -25,17 +27,60 @@ impl Default for Limits {
 /// Messages limits
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MessagesLimits {
-    /// Maximum size of normalised JSON, in bytes
+    /// Maximum size of the raw message, in bytes
     pub max_size: u32,
 }
 
 /// Events limits
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Sizes are measured on the raw `EVENT` message received from the relay.
+/// By default no per-event limit is applied.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct EventsLimits {
-    /// Maximum size of normalised JSON, in bytes
-    pub max_size: u32,
+    /// Maximum size, in bytes, of events of any kind except [`Kind::ContactList`]
+    pub max_size: Option<u32>,
+    /// Maximum size of [`Kind::ContactList`] events, in bytes
+    ///
+    /// Contact lists are usually far bigger than other events, so they are
+    /// not checked against [`EventsLimits::max_size`].
+    pub max_contact_list_size: Option<u32>,
     /* /// Maximum number of tags allowed
     pub max_num_tags: u16,
     /// Maximum size for tag values, in bytes
     pub max_tag_val_size: u16, */
 }
+
+impl EventsLimits {
+    /// Get the maximum size allowed for an event of the given [`Kind`]
+    pub fn get_max_size(&self, kind: Kind) -> Option<u32> {
+        match kind {
+            Kind::ContactList => self.max_contact_list_size,
+            _ => self.max_size,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_max_size() {
+        let limits = EventsLimits::default();
+        assert_eq!(limits.get_max_size(Kind::TextNote), None);
+        assert_eq!(limits.get_max_size(Kind::ContactList), None);
+
+        let limits = EventsLimits {
+            max_size: Some(65_536),
+            max_contact_list_size: None,
+        };
+        assert_eq!(limits.get_max_size(Kind::TextNote), Some(65_536));
+        assert_eq!(limits.get_max_size(Kind::ContactList), None);
+
+        let limits = EventsLimits {
+            max_size: Some(65_536),
+            max_contact_list_size: Some(1_000_000),
+        };
+        assert_eq!(limits.get_max_size(Kind::ContactList), Some(1_000_000));
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Event middleware
+
+use std::sync::Arc;
+
+use nostr::{Event, UnsignedEvent, Url};
+use nostr_database::{async_trait, AsyncTraitDeps};
+use tokio::sync::RwLock;
+
+/// Error returned by a [`Middleware`] to abort the sending of an event
+pub type MiddlewareError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Hooks on the events sent and received by the client
+///
+/// Allow to apply cross-cutting policies (i.e. add a `client` tag to all the events, drop the events of blocked authors).
+/// Both methods have a default implementation that let the event pass unchanged.
+///
+/// # Example
+/// ```rust,no_run
+/// use nostr_sdk::prelude::*;
+///
+/// #[derive(Debug)]
+/// struct ClientTag;
+///
+/// #[async_trait]
+/// impl Middleware for ClientTag {
+///     async fn before_send(
+///         &self,
+///         mut unsigned: UnsignedEvent,
+///     ) -> Result<UnsignedEvent, MiddlewareError> {
+///         unsigned.tags.push(Tag::Generic(
+///             TagKind::Custom(String::from("client")),
+///             vec![String::from("my-app")],
+///         ));
+///         Ok(unsigned)
+///     }
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// # let client = Client::new(&Keys::generate());
+/// client.add_middleware(ClientTag).await;
+/// # }
+/// ```
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait Middleware: AsyncTraitDeps {
+    /// Edit an event built by the client, before it's signed
+    ///
+    /// Called only for the events built from an `EventBuilder` (i.e. `Client::publish_text_note`):
+    /// the already signed events can't be edited.
+    /// The event ID is computed again (and the POW mined, if a difficulty is set) after all the middlewares.
+    ///
+    /// Return an error to abort the sending.
+    async fn before_send(&self, unsigned: UnsignedEvent) -> Result<UnsignedEvent, MiddlewareError> {
+        Ok(unsigned)
+    }
+
+    /// Inspect an event received from a relay, after its verification
+    /// and before it's saved into the database and notified
+    ///
+    /// Return `false` to drop the event.
+    async fn after_receive(&self, _relay_url: &Url, _event: &Event) -> bool {
+        true
+    }
+}
+
+/// Middlewares, called in registration order
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Middlewares {
+    list: Arc<RwLock<Vec<Arc<dyn Middleware>>>>,
+}
+
+impl Middlewares {
+    pub async fn push(&self, middleware: Arc<dyn Middleware>) {
+        let mut list = self.list.write().await;
+        list.push(middleware);
+    }
+
+    pub async fn clear(&self) {
+        let mut list = self.list.write().await;
+        list.clear();
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.list.read().await.is_empty()
+    }
+
+    /// Clone the list, to not hold the lock while the middlewares run
+    async fn snapshot(&self) -> Vec<Arc<dyn Middleware>> {
+        self.list.read().await.clone()
+    }
+
+    pub async fn before_send(
+        &self,
+        mut unsigned: UnsignedEvent,
+    ) -> Result<UnsignedEvent, MiddlewareError> {
+        for middleware in self.snapshot().await.into_iter() {
+            unsigned = middleware.before_send(unsigned).await?;
+        }
+        Ok(unsigned)
+    }
+
+    pub async fn after_receive(&self, relay_url: &Url, event: &Event) -> bool {
+        for middleware in self.snapshot().await.into_iter() {
+            if !middleware.after_receive(relay_url, event).await {
+                return false;
+            }
+        }
+        true
+    }
+}
@@ -13,7 +13,6 @@ use std::sync::Arc;
 use std::time::Duration;
 use std::{cmp, fmt};
 
-#[cfg(not(target_arch = "wasm32"))]
 use async_utility::futures_util::stream::AbortHandle;
 use async_utility::{futures_util, thread, time};
 use nostr::message::relay::NegentropyErrorCode;
@@ -22,37 +21,94 @@ use nostr::negentropy::{self, Bytes, Negentropy};
 #[cfg(feature = "nip11")]
 use nostr::nips::nip11::RelayInformationDocument;
 use nostr::secp256k1::rand::{self, Rng};
+use nostr::types::time::Instant;
 use nostr::{
-    ClientMessage, Event, EventId, Filter, JsonUtil, Keys, RawRelayMessage, RelayMessage,
+    ClientMessage, Event, EventId, Filter, JsonUtil, Keys, Kind, RawRelayMessage, RelayMessage,
     SubscriptionId, Timestamp, Url,
 };
 use nostr_database::{DatabaseError, DynNostrDatabase, Order};
 use nostr_sdk_net::futures_util::{Future, SinkExt, StreamExt};
+#[cfg(not(target_arch = "wasm32"))]
+use nostr_sdk_net::native::ConnectionCache;
+#[cfg(not(target_arch = "wasm32"))]
+pub use nostr_sdk_net::native::HeaderMap;
 use nostr_sdk_net::{self as net, WsMessage};
 use thiserror::Error;
+use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::mpsc::{self, Receiver, Sender};
-use tokio::sync::{broadcast, oneshot, Mutex, RwLock};
+use tokio::sync::{
+    broadcast, oneshot, AcquireError, Mutex, OwnedSemaphorePermit, RwLock, Semaphore,
+};
 
+pub mod bridge;
+#[cfg(feature = "nip11")]
+mod document;
+mod health;
+mod interceptor;
 pub mod limits;
+mod middleware;
+mod moderation;
 mod options;
 pub mod pool;
+mod quarantine;
+mod queue;
+pub mod shared;
 mod stats;
 
+pub use self::bridge::Bridge;
+#[cfg(feature = "nip11")]
+pub use self::document::{policy_changes, RelayPolicyChange};
+pub use self::health::RelayDemotionReason;
+pub use self::interceptor::{FrameAction, FrameInterceptor};
 pub use self::limits::Limits;
+pub(crate) use self::middleware::Middlewares;
+pub use self::middleware::{Middleware, MiddlewareError};
+pub use self::moderation::{Moderation, ModerationFilters, ModerationReason};
 pub use self::options::{
-    FilterOptions, NegentropyOptions, RelayOptions, RelayPoolOptions, RelaySendOptions,
+    FilterOptions, NegentropyOptions, RelayHealthMonitorOptions, RelayOptions, RelayPoolOptions,
+    RelaySendOptions,
 };
 use self::options::{MAX_ADJ_RETRY_SEC, MIN_RETRY_SEC};
-pub use self::pool::{RelayPoolMessage, RelayPoolNotification};
-pub use self::stats::RelayConnectionStats;
+pub use self::pool::{
+    ConnectReport, CountReport, MessageRejectedReason, NotificationQueue, RelayPoolMessage,
+    RelayPoolNotification, ShutdownReport,
+};
+pub use self::quarantine::{Quarantine, QuarantineReason, QuarantinedEvent};
+use self::queue::OutgoingQueue;
+pub use self::queue::QueuedMessage;
+pub use self::shared::{NotificationHub, SeenTracker};
+pub use self::stats::{RelayConnectionStats, RelayPoolStats};
 #[cfg(feature = "blocking")]
 use crate::RUNTIME;
 
-type Message = (RelayEvent, Option<oneshot::Sender<bool>>);
+/// Item of the relay channel
+///
+/// The messages to send are moved into the [`OutgoingQueue`], so that they can be listed
+/// and cancelled: only their queue ID goes through the channel.
+#[derive(Debug)]
+enum RelayCommand {
+    Queued(u64),
+    Event(RelayEvent),
+}
+
+type Message = (RelayCommand, Option<oneshot::Sender<bool>>);
 
 const MIN_UPTIME: f64 = 0.90;
-#[cfg(not(target_arch = "wasm32"))]
+const FLUSH_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Time after which an event sent without receiving the `OK` is no longer considered pending
+const PENDING_OK_TIMEOUT: Duration = Duration::from_secs(60);
+const NIP_COUNT: u16 = 45;
+pub(crate) const NIP_SEARCH: u16 = 50;
+const NIP_NEGENTROPY: u16 = 77;
 const PING_INTERVAL: u64 = 55;
+/// Browsers can't send WebSocket pings: a `REQ` with this prefix is answered by `EOSE`
+#[cfg(target_arch = "wasm32")]
+const PING_SUBSCRIPTION_PREFIX: &str = "ping-";
+#[cfg(target_arch = "wasm32")]
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(60);
+const DEFAULT_RATE_LIMIT_BACKOFF: u64 = 10;
+/// Kept below [`PING_INTERVAL`] so that a queued ping is not delayed long enough to drop the connection
+const MAX_RATE_LIMIT_BACKOFF: u64 = 30;
 
 /// [`Relay`] error
 #[derive(Debug, Error)]
@@ -63,6 +119,9 @@ pub enum Error {
     /// Database error
     #[error(transparent)]
     Database(#[from] DatabaseError),
+    /// Impossible to acquire a REQ slot
+    #[error(transparent)]
+    Acquire(#[from] AcquireError),
     /// Channel timeout
     #[error("channel timeout")]
     ChannelTimeout,
@@ -122,6 +181,9 @@ pub enum Error {
     /// Unknown negentropy error
     #[error("unknown negentropy error")]
     UnknownNegentropyError,
+    /// NIP not supported
+    #[error("NIP-{0} not supported by relay")]
+    NipNotSupported(u16),
 }
 
 /// Relay connection status
@@ -165,7 +227,6 @@ pub enum RelayEvent {
     /// Send multiple messages at once
     Batch(Vec<ClientMessage>),
     /// Ping
-    #[cfg(not(target_arch = "wasm32"))]
     Ping {
         /// Nonce
         nonce: u64,
@@ -185,6 +246,10 @@ pub enum InternalSubscriptionId {
     Default,
     /// Pool
     Pool,
+    /// Relay-specific subscription set from the pool (see [`RelayPool::subscribe_to`](pool::RelayPool::subscribe_to))
+    Relay,
+    /// Subscription set from the pool to the relays with a label (see [`RelayPool::subscribe_to_label`](pool::RelayPool::subscribe_to_label))
+    Label(String),
     /// Custom
     Custom(String),
 }
@@ -194,6 +259,8 @@ impl fmt::Display for InternalSubscriptionId {
         match self {
             Self::Default => write!(f, "default"),
             Self::Pool => write!(f, "pool"),
+            Self::Relay => write!(f, "relay"),
+            Self::Label(label) => write!(f, "label:{label}"),
             Self::Custom(c) => write!(f, "{c}"),
         }
     }
@@ -208,6 +275,7 @@ where
         match s.as_str() {
             "default" => Self::Default,
             "pool" => Self::Pool,
+            "relay" => Self::Relay,
             _ => Self::Custom(s),
         }
     }
@@ -220,6 +288,12 @@ pub struct ActiveSubscription {
     id: SubscriptionId,
     /// Subscriptions filters
     filters: Vec<Filter>,
+    /// Timestamp of the newest event received, once all stored events have been received
+    newest: Option<Timestamp>,
+    /// Timestamp of the newest stored event received before `EOSE`
+    newest_stored: Option<Timestamp>,
+    /// Whether `EOSE` has been received since the last `REQ`
+    eose: bool,
 }
 
 impl Default for ActiveSubscription {
@@ -234,6 +308,9 @@ impl ActiveSubscription {
         Self {
             id: SubscriptionId::generate(),
             filters: Vec::new(),
+            newest: None,
+            newest_stored: None,
+            eose: false,
         }
     }
 
@@ -242,6 +319,9 @@ impl ActiveSubscription {
         Self {
             id: SubscriptionId::generate(),
             filters,
+            newest: None,
+            newest_stored: None,
+            eose: false,
         }
     }
 
@@ -254,6 +334,14 @@ impl ActiveSubscription {
     pub fn filters(&self) -> Vec<Filter> {
         self.filters.clone()
     }
+
+    /// Get the timestamp of the newest event received
+    ///
+    /// Stored events count only after `EOSE`, so that a disconnection during the backfill
+    /// doesn't skip older events when resubscribing.
+    pub fn newest(&self) -> Option<Timestamp> {
+        self.newest
+    }
 }
 
 /// Relay
@@ -261,19 +349,25 @@ impl ActiveSubscription {
 pub struct Relay {
     url: Url,
     status: Arc<RwLock<RelayStatus>>,
+    last_connection_error: Arc<RwLock<Option<String>>>,
+    auth_challenge: Arc<RwLock<Option<String>>>,
     #[cfg(feature = "nip11")]
     document: Arc<RwLock<RelayInformationDocument>>,
     opts: RelayOptions,
     stats: RelayConnectionStats,
+    #[cfg(not(target_arch = "wasm32"))]
+    connection_cache: ConnectionCache,
     database: Arc<DynNostrDatabase>,
     scheduled_for_stop: Arc<AtomicBool>,
     scheduled_for_termination: Arc<AtomicBool>,
     pool_sender: Sender<RelayPoolMessage>,
     relay_sender: Sender<Message>,
     relay_receiver: Arc<Mutex<Receiver<Message>>>,
+    outgoing_queue: OutgoingQueue,
     notification_sender: broadcast::Sender<RelayPoolNotification>,
     subscriptions: Arc<RwLock<HashMap<InternalSubscriptionId, ActiveSubscription>>>,
-    limits: Limits,
+    pending_ok: Arc<RwLock<HashMap<EventId, Instant>>>,
+    pending_reqs: Arc<Semaphore>,
 }
 
 impl PartialEq for Relay {
@@ -290,26 +384,50 @@ impl Relay {
         pool_sender: Sender<RelayPoolMessage>,
         notification_sender: broadcast::Sender<RelayPoolNotification>,
         opts: RelayOptions,
-        limits: Limits,
+    ) -> Self {
+        Self::with_stats(
+            url,
+            database,
+            pool_sender,
+            notification_sender,
+            opts,
+            RelayConnectionStats::new(),
+        )
+    }
+
+    pub(crate) fn with_stats(
+        url: Url,
+        database: Arc<DynNostrDatabase>,
+        pool_sender: Sender<RelayPoolMessage>,
+        notification_sender: broadcast::Sender<RelayPoolNotification>,
+        opts: RelayOptions,
+        stats: RelayConnectionStats,
     ) -> Self {
         let (relay_sender, relay_receiver) = mpsc::channel::<Message>(1024);
+        let pending_reqs = Arc::new(Semaphore::new(opts.get_max_pending_reqs()));
 
         Self {
             url,
             status: Arc::new(RwLock::new(RelayStatus::Initialized)),
+            last_connection_error: Arc::new(RwLock::new(None)),
+            auth_challenge: Arc::new(RwLock::new(None)),
             #[cfg(feature = "nip11")]
             document: Arc::new(RwLock::new(RelayInformationDocument::new())),
             opts,
-            stats: RelayConnectionStats::new(),
+            stats,
+            #[cfg(not(target_arch = "wasm32"))]
+            connection_cache: ConnectionCache::new(),
             database,
             scheduled_for_stop: Arc::new(AtomicBool::new(false)),
             scheduled_for_termination: Arc::new(AtomicBool::new(false)),
             pool_sender,
             relay_sender,
             relay_receiver: Arc::new(Mutex::new(relay_receiver)),
+            outgoing_queue: OutgoingQueue::default(),
             notification_sender,
             subscriptions: Arc::new(RwLock::new(HashMap::new())),
-            limits,
+            pending_ok: Arc::new(RwLock::new(HashMap::new())),
+            pending_reqs,
         }
     }
 
@@ -336,11 +454,35 @@ impl Relay {
         RUNTIME.block_on(async { self.status().await })
     }
 
+    /// Get the error of the last connection attempt
+    ///
+    /// Cleared when the connection succeeds.
+    pub async fn last_connection_error(&self) -> Option<String> {
+        self.last_connection_error.read().await.clone()
+    }
+
+    async fn set_last_connection_error(&self, error: Option<String>) {
+        let mut last_connection_error = self.last_connection_error.write().await;
+        *last_connection_error = error;
+    }
+
     async fn set_status(&self, status: RelayStatus) {
         // Change status
         let mut s = self.status.write().await;
+        if *s == RelayStatus::Connected && status == RelayStatus::Disconnected {
+            self.stats.new_disconnection();
+        }
         *s = status;
 
+        // The `OK` of the sent events and the `EOSE` of the pending `REQ`s will never be received
+        if matches!(
+            status,
+            RelayStatus::Disconnected | RelayStatus::Stopped | RelayStatus::Terminated
+        ) {
+            self.pending_ok.write().await.clear();
+            self.stats.clear_pending_reqs().await;
+        }
+
         // Send notification
         if let Err(e) = self.pool_sender.try_send(RelayPoolMessage::RelayStatus {
             relay_url: self.url(),
@@ -350,6 +492,11 @@ impl Relay {
         }
     }
 
+    /// Get the last `AUTH` challenge received from the relay (NIP42)
+    pub async fn auth_challenge(&self) -> Option<String> {
+        self.auth_challenge.read().await.clone()
+    }
+
     /// Check if [`Relay`] is connected
     pub async fn is_connected(&self) -> bool {
         self.status().await == RelayStatus::Connected
@@ -368,13 +515,50 @@ impl Relay {
         RUNTIME.block_on(async { self.document().await })
     }
 
+    /// Check if the relay advertises support for a NIP in its information document (NIP11)
+    #[cfg(feature = "nip11")]
+    pub async fn supports_nip(&self, nip: u16) -> bool {
+        let document = self.document.read().await;
+        document
+            .supported_nips
+            .as_ref()
+            .map_or(false, |nips| nips.contains(&nip))
+    }
+
+    /// Return an error if [`RelayOptions::check_supported_nips`] is enabled and
+    /// the relay publishes a list of supported NIPs that doesn't include `nip`
+    #[cfg(feature = "nip11")]
+    async fn check_nip(&self, nip: u16) -> Result<(), Error> {
+        if self.opts.get_check_supported_nips() {
+            let document = self.document.read().await;
+            if let Some(nips) = &document.supported_nips {
+                if !nips.contains(&nip) {
+                    return Err(Error::NipNotSupported(nip));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(not(feature = "nip11"))]
+    async fn check_nip(&self, _nip: u16) -> Result<(), Error> {
+        Ok(())
+    }
+
+    async fn check_search_support(&self, filters: &[Filter]) -> Result<(), Error> {
+        if filters.iter().any(|f| f.search.is_some()) {
+            self.check_nip(NIP_SEARCH).await?;
+        }
+        Ok(())
+    }
+
     #[cfg(feature = "nip11")]
-    async fn set_document(&self, document: RelayInformationDocument) {
+    pub(crate) async fn set_document(&self, document: RelayInformationDocument) {
         let mut d = self.document.write().await;
         *d = document;
     }
 
-    /// Get [`ActiveSubscription`]
+    /// Get active subscriptions, with their [`SubscriptionId`] and filters
     pub async fn subscriptions(&self) -> HashMap<InternalSubscriptionId, ActiveSubscription> {
         let subscription = self.subscriptions.read().await;
         subscription.clone()
@@ -389,6 +573,58 @@ impl Relay {
         subscription.get(internal_id).cloned()
     }
 
+    /// Get the [`InternalSubscriptionId`] of an active subscription
+    pub(crate) async fn internal_subscription_id(
+        &self,
+        id: &SubscriptionId,
+    ) -> Option<InternalSubscriptionId> {
+        let subscriptions = self.subscriptions.read().await;
+        subscriptions
+            .iter()
+            .find(|(_, sub)| &sub.id == id)
+            .map(|(internal_id, _)| internal_id.clone())
+    }
+
+    /// Keep track of the newest event received by an active subscription
+    ///
+    /// Timestamps in the future are clamped to now.
+    async fn update_subscription_newest(&self, id: &SubscriptionId, created_at: Timestamp) {
+        let created_at: Timestamp = created_at.min(Timestamp::now());
+        let mut subscriptions = self.subscriptions.write().await;
+        if let Some(sub) = subscriptions.values_mut().find(|sub| &sub.id == id) {
+            let newest: &mut Option<Timestamp> = if sub.eose {
+                &mut sub.newest
+            } else {
+                &mut sub.newest_stored
+            };
+            if newest.map_or(true, |newest| created_at > newest) {
+                *newest = Some(created_at);
+            }
+        }
+    }
+
+    /// All stored events of the subscription have been received: take them into account for the newest event
+    async fn subscription_eose(&self, id: &SubscriptionId) {
+        let mut subscriptions = self.subscriptions.write().await;
+        if let Some(sub) = subscriptions.values_mut().find(|sub| &sub.id == id) {
+            sub.eose = true;
+            if let Some(stored) = sub.newest_stored.take() {
+                if sub.newest.map_or(true, |newest| stored > newest) {
+                    sub.newest = Some(stored);
+                }
+            }
+        }
+    }
+
+    /// A new `REQ` has been sent: wait again for `EOSE` before advancing the newest event
+    async fn subscription_requested(&self, id: &SubscriptionId) {
+        let mut subscriptions = self.subscriptions.write().await;
+        if let Some(sub) = subscriptions.values_mut().find(|sub| &sub.id == id) {
+            sub.eose = false;
+            sub.newest_stored = None;
+        }
+    }
+
     async fn update_subscription_filters(
         &self,
         internal_id: InternalSubscriptionId,
@@ -396,7 +632,12 @@ impl Relay {
     ) {
         let mut s = self.subscriptions.write().await;
         s.entry(internal_id)
-            .and_modify(|sub| sub.filters = filters.clone())
+            .and_modify(|sub| {
+                sub.filters = filters.clone();
+                sub.newest = None;
+                sub.newest_stored = None;
+                sub.eose = false;
+            })
             .or_insert_with(|| ActiveSubscription::with_filters(filters));
     }
 
@@ -405,6 +646,16 @@ impl Relay {
         self.opts.clone()
     }
 
+    /// Get labels (see [`RelayOptions::labels`])
+    pub fn labels(&self) -> HashSet<String> {
+        self.opts.get_labels()
+    }
+
+    /// Check if the relay has the `label`
+    pub fn has_label(&self, label: &str) -> bool {
+        self.opts.has_label(label)
+    }
+
     /// Get [`RelayConnectionStats`]
     pub fn stats(&self) -> RelayConnectionStats {
         self.stats.clone()
@@ -415,6 +666,88 @@ impl Relay {
         self.relay_sender.max_capacity() - self.relay_sender.capacity()
     }
 
+    /// Get the messages waiting in the outgoing queue
+    ///
+    /// The messages already sent but not yet confirmed are not included (see [`Relay::pending_ok`]).
+    pub fn queued_messages(&self) -> Vec<QueuedMessage> {
+        self.outgoing_queue.list()
+    }
+
+    /// Cancel a queued message
+    ///
+    /// Return `false` if the message was already sent (or cancelled).
+    pub fn cancel_queued(&self, id: u64) -> bool {
+        self.outgoing_queue.cancel(id)
+    }
+
+    /// Cancel the queued `EVENT` messages of `event_id`
+    ///
+    /// Return `false` if there wasn't any queued message to cancel.
+    pub fn cancel_queued_event(&self, event_id: EventId) -> bool {
+        self.outgoing_queue.cancel_event(&event_id)
+    }
+
+    /// Cancel all the queued messages (i.e. to not flood the relay when it comes back online)
+    ///
+    /// Return the number of cancelled messages.
+    pub fn clear_queue(&self) -> usize {
+        self.outgoing_queue.clear()
+    }
+
+    /// Get IDs of the events sent to relay but not yet confirmed by `OK`
+    ///
+    /// The events are discarded when the relay disconnects or if the `OK` is not received within 60 secs.
+    pub async fn pending_ok(&self) -> Vec<EventId> {
+        let mut pending_ok = self.pending_ok.write().await;
+        pending_ok.retain(|_, sent_at| sent_at.elapsed() < PENDING_OK_TIMEOUT);
+        pending_ok.keys().copied().collect()
+    }
+
+    /// Track the events sent, waiting for their `OK`
+    async fn add_pending_ok<I>(&self, ids: I)
+    where
+        I: IntoIterator<Item = EventId>,
+    {
+        let now = Instant::now();
+        let mut pending_ok = self.pending_ok.write().await;
+        pending_ok.retain(|_, sent_at| sent_at.elapsed() < PENDING_OK_TIMEOUT);
+        pending_ok.extend(ids.into_iter().map(|id| (id, now)));
+    }
+
+    /// Wait until the relay is connected
+    ///
+    /// Return `false` if `timeout` is reached.
+    pub async fn wait_for_connection(&self, timeout: Duration) -> bool {
+        time::timeout(Some(timeout), async {
+            while !self.is_connected().await {
+                thread::sleep(FLUSH_POLL_INTERVAL).await;
+            }
+        })
+        .await
+        .is_some()
+    }
+
+    /// Wait until the queue is empty and all the sent events have been confirmed by `OK`
+    ///
+    /// Return `false` if `timeout` is reached or if the relay is not connected and there are still pending messages.
+    pub async fn wait_for_flush(&self, timeout: Duration) -> bool {
+        time::timeout(Some(timeout), async {
+            loop {
+                if self.queue() == 0 && self.pending_ok().await.is_empty() {
+                    return true;
+                }
+
+                if !self.is_connected().await {
+                    return false;
+                }
+
+                thread::sleep(FLUSH_POLL_INTERVAL).await;
+            }
+        })
+        .await
+        .unwrap_or(false)
+    }
+
     fn is_scheduled_for_stop(&self) -> bool {
         self.scheduled_for_stop.load(Ordering::SeqCst)
     }
@@ -556,20 +889,43 @@ impl Relay {
             });
         }
 
+        let started_at = Instant::now();
+
         #[cfg(not(target_arch = "wasm32"))]
-        let connection = net::native::connect(&self.url, self.proxy(), None).await;
+        let connection = {
+            let cache: ConnectionCache = if self.opts.get_warm_standby() {
+                self.connection_cache.clone()
+            } else {
+                ConnectionCache::new()
+            };
+            net::native::connect_with_cache(
+                &self.url,
+                self.proxy(),
+                None,
+                &self.opts.get_handshake(),
+                &cache,
+            )
+            .await
+        };
         #[cfg(target_arch = "wasm32")]
-        let connection = net::wasm::connect(&self.url).await;
+        let connection =
+            match time::timeout(Some(CONNECTION_TIMEOUT), net::wasm::connect(&self.url)).await {
+                Some(res) => res.map_err(|e| e.to_string()),
+                None => Err(String::from("timeout")),
+            };
 
         // Connect
         match connection {
             Ok((mut ws_tx, mut ws_rx)) => {
+                self.set_last_connection_error(None).await;
                 self.set_status(RelayStatus::Connected).await;
                 tracing::info!("Connected to {}", url);
 
                 self.stats.new_success();
+                self.stats
+                    .save_connection_latency(started_at.elapsed())
+                    .await;
 
-                #[cfg(not(target_arch = "wasm32"))]
                 let ping_abort_handle: AbortHandle = {
                     let relay = self.clone();
                     thread::abortable(async move {
@@ -614,7 +970,46 @@ impl Relay {
                 thread::spawn(async move {
                     tracing::debug!("Relay Event Thread Started");
                     let mut rx = relay.relay_receiver.lock().await;
-                    while let Some((relay_event, oneshot_sender)) = rx.recv().await {
+                    while let Some((command, oneshot_sender)) = rx.recv().await {
+                        let relay_event = match command {
+                            RelayCommand::Queued(id) => {
+                                if let (Some(delay), Some(queued_at)) = (
+                                    relay.opts.get_send_delay(),
+                                    relay.outgoing_queue.queued_at(id),
+                                ) {
+                                    if let Some(delay) = delay.checked_sub(queued_at.elapsed()) {
+                                        thread::sleep(delay).await;
+                                    }
+                                }
+
+                                if let Some(until) = relay.stats.rate_limited_until() {
+                                    let delay: u64 =
+                                        until.as_u64().saturating_sub(Timestamp::now().as_u64());
+                                    tracing::debug!(
+                                        "{} is rate limiting: waiting {delay} secs before sending",
+                                        relay.url
+                                    );
+                                    thread::sleep(Duration::from_secs(delay)).await;
+                                }
+
+                                // Take the message from the queue: skip it, if cancelled in the meantime
+                                match relay.outgoing_queue.take(id) {
+                                    Some(mut msgs) if msgs.len() == 1 => {
+                                        RelayEvent::SendMsg(Box::new(msgs.remove(0)))
+                                    }
+                                    Some(msgs) => RelayEvent::Batch(msgs),
+                                    None => {
+                                        tracing::debug!("Queued message {id} cancelled");
+                                        if let Some(sender) = oneshot_sender {
+                                            let _ = sender.send(false);
+                                        }
+                                        continue;
+                                    }
+                                }
+                            }
+                            RelayCommand::Event(relay_event) => relay_event,
+                        };
+
                         match relay_event {
                             RelayEvent::SendMsg(msg) => {
                                 let json = msg.as_json();
@@ -626,6 +1021,23 @@ impl Relay {
                                 match ws_tx.send(WsMessage::Text(json)).await {
                                     Ok(_) => {
                                         relay.stats.add_bytes_sent(size);
+                                        if let ClientMessage::Event(event) = msg.as_ref() {
+                                            relay.stats.add_events_sent(1);
+                                            relay.add_pending_ok([event.id]).await;
+                                        }
+                                        if let ClientMessage::Req {
+                                            subscription_id, ..
+                                        } = msg.as_ref()
+                                        {
+                                            relay.stats.req_sent(subscription_id.to_string()).await;
+                                        }
+                                        if let ClientMessage::Close(subscription_id) = msg.as_ref()
+                                        {
+                                            relay
+                                                .stats
+                                                .req_closed(&subscription_id.to_string())
+                                                .await;
+                                        }
                                         if let Some(sender) = oneshot_sender {
                                             if let Err(e) = sender.send(true) {
                                                 tracing::error!(
@@ -641,6 +1053,7 @@ impl Relay {
                                             relay.url(),
                                             e.to_string()
                                         );
+                                        relay.stats.new_error();
                                         if let Some(sender) = oneshot_sender {
                                             if let Err(e) = sender.send(false) {
                                                 tracing::error!(
@@ -661,6 +1074,13 @@ impl Relay {
                                     "Sending {len} messages to {} (size: {size} bytes)",
                                     relay.url
                                 );
+                                let ids: Vec<EventId> = msgs
+                                    .iter()
+                                    .filter_map(|msg| match msg {
+                                        ClientMessage::Event(event) => Some(event.id),
+                                        _ => None,
+                                    })
+                                    .collect();
                                 let msgs = msgs
                                     .into_iter()
                                     .map(|msg| Ok(WsMessage::Text(msg.as_json())));
@@ -668,6 +1088,10 @@ impl Relay {
                                 match ws_tx.send_all(&mut stream).await {
                                     Ok(_) => {
                                         relay.stats.add_bytes_sent(size);
+                                        if !ids.is_empty() {
+                                            relay.stats.add_events_sent(ids.len());
+                                            relay.add_pending_ok(ids).await;
+                                        }
                                         if let Some(sender) = oneshot_sender {
                                             if let Err(e) = sender.send(true) {
                                                 tracing::error!(
@@ -683,6 +1107,7 @@ impl Relay {
                                             relay.url(),
                                             e.to_string()
                                         );
+                                        relay.stats.new_error();
                                         if let Some(sender) = oneshot_sender {
                                             if let Err(e) = sender.send(false) {
                                                 tracing::error!(
@@ -695,12 +1120,20 @@ impl Relay {
                                     }
                                 }
                             }
-                            #[cfg(not(target_arch = "wasm32"))]
                             RelayEvent::Ping { nonce } => {
-                                match ws_tx
-                                    .send(WsMessage::Ping(nonce.to_string().as_bytes().to_vec()))
-                                    .await
-                                {
+                                #[cfg(not(target_arch = "wasm32"))]
+                                let ping = WsMessage::Ping(nonce.to_string().as_bytes().to_vec());
+                                #[cfg(target_arch = "wasm32")]
+                                let ping = WsMessage::Text(
+                                    ClientMessage::new_req(
+                                        SubscriptionId::new(format!(
+                                            "{PING_SUBSCRIPTION_PREFIX}{nonce}"
+                                        )),
+                                        vec![Filter::new().limit(0)],
+                                    )
+                                    .as_json(),
+                                );
+                                match ws_tx.send(ping).await {
                                     Ok(_) => {
                                         relay.stats.ping.just_sent().await;
                                         tracing::debug!("Ping {} (nonce {})", relay.url, nonce);
@@ -743,7 +1176,6 @@ impl Relay {
 
                     tracing::debug!("Exited from Relay Event Thread");
 
-                    #[cfg(not(target_arch = "wasm32"))]
                     ping_abort_handle.abort();
                 });
 
@@ -752,22 +1184,126 @@ impl Relay {
                     tracing::debug!("Relay Message Thread Started");
 
                     async fn func(relay: &Relay, data: Vec<u8>) -> bool {
+                        let received_at: Timestamp = Timestamp::now();
                         let size: usize = data.len();
-                        let max_size: usize = relay.limits.messages.max_size as usize;
+                        let limits: Limits = relay.opts.get_limits();
+                        let max_size: usize = limits.messages.max_size as usize;
                         relay.stats.add_bytes_received(size);
                         if size <= max_size {
-                            match RawRelayMessage::from_json(&data) {
+                            let msg = match relay.opts.get_frame_interceptor() {
+                                Some(interceptor) => match interceptor.intercept(&relay.url, &data)
+                                {
+                                    FrameAction::Forward => RawRelayMessage::from_json(&data),
+                                    FrameAction::Replace(msg) => Ok(msg),
+                                    FrameAction::Consume => return false,
+                                },
+                                None => RawRelayMessage::from_json(&data),
+                            };
+                            match msg {
                                 Ok(msg) => {
                                     tracing::trace!(
                                         "Received message from {}: {:?}",
                                         relay.url,
                                         msg
                                     );
+
+                                    if let RawRelayMessage::EndOfStoredEvents(subscription_id) =
+                                        &msg
+                                    {
+                                        relay.stats.eose_received(subscription_id).await;
+
+                                        if relay.opts.get_adjust_since_on_resubscribe() {
+                                            relay
+                                                .subscription_eose(&SubscriptionId::new(
+                                                    subscription_id,
+                                                ))
+                                                .await;
+                                        }
+
+                                        #[cfg(target_arch = "wasm32")]
+                                        if relay.handle_ping_eose(subscription_id).await {
+                                            return false;
+                                        }
+                                    }
+
+                                    if let RawRelayMessage::Ok { event_id, .. } = &msg {
+                                        if let Ok(event_id) = EventId::from_hex(event_id) {
+                                            let mut pending_ok = relay.pending_ok.write().await;
+                                            pending_ok.remove(&event_id);
+                                        }
+                                    }
+
+                                    if let RawRelayMessage::Closed {
+                                        subscription_id, ..
+                                    } = &msg
+                                    {
+                                        relay.stats.req_closed(subscription_id).await;
+                                    }
+
+                                    if let RawRelayMessage::Auth { challenge } = &msg {
+                                        let mut auth_challenge = relay.auth_challenge.write().await;
+                                        *auth_challenge = Some(challenge.clone());
+                                    }
+
+                                    if let Some(backoff) = rate_limit_backoff(&msg) {
+                                        tracing::warn!(
+                                            "Rate limited by {}: backing off for {} secs",
+                                            relay.url,
+                                            backoff.as_secs()
+                                        );
+                                        relay.stats.new_rate_limit(Timestamp::now() + backoff);
+                                    }
+
+                                    if let RawRelayMessage::Event { event, .. } = &msg {
+                                        let max_size: Option<u32> =
+                                            event.get("kind").and_then(|k| k.as_u64()).and_then(
+                                                |k| limits.events.get_max_size(Kind::from(k)),
+                                            );
+                                        if let Some(max_size) = max_size {
+                                            let max_size: usize = max_size as usize;
+                                            if size > max_size {
+                                                relay.reject_message(
+                                                    MessageRejectedReason::EventTooLarge {
+                                                        max_size,
+                                                    },
+                                                    size,
+                                                );
+                                                return false;
+                                            }
+                                        }
+                                    }
+
+                                    let internal_subscription_id = match &msg {
+                                        RawRelayMessage::Event {
+                                            subscription_id,
+                                            event,
+                                        } => {
+                                            let subscription_id =
+                                                SubscriptionId::new(subscription_id);
+                                            if relay.opts.get_adjust_since_on_resubscribe() {
+                                                if let Some(created_at) =
+                                                    event.get("created_at").and_then(|t| t.as_u64())
+                                                {
+                                                    relay
+                                                        .update_subscription_newest(
+                                                            &subscription_id,
+                                                            Timestamp::from(created_at),
+                                                        )
+                                                        .await;
+                                                }
+                                            }
+                                            relay.internal_subscription_id(&subscription_id).await
+                                        }
+                                        _ => None,
+                                    };
+
                                     if let Err(err) = relay
                                         .pool_sender
                                         .send(RelayPoolMessage::ReceivedMsg {
                                             relay_url: relay.url(),
                                             msg,
+                                            internal_subscription_id,
+                                            received_at,
                                         })
                                         .await
                                     {
@@ -784,7 +1320,10 @@ impl Relay {
                                 },
                             };
                         } else {
-                            tracing::error!("Received message too large from {}: size={size}, max_size={max_size}", relay.url);
+                            relay.reject_message(
+                                MessageRejectedReason::MessageTooLarge { max_size },
+                                size,
+                            );
                         }
 
                         false
@@ -803,9 +1342,7 @@ impl Relay {
                                                     relay.url,
                                                     nonce
                                                 );
-                                                relay.stats.ping.set_replied(true);
-                                                let sent_at = relay.stats.ping.sent_at().await;
-                                                relay.stats.save_latency(sent_at.elapsed()).await;
+                                                relay.pong().await;
                                             } else {
                                                 tracing::error!("Pong nonce not match: received={nonce}, expected={}", relay.stats.ping.last_nonce());
                                             }
@@ -853,20 +1390,77 @@ impl Relay {
                 }
             }
             Err(err) => {
+                self.set_last_connection_error(Some(err.to_string())).await;
                 self.set_status(RelayStatus::Disconnected).await;
                 tracing::error!("Impossible to connect to {}: {}", url, err);
             }
         };
     }
 
+    /// Pong received: save latency
+    async fn pong(&self) {
+        self.stats.ping.set_replied(true);
+        let sent_at = self.stats.ping.sent_at().await;
+        self.stats.save_latency(sent_at.elapsed()).await;
+    }
+
+    /// Check if `EOSE` is the reply to a ping and close the ping subscription
+    #[cfg(target_arch = "wasm32")]
+    async fn handle_ping_eose(&self, subscription_id: &str) -> bool {
+        match subscription_id.strip_prefix(PING_SUBSCRIPTION_PREFIX) {
+            Some(nonce) => {
+                if nonce.parse::<u64>().ok() == Some(self.stats.ping.last_nonce()) {
+                    tracing::debug!("Pong from {} match nonce: {nonce}", self.url);
+                    self.pong().await;
+                }
+                let id = SubscriptionId::new(subscription_id);
+                if let Err(e) = self.send_relay_event(
+                    RelayEvent::SendMsg(Box::new(ClientMessage::close(id))),
+                    None,
+                ) {
+                    tracing::error!("Impossible to close ping subscription: {e}");
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
     fn send_relay_event(
         &self,
         relay_msg: RelayEvent,
         sender: Option<oneshot::Sender<bool>>,
     ) -> Result<(), Error> {
-        self.relay_sender
-            .try_send((relay_msg, sender))
-            .map_err(|_| Error::MessageNotSent)
+        let command: RelayCommand = match relay_msg {
+            RelayEvent::SendMsg(msg) => RelayCommand::Queued(self.outgoing_queue.push(vec![*msg])),
+            RelayEvent::Batch(msgs) => RelayCommand::Queued(self.outgoing_queue.push(msgs)),
+            relay_event => RelayCommand::Event(relay_event),
+        };
+        let queue_id: Option<u64> = match command {
+            RelayCommand::Queued(id) => Some(id),
+            RelayCommand::Event(..) => None,
+        };
+        self.relay_sender.try_send((command, sender)).map_err(|e| {
+            if let Some(id) = queue_id {
+                self.outgoing_queue.cancel(id);
+            }
+            if let TrySendError::Full(..) = e {
+                self.stats.new_dropped_message();
+            }
+            Error::MessageNotSent
+        })
+    }
+
+    fn reject_message(&self, reason: MessageRejectedReason, size: usize) {
+        tracing::error!("Rejected message from {}: {reason}, size={size}", self.url);
+        self.stats.new_rejected_message();
+        let _ = self
+            .notification_sender
+            .send(RelayPoolNotification::MessageRejected {
+                relay_url: self.url(),
+                reason,
+                size,
+            });
     }
 
     /// Disconnect from relay and set status to 'Disconnected'
@@ -1006,6 +1600,7 @@ impl Relay {
                                 status,
                                 message,
                             },
+                        ..
                     } => {
                         if self.url == relay_url && id == event_id {
                             if status {
@@ -1079,6 +1674,7 @@ impl Relay {
                                 status,
                                 message,
                             },
+                        ..
                     } => {
                         if self.url == relay_url && missing.remove(&event_id) {
                             if status {
@@ -1133,7 +1729,19 @@ impl Relay {
 
         for (internal_id, sub) in subscriptions.into_iter() {
             if !sub.filters.is_empty() {
-                self.send_msg(ClientMessage::new_req(sub.id.clone(), sub.filters), wait)
+                let filters: Vec<Filter> = match sub.newest {
+                    Some(newest) if self.opts.get_adjust_since_on_resubscribe() => sub
+                        .filters
+                        .into_iter()
+                        .map(|filter| {
+                            let since: Timestamp = filter.since.map_or(newest, |s| s.max(newest));
+                            filter.since(since)
+                        })
+                        .collect(),
+                    _ => sub.filters,
+                };
+                self.subscription_requested(&sub.id).await;
+                self.send_msg(ClientMessage::new_req(sub.id.clone(), filters), wait)
                     .await?;
             } else {
                 tracing::warn!("Subscription '{internal_id}' has empty filters");
@@ -1175,6 +1783,9 @@ impl Relay {
     }
 
     /// Subscribe with custom internal ID
+    ///
+    /// If a subscription with the same internal ID already exists, its filters are replaced
+    /// (the [`SubscriptionId`] is kept, so the relay update the subscription in place).
     pub async fn subscribe_with_internal_id(
         &self,
         internal_id: InternalSubscriptionId,
@@ -1189,11 +1800,29 @@ impl Relay {
             return Err(Error::FiltersEmpty);
         }
 
+        self.check_search_support(&filters).await?;
+
         self.update_subscription_filters(internal_id.clone(), filters)
             .await;
         self.resubscribe(internal_id, wait).await
     }
 
+    /// Update the filters of an existing subscription
+    ///
+    /// Return [`Error::InternalIdNotFound`] if there isn't an active subscription with `internal_id`.
+    pub async fn update_subscription(
+        &self,
+        internal_id: InternalSubscriptionId,
+        filters: Vec<Filter>,
+        wait: Option<Duration>,
+    ) -> Result<(), Error> {
+        if self.subscription(&internal_id).await.is_none() {
+            return Err(Error::InternalIdNotFound);
+        }
+        self.subscribe_with_internal_id(internal_id, filters, wait)
+            .await
+    }
+
     /// Unsubscribe
     ///
     /// Internal Subscription ID set to `InternalSubscriptionId::Default`
@@ -1212,10 +1841,12 @@ impl Relay {
             return Err(Error::ReadDisabled);
         }
 
-        let mut subscriptions = self.subscriptions().await;
-        let subscription = subscriptions
-            .remove(&internal_id)
-            .ok_or(Error::InternalIdNotFound)?;
+        let subscription = {
+            let mut subscriptions = self.subscriptions.write().await;
+            subscriptions
+                .remove(&internal_id)
+                .ok_or(Error::InternalIdNotFound)?
+        };
         self.send_msg(ClientMessage::close(subscription.id), wait)
             .await?;
         Ok(())
@@ -1227,7 +1858,10 @@ impl Relay {
             return Err(Error::ReadDisabled);
         }
 
-        let subscriptions = self.subscriptions().await;
+        let subscriptions = {
+            let mut subscriptions = self.subscriptions.write().await;
+            std::mem::take(&mut *subscriptions)
+        };
 
         for sub in subscriptions.into_values() {
             self.send_msg(ClientMessage::close(sub.id.clone()), wait)
@@ -1345,6 +1979,11 @@ impl Relay {
             return Err(Error::ReadDisabled);
         }
 
+        self.check_search_support(&filters).await?;
+
+        // Wait for a free REQ slot (released on drop): the wait counts against the timeout
+        let (_permit, timeout) = self.acquire_req_permit(timeout).await?;
+
         let id = SubscriptionId::generate();
 
         self.send_msg(ClientMessage::new_req(id.clone(), filters), None)
@@ -1359,6 +1998,31 @@ impl Relay {
         Ok(())
     }
 
+    /// Wait up to `timeout` for a free REQ slot
+    ///
+    /// Return the permit and the time left of `timeout`.
+    async fn acquire_req_permit(
+        &self,
+        timeout: Duration,
+    ) -> Result<(OwnedSemaphorePermit, Duration), Error> {
+        if self.pending_reqs.available_permits() == 0 {
+            tracing::debug!("Too many pending REQs for {}: queuing request", self.url);
+        }
+        let started_at = Instant::now();
+        let permit: OwnedSemaphorePermit =
+            time::timeout(Some(timeout), self.pending_reqs.clone().acquire_owned())
+                .await
+                .ok_or(Error::Timeout)??;
+        Ok((permit, timeout.saturating_sub(started_at.elapsed())))
+    }
+
+    /// Get number of REQs (sent by `get_events_of` or `req_events_of`) currently in-flight
+    pub fn pending_reqs(&self) -> usize {
+        self.opts
+            .get_max_pending_reqs()
+            .saturating_sub(self.pending_reqs.available_permits())
+    }
+
     /// Get events of filters
     ///
     /// Get events from local database and relay
@@ -1391,6 +2055,19 @@ impl Relay {
 
         let relay = self.clone();
         thread::spawn(async move {
+            if let Err(e) = relay.check_search_support(&filters).await {
+                tracing::error!("Impossible to request events to {}: {e}", relay.url());
+                return;
+            }
+
+            let (_permit, timeout) = match relay.acquire_req_permit(timeout).await {
+                Ok(res) => res,
+                Err(e) => {
+                    tracing::error!("Impossible to request events to {}: {e}", relay.url());
+                    return;
+                }
+            };
+
             let id = SubscriptionId::generate();
 
             // Subscribe
@@ -1424,11 +2101,26 @@ impl Relay {
     }
 
     /// Count events of filters
+    ///
+    /// If the relay doesn't support `COUNT` (see [`RelayOptions::check_supported_nips`]) and
+    /// [`RelayOptions::count_fallback`] is enabled, the events are fetched and counted locally
+    /// (including the matching events already stored in the database).
     pub async fn count_events_of(
         &self,
         filters: Vec<Filter>,
         timeout: Duration,
     ) -> Result<usize, Error> {
+        if let Err(e) = self.check_nip(NIP_COUNT).await {
+            if self.opts.get_count_fallback() {
+                tracing::debug!("{e}: fetching events from {} to count them", self.url);
+                let events: Vec<Event> = self
+                    .get_events_of(filters, timeout, FilterOptions::ExitOnEOSE)
+                    .await?;
+                return Ok(events.len());
+            }
+            return Err(e);
+        }
+
         let id = SubscriptionId::generate();
         self.send_msg(ClientMessage::new_count(id.clone(), filters), None)
             .await?;
@@ -1445,6 +2137,7 @@ impl Relay {
                             subscription_id,
                             count: c,
                         },
+                    ..
                 } = notification
                 {
                     if subscription_id == id && relay_url == self.url {
@@ -1481,6 +2174,10 @@ impl Relay {
             return Err(Error::NotConnected);
         }
 
+        if self.check_nip(NIP_NEGENTROPY).await.is_err() {
+            return Err(Error::NegentropyNotSupported);
+        }
+
         let id_size: usize = 32;
 
         let mut negentropy = Negentropy::new(id_size, Some(4_096))?;
@@ -1504,7 +2201,10 @@ impl Relay {
         // Check if negentropy it's supported
         time::timeout(Some(opts.initial_timeout), async {
             while let Ok(notification) = temp_notifications.recv().await {
-                if let RelayPoolNotification::Message { relay_url, message } = notification {
+                if let RelayPoolNotification::Message {
+                    relay_url, message, ..
+                } = notification
+                {
                     if relay_url == self.url {
                         match message {
                             RelayMessage::NegMsg {
@@ -1544,7 +2244,9 @@ impl Relay {
 
         while let Ok(notification) = notifications.recv().await {
             match notification {
-                RelayPoolNotification::Message { relay_url, message } => {
+                RelayPoolNotification::Message {
+                    relay_url, message, ..
+                } => {
                     if relay_url == self.url {
                         match message {
                             RelayMessage::NegMsg {
@@ -1680,3 +2382,140 @@ impl Relay {
         }
     }
 }
+
+/// Get the backoff requested by a `rate-limited:` OK or CLOSED reply
+///
+/// Look for a retry hint (i.e. `rate-limited: retry after 20 seconds`), otherwise use the default backoff.
+fn rate_limit_backoff(msg: &RawRelayMessage) -> Option<Duration> {
+    let message: &str = match msg {
+        RawRelayMessage::Ok {
+            status: false,
+            message,
+            ..
+        } => message,
+        RawRelayMessage::Closed { message, .. } => message,
+        _ => return None,
+    };
+
+    let hint: String = message.strip_prefix("rate-limited:")?.to_lowercase();
+    let secs: u64 = ["retry", "after", "again in", "wait"]
+        .iter()
+        .find_map(|keyword| {
+            let (_, rest) = hint.split_once(keyword)?;
+            rest.split(|c: char| !c.is_ascii_digit())
+                .find(|n| !n.is_empty())?
+                .parse()
+                .ok()
+        })
+        .unwrap_or(DEFAULT_RATE_LIMIT_BACKOFF);
+
+    Some(Duration::from_secs(secs.min(MAX_RATE_LIMIT_BACKOFF)))
+}
+
+#[cfg(test)]
+mod tests {
+    use nostr_database::{IntoNostrDatabase, MemoryDatabase};
+
+    use super::*;
+
+    fn relay(opts: RelayOptions) -> Relay {
+        let (pool_sender, _) = mpsc::channel(1024);
+        let (notification_sender, _) = broadcast::channel(1024);
+        Relay::new(
+            Url::parse("wss://relay.example.com").unwrap(),
+            MemoryDatabase::default().into_nostr_database(),
+            pool_sender,
+            notification_sender,
+            opts,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_req_permit_timeout() {
+        let relay = relay(RelayOptions::default().max_pending_reqs(1));
+        let (permit, _) = relay
+            .acquire_req_permit(Duration::from_secs(10))
+            .await
+            .unwrap();
+        assert_eq!(relay.pending_reqs(), 1);
+
+        // No free slot: the request times out
+        let res = relay.acquire_req_permit(Duration::from_millis(50)).await;
+        assert!(matches!(res, Err(Error::Timeout)));
+
+        // The request is queued until the slot is released
+        let queued = {
+            let relay = relay.clone();
+            tokio::spawn(async move { relay.acquire_req_permit(Duration::from_secs(10)).await })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!queued.is_finished());
+        drop(permit);
+        let (_permit, left) = queued.await.unwrap().unwrap();
+        assert!(left < Duration::from_secs(10));
+        assert_eq!(relay.pending_reqs(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_pending_ok_cleared_on_disconnect() {
+        let relay = relay(RelayOptions::default());
+        let id = EventId::all_zeros();
+        relay.add_pending_ok([id]).await;
+        assert_eq!(relay.pending_ok().await, vec![id]);
+
+        relay.set_status(RelayStatus::Disconnected).await;
+        assert!(relay.pending_ok().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pending_reqs_evicted() {
+        let relay = relay(RelayOptions::default());
+        let stats = relay.stats();
+
+        stats.req_sent("closed").await;
+        stats.req_closed("closed").await;
+        stats.req_sent("disconnected").await;
+        relay.set_status(RelayStatus::Disconnected).await;
+
+        // No latency recorded for the evicted REQs
+        stats.eose_received("closed").await;
+        stats.eose_received("disconnected").await;
+        assert_eq!(stats.eose_latency().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_subscription_newest_after_eose() {
+        let relay = relay(RelayOptions::default());
+        let internal_id = InternalSubscriptionId::Default;
+        relay
+            .update_subscription_filters(internal_id.clone(), vec![Filter::new()])
+            .await;
+        let id: SubscriptionId = relay.subscription(&internal_id).await.unwrap().id();
+
+        // Stored events don't count before EOSE
+        relay
+            .update_subscription_newest(&id, Timestamp::from(100))
+            .await;
+        let sub = relay.subscription(&internal_id).await.unwrap();
+        assert_eq!(sub.newest(), None);
+
+        relay.subscription_eose(&id).await;
+        let sub = relay.subscription(&internal_id).await.unwrap();
+        assert_eq!(sub.newest(), Some(Timestamp::from(100)));
+
+        // Live events count immediately, future timestamps are clamped
+        let future = Timestamp::now() + Duration::from_secs(3600);
+        relay.update_subscription_newest(&id, future).await;
+        let sub = relay.subscription(&internal_id).await.unwrap();
+        let newest: Timestamp = sub.newest().unwrap();
+        assert!(newest < future);
+
+        // A new REQ waits again for EOSE
+        relay.subscription_requested(&id).await;
+        relay
+            .update_subscription_newest(&id, newest + Duration::from_secs(1))
+            .await;
+        let sub = relay.subscription(&internal_id).await.unwrap();
+        assert_eq!(sub.newest(), Some(newest));
+    }
+}
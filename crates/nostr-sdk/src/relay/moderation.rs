@@ -0,0 +1,237 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Moderation of the received events
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, Mutex, RwLock};
+
+use nostr::key::XOnlyPublicKey;
+use nostr::nips::nip13;
+use nostr::{Event, JsonUtil, Kind};
+
+use crate::client::{DefaultMutedWordMatcher, MutedWordMatcher};
+
+/// Why the event has been dropped by the [`Moderation`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModerationReason {
+    /// The author is muted
+    MutedPublicKey,
+    /// The content contains a muted word
+    MutedWord,
+    /// The POW difficulty is lower than the min required
+    InsufficientPow,
+    /// The event is larger than the max size
+    TooLarge,
+    /// The kind is not in the allowed kinds
+    KindNotAllowed,
+}
+
+impl fmt::Display for ModerationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MutedPublicKey => write!(f, "muted public key"),
+            Self::MutedWord => write!(f, "muted word"),
+            Self::InsufficientPow => write!(f, "insufficient pow"),
+            Self::TooLarge => write!(f, "too large"),
+            Self::KindNotAllowed => write!(f, "kind not allowed"),
+        }
+    }
+}
+
+/// Filters applied to the events received from the relays
+///
+/// All the filters are disabled by default.
+/// The muted words are matched with the [`DefaultMutedWordMatcher`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModerationFilters {
+    /// Muted public keys
+    pub muted_public_keys: HashSet<XOnlyPublicKey>,
+    /// Muted words
+    pub muted_words: Vec<String>,
+    /// Min POW difficulty
+    pub min_pow: Option<u8>,
+    /// Max size of the event JSON, in bytes
+    pub max_event_size: Option<usize>,
+    /// Allowed kinds
+    pub allowed_kinds: Option<HashSet<Kind>>,
+}
+
+impl ModerationFilters {
+    /// New empty [`ModerationFilters`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mute public keys
+    pub fn mute_public_keys<I>(mut self, public_keys: I) -> Self
+    where
+        I: IntoIterator<Item = XOnlyPublicKey>,
+    {
+        self.muted_public_keys.extend(public_keys);
+        self
+    }
+
+    /// Mute words
+    pub fn mute_words<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.muted_words.extend(words.into_iter().map(|w| w.into()));
+        self
+    }
+
+    /// Min POW difficulty
+    pub fn min_pow(self, difficulty: u8) -> Self {
+        Self {
+            min_pow: Some(difficulty),
+            ..self
+        }
+    }
+
+    /// Max size of the event JSON, in bytes
+    pub fn max_event_size(self, size: usize) -> Self {
+        Self {
+            max_event_size: Some(size),
+            ..self
+        }
+    }
+
+    /// Allow only the `kinds`
+    pub fn allowed_kinds<I>(self, kinds: I) -> Self
+    where
+        I: IntoIterator<Item = Kind>,
+    {
+        Self {
+            allowed_kinds: Some(kinds.into_iter().collect()),
+            ..self
+        }
+    }
+
+    /// Check if the event must be dropped
+    pub fn check(&self, event: &Event) -> Option<ModerationReason> {
+        if self.muted_public_keys.contains(&event.pubkey) {
+            return Some(ModerationReason::MutedPublicKey);
+        }
+
+        if let Some(kinds) = &self.allowed_kinds {
+            if !kinds.contains(&event.kind) {
+                return Some(ModerationReason::KindNotAllowed);
+            }
+        }
+
+        if let Some(min_pow) = self.min_pow {
+            if nip13::get_leading_zero_bits(event.id.as_bytes()) < min_pow {
+                return Some(ModerationReason::InsufficientPow);
+            }
+        }
+
+        if let Some(max_size) = self.max_event_size {
+            if event.as_json().len() > max_size {
+                return Some(ModerationReason::TooLarge);
+            }
+        }
+
+        let matcher = DefaultMutedWordMatcher;
+        if self
+            .muted_words
+            .iter()
+            .any(|word| matcher.is_match(&event.content, word))
+        {
+            return Some(ModerationReason::MutedWord);
+        }
+
+        None
+    }
+}
+
+/// Moderation of the events received from the relays
+///
+/// The events matching the [`ModerationFilters`] are dropped before being saved into the database
+/// and notified. Set the initial filters with [`RelayPoolOptions::moderation`](super::RelayPoolOptions::moderation).
+///
+/// # Example
+/// ```rust,no_run
+/// use nostr_sdk::prelude::*;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let client = Client::new(&Keys::generate());
+/// client.moderation().set_filters(
+///     ModerationFilters::new()
+///         .mute_words(["airdrop"])
+///         .max_event_size(64 * 1024),
+/// );
+///
+/// // ...
+///
+/// for (reason, count) in client.moderation().dropped() {
+///     println!("{reason}: {count}");
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Moderation {
+    filters: Arc<RwLock<ModerationFilters>>,
+    dropped: Arc<Mutex<HashMap<ModerationReason, usize>>>,
+}
+
+impl Moderation {
+    pub(crate) fn new(filters: ModerationFilters) -> Self {
+        Self {
+            filters: Arc::new(RwLock::new(filters)),
+            dropped: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Get the current filters
+    pub fn filters(&self) -> ModerationFilters {
+        let filters = self.filters.read().unwrap_or_else(|e| e.into_inner());
+        filters.clone()
+    }
+
+    /// Replace the filters
+    pub fn set_filters(&self, filters: ModerationFilters) {
+        let mut current = self.filters.write().unwrap_or_else(|e| e.into_inner());
+        *current = filters;
+    }
+
+    /// Get the number of dropped events, per reason
+    pub fn dropped(&self) -> HashMap<ModerationReason, usize> {
+        let dropped = self.dropped.lock().unwrap_or_else(|e| e.into_inner());
+        dropped.clone()
+    }
+
+    /// Get the total number of dropped events
+    pub fn total_dropped(&self) -> usize {
+        let dropped = self.dropped.lock().unwrap_or_else(|e| e.into_inner());
+        dropped.values().sum()
+    }
+
+    /// Reset the counters of the dropped events
+    pub fn reset_counters(&self) {
+        let mut dropped = self.dropped.lock().unwrap_or_else(|e| e.into_inner());
+        dropped.clear();
+    }
+
+    /// Check the event, counting it if dropped
+    pub(crate) fn is_allowed(&self, event: &Event) -> bool {
+        let reason: Option<ModerationReason> = {
+            let filters = self.filters.read().unwrap_or_else(|e| e.into_inner());
+            filters.check(event)
+        };
+
+        match reason {
+            Some(reason) => {
+                tracing::trace!("Event {} dropped by moderation: {reason}", event.id);
+                let mut dropped = self.dropped.lock().unwrap_or_else(|e| e.into_inner());
+                *dropped.entry(reason).or_default() += 1;
+                false
+            }
+            None => true,
+        }
+    }
+}
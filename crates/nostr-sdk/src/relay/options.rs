@@ -2,17 +2,23 @@
 // Copyright (c) 2023-2024 Rust Nostr Developers
 // Distributed under the MIT software license
 
+use std::collections::HashSet;
 #[cfg(not(target_arch = "wasm32"))]
 use std::net::SocketAddr;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
+#[cfg(not(target_arch = "wasm32"))]
+use nostr_sdk_net::native::{HandshakeOptions, HeaderMap};
+
+use super::{FrameInterceptor, Limits, ModerationFilters, NotificationHub, SeenTracker};
 use crate::client::options::DEFAULT_SEND_TIMEOUT;
 
 pub const DEFAULT_RETRY_SEC: u64 = 10;
 pub const MIN_RETRY_SEC: u64 = 5;
 pub const MAX_ADJ_RETRY_SEC: u64 = 60;
+pub const DEFAULT_MAX_PENDING_REQS: usize = 10;
 
 /// [`Relay`](super::Relay) options
 #[derive(Debug, Clone)]
@@ -20,10 +26,21 @@ pub struct RelayOptions {
     /// Proxy
     #[cfg(not(target_arch = "wasm32"))]
     pub proxy: Option<SocketAddr>,
+    /// Additional headers of the WebSocket upgrade request
+    #[cfg(not(target_arch = "wasm32"))]
+    headers: HeaderMap,
+    /// Custom `User-Agent` of the WebSocket upgrade request
+    #[cfg(not(target_arch = "wasm32"))]
+    user_agent: Option<String>,
+    /// Keep the resolved addresses and the TLS sessions for the reconnections (default: true)
+    #[cfg(not(target_arch = "wasm32"))]
+    warm_standby: bool,
     /// Allow/disallow read actions (default: true)
     read: Arc<AtomicBool>,
     /// Allow/disallow write actions (default: true)
     write: Arc<AtomicBool>,
+    /// Labels used to route requests to a group of relays (default: none)
+    labels: Arc<RwLock<HashSet<String>>>,
     /// Enable/disable auto reconnection (default: true)
     reconnect: Arc<AtomicBool>,
     /// Retry connection time (default: 10 sec)
@@ -32,6 +49,21 @@ pub struct RelayOptions {
     retry_sec: Arc<AtomicU64>,
     /// Automatically adjust retry seconds based on success/attempts (default: true)
     adjust_retry_sec: Arc<AtomicBool>,
+    /// Messages and events limits (default: [`Limits::default`])
+    limits: Limits,
+    /// Bump `since` to the newest received event when resubscribing (default: false)
+    adjust_since_on_resubscribe: Arc<AtomicBool>,
+    /// Skip requests that need NIPs not advertised by the relay (default: false)
+    #[cfg(feature = "nip11")]
+    check_supported_nips: bool,
+    /// Fetch and count events locally if `COUNT` is not supported (default: true)
+    count_fallback: bool,
+    /// Max number of concurrent in-flight REQs sent by `get_events_of` and `req_events_of` (default: 10)
+    max_pending_reqs: usize,
+    /// Time for which the outgoing messages are held in the queue before sending (default: none)
+    send_delay: Option<Duration>,
+    /// Hook called on every received frame, before parsing (default: none)
+    frame_interceptor: Option<Arc<dyn FrameInterceptor>>,
 }
 
 impl Default for RelayOptions {
@@ -39,11 +71,26 @@ impl Default for RelayOptions {
         Self {
             #[cfg(not(target_arch = "wasm32"))]
             proxy: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            headers: HeaderMap::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            user_agent: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            warm_standby: true,
             read: Arc::new(AtomicBool::new(true)),
             write: Arc::new(AtomicBool::new(true)),
+            labels: Arc::new(RwLock::new(HashSet::new())),
             reconnect: Arc::new(AtomicBool::new(true)),
             retry_sec: Arc::new(AtomicU64::new(DEFAULT_RETRY_SEC)),
             adjust_retry_sec: Arc::new(AtomicBool::new(true)),
+            limits: Limits::default(),
+            adjust_since_on_resubscribe: Arc::new(AtomicBool::new(false)),
+            #[cfg(feature = "nip11")]
+            check_supported_nips: false,
+            count_fallback: true,
+            max_pending_reqs: DEFAULT_MAX_PENDING_REQS,
+            send_delay: None,
+            frame_interceptor: None,
         }
     }
 }
@@ -61,6 +108,51 @@ impl RelayOptions {
         self
     }
 
+    /// Set additional headers for the WebSocket handshake
+    ///
+    /// Useful for relays (or reverse proxies) that require custom headers.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn headers(self, headers: HeaderMap) -> Self {
+        Self { headers, ..self }
+    }
+
+    /// Set the `User-Agent` header of the WebSocket handshake
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn user_agent<S>(self, user_agent: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            user_agent: Some(user_agent.into()),
+            ..self
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn get_handshake(&self) -> HandshakeOptions {
+        HandshakeOptions {
+            headers: self.headers.clone(),
+            user_agent: self.user_agent.clone(),
+        }
+    }
+
+    /// Keep the resolved addresses and the TLS sessions between connections (default: true)
+    ///
+    /// The reconnections skip the DNS lookup and resume the previous TLS session.
+    /// If disabled, every connection starts from scratch.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn warm_standby(self, enable: bool) -> Self {
+        Self {
+            warm_standby: enable,
+            ..self
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn get_warm_standby(&self) -> bool {
+        self.warm_standby
+    }
+
     /// Set read option
     pub fn read(self, read: bool) -> Self {
         Self {
@@ -99,6 +191,50 @@ impl RelayOptions {
             .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |_| Some(write));
     }
 
+    /// Set labels (i.e. `dm`, `search`, `archive`)
+    ///
+    /// The label-scoped methods of the pool (i.e. [`RelayPool::send_event_to_label`](super::pool::RelayPool::send_event_to_label))
+    /// act only on the relays with the label.
+    pub fn labels<I, S>(self, labels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            labels: Arc::new(RwLock::new(labels.into_iter().map(|l| l.into()).collect())),
+            ..self
+        }
+    }
+
+    pub(crate) fn get_labels(&self) -> HashSet<String> {
+        self.labels
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    pub(crate) fn has_label(&self, label: &str) -> bool {
+        self.labels
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(label)
+    }
+
+    /// Add label
+    pub fn add_label<S>(&self, label: S)
+    where
+        S: Into<String>,
+    {
+        let mut labels = self.labels.write().unwrap_or_else(|e| e.into_inner());
+        labels.insert(label.into());
+    }
+
+    /// Remove label
+    pub fn remove_label(&self, label: &str) {
+        let mut labels = self.labels.write().unwrap_or_else(|e| e.into_inner());
+        labels.remove(label);
+    }
+
     /// Set reconnect option
     pub fn reconnect(self, reconnect: bool) -> Self {
         Self {
@@ -166,6 +302,117 @@ impl RelayOptions {
                 Some(adjust_retry_sec)
             });
     }
+
+    /// Set messages and events limits
+    ///
+    /// Useful to raise the default limits when talking to a self-hosted relay.
+    pub fn limits(self, limits: Limits) -> Self {
+        Self { limits, ..self }
+    }
+
+    pub(crate) fn get_limits(&self) -> Limits {
+        self.limits
+    }
+
+    /// Bump `since` of the filters to the timestamp of the newest event already received
+    /// when resubscribing after a reconnection (default: false)
+    ///
+    /// Avoid to re-download the whole history of the subscriptions at every reconnection.
+    pub fn adjust_since_on_resubscribe(self, value: bool) -> Self {
+        Self {
+            adjust_since_on_resubscribe: Arc::new(AtomicBool::new(value)),
+            ..self
+        }
+    }
+
+    pub(crate) fn get_adjust_since_on_resubscribe(&self) -> bool {
+        self.adjust_since_on_resubscribe.load(Ordering::SeqCst)
+    }
+
+    /// Set adjust_since_on_resubscribe option
+    pub fn update_adjust_since_on_resubscribe(&self, value: bool) {
+        let _ = self.adjust_since_on_resubscribe.fetch_update(
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+            |_| Some(value),
+        );
+    }
+
+    /// Consult the relay information document (NIP11) before sending `COUNT`, search (NIP50)
+    /// or negentropy requests, and skip them if the relay doesn't advertise support (default: false)
+    ///
+    /// Relays that don't publish the list of supported NIPs are always queried.
+    #[cfg(feature = "nip11")]
+    pub fn check_supported_nips(self, value: bool) -> Self {
+        Self {
+            check_supported_nips: value,
+            ..self
+        }
+    }
+
+    #[cfg(feature = "nip11")]
+    pub(crate) fn get_check_supported_nips(&self) -> bool {
+        self.check_supported_nips
+    }
+
+    /// Fetch and count events locally if the relay doesn't support `COUNT` (default: true)
+    ///
+    /// Used only if [`RelayOptions::check_supported_nips`] is enabled.
+    pub fn count_fallback(self, value: bool) -> Self {
+        Self {
+            count_fallback: value,
+            ..self
+        }
+    }
+
+    pub(crate) fn get_count_fallback(&self) -> bool {
+        self.count_fallback
+    }
+
+    /// Max number of concurrent in-flight REQs sent by `get_events_of` and `req_events_of` (default: 10)
+    ///
+    /// Additional requests are queued until one of the pending REQs is closed,
+    /// instead of exceeding the `max_subscriptions` of the relay. Values `<` 1 are ignored.
+    pub fn max_pending_reqs(self, max: usize) -> Self {
+        Self {
+            max_pending_reqs: max.max(1),
+            ..self
+        }
+    }
+
+    pub(crate) fn get_max_pending_reqs(&self) -> usize {
+        self.max_pending_reqs
+    }
+
+    /// Hold the outgoing messages in the queue for `delay` before sending them (default: none)
+    ///
+    /// Gives a grace window to cancel them (i.e. "undo send"), see [`Relay::cancel_queued_event`](super::Relay::cancel_queued_event).
+    /// Note that the methods waiting for the message to be sent (or for the `OK`) wait also for the delay.
+    pub fn send_delay(self, delay: Option<Duration>) -> Self {
+        Self {
+            send_delay: delay,
+            ..self
+        }
+    }
+
+    pub(crate) fn get_send_delay(&self) -> Option<Duration> {
+        self.send_delay
+    }
+
+    /// Set [`FrameInterceptor`], called on every frame received from the relay before it's processed
+    pub fn frame_interceptor<I>(self, interceptor: I) -> Self
+    where
+        I: FrameInterceptor + 'static,
+    {
+        Self {
+            frame_interceptor: Some(Arc::new(interceptor)),
+            ..self
+        }
+    }
+
+    pub(crate) fn get_frame_interceptor(&self) -> Option<Arc<dyn FrameInterceptor>> {
+        self.frame_interceptor.clone()
+    }
 }
 
 /// [`Relay`](super::Relay) send options
@@ -175,6 +422,8 @@ pub struct RelaySendOptions {
     pub skip_disconnected: bool,
     /// Timeout for sending event (default: 10 secs)
     pub timeout: Duration,
+    /// Min number of relays that must accept the event (default: 1)
+    pub min_success: usize,
 }
 
 impl Default for RelaySendOptions {
@@ -182,6 +431,7 @@ impl Default for RelaySendOptions {
         Self {
             skip_disconnected: true,
             timeout: DEFAULT_SEND_TIMEOUT,
+            min_success: 1,
         }
     }
 }
@@ -209,6 +459,16 @@ impl RelaySendOptions {
             ..self
         }
     }
+
+    /// Min number of relays that must accept the event with an `OK` message (default: 1)
+    ///
+    /// Used only when sending an event to multiple relays (i.e. [`RelayPool::send_event`](super::pool::RelayPool::send_event)).
+    pub fn min_success(self, value: usize) -> Self {
+        Self {
+            min_success: value,
+            ..self
+        }
+    }
 }
 
 /// Filter options
@@ -224,7 +484,7 @@ pub enum FilterOptions {
 }
 
 /// Relay Pool Options
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct RelayPoolOptions {
     /// Notification channel size (default: 1024)
     pub notification_channel_size: usize,
@@ -232,6 +492,27 @@ pub struct RelayPoolOptions {
     pub task_channel_size: usize,
     /// Shutdown on [RelayPool](super::pool::RelayPool) drop
     pub shutdown_on_drop: bool,
+    /// Relay health monitor (default: disabled)
+    pub health_monitor: Option<RelayHealthMonitorOptions>,
+    /// Relay information documents (NIP11) refresh interval (default: disabled)
+    #[cfg(feature = "nip11")]
+    pub document_refresh_interval: Option<Duration>,
+    /// Notify every copy of an event received from different relays (default: false)
+    pub notify_duplicates: bool,
+    /// Notify only the newest version of the replaceable and parameterized replaceable events (default: false)
+    pub only_latest_replaceable: bool,
+    /// Notify the matching events already stored into the database when subscribing (default: true)
+    pub warm_up: bool,
+    /// Number of recent events replayed to new notification listeners (default: 0, disabled)
+    pub replay_buffer_size: usize,
+    /// Shared seen events tracker (default: a new tracker for each pool)
+    pub seen_tracker: Option<SeenTracker>,
+    /// Shared notification hub (default: a new hub for each pool)
+    pub notification_hub: Option<NotificationHub>,
+    /// Max number of events failing the verification kept in quarantine (default: 0, disabled)
+    pub quarantine_size: usize,
+    /// Initial moderation filters of the received events (default: disabled)
+    pub moderation: ModerationFilters,
 }
 
 impl Default for RelayPoolOptions {
@@ -240,6 +521,17 @@ impl Default for RelayPoolOptions {
             notification_channel_size: 1024,
             task_channel_size: 1024,
             shutdown_on_drop: false,
+            health_monitor: None,
+            #[cfg(feature = "nip11")]
+            document_refresh_interval: None,
+            notify_duplicates: false,
+            only_latest_replaceable: false,
+            warm_up: true,
+            replay_buffer_size: 0,
+            seen_tracker: None,
+            notification_hub: None,
+            quarantine_size: 0,
+            moderation: ModerationFilters::default(),
         }
     }
 }
@@ -257,6 +549,195 @@ impl RelayPoolOptions {
             ..self
         }
     }
+
+    /// Enable the relay health monitor
+    ///
+    /// Chronically unhealthy relays are disconnected and periodically re-probed.
+    pub fn health_monitor(self, opts: RelayHealthMonitorOptions) -> Self {
+        Self {
+            health_monitor: Some(opts),
+            ..self
+        }
+    }
+
+    /// Periodically refresh the relay information documents (NIP11)
+    ///
+    /// A [`RelayPoolNotification::RelayPolicyChanged`](super::RelayPoolNotification::RelayPolicyChanged)
+    /// is sent when a relay starts requiring auth or payment, or lowers its limits.
+    #[cfg(feature = "nip11")]
+    pub fn refresh_documents(self, interval: Duration) -> Self {
+        Self {
+            document_refresh_interval: Some(interval),
+            ..self
+        }
+    }
+
+    /// Notify every copy of an event received from different relays (default: false)
+    ///
+    /// Useful for relay monitors and analytics. Events are still saved into the database only once:
+    /// use the `first_seen` field of [`RelayPoolNotification::Event`](super::RelayPoolNotification::Event) to distinguish the copies.
+    pub fn notify_duplicates(self, value: bool) -> Self {
+        Self {
+            notify_duplicates: value,
+            ..self
+        }
+    }
+
+    /// Notify only the newest version of the replaceable and parameterized replaceable events (default: false)
+    ///
+    /// The versions older than the last one notified for the same `(kind, pubkey, d tag)`
+    /// are not notified as [`RelayPoolNotification::Event`](super::RelayPoolNotification::Event).
+    /// The relay messages are still notified, so `get_events_of` and similar are not affected.
+    pub fn only_latest_replaceable(self, value: bool) -> Self {
+        Self {
+            only_latest_replaceable: value,
+            ..self
+        }
+    }
+
+    /// Notify the matching events already stored into the database when subscribing (default: true)
+    ///
+    /// The stored events are notified as [`RelayPoolNotification::CachedEvent`](super::RelayPoolNotification::CachedEvent)
+    /// before the relays reply, to render a feed instantly. The same events received later from the relays
+    /// are not notified again, unless [`RelayPoolOptions::notify_duplicates`] is enabled.
+    pub fn warm_up(self, value: bool) -> Self {
+        Self {
+            warm_up: value,
+            ..self
+        }
+    }
+
+    /// Keep the last `size` [`RelayPoolNotification::Event`](super::RelayPoolNotification::Event)s
+    /// and replay them to every new [`NotificationQueue`](super::NotificationQueue) (default: 0, disabled)
+    ///
+    /// Useful for listeners created after the connection, that would miss the events received during the startup.
+    pub fn replay_buffer(self, size: usize) -> Self {
+        Self {
+            replay_buffer_size: size,
+            ..self
+        }
+    }
+
+    /// Share the [`SeenTracker`] with other pools
+    ///
+    /// An event already seen by a pool sharing the tracker is handled as a duplicate:
+    /// it's saved into the database but not notified again (unless [`RelayPoolOptions::notify_duplicates`] is enabled).
+    pub fn seen_tracker(self, tracker: SeenTracker) -> Self {
+        Self {
+            seen_tracker: Some(tracker),
+            ..self
+        }
+    }
+
+    /// Send the notifications to a shared [`NotificationHub`]
+    ///
+    /// The [`RelayPoolOptions::notification_channel_size`] is ignored: the capacity is the one of the hub.
+    pub fn notification_hub(self, hub: NotificationHub) -> Self {
+        Self {
+            notification_hub: Some(hub),
+            ..self
+        }
+    }
+
+    /// Keep the last `size` events failing the id or signature verification (default: 0, disabled)
+    ///
+    /// The events are still rejected: inspect them with [`RelayPool::quarantine`](super::pool::RelayPool::quarantine).
+    /// The rejections are always counted in the [`RelayPoolStats`](super::RelayPoolStats).
+    pub fn quarantine(self, size: usize) -> Self {
+        Self {
+            quarantine_size: size,
+            ..self
+        }
+    }
+
+    /// Drop the received events matching the moderation `filters`
+    ///
+    /// The filters can be changed later with [`Moderation::set_filters`](super::Moderation::set_filters).
+    pub fn moderation(self, filters: ModerationFilters) -> Self {
+        Self {
+            moderation: filters,
+            ..self
+        }
+    }
+}
+
+/// Relay health monitor options
+#[derive(Debug, Clone, Copy)]
+pub struct RelayHealthMonitorOptions {
+    /// Check interval (default: 60 secs)
+    pub interval: Duration,
+    /// Max errors allowed between two checks (default: 10)
+    pub max_errors: usize,
+    /// Max disconnections allowed between two checks (default: 3)
+    pub max_disconnections: usize,
+    /// Max average time between `REQ` and `EOSE` (default: 10 secs)
+    pub max_eose_latency: Duration,
+    /// Consecutive unhealthy checks before demoting a relay (default: 3)
+    pub unhealthy_checks: usize,
+    /// Time after which a demoted relay is re-probed (default: 10 min)
+    pub reprobe_after: Duration,
+}
+
+impl Default for RelayHealthMonitorOptions {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(60),
+            max_errors: 10,
+            max_disconnections: 3,
+            max_eose_latency: Duration::from_secs(10),
+            unhealthy_checks: 3,
+            reprobe_after: Duration::from_secs(600),
+        }
+    }
+}
+
+impl RelayHealthMonitorOptions {
+    /// New default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check interval (default: 60 secs)
+    pub fn interval(self, interval: Duration) -> Self {
+        Self { interval, ..self }
+    }
+
+    /// Max errors allowed between two checks (default: 10)
+    pub fn max_errors(self, max_errors: usize) -> Self {
+        Self { max_errors, ..self }
+    }
+
+    /// Max disconnections allowed between two checks (default: 3)
+    pub fn max_disconnections(self, max_disconnections: usize) -> Self {
+        Self {
+            max_disconnections,
+            ..self
+        }
+    }
+
+    /// Max average time between `REQ` and `EOSE` (default: 10 secs)
+    pub fn max_eose_latency(self, max_eose_latency: Duration) -> Self {
+        Self {
+            max_eose_latency,
+            ..self
+        }
+    }
+
+    /// Consecutive unhealthy checks before demoting a relay (default: 3)
+    pub fn unhealthy_checks(self, unhealthy_checks: usize) -> Self {
+        Self {
+            unhealthy_checks: unhealthy_checks.max(1),
+            ..self
+        }
+    }
+
+    /// Time after which a demoted relay is re-probed (default: 10 min)
+    pub fn reprobe_after(self, reprobe_after: Duration) -> Self {
+        Self {
+            reprobe_after,
+            ..self
+        }
+    }
 }
 
 /// Negentropy reconciliation options
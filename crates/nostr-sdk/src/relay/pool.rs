@@ -4,14 +4,21 @@
 
 //! Relay Pool
 
-use std::collections::{HashMap, HashSet};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex as StdMutex};
 use std::time::Duration;
 
-use async_utility::thread;
+use async_utility::futures_util::stream::AbortHandle;
+use async_utility::{thread, time};
+#[cfg(feature = "nip59")]
+use nostr::key::XOnlyPublicKey;
 use nostr::message::MessageHandleError;
 use nostr::nips::nip01::Coordinate;
+#[cfg(feature = "nip59")]
+use nostr::UnsignedEvent;
 use nostr::{
     event, ClientMessage, Event, EventId, Filter, JsonUtil, MissingPartialEvent, PartialEvent,
     RawRelayMessage, RelayMessage, SubscriptionId, Timestamp, Url,
@@ -21,10 +28,15 @@ use thiserror::Error;
 use tokio::sync::mpsc::{self, Receiver, Sender};
 use tokio::sync::{broadcast, Mutex, RwLock};
 
+#[cfg(feature = "nip11")]
+use super::document::{RelayDocumentMonitor, RelayPolicyChange};
+use super::health::{RelayDemotionReason, RelayHealthMonitor};
 use super::options::RelayPoolOptions;
 use super::{
-    Error as RelayError, FilterOptions, InternalSubscriptionId, Limits, NegentropyOptions, Relay,
-    RelayOptions, RelaySendOptions, RelayStatus,
+    Error as RelayError, FilterOptions, InternalSubscriptionId, Middlewares, Moderation,
+    NegentropyOptions, NotificationHub, Quarantine, QuarantineReason, QuarantinedEvent,
+    QueuedMessage, Relay, RelayConnectionStats, RelayOptions, RelayPoolStats, RelaySendOptions,
+    RelayStatus, SeenTracker,
 };
 use crate::util::TryIntoUrl;
 
@@ -67,6 +79,18 @@ pub enum Error {
     /// Events not published
     #[error("events not published")]
     EventsNotPublished,
+    /// Event not accepted by enough relays
+    #[error("event {event_id} accepted by {} relays, {min_success} required", accepted.len())]
+    QuorumNotReached {
+        /// Event ID
+        event_id: EventId,
+        /// Min number of relays required
+        min_success: usize,
+        /// Relays that accepted the event
+        accepted: Vec<Url>,
+        /// Relays that rejected the event (or that failed), with the reason
+        rejected: HashMap<Url, String>,
+    },
     /// Relay not found
     #[error("relay not found")]
     RelayNotFound,
@@ -84,6 +108,10 @@ pub enum RelayPoolMessage {
         relay_url: Url,
         /// Relay message
         msg: RawRelayMessage,
+        /// Internal ID of the subscription (only for events)
+        internal_subscription_id: Option<InternalSubscriptionId>,
+        /// Receipt timestamp
+        received_at: Timestamp,
     },
     /// Relay status changed
     RelayStatus {
@@ -99,21 +127,62 @@ pub enum RelayPoolMessage {
 }
 
 /// Relay Pool Notification
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RelayPoolNotification {
     /// Received an [`Event`]. Does not include events sent by this client.
+    ///
+    /// By default, each event is notified only once, when received for the first time.
+    /// See [`RelayPoolOptions::notify_duplicates`] to be notified of every copy.
     Event {
         /// Relay url
         relay_url: Url,
         /// Event
         event: Event,
+        /// Subscription ID
+        subscription_id: SubscriptionId,
+        /// Internal ID of the subscription, if the event has been received by a subscription
+        /// managed by the client (i.e. `None` for [`RelayPool::get_events_of`])
+        internal_subscription_id: Option<InternalSubscriptionId>,
+        /// `true` if it's the first time that the event is received (from any relay)
+        first_seen: bool,
+        /// Sequence number of the [`RelayPoolNotification::Message`] wrapping this event
+        sequence: u64,
+        /// When the event has been received
+        received_at: Timestamp,
+    },
+    /// Event already stored into the database, matching the filters of a new subscription
+    ///
+    /// Notified before the relay results (see [`RelayPoolOptions::warm_up`]).
+    CachedEvent {
+        /// Event
+        event: Event,
+        /// Internal ID of the subscription
+        internal_subscription_id: InternalSubscriptionId,
     },
     /// Received a [`RelayMessage`]. Includes messages wrapping events that were sent by this client.
+    ///
+    /// Messages of the same relay are numbered with contiguous sequence numbers, starting from `1`:
+    /// a gap means that some notifications have been dropped (i.e. lagged receiver)
+    /// and that the missed events should be re-queried from the database.
     Message {
         /// Relay url
         relay_url: Url,
         /// Relay Message
         message: RelayMessage,
+        /// Per-relay sequence number
+        sequence: u64,
+        /// When the message has been received
+        received_at: Timestamp,
+    },
+    /// A message received from a relay has been rejected and not processed
+    MessageRejected {
+        /// Relay url
+        relay_url: Url,
+        /// Reason
+        reason: MessageRejectedReason,
+        /// Size of the rejected message or event, in bytes
+        size: usize,
     },
     /// Relay status changed
     RelayStatus {
@@ -122,18 +191,301 @@ pub enum RelayPoolNotification {
         /// Relay Status
         status: RelayStatus,
     },
+    /// Relay demoted (stopped) by the health monitor
+    RelayDemoted {
+        /// Relay url
+        relay_url: Url,
+        /// Reason
+        reason: RelayDemotionReason,
+    },
+    /// Relay policy changed (see [`RelayPoolOptions::refresh_documents`])
+    #[cfg(feature = "nip11")]
+    RelayPolicyChanged {
+        /// Relay url
+        relay_url: Url,
+        /// Changes
+        changes: Vec<RelayPolicyChange>,
+    },
+    /// Private message, i.e. the rumor of a gift wrap addressed to the client signer
+    ///
+    /// Notified only if [`Options::unwrap_gift_wraps`](crate::Options::unwrap_gift_wraps) is enabled.
+    #[cfg(feature = "nip59")]
+    PrivateMessage {
+        /// Relay url
+        relay_url: Url,
+        /// Gift wrap ID
+        gift_wrap_id: EventId,
+        /// Sender
+        sender: XOnlyPublicKey,
+        /// Rumor
+        rumor: UnsignedEvent,
+    },
+    /// The listener was too slow and missed `n` notifications
+    ///
+    /// Emitted by [`NotificationQueue`] and [`Client::handle_notifications`](crate::Client::handle_notifications).
+    Lagged(u64),
     /// Stop
     Stop,
     /// Shutdown
     Shutdown,
 }
 
+/// Notification listener backed by an unbounded queue
+///
+/// The notifications are moved from the pool broadcast channel to a queue owned by this listener,
+/// so a slow consumer doesn't miss notifications (at the cost of memory usage).
+#[derive(Debug)]
+pub struct NotificationQueue {
+    receiver: mpsc::UnboundedReceiver<RelayPoolNotification>,
+}
+
+impl NotificationQueue {
+    fn new(
+        mut notifications: broadcast::Receiver<RelayPoolNotification>,
+        replay: Vec<RelayPoolNotification>,
+    ) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        for notification in replay.into_iter() {
+            let _ = sender.send(notification);
+        }
+        thread::spawn(async move {
+            loop {
+                let notification: RelayPoolNotification = match notifications.recv().await {
+                    Ok(notification) => notification,
+                    Err(broadcast::error::RecvError::Lagged(n)) => RelayPoolNotification::Lagged(n),
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                let shutdown: bool = notification == RelayPoolNotification::Shutdown;
+                if sender.send(notification).is_err() || shutdown {
+                    break;
+                }
+            }
+        });
+        Self { receiver }
+    }
+
+    /// Receive the next notification
+    ///
+    /// Return `None` after the pool shutdown.
+    pub async fn recv(&mut self) -> Option<RelayPoolNotification> {
+        self.receiver.recv().await
+    }
+
+    /// Receive the next notification, if any, without waiting
+    pub fn try_recv(&mut self) -> Option<RelayPoolNotification> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+/// Last notified events, replayed to new [`NotificationQueue`]s
+#[derive(Debug, Clone)]
+struct ReplayBuffer {
+    events: Arc<StdMutex<VecDeque<RelayPoolNotification>>>,
+    size: usize,
+}
+
+impl ReplayBuffer {
+    fn new(size: usize) -> Self {
+        Self {
+            events: Arc::new(StdMutex::new(VecDeque::with_capacity(size))),
+            size,
+        }
+    }
+
+    /// Send the notification and keep it in the buffer
+    fn send(
+        &self,
+        sender: &broadcast::Sender<RelayPoolNotification>,
+        notification: RelayPoolNotification,
+    ) {
+        if self.size == 0 {
+            let _ = sender.send(notification);
+            return;
+        }
+
+        // Keep the lock while sending, so a new listener never gets the same notification twice
+        let mut events = self.events.lock().unwrap_or_else(|e| e.into_inner());
+        if events.len() >= self.size {
+            events.pop_front();
+        }
+        events.push_back(notification.clone());
+        let _ = sender.send(notification);
+    }
+
+    /// Subscribe to the notifications and get the events to replay
+    fn subscribe(
+        &self,
+        sender: &broadcast::Sender<RelayPoolNotification>,
+    ) -> (
+        broadcast::Receiver<RelayPoolNotification>,
+        Vec<RelayPoolNotification>,
+    ) {
+        let events = self.events.lock().unwrap_or_else(|e| e.into_inner());
+        (sender.subscribe(), events.iter().cloned().collect())
+    }
+}
+
+/// Why a relay message has been rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageRejectedReason {
+    /// The message exceeds the [`Limits`](super::Limits) messages max size
+    MessageTooLarge {
+        /// Max allowed size, in bytes
+        max_size: usize,
+    },
+    /// The event exceeds the [`Limits`](super::Limits) events max size for its kind
+    EventTooLarge {
+        /// Max allowed size, in bytes
+        max_size: usize,
+    },
+}
+
+impl fmt::Display for MessageRejectedReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MessageTooLarge { max_size } => {
+                write!(f, "message too large (max_size={max_size})")
+            }
+            Self::EventTooLarge { max_size } => write!(f, "event too large (max_size={max_size})"),
+        }
+    }
+}
+
+/// Messages not flushed before the shutdown
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// Number of messages still in the queue of each relay
+    pub queued: HashMap<Url, usize>,
+    /// Events sent to each relay without receiving the `OK`
+    pub unconfirmed: HashMap<Url, Vec<EventId>>,
+}
+
+impl ShutdownReport {
+    /// Check if all messages have been flushed
+    pub fn is_empty(&self) -> bool {
+        self.queued.is_empty() && self.unconfirmed.is_empty()
+    }
+}
+
+/// Outcome of the connection to the relays
+///
+/// # Example
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// use nostr_sdk::prelude::*;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// #   let client = Client::new(&Keys::generate());
+/// #   client.add_relay("wss://relay.damus.io").await.unwrap();
+/// let report = client.connect_with_timeout(Duration::from_secs(5)).await;
+/// for (url, error) in report.failed.iter() {
+///     println!("Impossible to connect to {url}: {error}");
+/// }
+/// if !report.pending.is_empty() {
+///     println!("Still connecting to {} relays", report.pending.len());
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectReport {
+    /// Connected relays
+    pub connected: HashSet<Url>,
+    /// Relays whose last connection attempt failed, with the error
+    pub failed: HashMap<Url, String>,
+    /// Relays still connecting (or not yet attempted)
+    pub pending: HashSet<Url>,
+}
+
+impl ConnectReport {
+    /// Check if all relays are connected
+    pub fn is_all_connected(&self) -> bool {
+        self.failed.is_empty() && self.pending.is_empty()
+    }
+}
+
+/// Result of a `COUNT` sent to multiple relays
+///
+/// # Example
+/// ```rust
+/// use nostr_sdk::prelude::*;
+///
+/// let mut report = CountReport::default();
+/// report.counts.insert(Url::parse("wss://relay.damus.io").unwrap(), 1200);
+/// report.counts.insert(Url::parse("wss://nos.lol").unwrap(), 1150);
+/// report.counts.insert(Url::parse("wss://relay.nostr.band").unwrap(), 600);
+///
+/// assert_eq!(report.estimate(), Some(1150));
+/// assert!(report.relays_disagree(0.1));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CountReport {
+    /// Count returned by each relay
+    ///
+    /// Relays that failed or didn't reply before the timeout are not included.
+    pub counts: HashMap<Url, usize>,
+}
+
+impl CountReport {
+    /// Merged estimate (median of the counts)
+    ///
+    /// Return `None` if no relay replied.
+    pub fn estimate(&self) -> Option<usize> {
+        let mut counts: Vec<usize> = self.counts.values().copied().collect();
+        if counts.is_empty() {
+            return None;
+        }
+        counts.sort_unstable();
+        let mid: usize = counts.len() / 2;
+        if counts.len() % 2 == 0 {
+            Some((counts[mid - 1] + counts[mid]) / 2)
+        } else {
+            Some(counts[mid])
+        }
+    }
+
+    /// Lowest count
+    pub fn min(&self) -> Option<usize> {
+        self.counts.values().min().copied()
+    }
+
+    /// Highest count
+    pub fn max(&self) -> Option<usize> {
+        self.counts.values().max().copied()
+    }
+
+    /// Difference between the highest and the lowest count, relative to the highest one (`0.0..=1.0`)
+    pub fn spread(&self) -> f64 {
+        match (self.min(), self.max()) {
+            (Some(min), Some(max)) if max > 0 => (max - min) as f64 / max as f64,
+            _ => 0.0,
+        }
+    }
+
+    /// Check if the relays disagree, i.e. if the [`CountReport::spread`] is greater than `tolerance`
+    ///
+    /// For example, with a `tolerance` of `0.1` the counts can differ up to the 10%.
+    pub fn relays_disagree(&self, tolerance: f64) -> bool {
+        self.spread() > tolerance
+    }
+}
+
 #[derive(Debug, Clone)]
 struct RelayPoolTask {
     database: Arc<DynNostrDatabase>,
     receiver: Arc<Mutex<Receiver<RelayPoolMessage>>>,
     notification_sender: broadcast::Sender<RelayPoolNotification>,
     running: Arc<AtomicBool>,
+    notify_duplicates: bool,
+    only_latest_replaceable: bool,
+    latest_replaceable: Arc<Mutex<HashMap<Coordinate, (Timestamp, EventId)>>>,
+    stats: RelayPoolStats,
+    replay: ReplayBuffer,
+    seen: SeenTracker,
+    quarantine: Quarantine,
+    moderation: Moderation,
+    middlewares: Middlewares,
 }
 
 impl RelayPoolTask {
@@ -141,12 +493,25 @@ impl RelayPoolTask {
         database: Arc<DynNostrDatabase>,
         pool_task_receiver: Receiver<RelayPoolMessage>,
         notification_sender: broadcast::Sender<RelayPoolNotification>,
+        opts: &RelayPoolOptions,
+        stats: RelayPoolStats,
+        replay: ReplayBuffer,
+        seen: SeenTracker,
     ) -> Self {
         Self {
             database,
             receiver: Arc::new(Mutex::new(pool_task_receiver)),
             notification_sender,
             running: Arc::new(AtomicBool::new(false)),
+            notify_duplicates: opts.notify_duplicates,
+            only_latest_replaceable: opts.only_latest_replaceable,
+            latest_replaceable: Arc::new(Mutex::new(HashMap::new())),
+            stats,
+            replay,
+            seen,
+            quarantine: Quarantine::new(opts.quarantine_size),
+            moderation: Moderation::new(opts.moderation.clone()),
+            middlewares: Middlewares::default(),
         }
     }
 
@@ -169,15 +534,36 @@ impl RelayPoolTask {
             let this = self.clone();
             thread::spawn(async move {
                 let mut receiver = this.receiver.lock().await;
+                let mut sequences: HashMap<Url, u64> = HashMap::new();
                 while let Some(msg) = receiver.recv().await {
                     match msg {
-                        RelayPoolMessage::ReceivedMsg { relay_url, msg } => {
-                            match this.handle_relay_message(relay_url.clone(), msg).await {
+                        RelayPoolMessage::ReceivedMsg {
+                            relay_url,
+                            msg,
+                            internal_subscription_id,
+                            received_at,
+                        } => {
+                            // Assigned only if the message is delivered, to keep the sequence contiguous
+                            let sequence: u64 =
+                                sequences.get(&relay_url).copied().unwrap_or_default() + 1;
+                            match this
+                                .handle_relay_message(
+                                    relay_url.clone(),
+                                    msg,
+                                    internal_subscription_id,
+                                    sequence,
+                                    received_at,
+                                )
+                                .await
+                            {
                                 Ok(Some(msg)) => {
+                                    sequences.insert(relay_url.clone(), sequence);
                                     let _ = this.notification_sender.send(
                                         RelayPoolNotification::Message {
                                             relay_url: relay_url.clone(),
                                             message: msg.clone(),
+                                            sequence,
+                                            received_at,
                                         },
                                     );
 
@@ -241,12 +627,17 @@ impl RelayPoolTask {
         &self,
         relay_url: Url,
         msg: RawRelayMessage,
+        internal_subscription_id: Option<InternalSubscriptionId>,
+        sequence: u64,
+        received_at: Timestamp,
     ) -> Result<Option<RelayMessage>, Error> {
         match msg {
             RawRelayMessage::Event {
                 subscription_id,
                 event,
             } => {
+                self.stats.new_event_received();
+
                 // Deserialize partial event (id, pubkey and sig)
                 let partial_event: PartialEvent = PartialEvent::from_json(event.to_string())?;
 
@@ -286,11 +677,13 @@ impl RelayPoolTask {
                     }
                 }
 
-                // Check if event id was already seen
-                let seen: bool = self
-                    .database
-                    .has_event_already_been_seen(&partial_event.id)
-                    .await?;
+                // Check if event id was already seen (also by the pools sharing the tracker)
+                let first_time: bool = self.seen.insert(partial_event.id);
+                let seen: bool = !first_time
+                    || self
+                        .database
+                        .has_event_already_been_seen(&partial_event.id)
+                        .await?;
 
                 // Set event as seen by relay
                 if let Err(e) = self
@@ -305,11 +698,14 @@ impl RelayPoolTask {
                 }
 
                 // Check if event was already saved
-                if self
+                let saved: bool = self
                     .database
                     .has_event_already_been_saved(&partial_event.id)
-                    .await?
-                {
+                    .await?;
+                if saved {
+                    self.stats.new_duplicate_event();
+                }
+                if saved && !self.notify_duplicates {
                     tracing::trace!("Event {} already saved into database", partial_event.id);
                     return Ok(None);
                 }
@@ -323,28 +719,89 @@ impl RelayPoolTask {
                 }
 
                 // Verify event
-                event.verify()?;
+                if let Err(e) = event.verify() {
+                    let reason = QuarantineReason::from(&e);
+                    self.stats.new_invalid_event(reason);
+                    self.quarantine.insert(QuarantinedEvent {
+                        event,
+                        relay_url,
+                        reason,
+                        received_at,
+                    });
+                    return Err(Error::Event(e));
+                }
+
+                // Apply moderation filters
+                if !self.moderation.is_allowed(&event) {
+                    return Ok(None);
+                }
+
+                // Run middlewares
+                if !self.middlewares.after_receive(&relay_url, &event).await {
+                    tracing::trace!("Event {} dropped by middleware", event.id);
+                    return Ok(None);
+                }
 
                 // Save event
-                self.database.save_event(&event).await?;
+                if !saved {
+                    self.database.save_event(&event).await?;
+                }
 
-                // If not seen, send RelayPoolNotification::Event
-                if !seen {
-                    let _ = self.notification_sender.send(RelayPoolNotification::Event {
-                        relay_url,
-                        event: event.clone(),
-                    });
+                let subscription_id = SubscriptionId::new(subscription_id);
+
+                // If not seen (or if duplicates must be notified), send RelayPoolNotification::Event
+                if (!seen || self.notify_duplicates) && self.is_latest_replaceable(&event).await {
+                    self.replay.send(
+                        &self.notification_sender,
+                        RelayPoolNotification::Event {
+                            relay_url,
+                            event: event.clone(),
+                            subscription_id: subscription_id.clone(),
+                            internal_subscription_id,
+                            first_seen: !seen,
+                            sequence,
+                            received_at,
+                        },
+                    );
                 }
 
                 // Compose RelayMessage
                 Ok(Some(RelayMessage::Event {
-                    subscription_id: SubscriptionId::new(subscription_id),
+                    subscription_id,
                     event: Box::new(event),
                 }))
             }
             m => Ok(Some(RelayMessage::try_from(m)?)),
         }
     }
+
+    /// Check if the event is the newest version of its coordinate received so far
+    ///
+    /// Always `true` for regular events or if [`RelayPoolOptions::only_latest_replaceable`] is disabled.
+    async fn is_latest_replaceable(&self, event: &Event) -> bool {
+        if !self.only_latest_replaceable
+            || !(event.kind.is_replaceable() || event.kind.is_parameterized_replaceable())
+        {
+            return true;
+        }
+
+        let coordinate: Coordinate = Coordinate::new(event.kind, event.pubkey)
+            .identifier(event.identifier().unwrap_or_default());
+        let mut latest = self.latest_replaceable.lock().await;
+        match latest.get(&coordinate) {
+            // On the same timestamp, the lowest ID wins (NIP01)
+            Some((created_at, id))
+                if (*created_at, Reverse(*id)) > (event.created_at, Reverse(event.id)) =>
+            {
+                tracing::trace!("Event {} replaced by {id}", event.id);
+                false
+            }
+            _ => {
+                latest.insert(coordinate, (event.created_at, event.id));
+                true
+            }
+        }
+    }
 }
 
 /// Relay Pool
@@ -357,6 +814,10 @@ pub struct RelayPool {
     filters: Arc<RwLock<Vec<Filter>>>,
     pool_task: RelayPoolTask,
     opts: RelayPoolOptions,
+    stats: RelayPoolStats,
+    replay: ReplayBuffer,
+    seen: SeenTracker,
+    health_monitor: Arc<StdMutex<Option<AbortHandle>>>,
     dropped: Arc<AtomicBool>,
 }
 
@@ -392,15 +853,25 @@ impl RelayPool {
     where
         D: IntoNostrDatabase,
     {
-        let (notification_sender, _) = broadcast::channel(opts.notification_channel_size);
+        let notification_sender = match &opts.notification_hub {
+            Some(hub) => hub.sender(),
+            None => broadcast::channel(opts.notification_channel_size).0,
+        };
         let (pool_task_sender, pool_task_receiver) = mpsc::channel(opts.task_channel_size);
 
         let database: Arc<DynNostrDatabase> = database.into_nostr_database();
+        let stats = RelayPoolStats::new(notification_sender.clone());
+        let replay = ReplayBuffer::new(opts.replay_buffer_size);
+        let seen: SeenTracker = opts.seen_tracker.clone().unwrap_or_default();
 
         let relay_pool_task = RelayPoolTask::new(
             database.clone(),
             pool_task_receiver,
             notification_sender.clone(),
+            &opts,
+            stats.clone(),
+            replay.clone(),
+            seen.clone(),
         );
 
         let pool = Self {
@@ -411,6 +882,10 @@ impl RelayPool {
             filters: Arc::new(RwLock::new(Vec::new())),
             pool_task: relay_pool_task,
             opts,
+            stats,
+            replay,
+            seen,
+            health_monitor: Arc::new(StdMutex::new(None)),
             dropped: Arc::new(AtomicBool::new(false)),
         };
 
@@ -421,6 +896,38 @@ impl RelayPool {
 
     /// Start [`RelayPoolTask`]
     pub fn start(&self) {
+        if !self.is_running() {
+            if let Some(opts) = self.opts.health_monitor {
+                let pool_task = self.pool_task.clone();
+                let handle: AbortHandle = RelayHealthMonitor::new(
+                    self.relays.clone(),
+                    self.notification_sender.clone(),
+                    opts,
+                )
+                .run(move || pool_task.is_running());
+
+                // The monitor of a previous run may still be sleeping: abort it
+                let mut health_monitor = self
+                    .health_monitor
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner());
+                if let Some(previous) = health_monitor.replace(handle) {
+                    previous.abort();
+                }
+            }
+
+            #[cfg(feature = "nip11")]
+            if let Some(interval) = self.opts.document_refresh_interval {
+                let pool_task = self.pool_task.clone();
+                RelayDocumentMonitor::new(
+                    self.relays.clone(),
+                    self.notification_sender.clone(),
+                    interval,
+                )
+                .run(move || pool_task.is_running());
+            }
+        }
+
         self.pool_task.run();
     }
 
@@ -442,20 +949,112 @@ impl RelayPool {
     }
 
     /// Completely shutdown pool
+    ///
+    /// The pending messages are not waited: use [`RelayPool::shutdown_with_timeout`] to flush them first.
     pub async fn shutdown(self) -> Result<(), Error> {
         self.disconnect().await?;
-        thread::spawn(async move {
-            thread::sleep(Duration::from_secs(3)).await;
-            let _ = self.pool_task_sender.send(RelayPoolMessage::Shutdown).await;
-        });
+        let _ = self.pool_task_sender.send(RelayPoolMessage::Shutdown).await;
         Ok(())
     }
 
+    /// Completely shutdown pool, after flushing the pending messages
+    ///
+    /// Wait (up to `timeout`) for the queued messages to be sent and for the `OK` of the sent events,
+    /// then disconnect from all relays. Return what couldn't be flushed.
+    pub async fn shutdown_with_timeout(self, timeout: Duration) -> Result<ShutdownReport, Error> {
+        let relays = self.relays().await;
+
+        let mut handles = Vec::with_capacity(relays.len());
+        for relay in relays.values() {
+            let relay = relay.clone();
+            if let Some(handle) = thread::spawn(async move { relay.wait_for_flush(timeout).await })
+            {
+                handles.push(handle);
+            }
+        }
+        for handle in handles.into_iter() {
+            handle.join().await?;
+        }
+
+        let mut report = ShutdownReport::default();
+        for (url, relay) in relays.iter() {
+            let queued: usize = relay.queue();
+            if queued > 0 {
+                report.queued.insert(url.clone(), queued);
+            }
+
+            let unconfirmed: Vec<EventId> = relay.pending_ok().await;
+            if !unconfirmed.is_empty() {
+                report.unconfirmed.insert(url.clone(), unconfirmed);
+            }
+        }
+
+        if !report.is_empty() {
+            tracing::warn!(
+                "Shutdown with {} queued messages and {} unconfirmed events",
+                report.queued.values().sum::<usize>(),
+                report
+                    .unconfirmed
+                    .values()
+                    .map(|ids| ids.len())
+                    .sum::<usize>()
+            );
+        }
+
+        self.disconnect().await?;
+        let _ = self.pool_task_sender.send(RelayPoolMessage::Shutdown).await;
+
+        Ok(report)
+    }
+
     /// Get new notification listener
     pub fn notifications(&self) -> broadcast::Receiver<RelayPoolNotification> {
         self.notification_sender.subscribe()
     }
 
+    /// Get new notification listener that never lags (see [`NotificationQueue`])
+    ///
+    /// The events kept by the replay buffer (see [`RelayPoolOptions::replay_buffer`]) are received first.
+    pub fn notification_queue(&self) -> NotificationQueue {
+        let (notifications, replay) = self.replay.subscribe(&self.notification_sender);
+        NotificationQueue::new(notifications, replay)
+    }
+
+    /// Get [`NotificationHub`]
+    ///
+    /// Pass it to [`RelayPoolOptions::notification_hub`] to send the notifications of another pool to the same listeners.
+    pub fn notification_hub(&self) -> NotificationHub {
+        NotificationHub::from_sender(self.notification_sender.clone())
+    }
+
+    /// Get [`SeenTracker`]
+    ///
+    /// Pass it to [`RelayPoolOptions::seen_tracker`] to share the deduplication with another pool.
+    pub fn seen_tracker(&self) -> SeenTracker {
+        self.seen.clone()
+    }
+
+    /// Get the [`Quarantine`] of the events failing the verification
+    ///
+    /// Empty if not enabled with [`RelayPoolOptions::quarantine`].
+    pub fn quarantine(&self) -> Quarantine {
+        self.pool_task.quarantine.clone()
+    }
+
+    /// Get the [`Moderation`] of the received events
+    pub fn moderation(&self) -> Moderation {
+        self.pool_task.moderation.clone()
+    }
+
+    pub(crate) fn middlewares(&self) -> &Middlewares {
+        &self.pool_task.middlewares
+    }
+
+    /// Get [`RelayPoolStats`]
+    pub fn stats(&self) -> RelayPoolStats {
+        self.stats.clone()
+    }
+
     /// Get database
     pub fn database(&self) -> Arc<DynNostrDatabase> {
         self.database.clone()
@@ -467,6 +1066,40 @@ impl RelayPool {
         relays.clone()
     }
 
+    /// Get relays with the `label` (see [`RelayOptions::labels`])
+    pub async fn relays_with_label(&self, label: &str) -> HashMap<Url, Relay> {
+        let relays = self.relays.read().await;
+        relays
+            .iter()
+            .filter(|(_, relay)| relay.has_label(label))
+            .map(|(url, relay)| (url.clone(), relay.clone()))
+            .collect()
+    }
+
+    /// Get the messages waiting in the outgoing queue of every relay
+    ///
+    /// The relays with an empty queue are not included.
+    pub async fn queued_messages(&self) -> HashMap<Url, Vec<QueuedMessage>> {
+        let relays = self.relays.read().await;
+        relays
+            .iter()
+            .map(|(url, relay)| (url.clone(), relay.queued_messages()))
+            .filter(|(_, msgs)| !msgs.is_empty())
+            .collect()
+    }
+
+    /// Cancel the queued `EVENT` messages of `event_id` in every relay
+    ///
+    /// Return the relays from which the event has been removed before being sent.
+    pub async fn cancel_queued_event(&self, event_id: EventId) -> HashSet<Url> {
+        let relays = self.relays.read().await;
+        relays
+            .iter()
+            .filter(|(_, relay)| relay.cancel_queued_event(event_id))
+            .map(|(url, _)| url.clone())
+            .collect()
+    }
+
     /// Get [`Relay`]
     pub async fn relay<U>(&self, url: U) -> Result<Relay, Error>
     where
@@ -484,11 +1117,22 @@ impl RelayPool {
     }
 
     /// Update subscription filters
-    async fn update_subscription_filters(&self, filters: Vec<Filter>) {
+    pub(crate) async fn update_subscription_filters(&self, filters: Vec<Filter>) {
         let mut f = self.filters.write().await;
         *f = filters;
     }
 
+    fn internal_new_relay(&self, url: Url, opts: RelayOptions) -> Relay {
+        Relay::with_stats(
+            url,
+            self.database.clone(),
+            self.pool_task_sender.clone(),
+            self.notification_sender.clone(),
+            opts,
+            RelayConnectionStats::with_pool_stats(self.stats.clone()),
+        )
+    }
+
     /// Add new relay
     pub async fn add_relay<U>(&self, url: U, opts: RelayOptions) -> Result<bool, Error>
     where
@@ -498,14 +1142,7 @@ impl RelayPool {
         let url: Url = url.try_into_url()?;
         let mut relays = self.relays.write().await;
         if !relays.contains_key(&url) {
-            let relay = Relay::new(
-                url,
-                self.database.clone(),
-                self.pool_task_sender.clone(),
-                self.notification_sender.clone(),
-                opts,
-                Limits::default(),
-            );
+            let relay = self.internal_new_relay(url, opts);
             relays.insert(relay.url(), relay);
             Ok(true)
         } else {
@@ -513,6 +1150,21 @@ impl RelayPool {
         }
     }
 
+    /// Add multiple relays
+    ///
+    /// Relays already in the pool are skipped.
+    pub async fn add_relays<I, U>(&self, urls: I, opts: RelayOptions) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = U>,
+        U: TryIntoUrl,
+        Error: From<<U as TryIntoUrl>::Err>,
+    {
+        for url in urls.into_iter() {
+            self.add_relay(url, opts.clone()).await?;
+        }
+        Ok(())
+    }
+
     /// Disconnect and remove relay
     pub async fn remove_relay<U>(&self, url: U) -> Result<(), Error>
     where
@@ -527,6 +1179,58 @@ impl RelayPool {
         Ok(())
     }
 
+    /// Disconnect and remove all relays
+    pub async fn remove_all_relays(&self) -> Result<(), Error> {
+        let mut relays = self.relays.write().await;
+        for (_, relay) in relays.drain() {
+            self.disconnect_relay(&relay).await?;
+        }
+        Ok(())
+    }
+
+    /// Replace the relays of the pool with the given set
+    ///
+    /// Relays not in `urls` are disconnected and removed, new ones are added with [`RelayOptions`].
+    /// Relays that are both in the pool and in `urls` are left untouched (connection preserved).
+    ///
+    /// **New relays are NOT automatically connected!**
+    pub async fn set_relays<I, U>(&self, urls: I, opts: RelayOptions) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = U>,
+        U: TryIntoUrl,
+        Error: From<<U as TryIntoUrl>::Err>,
+    {
+        // Parse all the URLs before touching the pool
+        let mut new_urls: HashSet<Url> = HashSet::new();
+        for url in urls.into_iter() {
+            new_urls.insert(url.try_into_url()?);
+        }
+
+        let mut relays = self.relays.write().await;
+
+        // Remove relays that are no longer needed
+        let to_remove: Vec<Url> = relays
+            .keys()
+            .filter(|url| !new_urls.contains(url))
+            .cloned()
+            .collect();
+        for url in to_remove.into_iter() {
+            if let Some(relay) = relays.remove(&url) {
+                self.disconnect_relay(&relay).await?;
+            }
+        }
+
+        // Add missing relays
+        for url in new_urls.into_iter() {
+            if !relays.contains_key(&url) {
+                let relay = self.internal_new_relay(url, opts.clone());
+                relays.insert(relay.url(), relay);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Send client message
     pub async fn send_msg(&self, msg: ClientMessage, wait: Option<Duration>) -> Result<(), Error> {
         let relays = self.relays().await;
@@ -643,7 +1347,65 @@ impl RelayPool {
         }
     }
 
+    /// Send client message to multiple relays at once
+    ///
+    /// Return the result of each relay: relays not in the pool are reported as [`Error::RelayNotFound`].
+    pub async fn send_msg_to_many<I, U>(
+        &self,
+        urls: I,
+        msg: ClientMessage,
+        wait: Option<Duration>,
+    ) -> Result<HashMap<Url, Result<(), Error>>, Error>
+    where
+        I: IntoIterator<Item = U>,
+        U: TryIntoUrl,
+        Error: From<<U as TryIntoUrl>::Err>,
+    {
+        let urls: HashSet<Url> = urls
+            .into_iter()
+            .map(|url| url.try_into_url())
+            .collect::<Result<_, _>>()?;
+
+        if urls.is_empty() {
+            return Err(Error::NoRelays);
+        }
+
+        if let ClientMessage::Event(event) = &msg {
+            self.database.save_event(event).await?;
+        }
+
+        let relays = self.relays().await;
+        let mut results: HashMap<Url, Result<(), Error>> = HashMap::with_capacity(urls.len());
+        let mut handles = Vec::new();
+
+        for url in urls.into_iter() {
+            match relays.get(&url) {
+                Some(relay) => {
+                    let relay = relay.clone();
+                    let msg = msg.clone();
+                    let handle = thread::spawn(async move {
+                        let res = relay.send_msg(msg, wait).await.map_err(Error::Relay);
+                        (url, res)
+                    });
+                    handles.push(handle);
+                }
+                None => {
+                    results.insert(url, Err(Error::RelayNotFound));
+                }
+            }
+        }
+
+        for handle in handles.into_iter().flatten() {
+            let (url, res) = handle.join().await?;
+            results.insert(url, res);
+        }
+
+        Ok(results)
+    }
+
     /// Send event and wait for `OK` relay msg
+    ///
+    /// Return an error if less than [`RelaySendOptions::min_success`] relays accepted the event.
     pub async fn send_event(&self, event: Event, opts: RelaySendOptions) -> Result<EventId, Error> {
         let relays = self.relays().await;
 
@@ -653,32 +1415,46 @@ impl RelayPool {
 
         self.database.save_event(&event).await?;
 
-        let sent_to_at_least_one_relay: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
         let mut handles = Vec::new();
 
         let event_id = event.id;
 
         for (url, relay) in relays.into_iter() {
             let event = event.clone();
-            let sent = sent_to_at_least_one_relay.clone();
             let handle = thread::spawn(async move {
-                match relay.send_event(event, opts).await {
-                    Ok(_) => {
-                        let _ =
-                            sent.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |_| Some(true));
-                    }
-                    Err(e) => tracing::error!("Impossible to send event to {url}: {e}"),
+                let res = relay.send_event(event, opts).await;
+                if let Err(e) = &res {
+                    tracing::error!("Impossible to send event to {url}: {e}");
                 }
+                (url, res)
             });
             handles.push(handle);
         }
 
+        let mut accepted: Vec<Url> = Vec::new();
+        let mut rejected: HashMap<Url, String> = HashMap::new();
+
         for handle in handles.into_iter().flatten() {
-            handle.join().await?;
+            let (url, res) = handle.join().await?;
+            match res {
+                Ok(_) => accepted.push(url),
+                Err(e) => {
+                    rejected.insert(url, e.to_string());
+                }
+            }
         }
 
-        if !sent_to_at_least_one_relay.load(Ordering::SeqCst) {
-            return Err(Error::EventNotPublished(event_id));
+        if accepted.len() < opts.min_success {
+            if accepted.is_empty() && opts.min_success == 1 {
+                return Err(Error::EventNotPublished(event_id));
+            }
+
+            return Err(Error::QuorumNotReached {
+                event_id,
+                min_success: opts.min_success,
+                accepted,
+                rejected,
+            });
         }
 
         Ok(event_id)
@@ -752,12 +1528,70 @@ impl RelayPool {
         }
     }
 
+    /// Send event to multiple relays at once and wait for `OK` relay msgs
+    ///
+    /// Return the result of each relay: relays not in the pool are reported as [`Error::RelayNotFound`].
+    pub async fn send_event_to_many<I, U>(
+        &self,
+        urls: I,
+        event: Event,
+        opts: RelaySendOptions,
+    ) -> Result<HashMap<Url, Result<EventId, Error>>, Error>
+    where
+        I: IntoIterator<Item = U>,
+        U: TryIntoUrl,
+        Error: From<<U as TryIntoUrl>::Err>,
+    {
+        let urls: HashSet<Url> = urls
+            .into_iter()
+            .map(|url| url.try_into_url())
+            .collect::<Result<_, _>>()?;
+
+        if urls.is_empty() {
+            return Err(Error::NoRelays);
+        }
+
+        self.database.save_event(&event).await?;
+
+        let relays = self.relays().await;
+        let mut results: HashMap<Url, Result<EventId, Error>> = HashMap::with_capacity(urls.len());
+        let mut handles = Vec::new();
+
+        for url in urls.into_iter() {
+            match relays.get(&url) {
+                Some(relay) => {
+                    let relay = relay.clone();
+                    let event = event.clone();
+                    let handle = thread::spawn(async move {
+                        let res = relay.send_event(event, opts).await.map_err(Error::Relay);
+                        (url, res)
+                    });
+                    handles.push(handle);
+                }
+                None => {
+                    results.insert(url, Err(Error::RelayNotFound));
+                }
+            }
+        }
+
+        for handle in handles.into_iter().flatten() {
+            let (url, res) = handle.join().await?;
+            results.insert(url, res);
+        }
+
+        Ok(results)
+    }
+
     /// Subscribe to filters
     ///
     /// Internal Subscription ID set to `InternalSubscriptionId::Pool`
     pub async fn subscribe(&self, filters: Vec<Filter>, wait: Option<Duration>) {
         let relays = self.relays().await;
         self.update_subscription_filters(filters.clone()).await;
+        if self.opts.warm_up {
+            self.notify_cached_events(filters.clone(), InternalSubscriptionId::Pool)
+                .await;
+        }
         for relay in relays.values() {
             if let Err(e) = relay
                 .subscribe_with_internal_id(InternalSubscriptionId::Pool, filters.clone(), wait)
@@ -768,6 +1602,26 @@ impl RelayPool {
         }
     }
 
+    async fn notify_cached_events(
+        &self,
+        filters: Vec<Filter>,
+        internal_subscription_id: InternalSubscriptionId,
+    ) {
+        match self.database.query(filters, Order::Desc).await {
+            Ok(events) => {
+                for event in events.into_iter() {
+                    let _ = self
+                        .notification_sender
+                        .send(RelayPoolNotification::CachedEvent {
+                            event,
+                            internal_subscription_id: internal_subscription_id.clone(),
+                        });
+                }
+            }
+            Err(e) => tracing::error!("Impossible to query cached events: {e}"),
+        }
+    }
+
     /// Unsubscribe from filters
     ///
     /// Internal Subscription ID set to `InternalSubscriptionId::Pool`
@@ -783,6 +1637,113 @@ impl RelayPool {
         }
     }
 
+    /// Subscribe a single relay to filters
+    ///
+    /// The filters are kept alongside the pool-wide ones set with [`RelayPool::subscribe`].
+    ///
+    /// Internal Subscription ID set to `InternalSubscriptionId::Relay`
+    pub async fn subscribe_to<U>(
+        &self,
+        url: U,
+        filters: Vec<Filter>,
+        wait: Option<Duration>,
+    ) -> Result<(), Error>
+    where
+        U: TryIntoUrl,
+        Error: From<<U as TryIntoUrl>::Err>,
+    {
+        let relay: Relay = self.relay(url).await?;
+        relay
+            .subscribe_with_internal_id(InternalSubscriptionId::Relay, filters, wait)
+            .await?;
+        Ok(())
+    }
+
+    /// Unsubscribe a single relay from the filters set with [`RelayPool::subscribe_to`]
+    ///
+    /// Internal Subscription ID set to `InternalSubscriptionId::Relay`
+    pub async fn unsubscribe_from<U>(&self, url: U, wait: Option<Duration>) -> Result<(), Error>
+    where
+        U: TryIntoUrl,
+        Error: From<<U as TryIntoUrl>::Err>,
+    {
+        let relay: Relay = self.relay(url).await?;
+        relay
+            .unsubscribe_with_internal_id(InternalSubscriptionId::Relay, wait)
+            .await?;
+        Ok(())
+    }
+
+    /// Subscribe the relays with the `label` to filters
+    ///
+    /// The filters are kept alongside the pool-wide and the relay-specific ones.
+    ///
+    /// Internal Subscription ID set to `InternalSubscriptionId::Label`
+    pub async fn subscribe_to_label(
+        &self,
+        label: &str,
+        filters: Vec<Filter>,
+        wait: Option<Duration>,
+    ) -> Result<(), Error> {
+        let relays = self.relays_with_label(label).await;
+        if relays.is_empty() {
+            return Err(Error::NoRelays);
+        }
+
+        let id = InternalSubscriptionId::Label(label.to_string());
+        for relay in relays.values() {
+            if let Err(e) = relay
+                .subscribe_with_internal_id(id.clone(), filters.clone(), wait)
+                .await
+            {
+                tracing::error!("{e}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Unsubscribe the relays with the `label` from the filters set with [`RelayPool::subscribe_to_label`]
+    ///
+    /// Internal Subscription ID set to `InternalSubscriptionId::Label`
+    pub async fn unsubscribe_from_label(&self, label: &str, wait: Option<Duration>) {
+        let id = InternalSubscriptionId::Label(label.to_string());
+        let relays = self.relays().await;
+        for relay in relays.values() {
+            // The relay may have been unlabeled after the subscription
+            if relay.subscription(&id).await.is_some() {
+                if let Err(e) = relay.unsubscribe_with_internal_id(id.clone(), wait).await {
+                    tracing::error!("{e}");
+                }
+            }
+        }
+    }
+
+    /// Send client message to the relays with the `label`
+    ///
+    /// Return the result of each relay.
+    pub async fn send_msg_to_label(
+        &self,
+        label: &str,
+        msg: ClientMessage,
+        wait: Option<Duration>,
+    ) -> Result<HashMap<Url, Result<(), Error>>, Error> {
+        let urls = self.relays_with_label(label).await.into_keys();
+        self.send_msg_to_many(urls, msg, wait).await
+    }
+
+    /// Send event to the relays with the `label` and wait for `OK` relay msgs
+    ///
+    /// Return the result of each relay.
+    pub async fn send_event_to_label(
+        &self,
+        label: &str,
+        event: Event,
+        opts: RelaySendOptions,
+    ) -> Result<HashMap<Url, Result<EventId, Error>>, Error> {
+        let urls = self.relays_with_label(label).await.into_keys();
+        self.send_event_to_many(urls, event, opts).await
+    }
+
     /// Get events of filters
     ///
     /// Get events from local database and relays
@@ -791,6 +1752,65 @@ impl RelayPool {
         filters: Vec<Filter>,
         timeout: Duration,
         opts: FilterOptions,
+    ) -> Result<Vec<Event>, Error> {
+        let relays = self.relays().await;
+        self.get_events_from_relays(relays, filters, timeout, opts)
+            .await
+    }
+
+    /// Get events of filters from the relays with the `label`
+    ///
+    /// Get events from local database and relays with the `label`
+    pub async fn get_events_from_label(
+        &self,
+        label: &str,
+        filters: Vec<Filter>,
+        timeout: Duration,
+        opts: FilterOptions,
+    ) -> Result<Vec<Event>, Error> {
+        let relays = self.relays_with_label(label).await;
+        if relays.is_empty() {
+            return Err(Error::NoRelays);
+        }
+        self.get_events_from_relays(relays, filters, timeout, opts)
+            .await
+    }
+
+    /// Get events of filters from specific relays
+    ///
+    /// Get events from local database and relays. Return [`Error::RelayNotFound`] if a relay is not in the pool.
+    pub async fn get_events_from<I, U>(
+        &self,
+        urls: I,
+        filters: Vec<Filter>,
+        timeout: Duration,
+        opts: FilterOptions,
+    ) -> Result<Vec<Event>, Error>
+    where
+        I: IntoIterator<Item = U>,
+        U: TryIntoUrl,
+        Error: From<<U as TryIntoUrl>::Err>,
+    {
+        let all_relays: HashMap<Url, Relay> = self.relays().await;
+        let mut relays: HashMap<Url, Relay> = HashMap::new();
+        for url in urls.into_iter() {
+            let url: Url = url.try_into_url()?;
+            let relay: Relay = all_relays.get(&url).cloned().ok_or(Error::RelayNotFound)?;
+            relays.insert(url, relay);
+        }
+        if relays.is_empty() {
+            return Err(Error::NoRelays);
+        }
+        self.get_events_from_relays(relays, filters, timeout, opts)
+            .await
+    }
+
+    async fn get_events_from_relays(
+        &self,
+        relays: HashMap<Url, Relay>,
+        filters: Vec<Filter>,
+        timeout: Duration,
+        opts: FilterOptions,
     ) -> Result<Vec<Event>, Error> {
         // Get stored events
         let stored_events: Vec<Event> = self
@@ -804,9 +1824,8 @@ impl RelayPool {
             Arc::new(Mutex::new(stored_events.iter().map(|e| e.id).collect()));
         let events: Arc<Mutex<Vec<Event>>> = Arc::new(Mutex::new(stored_events));
 
-        // Get relays and start query
+        // Start query
         let mut handles = Vec::new();
-        let relays = self.relays().await;
         for (url, relay) in relays.into_iter() {
             let filters = filters.clone();
             let ids = ids.clone();
@@ -837,6 +1856,41 @@ impl RelayPool {
         Ok(events.lock_owned().await.clone())
     }
 
+    /// Count events of filters on all relays
+    ///
+    /// Return the count of each relay, to be merged with [`CountReport::estimate`].
+    pub async fn count_events_of(
+        &self,
+        filters: Vec<Filter>,
+        timeout: Duration,
+    ) -> Result<CountReport, Error> {
+        let counts: Arc<Mutex<HashMap<Url, usize>>> = Arc::new(Mutex::new(HashMap::new()));
+
+        let mut handles = Vec::new();
+        let relays = self.relays().await;
+        for (url, relay) in relays.into_iter() {
+            let filters = filters.clone();
+            let counts = counts.clone();
+            let handle = thread::spawn(async move {
+                match relay.count_events_of(filters, timeout).await {
+                    Ok(count) => {
+                        counts.lock().await.insert(url, count);
+                    }
+                    Err(e) => tracing::error!("Failed to count events on {url}: {e}"),
+                }
+            });
+            handles.push(handle);
+        }
+
+        for handle in handles.into_iter().flatten() {
+            handle.join().await?;
+        }
+
+        Ok(CountReport {
+            counts: counts.lock_owned().await.clone(),
+        })
+    }
+
     /// Request events of filter.
     ///
     /// If the events aren't already stored in the database, will be sent to notification listener
@@ -854,14 +1908,15 @@ impl RelayPool {
     }
 
     /// Connect to all added relays and keep connection alive
-    pub async fn connect(&self, wait_for_connection: bool) {
+    pub async fn connect(&self, wait_for_connection: bool) -> ConnectReport {
         let relays: HashMap<Url, Relay> = self.relays().await;
 
         if wait_for_connection {
             let mut handles = Vec::new();
 
-            for relay in relays.into_values() {
+            for relay in relays.values() {
                 let pool = self.clone();
+                let relay = relay.clone();
                 let handle = thread::spawn(async move {
                     pool.connect_relay(&relay, wait_for_connection).await;
                 });
@@ -876,6 +1931,52 @@ impl RelayPool {
                 self.connect_relay(relay, wait_for_connection).await;
             }
         }
+
+        Self::connect_report(&relays).await
+    }
+
+    /// Connect to all added relays, waiting up to `timeout` for the connections
+    ///
+    /// The relays not connected before the `timeout` keep connecting in background
+    /// and are listed as pending in the [`ConnectReport`].
+    pub async fn connect_with_timeout(&self, timeout: Duration) -> ConnectReport {
+        let relays: HashMap<Url, Relay> = self.relays().await;
+
+        let mut handles = Vec::with_capacity(relays.len());
+        for relay in relays.values() {
+            let pool = self.clone();
+            let relay = relay.clone();
+            let handle = thread::spawn(async move {
+                pool.connect_relay(&relay, true).await;
+            });
+            handles.push(handle);
+        }
+
+        let res = time::timeout(Some(timeout), async move {
+            for handle in handles.into_iter().flatten() {
+                let _ = handle.join().await;
+            }
+        })
+        .await;
+        if res.is_none() {
+            tracing::warn!("Timeout reached while connecting to relays");
+        }
+
+        Self::connect_report(&relays).await
+    }
+
+    async fn connect_report(relays: &HashMap<Url, Relay>) -> ConnectReport {
+        let mut report = ConnectReport::default();
+        for (url, relay) in relays.iter() {
+            if relay.status().await == RelayStatus::Connected {
+                report.connected.insert(url.clone());
+            } else if let Some(error) = relay.last_connection_error().await {
+                report.failed.insert(url.clone(), error);
+            } else {
+                report.pending.insert(url.clone());
+            }
+        }
+        report
     }
 
     /// Disconnect from all relays
@@ -0,0 +1,149 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Quarantine of the events failing the verification
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use nostr::event;
+use nostr::{Event, Timestamp, Url};
+
+/// Why the event has been quarantined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuarantineReason {
+    /// The ID doesn't match the event fields
+    InvalidId,
+    /// The signature is not valid
+    InvalidSignature,
+}
+
+impl fmt::Display for QuarantineReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidId => write!(f, "invalid id"),
+            Self::InvalidSignature => write!(f, "invalid signature"),
+        }
+    }
+}
+
+impl From<&event::Error> for QuarantineReason {
+    fn from(e: &event::Error) -> Self {
+        match e {
+            event::Error::InvalidId => Self::InvalidId,
+            _ => Self::InvalidSignature,
+        }
+    }
+}
+
+/// Event rejected by the verification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuarantinedEvent {
+    /// The rejected event
+    pub event: Event,
+    /// Relay that sent the event
+    pub relay_url: Url,
+    /// Reason
+    pub reason: QuarantineReason,
+    /// Timestamp of when the event has been received
+    pub received_at: Timestamp,
+}
+
+/// Bounded store of the events failing the id or signature verification
+///
+/// Useful to debug the interoperability with relay implementations.
+/// When full, the oldest events are dropped. Enable it with
+/// [`RelayPoolOptions::quarantine`](super::RelayPoolOptions::quarantine).
+///
+/// # Example
+/// ```rust,no_run
+/// use nostr_sdk::prelude::*;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let opts = Options::new().pool(RelayPoolOptions::new().quarantine(100));
+/// let client = Client::with_opts(&Keys::generate(), opts);
+/// client.add_relay("wss://relay.damus.io").await.unwrap();
+/// client.connect().await;
+///
+/// // ...
+///
+/// for quarantined in client.quarantine().events() {
+///     println!(
+///         "Rejected {} from {}: {}",
+///         quarantined.event.id, quarantined.relay_url, quarantined.reason
+///     );
+/// }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Quarantine {
+    events: Arc<Mutex<VecDeque<QuarantinedEvent>>>,
+    max: usize,
+}
+
+impl fmt::Debug for Quarantine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Quarantine")
+            .field("len", &self.len())
+            .field("max", &self.max)
+            .finish()
+    }
+}
+
+impl Quarantine {
+    /// New quarantine keeping up to `max` events (`0` to disable it)
+    pub fn new(max: usize) -> Self {
+        Self {
+            events: Arc::new(Mutex::new(VecDeque::new())),
+            max,
+        }
+    }
+
+    pub(crate) fn insert(&self, event: QuarantinedEvent) {
+        if self.max == 0 {
+            return;
+        }
+
+        let mut events = self.events.lock().unwrap_or_else(|e| e.into_inner());
+        if events.len() >= self.max {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+
+    /// Get the quarantined events, from the oldest
+    pub fn events(&self) -> Vec<QuarantinedEvent> {
+        let events = self.events.lock().unwrap_or_else(|e| e.into_inner());
+        events.iter().cloned().collect()
+    }
+
+    /// Get the quarantined events sent by `relay_url`
+    pub fn events_from(&self, relay_url: &Url) -> Vec<QuarantinedEvent> {
+        let events = self.events.lock().unwrap_or_else(|e| e.into_inner());
+        events
+            .iter()
+            .filter(|e| &e.relay_url == relay_url)
+            .cloned()
+            .collect()
+    }
+
+    /// Number of quarantined events
+    pub fn len(&self) -> usize {
+        let events = self.events.lock().unwrap_or_else(|e| e.into_inner());
+        events.len()
+    }
+
+    /// Check if there aren't quarantined events
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all the events
+    pub fn clear(&self) {
+        let mut events = self.events.lock().unwrap_or_else(|e| e.into_inner());
+        events.clear();
+    }
+}
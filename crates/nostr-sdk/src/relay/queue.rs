@@ -0,0 +1,172 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Outgoing queue
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use nostr::types::time::Instant;
+use nostr::{ClientMessage, EventId, Timestamp};
+
+/// Message waiting in the outgoing queue of a [`Relay`](super::Relay)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedMessage {
+    /// Queue ID (to cancel the message)
+    pub id: u64,
+    /// Messages (more than one if sent as batch)
+    pub msgs: Vec<ClientMessage>,
+    /// Timestamp of when the message has been queued
+    pub queued_at: Timestamp,
+}
+
+#[derive(Debug)]
+struct Entry {
+    msg: QueuedMessage,
+    instant: Instant,
+}
+
+/// Messages queued but not yet sent
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OutgoingQueue {
+    next_id: Arc<AtomicU64>,
+    entries: Arc<Mutex<BTreeMap<u64, Entry>>>,
+}
+
+impl OutgoingQueue {
+    pub fn push(&self, msgs: Vec<ClientMessage>) -> u64 {
+        let id: u64 = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert(
+            id,
+            Entry {
+                msg: QueuedMessage {
+                    id,
+                    msgs,
+                    queued_at: Timestamp::now(),
+                },
+                instant: Instant::now(),
+            },
+        );
+        id
+    }
+
+    /// Get when the message has been queued
+    pub fn queued_at(&self, id: u64) -> Option<Instant> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.get(&id).map(|entry| entry.instant)
+    }
+
+    /// Remove the message from the queue, to send it
+    ///
+    /// Return `None` if the message has been cancelled.
+    pub fn take(&self, id: u64) -> Option<Vec<ClientMessage>> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.remove(&id).map(|entry| entry.msg.msgs)
+    }
+
+    pub fn list(&self) -> Vec<QueuedMessage> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.values().map(|entry| entry.msg.clone()).collect()
+    }
+
+    pub fn cancel(&self, id: u64) -> bool {
+        self.take(id).is_some()
+    }
+
+    /// Remove the `EVENT` messages of `event_id`
+    ///
+    /// The queued messages left empty are cancelled.
+    pub fn cancel_event(&self, event_id: &EventId) -> bool {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let mut cancelled: bool = false;
+        entries.retain(|_, entry| {
+            let len: usize = entry.msg.msgs.len();
+            entry
+                .msg
+                .msgs
+                .retain(|msg| !matches!(msg, ClientMessage::Event(event) if &event.id == event_id));
+            cancelled |= entry.msg.msgs.len() != len;
+            !entry.msg.msgs.is_empty()
+        });
+        cancelled
+    }
+
+    pub fn clear(&self) -> usize {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let len: usize = entries.len();
+        entries.clear();
+        len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nostr::{EventBuilder, Keys, SubscriptionId};
+
+    use super::*;
+
+    #[test]
+    fn test_push_take_cancel() {
+        let queue = OutgoingQueue::default();
+        let close = ClientMessage::close(SubscriptionId::new("test"));
+        let id1 = queue.push(vec![close.clone()]);
+        let id2 = queue.push(vec![close.clone(), close.clone()]);
+        assert_ne!(id1, id2);
+        assert!(queue.queued_at(id1).is_some());
+
+        let list = queue.list();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].id, id1);
+        assert_eq!(list[1].msgs.len(), 2);
+
+        assert_eq!(queue.take(id1), Some(vec![close]));
+        assert_eq!(queue.take(id1), None);
+        assert!(queue.queued_at(id1).is_none());
+
+        assert!(queue.cancel(id2));
+        assert!(!queue.cancel(id2));
+        assert!(queue.list().is_empty());
+    }
+
+    #[test]
+    fn test_cancel_event() {
+        let keys = Keys::generate();
+        let event1 = EventBuilder::new_text_note("1", [])
+            .to_event(&keys)
+            .unwrap();
+        let event2 = EventBuilder::new_text_note("2", [])
+            .to_event(&keys)
+            .unwrap();
+
+        let queue = OutgoingQueue::default();
+        let single = queue.push(vec![ClientMessage::new_event(event1.clone())]);
+        let batch = queue.push(vec![
+            ClientMessage::new_event(event1.clone()),
+            ClientMessage::new_event(event2.clone()),
+        ]);
+
+        assert!(queue.cancel_event(&event1.id));
+        assert!(!queue.cancel_event(&event1.id));
+
+        // The message left empty is cancelled, the batch keeps the other event
+        assert_eq!(queue.take(single), None);
+        assert_eq!(
+            queue.take(batch),
+            Some(vec![ClientMessage::new_event(event2)])
+        );
+    }
+
+    #[test]
+    fn test_clear() {
+        let queue = OutgoingQueue::default();
+        let close = ClientMessage::close(SubscriptionId::new("test"));
+        let id = queue.push(vec![close.clone()]);
+        queue.push(vec![close]);
+        assert_eq!(queue.clear(), 2);
+        assert_eq!(queue.take(id), None);
+        assert_eq!(queue.clear(), 0);
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Components shareable between pools and between binding layers
+
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use nostr::EventId;
+use tokio::sync::broadcast;
+
+use super::RelayPoolNotification;
+
+/// Default max number of IDs remembered by [`SeenTracker`]
+pub const DEFAULT_MAX_SEEN: usize = 100_000;
+
+#[derive(Debug)]
+struct SeenIds {
+    ids: HashSet<EventId>,
+    order: VecDeque<EventId>,
+    max: usize,
+}
+
+/// Track the IDs of the events already seen
+///
+/// Cloning the tracker share the same IDs: pass it to [`RelayPoolOptions::seen_tracker`](super::RelayPoolOptions::seen_tracker)
+/// of multiple pools (i.e. the one of the FFI client and the one of an embedded Rust component)
+/// to notify an event only once, even if received by both pools.
+/// When full, the oldest IDs are forgotten.
+///
+/// # Example
+/// ```rust
+/// use nostr_sdk::prelude::*;
+///
+/// let tracker = SeenTracker::new(2);
+/// let id = EventId::all_zeros();
+/// assert!(tracker.insert(id));
+/// assert!(!tracker.clone().insert(id));
+/// assert!(tracker.contains(&id));
+/// ```
+#[derive(Clone)]
+pub struct SeenTracker {
+    inner: Arc<Mutex<SeenIds>>,
+}
+
+impl fmt::Debug for SeenTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SeenTracker")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl Default for SeenTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_SEEN)
+    }
+}
+
+impl SeenTracker {
+    /// New tracker remembering up to `max` IDs
+    pub fn new(max: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(SeenIds {
+                ids: HashSet::new(),
+                order: VecDeque::new(),
+                max,
+            })),
+        }
+    }
+
+    /// Check if the ID was already seen
+    pub fn contains(&self, id: &EventId) -> bool {
+        let seen = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        seen.ids.contains(id)
+    }
+
+    /// Mark the ID as seen
+    ///
+    /// Return `false` if the ID was already seen.
+    pub fn insert(&self, id: EventId) -> bool {
+        let mut seen = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if !seen.ids.insert(id) {
+            return false;
+        }
+
+        seen.order.push_back(id);
+        if seen.order.len() > seen.max {
+            if let Some(oldest) = seen.order.pop_front() {
+                seen.ids.remove(&oldest);
+            }
+        }
+
+        true
+    }
+
+    /// Number of remembered IDs
+    pub fn len(&self) -> usize {
+        let seen = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        seen.ids.len()
+    }
+
+    /// Check if no ID is remembered
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forget all the IDs
+    pub fn clear(&self) {
+        let mut seen = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        seen.ids.clear();
+        seen.order.clear();
+    }
+}
+
+/// Broadcast channel of the [`RelayPoolNotification`]s
+///
+/// Pass the same hub to [`RelayPoolOptions::notification_hub`](super::RelayPoolOptions::notification_hub)
+/// of multiple pools to consume all their notifications from a single place.
+/// Note that the `Stop` and `Shutdown` notifications of every pool are received by all the listeners.
+#[derive(Debug, Clone)]
+pub struct NotificationHub {
+    sender: broadcast::Sender<RelayPoolNotification>,
+}
+
+impl NotificationHub {
+    /// New hub, keeping up to `capacity` notifications for slow listeners
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    pub(crate) fn from_sender(sender: broadcast::Sender<RelayPoolNotification>) -> Self {
+        Self { sender }
+    }
+
+    pub(crate) fn sender(&self) -> broadcast::Sender<RelayPoolNotification> {
+        self.sender.clone()
+    }
+
+    /// Get a new notifications listener
+    pub fn notifications(&self) -> broadcast::Receiver<RelayPoolNotification> {
+        self.sender.subscribe()
+    }
+
+    /// Number of active listeners
+    pub fn listeners(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
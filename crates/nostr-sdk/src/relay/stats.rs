@@ -4,24 +4,21 @@
 
 //! Relay Stats
 
-#[cfg(not(target_arch = "wasm32"))]
-use std::collections::VecDeque;
-#[cfg(not(target_arch = "wasm32"))]
-use std::sync::atomic::AtomicBool;
-use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
-#[cfg(not(target_arch = "wasm32"))]
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
+use nostr::types::time::Instant;
 use nostr::Timestamp;
-#[cfg(not(target_arch = "wasm32"))]
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
+
+use super::{QuarantineReason, RelayPoolNotification};
 
 #[cfg(feature = "blocking")]
 use crate::RUNTIME;
 
 /// Ping Stats
-#[cfg(not(target_arch = "wasm32"))]
 #[derive(Debug, Clone)]
 pub(crate) struct PingStats {
     sent_at: Arc<RwLock<Instant>>,
@@ -29,14 +26,12 @@ pub(crate) struct PingStats {
     replied: Arc<AtomicBool>,
 }
 
-#[cfg(not(target_arch = "wasm32"))]
 impl Default for PingStats {
     fn default() -> Self {
         Self::new()
     }
 }
 
-#[cfg(not(target_arch = "wasm32"))]
 impl PingStats {
     /// New default ping stats
     pub fn new() -> Self {
@@ -94,10 +89,18 @@ pub struct RelayConnectionStats {
     bytes_received: Arc<AtomicUsize>,
     connected_at: Arc<AtomicU64>,
     first_connection_timestamp: Arc<AtomicU64>,
-    #[cfg(not(target_arch = "wasm32"))]
+    rate_limited_until: Arc<AtomicU64>,
+    rate_limits: Arc<AtomicUsize>,
+    rejected_messages: Arc<AtomicUsize>,
+    errors: Arc<AtomicUsize>,
+    disconnections: Arc<AtomicUsize>,
     latencies: Arc<RwLock<VecDeque<Duration>>>,
-    #[cfg(not(target_arch = "wasm32"))]
+    first_connection_latency: Arc<RwLock<Option<Duration>>>,
+    reconnection_latencies: Arc<RwLock<VecDeque<Duration>>>,
+    pending_reqs: Arc<RwLock<HashMap<String, Instant>>>,
+    eose_latencies: Arc<RwLock<VecDeque<Duration>>>,
     pub(crate) ping: PingStats,
+    pool: Option<RelayPoolStats>,
 }
 
 impl Default for RelayConnectionStats {
@@ -116,10 +119,26 @@ impl RelayConnectionStats {
             bytes_received: Arc::new(AtomicUsize::new(0)),
             connected_at: Arc::new(AtomicU64::new(0)),
             first_connection_timestamp: Arc::new(AtomicU64::new(0)),
-            #[cfg(not(target_arch = "wasm32"))]
+            rate_limited_until: Arc::new(AtomicU64::new(0)),
+            rate_limits: Arc::new(AtomicUsize::new(0)),
+            rejected_messages: Arc::new(AtomicUsize::new(0)),
+            errors: Arc::new(AtomicUsize::new(0)),
+            disconnections: Arc::new(AtomicUsize::new(0)),
             latencies: Arc::new(RwLock::new(VecDeque::new())),
-            #[cfg(not(target_arch = "wasm32"))]
+            first_connection_latency: Arc::new(RwLock::new(None)),
+            reconnection_latencies: Arc::new(RwLock::new(VecDeque::new())),
+            pending_reqs: Arc::new(RwLock::new(HashMap::new())),
+            eose_latencies: Arc::new(RwLock::new(VecDeque::new())),
             ping: PingStats::default(),
+            pool: None,
+        }
+    }
+
+    /// New connection stats that also update the aggregate [`RelayPoolStats`]
+    pub(crate) fn with_pool_stats(pool: RelayPoolStats) -> Self {
+        Self {
+            pool: Some(pool),
+            ..Self::new()
         }
     }
 
@@ -164,8 +183,51 @@ impl RelayConnectionStats {
         Timestamp::from(self.first_connection_timestamp.load(Ordering::SeqCst))
     }
 
+    /// Get UNIX timestamp until which the relay asked to slow down (`rate-limited:` reply)
+    ///
+    /// Return `None` if the relay is not rate limiting this client.
+    pub fn rate_limited_until(&self) -> Option<Timestamp> {
+        let until = Timestamp::from(self.rate_limited_until.load(Ordering::SeqCst));
+        if until > Timestamp::now() {
+            Some(until)
+        } else {
+            None
+        }
+    }
+
+    /// Check if the relay is currently rate limiting this client
+    pub fn is_rate_limited(&self) -> bool {
+        self.rate_limited_until().is_some()
+    }
+
+    /// The number of `rate-limited:` replies received from the relay
+    pub fn rate_limits(&self) -> usize {
+        self.rate_limits.load(Ordering::SeqCst)
+    }
+
+    /// The number of received messages rejected because exceeding the [`Limits`](super::Limits)
+    pub fn rejected_messages(&self) -> usize {
+        self.rejected_messages.load(Ordering::SeqCst)
+    }
+
+    /// The number of errors (failed sends and rejected messages)
+    pub fn errors(&self) -> usize {
+        self.errors.load(Ordering::SeqCst)
+    }
+
+    /// The number of times an established connection has been lost
+    pub fn disconnections(&self) -> usize {
+        self.disconnections.load(Ordering::SeqCst)
+    }
+
+    /// Calculate average time between `REQ` and `EOSE`
+    pub async fn eose_latency(&self) -> Option<Duration> {
+        let latencies = self.eose_latencies.read().await;
+        let sum: Duration = latencies.iter().sum();
+        sum.checked_div(latencies.len() as u32)
+    }
+
     /// Calculate latency
-    #[cfg(not(target_arch = "wasm32"))]
     pub async fn latency(&self) -> Option<Duration> {
         let latencies = self.latencies.read().await;
         let sum: Duration = latencies.iter().sum();
@@ -178,6 +240,21 @@ impl RelayConnectionStats {
         RUNTIME.block_on(async { self.latency().await })
     }
 
+    /// Time taken to establish the first connection (DNS lookup, TCP, TLS and WebSocket handshakes)
+    pub async fn first_connection_latency(&self) -> Option<Duration> {
+        *self.first_connection_latency.read().await
+    }
+
+    /// Calculate the average time taken to establish the last reconnections
+    ///
+    /// Compare with [`RelayConnectionStats::first_connection_latency`] to check the benefit of the warm standby
+    /// (see [`RelayOptions::warm_standby`](super::RelayOptions::warm_standby)).
+    pub async fn reconnection_latency(&self) -> Option<Duration> {
+        let latencies = self.reconnection_latencies.read().await;
+        let sum: Duration = latencies.iter().sum();
+        sum.checked_div(latencies.len() as u32)
+    }
+
     pub(crate) fn new_attempt(&self) {
         self.attempts.fetch_add(1, Ordering::SeqCst);
     }
@@ -200,17 +277,102 @@ impl RelayConnectionStats {
         }
     }
 
+    pub(crate) fn new_rate_limit(&self, until: Timestamp) {
+        self.rate_limits.fetch_add(1, Ordering::SeqCst);
+        let until: u64 = until.as_u64();
+        let _ =
+            self.rate_limited_until
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |current| {
+                    Some(current.max(until))
+                });
+    }
+
+    pub(crate) fn new_rejected_message(&self) {
+        self.rejected_messages.fetch_add(1, Ordering::SeqCst);
+        self.new_error();
+    }
+
+    pub(crate) fn new_error(&self) {
+        self.errors.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub(crate) fn new_disconnection(&self) {
+        self.disconnections.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub(crate) async fn req_sent<S>(&self, subscription_id: S)
+    where
+        S: Into<String>,
+    {
+        let mut pending_reqs = self.pending_reqs.write().await;
+        pending_reqs.insert(subscription_id.into(), Instant::now());
+    }
+
+    /// The `REQ` has been closed (by the client or the relay) without `EOSE`
+    pub(crate) async fn req_closed(&self, subscription_id: &str) {
+        let mut pending_reqs = self.pending_reqs.write().await;
+        pending_reqs.remove(subscription_id);
+    }
+
+    /// The `EOSE` of the pending `REQ`s will never be received
+    pub(crate) async fn clear_pending_reqs(&self) {
+        let mut pending_reqs = self.pending_reqs.write().await;
+        pending_reqs.clear();
+    }
+
+    pub(crate) async fn eose_received(&self, subscription_id: &str) {
+        let mut pending_reqs = self.pending_reqs.write().await;
+        if let Some(sent_at) = pending_reqs.remove(subscription_id) {
+            let mut latencies = self.eose_latencies.write().await;
+            if latencies.len() >= 5 {
+                latencies.pop_back();
+            }
+            latencies.push_front(sent_at.elapsed())
+        }
+    }
+
     pub(crate) fn add_bytes_sent(&self, size: usize) {
         self.bytes_sent.fetch_add(size, Ordering::SeqCst);
+        if let Some(pool) = &self.pool {
+            pool.bytes_sent.fetch_add(size, Ordering::SeqCst);
+        }
     }
 
     pub(crate) fn add_bytes_received(&self, size: usize) {
         if size > 0 {
             self.bytes_received.fetch_add(size, Ordering::SeqCst);
+            if let Some(pool) = &self.pool {
+                pool.bytes_received.fetch_add(size, Ordering::SeqCst);
+            }
+        }
+    }
+
+    pub(crate) fn add_events_sent(&self, count: usize) {
+        if let Some(pool) = &self.pool {
+            pool.events_sent.fetch_add(count, Ordering::SeqCst);
         }
     }
 
-    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn new_dropped_message(&self) {
+        if let Some(pool) = &self.pool {
+            pool.dropped_messages.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    pub(crate) async fn save_connection_latency(&self, latency: Duration) {
+        let mut first = self.first_connection_latency.write().await;
+        if first.is_none() {
+            *first = Some(latency);
+            return;
+        }
+
+        let mut latencies = self.reconnection_latencies.write().await;
+        if latencies.len() >= 5 {
+            latencies.pop_back();
+        }
+        latencies.push_front(latency)
+    }
+
     pub(crate) async fn save_latency(&self, latency: Duration) {
         let mut latencies = self.latencies.write().await;
         if latencies.len() >= 5 {
@@ -219,3 +381,95 @@ impl RelayConnectionStats {
         latencies.push_front(latency)
     }
 }
+
+/// [`RelayPool`](super::pool::RelayPool) aggregate stats
+#[derive(Debug, Clone)]
+pub struct RelayPoolStats {
+    bytes_sent: Arc<AtomicUsize>,
+    bytes_received: Arc<AtomicUsize>,
+    events_sent: Arc<AtomicUsize>,
+    events_received: Arc<AtomicUsize>,
+    duplicate_events: Arc<AtomicUsize>,
+    dropped_messages: Arc<AtomicUsize>,
+    invalid_ids: Arc<AtomicUsize>,
+    invalid_signatures: Arc<AtomicUsize>,
+    notification_sender: broadcast::Sender<RelayPoolNotification>,
+}
+
+impl RelayPoolStats {
+    pub(crate) fn new(notification_sender: broadcast::Sender<RelayPoolNotification>) -> Self {
+        Self {
+            bytes_sent: Arc::new(AtomicUsize::new(0)),
+            bytes_received: Arc::new(AtomicUsize::new(0)),
+            events_sent: Arc::new(AtomicUsize::new(0)),
+            events_received: Arc::new(AtomicUsize::new(0)),
+            duplicate_events: Arc::new(AtomicUsize::new(0)),
+            dropped_messages: Arc::new(AtomicUsize::new(0)),
+            invalid_ids: Arc::new(AtomicUsize::new(0)),
+            invalid_signatures: Arc::new(AtomicUsize::new(0)),
+            notification_sender,
+        }
+    }
+
+    /// Bytes sent to all relays
+    pub fn bytes_sent(&self) -> usize {
+        self.bytes_sent.load(Ordering::SeqCst)
+    }
+
+    /// Bytes received from all relays
+    pub fn bytes_received(&self) -> usize {
+        self.bytes_received.load(Ordering::SeqCst)
+    }
+
+    /// Number of `EVENT` messages sent to relays
+    pub fn events_sent(&self) -> usize {
+        self.events_sent.load(Ordering::SeqCst)
+    }
+
+    /// Number of events received from relays (duplicates included)
+    pub fn events_received(&self) -> usize {
+        self.events_received.load(Ordering::SeqCst)
+    }
+
+    /// Number of received events filtered because already saved in the database
+    pub fn duplicate_events(&self) -> usize {
+        self.duplicate_events.load(Ordering::SeqCst)
+    }
+
+    /// Number of outgoing messages dropped because the relay queue was full
+    pub fn dropped_messages(&self) -> usize {
+        self.dropped_messages.load(Ordering::SeqCst)
+    }
+
+    /// Number of received events rejected because the ID doesn't match the event fields
+    pub fn invalid_ids(&self) -> usize {
+        self.invalid_ids.load(Ordering::SeqCst)
+    }
+
+    /// Number of received events rejected because of an invalid signature
+    pub fn invalid_signatures(&self) -> usize {
+        self.invalid_signatures.load(Ordering::SeqCst)
+    }
+
+    /// Number of notifications not yet received by the slowest listener
+    pub fn notification_lag(&self) -> usize {
+        self.notification_sender.len()
+    }
+
+    pub(crate) fn new_event_received(&self) {
+        self.events_received.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub(crate) fn new_duplicate_event(&self) {
+        self.duplicate_events.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub(crate) fn new_invalid_event(&self, reason: QuarantineReason) {
+        match reason {
+            QuarantineReason::InvalidId => self.invalid_ids.fetch_add(1, Ordering::SeqCst),
+            QuarantineReason::InvalidSignature => {
+                self.invalid_signatures.fetch_add(1, Ordering::SeqCst)
+            }
+        };
+    }
+}
@@ -6,7 +6,37 @@
 
 use nostr::url::{ParseError, Url};
 
+/// Normalize relay [`Url`]
+///
+/// Scheme and host are lowercased and the default port is removed while parsing.
+/// In addition, remove fragment, empty query and trailing slash of the path
+/// (the root path is always `/`).
+///
+/// # Example
+/// ```rust
+/// use nostr_sdk::util::normalize_url;
+/// use nostr_sdk::Url;
+///
+/// let a = normalize_url(Url::parse("WSS://Relay.Example.com:443/nostr/").unwrap());
+/// let b = normalize_url(Url::parse("wss://relay.example.com/nostr").unwrap());
+/// assert_eq!(a, b);
+/// assert_eq!(a.as_str(), "wss://relay.example.com/nostr");
+/// ```
+pub fn normalize_url(mut url: Url) -> Url {
+    url.set_fragment(None);
+    if url.query() == Some("") {
+        url.set_query(None);
+    }
+    let path: String = url.path().trim_end_matches('/').to_string();
+    if !path.is_empty() && path != url.path() {
+        url.set_path(&path);
+    }
+    url
+}
+
 /// Try into [`Url`]
+///
+/// The [`Url`] is normalized (see [`normalize_url`]), so the same relay is never added twice.
 pub trait TryIntoUrl {
     /// Error
     type Err;
@@ -17,27 +47,27 @@ pub trait TryIntoUrl {
 impl TryIntoUrl for Url {
     type Err = ParseError;
     fn try_into_url(&self) -> Result<Url, Self::Err> {
-        Ok(self.clone())
+        Ok(normalize_url(self.clone()))
     }
 }
 
 impl TryIntoUrl for &Url {
     type Err = ParseError;
     fn try_into_url(&self) -> Result<Url, Self::Err> {
-        Ok(<&Url>::clone(self).clone())
+        Ok(normalize_url(<&Url>::clone(self).clone()))
     }
 }
 
 impl TryIntoUrl for String {
     type Err = ParseError;
     fn try_into_url(&self) -> Result<Url, Self::Err> {
-        Url::parse(self)
+        Url::parse(self).map(normalize_url)
     }
 }
 
 impl TryIntoUrl for &str {
     type Err = ParseError;
     fn try_into_url(&self) -> Result<Url, Self::Err> {
-        Url::parse(self)
+        Url::parse(self).map(normalize_url)
     }
 }
@@ -0,0 +1,199 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Dockerized relays for integration tests
+//!
+//! Set `NOSTR_TEST_<RELAY>_URL` (i.e. `NOSTR_TEST_STRFRY_URL`) to run the tests against an
+//! already running relay instead of starting a container.
+
+#![allow(dead_code)]
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use nostr_sdk::prelude::*;
+use once_cell::sync::Lazy;
+use testcontainers::clients::Cli;
+use testcontainers::core::WaitFor;
+use testcontainers::{Container, GenericImage};
+
+pub const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Max size of the events accepted by the relays, in bytes
+pub const MAX_EVENT_BYTES: usize = 8192;
+
+static DOCKER: Lazy<Cli> = Lazy::new(Cli::default);
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Relay implementation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelayImpl {
+    /// <https://github.com/hoytech/strfry>
+    Strfry,
+    /// <https://github.com/scsibug/nostr-rs-relay>
+    NostrRsRelay,
+}
+
+impl RelayImpl {
+    pub const ALL: [Self; 2] = [Self::Strfry, Self::NostrRsRelay];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Strfry => "strfry",
+            Self::NostrRsRelay => "nostr-rs-relay",
+        }
+    }
+
+    /// Require NIP42 authentication
+    pub fn auth(&self) -> bool {
+        matches!(self, Self::NostrRsRelay)
+    }
+
+    /// Support negentropy sync
+    pub fn negentropy(&self) -> bool {
+        matches!(self, Self::Strfry)
+    }
+
+    fn env_url(&self) -> String {
+        format!(
+            "NOSTR_TEST_{}_URL",
+            self.name().replace('-', "_").to_uppercase()
+        )
+    }
+
+    fn image(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::Strfry => ("dockurr/strfry", "latest"),
+            Self::NostrRsRelay => ("scsibug/nostr-rs-relay", "latest"),
+        }
+    }
+
+    fn port(&self) -> u16 {
+        match self {
+            Self::Strfry => 7777,
+            Self::NostrRsRelay => 8080,
+        }
+    }
+
+    fn config_path(&self) -> &'static str {
+        match self {
+            Self::Strfry => "/etc/strfry.conf",
+            Self::NostrRsRelay => "/usr/src/app/config.toml",
+        }
+    }
+
+    fn config(&self) -> String {
+        match self {
+            Self::Strfry => format!(
+                r#"db = "/tmp/"
+relay {{
+    bind = "0.0.0.0"
+    port = {port}
+    nofiles = 0
+    info {{
+        name = "nostr-sdk tests"
+    }}
+}}
+events {{
+    maxEventSize = {MAX_EVENT_BYTES}
+}}
+"#,
+                port = self.port()
+            ),
+            Self::NostrRsRelay => format!(
+                r#"[info]
+name = "nostr-sdk tests"
+
+[network]
+address = "0.0.0.0"
+port = {port}
+
+[authorization]
+nip42_auth = true
+
+[limits]
+max_event_bytes = {MAX_EVENT_BYTES}
+"#,
+                port = self.port()
+            ),
+        }
+    }
+}
+
+/// Relay running in a docker container
+pub struct TestRelay {
+    pub relay: RelayImpl,
+    pub url: Url,
+    config: Option<PathBuf>,
+    _container: Option<Container<'static, GenericImage>>,
+}
+
+impl Drop for TestRelay {
+    fn drop(&mut self) {
+        if let Some(path) = &self.config {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+impl TestRelay {
+    pub fn start(relay: RelayImpl) -> Self {
+        if let Ok(url) = env::var(relay.env_url()) {
+            return Self {
+                relay,
+                url: Url::parse(&url).expect("Invalid relay url"),
+                config: None,
+                _container: None,
+            };
+        }
+
+        let config: PathBuf = env::temp_dir().join(format!(
+            "nostr-sdk-{}-{}-{}.conf",
+            relay.name(),
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        fs::write(&config, relay.config()).expect("Impossible to write relay config");
+
+        let (name, tag) = relay.image();
+        let image = GenericImage::new(name, tag)
+            .with_exposed_port(relay.port())
+            .with_volume(config.to_string_lossy(), relay.config_path())
+            .with_wait_for(WaitFor::seconds(2));
+        let container = DOCKER.run(image);
+        let port: u16 = container.get_host_port_ipv4(relay.port());
+
+        Self {
+            relay,
+            url: Url::parse(&format!("ws://127.0.0.1:{port}")).expect("Invalid relay url"),
+            config: Some(config),
+            _container: Some(container),
+        }
+    }
+
+    /// New client connected only to this relay
+    pub async fn client(&self, keys: &Keys, opts: RelayOptions) -> Client {
+        let client = Client::with_opts(keys, Options::new().wait_for_connection(true));
+        client
+            .add_relay_with_opts(self.url.clone(), opts)
+            .await
+            .unwrap();
+        client.connect().await;
+        client
+    }
+}
+
+/// Wait until the relay information document (NIP11) has been fetched
+pub async fn wait_for_document(relay: &Relay) {
+    let start = Instant::now();
+    while relay.document().await.supported_nips.is_none() {
+        if start.elapsed() > TIMEOUT {
+            panic!("Information document of {} not fetched", relay.url());
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
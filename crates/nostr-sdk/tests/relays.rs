@@ -0,0 +1,1903 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Integration tests against real relay implementations
+//!
+//! Require docker: `cargo test -p nostr-sdk --test relays -- --ignored`
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use nostr_sdk::prelude::*;
+use nostr_sdk_net::futures_util::StreamExt;
+
+mod common;
+
+use self::common::{RelayImpl, TestRelay, MAX_EVENT_BYTES, TIMEOUT};
+
+#[derive(Debug)]
+struct ClientTag;
+
+#[async_trait]
+impl Middleware for ClientTag {
+    async fn before_send(
+        &self,
+        mut unsigned: UnsignedEvent,
+    ) -> Result<UnsignedEvent, MiddlewareError> {
+        unsigned.tags.push(Tag::Generic(
+            TagKind::Custom(String::from("client")),
+            vec![String::from("integration-tests")],
+        ));
+        Ok(unsigned)
+    }
+}
+
+#[derive(Debug)]
+struct DropAuthor(XOnlyPublicKey);
+
+#[async_trait]
+impl Middleware for DropAuthor {
+    async fn after_receive(&self, _relay_url: &Url, event: &Event) -> bool {
+        event.pubkey != self.0
+    }
+}
+
+#[derive(Debug)]
+struct WaitForAuthor(XOnlyPublicKey);
+
+#[async_trait]
+impl NotificationHandler for WaitForAuthor {
+    async fn on_event(
+        &self,
+        _relay_url: Url,
+        _subscription_id: SubscriptionId,
+        event: Event,
+    ) -> Result<bool, HandlerError> {
+        Ok(event.pubkey == self.0)
+    }
+}
+
+#[derive(Debug, Default)]
+struct ShutdownFlag(Arc<AtomicBool>);
+
+#[async_trait]
+impl NotificationHandler for ShutdownFlag {
+    async fn on_shutdown(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+async fn next_live_update(watcher: &mut LiveEventWatcher) -> LiveEventUpdate {
+    tokio::time::timeout(TIMEOUT, watcher.next())
+        .await
+        .expect("live event update not received")
+        .unwrap()
+}
+
+async fn publish_notes(client: &Client, n: usize) -> HashSet<EventId> {
+    let mut ids = HashSet::new();
+    for i in 0..n {
+        let id = client
+            .publish_text_note(format!("Integration test note {i}"), [])
+            .await
+            .unwrap();
+        ids.insert(id);
+    }
+    ids
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_publish_and_fetch() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let writer = test_relay.client(&keys, RelayOptions::new()).await;
+        let ids = publish_notes(&writer, 3).await;
+
+        // Use another client, so the events can't come from the local database
+        let reader = test_relay
+            .client(&Keys::generate(), RelayOptions::new())
+            .await;
+        let filter = Filter::new().author(keys.public_key()).kind(Kind::TextNote);
+        let events = reader
+            .get_events_of(vec![filter], Some(TIMEOUT))
+            .await
+            .unwrap();
+        let fetched: HashSet<EventId> = events.into_iter().map(|e| e.id).collect();
+        assert_eq!(fetched, ids, "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_count() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let writer = test_relay.client(&keys, RelayOptions::new()).await;
+        publish_notes(&writer, 3).await;
+
+        // Fallback to local counting if the relay doesn't support COUNT
+        let opts = RelayOptions::new()
+            .check_supported_nips(true)
+            .count_fallback(true);
+        let reader = test_relay.client(&Keys::generate(), opts).await;
+        common::wait_for_document(&reader.relay(test_relay.url.clone()).await.unwrap()).await;
+
+        let filter = Filter::new().author(keys.public_key()).kind(Kind::TextNote);
+        let report = reader
+            .count_events_of(vec![filter], Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert_eq!(
+            report.counts.get(&test_relay.url),
+            Some(&3),
+            "{}",
+            relay.name()
+        );
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_negentropy() {
+    for relay in RelayImpl::ALL.into_iter().filter(|r| r.negentropy()) {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let writer = test_relay.client(&keys, RelayOptions::new()).await;
+        let ids = publish_notes(&writer, 3).await;
+
+        let reader = test_relay
+            .client(&Keys::generate(), RelayOptions::new())
+            .await;
+        let filter = Filter::new().author(keys.public_key()).kind(Kind::TextNote);
+        reader
+            .reconcile(filter.clone(), NegentropyOptions::default())
+            .await
+            .unwrap();
+
+        let stored: HashSet<EventId> = reader
+            .database()
+            .query(vec![filter], Order::Desc)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(stored, ids, "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_auth() {
+    for relay in RelayImpl::ALL.into_iter().filter(|r| r.auth()) {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let client = Client::new(&keys);
+        client.add_relay(test_relay.url.clone()).await.unwrap();
+
+        let mut notifications = client.notifications();
+        client.connect().await;
+
+        let challenge: String = tokio::time::timeout(TIMEOUT, async {
+            loop {
+                if let Ok(RelayPoolNotification::Message {
+                    message: RelayMessage::Auth { challenge },
+                    ..
+                }) = notifications.recv().await
+                {
+                    break challenge;
+                }
+            }
+        })
+        .await
+        .expect("AUTH challenge not received");
+
+        let event = EventBuilder::auth(challenge, test_relay.url.clone())
+            .to_event(&keys)
+            .unwrap();
+        let id = event.id;
+        let relay_handle = client.relay(test_relay.url.clone()).await.unwrap();
+        relay_handle
+            .send_msg(ClientMessage::new_auth(event), None)
+            .await
+            .unwrap();
+
+        let accepted: bool = tokio::time::timeout(TIMEOUT, async {
+            loop {
+                if let Ok(RelayPoolNotification::Message {
+                    message:
+                        RelayMessage::Ok {
+                            event_id, status, ..
+                        },
+                    ..
+                }) = notifications.recv().await
+                {
+                    if event_id == id {
+                        break status;
+                    }
+                }
+            }
+        })
+        .await
+        .expect("AUTH response not received");
+        assert!(accepted, "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_automatic_authentication_with_auth_signer() {
+    for relay in RelayImpl::ALL.into_iter().filter(|r| r.auth()) {
+        let test_relay = TestRelay::start(relay);
+        let device_keys = Keys::generate();
+        let opts = Options::new()
+            .auth_signer(&device_keys)
+            .automatic_authentication(true);
+        let client = Client::with_opts(Keys::generate(), opts);
+        client.add_relay(test_relay.url.clone()).await.unwrap();
+
+        let mut notifications = client.notifications();
+        client.connect().await;
+
+        // The only event sent to the relay is the AUTH one
+        let accepted: bool = tokio::time::timeout(TIMEOUT, async {
+            loop {
+                if let Ok(RelayPoolNotification::Message {
+                    message: RelayMessage::Ok { status, .. },
+                    ..
+                }) = notifications.recv().await
+                {
+                    break status;
+                }
+            }
+        })
+        .await
+        .expect("AUTH response not received");
+        assert!(accepted, "{}", relay.name());
+        let auth_signer = client.auth_signer().await.unwrap();
+        assert_eq!(
+            auth_signer.get_public_key().await.unwrap(),
+            device_keys.public_key()
+        );
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_connect_report() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let unreachable = Url::parse("ws://127.0.0.1:1").unwrap();
+        let client = Client::new(Keys::generate());
+        client.add_relay(test_relay.url.clone()).await.unwrap();
+        client.add_relay(unreachable.clone()).await.unwrap();
+
+        let report = client.connect_with_timeout(TIMEOUT).await;
+        assert!(
+            report.connected.contains(&test_relay.url),
+            "{}",
+            relay.name()
+        );
+        assert!(report.failed.contains_key(&unreachable), "{}", relay.name());
+        assert!(!report.is_all_connected(), "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_relay_labels() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let client = test_relay
+            .client(&keys, RelayOptions::new().labels(["archive"]))
+            .await;
+
+        let event = EventBuilder::new_text_note("Archived note", [])
+            .to_event(&keys)
+            .unwrap();
+        let results = client
+            .send_event_to_label("archive", event.clone())
+            .await
+            .unwrap();
+        assert!(
+            results.get(&test_relay.url).unwrap().is_ok(),
+            "{}",
+            relay.name()
+        );
+        assert!(client.send_event_to_label("dm", event).await.is_err());
+
+        let filter = Filter::new().author(keys.public_key());
+        let events = client
+            .get_events_from_label("archive", vec![filter], Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1, "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_shared_seen_tracker() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let writer = test_relay.client(&keys, RelayOptions::new()).await;
+        let ids = publish_notes(&writer, 1).await;
+
+        // Two clients sharing the tracker and the hub, i.e. the FFI client and an embedded component
+        let first = test_relay
+            .client(&Keys::generate(), RelayOptions::new())
+            .await;
+        let pool_opts = RelayPoolOptions::new()
+            .seen_tracker(first.seen_tracker())
+            .notification_hub(first.notification_hub());
+        let second = Client::with_opts(Keys::generate(), Options::new().pool(pool_opts));
+        second.add_relay(test_relay.url.clone()).await.unwrap();
+        second.connect().await;
+
+        let mut notifications = first.notifications();
+        let filter = Filter::new().author(keys.public_key()).kind(Kind::TextNote);
+        first.subscribe(vec![filter.clone()]).await;
+        second.subscribe(vec![filter]).await;
+
+        let mut received: Vec<EventId> = Vec::new();
+        let _ = tokio::time::timeout(TIMEOUT, async {
+            while let Ok(notification) = notifications.recv().await {
+                if let RelayPoolNotification::Event { event, .. } = notification {
+                    received.push(event.id);
+                }
+            }
+        })
+        .await;
+        assert_eq!(received.len(), 1, "{}", relay.name());
+        assert!(ids.contains(&received[0]), "{}", relay.name());
+        assert!(second.seen_tracker().contains(&received[0]));
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_private_msg() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let sender_keys = Keys::generate();
+        let sender = test_relay.client(&sender_keys, RelayOptions::new()).await;
+
+        let receiver_keys = Keys::generate();
+        let receiver = Client::with_opts(&receiver_keys, Options::new().unwrap_gift_wraps(true));
+        receiver.add_relay(test_relay.url.clone()).await.unwrap();
+        receiver.connect().await;
+
+        let mut notifications = receiver.notifications();
+        let filter = Filter::new()
+            .kind(Kind::GiftWrap)
+            .pubkey(receiver_keys.public_key());
+        receiver.subscribe(vec![filter]).await;
+
+        // No DM relays published: sent to the client relays
+        sender
+            .send_private_msg(receiver_keys.public_key(), "Private message", None)
+            .await
+            .unwrap();
+
+        let (from, rumor) = tokio::time::timeout(TIMEOUT, async {
+            loop {
+                if let Ok(RelayPoolNotification::PrivateMessage { sender, rumor, .. }) =
+                    notifications.recv().await
+                {
+                    break (sender, rumor);
+                }
+            }
+        })
+        .await
+        .expect("private message not received");
+        assert_eq!(from, sender_keys.public_key(), "{}", relay.name());
+        assert_eq!(rumor.kind, Kind::PrivateDirectMessage, "{}", relay.name());
+        assert_eq!(rumor.content, "Private message", "{}", relay.name());
+
+        // The sender keeps a copy
+        let filter = Filter::new()
+            .kind(Kind::GiftWrap)
+            .pubkey(sender_keys.public_key());
+        let events = sender
+            .get_events_of(vec![filter], Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1, "{}", relay.name());
+        let unwrapped = sender.unwrap_gift_wrap(&events[0]).await.unwrap();
+        assert_eq!(unwrapped.rumor, rumor, "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_follow_unfollow() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let client = test_relay.client(&keys, RelayOptions::new()).await;
+
+        let alice = Keys::generate().public_key();
+        let bob = Keys::generate().public_key();
+        client.follow(alice, None, Some("alice")).await.unwrap();
+        client.follow(bob, None, None::<String>).await.unwrap();
+        client.unfollow(alice).await.unwrap();
+
+        let contacts = client.get_contact_list(Some(TIMEOUT)).await.unwrap();
+        let public_keys: Vec<XOnlyPublicKey> = contacts.iter().map(|c| c.pk).collect();
+        assert_eq!(public_keys, vec![bob], "{}", relay.name());
+
+        // Another device publishes a newer list
+        let other_device = test_relay.client(&keys, RelayOptions::new()).await;
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        other_device
+            .follow(alice, None, None::<String>)
+            .await
+            .unwrap();
+        let res = client.set_contact_list(contacts).await;
+        assert!(
+            matches!(
+                res,
+                Err(nostr_sdk::client::Error::ContactListConflict { .. })
+            ),
+            "{}",
+            relay.name()
+        );
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_undo_send() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let opts = RelayOptions::new().send_delay(Some(std::time::Duration::from_secs(2)));
+        let client = test_relay.client(&keys, opts).await;
+        let relay_handle = client.relay(test_relay.url.clone()).await.unwrap();
+
+        let cancelled = EventBuilder::new_text_note("Cancelled note", [])
+            .to_event(&keys)
+            .unwrap();
+        let sent = EventBuilder::new_text_note("Sent note", [])
+            .to_event(&keys)
+            .unwrap();
+        relay_handle
+            .batch_msg(
+                vec![
+                    ClientMessage::new_event(cancelled.clone()),
+                    ClientMessage::new_event(sent.clone()),
+                ],
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(relay_handle.queued_messages().len(), 1, "{}", relay.name());
+
+        let relays = client.cancel_queued_event(cancelled.id).await;
+        assert!(relays.contains(&test_relay.url), "{}", relay.name());
+
+        tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+        assert!(
+            relay_handle.queued_messages().is_empty(),
+            "{}",
+            relay.name()
+        );
+
+        let filter = Filter::new().author(keys.public_key());
+        let events = client
+            .get_events_of(vec![filter], Some(TIMEOUT))
+            .await
+            .unwrap();
+        let ids: Vec<EventId> = events.into_iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![sent.id], "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_sync_relay_list() {
+    for relay in RelayImpl::ALL {
+        let first = TestRelay::start(relay);
+        let second = TestRelay::start(relay);
+        let keys = Keys::generate();
+
+        let client = Client::with_opts(
+            &keys,
+            Options::new()
+                .wait_for_connection(true)
+                .sync_relay_list(true),
+        );
+        client.add_relay(first.url.clone()).await.unwrap();
+        client.connect().await;
+        client
+            .subscribe(vec![Filter::new()
+                .author(keys.public_key())
+                .kind(Kind::RelayList)])
+            .await;
+
+        client
+            .publish_relay_list([
+                (
+                    UncheckedUrl::from(first.url.clone()),
+                    Some(RelayMetadata::Read),
+                ),
+                (UncheckedUrl::from(second.url.clone()), None),
+            ])
+            .await
+            .unwrap();
+
+        let relays = client.relays().await;
+        assert_eq!(relays.len(), 2, "{}", relay.name());
+
+        // The first relay is read-only now
+        let note = EventBuilder::new_text_note("GM", [])
+            .to_event(&keys)
+            .unwrap();
+        assert!(
+            client
+                .send_event_to(first.url.clone(), note.clone())
+                .await
+                .is_err(),
+            "{}",
+            relay.name()
+        );
+        assert!(
+            relays[&second.url].has_label(RELAY_LIST_LABEL),
+            "{}",
+            relay.name()
+        );
+
+        // Another device drops the second relay
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        let other_device = first.client(&keys, RelayOptions::new()).await;
+        other_device
+            .publish_relay_list([(UncheckedUrl::from(first.url.clone()), None)])
+            .await
+            .unwrap();
+
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        let relays = client.relays().await;
+        assert_eq!(relays.len(), 1, "{}", relay.name());
+        assert!(
+            client.send_event_to(first.url.clone(), note).await.is_ok(),
+            "{}",
+            relay.name()
+        );
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_delete_events() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let client = test_relay.client(&keys, RelayOptions::new()).await;
+
+        let ids: Vec<EventId> = publish_notes(&client, 3).await.into_iter().collect();
+        let deletion_id = client
+            .delete_events(ids[..2].iter().copied(), Some("mistake"))
+            .await
+            .unwrap();
+
+        let events = client
+            .get_events_of(vec![Filter::new().ids(ids.clone())], Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1, "{}", relay.name());
+        assert_eq!(events[0].id, ids[2], "{}", relay.name());
+
+        let deletion = client
+            .get_events_of(vec![Filter::new().id(deletion_id)], Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert_eq!(deletion[0].content, "mistake", "{}", relay.name());
+        assert_eq!(deletion[0].event_ids().count(), 2, "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_repost_and_quote() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let client = test_relay.client(&keys, RelayOptions::new()).await;
+
+        let note = EventBuilder::new_text_note("GM", [])
+            .to_event(&keys)
+            .unwrap();
+        let metadata = EventBuilder::set_metadata(&Metadata::new().name("alice"))
+            .to_event(&keys)
+            .unwrap();
+
+        let repost_id = client.repost(&note, None).await.unwrap();
+        let generic_id = client.repost(&metadata, None).await.unwrap();
+        let quote_id = client.quote(&note, "So true").await.unwrap();
+
+        let events = client
+            .get_events_of(
+                vec![Filter::new().ids([repost_id, generic_id, quote_id])],
+                Some(TIMEOUT),
+            )
+            .await
+            .unwrap();
+        let get = |id: EventId| events.iter().find(|e| e.id == id).unwrap();
+
+        let repost = get(repost_id);
+        assert_eq!(repost.kind, Kind::Repost, "{}", relay.name());
+        assert_eq!(
+            Event::from_json(&repost.content).unwrap(),
+            note,
+            "{}",
+            relay.name()
+        );
+        assert_eq!(
+            get(generic_id).kind,
+            Kind::GenericRepost,
+            "{}",
+            relay.name()
+        );
+        assert_eq!(
+            get(generic_id).reposted_event(),
+            Some(metadata),
+            "{}",
+            relay.name()
+        );
+
+        let quote = get(quote_id);
+        assert!(quote.is_quote(), "{}", relay.name());
+        assert!(quote.content.starts_with("So true"), "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_reactions() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let author = Keys::generate();
+        let client = test_relay.client(&author, RelayOptions::new()).await;
+        let note_id = client.publish_text_note("GM", []).await.unwrap();
+        let note = client
+            .get_events_of(vec![Filter::new().id(note_id)], Some(TIMEOUT))
+            .await
+            .unwrap()
+            .remove(0);
+
+        let emoji = Reaction::custom_emoji(
+            "soapbox",
+            UncheckedUrl::from("https://example.com/soapbox.png"),
+        );
+        for reaction in [Reaction::Like, emoji.clone()] {
+            let keys = Keys::generate();
+            let client = test_relay.client(&keys, RelayOptions::new()).await;
+            client.react(&note, reaction).await.unwrap();
+            client.react(&note, "+").await.unwrap();
+        }
+
+        let reactions = client.reactions_of(note_id, Some(TIMEOUT)).await.unwrap();
+        assert_eq!(reactions.count(&Reaction::Like), 2, "{}", relay.name());
+        assert_eq!(reactions.count(&emoji), 1, "{}", relay.name());
+        assert_eq!(reactions.len(), 3, "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_fetch_thread() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let client = test_relay.client(&keys, RelayOptions::new()).await;
+
+        let tag = |event_id: EventId, marker: Marker| Tag::Event {
+            event_id,
+            relay_url: None,
+            marker: Some(marker),
+        };
+
+        let root = client.publish_text_note("root", []).await.unwrap();
+        let reply = client
+            .publish_text_note("reply", [tag(root, Marker::Root)])
+            .await
+            .unwrap();
+        let nested = client
+            .publish_text_note(
+                "nested",
+                [tag(root, Marker::Root), tag(reply, Marker::Reply)],
+            )
+            .await
+            .unwrap();
+
+        // Reply to an event never published
+        let missing = EventId::all_zeros();
+        let orphan = client
+            .publish_text_note(
+                "orphan",
+                [tag(root, Marker::Root), tag(missing, Marker::Reply)],
+            )
+            .await
+            .unwrap();
+
+        let thread = client.fetch_thread(nested, Some(TIMEOUT)).await.unwrap();
+        assert_eq!(thread.root.id, root, "{}", relay.name());
+        assert_eq!(thread.ancestors(), vec![root, reply], "{}", relay.name());
+        assert_eq!(thread.missing(), vec![missing], "{}", relay.name());
+        assert_eq!(
+            thread.find(&missing).unwrap().replies[0].id,
+            orphan,
+            "{}",
+            relay.name()
+        );
+        assert_eq!(thread.len(), 5, "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_fetch_metadatas() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+
+        let alice = Keys::generate();
+        let bob = Keys::generate();
+        let unknown = Keys::generate().public_key();
+        for (keys, name) in [(&alice, "alice"), (&bob, "bob")] {
+            let client = test_relay.client(keys, RelayOptions::new()).await;
+            client
+                .set_metadata(&Metadata::new().name(name))
+                .await
+                .unwrap();
+        }
+
+        let client = test_relay
+            .client(&Keys::generate(), RelayOptions::new())
+            .await;
+        let metadatas = client
+            .fetch_metadatas(
+                [alice.public_key(), bob.public_key(), unknown],
+                Some(TIMEOUT),
+            )
+            .await
+            .unwrap();
+        assert_eq!(metadatas.len(), 2, "{}", relay.name());
+        assert_eq!(
+            metadatas[&alice.public_key()].name.as_deref(),
+            Some("alice"),
+            "{}",
+            relay.name()
+        );
+
+        // Served from the cache: the newer metadata is not fetched until the TTL expires
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        let alice_client = test_relay.client(&alice, RelayOptions::new()).await;
+        alice_client
+            .set_metadata(&Metadata::new().name("alice2"))
+            .await
+            .unwrap();
+        let metadata = client
+            .fetch_metadata(alice.public_key(), Some(TIMEOUT))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata.name.as_deref(), Some("alice"), "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_lists() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let client = test_relay.client(&keys, RelayOptions::new()).await;
+
+        let muted = Keys::generate().public_key();
+        client
+            .add_to_list(List::Mute, [Tag::Hashtag(String::from("spam"))], false)
+            .await
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+        // Another device mutes a user privately: the hashtag must be preserved
+        let other_device = test_relay.client(&keys, RelayOptions::new()).await;
+        other_device
+            .add_to_list(List::Mute, [Tag::public_key(muted)], true)
+            .await
+            .unwrap();
+
+        let items = client.get_list(List::Mute, Some(TIMEOUT)).await.unwrap();
+        assert_eq!(
+            items.public,
+            vec![Tag::Hashtag(String::from("spam"))],
+            "{}",
+            relay.name()
+        );
+        assert_eq!(
+            items.private,
+            vec![Tag::public_key(muted)],
+            "{}",
+            relay.name()
+        );
+
+        let mute_list = client.sync_mute_list(Some(TIMEOUT)).await.unwrap();
+        assert!(mute_list.public_keys.contains(&muted), "{}", relay.name());
+
+        // Bookmark sets are distinguished by the identifier
+        let set = List::BookmarkSet(String::from("articles"));
+        client
+            .add_to_list(set.clone(), [Tag::Hashtag(String::from("rust"))], false)
+            .await
+            .unwrap();
+        let items = client.get_list(set, Some(TIMEOUT)).await.unwrap();
+        assert_eq!(items.len(), 1, "{}", relay.name());
+        let items = client
+            .get_list(List::BookmarkSet(String::from("other")), Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert!(items.is_empty(), "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_event_size_limit() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let client = test_relay
+            .client(&Keys::generate(), RelayOptions::new())
+            .await;
+
+        let content: String = "a".repeat(MAX_EVENT_BYTES * 2);
+        let res = client.publish_text_note(content, []).await;
+        assert!(res.is_err(), "{}", relay.name());
+
+        // The connection must survive the rejection
+        let res = client.publish_text_note("small note", []).await;
+        assert!(res.is_ok(), "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_middleware() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let client = test_relay.client(&keys, RelayOptions::new()).await;
+        client.add_middleware(ClientTag).await;
+
+        let id = client.publish_text_note("tagged", []).await.unwrap();
+        let events = client
+            .get_events_of(vec![Filter::new().id(id)], Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1, "{}", relay.name());
+        assert!(events[0].verify().is_ok(), "{}", relay.name());
+        assert!(
+            events[0]
+                .tags
+                .iter()
+                .any(|t| t.kind() == TagKind::Custom(String::from("client"))),
+            "{}",
+            relay.name()
+        );
+
+        // Drop own events on receive
+        client.add_middleware(DropAuthor(keys.public_key())).await;
+        let events = client
+            .get_events_of(vec![Filter::new().id(id)], Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert!(events.is_empty(), "{}", relay.name());
+
+        client.clear_middlewares().await;
+        let id = client.publish_text_note("untagged", []).await.unwrap();
+        let events = client
+            .get_events_of(vec![Filter::new().id(id)], Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1, "{}", relay.name());
+        assert!(events[0].tags.is_empty(), "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_notification_handler() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let author = Keys::generate();
+        let sender = test_relay.client(&author, RelayOptions::new()).await;
+        let receiver = test_relay
+            .client(&Keys::generate(), RelayOptions::new())
+            .await;
+
+        let shutdown = ShutdownFlag::default();
+        let flag = shutdown.0.clone();
+        receiver.add_notification_handler(shutdown);
+
+        receiver
+            .subscribe(vec![Filter::new().author(author.public_key())])
+            .await;
+        publish_notes(&sender, 1).await;
+
+        let handler = WaitForAuthor(author.public_key());
+        tokio::time::timeout(TIMEOUT, receiver.handle_notifications_with(&handler))
+            .await
+            .expect("event not received")
+            .unwrap();
+
+        receiver.shutdown().await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        assert!(flag.load(Ordering::SeqCst), "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_get_events_paged() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let client = test_relay.client(&keys, RelayOptions::new()).await;
+
+        // Distinct timestamps, so every page can advance
+        let now = Timestamp::now();
+        let mut ids = HashSet::new();
+        for i in 0..5u64 {
+            let builder = EventBuilder::new_text_note(format!("Paged note {i}"), [])
+                .custom_created_at(now - i);
+            ids.insert(client.send_event_builder(builder).await.unwrap());
+        }
+
+        let filter = Filter::new().author(keys.public_key());
+        let pages: Vec<Vec<Event>> = client
+            .get_events_paged(vec![filter], 2, Some(TIMEOUT))
+            .map(|page| page.unwrap())
+            .collect()
+            .await;
+        let sizes: Vec<usize> = pages.iter().map(|p| p.len()).collect();
+        assert_eq!(sizes, vec![2, 2, 1], "{}", relay.name());
+
+        let received: HashSet<EventId> = pages.into_iter().flatten().map(|e| e.id).collect();
+        assert_eq!(received, ids, "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_search() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let client = test_relay.client(&keys, RelayOptions::new()).await;
+        let r = client.relay(test_relay.url.clone()).await.unwrap();
+        common::wait_for_document(&r).await;
+
+        let id = client
+            .publish_text_note("Searching for nostr", [])
+            .await
+            .unwrap();
+        client
+            .publish_text_note("Something else", [])
+            .await
+            .unwrap();
+
+        let filter = Filter::new().author(keys.public_key());
+        let res = client.search("nostr", filter, Some(TIMEOUT)).await;
+        if r.supports_nip(50).await {
+            let results = res.unwrap();
+            assert_eq!(results.len(), 1, "{}", relay.name());
+            assert_eq!(results[0].event.id, id, "{}", relay.name());
+            assert!(
+                results[0].relays.contains(&test_relay.url),
+                "{}",
+                relay.name()
+            );
+        } else {
+            assert!(
+                matches!(res, Err(nostr_sdk::client::Error::SearchNotSupported)),
+                "{}",
+                relay.name()
+            );
+        }
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_conversations() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let alice_keys = Keys::generate();
+        let alice = test_relay.client(&alice_keys, RelayOptions::new()).await;
+        let bob_keys = Keys::generate();
+        let bob = test_relay.client(&bob_keys, RelayOptions::new()).await;
+
+        alice
+            .send_direct_msg(bob_keys.public_key(), "Legacy DM", None)
+            .await
+            .unwrap();
+        alice
+            .send_private_msg(bob_keys.public_key(), "Private DM", None)
+            .await
+            .unwrap();
+
+        // Sent messages are tracked without syncing
+        let conversation = alice
+            .conversations()
+            .get(&bob_keys.public_key())
+            .await
+            .unwrap();
+        assert_eq!(conversation.messages().len(), 2, "{}", relay.name());
+        assert_eq!(conversation.unread_count(), 0, "{}", relay.name());
+
+        let new = bob.sync_conversations(Some(TIMEOUT)).await.unwrap();
+        assert_eq!(new, 2, "{}", relay.name());
+        let conversations = bob.conversations();
+        let conversation = conversations.get(&alice_keys.public_key()).await.unwrap();
+        let kinds: Vec<Kind> = conversation.messages().iter().map(|m| m.kind).collect();
+        assert!(
+            kinds.contains(&Kind::EncryptedDirectMessage),
+            "{}",
+            relay.name()
+        );
+        assert!(
+            kinds.contains(&Kind::PrivateDirectMessage),
+            "{}",
+            relay.name()
+        );
+        assert_eq!(conversations.unread_count().await, 2, "{}", relay.name());
+
+        conversations.mark_as_read(&alice_keys.public_key()).await;
+        assert_eq!(conversations.unread_count().await, 0, "{}", relay.name());
+
+        // Already known
+        let new = bob.sync_conversations(Some(TIMEOUT)).await.unwrap();
+        assert_eq!(new, 0, "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_watch_live_event() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let host_keys = Keys::generate();
+        let host = test_relay.client(&host_keys, RelayOptions::new()).await;
+        let viewer = test_relay
+            .client(&Keys::generate(), RelayOptions::new())
+            .await;
+
+        let mut live_event = LiveEvent::new("stream");
+        live_event.status = Some(LiveEventStatus::Planned);
+        host.send_event_builder(EventBuilder::live_event(live_event.clone()))
+            .await
+            .unwrap();
+
+        let coordinate =
+            Coordinate::new(Kind::LiveEvent, host_keys.public_key()).identifier("stream");
+        let mut watcher = viewer.watch_live_event(coordinate).await.unwrap();
+
+        assert_eq!(
+            next_live_update(&mut watcher).await,
+            LiveEventUpdate::Status(LiveEventStatus::Planned),
+            "{}",
+            relay.name()
+        );
+        assert!(matches!(
+            next_live_update(&mut watcher).await,
+            LiveEventUpdate::Participants { .. }
+        ));
+
+        // Chat message
+        host.send_event_builder(EventBuilder::live_event_msg(
+            "stream",
+            host_keys.public_key(),
+            "Welcome!",
+            None,
+            Vec::new(),
+        ))
+        .await
+        .unwrap();
+        match next_live_update(&mut watcher).await {
+            LiveEventUpdate::Message(event) => assert_eq!(event.content, "Welcome!"),
+            update => panic!("{}: unexpected update {update:?}", relay.name()),
+        }
+
+        // Status change (newer timestamp)
+        live_event.status = Some(LiveEventStatus::Live);
+        host.send_event_builder(
+            EventBuilder::live_event(live_event).custom_created_at(Timestamp::now() + 1u64),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            next_live_update(&mut watcher).await,
+            LiveEventUpdate::Status(LiveEventStatus::Live),
+            "{}",
+            relay.name()
+        );
+
+        watcher.stop().await;
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_badges() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let issuer = test_relay
+            .client(&Keys::generate(), RelayOptions::new())
+            .await;
+        let keys = Keys::generate();
+        let client = test_relay.client(&keys, RelayOptions::new()).await;
+
+        let mut awards: Vec<Event> = Vec::new();
+        for badge_id in ["bravery", "honor"] {
+            let builder = EventBuilder::define_badge(badge_id, None, None, None, None, Vec::new());
+            let definition_id = issuer.send_event_builder(builder).await.unwrap();
+            let definition = issuer
+                .get_events_of(vec![Filter::new().id(definition_id)], Some(TIMEOUT))
+                .await
+                .unwrap()
+                .remove(0);
+            let award_id = issuer
+                .award_badge(&definition, [keys.public_key()])
+                .await
+                .unwrap();
+            awards.extend(
+                client
+                    .get_events_of(vec![Filter::new().id(award_id)], Some(TIMEOUT))
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        client
+            .set_profile_badges([awards[0].clone()])
+            .await
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        // The first award is already accepted
+        client.set_profile_badges(awards.clone()).await.unwrap();
+
+        let badges = client
+            .fetch_badges(keys.public_key(), Some(TIMEOUT))
+            .await
+            .unwrap();
+        let identifiers: Vec<&str> = badges
+            .iter()
+            .filter_map(|badge| badge.definition.identifier())
+            .collect();
+        assert_eq!(identifiers, vec!["bravery", "honor"], "{}", relay.name());
+        assert_eq!(badges[1].award.id, awards[1].id, "{}", relay.name());
+
+        // Not awarded to the signer
+        let res = issuer.set_profile_badges([awards[0].clone()]).await;
+        assert!(
+            matches!(res, Err(nostr_sdk::client::Error::NIP58(..))),
+            "{}",
+            relay.name()
+        );
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_channel() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let client = test_relay
+            .client(&Keys::generate(), RelayOptions::new())
+            .await;
+        let other = test_relay
+            .client(&Keys::generate(), RelayOptions::new())
+            .await;
+        let relay_url = test_relay.url.clone();
+
+        let channel_id = client
+            .new_channel(&Metadata::new().name("nostr"))
+            .await
+            .unwrap();
+        client
+            .set_channel_metadata(channel_id, None, &Metadata::new().name("rust-nostr"))
+            .await
+            .unwrap();
+        // Not the creator
+        other
+            .set_channel_metadata(channel_id, None, &Metadata::new().name("spam"))
+            .await
+            .unwrap();
+
+        let channel = client
+            .fetch_channel(channel_id, Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert_eq!(
+            channel.metadata.name.as_deref(),
+            Some("rust-nostr"),
+            "{}",
+            relay.name()
+        );
+        assert!(channel.updated_at.is_some(), "{}", relay.name());
+
+        let msg_id = client
+            .send_channel_msg(channel_id, relay_url.clone(), "GM")
+            .await
+            .unwrap();
+        let msg = other
+            .get_events_of(vec![Filter::new().id(msg_id)], Some(TIMEOUT))
+            .await
+            .unwrap()
+            .remove(0);
+        let reply_id = other
+            .reply_channel_msg(channel_id, relay_url, &msg, "GM!")
+            .await
+            .unwrap();
+
+        let replies = client
+            .get_events_of(
+                vec![Filter::new().kind(Kind::ChannelMessage).event(msg_id)],
+                Some(TIMEOUT),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            replies.iter().map(|e| e.id).collect::<Vec<_>>(),
+            vec![reply_id],
+            "{}",
+            relay.name()
+        );
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_set_signer() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let alice = Keys::generate();
+        let bob = Keys::generate();
+        let client = test_relay.client(&alice, RelayOptions::new()).await;
+        let other_keys = Keys::generate();
+        let other = test_relay.client(&other_keys, RelayOptions::new()).await;
+
+        client
+            .send_direct_msg(other_keys.public_key(), "Hi", None)
+            .await
+            .unwrap();
+        client
+            .subscribe(vec![Filter::new()
+                .kind(Kind::TextNote)
+                .pubkey(alice.public_key())])
+            .await;
+
+        client.set_signer(Some(bob.clone().into())).await;
+        assert!(
+            client.conversations().list().await.is_empty(),
+            "{}",
+            relay.name()
+        );
+        assert_eq!(
+            client.pool().subscription_filters().await,
+            vec![Filter::new().kind(Kind::TextNote).pubkey(bob.public_key())],
+            "{}",
+            relay.name()
+        );
+
+        // The subscription now matches the mentions of the new identity
+        let author = other_keys.public_key();
+        let handle = tokio::spawn({
+            let client = client.clone();
+            async move {
+                client
+                    .handle_notifications_with(&WaitForAuthor(author))
+                    .await
+            }
+        });
+        other
+            .publish_text_note("GM bob", [Tag::public_key(bob.public_key())])
+            .await
+            .unwrap();
+        tokio::time::timeout(TIMEOUT, handle)
+            .await
+            .expect("mention not received")
+            .unwrap()
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_outbox() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let client = Client::with_opts(&keys, Options::new().outbox(true));
+
+        // No relays: the event is kept in the outbox
+        let res = client.publish_text_note("Sent later", []).await;
+        assert!(res.is_err(), "{}", relay.name());
+        let pending = client.outbox().pending().await;
+        assert_eq!(pending.len(), 1, "{}", relay.name());
+        let event_id = pending[0].id;
+
+        client.add_relay(test_relay.url.clone()).await.unwrap();
+        client.connect().await;
+
+        let outbox = client.outbox();
+        tokio::time::timeout(TIMEOUT, async {
+            while !outbox.is_empty().await {
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .expect("outbox not flushed");
+
+        let reader = test_relay
+            .client(&Keys::generate(), RelayOptions::new())
+            .await;
+        let events = reader
+            .get_events_of(vec![Filter::new().id(event_id)], Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1, "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_accounts() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let alice = Keys::generate();
+        let bob = Keys::generate();
+        let client = Client::new(&alice);
+
+        let public_key = client.add_account(&bob).await.unwrap();
+        assert_eq!(public_key, bob.public_key());
+        assert_eq!(client.active_account().await, Some(alice.public_key()));
+
+        client
+            .add_account_relay(&public_key, test_relay.url.clone())
+            .await
+            .unwrap();
+        client.connect().await;
+
+        let event_id = client
+            .send_event_builder_as(&public_key, EventBuilder::new_text_note("Hi from bob", []))
+            .await
+            .unwrap();
+        let events = client
+            .get_events_of_as(&public_key, vec![Filter::new().id(event_id)], Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1, "{}", relay.name());
+        assert_eq!(events[0].pubkey, bob.public_key(), "{}", relay.name());
+
+        client.switch_account(&public_key).await.unwrap();
+        assert_eq!(client.active_account().await, Some(bob.public_key()));
+
+        assert!(client.remove_account(&public_key).await);
+        assert!(client
+            .relays_with_label(&account_label(&public_key))
+            .await
+            .is_empty());
+        assert!(matches!(
+            client.switch_account(&public_key).await,
+            Err(nostr_sdk::client::Error::AccountNotFound(..))
+        ));
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_rebroadcast() {
+    for relay in RelayImpl::ALL {
+        let old_relay = TestRelay::start(relay);
+        let new_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let client = old_relay.client(&keys, RelayOptions::new()).await;
+        let ids = publish_notes(&client, 3).await;
+
+        client.add_relay(new_relay.url.clone()).await.unwrap();
+        client.connect().await;
+
+        let filter = Filter::new().kind(Kind::TextNote);
+        let report = client
+            .rebroadcast(filter.clone(), Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert_eq!(report[&old_relay.url].present, 3, "{}", relay.name());
+        assert!(report[&old_relay.url].sent.is_empty(), "{}", relay.name());
+        let sent: HashSet<EventId> = report[&new_relay.url].sent.iter().copied().collect();
+        assert_eq!(sent, ids, "{}", relay.name());
+
+        let reader = new_relay
+            .client(&Keys::generate(), RelayOptions::new())
+            .await;
+        let events = reader
+            .get_events_of(vec![filter.author(keys.public_key())], Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 3, "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_fetch_latest_replaceable() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let writer = test_relay.client(&keys, RelayOptions::new()).await;
+
+        let now = Timestamp::now();
+        for (content, created_at) in [("old", now - 10u64), ("new", now)] {
+            let builder = EventBuilder::new(
+                Kind::ParameterizedReplaceable(30078),
+                content,
+                [Tag::Identifier(String::from("settings"))],
+            )
+            .custom_created_at(created_at);
+            writer.send_event_builder(builder).await.unwrap();
+        }
+
+        let reader = Client::with_opts(
+            Keys::generate(),
+            Options::new().pool(RelayPoolOptions::new().only_latest_replaceable(true)),
+        );
+        reader.add_relay(test_relay.url.clone()).await.unwrap();
+        reader.connect().await;
+
+        let coordinate = Coordinate::new(Kind::ParameterizedReplaceable(30078), keys.public_key())
+            .identifier("settings");
+        let event = reader
+            .fetch_latest_replaceable(coordinate, Some(TIMEOUT))
+            .await
+            .unwrap()
+            .expect("replaceable event not found");
+        assert_eq!(event.content, "new", "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_schedule_event() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let client = test_relay.client(&keys, RelayOptions::new()).await;
+
+        let publish_at = Timestamp::now() + 2u64;
+        let event = EventBuilder::new_text_note("Scheduled", [])
+            .to_event(&keys)
+            .unwrap();
+        let cancelled = EventBuilder::new_text_note("Cancelled", [])
+            .to_event(&keys)
+            .unwrap();
+        client.schedule_event(event.clone(), publish_at).await;
+        client.schedule_event(cancelled.clone(), publish_at).await;
+        assert_eq!(client.scheduled_events().await.len(), 2, "{}", relay.name());
+        assert!(client.cancel_scheduled_event(&cancelled.id).await);
+
+        // Not yet published
+        let reader = test_relay
+            .client(&Keys::generate(), RelayOptions::new())
+            .await;
+        let filter = Filter::new().author(keys.public_key());
+        let events = reader
+            .get_events_of(vec![filter.clone()], Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert!(events.is_empty(), "{}", relay.name());
+
+        tokio::time::sleep(std::time::Duration::from_secs(4)).await;
+        assert!(
+            client.scheduled_events().await.is_empty(),
+            "{}",
+            relay.name()
+        );
+        let reader = test_relay
+            .client(&Keys::generate(), RelayOptions::new())
+            .await;
+        let events = reader
+            .get_events_of(vec![filter], Some(TIMEOUT))
+            .await
+            .unwrap();
+        let ids: Vec<EventId> = events.iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![event.id], "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_user_status() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let client = test_relay.client(&keys, RelayOptions::new()).await;
+
+        client
+            .set_status(StatusType::General, "Working", None, None)
+            .await
+            .unwrap();
+        client
+            .set_status(
+                StatusType::Music,
+                "Intergalatic - Beastie Boys",
+                Some(Timestamp::now() + 3600u64),
+                Some(String::from("https://example.com/song")),
+            )
+            .await
+            .unwrap();
+        // Already expired
+        client
+            .set_status(
+                StatusType::from("gaming"),
+                "Playing",
+                Some(Timestamp::now() - 60u64),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let statuses = client
+            .fetch_status(keys.public_key(), Some(TIMEOUT))
+            .await
+            .unwrap();
+        let types: Vec<StatusType> = statuses.iter().map(|s| s.status_type.clone()).collect();
+        assert_eq!(
+            types,
+            vec![StatusType::General, StatusType::Music],
+            "{}",
+            relay.name()
+        );
+
+        // Clear the general status
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        client
+            .set_status(StatusType::General, "", None, None)
+            .await
+            .unwrap();
+        let statuses = client
+            .fetch_status(keys.public_key(), Some(TIMEOUT))
+            .await
+            .unwrap();
+        let types: Vec<StatusType> = statuses.iter().map(|s| s.status_type.clone()).collect();
+        assert_eq!(types, vec![StatusType::Music], "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_report() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let client = test_relay.client(&keys, RelayOptions::new()).await;
+
+        let spammer = Keys::generate();
+        let spam = EventBuilder::new_text_note("Buy now!", [])
+            .to_event(&spammer)
+            .unwrap();
+        let event_id = client
+            .report(&spam, Report::Spam, "Scam link", false)
+            .await
+            .unwrap();
+        client
+            .report(spammer.public_key(), Report::Impersonation, "", true)
+            .await
+            .unwrap();
+
+        let filter = Filter::new()
+            .author(keys.public_key())
+            .kind(Kind::Reporting);
+        let events = client
+            .get_events_of(vec![filter], Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 2, "{}", relay.name());
+        let event = events.iter().find(|e| e.id == event_id).unwrap();
+        assert_eq!(event.content, "Scam link", "{}", relay.name());
+        assert!(
+            event
+                .tags
+                .contains(&Tag::EventReport(spam.id, Report::Spam)),
+            "{}",
+            relay.name()
+        );
+        assert!(
+            event.tags.contains(&Tag::public_key(spammer.public_key())),
+            "{}",
+            relay.name()
+        );
+
+        let mute_list = client.sync_mute_list(Some(TIMEOUT)).await.unwrap();
+        assert!(
+            mute_list.public_keys.contains(&spammer.public_key()),
+            "{}",
+            relay.name()
+        );
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_articles() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let client = test_relay.client(&keys, RelayOptions::new()).await;
+
+        let article = ArticleBuilder::new("hello-nostr", "Work in progress")
+            .title("Hello Nostr")
+            .hashtags(["nostr"])
+            .draft(true);
+        client.publish_article(article).await.unwrap();
+
+        let filter = Filter::new()
+            .author(keys.public_key())
+            .kind(Kind::LongFormTextNoteDraft);
+        let events = client
+            .get_events_of(vec![filter.clone()], Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1, "{}", relay.name());
+        let draft = ArticleBuilder::from_event(&events[0]).unwrap();
+        assert!(draft.published_at.is_none(), "{}", relay.name());
+
+        // Edit and promote the draft
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        let draft = EventBuilder::article(draft.content("Done"))
+            .to_event(&keys)
+            .unwrap();
+        client.send_event(draft.clone()).await.unwrap();
+        client.promote_article_draft(&draft).await.unwrap();
+
+        let filter = Filter::new()
+            .author(keys.public_key())
+            .kind(Kind::LongFormTextNote);
+        let events = client
+            .get_events_of(vec![filter], Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1, "{}", relay.name());
+        let article = ArticleBuilder::from_event(&events[0]).unwrap();
+        assert_eq!(article.identifier, "hello-nostr", "{}", relay.name());
+        assert_eq!(article.content, "Done", "{}", relay.name());
+        assert_eq!(
+            article.title.as_deref(),
+            Some("Hello Nostr"),
+            "{}",
+            relay.name()
+        );
+        assert!(article.published_at.is_some(), "{}", relay.name());
+
+        let filter = Filter::new()
+            .author(keys.public_key())
+            .kind(Kind::EventDeletion);
+        let events = client
+            .get_events_of(vec![filter], Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1, "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_send_event_and_wait() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let client = test_relay.client(&keys, RelayOptions::new()).await;
+
+        let event = EventBuilder::set_metadata(&Metadata::new().name("nostr"))
+            .to_event(&keys)
+            .unwrap();
+        let opts = SendAndWaitOptions::new()
+            .timeout(std::time::Duration::from_secs(5))
+            .interval(std::time::Duration::from_millis(500));
+        let event_id = client.send_event_and_wait(event, opts).await.unwrap();
+
+        // Queryable also by another client
+        let reader = test_relay
+            .client(&Keys::generate(), RelayOptions::new())
+            .await;
+        let events = reader
+            .get_events_of(vec![Filter::new().id(event_id)], Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1, "{}", relay.name());
+
+        // An older metadata is replaced by the relay: never returned
+        let older = EventBuilder::set_metadata(&Metadata::new().name("old"))
+            .custom_created_at(Timestamp::now() - 60u64)
+            .to_event(&keys)
+            .unwrap();
+        let res = client.send_event_and_wait(older, opts).await;
+        assert!(res.is_err(), "{}", relay.name());
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_moderation() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let writer = test_relay
+            .client(&Keys::generate(), RelayOptions::new())
+            .await;
+        let spammer = Keys::generate();
+        let spammer_client = test_relay.client(&spammer, RelayOptions::new()).await;
+
+        let allowed = writer.publish_text_note("Hello", []).await.unwrap();
+        writer.publish_text_note("Free airdrop!", []).await.unwrap();
+        writer
+            .publish_text_note("a".repeat(4096), [])
+            .await
+            .unwrap();
+        writer
+            .send_event_builder(EventBuilder::new(Kind::Custom(1111), "Hello", []))
+            .await
+            .unwrap();
+        spammer_client
+            .publish_text_note("Hello from a muted user", [])
+            .await
+            .unwrap();
+
+        let reader = test_relay
+            .client(&Keys::generate(), RelayOptions::new())
+            .await;
+        reader.moderation().set_filters(
+            ModerationFilters::new()
+                .mute_public_keys([spammer.public_key()])
+                .mute_words(["airdrop"])
+                .max_event_size(1024)
+                .allowed_kinds([Kind::TextNote]),
+        );
+        let events = reader
+            .get_events_of(vec![Filter::new()], Some(TIMEOUT))
+            .await
+            .unwrap();
+        let ids: Vec<EventId> = events.iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![allowed], "{}", relay.name());
+
+        let dropped = reader.moderation().dropped();
+        assert_eq!(reader.moderation().total_dropped(), 4, "{}", relay.name());
+        for reason in [
+            ModerationReason::MutedPublicKey,
+            ModerationReason::MutedWord,
+            ModerationReason::TooLarge,
+            ModerationReason::KindNotAllowed,
+        ] {
+            assert_eq!(dropped.get(&reason), Some(&1), "{}", relay.name());
+        }
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_highlight() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let client = test_relay.client(&keys, RelayOptions::new()).await;
+
+        let author = Keys::generate();
+        let article = EventBuilder::long_form_text_note(
+            "Lorem ipsum dolor sit amet",
+            [Tag::Identifier(String::from("lorem"))],
+        )
+        .to_event(&author)
+        .unwrap();
+        client.send_event(article.clone()).await.unwrap();
+
+        let event_id = client
+            .highlight(&article, "dolor sit", Some("So true"))
+            .await
+            .unwrap();
+        let filter = Filter::new().id(event_id);
+        let events = client
+            .get_events_of(vec![filter], Some(TIMEOUT))
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1, "{}", relay.name());
+
+        let highlight = Highlight::from_event(&events[0]).unwrap();
+        assert_eq!(
+            highlight.comment.as_deref(),
+            Some("So true"),
+            "{}",
+            relay.name()
+        );
+        assert_eq!(
+            highlight.source.as_ref().and_then(|s| s.author()),
+            Some(author.public_key()),
+            "{}",
+            relay.name()
+        );
+        assert_eq!(
+            highlight.locate(&article.content),
+            Some(12..21),
+            "{}",
+            relay.name()
+        );
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires docker"]
+async fn test_find_handlers() {
+    for relay in RelayImpl::ALL {
+        let test_relay = TestRelay::start(relay);
+        let keys = Keys::generate();
+        let client = test_relay.client(&keys, RelayOptions::new()).await;
+
+        let alice = Keys::generate();
+        let bob = Keys::generate();
+        let developer = Keys::generate();
+        client
+            .set_contact_list([
+                Contact::new::<String>(alice.public_key(), None, None),
+                Contact::new::<String>(bob.public_key(), None, None),
+            ])
+            .await
+            .unwrap();
+
+        let reader = HandlerInformation::new("reader")
+            .kinds([Kind::LongFormTextNote])
+            .platform("web", "https://reader.example.com/<bech32>", Some("naddr"));
+        let notes = HandlerInformation::new("notes").kinds([Kind::TextNote]);
+        let other = HandlerInformation::new("other").kinds([Kind::LongFormTextNote]);
+        for info in [reader.clone(), notes.clone(), other.clone()] {
+            let event = EventBuilder::handler_information(info)
+                .to_event(&developer)
+                .unwrap();
+            client.send_event(event).await.unwrap();
+        }
+
+        for (follow, handlers) in [
+            (&alice, vec![&reader, &other]),
+            (&bob, vec![&reader, &notes]),
+        ] {
+            let mut recommendation = HandlerRecommendation::new(Kind::LongFormTextNote);
+            for info in handlers {
+                recommendation =
+                    recommendation.handler(info.coordinate(developer.public_key()), None);
+            }
+            let event = EventBuilder::handler_recommendation(recommendation)
+                .to_event(follow)
+                .unwrap();
+            client.send_event(event).await.unwrap();
+        }
+
+        let apps = client
+            .find_handlers(Kind::LongFormTextNote, Some(TIMEOUT))
+            .await
+            .unwrap();
+        let identifiers: Vec<&str> = apps.iter().map(|a| a.info.identifier.as_str()).collect();
+        assert_eq!(identifiers, vec!["reader", "other"], "{}", relay.name());
+        assert_eq!(apps[0].recommended_by.len(), 2, "{}", relay.name());
+        assert_eq!(
+            apps[0].public_key,
+            developer.public_key(),
+            "{}",
+            relay.name()
+        );
+    }
+}
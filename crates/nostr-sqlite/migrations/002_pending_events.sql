@@ -0,0 +1,5 @@
+PRAGMA user_version = 2; -- Schema version
+
+CREATE TABLE IF NOT EXISTS pending_events (
+    event_id BLOB PRIMARY KEY NOT NULL
+);
@@ -8,6 +8,7 @@
 #![warn(missing_docs)]
 #![warn(rustdoc::bare_urls)]
 
+use std::borrow::Cow;
 use std::collections::{BTreeSet, HashSet};
 use std::path::Path;
 use std::sync::Arc;
@@ -20,7 +21,7 @@ use deadpool_sqlite::{Config, Object, Pool, Runtime};
 use nostr::nips::nip01::Coordinate;
 use nostr::{Event, EventId, Filter, Timestamp, Url};
 use nostr_database::{
-    Backend, DatabaseIndexes, DatabaseOptions, EventIndexResult, FlatBufferBuilder,
+    Backend, DatabaseError, DatabaseIndexes, DatabaseOptions, EventIndexResult, FlatBufferBuilder,
     FlatBufferDecode, FlatBufferEncode, NostrDatabase, Order, RawEvent,
 };
 use rusqlite::config::DbConfig;
@@ -37,12 +38,21 @@ use self::migration::STARTUP_SQL;
 pub struct SQLiteDatabase {
     db: Pool,
     indexes: DatabaseIndexes,
+    opts: DatabaseOptions,
     fbb: Arc<RwLock<FlatBufferBuilder<'static>>>,
 }
 
 impl SQLiteDatabase {
     /// Open SQLite store
     pub async fn open<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::open_with_opts(path, DatabaseOptions::default()).await
+    }
+
+    /// Open SQLite store with [`DatabaseOptions`]
+    pub async fn open_with_opts<P>(path: P, opts: DatabaseOptions) -> Result<Self, Error>
     where
         P: AsRef<Path>,
     {
@@ -56,6 +66,7 @@ impl SQLiteDatabase {
         let this = Self {
             db: pool,
             indexes: DatabaseIndexes::new(),
+            opts,
             fbb: Arc::new(RwLock::new(FlatBufferBuilder::with_capacity(70_000))),
         };
 
@@ -117,11 +128,17 @@ impl NostrDatabase for SQLiteDatabase {
     }
 
     fn opts(&self) -> DatabaseOptions {
-        DatabaseOptions::default()
+        self.opts.clone()
     }
 
     #[tracing::instrument(skip_all, level = "trace")]
     async fn save_event(&self, event: &Event) -> Result<bool, Self::Err> {
+        let event: Cow<Event> = match self.opts.redact_event(event) {
+            Some(event) => event,
+            None => return Ok(false),
+        };
+        let event: &Event = event.as_ref();
+
         // Index event
         let EventIndexResult {
             to_store,
@@ -252,6 +269,40 @@ impl NostrDatabase for SQLiteDatabase {
         .await?
     }
 
+    async fn set_event_pending(&self, event_id: EventId, pending: bool) -> Result<(), Self::Err> {
+        let conn = self.acquire().await?;
+        conn.interact(move |conn| {
+            if pending {
+                conn.execute(
+                    "INSERT OR IGNORE INTO pending_events (event_id) VALUES (?);",
+                    [event_id.to_hex()],
+                )
+            } else {
+                conn.execute(
+                    "DELETE FROM pending_events WHERE event_id = ?;",
+                    [event_id.to_hex()],
+                )
+            }
+        })
+        .await??;
+        Ok(())
+    }
+
+    async fn pending_event_ids(&self) -> Result<BTreeSet<EventId>, Self::Err> {
+        let conn = self.acquire().await?;
+        conn.interact(move |conn| {
+            let mut stmt = conn.prepare_cached("SELECT event_id FROM pending_events;")?;
+            let mut rows = stmt.query([])?;
+            let mut ids = BTreeSet::new();
+            while let Ok(Some(row)) = rows.next() {
+                let id: String = row.get(0)?;
+                ids.insert(EventId::from_hex(id).map_err(DatabaseError::nostr)?);
+            }
+            Ok(ids)
+        })
+        .await?
+    }
+
     #[tracing::instrument(skip_all, level = "trace")]
     async fn event_by_id(&self, event_id: EventId) -> Result<Event, Self::Err> {
         let conn = self.acquire().await?;
@@ -16,5 +16,10 @@ fn main() -> Result<()> {
     let keys = Keys::from_mnemonic(MNEMONIC_PHRASE, Some("mypassphrase"))?;
     println!("{}", keys.secret_key()?.to_bech32()?);
 
+    // Derive multiple accounts
+    for keys in Keys::from_mnemonic_accounts(MNEMONIC_PHRASE, Some("mypassphrase"), 0..3)? {
+        println!("{}", keys.public_key().to_bech32()?);
+    }
+
     Ok(())
 }
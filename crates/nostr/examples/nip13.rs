@@ -27,5 +27,16 @@ fn main() -> Result<()> {
 
     println!("{:#?}", event);
 
+    // Mine on all the cores
+    let cancel = PowCancelToken::new();
+    let event: Event = EventBuilder::new_text_note(msg_content, []).to_pow_event_with(
+        &alice_keys,
+        difficulty,
+        num_cpus::get(),
+        &cancel,
+    )?;
+
+    println!("{:#?}", event);
+
     Ok(())
 }
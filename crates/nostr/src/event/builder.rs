@@ -7,7 +7,21 @@
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::fmt;
+#[cfg(feature = "std")]
+use core::future::Future;
 use core::ops::Range;
+#[cfg(feature = "std")]
+use core::pin::Pin;
+#[cfg(feature = "std")]
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "std")]
+use core::task::{Context, Poll, Waker};
+#[cfg(feature = "std")]
+use std::sync::mpsc::{self, Receiver};
+#[cfg(feature = "std")]
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "std")]
+use std::thread;
 
 #[cfg(feature = "std")]
 use bitcoin::secp256k1::rand;
@@ -22,17 +36,33 @@ use super::{Event, EventId, Marker, Tag, TagKind, UnsignedEvent};
 use crate::key::{self, Keys};
 #[cfg(feature = "nip04")]
 use crate::nips::nip04;
+#[cfg(feature = "std")]
+use crate::nips::nip13::PowCancelToken;
 use crate::nips::nip15::{ProductData, StallData};
+use crate::nips::nip23::ArticleBuilder;
+use crate::nips::nip25::Reaction;
+use crate::nips::nip34::GitPatch;
+use crate::nips::nip38::StatusType;
+#[cfg(all(feature = "std", feature = "nip59"))]
+use crate::nips::nip44::{self, Version as Nip44Version};
 #[cfg(all(feature = "std", feature = "nip46"))]
 use crate::nips::nip46::Message as NostrConnectMessage;
+#[cfg(feature = "nip47")]
+use crate::nips::nip47::Method as NostrWalletConnectMethod;
+#[cfg(all(feature = "std", feature = "nip47"))]
+use crate::nips::nip47::Response as NostrWalletConnectResponse;
 use crate::nips::nip53::LiveEvent;
 #[cfg(feature = "nip57")]
 use crate::nips::nip57::ZapRequestData;
 use crate::nips::nip58::Error as Nip58Error;
+#[cfg(all(feature = "std", feature = "nip59"))]
+use crate::nips::nip59;
+use crate::nips::nip84::Highlight;
+use crate::nips::nip89::{HandlerInformation, HandlerRecommendation};
 use crate::nips::nip90::DataVendingMachineStatus;
 use crate::nips::nip94::FileMetadata;
 use crate::nips::nip98::HttpData;
-use crate::nips::{nip13, nip58};
+use crate::nips::{nip13, nip38, nip58};
 #[cfg(feature = "std")]
 use crate::types::time::Instant;
 use crate::types::time::TimeSupplier;
@@ -77,8 +107,16 @@ pub enum Error {
     /// NIP04 error
     #[cfg(feature = "nip04")]
     NIP04(nip04::Error),
+    /// NIP44 error
+    #[cfg(feature = "nip44")]
+    NIP44(crate::nips::nip44::Error),
     /// NIP58 error
     NIP58(nip58::Error),
+    /// NIP59 error
+    #[cfg(feature = "nip59")]
+    NIP59(crate::nips::nip59::Error),
+    /// POW mining aborted
+    PowCancelled,
     /// Wrong kind
     WrongKind {
         /// The received wrong kind
@@ -102,7 +140,12 @@ impl fmt::Display for Error {
             Self::OpenTimestamps(e) => write!(f, "NIP03: {e}"),
             #[cfg(feature = "nip04")]
             Self::NIP04(e) => write!(f, "NIP04: {e}"),
+            #[cfg(feature = "nip44")]
+            Self::NIP44(e) => write!(f, "NIP44: {e}"),
             Self::NIP58(e) => write!(f, "NIP58: {e}"),
+            #[cfg(feature = "nip59")]
+            Self::NIP59(e) => write!(f, "NIP59: {e}"),
+            Self::PowCancelled => write!(f, "POW mining cancelled"),
             Self::WrongKind { received, expected } => {
                 write!(f, "Wrong kind: received={received}, expected={expected}")
             }
@@ -148,6 +191,20 @@ impl From<nip04::Error> for Error {
     }
 }
 
+#[cfg(feature = "nip44")]
+impl From<crate::nips::nip44::Error> for Error {
+    fn from(e: crate::nips::nip44::Error) -> Self {
+        Self::NIP44(e)
+    }
+}
+
+#[cfg(feature = "nip59")]
+impl From<crate::nips::nip59::Error> for Error {
+    fn from(e: crate::nips::nip59::Error) -> Self {
+        Self::NIP59(e)
+    }
+}
+
 impl From<nip58::Error> for Error {
     fn from(e: nip58::Error) -> Self {
         Self::NIP58(e)
@@ -326,6 +383,180 @@ impl EventBuilder {
     pub fn to_unsigned_pow_event(self, pubkey: XOnlyPublicKey, difficulty: u8) -> UnsignedEvent {
         self.to_unsigned_pow_event_with_supplier(&Instant::now(), pubkey, difficulty)
     }
+
+    /// Build POW [`Event`] mining on `threads` worker threads
+    ///
+    /// Return [`Error::PowCancelled`] if the mining is aborted with the `cancel` token.
+    #[cfg(feature = "std")]
+    pub fn to_pow_event_with(
+        self,
+        keys: &Keys,
+        difficulty: u8,
+        threads: usize,
+        cancel: &PowCancelToken,
+    ) -> Result<Event, Error> {
+        let unsigned: UnsignedEvent =
+            self.to_unsigned_pow_event_with(keys.public_key(), difficulty, threads, cancel)?;
+        Ok(unsigned.sign_with_ctx(&SECP256K1, &mut rand::thread_rng(), keys)?)
+    }
+
+    /// Build unsigned POW [`Event`] mining on `threads` worker threads
+    ///
+    /// Return [`Error::PowCancelled`] if the mining is aborted with the `cancel` token.
+    #[cfg(feature = "std")]
+    pub fn to_unsigned_pow_event_with(
+        self,
+        pubkey: XOnlyPublicKey,
+        difficulty: u8,
+        threads: usize,
+        cancel: &PowCancelToken,
+    ) -> Result<UnsignedEvent, Error> {
+        let stop = Arc::new(AtomicBool::new(false));
+        self.spawn_pow_workers(pubkey, difficulty, threads, cancel, stop)
+            .recv()
+            .map_err(|_| Error::PowCancelled)
+    }
+
+    /// Build POW [`Event`] mining on `threads` worker threads, without blocking the async runtime
+    ///
+    /// Return [`Error::PowCancelled`] if the mining is aborted with the `cancel` token.
+    /// Dropping the future stops the workers.
+    #[cfg(feature = "std")]
+    pub async fn to_pow_event_async(
+        self,
+        keys: &Keys,
+        difficulty: u8,
+        threads: usize,
+        cancel: &PowCancelToken,
+    ) -> Result<Event, Error> {
+        let unsigned: UnsignedEvent = self
+            .to_unsigned_pow_event_async(keys.public_key(), difficulty, threads, cancel)
+            .await?;
+        Ok(unsigned.sign_with_ctx(&SECP256K1, &mut rand::thread_rng(), keys)?)
+    }
+
+    /// Build unsigned POW [`Event`] mining on `threads` worker threads, without blocking the async runtime
+    ///
+    /// Return [`Error::PowCancelled`] if the mining is aborted with the `cancel` token.
+    /// Dropping the future stops the workers.
+    #[cfg(feature = "std")]
+    pub async fn to_unsigned_pow_event_async(
+        self,
+        pubkey: XOnlyPublicKey,
+        difficulty: u8,
+        threads: usize,
+        cancel: &PowCancelToken,
+    ) -> Result<UnsignedEvent, Error> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let rx = self.spawn_pow_workers(pubkey, difficulty, threads, cancel, stop.clone());
+        PowFuture::new(rx, stop).await.ok_or(Error::PowCancelled)
+    }
+
+    /// Spawn the POW workers. Worker `n` tries the nonces `n + 1 + k * threads`.
+    ///
+    /// The channel is closed without result if the mining is cancelled or stopped.
+    #[cfg(feature = "std")]
+    fn spawn_pow_workers(
+        self,
+        pubkey: XOnlyPublicKey,
+        difficulty: u8,
+        threads: usize,
+        cancel: &PowCancelToken,
+        stop: Arc<AtomicBool>,
+    ) -> Receiver<UnsignedEvent> {
+        let threads: usize = threads.max(1);
+        let created_at: Timestamp = self.custom_created_at.unwrap_or_else(Timestamp::now);
+        let (tx, rx) = mpsc::sync_channel::<UnsignedEvent>(1);
+
+        for n in 0..threads {
+            let tx = tx.clone();
+            let stop = stop.clone();
+            let cancel = cancel.clone();
+            let kind: Kind = self.kind;
+            let mut tags: Vec<Tag> = self.tags.clone();
+            let content: String = self.content.clone();
+            thread::spawn(move || {
+                let mut nonce: u128 = n as u128 + 1;
+                while !stop.load(Ordering::Relaxed) && !cancel.is_cancelled() {
+                    tags.push(Tag::POW { nonce, difficulty });
+
+                    let id = EventId::new(&pubkey, created_at, &kind, &tags, &content);
+                    if nip13::get_leading_zero_bits(id.inner()) >= difficulty {
+                        stop.store(true, Ordering::SeqCst);
+                        let _ = tx.try_send(UnsignedEvent {
+                            id,
+                            pubkey,
+                            created_at,
+                            kind,
+                            tags,
+                            content,
+                        });
+                        break;
+                    }
+
+                    tags.pop();
+                    nonce += threads as u128;
+                }
+            });
+        }
+
+        rx
+    }
+}
+
+#[cfg(feature = "std")]
+#[derive(Default)]
+struct PowFutureState {
+    done: bool,
+    result: Option<UnsignedEvent>,
+    waker: Option<Waker>,
+}
+
+/// Wait for the POW workers without blocking the executor
+#[cfg(feature = "std")]
+struct PowFuture {
+    state: Arc<Mutex<PowFutureState>>,
+    stop: Arc<AtomicBool>,
+}
+
+#[cfg(feature = "std")]
+impl PowFuture {
+    fn new(rx: Receiver<UnsignedEvent>, stop: Arc<AtomicBool>) -> Self {
+        let state = Arc::new(Mutex::new(PowFutureState::default()));
+        let shared = state.clone();
+        thread::spawn(move || {
+            let result: Option<UnsignedEvent> = rx.recv().ok();
+            let mut state = shared.lock().unwrap_or_else(|e| e.into_inner());
+            state.done = true;
+            state.result = result;
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+        Self { state, stop }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Future for PowFuture {
+    type Output = Option<UnsignedEvent>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.done {
+            Poll::Ready(state.result.take())
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for PowFuture {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
 }
 
 impl EventBuilder {
@@ -388,6 +619,49 @@ impl EventBuilder {
         Self::new(Kind::TextNote, content, tags)
     }
 
+    /// Text note reply
+    ///
+    /// Tag the root of the thread and the replied event with the `root` and `reply` markers
+    /// (only `root` for a direct reply to the root), and the participants of the thread.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/10.md>
+    pub fn text_note_reply<S>(content: S, reply_to: &Event, relay_url: Option<UncheckedUrl>) -> Self
+    where
+        S: Into<String>,
+    {
+        let mut tags: Vec<Tag> = Vec::new();
+
+        match reply_to.thread_root() {
+            Some(root) => {
+                tags.push(Tag::Event {
+                    event_id: root,
+                    relay_url: None,
+                    marker: Some(Marker::Root),
+                });
+                tags.push(Tag::Event {
+                    event_id: reply_to.id,
+                    relay_url,
+                    marker: Some(Marker::Reply),
+                });
+            }
+            None => tags.push(Tag::Event {
+                event_id: reply_to.id,
+                relay_url,
+                marker: Some(Marker::Root),
+            }),
+        }
+
+        let mut public_keys: Vec<XOnlyPublicKey> = vec![reply_to.pubkey];
+        for public_key in reply_to.public_keys() {
+            if !public_keys.contains(public_key) {
+                public_keys.push(*public_key);
+            }
+        }
+        tags.extend(public_keys.into_iter().map(Tag::public_key));
+
+        Self::new(Kind::TextNote, content, tags)
+    }
+
     /// Long-form text note (generally referred to as "articles" or "blog posts").
     ///
     /// <https://github.com/nostr-protocol/nips/blob/master/23.md>
@@ -417,6 +691,16 @@ impl EventBuilder {
         Self::new(Kind::LongFormTextNote, content, tags)
     }
 
+    /// Long-form article or draft
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/23.md>
+    pub fn article(mut article: ArticleBuilder) -> Self {
+        let kind: Kind = article.kind();
+        let content: String = core::mem::take(&mut article.content);
+        let tags: Vec<Tag> = article.into();
+        Self::new(kind, content, tags)
+    }
+
     /// Set contact list
     pub fn set_contact_list<I>(contacts: I) -> Self
     where
@@ -433,6 +717,9 @@ impl EventBuilder {
 
     /// OpenTimestamps Attestations for Events
     ///
+    /// Timestamp the event with the OpenTimestamps calendars (blocking).
+    /// Use [`EventBuilder::ots_attestation`] if you already have the proof.
+    ///
     /// <https://github.com/nostr-protocol/nips/blob/master/03.md>
     #[cfg(feature = "nip03")]
     pub fn opentimestamps(
@@ -443,10 +730,24 @@ impl EventBuilder {
         Ok(Self::new(
             Kind::OpenTimestamps,
             ots,
-            [Tag::Event(event_id, relay_url, None)],
+            [Tag::Event {
+                event_id,
+                relay_url,
+                marker: None,
+            }],
         ))
     }
 
+    /// OpenTimestamps Attestation for an event, from a base64-encoded `.ots` proof
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/03.md>
+    pub fn ots_attestation<S>(event_id: EventId, ots_proof: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::new(Kind::OpenTimestamps, ots_proof, [Tag::event(event_id)])
+    }
+
     /// Create encrypted direct msg event
     #[cfg(all(feature = "std", feature = "nip04"))]
     pub fn new_encrypted_direct_msg<S>(
@@ -470,6 +771,7 @@ impl EventBuilder {
     }
 
     /// Repost event
+    #[deprecated(since = "0.27.0", note = "Use `repost_embedded` instead")]
     pub fn repost(event_id: EventId, public_key: XOnlyPublicKey) -> Self {
         Self::new(
             Kind::Repost,
@@ -478,6 +780,64 @@ impl EventBuilder {
         )
     }
 
+    /// Repost event
+    ///
+    /// Text notes are reposted with a [`Kind::Repost`], the other events with a [`Kind::GenericRepost`]
+    /// with the `k` tag of the reposted kind (and the `a` tag for parameterized replaceable events).
+    /// The reposted event is embedded in the `content`.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/18.md>
+    pub fn repost_embedded(event: &Event, relay_url: Option<UncheckedUrl>) -> Self {
+        let mut tags: Vec<Tag> = vec![
+            Tag::Event {
+                event_id: event.id,
+                relay_url: relay_url.clone(),
+                marker: None,
+            },
+            Tag::public_key(event.pubkey),
+        ];
+
+        if event.kind == Kind::TextNote {
+            return Self::new(Kind::Repost, event.as_json(), tags);
+        }
+
+        tags.push(Tag::Generic(
+            TagKind::Custom(String::from("k")),
+            vec![event.kind.to_string()],
+        ));
+
+        if event.is_parameterized_replaceable() {
+            tags.push(Tag::A {
+                kind: event.kind,
+                public_key: event.pubkey,
+                identifier: event.identifier().unwrap_or_default().to_string(),
+                relay_url,
+            });
+        }
+
+        Self::new(Kind::GenericRepost, event.as_json(), tags)
+    }
+
+    /// Quote repost
+    ///
+    /// Add the `q` tag of the quoted event and the `p` tag of its author.
+    /// The `content` should contain the `nostr:` URI of the quoted event (NIP21).
+    pub fn quote<S>(content: S, quoted: &Event, relay_url: Option<UncheckedUrl>) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::new_text_note(
+            content,
+            [
+                Tag::Quote {
+                    event_id: quoted.id,
+                    relay_url,
+                },
+                Tag::public_key(quoted.pubkey),
+            ],
+        )
+    }
+
     /// Create delete event
     pub fn delete<I, T>(ids: I) -> Self
     where
@@ -513,6 +873,17 @@ impl EventBuilder {
         )
     }
 
+    /// React to an [`Event`]
+    ///
+    /// The custom emojis have the `emoji` tag.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/25.md>
+    pub fn react(event: &Event, reaction: Reaction) -> Self {
+        let mut tags: Vec<Tag> = vec![Tag::event(event.id), Tag::public_key(event.pubkey)];
+        tags.extend(reaction.emoji_tag());
+        Self::new(Kind::Reaction, reaction.content(), tags)
+    }
+
     /// Create new channel
     ///
     /// <https://github.com/nostr-protocol/nips/blob/master/28.md>
@@ -557,6 +928,43 @@ impl EventBuilder {
         )
     }
 
+    /// Reply to a channel message
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/28.md>
+    pub fn channel_msg_reply<S>(
+        channel_id: EventId,
+        relay_url: Url,
+        reply_to: &Event,
+        content: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        let relay_url: UncheckedUrl = relay_url.into();
+        Self::new(
+            Kind::ChannelMessage,
+            content,
+            [
+                Tag::Event {
+                    event_id: channel_id,
+                    relay_url: Some(relay_url.clone()),
+                    marker: Some(Marker::Root),
+                },
+                Tag::Event {
+                    event_id: reply_to.id,
+                    relay_url: Some(relay_url.clone()),
+                    marker: Some(Marker::Reply),
+                },
+                Tag::PublicKey {
+                    public_key: reply_to.pubkey,
+                    relay_url: Some(relay_url),
+                    alias: None,
+                    uppercase: false,
+                },
+            ],
+        )
+    }
+
     /// Hide message
     ///
     /// <https://github.com/nostr-protocol/nips/blob/master/28.md>
@@ -626,6 +1034,42 @@ impl EventBuilder {
         ))
     }
 
+    /// Nostr Wallet Connect Info
+    ///
+    /// Advertise the methods supported by the wallet service.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/47.md>
+    #[cfg(feature = "nip47")]
+    pub fn nostr_wallet_connect_info<I>(methods: I) -> Self
+    where
+        I: IntoIterator<Item = NostrWalletConnectMethod>,
+    {
+        let methods: Vec<String> = methods.into_iter().map(|m| m.to_string()).collect();
+        Self::new(Kind::WalletConnectInfo, methods.join(" "), [])
+    }
+
+    /// Nostr Wallet Connect Response
+    ///
+    /// Reply to the `request` event with the keys of the wallet service.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/47.md>
+    #[cfg(all(feature = "std", feature = "nip04", feature = "nip47"))]
+    pub fn nostr_wallet_connect_response(
+        service_keys: &Keys,
+        request: &Event,
+        response: NostrWalletConnectResponse,
+    ) -> Result<Self, Error> {
+        Ok(Self::new(
+            Kind::WalletConnectResponse,
+            nip04::encrypt(
+                &service_keys.secret_key()?,
+                &request.pubkey,
+                response.as_json(),
+            )?,
+            [Tag::public_key(request.pubkey), Tag::event(request.id)],
+        ))
+    }
+
     /// Live Event
     ///
     /// <https://github.com/nostr-protocol/nips/blob/master/53.md>
@@ -634,6 +1078,24 @@ impl EventBuilder {
         Self::new(Kind::LiveEvent, "", tags)
     }
 
+    /// User status
+    ///
+    /// Publish an empty `content` to clear the status.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/38.md>
+    pub fn user_status<S>(
+        status_type: StatusType,
+        content: S,
+        expiration: Option<Timestamp>,
+        link: Option<String>,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        let tags: Vec<Tag> = nip38::tags(status_type, expiration, link);
+        Self::new(Kind::UserStatus, content, tags)
+    }
+
     /// Live Event Message
     ///
     /// <https://github.com/nostr-protocol/nips/blob/master/53.md>
@@ -656,6 +1118,36 @@ impl EventBuilder {
         Self::new(Kind::LiveEventMessage, content, tags)
     }
 
+    /// Highlight
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/84.md>
+    pub fn highlight(mut highlight: Highlight) -> Self {
+        let content: String = core::mem::take(&mut highlight.content);
+        let tags: Vec<Tag> = highlight.into();
+        Self::new(Kind::Highlight, content, tags)
+    }
+
+    /// Application handler information
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/89.md>
+    pub fn handler_information(info: HandlerInformation) -> Self {
+        let content: String = info
+            .metadata
+            .as_ref()
+            .map(|metadata| metadata.as_json())
+            .unwrap_or_default();
+        let tags: Vec<Tag> = info.into();
+        Self::new(Kind::HandlerInformation, content, tags)
+    }
+
+    /// Application handler recommendation
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/89.md>
+    pub fn handler_recommendation(recommendation: HandlerRecommendation) -> Self {
+        let tags: Vec<Tag> = recommendation.into();
+        Self::new(Kind::HandlerRecommendation, String::new(), tags)
+    }
+
     /// Create report event
     ///
     /// <https://github.com/nostr-protocol/nips/blob/master/56.md>
@@ -904,7 +1396,7 @@ impl EventBuilder {
         }
 
         // Add identifier `d` tag
-        let id_tag: Tag = Tag::Identifier("profile_badges".to_string());
+        let id_tag: Tag = Tag::Identifier(nip58::PROFILE_BADGES_IDENTIFIER.to_string());
         let mut tags: Vec<Tag> = vec![id_tag];
 
         let badge_definitions_identifiers = badge_definitions.into_iter().filter_map(|event| {
@@ -1060,6 +1552,124 @@ impl EventBuilder {
         let tags: Vec<Tag> = data.clone().into();
         Self::new(Kind::SetProduct, data, tags)
     }
+
+    /// Git patch
+    ///
+    /// Use [`GitPatch::from_format_patch`] to build it from the `git format-patch` output.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/34.md>
+    pub fn git_patch(patch: GitPatch) -> Self {
+        let content: String = patch.content.clone();
+        let tags: Vec<Tag> = patch.into();
+        Self::new(Kind::GitPatch, content, tags)
+    }
+
+    /// Draft
+    ///
+    /// The `encrypted_draft` is the JSON of the unsigned draft event, NIP44 encrypted to self.
+    /// An empty `encrypted_draft` marks the draft as deleted.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/37.md>
+    pub fn draft<I, C>(identifier: I, draft_kind: Kind, encrypted_draft: C) -> Self
+    where
+        I: Into<String>,
+        C: Into<String>,
+    {
+        let tags: Vec<Tag> = vec![
+            Tag::Identifier(identifier.into()),
+            Tag::Generic(
+                TagKind::Custom(String::from("k")),
+                vec![draft_kind.as_u64().to_string()],
+            ),
+        ];
+        Self::new(Kind::Draft, encrypted_draft, tags)
+    }
+
+    /// Private Direct Message rumor
+    ///
+    /// The rumor must not be signed: seal and gift wrap it with [`EventBuilder::gift_wrap`].
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/17.md>
+    #[cfg(feature = "nip59")]
+    pub fn private_msg_rumor<S>(
+        receiver: XOnlyPublicKey,
+        message: S,
+        reply_to: Option<EventId>,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        let mut tags: Vec<Tag> = vec![Tag::public_key(receiver)];
+        if let Some(reply_to) = reply_to {
+            tags.push(Tag::event(reply_to));
+        }
+        Self::new(Kind::PrivateDirectMessage, message, tags)
+    }
+
+    /// Seal
+    ///
+    /// The `rumor` is NIP44 encrypted to the `receiver_pubkey`.
+    /// The `created_at` is randomized within [`nip59::DEFAULT_TIMESTAMP_TWEAK`].
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/59.md>
+    #[cfg(all(feature = "std", feature = "nip59"))]
+    pub fn seal(
+        sender_keys: &Keys,
+        receiver_pubkey: &XOnlyPublicKey,
+        rumor: UnsignedEvent,
+    ) -> Result<Self, Error> {
+        let content: String = nip44::encrypt(
+            &sender_keys.secret_key()?,
+            receiver_pubkey,
+            rumor.as_json(),
+            Nip44Version::V2,
+        )?;
+        Ok(Self::new(Kind::Seal, content, [])
+            .custom_created_at(nip59::created_at(Some(nip59::DEFAULT_TIMESTAMP_TWEAK))))
+    }
+
+    /// Gift Wrap from seal
+    ///
+    /// The seal is NIP44 encrypted and signed with new ephemeral keys.
+    /// The `created_at` is randomized within [`nip59::DEFAULT_TIMESTAMP_TWEAK`].
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/59.md>
+    #[cfg(all(feature = "std", feature = "nip59"))]
+    pub fn gift_wrap_from_seal(receiver: &XOnlyPublicKey, seal: &Event) -> Result<Event, Error> {
+        if seal.kind != Kind::Seal {
+            return Err(Error::WrongKind {
+                received: seal.kind,
+                expected: WrongKindError::Single(Kind::Seal),
+            });
+        }
+
+        Ok(nip59::gift_wrap_from_seal(
+            receiver,
+            seal,
+            Some(nip59::DEFAULT_TIMESTAMP_TWEAK),
+        )?)
+    }
+
+    /// Gift Wrap
+    ///
+    /// Seal the `rumor` with the `sender_keys` and wrap it for the `receiver`.
+    /// The rumor must be authored by the `sender_keys`.
+    /// Use [`nip59::gift_wrap`] to change or disable the randomization of the `created_at`.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/59.md>
+    #[cfg(all(feature = "std", feature = "nip59"))]
+    pub fn gift_wrap(
+        sender_keys: &Keys,
+        receiver: &XOnlyPublicKey,
+        rumor: UnsignedEvent,
+    ) -> Result<Event, Error> {
+        Ok(nip59::gift_wrap(
+            sender_keys,
+            receiver,
+            rumor,
+            Some(nip59::DEFAULT_TIMESTAMP_TWEAK),
+        )?)
+    }
 }
 
 #[cfg(test)]
@@ -1123,6 +1733,68 @@ mod tests {
         event.verify().unwrap();
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_channel_msg_reply() {
+        let keys = Keys::generate();
+        let relay_url = Url::parse("wss://relay.example.com").unwrap();
+        let channel = EventBuilder::new_channel(&Metadata::new().name("channel"))
+            .to_event(&keys)
+            .unwrap();
+        let msg = EventBuilder::new_channel_msg(channel.id, relay_url.clone(), "hello")
+            .to_event(&keys)
+            .unwrap();
+        let reply = EventBuilder::channel_msg_reply(channel.id, relay_url.clone(), &msg, "hi")
+            .to_event(&keys)
+            .unwrap();
+
+        assert_eq!(reply.kind, Kind::ChannelMessage);
+        assert_eq!(
+            reply.tags,
+            vec![
+                Tag::Event {
+                    event_id: channel.id,
+                    relay_url: Some(relay_url.clone().into()),
+                    marker: Some(Marker::Root),
+                },
+                Tag::Event {
+                    event_id: msg.id,
+                    relay_url: Some(relay_url.clone().into()),
+                    marker: Some(Marker::Reply),
+                },
+                Tag::PublicKey {
+                    public_key: keys.public_key(),
+                    relay_url: Some(relay_url.into()),
+                    alias: None,
+                    uppercase: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_quote() {
+        let keys = Keys::generate();
+        let note = EventBuilder::new_text_note("hello", [])
+            .to_event(&keys)
+            .unwrap();
+        let mention = EventBuilder::new_text_note("hi", [Tag::event(note.id)])
+            .to_event(&keys)
+            .unwrap();
+        let quote = EventBuilder::quote("nostr:note1...", &note, None)
+            .to_event(&keys)
+            .unwrap();
+
+        assert!(!mention.is_quote());
+        assert!(quote.is_quote());
+        assert_eq!(quote.quoted_events().collect::<Vec<_>>(), vec![&note.id]);
+        assert_eq!(
+            quote.public_keys().collect::<Vec<_>>(),
+            vec![&keys.public_key()]
+        );
+    }
+
     #[test]
     #[cfg(feature = "nip57")]
     fn test_zap_event_builder() {
@@ -1375,4 +2047,263 @@ mod tests {
         assert_eq!(profile_badges.kind, Kind::ProfileBadges);
         assert_eq!(profile_badges.tags, example_event.tags);
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_draft() {
+        let keys = Keys::generate();
+        let draft = EventBuilder::draft("my-draft", Kind::LongFormTextNote, "encrypted")
+            .to_event(&keys)
+            .unwrap();
+
+        assert_eq!(draft.kind, Kind::Draft);
+        assert_eq!(draft.identifier(), Some("my-draft"));
+        assert_eq!(
+            draft.tags[1].as_vec(),
+            vec![String::from("k"), String::from("30023")]
+        );
+
+        // Deleted draft
+        let draft = EventBuilder::draft("my-draft", Kind::LongFormTextNote, String::new())
+            .to_event(&keys)
+            .unwrap();
+        assert!(draft.content.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "nip47")]
+    fn test_nostr_wallet_connect_info() {
+        use crate::nips::nip47::Method;
+
+        let keys = Keys::generate();
+        let info =
+            EventBuilder::nostr_wallet_connect_info([Method::PayInvoice, Method::GetBalance])
+                .to_event(&keys)
+                .unwrap();
+
+        assert_eq!(info.kind, Kind::WalletConnectInfo);
+        assert_eq!(info.content, "pay_invoice get_balance");
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", feature = "nip47"))]
+    fn test_nostr_wallet_connect_response() {
+        use crate::nips::nip47::{
+            ErrorCode, Method, NostrWalletConnectURI, PayInvoiceRequestParams, Request,
+            RequestParams, Response,
+        };
+
+        let service_keys = Keys::generate();
+        let app_keys = Keys::generate();
+        let uri = NostrWalletConnectURI::new(
+            service_keys.public_key(),
+            Url::parse("wss://relay.example.com").unwrap(),
+            app_keys.secret_key().unwrap(),
+            None,
+        )
+        .unwrap();
+
+        // App side
+        let req = Request {
+            method: Method::PayInvoice,
+            params: RequestParams::PayInvoice(PayInvoiceRequestParams {
+                invoice: String::from("lnbc1"),
+            }),
+        };
+        let content = nip04::encrypt(&uri.secret, &uri.public_key, req.as_json()).unwrap();
+        let request = EventBuilder::new(
+            Kind::WalletConnectRequest,
+            content,
+            [Tag::public_key(uri.public_key)],
+        )
+        .to_event(&app_keys)
+        .unwrap();
+
+        // Service side
+        let parsed = Request::from_event(&request, &service_keys.secret_key().unwrap()).unwrap();
+        assert_eq!(parsed, req);
+
+        let response = Response::error(parsed.method, ErrorCode::InsufficientBalance, "no funds");
+        let event = EventBuilder::nostr_wallet_connect_response(&service_keys, &request, response)
+            .unwrap()
+            .to_event(&service_keys)
+            .unwrap();
+        assert_eq!(event.kind, Kind::WalletConnectResponse);
+        assert!(event.event_ids().any(|id| id == &request.id));
+
+        // App side
+        let msg = nip04::decrypt(&uri.secret, &uri.public_key, &event.content).unwrap();
+        let response = Response::from_json(msg).unwrap();
+        assert_eq!(response.result_type, Method::PayInvoice);
+        assert_eq!(response.error.unwrap().message, "no funds");
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", feature = "nip59"))]
+    fn test_gift_wrap_from_seal() {
+        let sender_keys = Keys::generate();
+        let receiver = Keys::generate().public_key();
+        let rumor = EventBuilder::private_msg_rumor(receiver, "Test", None)
+            .to_unsigned_event(sender_keys.public_key());
+
+        let seal = EventBuilder::seal(&sender_keys, &receiver, rumor)
+            .unwrap()
+            .to_event(&sender_keys)
+            .unwrap();
+        assert_eq!(seal.kind, Kind::Seal);
+        assert!(seal.tags.is_empty());
+
+        let gift_wrap = EventBuilder::gift_wrap_from_seal(&receiver, &seal).unwrap();
+        assert_eq!(gift_wrap.kind, Kind::GiftWrap);
+        assert_eq!(gift_wrap.public_keys().next(), Some(&receiver));
+
+        // Only seals can be wrapped
+        let note = EventBuilder::new_text_note("Test", [])
+            .to_event(&sender_keys)
+            .unwrap();
+        assert!(EventBuilder::gift_wrap_from_seal(&receiver, &note).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_repost() {
+        let keys = Keys::generate();
+
+        let note = EventBuilder::new_text_note("GM", [])
+            .to_event(&keys)
+            .unwrap();
+        let repost = EventBuilder::repost_embedded(&note, None)
+            .to_event(&keys)
+            .unwrap();
+        assert_eq!(repost.kind, Kind::Repost);
+        assert!(repost.is_repost());
+        assert_eq!(repost.reposted_event(), Some(note.clone()));
+
+        let article = EventBuilder::long_form_text_note(
+            "Lorem ipsum",
+            [Tag::Identifier(String::from("lorem-ipsum"))],
+        )
+        .to_event(&keys)
+        .unwrap();
+        let repost = EventBuilder::repost_embedded(&article, None)
+            .to_event(&keys)
+            .unwrap();
+        assert_eq!(repost.kind, Kind::GenericRepost);
+        assert!(repost.tags.contains(&Tag::Generic(
+            TagKind::Custom(String::from("k")),
+            vec![String::from("30023")]
+        )));
+        assert_eq!(repost.coordinates().count(), 1);
+        assert_eq!(repost.reposted_event(), Some(article));
+
+        // Embedded event not matching the `e` tag
+        let other = EventBuilder::new_text_note("GN", [])
+            .to_event(&keys)
+            .unwrap();
+        let fake = EventBuilder::new(Kind::Repost, other.as_json(), [Tag::event(note.id)])
+            .to_event(&keys)
+            .unwrap();
+        assert_eq!(fake.reposted_event(), None);
+
+        // Deprecated constructor: the reposted event is not embedded
+        #[allow(deprecated)]
+        let repost = EventBuilder::repost(note.id, note.pubkey)
+            .to_event(&keys)
+            .unwrap();
+        assert_eq!(repost.kind, Kind::Repost);
+        assert!(repost.content.is_empty());
+        assert_eq!(repost.reposted_event(), None);
+
+        // Reposts without the embedded event are valid
+        let empty = EventBuilder::new(Kind::Repost, "", [Tag::event(note.id)])
+            .to_event(&keys)
+            .unwrap();
+        assert!(empty.is_repost());
+        assert_eq!(empty.reposted_event(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_text_note_reply() {
+        let alice = Keys::generate();
+        let bob = Keys::generate();
+
+        let root = EventBuilder::new_text_note("root", [])
+            .to_event(&alice)
+            .unwrap();
+        let reply = EventBuilder::text_note_reply("reply", &root, None)
+            .to_event(&bob)
+            .unwrap();
+        assert_eq!(reply.thread_root(), Some(root.id));
+        assert_eq!(reply.reply_to(), Some(root.id));
+        assert_eq!(
+            reply.public_keys().copied().collect::<Vec<_>>(),
+            vec![alice.public_key()]
+        );
+
+        let nested = EventBuilder::text_note_reply("nested", &reply, None)
+            .to_event(&alice)
+            .unwrap();
+        assert_eq!(nested.thread_root(), Some(root.id));
+        assert_eq!(nested.reply_to(), Some(reply.id));
+        assert_eq!(
+            nested.public_keys().copied().collect::<Vec<_>>(),
+            vec![bob.public_key(), alice.public_key()]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_pow_event_with_threads() {
+        let keys = Keys::generate();
+        let cancel = PowCancelToken::new();
+        let event = EventBuilder::new_text_note("POW", [])
+            .to_pow_event_with(&keys, 8, 4, &cancel)
+            .unwrap();
+        assert!(event.verify().is_ok());
+        assert!(nip13::get_leading_zero_bits(event.id.inner()) >= 8);
+        assert!(event
+            .tags
+            .iter()
+            .any(|tag| matches!(tag, Tag::POW { difficulty: 8, .. })));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_pow_event_cancelled() {
+        let keys = Keys::generate();
+        let cancel = PowCancelToken::new();
+        cancel.cancel();
+        let res = EventBuilder::new_text_note("POW", []).to_pow_event_with(&keys, 255, 2, &cancel);
+        assert!(matches!(res, Err(Error::PowCancelled)));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_pow_event_async() {
+        use std::task::Wake;
+
+        struct ThreadWaker(thread::Thread);
+
+        impl Wake for ThreadWaker {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        let keys = Keys::generate();
+        let cancel = PowCancelToken::new();
+        let mut fut = Box::pin(
+            EventBuilder::new_text_note("POW", []).to_pow_event_async(&keys, 8, 2, &cancel),
+        );
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        let event = loop {
+            match fut.as_mut().poll(&mut cx) {
+                Poll::Ready(res) => break res.unwrap(),
+                Poll::Pending => thread::park(),
+            }
+        };
+        assert!(nip13::get_leading_zero_bits(event.id.inner()) >= 8);
+    }
 }
@@ -44,10 +44,18 @@ pub enum Kind {
     EncryptedDirectMessage,
     /// Event Deletion (NIP09)
     EventDeletion,
+    /// Seal (NIP59)
+    Seal,
+    /// Private Direct Message (NIP17)
+    PrivateDirectMessage,
     /// Repost (NIP18)
     Repost,
+    /// Generic Repost (NIP18)
+    GenericRepost,
     /// Reaction (NIP25)
     Reaction,
+    /// Gift Wrap (NIP59)
+    GiftWrap,
     /// Badge Award (NIP58)
     BadgeAward,
     /// Channel Creation (NIP28)
@@ -84,8 +92,12 @@ pub enum Kind {
     MuteList,
     /// Pin List (NIP51)
     PinList,
+    /// Bookmarks (NIP51)
+    Bookmarks,
     /// Relay List Metadata (NIP65)
     RelayList,
+    /// Direct Message Relays (NIP17)
+    DirectMessageRelays,
     /// Client Authentication (NIP42)
     Authentication,
     /// Wallet Connect Request (NIP47)
@@ -98,16 +110,24 @@ pub enum Kind {
     CategorizedPeopleList,
     /// Categorized Bookmark List (NIP51)
     CategorizedBookmarkList,
+    /// Bookmark Set (NIP51)
+    BookmarkSet,
     /// Live Event (NIP53)
     LiveEvent,
     /// Live Event Message (NIP53)
     LiveEventMessage,
+    /// User Status (NIP38)
+    UserStatus,
+    /// Highlight (NIP84)
+    Highlight,
     /// Profile Badges (NIP58)
     ProfileBadges,
     /// Badge Definition (NIP58)
     BadgeDefinition,
     /// Long-form Text Note (NIP23)
     LongFormTextNote,
+    /// Long-form Text Note Draft (NIP23)
+    LongFormTextNoteDraft,
     /// Application-specific Data (NIP78)
     ApplicationSpecificData,
     /// File Metadata (NIP94)
@@ -120,6 +140,16 @@ pub enum Kind {
     SetProduct,
     /// Job Feedback (NIP90)
     JobFeedback,
+    /// Git Repository Announcement (NIP34)
+    GitRepoAnnouncement,
+    /// Git Patch (NIP34)
+    GitPatch,
+    /// Draft (NIP37)
+    Draft,
+    /// Handler Recommendation (NIP89)
+    HandlerRecommendation,
+    /// Handler Information (NIP89)
+    HandlerInformation,
     /// Regular Events (must be between 5000 and <=5999)
     JobRequest(u16),
     /// Regular Events (must be between 6000 and <=6999)
@@ -235,8 +265,12 @@ impl From<u64> for Kind {
             1040 => Self::OpenTimestamps,
             4 => Self::EncryptedDirectMessage,
             5 => Self::EventDeletion,
+            13 => Self::Seal,
+            14 => Self::PrivateDirectMessage,
             6 => Self::Repost,
+            16 => Self::GenericRepost,
             7 => Self::Reaction,
+            1059 => Self::GiftWrap,
             8 => Self::BadgeAward,
             40 => Self::ChannelCreation,
             41 => Self::ChannelMetadata,
@@ -256,23 +290,34 @@ impl From<u64> for Kind {
             10000 => Self::MuteList,
             10001 => Self::PinList,
             10002 => Self::RelayList,
+            10003 => Self::Bookmarks,
+            10050 => Self::DirectMessageRelays,
             22242 => Self::Authentication,
             23194 => Self::WalletConnectRequest,
             23195 => Self::WalletConnectResponse,
             24133 => Self::NostrConnect,
             30000 => Self::CategorizedPeopleList,
             30001 => Self::CategorizedBookmarkList,
+            30003 => Self::BookmarkSet,
             30311 => Self::LiveEvent,
             1311 => Self::LiveEventMessage,
+            30315 => Self::UserStatus,
+            9802 => Self::Highlight,
             30008 => Self::ProfileBadges,
             30009 => Self::BadgeDefinition,
             30017 => Self::SetStall,
             30018 => Self::SetProduct,
             30023 => Self::LongFormTextNote,
+            30024 => Self::LongFormTextNoteDraft,
             30078 => Self::ApplicationSpecificData,
             1063 => Self::FileMetadata,
             27235 => Self::HttpAuth,
             7000 => Self::JobFeedback,
+            30617 => Self::GitRepoAnnouncement,
+            1617 => Self::GitPatch,
+            31234 => Self::Draft,
+            31989 => Self::HandlerRecommendation,
+            31990 => Self::HandlerInformation,
             x if (NIP90_JOB_REQUEST_RANGE).contains(&x) => Self::JobRequest(x as u16),
             x if (NIP90_JOB_RESULT_RANGE).contains(&x) => Self::JobResult(x as u16),
             x if (REGULAR_RANGE).contains(&x) => Self::Regular(x as u16),
@@ -296,8 +341,12 @@ impl From<Kind> for u64 {
             Kind::OpenTimestamps => 1040,
             Kind::EncryptedDirectMessage => 4,
             Kind::EventDeletion => 5,
+            Kind::Seal => 13,
+            Kind::PrivateDirectMessage => 14,
             Kind::Repost => 6,
+            Kind::GenericRepost => 16,
             Kind::Reaction => 7,
+            Kind::GiftWrap => 1059,
             Kind::BadgeAward => 8,
             Kind::ChannelCreation => 40,
             Kind::ChannelMetadata => 41,
@@ -317,23 +366,34 @@ impl From<Kind> for u64 {
             Kind::MuteList => 10000,
             Kind::PinList => 10001,
             Kind::RelayList => 10002,
+            Kind::Bookmarks => 10003,
+            Kind::DirectMessageRelays => 10050,
             Kind::Authentication => 22242,
             Kind::WalletConnectRequest => 23194,
             Kind::WalletConnectResponse => 23195,
             Kind::NostrConnect => 24133,
             Kind::CategorizedPeopleList => 30000,
             Kind::CategorizedBookmarkList => 30001,
+            Kind::BookmarkSet => 30003,
             Kind::LiveEvent => 30311,
             Kind::LiveEventMessage => 1311,
+            Kind::UserStatus => 30315,
+            Kind::Highlight => 9802,
             Kind::ProfileBadges => 30008,
             Kind::BadgeDefinition => 30009,
             Kind::SetStall => 30017,
             Kind::SetProduct => 30018,
             Kind::LongFormTextNote => 30023,
+            Kind::LongFormTextNoteDraft => 30024,
             Kind::ApplicationSpecificData => 30078,
             Kind::FileMetadata => 1063,
             Kind::HttpAuth => 27235,
             Kind::JobFeedback => 7000,
+            Kind::GitRepoAnnouncement => 30617,
+            Kind::GitPatch => 1617,
+            Kind::Draft => 31234,
+            Kind::HandlerRecommendation => 31989,
+            Kind::HandlerInformation => 31990,
             Kind::JobRequest(u) => u as u64,
             Kind::JobResult(u) => u as u64,
             Kind::Regular(u) => u as u64,
@@ -411,8 +471,18 @@ mod tests {
         assert_eq!(Kind::Custom(20100), Kind::Custom(20100));
         assert_eq!(Kind::Custom(20100), Kind::Ephemeral(20100));
         assert_eq!(Kind::TextNote, Kind::Custom(1));
+        assert_eq!(Kind::Regular(9802), Kind::Highlight);
         assert_eq!(Kind::ParameterizedReplaceable(30017), Kind::SetStall);
         assert_eq!(Kind::ParameterizedReplaceable(30018), Kind::SetProduct);
+        assert_eq!(Kind::ParameterizedReplaceable(30315), Kind::UserStatus);
+        assert_eq!(
+            Kind::ParameterizedReplaceable(30024),
+            Kind::LongFormTextNoteDraft
+        );
+        assert_eq!(
+            Kind::ParameterizedReplaceable(31990),
+            Kind::HandlerInformation
+        );
     }
 
     #[test]
@@ -31,6 +31,8 @@ pub use self::partial::{MissingPartialEvent, PartialEvent};
 pub use self::tag::{Marker, Tag, TagKind};
 pub use self::unsigned::UnsignedEvent;
 use crate::nips::nip01::Coordinate;
+use crate::nips::nip10::ThreadTags;
+use crate::nips::nip13;
 #[cfg(feature = "std")]
 use crate::types::time::Instant;
 use crate::types::time::TimeSupplier;
@@ -240,6 +242,32 @@ impl Event {
         None
     }
 
+    /// Get POW target difficulty committed in the `nonce` tag
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/13.md>
+    pub fn pow_target(&self) -> Option<u8> {
+        self.tags.iter().find_map(|tag| match tag {
+            Tag::POW { difficulty, .. } => Some(*difficulty),
+            _ => None,
+        })
+    }
+
+    /// Get POW difficulty committed by the event
+    ///
+    /// Return `0` if the event has no `nonce` tag or if the ID doesn't reach the committed target.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/13.md>
+    pub fn pow_difficulty(&self) -> u8 {
+        nip13::get_committed_difficulty(self.id.as_bytes(), self.pow_target())
+    }
+
+    /// Check if the event has a committed POW of at least `difficulty` leading zero bits
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/13.md>
+    pub fn check_pow(&self, difficulty: u8) -> bool {
+        self.pow_difficulty() >= difficulty
+    }
+
     /// Returns `true` if the event has an expiration tag that is expired.
     /// If an event has no `Expiration` tag, then it will return `false`.
     ///
@@ -337,6 +365,64 @@ impl Event {
         })
     }
 
+    /// Get the root of the thread the event belongs to (`e` tags)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/10.md>
+    pub fn thread_root(&self) -> Option<EventId> {
+        ThreadTags::from_event(self).thread_root()
+    }
+
+    /// Get the event this event is replying to (`e` tags)
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/10.md>
+    pub fn reply_to(&self) -> Option<EventId> {
+        ThreadTags::from_event(self).reply_to()
+    }
+
+    /// Extract quoted event IDs from tags (`q` tag)
+    pub fn quoted_events(&self) -> impl Iterator<Item = &EventId> {
+        self.tags.iter().filter_map(|t| match t {
+            Tag::Quote { event_id, .. } => Some(event_id),
+            _ => None,
+        })
+    }
+
+    /// Check if it's a quote repost (text note with at least one `q` tag)
+    ///
+    /// Events referenced only by `e` tags or `nostr:` URIs are plain mentions.
+    pub fn is_quote(&self) -> bool {
+        self.kind == Kind::TextNote && self.quoted_events().next().is_some()
+    }
+
+    /// Check if it's a repost ([`Kind::Repost`] or [`Kind::GenericRepost`])
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/18.md>
+    pub fn is_repost(&self) -> bool {
+        matches!(self.kind, Kind::Repost | Kind::GenericRepost)
+    }
+
+    /// Get the reposted event embedded in the `content`
+    ///
+    /// Return `None` if it's not a repost, if the `content` is empty or not a valid event,
+    /// or if the embedded event is not the one referenced by the `e` tag.
+    /// The signature of the embedded event is verified.
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/18.md>
+    #[cfg(feature = "std")]
+    pub fn reposted_event(&self) -> Option<Event> {
+        if !self.is_repost() || self.content.is_empty() {
+            return None;
+        }
+
+        let event: Event = Event::from_json(&self.content).ok()?;
+        if self.event_ids().next() != Some(&event.id) {
+            return None;
+        }
+
+        event.verify().ok()?;
+        Some(event)
+    }
+
     /// Extract coordinates from tags (`a` tag)
     pub fn coordinates(&self) -> impl Iterator<Item = Coordinate> + '_ {
         self.tags.iter().filter_map(|t| match t {
@@ -527,6 +613,39 @@ mod tests {
         assert!(!&event.is_expired());
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_event_check_pow() {
+        let keys = Keys::generate();
+        let event = EventBuilder::new_text_note("POW", [])
+            .to_pow_event(&keys, 8)
+            .unwrap();
+        assert_eq!(event.pow_target(), Some(8));
+        assert_eq!(event.pow_difficulty(), 8);
+        assert!(event.check_pow(8));
+        // A lucky ID doesn't count more than the committed target
+        assert!(!event.check_pow(9));
+
+        let event = EventBuilder::new_text_note("POW", [])
+            .to_event(&keys)
+            .unwrap();
+        assert!(event.check_pow(0));
+        assert!(!event.check_pow(1));
+
+        // Target not reached
+        let event = EventBuilder::new_text_note(
+            "POW",
+            [Tag::POW {
+                nonce: 1,
+                difficulty: 255,
+            }],
+        )
+        .to_event(&keys)
+        .unwrap();
+        assert_eq!(event.pow_target(), Some(255));
+        assert_eq!(event.pow_difficulty(), 0);
+    }
+
     #[test]
     fn test_verify_event_id() {
         let event = Event::from_json(r#"{"content":"","created_at":1698412975,"id":"f55c30722f056e330d8a7a6a9ba1522f7522c0f1ced1c93d78ea833c78a3d6ec","kind":3,"pubkey":"f831caf722214748c72db4829986bd0cbb2bb8b3aeade1c959624a52a9629046","sig":"5092a9ffaecdae7d7794706f085ff5852befdf79df424cc3419bb797bf515ae05d4f19404cb8324b8b4380a4bd497763ac7b0f3b1b63ef4d3baa17e5f5901808","tags":[["p","4ddeb9109a8cd29ba279a637f5ec344f2479ee07df1f4043f3fe26d8948cfef9","",""],["p","bb6fd06e156929649a73e6b278af5e648214a69d88943702f1fb627c02179b95","",""],["p","b8b8210f33888fdbf5cedee9edf13c3e9638612698fe6408aff8609059053420","",""],["p","9dcee4fabcd690dc1da9abdba94afebf82e1e7614f4ea92d61d52ef9cd74e083","",""],["p","3eea9e831fefdaa8df35187a204d82edb589a36b170955ac5ca6b88340befaa0","",""],["p","885238ab4568f271b572bf48b9d6f99fa07644731f288259bd395998ee24754e","",""],["p","568a25c71fba591e39bebe309794d5c15d27dbfa7114cacb9f3586ea1314d126","",""]]}"#).unwrap();
@@ -180,6 +180,8 @@ where
 pub enum Report {
     /// Depictions of nudity, porn, etc
     Nudity,
+    /// Virus, trojan horse, worm, robot, spyware, adware, back door, ransomware, rootkit, kidnapper, etc.
+    Malware,
     /// Profanity, hateful speech, etc.
     Profanity,
     /// Something which may be illegal in some jurisdiction
@@ -190,16 +192,20 @@ pub enum Report {
     Spam,
     /// Someone pretending to be someone else
     Impersonation,
+    /// Reports that don't fit in the above categories
+    Other,
 }
 
 impl fmt::Display for Report {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Nudity => write!(f, "nudity"),
+            Self::Malware => write!(f, "malware"),
             Self::Profanity => write!(f, "profanity"),
             Self::Illegal => write!(f, "illegal"),
             Self::Spam => write!(f, "spam"),
             Self::Impersonation => write!(f, "impersonation"),
+            Self::Other => write!(f, "other"),
         }
     }
 }
@@ -209,10 +215,12 @@ impl FromStr for Report {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "nudity" => Ok(Self::Nudity),
+            "malware" => Ok(Self::Malware),
             "profanity" => Ok(Self::Profanity),
             "illegal" => Ok(Self::Illegal),
             "spam" => Ok(Self::Spam),
             "impersonation" => Ok(Self::Impersonation),
+            "other" => Ok(Self::Other),
             _ => Err(Error::UnknownReportType),
         }
     }
@@ -346,6 +354,8 @@ pub enum TagKind {
     U,
     /// SHA256
     X,
+    /// Quote
+    Q,
     /// Relay
     Relay,
     /// Nonce
@@ -441,6 +451,7 @@ impl fmt::Display for TagKind {
             Self::M => write!(f, "m"),
             Self::U => write!(f, "u"),
             Self::X => write!(f, "x"),
+            Self::Q => write!(f, "q"),
             Self::Relay => write!(f, "relay"),
             Self::Nonce => write!(f, "nonce"),
             Self::Delegation => write!(f, "delegation"),
@@ -502,6 +513,7 @@ where
             "m" => Self::M,
             "u" => Self::U,
             "x" => Self::X,
+            "q" => Self::Q,
             "relay" => Self::Relay,
             "nonce" => Self::Nonce,
             "delegation" => Self::Delegation,
@@ -581,6 +593,10 @@ pub enum Tag {
         identifier: String,
         relay_url: Option<UncheckedUrl>,
     },
+    Quote {
+        event_id: EventId,
+        relay_url: Option<UncheckedUrl>,
+    },
     Relay(UncheckedUrl),
     POW {
         nonce: u128,
@@ -716,6 +732,7 @@ impl Tag {
             Self::Identifier(..) => TagKind::D,
             Self::ExternalIdentity(..) => TagKind::I,
             Self::A { .. } => TagKind::A,
+            Self::Quote { .. } => TagKind::Q,
             Self::Relay(..) => TagKind::Relay,
             Self::POW { .. } => TagKind::Nonce,
             Self::Delegation { .. } => TagKind::Delegation,
@@ -818,6 +835,13 @@ where
                     })
                 }
                 TagKind::E => Ok(Self::event(EventId::from_hex(tag_1)?)),
+                TagKind::Q => match EventId::from_hex(tag_1) {
+                    Ok(event_id) => Ok(Self::Quote {
+                        event_id,
+                        relay_url: None,
+                    }),
+                    Err(_) => Ok(Self::Generic(tag_kind, vec![tag_1.to_owned()])),
+                },
                 TagKind::R => {
                     if tag_1.starts_with("ws://") || tag_1.starts_with("wss://") {
                         Ok(Self::RelayMetadata(UncheckedUrl::from(tag_1), None))
@@ -921,6 +945,16 @@ where
                         }
                     }
                 }
+                TagKind::Q => match EventId::from_hex(tag_1) {
+                    Ok(event_id) => Ok(Self::Quote {
+                        event_id,
+                        relay_url: (!tag_2.is_empty()).then_some(UncheckedUrl::from(tag_2)),
+                    }),
+                    Err(_) => Ok(Self::Generic(
+                        tag_kind,
+                        tag[1..].iter().map(|s| s.as_ref().to_owned()).collect(),
+                    )),
+                },
                 TagKind::I => match Identity::new(tag_1, tag_2) {
                     Ok(identity) => Ok(Self::ExternalIdentity(identity)),
                     Err(_) => Ok(Self::Generic(
@@ -1143,6 +1177,16 @@ impl From<Tag> for Vec<String> {
                 }
                 vec
             }
+            Tag::Quote {
+                event_id,
+                relay_url,
+            } => {
+                let mut tag = vec![TagKind::Q.to_string(), event_id.to_hex()];
+                if let Some(relay_url) = relay_url {
+                    tag.push(relay_url.to_string());
+                }
+                tag
+            }
             Tag::ExternalIdentity(identity) => identity.into(),
             Tag::Relay(url) => vec![TagKind::Relay.to_string(), url.to_string()],
             Tag::POW { nonce, difficulty } => vec![
@@ -1416,6 +1460,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_deserialize_non_hex_quote_tag() {
+        let event: &str = r#"{"id":"2be17aa3031bdcb006f0fce80c146dea9c1c0268b0af2398bb673365c6444d45","pubkey":"f86c44a2de95d9149b51c6a29afeabba264c18e2fa7c49de93424a0c56947785","created_at":1640839235,"kind":1,"tags":[["q","30023:f86c44a2de95d9149b51c6a29afeabba264c18e2fa7c49de93424a0c56947785:article","wss://relay.damus.io"]],"content":"quote","sig":"a5d9290ef9659083c490b303eb7ee41356d8778ff19f2f91776c8dc4443388a64ffcf336e61af4c25c05ac3ae952d1ced889ed655b67790891222aaa15b99fdd"}"#;
+        let event = Event::from_json(event).unwrap();
+        let tag = event.tags.first().unwrap();
+
+        assert_eq!(
+            tag,
+            &Tag::Generic(
+                TagKind::Q,
+                vec![
+                    String::from(
+                        "30023:f86c44a2de95d9149b51c6a29afeabba264c18e2fa7c49de93424a0c56947785:article"
+                    ),
+                    String::from("wss://relay.damus.io")
+                ]
+            )
+        );
+        assert_eq!(
+            tag.as_vec(),
+            vec![
+                "q",
+                "30023:f86c44a2de95d9149b51c6a29afeabba264c18e2fa7c49de93424a0c56947785:article",
+                "wss://relay.damus.io"
+            ]
+        );
+    }
+
     #[test]
     fn test_serialize_tag_to_event() {
         let public_key = XOnlyPublicKey::from_str(
@@ -1928,6 +2000,22 @@ mod tests {
             }
         );
 
+        assert_eq!(
+            Tag::parse(vec![
+                "q",
+                "378f145897eea948952674269945e88612420db35791784abf0616b4fed56ef7",
+                "wss://relay.damus.io"
+            ])
+            .unwrap(),
+            Tag::Quote {
+                event_id: EventId::from_hex(
+                    "378f145897eea948952674269945e88612420db35791784abf0616b4fed56ef7"
+                )
+                .unwrap(),
+                relay_url: Some(UncheckedUrl::from("wss://relay.damus.io"))
+            }
+        );
+
         assert_eq!(
             Tag::parse(vec!["r", "wss://alicerelay.example.com", "read"]).unwrap(),
             Tag::RelayMetadata(
@@ -58,7 +58,8 @@ pub use self::event::{
 };
 pub use self::key::Keys;
 pub use self::message::{
-    Alphabet, ClientMessage, Filter, GenericTagValue, RawRelayMessage, RelayMessage, SubscriptionId,
+    Alphabet, ClientMessage, Filter, GenericTagValue, PageCursor, RawRelayMessage, RelayMessage,
+    SubscriptionId,
 };
 pub use self::nips::nip19::{FromBech32, ToBech32};
 pub use self::types::{Contact, Metadata, Timestamp, UncheckedUrl};
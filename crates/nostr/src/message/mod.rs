@@ -7,12 +7,14 @@
 use core::fmt;
 
 pub mod client;
+pub mod registry;
 pub mod relay;
 pub mod subscription;
 
 pub use self::client::ClientMessage;
+pub use self::registry::SubscriptionRegistry;
 pub use self::relay::{RawRelayMessage, RelayMessage};
-pub use self::subscription::{Alphabet, Filter, GenericTagValue, SubscriptionId};
+pub use self::subscription::{Alphabet, Filter, GenericTagValue, PageCursor, SubscriptionId};
 use crate::event;
 
 /// Messages error
@@ -0,0 +1,248 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! Subscription registry
+//!
+//! Relay-side bookkeeping of the subscriptions (`REQ`) opened by the connected clients.
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::{ClientMessage, Filter, RelayMessage, SubscriptionId};
+use crate::Event;
+
+/// Subscription registry error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// Too many subscriptions opened by the same connection
+    TooManySubscriptions {
+        /// Max allowed subscriptions
+        max: usize,
+    },
+    /// Too many filters in the same subscription
+    TooManyFilters {
+        /// Max allowed filters
+        max: usize,
+    },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManySubscriptions { max } => write!(f, "too many subscriptions (max {max})"),
+            Self::TooManyFilters { max } => write!(f, "too many filters (max {max})"),
+        }
+    }
+}
+
+/// Subscriptions of the clients connected to a relay
+///
+/// `C` identifies a connection (i.e. the socket address or an incremental ID).
+#[derive(Debug, Clone)]
+pub struct SubscriptionRegistry<C> {
+    subscriptions: BTreeMap<C, BTreeMap<SubscriptionId, Vec<Filter>>>,
+    max_subscriptions: Option<usize>,
+    max_filters: Option<usize>,
+}
+
+impl<C> Default for SubscriptionRegistry<C>
+where
+    C: Ord + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> SubscriptionRegistry<C>
+where
+    C: Ord + Clone,
+{
+    /// New empty registry without limits
+    pub fn new() -> Self {
+        Self {
+            subscriptions: BTreeMap::new(),
+            max_subscriptions: None,
+            max_filters: None,
+        }
+    }
+
+    /// Set max number of subscriptions per connection
+    pub fn max_subscriptions(self, max: usize) -> Self {
+        Self {
+            max_subscriptions: Some(max),
+            ..self
+        }
+    }
+
+    /// Set max number of filters per subscription
+    pub fn max_filters(self, max: usize) -> Self {
+        Self {
+            max_filters: Some(max),
+            ..self
+        }
+    }
+
+    /// Open a subscription or replace the filters of an existing one
+    pub fn subscribe(
+        &mut self,
+        conn: C,
+        subscription_id: SubscriptionId,
+        filters: Vec<Filter>,
+    ) -> Result<(), Error> {
+        if let Some(max) = self.max_filters {
+            if filters.len() > max {
+                return Err(Error::TooManyFilters { max });
+            }
+        }
+
+        let subscriptions = self.subscriptions.entry(conn).or_default();
+
+        if let Some(max) = self.max_subscriptions {
+            if !subscriptions.contains_key(&subscription_id) && subscriptions.len() >= max {
+                return Err(Error::TooManySubscriptions { max });
+            }
+        }
+
+        subscriptions.insert(subscription_id, filters);
+        Ok(())
+    }
+
+    /// Close a subscription
+    ///
+    /// Return `false` if the subscription doesn't exist.
+    pub fn unsubscribe(&mut self, conn: &C, subscription_id: &SubscriptionId) -> bool {
+        match self.subscriptions.get_mut(conn) {
+            Some(subscriptions) => {
+                let removed: bool = subscriptions.remove(subscription_id).is_some();
+                if subscriptions.is_empty() {
+                    self.subscriptions.remove(conn);
+                }
+                removed
+            }
+            None => false,
+        }
+    }
+
+    /// Remove all the subscriptions of a connection (i.e. on disconnection)
+    pub fn remove_connection(&mut self, conn: &C) {
+        self.subscriptions.remove(conn);
+    }
+
+    /// Get the filters of a subscription
+    pub fn filters(&self, conn: &C, subscription_id: &SubscriptionId) -> Option<&[Filter]> {
+        self.subscriptions
+            .get(conn)?
+            .get(subscription_id)
+            .map(|filters| filters.as_slice())
+    }
+
+    /// Number of subscriptions opened by a connection
+    pub fn subscriptions_count(&self, conn: &C) -> usize {
+        self.subscriptions.get(conn).map_or(0, |s| s.len())
+    }
+
+    /// Get the subscriptions that match the [`Event`]
+    ///
+    /// Use it to fan out a new event to the interested connections.
+    pub fn matching<'a>(
+        &'a self,
+        event: &'a Event,
+    ) -> impl Iterator<Item = (&'a C, &'a SubscriptionId)> + 'a {
+        self.subscriptions
+            .iter()
+            .flat_map(move |(conn, subscriptions)| {
+                subscriptions
+                    .iter()
+                    .filter(move |(_, filters)| filters.iter().any(|f| f.match_event(event)))
+                    .map(move |(id, _)| (conn, id))
+            })
+    }
+
+    /// Handle `REQ` and `CLOSE` messages
+    ///
+    /// Return the `CLOSED` message to send back if the subscription has been refused.
+    /// Other messages are ignored.
+    pub fn handle_client_message(&mut self, conn: C, msg: &ClientMessage) -> Option<RelayMessage> {
+        match msg {
+            ClientMessage::Req {
+                subscription_id,
+                filters,
+            } => match self.subscribe(conn, subscription_id.clone(), filters.clone()) {
+                Ok(()) => None,
+                Err(e) => Some(RelayMessage::new_closed(
+                    subscription_id.clone(),
+                    format!("error: {e}"),
+                )),
+            },
+            ClientMessage::Close(subscription_id) => {
+                self.unsubscribe(&conn, subscription_id);
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, Keys, Kind};
+
+    #[test]
+    fn test_registry() {
+        let keys = Keys::generate();
+        let event = EventBuilder::new_text_note("Hello", [])
+            .to_event(&keys)
+            .unwrap();
+
+        let mut registry: SubscriptionRegistry<u64> = SubscriptionRegistry::new()
+            .max_subscriptions(2)
+            .max_filters(1);
+
+        let notes = SubscriptionId::new("notes");
+        let metadata = SubscriptionId::new("metadata");
+        registry
+            .subscribe(1, notes.clone(), vec![Filter::new().kind(Kind::TextNote)])
+            .unwrap();
+        registry
+            .subscribe(
+                1,
+                metadata.clone(),
+                vec![Filter::new().kind(Kind::Metadata)],
+            )
+            .unwrap();
+        registry
+            .subscribe(
+                2,
+                notes.clone(),
+                vec![Filter::new().author(keys.public_key())],
+            )
+            .unwrap();
+
+        assert_eq!(
+            registry.subscribe(1, SubscriptionId::new("other"), vec![]),
+            Err(Error::TooManySubscriptions { max: 2 })
+        );
+        assert_eq!(
+            registry.subscribe(3, notes.clone(), vec![Filter::new(), Filter::new()]),
+            Err(Error::TooManyFilters { max: 1 })
+        );
+
+        let matching: Vec<(&u64, &SubscriptionId)> = registry.matching(&event).collect();
+        assert_eq!(matching, vec![(&1, &notes), (&2, &notes)]);
+
+        let close = ClientMessage::close(notes.clone());
+        assert_eq!(registry.handle_client_message(1, &close), None);
+        assert_eq!(registry.subscriptions_count(&1), 1);
+
+        registry.remove_connection(&2);
+        assert_eq!(registry.matching(&event).count(), 0);
+    }
+}
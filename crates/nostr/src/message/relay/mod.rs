@@ -547,4 +547,38 @@ mod tests {
 
         assert_eq!(msg, RelayMessage::from_json(SAMPLE_EVENT).unwrap());
     }
+
+    #[test]
+    fn test_raw_relay_message_serialization() {
+        let raw = RawRelayMessage::new_ok(
+            "70b10f70c1318967eddf12527799411b1a9780ad9c43858f5e5fcd45486a13a5",
+            false,
+            "rate-limited: slow down",
+        );
+        let json = r#"["OK","70b10f70c1318967eddf12527799411b1a9780ad9c43858f5e5fcd45486a13a5",false,"rate-limited: slow down"]"#;
+        assert_eq!(raw.as_json(), json);
+        assert_eq!(RawRelayMessage::from_json(json).unwrap(), raw);
+
+        let raw = RawRelayMessage::new_count("sub", 3);
+        assert_eq!(raw.as_json(), r#"["COUNT","sub",{"count":3}]"#);
+        assert_eq!(
+            RelayMessage::try_from(raw).unwrap(),
+            RelayMessage::new_count(SubscriptionId::new("sub"), 3)
+        );
+    }
+
+    #[test]
+    fn test_raw_relay_message_from_relay_message() {
+        let msg = RelayMessage::from_json(r#"["EVENT", "random_string", {"id":"70b10f70c1318967eddf12527799411b1a9780ad9c43858f5e5fcd45486a13a5","pubkey":"379e863e8357163b5bce5d2688dc4f1dcc2d505222fb8d74db600f30535dfdfe","created_at":1612809991,"kind":1,"tags":[],"content":"test","sig":"273a9cd5d11455590f4359500bccb7a89428262b96b3ea87a756b770964472f8c3e87f5d5e64d8d2e859a71462a3f477b554565c4f2f326cb01dd7620db71502"}]"#).unwrap();
+        let raw = RawRelayMessage::from(msg.clone());
+        assert_eq!(RelayMessage::try_from(raw.clone()).unwrap(), msg);
+        assert_eq!(raw.as_json(), msg.as_json());
+
+        let msg = RelayMessage::NegErr {
+            subscription_id: SubscriptionId::new("sub"),
+            code: NegentropyErrorCode::ResultsTooBig,
+        };
+        let raw = RawRelayMessage::from(msg.clone());
+        assert_eq!(RelayMessage::try_from(raw).unwrap(), msg);
+    }
 }
@@ -4,12 +4,18 @@
 
 //! Raw Relay messages
 
-use alloc::string::String;
-use serde_json::Value;
+use alloc::string::{String, ToString};
 
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{json, Value};
+
+use super::RelayMessage;
 use crate::message::MessageHandleError;
+use crate::JsonUtil;
 
 /// Raw Relay Message
+///
+/// Relay message before the deserialization and verification of the event.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RawRelayMessage {
     /// `["EVENT", <subscription_id>, <event JSON>]` (NIP01)
@@ -70,7 +76,136 @@ pub enum RawRelayMessage {
     },
 }
 
+impl Serialize for RawRelayMessage {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let json_value: Value = self.as_value();
+        json_value.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RawRelayMessage {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let json_value = Value::deserialize(deserializer)?;
+        RawRelayMessage::from_value(json_value).map_err(serde::de::Error::custom)
+    }
+}
+
 impl RawRelayMessage {
+    /// Create new `EVENT` message
+    pub fn new_event<S>(subscription_id: S, event: Value) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Event {
+            subscription_id: subscription_id.into(),
+            event,
+        }
+    }
+
+    /// Create new `OK` message
+    pub fn new_ok<I, S>(event_id: I, status: bool, message: S) -> Self
+    where
+        I: Into<String>,
+        S: Into<String>,
+    {
+        Self::Ok {
+            event_id: event_id.into(),
+            status,
+            message: message.into(),
+        }
+    }
+
+    /// Create new `EOSE` message
+    pub fn new_eose<S>(subscription_id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::EndOfStoredEvents(subscription_id.into())
+    }
+
+    /// Create new `NOTICE` message
+    pub fn new_notice<S>(message: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Notice {
+            message: message.into(),
+        }
+    }
+
+    /// Create new `CLOSED` message
+    pub fn new_closed<I, S>(subscription_id: I, message: S) -> Self
+    where
+        I: Into<String>,
+        S: Into<String>,
+    {
+        Self::Closed {
+            subscription_id: subscription_id.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Create new `AUTH` message
+    pub fn new_auth<S>(challenge: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Auth {
+            challenge: challenge.into(),
+        }
+    }
+
+    /// Create new `COUNT` message
+    pub fn new_count<S>(subscription_id: S, count: usize) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Count {
+            subscription_id: subscription_id.into(),
+            count,
+        }
+    }
+
+    /// Serialize [`RawRelayMessage`] as [`Value`]
+    pub fn as_value(&self) -> Value {
+        match self {
+            Self::Event {
+                subscription_id,
+                event,
+            } => json!(["EVENT", subscription_id, event]),
+            Self::Ok {
+                event_id,
+                status,
+                message,
+            } => json!(["OK", event_id, status, message]),
+            Self::EndOfStoredEvents(subscription_id) => json!(["EOSE", subscription_id]),
+            Self::Notice { message } => json!(["NOTICE", message]),
+            Self::Closed {
+                subscription_id,
+                message,
+            } => json!(["CLOSED", subscription_id, message]),
+            Self::Auth { challenge } => json!(["AUTH", challenge]),
+            Self::Count {
+                subscription_id,
+                count,
+            } => json!(["COUNT", subscription_id, { "count": count }]),
+            Self::NegMsg {
+                subscription_id,
+                message,
+            } => json!(["NEG-MSG", subscription_id, message]),
+            Self::NegErr {
+                subscription_id,
+                code,
+            } => json!(["NEG-ERR", subscription_id, code]),
+        }
+    }
+
     /// Deserialize [`RawRelayMessage`] from [`Value`]
     pub fn from_value(msg: Value) -> Result<Self, MessageHandleError> {
         let v = msg
@@ -206,9 +341,13 @@ impl RawRelayMessage {
 
         Err(MessageHandleError::InvalidMessageFormat)
     }
+}
+
+impl JsonUtil for RawRelayMessage {
+    type Err = MessageHandleError;
 
     /// Deserialize [`RawRelayMessage`] from JSON string
-    pub fn from_json<T>(json: T) -> Result<Self, MessageHandleError>
+    fn from_json<T>(json: T) -> Result<Self, Self::Err>
     where
         T: AsRef<[u8]>,
     {
@@ -222,3 +361,59 @@ impl RawRelayMessage {
         Self::from_value(value)
     }
 }
+
+impl From<RelayMessage> for RawRelayMessage {
+    fn from(msg: RelayMessage) -> Self {
+        match msg {
+            RelayMessage::Event {
+                subscription_id,
+                event,
+            } => Self::Event {
+                subscription_id: subscription_id.to_string(),
+                event: json!(event),
+            },
+            RelayMessage::Ok {
+                event_id,
+                status,
+                message,
+            } => Self::Ok {
+                event_id: event_id.to_hex(),
+                status,
+                message,
+            },
+            RelayMessage::EndOfStoredEvents(subscription_id) => {
+                Self::EndOfStoredEvents(subscription_id.to_string())
+            }
+            RelayMessage::Notice { message } => Self::Notice { message },
+            RelayMessage::Closed {
+                subscription_id,
+                message,
+            } => Self::Closed {
+                subscription_id: subscription_id.to_string(),
+                message,
+            },
+            RelayMessage::Auth { challenge } => Self::Auth { challenge },
+            RelayMessage::Count {
+                subscription_id,
+                count,
+            } => Self::Count {
+                subscription_id: subscription_id.to_string(),
+                count,
+            },
+            RelayMessage::NegMsg {
+                subscription_id,
+                message,
+            } => Self::NegMsg {
+                subscription_id: subscription_id.to_string(),
+                message,
+            },
+            RelayMessage::NegErr {
+                subscription_id,
+                code,
+            } => Self::NegErr {
+                subscription_id: subscription_id.to_string(),
+                code: code.to_string(),
+            },
+        }
+    }
+}
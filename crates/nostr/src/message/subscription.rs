@@ -8,6 +8,8 @@
 #[cfg(not(feature = "std"))]
 use alloc::collections::{BTreeMap as AllocMap, BTreeSet as AllocSet};
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Reverse;
 use core::fmt;
 use core::str::FromStr;
 #[cfg(feature = "std")]
@@ -24,7 +26,7 @@ use serde::ser::{SerializeMap, Serializer};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::{EventId, JsonUtil, Kind, Timestamp};
+use crate::{Event, EventId, JsonUtil, Kind, Timestamp};
 
 /// Alphabet Error
 #[derive(Debug)]
@@ -356,6 +358,11 @@ pub struct Filter {
     )]
     #[serde(default)]
     pub generic_tags: AllocMap<Alphabet, AllocSet<GenericTagValue>>,
+    /// Minimum committed POW difficulty (NIP13)
+    ///
+    /// Not part of the relay protocol: it's never sent to relays and is only applied when matching events locally.
+    #[serde(skip)]
+    pub pow: Option<u8>,
 }
 
 impl Filter {
@@ -641,6 +648,21 @@ impl Filter {
         }
     }
 
+    /// Add minimum committed POW difficulty (NIP13)
+    ///
+    /// Only applied when matching events locally: relays ignore it.
+    pub fn pow(self, difficulty: u8) -> Self {
+        Self {
+            pow: Some(difficulty),
+            ..self
+        }
+    }
+
+    /// Remove minimum POW difficulty
+    pub fn remove_pow(self) -> Self {
+        Self { pow: None, ..self }
+    }
+
     /// Add custom tag
     pub fn custom_tag<I, T>(mut self, tag: Alphabet, values: I) -> Self
     where
@@ -680,12 +702,166 @@ impl Filter {
     pub fn is_empty(&self) -> bool {
         self == &Filter::default()
     }
+
+    fn ids_match(&self, event: &Event) -> bool {
+        self.ids.is_empty() || self.ids.contains(&event.id)
+    }
+
+    fn authors_match(&self, event: &Event) -> bool {
+        self.authors.is_empty() || self.authors.contains(&event.pubkey)
+    }
+
+    fn kind_match(&self, event: &Event) -> bool {
+        self.kinds.is_empty() || self.kinds.contains(&event.kind)
+    }
+
+    fn tag_match(&self, event: &Event) -> bool {
+        self.generic_tags.iter().all(|(tagname, values)| {
+            let tagname: String = tagname.to_string();
+            event.tags.iter().any(|tag| {
+                let tag: Vec<String> = tag.as_vec();
+                match (tag.first(), tag.get(1)) {
+                    (Some(kind), Some(content)) if *kind == tagname => {
+                        values.iter().any(|value| value.to_string() == *content)
+                    }
+                    _ => false,
+                }
+            })
+        })
+    }
+
+    fn search_match(&self, event: &Event) -> bool {
+        match &self.search {
+            Some(query) => event.content.to_lowercase().contains(&query.to_lowercase()),
+            None => true,
+        }
+    }
+
+    /// Continue the pagination from [`PageCursor`]
+    ///
+    /// Set `until` to the timestamp of the cursor (inclusive, so events with the same `created_at`
+    /// are not lost): use [`PageCursor::paginate`] to discard the events already received.
+    ///
+    /// With a `limit`, a relay may never return some of the events sharing the cursor timestamp:
+    /// use [`PageCursor::page_filters`] to build the filters of the next page.
+    pub fn cursor(self, cursor: PageCursor) -> Self {
+        self.until(cursor.created_at)
+    }
+
+    /// Determine if [`Filter`] match the provided [`Event`]
+    ///
+    /// `limit` is ignored and `search` is matched as case-insensitive substring of the content.
+    pub fn match_event(&self, event: &Event) -> bool {
+        self.ids_match(event)
+            && self.since.map_or(true, |t| event.created_at >= t)
+            && self.until.map_or(true, |t| event.created_at <= t)
+            && self.kind_match(event)
+            && self.authors_match(event)
+            && self.tag_match(event)
+            && self.search_match(event)
+            && self
+                .pow
+                .map_or(true, |difficulty| event.check_pow(difficulty))
+    }
 }
 
 impl JsonUtil for Filter {
     type Err = serde_json::Error;
 }
 
+/// Pagination cursor
+///
+/// Events are ordered by `created_at` (newest first) and, for the same timestamp, by [`EventId`]
+/// (highest first), so pages never skip or duplicate events with identical timestamps.
+///
+/// Relays can only page by `until`: if more than `limit` events share the same timestamp, a
+/// relay may return only some of them. [`PageCursor::page_filters`] queries all the events of the
+/// cursor timestamp (without limit) and the older ones separately, and [`PageCursor::second_filters`]
+/// completes the last timestamp of a full page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PageCursor {
+    /// Timestamp of the last received event
+    pub created_at: Timestamp,
+    /// ID of the last received event
+    pub id: EventId,
+}
+
+impl From<&Event> for PageCursor {
+    fn from(event: &Event) -> Self {
+        Self::new(event.created_at, event.id)
+    }
+}
+
+impl PageCursor {
+    /// New cursor
+    pub fn new(created_at: Timestamp, id: EventId) -> Self {
+        Self { created_at, id }
+    }
+
+    /// Check if [`Event`] comes after the cursor (i.e. belongs to the next pages)
+    pub fn is_after(&self, event: &Event) -> bool {
+        (event.created_at, event.id) < (self.created_at, self.id)
+    }
+
+    /// Build the filters to query the page of max `limit` events after the `cursor` (the first page if `None`)
+    ///
+    /// For each filter, the events with the cursor timestamp are all queried (`since` and `until` set to it,
+    /// without limit) and the older events with `until` set to the second before and `limit`.
+    pub fn page_filters(cursor: Option<Self>, filters: Vec<Filter>, limit: usize) -> Vec<Filter> {
+        match cursor {
+            Some(cursor) => {
+                let mut page: Vec<Filter> =
+                    Self::second_filters(filters.clone(), cursor.created_at);
+                page.extend(
+                    filters
+                        .into_iter()
+                        .map(|filter| filter.until(cursor.created_at - 1u64).limit(limit)),
+                );
+                page
+            }
+            None => filters
+                .into_iter()
+                .map(|filter| filter.limit(limit))
+                .collect(),
+        }
+    }
+
+    /// Build the filters to query all the events with `created_at` timestamp (without limit)
+    ///
+    /// Use it when a page is full, so that the events sharing the timestamp of its last event
+    /// and not returned by a relay because of the `limit` are not skipped.
+    pub fn second_filters(filters: Vec<Filter>, created_at: Timestamp) -> Vec<Filter> {
+        filters
+            .into_iter()
+            .filter(|filter| {
+                filter.since.map_or(true, |since| since <= created_at)
+                    && filter.until.map_or(true, |until| until >= created_at)
+            })
+            .map(|filter| filter.since(created_at).until(created_at).remove_limit())
+            .collect()
+    }
+
+    /// Compose a page of max `limit` events
+    ///
+    /// Discard the events that don't come after the `cursor` (if any), sort them (newest first)
+    /// and return the page with the cursor for the next one (`None` if no events left).
+    pub fn paginate(
+        cursor: Option<Self>,
+        events: Vec<Event>,
+        limit: usize,
+    ) -> (Vec<Event>, Option<Self>) {
+        let mut events: Vec<Event> = events
+            .into_iter()
+            .filter(|e| cursor.map_or(true, |c| c.is_after(e)))
+            .collect();
+        events.sort_by_key(|e| Reverse((e.created_at, e.id)));
+        events.dedup_by_key(|e| e.id);
+        events.truncate(limit);
+        let next: Option<Self> = events.last().map(Self::from);
+        (events, next)
+    }
+}
+
 fn serialize_generic_tags<S>(
     generic_tags: &AllocMap<Alphabet, AllocSet<GenericTagValue>>,
     serializer: S,
@@ -748,6 +924,7 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::{EventBuilder, Keys, Tag};
 
     #[test]
     fn test_kind_concatenation() {
@@ -854,4 +1031,159 @@ mod test {
         let filter = Filter::new();
         assert!(filter.is_empty());
     }
+
+    #[test]
+    fn test_filter_match_event() {
+        let keys = Keys::generate();
+        let event = EventBuilder::new_text_note(
+            "Hello Nostr",
+            [
+                Tag::Hashtag(String::from("nostr")),
+                Tag::Identifier(String::from("test")),
+            ],
+        )
+        .to_event(&keys)
+        .unwrap();
+
+        assert!(Filter::new().match_event(&event));
+        assert!(Filter::new()
+            .author(keys.public_key())
+            .kind(Kind::TextNote)
+            .hashtag("nostr")
+            .since(event.created_at)
+            .match_event(&event));
+        assert!(Filter::new().search("hello").match_event(&event));
+        assert!(Filter::new().identifier("test").match_event(&event));
+
+        assert!(!Filter::new().kind(Kind::Metadata).match_event(&event));
+        assert!(!Filter::new().hashtag("bitcoin").match_event(&event));
+        assert!(!Filter::new()
+            .until(event.created_at - 1_i64)
+            .match_event(&event));
+        assert!(!Filter::new().search("bitcoin").match_event(&event));
+
+        // POW
+        assert!(Filter::new().pow(0).match_event(&event));
+        assert!(!Filter::new().pow(1).match_event(&event));
+        let pow_event = EventBuilder::new_text_note("Hello Nostr", [])
+            .to_pow_event(&keys, 4)
+            .unwrap();
+        assert!(Filter::new().pow(4).match_event(&pow_event));
+        assert_eq!(Filter::new().pow(4).as_json(), "{}");
+    }
+
+    #[test]
+    fn test_page_cursor() {
+        let keys = Keys::generate();
+        let created_at = Timestamp::from(1_700_000_000);
+        let events: Vec<Event> = (0..5)
+            .map(|i| {
+                EventBuilder::new_text_note(i.to_string(), [])
+                    .custom_created_at(created_at)
+                    .to_event(&keys)
+                    .unwrap()
+            })
+            .collect();
+
+        let (first, cursor) = PageCursor::paginate(None, events.clone(), 2);
+        assert_eq!(first.len(), 2);
+        let cursor = cursor.unwrap();
+        assert_eq!(Filter::new().cursor(cursor).until, Some(created_at));
+
+        // Relay return again all the events with the same timestamp
+        let (second, cursor) = PageCursor::paginate(Some(cursor), events.clone(), 2);
+        let (third, cursor) = PageCursor::paginate(cursor, events.clone(), 2);
+        let (last, cursor) = PageCursor::paginate(cursor, events, 2);
+        assert_eq!(second.len(), 2);
+        assert_eq!(third.len(), 1);
+        assert!(last.is_empty());
+        assert!(cursor.is_none());
+
+        let mut ids: Vec<EventId> = first
+            .iter()
+            .chain(second.iter())
+            .chain(third.iter())
+            .map(|e| e.id)
+            .collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), 5);
+    }
+
+    #[test]
+    fn test_page_filters_timestamp_collision() {
+        // Relay returning, for each filter, the newest `limit` events, with the ones sharing
+        // the same timestamp in the opposite order of the pagination
+        fn query(events: &[Event], filters: &[Filter]) -> Vec<Event> {
+            let mut result: Vec<Event> = Vec::new();
+            for filter in filters.iter() {
+                let mut matched: Vec<&Event> =
+                    events.iter().filter(|e| filter.match_event(e)).collect();
+                matched.sort_by_key(|e| (Reverse(e.created_at), e.id));
+                matched.truncate(filter.limit.unwrap_or(usize::MAX));
+                result.extend(matched.into_iter().cloned());
+            }
+            result
+        }
+
+        fn page(
+            events: &[Event],
+            filters: &[Filter],
+            cursor: Option<PageCursor>,
+            limit: usize,
+        ) -> (Vec<Event>, Option<PageCursor>) {
+            let mut received: Vec<Event> = query(
+                events,
+                &PageCursor::page_filters(cursor, filters.to_vec(), limit),
+            );
+            let (page, next) = PageCursor::paginate(cursor, received.clone(), limit);
+            match page.last() {
+                Some(last) if page.len() == limit => {
+                    received.extend(query(
+                        events,
+                        &PageCursor::second_filters(filters.to_vec(), last.created_at),
+                    ));
+                    PageCursor::paginate(cursor, received, limit)
+                }
+                _ => (page, next),
+            }
+        }
+
+        let keys = Keys::generate();
+        let created_at = Timestamp::from(1_700_000_000);
+        let mut events: Vec<Event> = (0..7)
+            .map(|i| {
+                EventBuilder::new_text_note(i.to_string(), [])
+                    .custom_created_at(created_at)
+                    .to_event(&keys)
+                    .unwrap()
+            })
+            .collect();
+        events.extend((0..3).map(|i| {
+            EventBuilder::new_text_note(i.to_string(), [])
+                .custom_created_at(created_at - 10u64)
+                .to_event(&keys)
+                .unwrap()
+        }));
+
+        let filters = vec![Filter::new().kind(Kind::TextNote)];
+        let limit: usize = 3;
+        let mut cursor: Option<PageCursor> = None;
+        let mut received: Vec<Event> = Vec::new();
+        loop {
+            let (events, next) = page(&events, &filters, cursor, limit);
+            if events.is_empty() {
+                break;
+            }
+            assert!(events.len() <= limit);
+            received.extend(events);
+            cursor = next;
+        }
+
+        let mut ids: Vec<EventId> = received.iter().map(|e| e.id).collect();
+        assert_eq!(ids.len(), 10);
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), 10);
+    }
 }
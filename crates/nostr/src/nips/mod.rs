@@ -7,6 +7,8 @@
 //! See all at <https://github.com/nostr-protocol/nips>
 
 pub mod nip01;
+#[cfg(feature = "nip03")]
+pub mod nip03;
 #[cfg(feature = "nip04")]
 pub mod nip04;
 #[cfg(all(feature = "std", feature = "nip05"))]
@@ -15,13 +17,19 @@ pub mod nip05;
 pub mod nip06;
 #[cfg(all(feature = "nip07", target_arch = "wasm32"))]
 pub mod nip07;
+pub mod nip10;
 #[cfg(all(feature = "std", feature = "nip11"))]
 pub mod nip11;
 pub mod nip13;
 pub mod nip15;
 pub mod nip19;
 pub mod nip21;
+pub mod nip23;
+pub mod nip25;
 pub mod nip26;
+pub mod nip34;
+pub mod nip38;
+pub mod nip42;
 #[cfg(feature = "nip44")]
 pub mod nip44;
 #[cfg(all(feature = "std", feature = "nip46"))]
@@ -29,11 +37,19 @@ pub mod nip46;
 #[cfg(feature = "nip47")]
 pub mod nip47;
 pub mod nip48;
+#[cfg(feature = "nip49")]
+pub mod nip49;
+pub mod nip51;
 pub mod nip53;
+pub mod nip56;
 #[cfg(feature = "nip57")]
 pub mod nip57;
 pub mod nip58;
+#[cfg(feature = "nip59")]
+pub mod nip59;
 pub mod nip65;
+pub mod nip84;
+pub mod nip89;
 pub mod nip90;
 pub mod nip94;
 pub mod nip98;
@@ -0,0 +1,219 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP03
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/03.md>
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use base64::engine::{general_purpose, Engine};
+
+use crate::{Event, EventId, Kind, Tag, UncheckedUrl};
+
+/// Magic header of a detached OpenTimestamps proof
+const OTS_HEADER_MAGIC: &[u8] =
+    b"\x00OpenTimestamps\x00\x00Proof\x00\xbf\x89\xe2\xe8\x84\xe8\x92\x94";
+/// Supported major version of the OpenTimestamps proof format
+const OTS_VERSION: u8 = 0x01;
+/// SHA256 file hash op
+const OTS_OP_SHA256: u8 = 0x08;
+
+/// NIP03 error
+#[derive(Debug)]
+pub enum Error {
+    /// Not a [`Kind::OpenTimestamps`]
+    WrongKind,
+    /// Event (`e`) tag not found
+    EventTagNotFound,
+    /// Error while decoding the proof from base64
+    Base64(base64::DecodeError),
+    /// Not a valid OpenTimestamps proof
+    InvalidProof,
+    /// The proof doesn't commit to the attested event ID
+    DigestMismatch,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongKind => write!(f, "Wrong kind: expected OpenTimestamps attestation"),
+            Self::EventTagNotFound => write!(f, "Event tag not found"),
+            Self::Base64(e) => write!(f, "Base64: {e}"),
+            Self::InvalidProof => write!(f, "Invalid OpenTimestamps proof"),
+            Self::DigestMismatch => write!(f, "Proof digest doesn't match the event ID"),
+        }
+    }
+}
+
+impl From<base64::DecodeError> for Error {
+    fn from(e: base64::DecodeError) -> Self {
+        Self::Base64(e)
+    }
+}
+
+/// OpenTimestamps attestation (kind `1040`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenTimestampsAttestation {
+    /// Attested event ID
+    pub event_id: EventId,
+    /// Relay hint of the attested event
+    pub relay_url: Option<UncheckedUrl>,
+    /// Base64-encoded OpenTimestamps proof
+    pub ots_proof: String,
+}
+
+impl OpenTimestampsAttestation {
+    /// New attestation
+    pub fn new<S>(event_id: EventId, ots_proof: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            event_id,
+            relay_url: None,
+            ots_proof: ots_proof.into(),
+        }
+    }
+
+    /// Set relay hint of the attested event
+    pub fn relay_url(self, relay_url: UncheckedUrl) -> Self {
+        Self {
+            relay_url: Some(relay_url),
+            ..self
+        }
+    }
+
+    /// Parse attestation from [`Event`]
+    ///
+    /// The proof is not checked: use [`OpenTimestampsAttestation::verify`].
+    pub fn from_event(event: &Event) -> Result<Self, Error> {
+        if event.kind != Kind::OpenTimestamps {
+            return Err(Error::WrongKind);
+        }
+
+        let (event_id, relay_url) = event
+            .tags
+            .iter()
+            .find_map(|tag| match tag {
+                Tag::Event {
+                    event_id,
+                    relay_url,
+                    ..
+                } => Some((*event_id, relay_url.clone())),
+                _ => None,
+            })
+            .ok_or(Error::EventTagNotFound)?;
+
+        Ok(Self {
+            event_id,
+            relay_url,
+            ots_proof: event.content.clone(),
+        })
+    }
+
+    /// Decode the OpenTimestamps proof
+    pub fn proof(&self) -> Result<Vec<u8>, Error> {
+        Ok(general_purpose::STANDARD.decode(self.ots_proof.trim())?)
+    }
+
+    /// Get the SHA256 digest the proof commits to
+    pub fn digest(&self) -> Result<[u8; 32], Error> {
+        let proof: Vec<u8> = self.proof()?;
+        let rest: &[u8] = proof
+            .strip_prefix(OTS_HEADER_MAGIC)
+            .ok_or(Error::InvalidProof)?;
+        match rest {
+            [OTS_VERSION, OTS_OP_SHA256, digest @ ..] if digest.len() >= 32 => {
+                let mut buf: [u8; 32] = [0u8; 32];
+                buf.copy_from_slice(&digest[..32]);
+                Ok(buf)
+            }
+            _ => Err(Error::InvalidProof),
+        }
+    }
+
+    /// Check that the proof is a well-formed OpenTimestamps proof for the attested event ID
+    ///
+    /// The Bitcoin attestations contained in the proof are **not** verified.
+    pub fn verify(&self) -> Result<(), Error> {
+        if self.digest()? == self.event_id.to_bytes() {
+            Ok(())
+        } else {
+            Err(Error::DigestMismatch)
+        }
+    }
+}
+
+impl From<OpenTimestampsAttestation> for Vec<Tag> {
+    fn from(attestation: OpenTimestampsAttestation) -> Self {
+        vec![Tag::Event {
+            event_id: attestation.event_id,
+            relay_url: attestation.relay_url,
+            marker: None,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use super::*;
+    use crate::{EventBuilder, Keys};
+
+    const EVENT_ID: &str = "f5e5842b677ec450c5668daf8f99827cba91a9d80705ab3e0422f0ac4519cf84";
+
+    fn proof_for(event_id: &EventId) -> String {
+        let mut proof: Vec<u8> = OTS_HEADER_MAGIC.to_vec();
+        proof.extend([OTS_VERSION, OTS_OP_SHA256]);
+        proof.extend(event_id.as_bytes());
+        // Pending attestation
+        proof.extend([0x00, 0x83, 0xdf, 0xe3, 0x0d, 0x2e, 0xf9, 0x0c, 0x8e]);
+        general_purpose::STANDARD.encode(proof)
+    }
+
+    #[test]
+    fn test_attestation_from_event() {
+        let keys = Keys::generate();
+        let event_id = EventId::from_hex(EVENT_ID).unwrap();
+        let event = EventBuilder::ots_attestation(event_id, proof_for(&event_id))
+            .to_event(&keys)
+            .unwrap();
+        assert_eq!(event.kind, Kind::OpenTimestamps);
+
+        let attestation = OpenTimestampsAttestation::from_event(&event).unwrap();
+        assert_eq!(attestation.event_id, event_id);
+        assert!(attestation.relay_url.is_none());
+        assert!(attestation.verify().is_ok());
+
+        let event = EventBuilder::new_text_note("", []).to_event(&keys).unwrap();
+        assert!(matches!(
+            OpenTimestampsAttestation::from_event(&event),
+            Err(Error::WrongKind)
+        ));
+    }
+
+    #[test]
+    fn test_attestation_verify() {
+        let event_id = EventId::from_hex(EVENT_ID).unwrap();
+        let other_id =
+            EventId::from_str("7469af3be8c8e06e1b50ef1caceba30392ddc0b6614507398b7d7daa4c218e96")
+                .unwrap();
+
+        let attestation = OpenTimestampsAttestation::new(other_id, proof_for(&event_id));
+        assert!(matches!(attestation.verify(), Err(Error::DigestMismatch)));
+
+        let attestation = OpenTimestampsAttestation::new(event_id, "aGVsbG8=");
+        assert!(matches!(attestation.verify(), Err(Error::InvalidProof)));
+
+        let attestation = OpenTimestampsAttestation::new(event_id, "not base64!");
+        assert!(matches!(attestation.verify(), Err(Error::Base64(_))));
+    }
+}
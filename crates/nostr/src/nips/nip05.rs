@@ -5,6 +5,10 @@
 //! NIP05
 //!
 //! <https://github.com/nostr-protocol/nips/blob/master/05.md>
+//!
+//! [`verify`] and [`profile`] fetch the `.well-known/nostr.json` with `reqwest`.
+//! To use another HTTP client, fetch the [`Nip05Address::url`] and pass the response body
+//! to [`verify_from_json`] or [`profile_from_json`].
 
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
@@ -14,7 +18,10 @@ use std::net::SocketAddr;
 
 use bitcoin::secp256k1::{self, XOnlyPublicKey};
 #[cfg(not(target_arch = "wasm32"))]
+use reqwest::redirect::Policy;
+#[cfg(not(target_arch = "wasm32"))]
 use reqwest::Proxy;
+use reqwest::StatusCode;
 use serde_json::Value;
 
 use crate::nips::nip19::Nip19Profile;
@@ -24,9 +31,17 @@ use crate::nips::nip19::Nip19Profile;
 pub enum Error {
     /// Invalid format
     InvalidFormat,
+    /// The name is not listed by the domain
+    NotFound,
+    /// The name is listed with another public key
+    PublicKeyMismatch,
     /// Impossible to verify
+    #[deprecated(
+        since = "0.27.0",
+        note = "Use `NotFound` or `PublicKeyMismatch` instead"
+    )]
     ImpossibleToVerify,
-    /// Reqwest error
+    /// Network error
     Reqwest(reqwest::Error),
     /// Error deserializing JSON data
     Json(serde_json::Error),
@@ -41,6 +56,9 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::InvalidFormat => write!(f, "invalid format"),
+            Self::NotFound => write!(f, "not found"),
+            Self::PublicKeyMismatch => write!(f, "public key mismatch"),
+            #[allow(deprecated)]
             Self::ImpossibleToVerify => write!(f, "impossible to verify"),
             Self::Reqwest(e) => write!(f, "{e}"),
             Self::Json(e) => write!(f, "impossible to deserialize NIP05 data: {e}"),
@@ -67,75 +85,123 @@ impl From<secp256k1::Error> for Error {
     }
 }
 
-fn compose_url<S>(nip05: S) -> Result<(String, String), Error>
-where
-    S: Into<String>,
-{
-    let nip05: String = nip05.into();
-    let data: Vec<&str> = nip05.split('@').collect();
-    if data.len() != 2 {
-        return Err(Error::InvalidFormat);
-    }
-    let name: &str = data[0];
-    let domain: &str = data[1];
-    let url = format!("https://{domain}/.well-known/nostr.json?name={name}");
-    Ok((url, name.to_string()))
+/// NIP05 address (`<name>@<domain>`)
+///
+/// A bare domain is parsed as `_@<domain>`. The name is case-insensitive.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Nip05Address {
+    name: String,
+    domain: String,
 }
 
-fn get_key_from_json<S>(json: Value, name: S) -> Option<XOnlyPublicKey>
-where
-    S: Into<String>,
-{
-    let name: String = name.into();
-    json.get("names")
-        .and_then(|names| names.get(name))
-        .and_then(|value| value.as_str())
-        .and_then(|pubkey| XOnlyPublicKey::from_str(pubkey).ok())
+impl Nip05Address {
+    /// Parse NIP05 address
+    pub fn parse<S>(address: S) -> Result<Self, Error>
+    where
+        S: AsRef<str>,
+    {
+        let address: &str = address.as_ref().trim();
+        let (name, domain) = match address.split_once('@') {
+            Some((name, domain)) => (name, domain),
+            None => ("_", address),
+        };
+
+        if name.is_empty() || domain.is_empty() || domain.contains(['@', '/', '?', '#']) {
+            return Err(Error::InvalidFormat);
+        }
+
+        Ok(Self {
+            name: name.to_lowercase(),
+            domain: domain.to_lowercase(),
+        })
+    }
+
+    /// Get name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get domain
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// Get the `.well-known/nostr.json` URL
+    pub fn url(&self) -> String {
+        format!(
+            "https://{}/.well-known/nostr.json?name={}",
+            self.domain, self.name
+        )
+    }
 }
 
-fn get_relays_from_json(json: Value, pk: XOnlyPublicKey) -> Vec<String> {
-    let relays_list: Option<Vec<String>> = json
-        .get("relays")
-        .and_then(|relays| relays.get(pk.to_string()))
-        .and_then(|value| serde_json::from_value(value.clone()).ok());
+impl FromStr for Nip05Address {
+    type Err = Error;
 
-    match relays_list {
-        None => vec![],
-        Some(v) => v,
+    fn from_str(address: &str) -> Result<Self, Self::Err> {
+        Self::parse(address)
     }
 }
 
-fn verify_json<S>(public_key: XOnlyPublicKey, json: Value, name: S) -> Result<(), Error>
-where
-    S: Into<String>,
-{
-    if let Some(pubkey) = get_key_from_json(json, name) {
-        if pubkey == public_key {
-            return Ok(());
-        }
+impl fmt::Display for Nip05Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.name, self.domain)
     }
+}
 
-    Err(Error::ImpossibleToVerify)
+fn get_key_from_json(json: &Value, address: &Nip05Address) -> Result<XOnlyPublicKey, Error> {
+    let public_key: &str = json
+        .get("names")
+        .and_then(|names| names.get(address.name()))
+        .and_then(|value| value.as_str())
+        .ok_or(Error::NotFound)?;
+    Ok(XOnlyPublicKey::from_str(public_key)?)
 }
 
-/// Verify NIP05
-///
-/// **Proxy is ignored for WASM targets!**
-pub async fn verify<S>(
+fn get_relays_from_json(json: &Value, pk: XOnlyPublicKey) -> Vec<String> {
+    json.get("relays")
+        .and_then(|relays| relays.get(pk.to_string()))
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Verify NIP05 from the content of the `.well-known/nostr.json`
+pub fn verify_from_json<S>(
     public_key: XOnlyPublicKey,
-    nip05: S,
-    _proxy: Option<SocketAddr>,
+    address: &Nip05Address,
+    json: S,
 ) -> Result<(), Error>
 where
-    S: Into<String>,
+    S: AsRef<str>,
 {
-    use reqwest::Client;
+    let json: Value = serde_json::from_str(json.as_ref())?;
+    if get_key_from_json(&json, address)? == public_key {
+        Ok(())
+    } else {
+        Err(Error::PublicKeyMismatch)
+    }
+}
+
+/// Get [`Nip19Profile`] (public key and list of advertised relays) from the content of the `.well-known/nostr.json`
+pub fn profile_from_json<S>(address: &Nip05Address, json: S) -> Result<Nip19Profile, Error>
+where
+    S: AsRef<str>,
+{
+    let json: Value = serde_json::from_str(json.as_ref())?;
+    let public_key: XOnlyPublicKey = get_key_from_json(&json, address)?;
+    let relays: Vec<String> = get_relays_from_json(&json, public_key);
+    Ok(Nip19Profile { public_key, relays })
+}
 
-    let (url, name) = compose_url(nip05)?;
+/// Fetch the `.well-known/nostr.json` of the address
+///
+/// The redirects are not followed, as required by NIP05.
+async fn fetch_json(address: &Nip05Address, _proxy: Option<SocketAddr>) -> Result<String, Error> {
+    use reqwest::Client;
 
     #[cfg(not(target_arch = "wasm32"))]
     let client: Client = {
-        let mut builder = Client::builder();
+        let mut builder = Client::builder().redirect(Policy::none());
         if let Some(proxy) = _proxy {
             let proxy = format!("socks5h://{proxy}");
             builder = builder.proxy(Proxy::all(proxy)?);
@@ -146,90 +212,185 @@ where
     #[cfg(target_arch = "wasm32")]
     let client: Client = Client::new();
 
-    let res = client.get(url).send().await?;
-    let json: Value = serde_json::from_str(&res.text().await?)?;
-    verify_json(public_key, json, name)
+    let res = client.get(address.url()).send().await?;
+    if res.status() == StatusCode::NOT_FOUND {
+        return Err(Error::NotFound);
+    }
+    Ok(res.error_for_status()?.text().await?)
 }
 
-/// Verify NIP05
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg(feature = "blocking")]
-pub fn verify_blocking<S>(
-    public_key: XOnlyPublicKey,
-    nip05: S,
-    proxy: Option<SocketAddr>,
-) -> Result<(), Error>
-where
-    S: Into<String>,
-{
+fn fetch_json_blocking(address: &Nip05Address, proxy: Option<SocketAddr>) -> Result<String, Error> {
     use reqwest::blocking::Client;
 
-    let (url, name) = compose_url(nip05)?;
-    let mut builder = Client::builder();
+    let mut builder = Client::builder().redirect(Policy::none());
     if let Some(proxy) = proxy {
         let proxy = format!("socks5h://{proxy}");
         builder = builder.proxy(Proxy::all(proxy)?);
     }
     let client: Client = builder.build()?;
-    let res = client.get(url).send()?;
-    let json: Value = serde_json::from_str(&res.text()?)?;
-    verify_json(public_key, json, name)
+    let res = client.get(address.url()).send()?;
+    if res.status() == StatusCode::NOT_FOUND {
+        return Err(Error::NotFound);
+    }
+    Ok(res.error_for_status()?.text()?)
 }
 
-/// Get [Profile] from NIP05 (public key and list of advertised relays)
+/// Verify NIP05
 ///
 /// **Proxy is ignored for WASM targets!**
-pub async fn get_profile<S>(nip05: S, _proxy: Option<SocketAddr>) -> Result<Nip19Profile, Error>
+pub async fn verify<S>(
+    public_key: XOnlyPublicKey,
+    nip05: S,
+    proxy: Option<SocketAddr>,
+) -> Result<(), Error>
 where
     S: Into<String>,
 {
-    use reqwest::Client;
-
-    let (url, name) = compose_url(nip05)?;
-
-    #[cfg(not(target_arch = "wasm32"))]
-    let client: Client = {
-        let mut builder = Client::builder();
-        if let Some(proxy) = _proxy {
-            let proxy = format!("socks5h://{proxy}");
-            builder = builder.proxy(Proxy::all(proxy)?);
-        }
-        builder.build()?
-    };
+    let address = Nip05Address::parse(nip05.into())?;
+    let json: String = fetch_json(&address, proxy).await?;
+    verify_from_json(public_key, &address, json)
+}
 
-    #[cfg(target_arch = "wasm32")]
-    let client: Client = Client::new();
+/// Verify NIP05
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(feature = "blocking")]
+pub fn verify_blocking<S>(
+    public_key: XOnlyPublicKey,
+    nip05: S,
+    proxy: Option<SocketAddr>,
+) -> Result<(), Error>
+where
+    S: Into<String>,
+{
+    let address = Nip05Address::parse(nip05.into())?;
+    let json: String = fetch_json_blocking(&address, proxy)?;
+    verify_from_json(public_key, &address, json)
+}
 
-    let res = client.get(url).send().await?;
-    let json: Value = serde_json::from_str(&res.text().await?)?;
+/// Get [`Nip19Profile`] from NIP05 (public key and list of advertised relays)
+///
+/// **Proxy is ignored for WASM targets!**
+pub async fn profile<S>(nip05: S, proxy: Option<SocketAddr>) -> Result<Nip19Profile, Error>
+where
+    S: Into<String>,
+{
+    let address = Nip05Address::parse(nip05.into())?;
+    let json: String = fetch_json(&address, proxy).await?;
+    profile_from_json(&address, json)
+}
 
-    let public_key = get_key_from_json(json.clone(), name).ok_or(Error::ImpossibleToVerify)?;
-    let relays = get_relays_from_json(json, public_key);
+/// Get [`Nip19Profile`] from NIP05 (public key and list of advertised relays)
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(feature = "blocking")]
+pub fn profile_blocking<S>(nip05: S, proxy: Option<SocketAddr>) -> Result<Nip19Profile, Error>
+where
+    S: Into<String>,
+{
+    let address = Nip05Address::parse(nip05.into())?;
+    let json: String = fetch_json_blocking(&address, proxy)?;
+    profile_from_json(&address, json)
+}
 
-    Ok(Nip19Profile { public_key, relays })
+/// Get [`Nip19Profile`] from NIP05 (public key and list of advertised relays)
+#[deprecated(since = "0.27.0", note = "Use `profile` instead")]
+pub async fn get_profile<S>(nip05: S, proxy: Option<SocketAddr>) -> Result<Nip19Profile, Error>
+where
+    S: Into<String>,
+{
+    profile(nip05, proxy).await
 }
 
-/// Get [Profile] from NIP05 (public key and list of advertised relays)
+/// Get [`Nip19Profile`] from NIP05 (public key and list of advertised relays)
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg(feature = "blocking")]
+#[deprecated(since = "0.27.0", note = "Use `profile_blocking` instead")]
 pub fn get_profile_blocking<S>(nip05: S, proxy: Option<SocketAddr>) -> Result<Nip19Profile, Error>
 where
     S: Into<String>,
 {
-    use reqwest::blocking::Client;
+    profile_blocking(nip05, proxy)
+}
 
-    let (url, name) = compose_url(nip05)?;
-    let mut builder = Client::builder();
-    if let Some(proxy) = proxy {
-        let proxy = format!("socks5h://{proxy}");
-        builder = builder.proxy(Proxy::all(proxy)?);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUBLIC_KEY: &str = "b0635d6a9851d3aed0cd6c495b282167acf761729078d975fc341b22650b07b9";
+    const JSON: &str = r#"{
+        "names": {
+            "bob": "b0635d6a9851d3aed0cd6c495b282167acf761729078d975fc341b22650b07b9",
+            "alice": "32e1827635450ebb3c5a7d12c1f8e7b2b514439ac10a67eef3d9fd9c5c68e245"
+        },
+        "relays": {
+            "b0635d6a9851d3aed0cd6c495b282167acf761729078d975fc341b22650b07b9": ["wss://relay.example.com", "wss://relay2.example.com"]
+        }
+    }"#;
+
+    #[test]
+    fn test_parse_address() {
+        let address = Nip05Address::parse("Bob@Example.com").unwrap();
+        assert_eq!(address.name(), "bob");
+        assert_eq!(address.domain(), "example.com");
+        assert_eq!(
+            address.url(),
+            "https://example.com/.well-known/nostr.json?name=bob"
+        );
+        assert_eq!(address.to_string(), "bob@example.com");
+
+        let address = Nip05Address::parse("example.com").unwrap();
+        assert_eq!(address.name(), "_");
+
+        assert!(matches!(
+            Nip05Address::parse("bob@"),
+            Err(Error::InvalidFormat)
+        ));
+        assert!(matches!(
+            Nip05Address::parse("a@b@example.com"),
+            Err(Error::InvalidFormat)
+        ));
     }
-    let client: Client = builder.build()?;
-    let res = client.get(url).send()?;
-    let json: Value = serde_json::from_str(&res.text()?)?;
 
-    let public_key = get_key_from_json(json.clone(), name).ok_or(Error::ImpossibleToVerify)?;
-    let relays = get_relays_from_json(json, public_key);
+    #[test]
+    fn test_verify_from_json() {
+        let public_key = XOnlyPublicKey::from_str(PUBLIC_KEY).unwrap();
 
-    Ok(Nip19Profile { public_key, relays })
+        let address = Nip05Address::parse("bob@example.com").unwrap();
+        assert!(verify_from_json(public_key, &address, JSON).is_ok());
+
+        let address = Nip05Address::parse("alice@example.com").unwrap();
+        assert!(matches!(
+            verify_from_json(public_key, &address, JSON),
+            Err(Error::PublicKeyMismatch)
+        ));
+
+        let address = Nip05Address::parse("carol@example.com").unwrap();
+        assert!(matches!(
+            verify_from_json(public_key, &address, JSON),
+            Err(Error::NotFound)
+        ));
+    }
+
+    #[test]
+    fn test_profile_from_json() {
+        let address = Nip05Address::parse("bob@example.com").unwrap();
+        let profile = profile_from_json(&address, JSON).unwrap();
+        assert_eq!(
+            profile.public_key,
+            XOnlyPublicKey::from_str(PUBLIC_KEY).unwrap()
+        );
+        assert_eq!(
+            profile.relays,
+            vec![
+                String::from("wss://relay.example.com"),
+                String::from("wss://relay2.example.com")
+            ]
+        );
+
+        // No relays
+        let address = Nip05Address::parse("alice@example.com").unwrap();
+        let profile = profile_from_json(&address, JSON).unwrap();
+        assert!(profile.relays.is_empty());
+    }
 }
@@ -8,6 +8,7 @@
 
 #[cfg(feature = "std")]
 use alloc::string::String;
+use alloc::vec::Vec;
 use core::fmt;
 use core::str::FromStr;
 
@@ -94,6 +95,39 @@ pub trait FromMnemonic: Sized {
     ) -> Result<Self, Self::Err>
     where
         C: Signing;
+
+    /// Derive multiple **accounts** from the same BIP-39 mnemonics (ENGLISH wordlist).
+    ///
+    /// The seed is computed only once, so it's faster than deriving the accounts one by one.
+    #[cfg(feature = "std")]
+    fn from_mnemonic_accounts<S, I>(
+        mnemonic: S,
+        passphrase: Option<S>,
+        accounts: I,
+    ) -> Result<Vec<Self>, Self::Err>
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = u32>,
+    {
+        let passphrase: Option<String> = passphrase.map(|p| p.into());
+        Self::from_mnemonic_accounts_with_ctx(
+            &SECP256K1,
+            &mnemonic.into(),
+            passphrase.as_deref(),
+            accounts,
+        )
+    }
+
+    /// Derive multiple **accounts** from the same BIP-39 mnemonics (ENGLISH wordlist).
+    fn from_mnemonic_accounts_with_ctx<C, I>(
+        secp: &Secp256k1<C>,
+        mnemonic: &str,
+        passphrase: Option<&str>,
+        accounts: I,
+    ) -> Result<Vec<Self>, Self::Err>
+    where
+        C: Signing,
+        I: IntoIterator<Item = u32>;
 }
 
 #[allow(missing_docs)]
@@ -120,16 +154,48 @@ impl FromMnemonic for Keys {
     where
         C: Signing,
     {
-        let mnemonic: Mnemonic = Mnemonic::from_str(mnemonic)?;
-        let seed: [u8; 64] = mnemonic.to_seed_normalized(passphrase.unwrap_or_default());
-        let root_key = ExtendedPrivKey::new_master(Network::Bitcoin, &seed)?;
-        let account: u32 = account.unwrap_or_default();
-        let path = DerivationPath::from_str(&format!("m/44'/1237'/{account}'/0/0"))?;
-        let child_xprv = root_key.derive_priv(secp, &path)?;
-        Ok(Self::new_with_ctx(secp, child_xprv.private_key))
+        let root_key: ExtendedPrivKey = root_key(mnemonic, passphrase)?;
+        derive_account(secp, &root_key, account.unwrap_or_default())
+    }
+
+    fn from_mnemonic_accounts_with_ctx<C, I>(
+        secp: &Secp256k1<C>,
+        mnemonic: &str,
+        passphrase: Option<&str>,
+        accounts: I,
+    ) -> Result<Vec<Self>, Self::Err>
+    where
+        C: Signing,
+        I: IntoIterator<Item = u32>,
+    {
+        let root_key: ExtendedPrivKey = root_key(mnemonic, passphrase)?;
+        accounts
+            .into_iter()
+            .map(|account| derive_account(secp, &root_key, account))
+            .collect()
     }
 }
 
+fn root_key(mnemonic: &str, passphrase: Option<&str>) -> Result<ExtendedPrivKey, Error> {
+    let mnemonic: Mnemonic = Mnemonic::from_str(mnemonic)?;
+    let seed: [u8; 64] = mnemonic.to_seed_normalized(passphrase.unwrap_or_default());
+    Ok(ExtendedPrivKey::new_master(Network::Bitcoin, &seed)?)
+}
+
+/// Derive `m/44'/1237'/<account>'/0/0`
+fn derive_account<C>(
+    secp: &Secp256k1<C>,
+    root_key: &ExtendedPrivKey,
+    account: u32,
+) -> Result<Keys, Error>
+where
+    C: Signing,
+{
+    let path = DerivationPath::from_str(&format!("m/44'/1237'/{account}'/0/0"))?;
+    let child_xprv = root_key.derive_priv(secp, &path)?;
+    Ok(Keys::new_with_ctx(secp, child_xprv.private_key))
+}
+
 impl GenerateMnemonic for Keys {
     type Err = Error;
 
@@ -172,5 +238,51 @@ mod tests {
             SecretKey::from_str("06992419a8fe821dd8de03d4c300614e8feefb5ea936b76f89976dcace8aebee")
                 .unwrap()
         );
+
+        let mnemonic: &str =
+            "leader monkey parrot ring guide accident before fence cannon height naive bean";
+        let keys = Keys::from_mnemonic_with_ctx(&secp, mnemonic, None, None).unwrap();
+
+        assert_eq!(
+            keys.secret_key().unwrap(),
+            SecretKey::from_str("7f7ff03d123792d6ac594bfa67bf6d0c0ab55b6b1fdb6249303fe861f1ccba9a")
+                .unwrap()
+        );
+
+        let mnemonic: &str = "what bleak badge arrange retreat wolf trade produce cricket blur garlic valid proud rude strong choose busy staff weather area salt hollow arm fade";
+        let keys = Keys::from_mnemonic_with_ctx(&secp, mnemonic, None, None).unwrap();
+
+        assert_eq!(
+            keys.secret_key().unwrap(),
+            SecretKey::from_str("c15d739894c81a2fcfd3a2df85a0d2c0dbc47a280d092799f144d73d7ae78add")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_nip06_accounts() {
+        let secp = Secp256k1::new();
+
+        let mnemonic: &str = "equal dragon fabric refuse stable cherry smoke allow alley easy never medal attend together lumber movie what sad siege weather matrix buffalo state shoot";
+        let accounts =
+            Keys::from_mnemonic_accounts_with_ctx(&secp, mnemonic, Some("passphrase"), 0..3)
+                .unwrap();
+        assert_eq!(accounts.len(), 3);
+
+        for (account, keys) in accounts.iter().enumerate() {
+            let expected = Keys::from_mnemonic_with_ctx(
+                &secp,
+                mnemonic,
+                Some("passphrase"),
+                Some(account as u32),
+            )
+            .unwrap();
+            assert_eq!(keys.public_key(), expected.public_key());
+        }
+        assert_ne!(accounts[0].public_key(), accounts[1].public_key());
+
+        // The passphrase changes the keys
+        let keys = Keys::from_mnemonic_with_ctx(&secp, mnemonic, None, Some(0)).unwrap();
+        assert_ne!(keys.public_key(), accounts[0].public_key());
     }
 }
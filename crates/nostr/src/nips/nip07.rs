@@ -198,29 +198,28 @@ impl Nip07Signer {
 
     // TODO: add `getRelays`
 
-    fn nip04_obj(&self) -> Result<Object, Error> {
-        let namespace: JsValue = Reflect::get(&self.nostr_obj, &JsValue::from_str("nip04"))
-            .map_err(|_| Error::NamespaceNotFound(String::from("nip04")))?;
+    fn namespace_obj(&self, name: &str) -> Result<Object, Error> {
+        let namespace: JsValue = Reflect::get(&self.nostr_obj, &JsValue::from_str(name))
+            .map_err(|_| Error::NamespaceNotFound(name.to_string()))?;
         namespace
             .dyn_into()
-            .map_err(|_| Error::NamespaceNotFound(String::from("nip04")))
+            .map_err(|_| Error::NamespaceNotFound(name.to_string()))
     }
 
-    /// NIP04 encrypt
-    pub async fn nip04_encrypt<S>(
+    /// Call `window.nostr.<namespace>.<name>(pubkey, text)`
+    async fn call_namespace_func(
         &self,
+        namespace: &str,
+        name: &str,
         public_key: XOnlyPublicKey,
-        plaintext: S,
-    ) -> Result<String, Error>
-    where
-        S: AsRef<str>,
-    {
-        let nip04_obj: Object = self.nip04_obj()?;
-        let func: Function = self.get_func(&nip04_obj, "encrypt")?;
+        text: &str,
+    ) -> Result<String, Error> {
+        let namespace_obj: Object = self.namespace_obj(namespace)?;
+        let func: Function = self.get_func(&namespace_obj, name)?;
         let promise: Promise = Promise::resolve(&func.call2(
-            &nip04_obj,
+            &namespace_obj,
             &JsValue::from_str(&public_key.to_string()),
-            &JsValue::from_str(plaintext.as_ref()),
+            &JsValue::from_str(text),
         )?);
         let result: JsValue = JsFuture::from(promise).await?;
         result
@@ -228,6 +227,19 @@ impl Nip07Signer {
             .ok_or_else(|| Error::TypeMismatch(String::from("expected a string")))
     }
 
+    /// NIP04 encrypt
+    pub async fn nip04_encrypt<S>(
+        &self,
+        public_key: XOnlyPublicKey,
+        plaintext: S,
+    ) -> Result<String, Error>
+    where
+        S: AsRef<str>,
+    {
+        self.call_namespace_func("nip04", "encrypt", public_key, plaintext.as_ref())
+            .await
+    }
+
     /// NIP04 decrypt
     pub async fn nip04_decrypt<S>(
         &self,
@@ -237,16 +249,37 @@ impl Nip07Signer {
     where
         S: AsRef<str>,
     {
-        let nip04_obj: Object = self.nip04_obj()?;
-        let func: Function = self.get_func(&nip04_obj, "decrypt")?;
-        let promise: Promise = Promise::resolve(&func.call2(
-            &nip04_obj,
-            &JsValue::from_str(&public_key.to_string()),
-            &JsValue::from_str(ciphertext.as_ref()),
-        )?);
-        let result: JsValue = JsFuture::from(promise).await?;
-        result
-            .as_string()
-            .ok_or_else(|| Error::TypeMismatch(String::from("expected a string")))
+        self.call_namespace_func("nip04", "decrypt", public_key, ciphertext.as_ref())
+            .await
+    }
+
+    /// NIP44 encrypt
+    ///
+    /// Require an extension supporting `window.nostr.nip44`.
+    pub async fn nip44_encrypt<S>(
+        &self,
+        public_key: XOnlyPublicKey,
+        plaintext: S,
+    ) -> Result<String, Error>
+    where
+        S: AsRef<str>,
+    {
+        self.call_namespace_func("nip44", "encrypt", public_key, plaintext.as_ref())
+            .await
+    }
+
+    /// NIP44 decrypt
+    ///
+    /// Require an extension supporting `window.nostr.nip44`.
+    pub async fn nip44_decrypt<S>(
+        &self,
+        public_key: XOnlyPublicKey,
+        payload: S,
+    ) -> Result<String, Error>
+    where
+        S: AsRef<str>,
+    {
+        self.call_namespace_func("nip44", "decrypt", public_key, payload.as_ref())
+            .await
     }
 }
@@ -0,0 +1,155 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP10
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/10.md>
+
+use alloc::vec::Vec;
+
+use crate::event::tag::Marker;
+use crate::{Event, EventId, Tag};
+
+/// `e` tags of an event, classified by their role in the thread
+///
+/// The marked scheme is used if at least one `e` tag has the `root` or `reply` marker:
+/// the other `e` tags are mentions.
+/// Otherwise the deprecated positional scheme is used: the first tag is the root,
+/// the last one the reply and the ones in between are mentions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThreadTags {
+    /// Root of the thread
+    pub root: Option<EventId>,
+    /// Event directly replied to (`None` for a direct reply to the root)
+    pub reply: Option<EventId>,
+    /// Mentioned events
+    pub mentions: Vec<EventId>,
+}
+
+impl ThreadTags {
+    /// Classify the `e` tags
+    pub fn from_tags<'a, I>(tags: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Tag>,
+    {
+        let tags: Vec<(EventId, Option<&Marker>)> = tags
+            .into_iter()
+            .filter_map(|tag| match tag {
+                Tag::Event {
+                    event_id, marker, ..
+                } => Some((*event_id, marker.as_ref())),
+                _ => None,
+            })
+            .collect();
+
+        let is_marked: bool = tags
+            .iter()
+            .any(|(_, marker)| matches!(marker, Some(Marker::Root | Marker::Reply)));
+
+        let mut thread = Self::default();
+
+        if is_marked {
+            for (event_id, marker) in tags.into_iter() {
+                match marker {
+                    Some(Marker::Root) if thread.root.is_none() => thread.root = Some(event_id),
+                    Some(Marker::Reply) if thread.reply.is_none() => thread.reply = Some(event_id),
+                    _ => thread.mentions.push(event_id),
+                }
+            }
+        } else {
+            let len: usize = tags.len();
+            for (index, (event_id, _)) in tags.into_iter().enumerate() {
+                if index == 0 {
+                    thread.root = Some(event_id);
+                } else if index == len - 1 {
+                    thread.reply = Some(event_id);
+                } else {
+                    thread.mentions.push(event_id);
+                }
+            }
+        }
+
+        thread
+    }
+
+    /// Classify the `e` tags of the event
+    pub fn from_event(event: &Event) -> Self {
+        Self::from_tags(event.tags.iter())
+    }
+
+    /// Root of the thread, falling back to the replied event if the root is not tagged
+    pub fn thread_root(&self) -> Option<EventId> {
+        self.root.or(self.reply)
+    }
+
+    /// Event replied to, falling back to the root for direct replies
+    pub fn reply_to(&self) -> Option<EventId> {
+        self.reply.or(self.root)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use super::*;
+    use crate::JsonUtil;
+
+    fn event_id(n: u8) -> EventId {
+        EventId::from_slice(&[n; 32]).unwrap()
+    }
+
+    fn e_tag(n: u8, marker: Option<&str>) -> Tag {
+        Tag::Event {
+            event_id: event_id(n),
+            relay_url: None,
+            marker: marker.map(Marker::from),
+        }
+    }
+
+    #[test]
+    fn test_marked_scheme() {
+        let tags = [
+            e_tag(1, Some("root")),
+            e_tag(2, None),
+            e_tag(3, Some("reply")),
+            e_tag(4, Some("mention")),
+        ];
+        let thread = ThreadTags::from_tags(tags.iter());
+        assert_eq!(thread.root, Some(event_id(1)));
+        assert_eq!(thread.reply, Some(event_id(3)));
+        assert_eq!(thread.mentions, vec![event_id(2), event_id(4)]);
+
+        // Direct reply to the root
+        let tags = [e_tag(1, Some("root")), e_tag(2, None)];
+        let thread = ThreadTags::from_tags(tags.iter());
+        assert_eq!(thread.reply, None);
+        assert_eq!(thread.reply_to(), Some(event_id(1)));
+        assert_eq!(thread.mentions, vec![event_id(2)]);
+    }
+
+    #[test]
+    fn test_positional_scheme() {
+        let tags = [e_tag(1, None)];
+        let thread = ThreadTags::from_tags(tags.iter());
+        assert_eq!(thread.thread_root(), Some(event_id(1)));
+        assert_eq!(thread.reply_to(), Some(event_id(1)));
+
+        let tags = [e_tag(1, None), e_tag(2, None), e_tag(3, None)];
+        let thread = ThreadTags::from_tags(tags.iter());
+        assert_eq!(thread.root, Some(event_id(1)));
+        assert_eq!(thread.reply, Some(event_id(3)));
+        assert_eq!(thread.mentions, vec![event_id(2)]);
+
+        let tags = [Tag::Hashtag(String::from("nostr"))];
+        assert_eq!(ThreadTags::from_tags(tags.iter()), ThreadTags::default());
+    }
+
+    #[test]
+    fn test_from_event() {
+        let event = Event::from_json(r#"{"id":"2be17aa3031bdcb006f0fce80c146dea9c1c0268b0af2398bb673365c6444d45","pubkey":"f86c44a2de95d9149b51c6a29afeabba264c18e2fa7c49de93424a0c56947785","created_at":1640839235,"kind":1,"tags":[["e","0101010101010101010101010101010101010101010101010101010101010101","","root"]],"content":"reply","sig":"a5d9290ef9659083c490b303eb7ee41356d8778ff19f2f91776c8dc4443388a64ffcf336e61af4c25c05ac3ae952d1ced889ed655b67790891222aaa15b99fdd"}"#).unwrap();
+        assert_eq!(event.thread_root(), Some(event_id(1)));
+        assert_eq!(event.reply_to(), Some(event_id(1)));
+    }
+}
@@ -8,7 +8,11 @@
 //! <https://github.com/nostr-protocol/nips/blob/master/13.md>
 
 use alloc::string::String;
+#[cfg(feature = "std")]
+use alloc::sync::Arc;
 use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::sync::atomic::{AtomicBool, Ordering};
 
 /// Gets the number of leading zero bits. Result is between 0 and 255.
 pub fn get_leading_zero_bits<T>(h: T) -> u8
@@ -27,6 +31,20 @@ where
     res
 }
 
+/// Gets the POW difficulty committed by an event.
+///
+/// Returns the `target` difficulty of the `nonce` tag if the ID has at least `target` leading zero bits,
+/// otherwise `0`: an ID that is luckier than its committed target only counts for the target.
+pub fn get_committed_difficulty<T>(id: T, target: Option<u8>) -> u8
+where
+    T: AsRef<[u8]>,
+{
+    match target {
+        Some(target) if get_leading_zero_bits(id) >= target => target,
+        _ => 0,
+    }
+}
+
 /// Returns all possible ID prefixes (hex) that have the specified number of leading zero bits.
 ///
 /// Possible values: 0-255
@@ -56,6 +74,33 @@ pub fn get_prefixes_for_difficulty(leading_zero_bits: u8) -> Vec<String> {
     r
 }
 
+/// Token to abort a running POW mining
+///
+/// Clones share the same state: cancelling one of them stops all the workers.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default)]
+pub struct PowCancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+#[cfg(feature = "std")]
+impl PowCancelToken {
+    /// New cancel token
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Abort the mining
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Check if the mining has been aborted
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use core::str::FromStr;
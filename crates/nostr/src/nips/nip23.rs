@@ -0,0 +1,295 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP23
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/23.md>
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use bitcoin::secp256k1::XOnlyPublicKey;
+
+use crate::nips::nip01::Coordinate;
+use crate::{Event, ImageDimensions, Kind, Tag, Timestamp, UncheckedUrl};
+
+/// NIP23 Error
+#[derive(Debug)]
+pub enum Error {
+    /// Not a [`Kind::LongFormTextNote`] or [`Kind::LongFormTextNoteDraft`]
+    WrongKind,
+    /// Identifier (`d`) tag not found
+    IdentifierTagNotFound,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongKind => write!(f, "Wrong kind: expected long-form text note"),
+            Self::IdentifierTagNotFound => write!(f, "Identifier tag not found"),
+        }
+    }
+}
+
+/// Long-form article
+///
+/// The `identifier` is the `d` tag of the article: to edit an article, it must not change.
+/// Use [`ArticleBuilder::from_event`] to edit a published article or a draft.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArticleBuilder {
+    /// Identifier (`d` tag)
+    pub identifier: String,
+    /// Markdown content
+    pub content: String,
+    /// Title
+    pub title: Option<String>,
+    /// Summary
+    pub summary: Option<String>,
+    /// Image
+    pub image: Option<(UncheckedUrl, Option<ImageDimensions>)>,
+    /// First publication timestamp
+    pub published_at: Option<Timestamp>,
+    /// Hashtags
+    pub hashtags: Vec<String>,
+    /// Other tags (i.e. references to events or profiles)
+    pub tags: Vec<Tag>,
+    /// Draft ([`Kind::LongFormTextNoteDraft`])
+    pub draft: bool,
+}
+
+impl ArticleBuilder {
+    /// New article
+    pub fn new<S, C>(identifier: S, content: C) -> Self
+    where
+        S: Into<String>,
+        C: Into<String>,
+    {
+        Self {
+            identifier: identifier.into(),
+            content: content.into(),
+            title: None,
+            summary: None,
+            image: None,
+            published_at: None,
+            hashtags: Vec::new(),
+            tags: Vec::new(),
+            draft: false,
+        }
+    }
+
+    /// Parse a [`Kind::LongFormTextNote`] or a [`Kind::LongFormTextNoteDraft`]
+    ///
+    /// The identifier and the first publication timestamp are preserved,
+    /// so the article can be edited without creating a new one.
+    pub fn from_event(event: &Event) -> Result<Self, Error> {
+        let draft: bool = match event.kind {
+            Kind::LongFormTextNote => false,
+            Kind::LongFormTextNoteDraft => true,
+            _ => return Err(Error::WrongKind),
+        };
+
+        let identifier: String = event
+            .identifier()
+            .ok_or(Error::IdentifierTagNotFound)?
+            .to_string();
+        let mut article = Self::new(identifier, event.content.clone());
+        article.draft = draft;
+
+        for tag in event.tags.iter() {
+            match tag {
+                Tag::Identifier(..) => (),
+                Tag::Title(title) => article.title = Some(title.clone()),
+                Tag::Summary(summary) => article.summary = Some(summary.clone()),
+                Tag::Image(url, dim) => article.image = Some((url.clone(), *dim)),
+                Tag::PublishedAt(timestamp) => article.published_at = Some(*timestamp),
+                Tag::Hashtag(hashtag) => article.hashtags.push(hashtag.clone()),
+                tag => article.tags.push(tag.clone()),
+            }
+        }
+
+        Ok(article)
+    }
+
+    /// Set content
+    pub fn content<S>(self, content: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            content: content.into(),
+            ..self
+        }
+    }
+
+    /// Set title
+    pub fn title<S>(self, title: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            title: Some(title.into()),
+            ..self
+        }
+    }
+
+    /// Set summary
+    pub fn summary<S>(self, summary: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            summary: Some(summary.into()),
+            ..self
+        }
+    }
+
+    /// Set image
+    pub fn image(self, url: UncheckedUrl, dim: Option<ImageDimensions>) -> Self {
+        Self {
+            image: Some((url, dim)),
+            ..self
+        }
+    }
+
+    /// Set first publication timestamp
+    pub fn published_at(self, timestamp: Timestamp) -> Self {
+        Self {
+            published_at: Some(timestamp),
+            ..self
+        }
+    }
+
+    /// Add hashtags
+    pub fn hashtags<I, S>(mut self, hashtags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.hashtags.extend(hashtags.into_iter().map(|h| h.into()));
+        self
+    }
+
+    /// Add tags
+    pub fn tags<I>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = Tag>,
+    {
+        self.tags.extend(tags);
+        self
+    }
+
+    /// Set as draft
+    pub fn draft(self, draft: bool) -> Self {
+        Self { draft, ..self }
+    }
+
+    /// Get event kind
+    pub fn kind(&self) -> Kind {
+        if self.draft {
+            Kind::LongFormTextNoteDraft
+        } else {
+            Kind::LongFormTextNote
+        }
+    }
+
+    /// Get article [`Coordinate`]
+    pub fn coordinate(&self, public_key: XOnlyPublicKey) -> Coordinate {
+        Coordinate::new(self.kind(), public_key).identifier(self.identifier.clone())
+    }
+}
+
+impl From<ArticleBuilder> for Vec<Tag> {
+    fn from(article: ArticleBuilder) -> Self {
+        let ArticleBuilder {
+            identifier,
+            title,
+            summary,
+            image,
+            published_at,
+            hashtags,
+            tags: other_tags,
+            ..
+        } = article;
+
+        let mut tags: Vec<Tag> = vec![Tag::Identifier(identifier)];
+
+        if let Some(title) = title {
+            tags.push(Tag::Title(title));
+        }
+
+        if let Some(summary) = summary {
+            tags.push(Tag::Summary(summary));
+        }
+
+        if let Some((url, dim)) = image {
+            tags.push(Tag::Image(url, dim));
+        }
+
+        if let Some(published_at) = published_at {
+            tags.push(Tag::PublishedAt(published_at));
+        }
+
+        for hashtag in hashtags.into_iter() {
+            tags.push(Tag::Hashtag(hashtag));
+        }
+
+        tags.extend(other_tags);
+
+        tags
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventId, Keys};
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_article_roundtrip() {
+        let keys = Keys::generate();
+        let event_id = EventId::all_zeros();
+        let article = ArticleBuilder::new("lorem-ipsum", "Lorem ipsum dolor sit amet")
+            .title("Lorem Ipsum")
+            .summary("Placeholder")
+            .published_at(Timestamp::from(1296962229))
+            .hashtags(["placeholder"])
+            .tags([Tag::event(event_id)])
+            .draft(true);
+        assert_eq!(article.kind(), Kind::LongFormTextNoteDraft);
+
+        let event = EventBuilder::article(article.clone())
+            .to_event(&keys)
+            .unwrap();
+        assert_eq!(event.kind, Kind::LongFormTextNoteDraft);
+        assert_eq!(event.identifier(), Some("lorem-ipsum"));
+        assert_eq!(ArticleBuilder::from_event(&event).unwrap(), article);
+
+        // Promote
+        let article = ArticleBuilder::from_event(&event).unwrap().draft(false);
+        let event = EventBuilder::article(article).to_event(&keys).unwrap();
+        assert_eq!(event.kind, Kind::LongFormTextNote);
+        assert_eq!(event.identifier(), Some("lorem-ipsum"));
+        assert!(event
+            .tags
+            .contains(&Tag::PublishedAt(Timestamp::from(1296962229))));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_article_wrong_kind() {
+        let keys = Keys::generate();
+        let event = EventBuilder::new_text_note("Not an article", [])
+            .to_event(&keys)
+            .unwrap();
+        assert!(matches!(
+            ArticleBuilder::from_event(&event),
+            Err(Error::WrongKind)
+        ));
+    }
+}
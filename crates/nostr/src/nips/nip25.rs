@@ -0,0 +1,290 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP25
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/25.md>
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use core::fmt;
+
+use bitcoin::secp256k1::XOnlyPublicKey;
+
+use crate::{Event, EventId, Kind, Tag, UncheckedUrl};
+
+/// Reaction
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Reaction {
+    /// Like or upvote (`+`)
+    Like,
+    /// Dislike or downvote (`-`)
+    Dislike,
+    /// Emoji
+    Emoji(String),
+    /// Custom emoji
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/30.md>
+    CustomEmoji {
+        /// Name of the emoji (only alphanumeric characters and underscores)
+        shortcode: String,
+        /// URL of the emoji image
+        url: UncheckedUrl,
+    },
+}
+
+impl fmt::Display for Reaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Like => write!(f, "+"),
+            Self::Dislike => write!(f, "-"),
+            Self::Emoji(emoji) => write!(f, "{emoji}"),
+            Self::CustomEmoji { shortcode, .. } => write!(f, ":{shortcode}:"),
+        }
+    }
+}
+
+impl From<&str> for Reaction {
+    fn from(content: &str) -> Self {
+        match content {
+            "+" | "" => Self::Like,
+            "-" => Self::Dislike,
+            emoji => Self::Emoji(emoji.to_string()),
+        }
+    }
+}
+
+impl From<String> for Reaction {
+    fn from(content: String) -> Self {
+        Self::from(content.as_str())
+    }
+}
+
+impl Reaction {
+    /// Compose custom emoji reaction
+    pub fn custom_emoji<S>(shortcode: S, url: UncheckedUrl) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::CustomEmoji {
+            shortcode: shortcode.into(),
+            url,
+        }
+    }
+
+    /// Parse the reaction of a [`Kind::Reaction`] event
+    ///
+    /// `:shortcode:` is a [`Reaction::CustomEmoji`] only if the event has the matching `emoji` tag.
+    pub fn from_event(event: &Event) -> Self {
+        let content: &str = event.content.as_str();
+        if let Some(shortcode) = content.strip_prefix(':').and_then(|c| c.strip_suffix(':')) {
+            let url = event.tags.iter().find_map(|tag| match tag {
+                Tag::Emoji { shortcode: s, url } if s == shortcode => Some(url.clone()),
+                _ => None,
+            });
+            if let Some(url) = url {
+                return Self::custom_emoji(shortcode, url);
+            }
+        }
+        Self::from(content)
+    }
+
+    /// Reaction `content`
+    pub fn content(&self) -> String {
+        self.to_string()
+    }
+
+    /// `emoji` tag, for custom emojis
+    pub fn emoji_tag(&self) -> Option<Tag> {
+        match self {
+            Self::CustomEmoji { shortcode, url } => Some(Tag::Emoji {
+                shortcode: shortcode.clone(),
+                url: url.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Get the ID of the event the reaction is for (last `e` tag)
+pub fn reacted_event_id(event: &Event) -> Option<EventId> {
+    event
+        .tags
+        .iter()
+        .filter_map(|tag| match tag {
+            Tag::Event { event_id, .. } => Some(*event_id),
+            _ => None,
+        })
+        .next_back()
+}
+
+/// Reactions to an event, aggregated by content
+///
+/// Each public key is counted once per reaction content.
+///
+/// # Example
+/// ```rust
+/// use nostr::prelude::*;
+///
+/// let keys = Keys::generate();
+/// let note = EventBuilder::new_text_note("GM", []).to_event(&keys).unwrap();
+///
+/// let mut reactions = Reactions::new(note.id);
+/// for _ in 0..2 {
+///     let reaction = EventBuilder::react(&note, Reaction::Like)
+///         .to_event(&keys)
+///         .unwrap();
+///     reactions.add(&reaction);
+/// }
+/// assert_eq!(reactions.count(&Reaction::Like), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reactions {
+    event_id: EventId,
+    reactions: BTreeMap<Reaction, BTreeSet<XOnlyPublicKey>>,
+}
+
+impl Reactions {
+    /// No reactions to `event_id`
+    pub fn new(event_id: EventId) -> Self {
+        Self {
+            event_id,
+            reactions: BTreeMap::new(),
+        }
+    }
+
+    /// Aggregate the reactions to `event_id`
+    pub fn from_events<'a, I>(event_id: EventId, events: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Event>,
+    {
+        let mut reactions = Self::new(event_id);
+        for event in events.into_iter() {
+            reactions.add(event);
+        }
+        reactions
+    }
+
+    /// ID of the reacted event
+    pub fn event_id(&self) -> EventId {
+        self.event_id
+    }
+
+    /// Add a reaction
+    ///
+    /// Return `false` if it's not a reaction to the event or if the author already reacted with the same content.
+    pub fn add(&mut self, event: &Event) -> bool {
+        if event.kind != Kind::Reaction || reacted_event_id(event) != Some(self.event_id) {
+            return false;
+        }
+
+        self.reactions
+            .entry(Reaction::from_event(event))
+            .or_default()
+            .insert(event.pubkey)
+    }
+
+    /// Number of public keys that reacted with `reaction`
+    pub fn count(&self, reaction: &Reaction) -> usize {
+        self.reactions.get(reaction).map_or(0, |p| p.len())
+    }
+
+    /// Public keys that reacted with `reaction`
+    pub fn public_keys(&self, reaction: &Reaction) -> Option<&BTreeSet<XOnlyPublicKey>> {
+        self.reactions.get(reaction)
+    }
+
+    /// Iterate the reactions with the public keys that reacted
+    pub fn iter(&self) -> impl Iterator<Item = (&Reaction, &BTreeSet<XOnlyPublicKey>)> {
+        self.reactions.iter()
+    }
+
+    /// Total number of reactions
+    pub fn len(&self) -> usize {
+        self.reactions.values().map(|p| p.len()).sum()
+    }
+
+    /// Check if there aren't reactions
+    pub fn is_empty(&self) -> bool {
+        self.reactions.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, Keys};
+
+    #[test]
+    fn test_reaction_content() {
+        assert_eq!(Reaction::from("+"), Reaction::Like);
+        assert_eq!(Reaction::from(""), Reaction::Like);
+        assert_eq!(Reaction::from("-"), Reaction::Dislike);
+        assert_eq!(Reaction::from("🤙"), Reaction::Emoji(String::from("🤙")));
+
+        let keys = Keys::generate();
+        let note = EventBuilder::new_text_note("GM", [])
+            .to_event(&keys)
+            .unwrap();
+        let reaction =
+            Reaction::custom_emoji("soapbox", UncheckedUrl::from("https://example.com/s.png"));
+        let event = EventBuilder::react(&note, reaction.clone())
+            .to_event(&keys)
+            .unwrap();
+        assert_eq!(event.content, ":soapbox:");
+        assert_eq!(Reaction::from_event(&event), reaction);
+        assert_eq!(reacted_event_id(&event), Some(note.id));
+
+        // Without the emoji tag
+        let event = EventBuilder::new_reaction(note.id, note.pubkey, ":soapbox:")
+            .to_event(&keys)
+            .unwrap();
+        assert_eq!(
+            Reaction::from_event(&event),
+            Reaction::Emoji(String::from(":soapbox:"))
+        );
+    }
+
+    #[test]
+    fn test_aggregate_reactions() {
+        let author = Keys::generate();
+        let note = EventBuilder::new_text_note("GM", [])
+            .to_event(&author)
+            .unwrap();
+        let other = EventBuilder::new_text_note("GN", [])
+            .to_event(&author)
+            .unwrap();
+
+        let alice = Keys::generate();
+        let bob = Keys::generate();
+        let events = [
+            EventBuilder::react(&note, Reaction::Like)
+                .to_event(&alice)
+                .unwrap(),
+            EventBuilder::react(&note, Reaction::Like)
+                .to_event(&bob)
+                .unwrap(),
+            EventBuilder::react(&note, Reaction::Like)
+                .to_event(&bob)
+                .unwrap(),
+            EventBuilder::react(&note, Reaction::from("🤙"))
+                .to_event(&bob)
+                .unwrap(),
+            EventBuilder::react(&other, Reaction::Dislike)
+                .to_event(&alice)
+                .unwrap(),
+            note.clone(),
+        ];
+
+        let reactions = Reactions::from_events(note.id, events.iter());
+        assert_eq!(reactions.count(&Reaction::Like), 2);
+        assert_eq!(reactions.count(&Reaction::Emoji(String::from("🤙"))), 1);
+        assert_eq!(reactions.count(&Reaction::Dislike), 0);
+        assert_eq!(reactions.len(), 3);
+        assert!(reactions
+            .public_keys(&Reaction::Like)
+            .unwrap()
+            .contains(&alice.public_key()));
+    }
+}
@@ -0,0 +1,449 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP34
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/34.md>
+
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::nip01::Coordinate;
+use crate::{Tag, TagKind, Timestamp};
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// NIP34 error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The `From <commit>` line is missing or malformed
+    MissingCommitId,
+    /// The `From:` header is missing or malformed
+    MissingAuthor,
+    /// The `Date:` header is missing or malformed
+    InvalidDate,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingCommitId => write!(f, "missing or malformed commit id"),
+            Self::MissingAuthor => write!(f, "missing or malformed `From:` header"),
+            Self::InvalidDate => write!(f, "missing or malformed `Date:` header"),
+        }
+    }
+}
+
+/// Git committer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitCommitter {
+    /// Name
+    pub name: String,
+    /// Email
+    pub email: String,
+    /// Commit timestamp
+    pub timestamp: Timestamp,
+    /// Timezone offset, in minutes
+    pub offset_minutes: i32,
+}
+
+impl From<GitCommitter> for Tag {
+    fn from(committer: GitCommitter) -> Self {
+        Self::Generic(
+            TagKind::Custom(String::from("committer")),
+            vec![
+                committer.name,
+                committer.email,
+                committer.timestamp.to_string(),
+                committer.offset_minutes.to_string(),
+            ],
+        )
+    }
+}
+
+/// Git patch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitPatch {
+    /// Repository announcement coordinate
+    pub repository: Coordinate,
+    /// Earliest unique commit of the repository
+    pub euc: Option<String>,
+    /// Commit id
+    pub commit: String,
+    /// Parent commit id
+    pub parent_commit: Option<String>,
+    /// Committer
+    pub committer: Option<GitCommitter>,
+    /// PGP signature of the commit
+    pub commit_pgp_sig: Option<String>,
+    /// First patch of a series
+    pub root: bool,
+    /// `git format-patch` output
+    pub content: String,
+}
+
+impl GitPatch {
+    /// Parse the output of `git format-patch` for a single commit
+    ///
+    /// The commit id is taken from the `From <commit>` line and the committer from the
+    /// `From:` and `Date:` headers.
+    ///
+    /// The `base-commit:` trailer (`git format-patch --base`) is the base of the whole series:
+    /// it's used as parent commit only for the first patch (`[PATCH]` or `[PATCH 1/n]`).
+    /// For the other patches of a series, set it with [`GitPatch::parent_commit`].
+    ///
+    /// `git format-patch` only records the author: if the committer differs, override it with [`GitPatch::committer`].
+    pub fn from_format_patch<S>(repository: Coordinate, patch: S) -> Result<Self, Error>
+    where
+        S: Into<String>,
+    {
+        let content: String = patch.into();
+
+        let commit: String = content
+            .lines()
+            .next()
+            .and_then(|line| line.strip_prefix("From "))
+            .and_then(|line| line.split_whitespace().next())
+            .filter(|id| id.len() >= 40 && id.chars().all(|c| c.is_ascii_hexdigit()))
+            .map(|id| id.to_string())
+            .ok_or(Error::MissingCommitId)?;
+
+        let mut author: Option<(String, String)> = None;
+        let mut date: Option<(Timestamp, i32)> = None;
+        let mut base_commit: Option<String> = None;
+        let mut first: bool = true;
+
+        for line in content.lines() {
+            if author.is_none() {
+                if let Some(value) = line.strip_prefix("From: ") {
+                    author = Some(parse_author(value).ok_or(Error::MissingAuthor)?);
+                    continue;
+                }
+            }
+
+            if date.is_none() {
+                if let Some(value) = line.strip_prefix("Date: ") {
+                    date = Some(parse_date(value).ok_or(Error::InvalidDate)?);
+                    continue;
+                }
+            }
+
+            if let Some(value) = line.strip_prefix("Subject: ") {
+                first = patch_number(value).map_or(true, |n| n <= 1);
+                continue;
+            }
+
+            if let Some(value) = line.strip_prefix("base-commit: ") {
+                base_commit = Some(value.trim().to_string());
+            }
+        }
+
+        let parent_commit: Option<String> = base_commit.filter(|_| first);
+
+        let (name, email) = author.ok_or(Error::MissingAuthor)?;
+        let (timestamp, offset_minutes) = date.ok_or(Error::InvalidDate)?;
+
+        Ok(Self {
+            repository,
+            euc: None,
+            commit,
+            parent_commit,
+            committer: Some(GitCommitter {
+                name,
+                email,
+                timestamp,
+                offset_minutes,
+            }),
+            commit_pgp_sig: None,
+            root: false,
+            content,
+        })
+    }
+
+    /// Set earliest unique commit of the repository
+    pub fn euc<S>(self, euc: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            euc: Some(euc.into()),
+            ..self
+        }
+    }
+
+    /// Set parent commit
+    pub fn parent_commit<S>(self, parent_commit: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            parent_commit: Some(parent_commit.into()),
+            ..self
+        }
+    }
+
+    /// Set committer
+    pub fn committer(self, committer: GitCommitter) -> Self {
+        Self {
+            committer: Some(committer),
+            ..self
+        }
+    }
+
+    /// Set commit PGP signature
+    pub fn commit_pgp_sig<S>(self, sig: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            commit_pgp_sig: Some(sig.into()),
+            ..self
+        }
+    }
+
+    /// Mark as first patch of a series
+    pub fn root(self, root: bool) -> Self {
+        Self { root, ..self }
+    }
+}
+
+impl From<GitPatch> for Vec<Tag> {
+    fn from(patch: GitPatch) -> Self {
+        let GitPatch {
+            repository,
+            euc,
+            commit,
+            parent_commit,
+            committer,
+            commit_pgp_sig,
+            root,
+            ..
+        } = patch;
+
+        let mut tags: Vec<Tag> = Vec::new();
+
+        tags.push(Tag::public_key(repository.pubkey));
+        tags.push(repository.into());
+
+        if let Some(euc) = euc {
+            tags.push(Tag::Reference(euc));
+        }
+
+        if root {
+            tags.push(Tag::Hashtag(String::from("root")));
+        }
+
+        tags.push(Tag::Generic(
+            TagKind::Custom(String::from("commit")),
+            vec![commit.clone()],
+        ));
+        tags.push(Tag::Reference(commit));
+
+        if let Some(parent_commit) = parent_commit {
+            tags.push(Tag::Generic(
+                TagKind::Custom(String::from("parent-commit")),
+                vec![parent_commit],
+            ));
+        }
+
+        if let Some(sig) = commit_pgp_sig {
+            tags.push(Tag::Generic(
+                TagKind::Custom(String::from("commit-pgp-sig")),
+                vec![sig],
+            ));
+        }
+
+        if let Some(committer) = committer {
+            tags.push(committer.into());
+        }
+
+        tags
+    }
+}
+
+/// Parse the number of the patch in the series from the subject (i.e. `[PATCH v2 2/3] Fix typo`)
+fn patch_number(subject: &str) -> Option<u32> {
+    let prefix: &str = subject.strip_prefix('[')?.split_once(']')?.0;
+    let (number, _) = prefix.split_whitespace().last()?.split_once('/')?;
+    number.parse().ok()
+}
+
+/// Parse `Name <email>`
+fn parse_author(value: &str) -> Option<(String, String)> {
+    let (name, rest) = value.rsplit_once('<')?;
+    let email = rest.strip_suffix('>')?;
+    Some((name.trim().trim_matches('"').to_string(), email.to_string()))
+}
+
+/// Parse RFC 2822 date (i.e. `Tue, 2 Jan 2024 15:04:05 +0100`)
+fn parse_date(value: &str) -> Option<(Timestamp, i32)> {
+    let mut parts = value.split_whitespace().peekable();
+
+    // Skip optional day of week
+    if parts.peek()?.ends_with(',') {
+        parts.next();
+    }
+
+    let day: u32 = parts.next()?.parse().ok()?;
+    let month: &str = parts.next()?;
+    let month: u32 = MONTHS.iter().position(|m| *m == month)? as u32 + 1;
+    let year: i64 = parts.next()?.parse().ok()?;
+
+    let mut time = parts.next()?.split(':');
+    let hour: i64 = time.next()?.parse().ok()?;
+    let min: i64 = time.next()?.parse().ok()?;
+    let sec: i64 = time.next().unwrap_or("0").parse().ok()?;
+
+    let tz: &str = parts.next()?;
+    if tz.len() != 5 || !tz.is_ascii() {
+        return None;
+    }
+    let sign: i32 = match &tz[..1] {
+        "+" => 1,
+        "-" => -1,
+        _ => return None,
+    };
+    let tz_hour: i32 = tz[1..3].parse().ok()?;
+    let tz_min: i32 = tz[3..5].parse().ok()?;
+    let offset_minutes: i32 = sign * (tz_hour * 60 + tz_min);
+
+    if !(1..=31).contains(&day) || hour > 23 || min > 59 || sec > 60 {
+        return None;
+    }
+
+    let local: i64 = days_from_civil(year, month, day) * 86_400 + hour * 3_600 + min * 60 + sec;
+    let utc: i64 = local - offset_minutes as i64 * 60;
+
+    Some((Timestamp::from(u64::try_from(utc).ok()?), offset_minutes))
+}
+
+/// Days since 1970-01-01 in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year: i64 = if month <= 2 { year - 1 } else { year };
+    let era: i64 = if year >= 0 { year } else { year - 399 } / 400;
+    let yoe: i64 = year - era * 400;
+    let month: i64 = month as i64;
+    let doy: i64 = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe: i64 = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use bitcoin::secp256k1::XOnlyPublicKey;
+
+    use super::*;
+    use crate::Kind;
+
+    const PATCH: &str = "From 1f3b0cbe7e2ed3ec8aa4b0a5e2a6a5b8c1e0d9f2 Mon Sep 17 00:00:00 2001
+From: Satoshi Nakamoto <satoshi@example.com>
+Date: Tue, 2 Jan 2024 15:04:05 +0100
+Subject: [PATCH] Fix typo
+
+---
+ README.md | 2 +-
+ 1 file changed, 1 insertion(+), 1 deletion(-)
+
+base-commit: 9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b
+--
+2.43.0
+";
+
+    fn repository() -> Coordinate {
+        let pubkey = XOnlyPublicKey::from_str(
+            "b2d670de53b27691c0c3400225b65c35a26d06093bcc41f48ffc71e0907f9d4a",
+        )
+        .unwrap();
+        Coordinate::new(Kind::GitRepoAnnouncement, pubkey).identifier("rust-nostr")
+    }
+
+    #[test]
+    fn test_parse_format_patch() {
+        let patch = GitPatch::from_format_patch(repository(), PATCH).unwrap();
+        assert_eq!(patch.commit, "1f3b0cbe7e2ed3ec8aa4b0a5e2a6a5b8c1e0d9f2");
+        assert_eq!(
+            patch.parent_commit.as_deref(),
+            Some("9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b")
+        );
+        assert_eq!(
+            patch.committer,
+            Some(GitCommitter {
+                name: String::from("Satoshi Nakamoto"),
+                email: String::from("satoshi@example.com"),
+                timestamp: Timestamp::from(1704204245),
+                offset_minutes: 60,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_format_patch_series() {
+        let patch: String = PATCH.replace("[PATCH]", "[PATCH v2 1/3]");
+        let patch = GitPatch::from_format_patch(repository(), patch).unwrap();
+        assert_eq!(
+            patch.parent_commit.as_deref(),
+            Some("9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b")
+        );
+
+        // The base commit is not the parent of the next patches
+        let patch: String = PATCH.replace("[PATCH]", "[PATCH v2 2/3]");
+        let patch = GitPatch::from_format_patch(repository(), patch).unwrap();
+        assert_eq!(patch.parent_commit, None);
+    }
+
+    #[test]
+    fn test_patch_tags() {
+        let patch = GitPatch::from_format_patch(repository(), PATCH)
+            .unwrap()
+            .root(true);
+        let tags: Vec<Tag> = patch.into();
+        let tags: Vec<Vec<String>> = tags.into_iter().map(|t| t.as_vec()).collect();
+        assert!(tags.contains(&vec![
+            String::from("commit"),
+            String::from("1f3b0cbe7e2ed3ec8aa4b0a5e2a6a5b8c1e0d9f2")
+        ]));
+        assert!(tags.contains(&vec![String::from("t"), String::from("root")]));
+        assert!(tags.contains(&vec![
+            String::from("committer"),
+            String::from("Satoshi Nakamoto"),
+            String::from("satoshi@example.com"),
+            String::from("1704204245"),
+            String::from("60"),
+        ]));
+    }
+
+    #[test]
+    fn test_invalid_format_patch() {
+        assert_eq!(
+            GitPatch::from_format_patch(repository(), "Subject: [PATCH] Fix typo"),
+            Err(Error::MissingCommitId)
+        );
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(
+            parse_date("Tue, 2 Jan 2024 15:04:05 +0100"),
+            Some((Timestamp::from(1_704_204_245), 60))
+        );
+        assert_eq!(parse_date("Tue, 2 Jan 2024 15:04:05 +01é"), None);
+        assert_eq!(parse_date("Tue, 2 Jan 2024 15:04:05 é100"), None);
+        assert_eq!(parse_date("Tue, 2 Jan 2024 15:04:05 +1"), None);
+
+        let patch: String = PATCH.replace("+0100", "+01é");
+        assert_eq!(
+            GitPatch::from_format_patch(repository(), patch),
+            Err(Error::InvalidDate)
+        );
+    }
+}
@@ -0,0 +1,187 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP38
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/38.md>
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Event, Kind, Tag, Timestamp};
+
+/// NIP38 Error
+#[derive(Debug)]
+pub enum Error {
+    /// Not a [`Kind::UserStatus`]
+    WrongKind,
+    /// Identifier (`d`) tag not found
+    IdentifierTagNotFound,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongKind => write!(f, "Wrong kind: expected user status"),
+            Self::IdentifierTagNotFound => write!(f, "Identifier tag not found"),
+        }
+    }
+}
+
+/// User status type
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StatusType {
+    /// General
+    General,
+    /// Music
+    Music,
+    /// Custom
+    Custom(String),
+}
+
+impl fmt::Display for StatusType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::General => write!(f, "general"),
+            Self::Music => write!(f, "music"),
+            Self::Custom(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+impl<S> From<S> for StatusType
+where
+    S: Into<String>,
+{
+    fn from(s: S) -> Self {
+        let s: String = s.into();
+        match s.as_str() {
+            "general" => Self::General,
+            "music" => Self::Music,
+            _ => Self::Custom(s),
+        }
+    }
+}
+
+/// User status
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserStatus {
+    /// Status type
+    pub status_type: StatusType,
+    /// Status (empty if cleared)
+    pub content: String,
+    /// Link (`r` tag)
+    pub link: Option<String>,
+    /// Expiration
+    pub expiration: Option<Timestamp>,
+    /// Timestamp of the status
+    pub created_at: Timestamp,
+}
+
+impl UserStatus {
+    /// Parse from [`Kind::UserStatus`] event
+    pub fn from_event(event: &Event) -> Result<Self, Error> {
+        if event.kind != Kind::UserStatus {
+            return Err(Error::WrongKind);
+        }
+
+        let status_type: StatusType =
+            StatusType::from(event.identifier().ok_or(Error::IdentifierTagNotFound)?);
+        let mut status = Self {
+            status_type,
+            content: event.content.clone(),
+            link: None,
+            expiration: None,
+            created_at: event.created_at,
+        };
+
+        for tag in event.tags.iter() {
+            match tag {
+                Tag::Reference(link) => status.link = Some(link.clone()),
+                Tag::Expiration(timestamp) => status.expiration = Some(*timestamp),
+                _ => (),
+            }
+        }
+
+        Ok(status)
+    }
+
+    /// Check if the status is neither cleared (empty content) nor expired at `now`
+    pub fn is_active(&self, now: Timestamp) -> bool {
+        !self.content.is_empty() && self.expiration.map_or(true, |expiration| expiration > now)
+    }
+}
+
+/// Compose the tags of a user status
+pub(crate) fn tags(
+    status_type: StatusType,
+    expiration: Option<Timestamp>,
+    link: Option<String>,
+) -> Vec<Tag> {
+    let mut tags: Vec<Tag> = vec![Tag::Identifier(status_type.to_string())];
+    if let Some(link) = link {
+        tags.push(Tag::Reference(link));
+    }
+    if let Some(expiration) = expiration {
+        tags.push(Tag::Expiration(expiration));
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, Keys};
+
+    #[test]
+    fn test_status_type() {
+        assert_eq!(StatusType::from("general"), StatusType::General);
+        assert_eq!(StatusType::from("music"), StatusType::Music);
+        assert_eq!(
+            StatusType::from("gaming"),
+            StatusType::Custom(String::from("gaming"))
+        );
+        assert_eq!(StatusType::Music.to_string(), "music");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_user_status() {
+        let keys = Keys::generate();
+        let expiration = Timestamp::from(1_700_000_000);
+        let event = EventBuilder::user_status(
+            StatusType::Music,
+            "Intergalatic - Beastie Boys",
+            Some(expiration),
+            Some(String::from(
+                "spotify:search:Intergalatic%20-%20Beastie%20Boys",
+            )),
+        )
+        .to_event(&keys)
+        .unwrap();
+        assert_eq!(event.kind, Kind::UserStatus);
+
+        let status = UserStatus::from_event(&event).unwrap();
+        assert_eq!(status.status_type, StatusType::Music);
+        assert_eq!(status.content, "Intergalatic - Beastie Boys");
+        assert_eq!(
+            status.link.as_deref(),
+            Some("spotify:search:Intergalatic%20-%20Beastie%20Boys")
+        );
+        assert_eq!(status.expiration, Some(expiration));
+        assert!(status.is_active(expiration - 1u64));
+        assert!(!status.is_active(expiration));
+
+        // Cleared
+        let event = EventBuilder::user_status(StatusType::General, "", None, None)
+            .to_event(&keys)
+            .unwrap();
+        let status = UserStatus::from_event(&event).unwrap();
+        assert!(!status.is_active(Timestamp::now()));
+    }
+}
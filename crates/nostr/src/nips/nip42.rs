@@ -0,0 +1,161 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP42
+//!
+//! Relay-side authentication helpers.
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/42.md>
+
+use alloc::string::{String, ToString};
+use core::fmt;
+
+use bitcoin::hashes::sha256::Hash as Sha256Hash;
+use bitcoin::hashes::Hash;
+#[cfg(feature = "std")]
+use bitcoin::secp256k1::rand::rngs::OsRng;
+use bitcoin::secp256k1::rand::RngCore;
+
+use crate::event;
+use crate::{Event, Kind, Tag, Timestamp, Url};
+
+/// Max difference between the `created_at` of the auth event and the current time
+const MAX_TIME_DIFF: u64 = 600;
+
+/// NIP42 error
+#[derive(Debug)]
+pub enum Error {
+    /// Not a kind `22242` event
+    WrongKind,
+    /// Missing or different `challenge` tag
+    ChallengeMismatch,
+    /// Missing or different `relay` tag
+    RelayMismatch,
+    /// `created_at` too far from the current time
+    InvalidTimestamp,
+    /// Event error
+    Event(event::Error),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongKind => write!(f, "wrong event kind"),
+            Self::ChallengeMismatch => write!(f, "missing or wrong challenge"),
+            Self::RelayMismatch => write!(f, "missing or wrong relay"),
+            Self::InvalidTimestamp => write!(f, "created_at too far from current time"),
+            Self::Event(e) => write!(f, "Event: {e}"),
+        }
+    }
+}
+
+impl From<event::Error> for Error {
+    fn from(e: event::Error) -> Self {
+        Self::Event(e)
+    }
+}
+
+/// Generate a random challenge to send with the `AUTH` message
+#[cfg(feature = "std")]
+pub fn generate_challenge() -> String {
+    let mut rng = OsRng;
+    generate_challenge_with_rng(&mut rng)
+}
+
+/// Generate a random challenge to send with the `AUTH` message
+pub fn generate_challenge_with_rng<R>(rng: &mut R) -> String
+where
+    R: RngCore,
+{
+    let mut random = [0u8; 32];
+    rng.fill_bytes(&mut random);
+    Sha256Hash::hash(&random).to_string()
+}
+
+/// Verify the authentication event sent by a client in reply to `challenge`
+///
+/// Check kind, `challenge` and `relay` tags, `created_at` (max 10 min of difference) and signature.
+#[cfg(feature = "std")]
+pub fn verify_auth_event(event: &Event, relay_url: &Url, challenge: &str) -> Result<(), Error> {
+    check_auth_event(event, relay_url, challenge, Timestamp::now())?;
+    event.verify()?;
+    Ok(())
+}
+
+fn check_auth_event(
+    event: &Event,
+    relay_url: &Url,
+    challenge: &str,
+    now: Timestamp,
+) -> Result<(), Error> {
+    if event.kind != Kind::Authentication {
+        return Err(Error::WrongKind);
+    }
+
+    let challenge_match: bool = event
+        .tags
+        .iter()
+        .any(|tag| matches!(tag, Tag::Challenge(c) if c == challenge));
+    if !challenge_match {
+        return Err(Error::ChallengeMismatch);
+    }
+
+    let relay_match: bool = event.tags.iter().any(|tag| match tag {
+        Tag::Relay(url) => Url::try_from(url.clone()).map_or(false, |url| &url == relay_url),
+        _ => false,
+    });
+    if !relay_match {
+        return Err(Error::RelayMismatch);
+    }
+
+    if event.created_at.as_i64().abs_diff(now.as_i64()) > MAX_TIME_DIFF {
+        return Err(Error::InvalidTimestamp);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use super::*;
+    use crate::{EventBuilder, Keys};
+
+    #[test]
+    fn test_verify_auth_event() {
+        let keys = Keys::generate();
+        let relay_url = Url::from_str("wss://relay.example.com").unwrap();
+        let challenge = generate_challenge();
+        let event = EventBuilder::auth(challenge.clone(), relay_url.clone())
+            .to_event(&keys)
+            .unwrap();
+
+        assert!(verify_auth_event(&event, &relay_url, &challenge).is_ok());
+        assert!(matches!(
+            verify_auth_event(&event, &relay_url, "other"),
+            Err(Error::ChallengeMismatch)
+        ));
+        assert!(matches!(
+            verify_auth_event(
+                &event,
+                &Url::from_str("wss://other.example.com").unwrap(),
+                &challenge
+            ),
+            Err(Error::RelayMismatch)
+        ));
+        assert!(matches!(
+            check_auth_event(
+                &event,
+                &relay_url,
+                &challenge,
+                event.created_at + (MAX_TIME_DIFF + 1)
+            ),
+            Err(Error::InvalidTimestamp)
+        ));
+    }
+}
@@ -22,7 +22,7 @@ use chacha20::XChaCha20;
 
 pub mod v2;
 
-use self::v2::ConversationKey;
+pub use self::v2::ConversationKey;
 use crate::util;
 
 /// Error
@@ -173,6 +173,94 @@ where
     }
 }
 
+/// Encrypt the same content to many recipients (v2) - EXPERIMENTAL
+///
+/// The content is padded only once and every ciphertext has its own random nonce.
+/// Recipients are processed in parallel (except on `wasm32`).
+/// Return the ciphertexts in the same order of `recipients`.
+#[cfg(feature = "std")]
+pub fn encrypt_to_many<T>(
+    secret_key: &SecretKey,
+    recipients: &[XOnlyPublicKey],
+    content: T,
+) -> Result<Vec<(XOnlyPublicKey, String)>, Error>
+where
+    T: AsRef<[u8]>,
+{
+    let padded: Vec<u8> = v2::pad(content)?;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let threads: usize = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        if threads > 1 && recipients.len() > 1 {
+            let chunk_size: usize = (recipients.len() + threads - 1) / threads;
+            return std::thread::scope(|scope| {
+                let handles: Vec<_> = recipients
+                    .chunks(chunk_size)
+                    .map(|chunk| {
+                        let padded: &[u8] = &padded;
+                        scope.spawn(move || {
+                            internal_encrypt_to_many(&mut OsRng, secret_key, chunk, padded)
+                        })
+                    })
+                    .collect();
+
+                let mut ciphertexts = Vec::with_capacity(recipients.len());
+                for handle in handles.into_iter() {
+                    match handle.join() {
+                        Ok(res) => ciphertexts.extend(res?),
+                        Err(e) => std::panic::resume_unwind(e),
+                    }
+                }
+                Ok(ciphertexts)
+            });
+        }
+    }
+
+    internal_encrypt_to_many(&mut OsRng, secret_key, recipients, &padded)
+}
+
+/// Encrypt the same content to many recipients (v2), using custom Rng - EXPERIMENTAL
+///
+/// Return the ciphertexts in the same order of `recipients`.
+pub fn encrypt_to_many_with_rng<R, T>(
+    rng: &mut R,
+    secret_key: &SecretKey,
+    recipients: &[XOnlyPublicKey],
+    content: T,
+) -> Result<Vec<(XOnlyPublicKey, String)>, Error>
+where
+    R: RngCore,
+    T: AsRef<[u8]>,
+{
+    let padded: Vec<u8> = v2::pad(content)?;
+    internal_encrypt_to_many(rng, secret_key, recipients, &padded)
+}
+
+fn internal_encrypt_to_many<R>(
+    rng: &mut R,
+    secret_key: &SecretKey,
+    recipients: &[XOnlyPublicKey],
+    padded: &[u8],
+) -> Result<Vec<(XOnlyPublicKey, String)>, Error>
+where
+    R: RngCore,
+{
+    recipients
+        .iter()
+        .map(|public_key| {
+            let conversation_key: ConversationKey = ConversationKey::derive(secret_key, public_key);
+            let mut nonce: [u8; 32] = [0u8; 32];
+            rng.fill_bytes(&mut nonce);
+            let ciphertext: String =
+                v2::encrypt_padded(&conversation_key, &nonce, padded.to_vec())?;
+            Ok((*public_key, ciphertext))
+        })
+        .collect()
+}
+
 /// Decrypt
 pub fn decrypt<T>(
     secret_key: &SecretKey,
@@ -255,6 +343,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_nip44_encrypt_to_many() {
+        let secp = Secp256k1::new();
+        let (sender_sk, _) = secp.generate_keypair(&mut OsRng);
+        let sender_pk =
+            XOnlyPublicKey::from_keypair(&KeyPair::from_secret_key(&secp, &sender_sk)).0;
+
+        let recipients: Vec<(SecretKey, XOnlyPublicKey)> = (0..10)
+            .map(|_| {
+                let (sk, _) = secp.generate_keypair(&mut OsRng);
+                let pk = XOnlyPublicKey::from_keypair(&KeyPair::from_secret_key(&secp, &sk)).0;
+                (sk, pk)
+            })
+            .collect();
+        let public_keys: Vec<XOnlyPublicKey> = recipients.iter().map(|(_, pk)| *pk).collect();
+
+        let ciphertexts = encrypt_to_many(&sender_sk, &public_keys, "hello").unwrap();
+        assert_eq!(ciphertexts.len(), recipients.len());
+        for ((sk, pk), (recipient, ciphertext)) in recipients.iter().zip(ciphertexts.iter()) {
+            assert_eq!(pk, recipient);
+            assert_eq!(decrypt(sk, &sender_pk, ciphertext).unwrap(), "hello");
+        }
+
+        assert!(encrypt_to_many(&sender_sk, &public_keys, "").is_err());
+    }
+
     #[test]
     fn test_nip44_decryption() {
         let secret_key =
@@ -195,11 +195,20 @@ where
         }
     };
 
-    // Get Message Keys
-    let keys: MessageKeys = get_message_keys(conversation_key, &nonce)?;
-
     // Pad
-    let mut buffer: Vec<u8> = pad(plaintext)?;
+    let padded: Vec<u8> = pad(plaintext)?;
+
+    encrypt_padded(conversation_key, &nonce, padded)
+}
+
+/// Encrypt an already padded plaintext
+pub(super) fn encrypt_padded(
+    conversation_key: &ConversationKey,
+    nonce: &[u8; 32],
+    mut buffer: Vec<u8>,
+) -> Result<String, Error> {
+    // Get Message Keys
+    let keys: MessageKeys = get_message_keys(conversation_key, nonce)?;
 
     // Compose cipher and encrypt
     let mut cipher = ChaCha20::new(keys.encryption().into(), keys.nonce().into());
@@ -207,13 +216,13 @@ where
 
     // HMAC-SHA256
     let mut engine: HmacEngine<Sha256Hash> = HmacEngine::new(keys.auth());
-    engine.input(&nonce);
+    engine.input(nonce);
     engine.input(&buffer);
     let hmac: [u8; 32] = Hmac::from_engine(engine).to_byte_array();
 
     // Compose payload
     let mut payload: Vec<u8> = vec![2]; // Version
-    payload.extend_from_slice(&nonce);
+    payload.extend_from_slice(nonce);
     payload.extend_from_slice(&buffer);
     payload.extend_from_slice(&hmac);
 
@@ -291,7 +300,7 @@ fn get_message_keys(
     MessageKeys::from_slice(&expanded_key).map_err(|_| ErrorV2::HkdfLength(expanded_key.len()))
 }
 
-fn pad<T>(unpadded: T) -> Result<Vec<u8>, ErrorV2>
+pub(super) fn pad<T>(unpadded: T) -> Result<Vec<u8>, ErrorV2>
 where
     T: AsRef<[u8]>,
 {
@@ -136,6 +136,13 @@ pub enum Request {
     SignEvent(UnsignedEvent),
     /// Connect
     Connect(XOnlyPublicKey),
+    /// Connect with the secret shared through a [`BunkerURI`]
+    ConnectWithSecret {
+        /// Pubkey
+        public_key: XOnlyPublicKey,
+        /// Secret
+        secret: String,
+    },
     /// Disconnect
     Disconnect,
     /// Delegate
@@ -164,13 +171,21 @@ pub enum Request {
 }
 
 impl Request {
+    /// New `connect` request, with the optional secret of a [`BunkerURI`]
+    pub fn connect(public_key: XOnlyPublicKey, secret: Option<String>) -> Self {
+        match secret {
+            Some(secret) => Self::ConnectWithSecret { public_key, secret },
+            None => Self::Connect(public_key),
+        }
+    }
+
     /// Get req method
     pub fn method(&self) -> String {
         match self {
             Self::Describe => "describe".to_string(),
             Self::GetPublicKey => "get_public_key".to_string(),
             Self::SignEvent(_) => "sign_event".to_string(),
-            Self::Connect(_) => "connect".to_string(),
+            Self::Connect(_) | Self::ConnectWithSecret { .. } => "connect".to_string(),
             Self::Disconnect => "disconnect".to_string(),
             Self::Delegate { .. } => "delegate".to_string(),
             Self::Nip04Encrypt { .. } => "nip04_encrypt".to_string(),
@@ -186,6 +201,9 @@ impl Request {
             Self::GetPublicKey => Vec::new(),
             Self::SignEvent(event) => vec![json!(event)],
             Self::Connect(pubkey) => vec![json!(pubkey)],
+            Self::ConnectWithSecret { public_key, secret } => {
+                vec![json!(public_key), json!(secret)]
+            }
             Self::Disconnect => Vec::new(),
             Self::Delegate {
                 public_key,
@@ -231,7 +249,7 @@ impl Request {
                 let signed_event = unsigned_event.sign_with_ctx(secp, rng, keys)?;
                 Some(Response::SignEvent(signed_event))
             }
-            Self::Connect(_) => None,
+            Self::Connect(_) | Self::ConnectWithSecret { .. } => None,
             Self::Disconnect => None,
             Self::Delegate {
                 public_key,
@@ -290,6 +308,8 @@ pub enum Response {
     GetPublicKey(XOnlyPublicKey),
     /// Sign event
     SignEvent(Event),
+    /// Connect acknowledgement (`ack`)
+    Connect,
     /// Delegation
     Delegate(DelegationResult),
     /// Encrypted content (NIP04)
@@ -354,6 +374,7 @@ impl Message {
                 Response::Describe(v) => json!(v),
                 Response::GetPublicKey(pubkey) => json!(pubkey),
                 Response::SignEvent(sig) => json!(sig),
+                Response::Connect => json!(CONNECT_ACK),
                 Response::Delegate(delegation_result) => json!(delegation_result),
                 Response::Nip04Encrypt(encrypted_content) => json!(encrypted_content),
                 Response::Nip04Decrypt(decrypted_content) => json!(decrypted_content),
@@ -395,12 +416,16 @@ impl Message {
                     }
                 }
                 "connect" => {
-                    if params.len() != 1 {
+                    if params.is_empty() || params.len() > 2 {
                         return Err(Error::InvalidParamsLength);
                     }
 
-                    let pubkey: XOnlyPublicKey = serde_json::from_value(params[0].to_owned())?;
-                    Ok(Request::Connect(pubkey))
+                    let public_key: XOnlyPublicKey = serde_json::from_value(params[0].to_owned())?;
+                    let secret: Option<String> = match params.get(1) {
+                        Some(secret) => serde_json::from_value(secret.to_owned())?,
+                        None => None,
+                    };
+                    Ok(Request::connect(public_key, secret))
                 }
                 "disconnect" => Ok(Request::Disconnect),
                 "delegate" => {
@@ -498,6 +523,10 @@ where
 
 /// NIP46 URI Scheme
 pub const NOSTR_CONNECT_URI_SCHEME: &str = "nostrconnect";
+/// NIP46 bunker URI Scheme
+pub const BUNKER_URI_SCHEME: &str = "bunker";
+/// Result of an accepted `connect` request
+pub const CONNECT_ACK: &str = "ack";
 
 /// Nostr Connect Metadata
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -675,6 +704,96 @@ impl fmt::Display for NostrConnectURI {
     }
 }
 
+/// Bunker URI
+///
+/// Shared by the remote signer: `bunker://<signer-pubkey>?relay=<wss://relay>&secret=<optional-secret>`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BunkerURI {
+    /// Remote signer public key
+    pub signer_public_key: XOnlyPublicKey,
+    /// Relays where the signer is listening for requests
+    pub relays: Vec<Url>,
+    /// Optional secret, to send in the `connect` request
+    pub secret: Option<String>,
+}
+
+impl BunkerURI {
+    /// Create new [`BunkerURI`]
+    pub fn new<I>(signer_public_key: XOnlyPublicKey, relays: I) -> Self
+    where
+        I: IntoIterator<Item = Url>,
+    {
+        Self {
+            signer_public_key,
+            relays: relays.into_iter().collect(),
+            secret: None,
+        }
+    }
+
+    /// Set secret
+    pub fn secret<S>(self, secret: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            secret: Some(secret.into()),
+            ..self
+        }
+    }
+}
+
+impl FromStr for BunkerURI {
+    type Err = Error;
+    fn from_str(uri: &str) -> Result<Self, Self::Err> {
+        let url = Url::parse(uri)?;
+
+        if url.scheme() != BUNKER_URI_SCHEME {
+            return Err(Error::InvalidURIScheme);
+        }
+
+        let signer_public_key = match url.domain() {
+            Some(pubkey) => XOnlyPublicKey::from_str(pubkey)?,
+            None => return Err(Error::InvalidURI),
+        };
+
+        let mut relays: Vec<Url> = Vec::new();
+        let mut secret: Option<String> = None;
+
+        for (key, value) in url.query_pairs() {
+            match key {
+                Cow::Borrowed("relay") => relays.push(Url::parse(&value)?),
+                Cow::Borrowed("secret") => secret = Some(value.to_string()),
+                _ => (),
+            }
+        }
+
+        if relays.is_empty() {
+            return Err(Error::InvalidURI);
+        }
+
+        Ok(Self {
+            signer_public_key,
+            relays,
+            secret,
+        })
+    }
+}
+
+impl fmt::Display for BunkerURI {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{BUNKER_URI_SCHEME}://{}?", self.signer_public_key)?;
+        let mut params: Vec<String> = self
+            .relays
+            .iter()
+            .map(|url| format!("relay={}", url_encode(url.to_string())))
+            .collect();
+        if let Some(secret) = &self.secret {
+            params.push(format!("secret={}", url_encode(secret)));
+        }
+        write!(f, "{}", params.join("&"))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use core::str::FromStr;
@@ -709,4 +828,48 @@ mod test {
         let app_name = "Example";
         assert_eq!(uri, NostrConnectURI::new(pubkey, relay_url, app_name));
     }
+
+    #[test]
+    fn test_bunker_uri() {
+        let pubkey = XOnlyPublicKey::from_str(
+            "b889ff5b1513b641e2a139f661a661364979c5beee91842f8f0ef42ab558e9d4",
+        )
+        .unwrap();
+        let relays = vec![
+            Url::parse("wss://relay.damus.io").unwrap(),
+            Url::parse("wss://nos.lol").unwrap(),
+        ];
+        let uri = BunkerURI::new(pubkey, relays).secret("abcd");
+        let s = "bunker://b889ff5b1513b641e2a139f661a661364979c5beee91842f8f0ef42ab558e9d4?relay=wss%3A%2F%2Frelay.damus.io%2F&relay=wss%3A%2F%2Fnos.lol%2F&secret=abcd";
+        assert_eq!(uri.to_string(), s);
+        assert_eq!(BunkerURI::from_str(s).unwrap(), uri);
+
+        assert!(BunkerURI::from_str(
+            "bunker://b889ff5b1513b641e2a139f661a661364979c5beee91842f8f0ef42ab558e9d4"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_connect_request() {
+        let public_key = XOnlyPublicKey::from_str(
+            "b889ff5b1513b641e2a139f661a661364979c5beee91842f8f0ef42ab558e9d4",
+        )
+        .unwrap();
+        let req = Request::connect(public_key, Some(String::from("abcd")));
+        let msg = Message::request(req.clone());
+        assert_eq!(msg.to_request().unwrap(), req);
+
+        let req = Request::connect(public_key, None);
+        assert_eq!(req, Request::Connect(public_key));
+        let msg = Message::request(req.clone());
+        assert_eq!(msg.to_request().unwrap(), req);
+
+        let res = Message::response(msg.id(), Some(Response::Connect), None);
+        if let Message::Response { result, .. } = res {
+            assert_eq!(result, Some(json!(CONNECT_ACK)));
+        } else {
+            panic!("Expected response");
+        }
+    }
 }
@@ -18,7 +18,7 @@ use serde_json::Value;
 use url_fork::form_urlencoded::byte_serialize;
 use url_fork::{ParseError, Url};
 
-use crate::JsonUtil;
+use crate::{Event, JsonUtil, Kind};
 
 use super::nip04;
 
@@ -85,6 +85,12 @@ impl From<secp256k1::Error> for Error {
     }
 }
 
+impl From<nip04::Error> for Error {
+    fn from(e: nip04::Error) -> Self {
+        Self::NIP04(e)
+    }
+}
+
 /// NIP47 Response Error codes
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ErrorCode {
@@ -124,6 +130,7 @@ impl fmt::Display for Method {
             Method::ListInvoices => write!(f, "list_invoices"),
             Method::ListPayments => write!(f, "list_payments"),
             Method::GetBalance => write!(f, "get_balance"),
+            Method::ListTransactions => write!(f, "list_transactions"),
         }
     }
 }
@@ -139,6 +146,7 @@ impl FromStr for Method {
             "list_invoices" => Ok(Method::ListInvoices),
             "list_payments" => Ok(Method::ListPayments),
             "get_balance" => Ok(Method::GetBalance),
+            "list_transactions" => Ok(Method::ListTransactions),
             _ => Err(Error::InvalidURI),
         }
     }
@@ -177,6 +185,9 @@ pub enum Method {
     /// Get Balance
     #[serde(rename = "get_balance")]
     GetBalance,
+    /// List Transactions
+    #[serde(rename = "list_transactions")]
+    ListTransactions,
 }
 
 /// Nostr Wallet Connect Request Params
@@ -196,6 +207,8 @@ pub enum RequestParams {
     ListPayments(ListPaymentsRequestParams),
     /// Get Balance
     GetBalance,
+    /// List Transactions
+    ListTransactions(ListTransactionsRequestParams),
 }
 
 impl Serialize for RequestParams {
@@ -211,6 +224,7 @@ impl Serialize for RequestParams {
             RequestParams::ListInvoices(p) => p.serialize(serializer),
             RequestParams::ListPayments(p) => p.serialize(serializer),
             RequestParams::GetBalance => serializer.serialize_none(),
+            RequestParams::ListTransactions(p) => p.serialize(serializer),
         }
     }
 }
@@ -312,6 +326,40 @@ pub struct ListPaymentsRequestParams {
     pub offset: Option<u64>,
 }
 
+/// Transaction type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransactionType {
+    /// Received payment
+    Incoming,
+    /// Sent payment
+    Outgoing,
+}
+
+/// List Transactions Request Params
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ListTransactionsRequestParams {
+    /// Starting timestamp in seconds since epoch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<u64>,
+    /// Ending timestamp in seconds since epoch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<u64>,
+    /// Number of transactions to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+    /// Offset of the first transaction to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    /// If true, include unpaid invoices
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unpaid: Option<bool>,
+    /// Only incoming or outgoing transactions
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_type: Option<TransactionType>,
+}
+
 /// NIP47 Request
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 pub struct Request {
@@ -360,6 +408,11 @@ impl Request {
                 RequestParams::ListPayments(params)
             }
             Method::GetBalance => RequestParams::GetBalance,
+            Method::ListTransactions => {
+                let params: ListTransactionsRequestParams =
+                    serde_json::from_value(template.params)?;
+                RequestParams::ListTransactions(params)
+            }
         };
 
         Ok(Self {
@@ -367,6 +420,17 @@ impl Request {
             params,
         })
     }
+
+    /// Decrypt and deserialize the request of a [`Kind::WalletConnectRequest`] event
+    ///
+    /// For wallet services: `service_secret_key` is the secret key of the service.
+    pub fn from_event(event: &Event, service_secret_key: &SecretKey) -> Result<Self, Error> {
+        if event.kind != Kind::WalletConnectRequest {
+            return Err(Error::InvalidRequest);
+        }
+        let msg: String = nip04::decrypt(service_secret_key, &event.pubkey, &event.content)?;
+        Self::from_json(msg)
+    }
 }
 
 impl JsonUtil for Request {
@@ -453,6 +517,48 @@ pub struct GetBalanceResponseResult {
     pub budget_renewal: Option<BudgetType>,
 }
 
+/// NIP47 Response Result
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TransactionResponseResult {
+    /// Incoming or outgoing
+    #[serde(rename = "type")]
+    pub transaction_type: TransactionType,
+    /// Bolt11 invoice
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invoice: Option<String>,
+    /// Invoice description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Invoice description hash
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description_hash: Option<String>,
+    /// Payment preimage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preimage: Option<String>,
+    /// Payment hash
+    pub payment_hash: String,
+    /// Amount in millisatoshis
+    pub amount: u64,
+    /// Fees paid in millisatoshis
+    #[serde(default)]
+    pub fees_paid: u64,
+    /// Creation timestamp in seconds since epoch
+    pub created_at: u64,
+    /// Expiration timestamp in seconds since epoch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    /// Settlement timestamp in seconds since epoch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settled_at: Option<u64>,
+}
+
+/// NIP47 Response Result
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ListTransactionsResponseResult {
+    /// Transactions
+    pub transactions: Vec<TransactionResponseResult>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 /// NIP47 Response Result
 pub enum ResponseResult {
@@ -470,6 +576,24 @@ pub enum ResponseResult {
     ListPayments(Vec<ListPaymentResponseResult>),
     /// Get Balance
     GetBalance(GetBalanceResponseResult),
+    /// List Transactions
+    ListTransactions(ListTransactionsResponseResult),
+}
+
+impl ResponseResult {
+    /// Get the [`Method`] of the request this result is for
+    pub fn method(&self) -> Method {
+        match self {
+            Self::PayInvoice(..) => Method::PayInvoice,
+            Self::PayKeysend(..) => Method::PayKeysend,
+            Self::MakeInvoice(..) => Method::MakeInvoice,
+            Self::LookupInvoice(..) => Method::LookupInvoice,
+            Self::ListInvoices(..) => Method::ListInvoices,
+            Self::ListPayments(..) => Method::ListPayments,
+            Self::GetBalance(..) => Method::GetBalance,
+            Self::ListTransactions(..) => Method::ListTransactions,
+        }
+    }
 }
 
 impl Serialize for ResponseResult {
@@ -485,6 +609,7 @@ impl Serialize for ResponseResult {
             ResponseResult::ListInvoices(p) => p.serialize(serializer),
             ResponseResult::ListPayments(p) => p.serialize(serializer),
             ResponseResult::GetBalance(p) => p.serialize(serializer),
+            ResponseResult::ListTransactions(p) => p.serialize(serializer),
         }
     }
 }
@@ -512,6 +637,30 @@ struct ResponseTemplate {
 }
 
 impl Response {
+    /// New successful response
+    pub fn result(result: ResponseResult) -> Self {
+        Self {
+            result_type: result.method(),
+            error: None,
+            result: Some(result),
+        }
+    }
+
+    /// New error response to a `method` request
+    pub fn error<S>(method: Method, code: ErrorCode, message: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            result_type: method,
+            error: Some(NIP47Error {
+                code,
+                message: message.into(),
+            }),
+            result: None,
+        }
+    }
+
     /// Deserialize from JSON string
     pub fn from_value(value: Value) -> Result<Self, Error> {
         let template: ResponseTemplate = serde_json::from_value(value)?;
@@ -546,6 +695,10 @@ impl Response {
                     let result: GetBalanceResponseResult = serde_json::from_value(result)?;
                     ResponseResult::GetBalance(result)
                 }
+                Method::ListTransactions => {
+                    let result: ListTransactionsResponseResult = serde_json::from_value(result)?;
+                    ResponseResult::ListTransactions(result)
+                }
             };
 
             Ok(Self {
@@ -586,6 +739,8 @@ where
 
 /// NIP47 URI Scheme
 pub const NOSTR_WALLET_CONNECT_URI_SCHEME: &str = "nostr+walletconnect";
+/// Legacy NIP47 URI Scheme, still used by some wallets
+pub const NOSTR_WALLET_CONNECT_LEGACY_URI_SCHEME: &str = "nostrwalletconnect";
 
 /// Nostr Connect URI
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -622,7 +777,10 @@ impl FromStr for NostrWalletConnectURI {
     fn from_str(uri: &str) -> Result<Self, Self::Err> {
         let url = Url::parse(uri)?;
 
-        if url.scheme() != NOSTR_WALLET_CONNECT_URI_SCHEME {
+        if !matches!(
+            url.scheme(),
+            NOSTR_WALLET_CONNECT_URI_SCHEME | NOSTR_WALLET_CONNECT_LEGACY_URI_SCHEME
+        ) {
             return Err(Error::InvalidURIScheme);
         }
 
@@ -755,6 +913,17 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_parse_legacy_uri() {
+        let uri = "nostrwalletconnect://b889ff5b1513b641e2a139f661a661364979c5beee91842f8f0ef42ab558e9d4?relay=wss%3A%2F%2Frelay.damus.io%2F&secret=71a8c14c1407c113601079c4302dab36460f0ccd0ad506f1f2dc73b5100e4f3c";
+        let uri = NostrWalletConnectURI::from_str(uri).unwrap();
+        assert_eq!(uri.relay_url, Url::parse("wss://relay.damus.io").unwrap());
+        assert_eq!(uri.lud16, None);
+
+        // Always serialized with the current scheme
+        assert!(uri.to_string().starts_with("nostr+walletconnect://"));
+    }
+
     #[test]
     fn seralize_request() {
         let request = Request {
@@ -781,4 +950,22 @@ mod test {
             panic!("Invalid request params");
         }
     }
+
+    #[test]
+    fn test_parse_list_transactions_response() {
+        let response = r#"{"result_type":"list_transactions","result":{"transactions":[{"type":"incoming","invoice":"lnbc1","payment_hash":"abcd","amount":21000,"fees_paid":0,"created_at":1700000000,"settled_at":1700000010}]}}"#;
+        let response = Response::from_json(response).unwrap();
+
+        assert_eq!(response.result_type, Method::ListTransactions);
+        match response.result {
+            Some(ResponseResult::ListTransactions(result)) => {
+                assert_eq!(result.transactions.len(), 1);
+                let tx = &result.transactions[0];
+                assert_eq!(tx.transaction_type, TransactionType::Incoming);
+                assert_eq!(tx.amount, 21000);
+                assert_eq!(tx.settled_at, Some(1700000010));
+            }
+            _ => panic!("Invalid response result"),
+        }
+    }
 }
@@ -0,0 +1,505 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP49
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/49.md>
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use bitcoin::bech32::{self, FromBase32, ToBase32, Variant};
+#[cfg(feature = "std")]
+use bitcoin::secp256k1::rand::rngs::OsRng;
+use bitcoin::secp256k1::rand::RngCore;
+use bitcoin::secp256k1::{self, SecretKey};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::XChaCha20Poly1305;
+use scrypt::errors::{InvalidOutputLen, InvalidParams};
+use scrypt::Params as ScryptParams;
+use unicode_normalization::UnicodeNormalization;
+
+use super::nip19::{FromBech32, ToBech32};
+
+/// `ncryptsec` bech32 prefix
+pub const PREFIX_BECH32_ENCRYPTED_SECRET_KEY: &str = "ncryptsec";
+
+const SALT_SIZE: usize = 16;
+const NONCE_SIZE: usize = 24;
+const CIPHERTEXT_SIZE: usize = 48;
+const KEY_SIZE: usize = 32;
+const ENCRYPTED_SECRET_KEY_SIZE: usize = 1 + 1 + SALT_SIZE + NONCE_SIZE + 1 + CIPHERTEXT_SIZE;
+
+/// Max scrypt `log_n` accepted by [`EncryptedSecretKey::to_secret_key`]
+///
+/// With `log_n = 22` scrypt needs 4 GiB of memory.
+/// The `log_n` is read from the `ncryptsec`: without a limit, an untrusted key could exhaust the memory.
+pub const DEFAULT_MAX_LOG_N: u8 = 22;
+
+/// NIP49 error
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    /// ChaCha20Poly1305 error (i.e. wrong password)
+    ChaCha20Poly1305(chacha20poly1305::Error),
+    /// Invalid scrypt params
+    InvalidScryptParams(InvalidParams),
+    /// Invalid scrypt output len
+    InvalidScryptOutputLen(InvalidOutputLen),
+    /// Bech32 error
+    Bech32(bech32::Error),
+    /// Secp256k1 error
+    Secp256k1(secp256k1::Error),
+    /// Wrong bech32 prefix or variant
+    WrongPrefixOrVariant,
+    /// Invalid encrypted secret key length
+    InvalidLength {
+        /// Expected length
+        expected: usize,
+        /// Found length
+        found: usize,
+    },
+    /// Unknown version
+    UnknownVersion(u8),
+    /// Unknown key security
+    UnknownKeySecurity(u8),
+    /// The scrypt `log_n` exceeds the max allowed
+    LogNTooHigh {
+        /// Max allowed `log_n`
+        max: u8,
+        /// Found `log_n`
+        found: u8,
+    },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ChaCha20Poly1305(e) => write!(f, "ChaCha20Poly1305: {e}"),
+            Self::InvalidScryptParams(e) => write!(f, "Invalid scrypt params: {e}"),
+            Self::InvalidScryptOutputLen(e) => write!(f, "Invalid scrypt output len: {e}"),
+            Self::Bech32(e) => write!(f, "Bech32: {e}"),
+            Self::Secp256k1(e) => write!(f, "Secp256k1: {e}"),
+            Self::WrongPrefixOrVariant => write!(f, "Wrong prefix or variant"),
+            Self::InvalidLength { expected, found } => {
+                write!(f, "Invalid length: expected={expected}, found={found}")
+            }
+            Self::UnknownVersion(v) => write!(f, "Unknown version: {v}"),
+            Self::UnknownKeySecurity(s) => write!(f, "Unknown key security: {s}"),
+            Self::LogNTooHigh { max, found } => {
+                write!(f, "Scrypt log_n too high: max={max}, found={found}")
+            }
+        }
+    }
+}
+
+impl From<chacha20poly1305::Error> for Error {
+    fn from(e: chacha20poly1305::Error) -> Self {
+        Self::ChaCha20Poly1305(e)
+    }
+}
+
+impl From<InvalidParams> for Error {
+    fn from(e: InvalidParams) -> Self {
+        Self::InvalidScryptParams(e)
+    }
+}
+
+impl From<InvalidOutputLen> for Error {
+    fn from(e: InvalidOutputLen) -> Self {
+        Self::InvalidScryptOutputLen(e)
+    }
+}
+
+impl From<bech32::Error> for Error {
+    fn from(e: bech32::Error) -> Self {
+        Self::Bech32(e)
+    }
+}
+
+impl From<secp256k1::Error> for Error {
+    fn from(e: secp256k1::Error) -> Self {
+        Self::Secp256k1(e)
+    }
+}
+
+/// Encrypted secret key version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Version {
+    /// V2
+    V2 = 0x02,
+}
+
+impl Version {
+    /// Get [`Version`] as `u8`
+    pub fn as_u8(&self) -> u8 {
+        *self as u8
+    }
+}
+
+impl TryFrom<u8> for Version {
+    type Error = Error;
+
+    fn try_from(version: u8) -> Result<Self, Self::Error> {
+        match version {
+            0x02 => Ok(Self::V2),
+            v => Err(Error::UnknownVersion(v)),
+        }
+    }
+}
+
+/// How the secret key has been handled before being encrypted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeySecurity {
+    /// The key has been known to have been handled insecurely (stored unencrypted, cut and paste unencrypted, etc)
+    Weak = 0x00,
+    /// The key has NOT been known to have been handled insecurely (stored unencrypted, cut and paste unencrypted, etc)
+    Medium = 0x01,
+    /// The client does not track this data
+    Unknown = 0x02,
+}
+
+impl KeySecurity {
+    /// Get [`KeySecurity`] as `u8`
+    pub fn as_u8(&self) -> u8 {
+        *self as u8
+    }
+}
+
+impl TryFrom<u8> for KeySecurity {
+    type Error = Error;
+
+    fn try_from(key_security: u8) -> Result<Self, Self::Error> {
+        match key_security {
+            0x00 => Ok(Self::Weak),
+            0x01 => Ok(Self::Medium),
+            0x02 => Ok(Self::Unknown),
+            s => Err(Error::UnknownKeySecurity(s)),
+        }
+    }
+}
+
+/// Secret key encrypted with a password (`ncryptsec`)
+///
+/// The password is normalized to NFKC and stretched with scrypt (`log_n`, `r = 8`, `p = 1`):
+/// the derived key encrypts the secret key with XChaCha20-Poly1305.
+/// Each increment of `log_n` doubles the time (and memory) needed to decrypt the key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedSecretKey {
+    version: Version,
+    log_n: u8,
+    salt: [u8; SALT_SIZE],
+    nonce: [u8; NONCE_SIZE],
+    key_security: KeySecurity,
+    encrypted_secret_key: [u8; CIPHERTEXT_SIZE],
+}
+
+impl EncryptedSecretKey {
+    /// Encrypt the `secret_key` with the `password`
+    #[cfg(feature = "std")]
+    pub fn new<S>(
+        secret_key: &SecretKey,
+        password: S,
+        log_n: u8,
+        key_security: KeySecurity,
+    ) -> Result<Self, Error>
+    where
+        S: AsRef<str>,
+    {
+        Self::new_with_rng(&mut OsRng, secret_key, password, log_n, key_security)
+    }
+
+    /// Encrypt the `secret_key` with the `password`, using a custom random number generator
+    pub fn new_with_rng<R, S>(
+        rng: &mut R,
+        secret_key: &SecretKey,
+        password: S,
+        log_n: u8,
+        key_security: KeySecurity,
+    ) -> Result<Self, Error>
+    where
+        R: RngCore,
+        S: AsRef<str>,
+    {
+        let mut salt: [u8; SALT_SIZE] = [0u8; SALT_SIZE];
+        rng.fill_bytes(&mut salt);
+
+        let mut nonce: [u8; NONCE_SIZE] = [0u8; NONCE_SIZE];
+        rng.fill_bytes(&mut nonce);
+
+        let key: [u8; KEY_SIZE] = derive_key(password, &salt, log_n)?;
+        let cipher = XChaCha20Poly1305::new(&key.into());
+        let payload = Payload {
+            msg: &secret_key.secret_bytes(),
+            aad: &[key_security.as_u8()],
+        };
+        let ciphertext: Vec<u8> = cipher.encrypt(&nonce.into(), payload)?;
+
+        let mut encrypted_secret_key: [u8; CIPHERTEXT_SIZE] = [0u8; CIPHERTEXT_SIZE];
+        encrypted_secret_key.copy_from_slice(&ciphertext);
+
+        Ok(Self {
+            version: Version::V2,
+            log_n,
+            salt,
+            nonce,
+            key_security,
+            encrypted_secret_key,
+        })
+    }
+
+    /// Parse the raw bytes of an encrypted secret key
+    pub fn from_slice(slice: &[u8]) -> Result<Self, Error> {
+        if slice.len() != ENCRYPTED_SECRET_KEY_SIZE {
+            return Err(Error::InvalidLength {
+                expected: ENCRYPTED_SECRET_KEY_SIZE,
+                found: slice.len(),
+            });
+        }
+
+        let version: Version = Version::try_from(slice[0])?;
+        let log_n: u8 = slice[1];
+
+        let mut salt: [u8; SALT_SIZE] = [0u8; SALT_SIZE];
+        salt.copy_from_slice(&slice[2..2 + SALT_SIZE]);
+
+        let mut nonce: [u8; NONCE_SIZE] = [0u8; NONCE_SIZE];
+        nonce.copy_from_slice(&slice[2 + SALT_SIZE..2 + SALT_SIZE + NONCE_SIZE]);
+
+        let key_security: KeySecurity = KeySecurity::try_from(slice[2 + SALT_SIZE + NONCE_SIZE])?;
+
+        let mut encrypted_secret_key: [u8; CIPHERTEXT_SIZE] = [0u8; CIPHERTEXT_SIZE];
+        encrypted_secret_key.copy_from_slice(&slice[3 + SALT_SIZE + NONCE_SIZE..]);
+
+        Ok(Self {
+            version,
+            log_n,
+            salt,
+            nonce,
+            key_security,
+            encrypted_secret_key,
+        })
+    }
+
+    /// Get the raw bytes of the encrypted secret key
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(ENCRYPTED_SECRET_KEY_SIZE);
+        bytes.push(self.version.as_u8());
+        bytes.push(self.log_n);
+        bytes.extend_from_slice(&self.salt);
+        bytes.extend_from_slice(&self.nonce);
+        bytes.push(self.key_security.as_u8());
+        bytes.extend_from_slice(&self.encrypted_secret_key);
+        bytes
+    }
+
+    /// Get encrypted secret key version
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Get the scrypt `log_n` parameter
+    pub fn log_n(&self) -> u8 {
+        self.log_n
+    }
+
+    /// Get key security
+    pub fn key_security(&self) -> KeySecurity {
+        self.key_security
+    }
+
+    /// Decrypt the secret key with the `password`
+    ///
+    /// Return [`Error::LogNTooHigh`] if the `log_n` exceeds [`DEFAULT_MAX_LOG_N`]:
+    /// use [`EncryptedSecretKey::to_secret_key_with_max_log_n`] to change the limit.
+    pub fn to_secret_key<S>(&self, password: S) -> Result<SecretKey, Error>
+    where
+        S: AsRef<str>,
+    {
+        self.to_secret_key_with_max_log_n(password, DEFAULT_MAX_LOG_N)
+    }
+
+    /// Decrypt the secret key with the `password`, only if the `log_n` doesn't exceed `max_log_n`
+    pub fn to_secret_key_with_max_log_n<S>(
+        &self,
+        password: S,
+        max_log_n: u8,
+    ) -> Result<SecretKey, Error>
+    where
+        S: AsRef<str>,
+    {
+        if self.log_n > max_log_n {
+            return Err(Error::LogNTooHigh {
+                max: max_log_n,
+                found: self.log_n,
+            });
+        }
+
+        let key: [u8; KEY_SIZE] = derive_key(password, &self.salt, self.log_n)?;
+        let cipher = XChaCha20Poly1305::new(&key.into());
+        let payload = Payload {
+            msg: &self.encrypted_secret_key,
+            aad: &[self.key_security.as_u8()],
+        };
+        let secret_key: Vec<u8> = cipher.decrypt(&self.nonce.into(), payload)?;
+        Ok(SecretKey::from_slice(&secret_key)?)
+    }
+}
+
+impl FromBech32 for EncryptedSecretKey {
+    type Err = Error;
+
+    fn from_bech32<S>(encrypted_secret_key: S) -> Result<Self, Self::Err>
+    where
+        S: AsRef<str>,
+    {
+        let (hrp, data, checksum) = bech32::decode(encrypted_secret_key.as_ref())?;
+
+        if hrp != PREFIX_BECH32_ENCRYPTED_SECRET_KEY || checksum != Variant::Bech32 {
+            return Err(Error::WrongPrefixOrVariant);
+        }
+
+        let data: Vec<u8> = Vec::<u8>::from_base32(&data)?;
+        Self::from_slice(&data)
+    }
+}
+
+impl ToBech32 for EncryptedSecretKey {
+    type Err = Error;
+
+    fn to_bech32(&self) -> Result<String, Self::Err> {
+        let data = self.to_vec().to_base32();
+        Ok(bech32::encode(
+            PREFIX_BECH32_ENCRYPTED_SECRET_KEY,
+            data,
+            Variant::Bech32,
+        )?)
+    }
+}
+
+/// Derive the symmetric key from the NFKC normalized `password`
+fn derive_key<S>(password: S, salt: &[u8; SALT_SIZE], log_n: u8) -> Result<[u8; KEY_SIZE], Error>
+where
+    S: AsRef<str>,
+{
+    let password: String = password.as_ref().nfkc().collect();
+    let params: ScryptParams = ScryptParams::new(log_n, 8, 1, KEY_SIZE)?;
+    let mut key: [u8; KEY_SIZE] = [0u8; KEY_SIZE];
+    scrypt::scrypt(password.as_bytes(), salt, &params, &mut key)?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use super::*;
+
+    const SECRET_KEY: &str = "3501454135014541350145413501453fefb02227e449e57cf4d3a3ce05378683";
+
+    #[test]
+    fn test_decrypt_vector() {
+        let encrypted = EncryptedSecretKey::from_bech32("ncryptsec1qgg9947rlpvqu76pj5ecreduf9jxhselq2nae2kghhvd5g7dgjtcxfqtd67p9m0w57lspw8gsq6yphnm8623nsl8xn9j4jdzz84zm3frztj3z7s35vpzmqf6ksu8r89qk5z2zxfmu5gv8th8wclt0h4p").unwrap();
+        assert_eq!(encrypted.log_n(), 16);
+        assert_eq!(encrypted.key_security(), KeySecurity::Weak);
+
+        let secret_key = encrypted.to_secret_key("nostr").unwrap();
+        assert_eq!(secret_key, SecretKey::from_str(SECRET_KEY).unwrap());
+
+        assert!(matches!(
+            encrypted.to_secret_key("wrong"),
+            Err(Error::ChaCha20Poly1305(..))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_encrypt_decrypt() {
+        let secret_key = SecretKey::from_str(SECRET_KEY).unwrap();
+
+        // "ÅΩẛ̣" in different unicode forms
+        let encrypted = EncryptedSecretKey::new(
+            &secret_key,
+            "\u{212B}\u{2126}\u{1E9B}\u{0323}",
+            8,
+            KeySecurity::Unknown,
+        )
+        .unwrap();
+
+        let bech32 = encrypted.to_bech32().unwrap();
+        assert!(bech32.starts_with("ncryptsec1"));
+
+        let decoded = EncryptedSecretKey::from_bech32(&bech32).unwrap();
+        assert_eq!(decoded, encrypted);
+        assert_eq!(decoded.key_security(), KeySecurity::Unknown);
+        assert_eq!(
+            decoded.to_secret_key("\u{00C5}\u{03A9}\u{1E69}").unwrap(),
+            secret_key
+        );
+        assert_eq!(
+            decoded
+                .to_secret_key("\u{0041}\u{030A}\u{03A9}\u{1E9B}\u{0323}")
+                .unwrap(),
+            secret_key
+        );
+        assert_eq!(
+            decoded.to_secret_key("\u{00C5}\u{03A9}").unwrap_err(),
+            Error::ChaCha20Poly1305(chacha20poly1305::Error)
+        );
+    }
+
+    #[test]
+    fn test_max_log_n() {
+        let mut bytes = EncryptedSecretKey::from_bech32("ncryptsec1qgg9947rlpvqu76pj5ecreduf9jxhselq2nae2kghhvd5g7dgjtcxfqtd67p9m0w57lspw8gsq6yphnm8623nsl8xn9j4jdzz84zm3frztj3z7s35vpzmqf6ksu8r89qk5z2zxfmu5gv8th8wclt0h4p").unwrap().to_vec();
+        bytes[1] = 60;
+        let encrypted = EncryptedSecretKey::from_slice(&bytes).unwrap();
+        assert_eq!(
+            encrypted.to_secret_key("nostr").unwrap_err(),
+            Error::LogNTooHigh {
+                max: DEFAULT_MAX_LOG_N,
+                found: 60
+            }
+        );
+
+        let encrypted = EncryptedSecretKey::from_bech32("ncryptsec1qgg9947rlpvqu76pj5ecreduf9jxhselq2nae2kghhvd5g7dgjtcxfqtd67p9m0w57lspw8gsq6yphnm8623nsl8xn9j4jdzz84zm3frztj3z7s35vpzmqf6ksu8r89qk5z2zxfmu5gv8th8wclt0h4p").unwrap();
+        assert_eq!(
+            encrypted
+                .to_secret_key_with_max_log_n("nostr", 15)
+                .unwrap_err(),
+            Error::LogNTooHigh { max: 15, found: 16 }
+        );
+        assert_eq!(
+            encrypted.to_secret_key_with_max_log_n("nostr", 16).unwrap(),
+            SecretKey::from_str(SECRET_KEY).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_encrypted_secret_key() {
+        assert_eq!(
+            EncryptedSecretKey::from_slice(&[0x02; 10]),
+            Err(Error::InvalidLength {
+                expected: ENCRYPTED_SECRET_KEY_SIZE,
+                found: 10
+            })
+        );
+
+        let mut bytes = [0u8; ENCRYPTED_SECRET_KEY_SIZE];
+        bytes[0] = 0x01;
+        assert_eq!(
+            EncryptedSecretKey::from_slice(&bytes),
+            Err(Error::UnknownVersion(0x01))
+        );
+
+        bytes[0] = 0x02;
+        bytes[2 + SALT_SIZE + NONCE_SIZE] = 0x03;
+        assert_eq!(
+            EncryptedSecretKey::from_slice(&bytes),
+            Err(Error::UnknownKeySecurity(0x03))
+        );
+    }
+}
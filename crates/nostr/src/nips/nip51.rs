@@ -0,0 +1,301 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP51
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/51.md>
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use bitcoin::secp256k1::XOnlyPublicKey;
+
+use crate::{Event, Filter, Kind, Tag};
+
+/// NIP51 error
+#[derive(Debug)]
+pub enum Error {
+    /// Error serializing or deserializing JSON data
+    Json(serde_json::Error),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(e) => write!(f, "Json: {e}"),
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+/// List
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum List {
+    /// Mute list (kind `10000`)
+    Mute,
+    /// Pinned notes (kind `10001`)
+    Pin,
+    /// Bookmarks (kind `10003`)
+    Bookmarks,
+    /// Bookmark set (kind `30003`), with the identifier
+    BookmarkSet(String),
+}
+
+impl List {
+    /// Get [`Kind`]
+    pub fn kind(&self) -> Kind {
+        match self {
+            Self::Mute => Kind::MuteList,
+            Self::Pin => Kind::PinList,
+            Self::Bookmarks => Kind::Bookmarks,
+            Self::BookmarkSet(..) => Kind::BookmarkSet,
+        }
+    }
+
+    /// Get identifier (`d` tag), if it's a set
+    pub fn identifier(&self) -> Option<&str> {
+        match self {
+            Self::BookmarkSet(identifier) => Some(identifier),
+            _ => None,
+        }
+    }
+
+    /// Filter to get the latest list of `author`
+    pub fn filter(&self, author: XOnlyPublicKey) -> Filter {
+        let filter = Filter::new().kind(self.kind()).author(author).limit(1);
+        match self.identifier() {
+            Some(identifier) => filter.identifier(identifier),
+            None => filter,
+        }
+    }
+}
+
+/// List items
+///
+/// The items are tags: the private ones are stored encrypted in the content of the list event.
+/// Two tags are the same item if they have the same name and first value
+/// (i.e. a `p` tag with and without the relay hint).
+///
+/// # Example
+/// ```rust
+/// use nostr::prelude::*;
+///
+/// let public_key = Keys::generate().public_key();
+/// let mut items = ListItems::new();
+/// assert!(items.add(Tag::public_key(public_key), true));
+/// assert!(!items.add(Tag::public_key(public_key), true));
+/// assert!(items.contains(&Tag::public_key(public_key)));
+/// assert!(items.remove(&Tag::public_key(public_key)));
+/// assert!(items.is_empty());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListItems {
+    /// Public items (tags of the list event)
+    pub public: Vec<Tag>,
+    /// Private items
+    pub private: Vec<Tag>,
+}
+
+impl ListItems {
+    /// New empty list
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the public items of the list event
+    ///
+    /// The private items must be decrypted and set with [`ListItems::set_private`].
+    pub fn from_event(event: &Event) -> Self {
+        Self {
+            public: event
+                .tags
+                .iter()
+                .filter(|tag| !matches!(tag, Tag::Identifier(..)))
+                .cloned()
+                .collect(),
+            private: Vec::new(),
+        }
+    }
+
+    /// Set the private items from the decrypted content of the list event
+    pub fn set_private<S>(&mut self, decrypted_content: S) -> Result<(), Error>
+    where
+        S: AsRef<str>,
+    {
+        let content: &str = decrypted_content.as_ref();
+        self.private = if content.is_empty() {
+            Vec::new()
+        } else {
+            serde_json::from_str(content)?
+        };
+        Ok(())
+    }
+
+    /// Serialize the private items, to be encrypted into the content of the list event
+    ///
+    /// Return an empty string if there aren't private items.
+    pub fn private_content(&self) -> Result<String, Error> {
+        if self.private.is_empty() {
+            Ok(String::new())
+        } else {
+            Ok(serde_json::to_string(&self.private)?)
+        }
+    }
+
+    /// Iterate the public and private items
+    pub fn iter(&self) -> impl Iterator<Item = &Tag> {
+        self.public.iter().chain(self.private.iter())
+    }
+
+    /// Number of items
+    pub fn len(&self) -> usize {
+        self.public.len() + self.private.len()
+    }
+
+    /// Check if the list is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check if the item is in the list (public or private)
+    pub fn contains(&self, item: &Tag) -> bool {
+        self.iter().any(|tag| same_item(tag, item))
+    }
+
+    /// Add the item, or move it to the public/private items
+    ///
+    /// Return `false` if the item is already in the list, with the same visibility.
+    pub fn add(&mut self, item: Tag, private: bool) -> bool {
+        let (target, other) = if private {
+            (&mut self.private, &mut self.public)
+        } else {
+            (&mut self.public, &mut self.private)
+        };
+
+        if target.iter().any(|tag| same_item(tag, &item)) {
+            return false;
+        }
+
+        other.retain(|tag| !same_item(tag, &item));
+        target.push(item);
+        true
+    }
+
+    /// Remove the item
+    ///
+    /// Return `false` if the item wasn't in the list.
+    pub fn remove(&mut self, item: &Tag) -> bool {
+        let len: usize = self.len();
+        self.public.retain(|tag| !same_item(tag, item));
+        self.private.retain(|tag| !same_item(tag, item));
+        self.len() != len
+    }
+
+    /// Add the items of `other` not already in the list
+    pub fn merge(&mut self, other: ListItems) {
+        for tag in other.public.into_iter() {
+            if !self.contains(&tag) {
+                self.public.push(tag);
+            }
+        }
+        for tag in other.private.into_iter() {
+            if !self.contains(&tag) {
+                self.private.push(tag);
+            }
+        }
+    }
+}
+
+fn same_item(a: &Tag, b: &Tag) -> bool {
+    let a: Vec<String> = a.as_vec();
+    let b: Vec<String> = b.as_vec();
+    a.first() == b.first() && a.get(1) == b.get(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use super::*;
+    use crate::{EventId, UncheckedUrl};
+
+    #[test]
+    fn test_list_items() {
+        let public_key = XOnlyPublicKey::from_str(
+            "68d81165918100b7da43fc28f7d1fc12554466e1115886b9e7bb326f65ec4272",
+        )
+        .unwrap();
+        let event_id =
+            EventId::from_hex("7469af3be8c8e06e1b50ef1caceba30392ddc0b6614507398b7d7daa4c218e96")
+                .unwrap();
+
+        let mut items = ListItems::new();
+        assert!(items.add(Tag::public_key(public_key), false));
+        assert!(items.add(Tag::event(event_id), true));
+
+        // Same item, with relay hint
+        let with_hint = Tag::PublicKey {
+            public_key,
+            relay_url: Some(UncheckedUrl::from("wss://relay.damus.io")),
+            alias: None,
+            uppercase: false,
+        };
+        assert!(!items.add(with_hint.clone(), false));
+
+        // Moved to the private items
+        assert!(items.add(with_hint, true));
+        assert!(items.public.is_empty());
+        assert_eq!(items.private.len(), 2);
+
+        let content: String = items.private_content().unwrap();
+        let mut parsed = ListItems::new();
+        parsed.set_private(content).unwrap();
+        assert_eq!(parsed.private, items.private);
+
+        assert!(items.remove(&Tag::public_key(public_key)));
+        assert!(!items.remove(&Tag::public_key(public_key)));
+        assert_eq!(items.len(), 1);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut items = ListItems::new();
+        items.add(Tag::Hashtag(String::from("nostr")), false);
+
+        let mut other = ListItems::new();
+        other.add(Tag::Hashtag(String::from("nostr")), true);
+        other.add(Tag::Hashtag(String::from("bitcoin")), true);
+
+        items.merge(other);
+        assert_eq!(items.public, vec![Tag::Hashtag(String::from("nostr"))]);
+        assert_eq!(items.private, vec![Tag::Hashtag(String::from("bitcoin"))]);
+    }
+
+    #[test]
+    fn test_list_filter() {
+        let public_key = XOnlyPublicKey::from_str(
+            "68d81165918100b7da43fc28f7d1fc12554466e1115886b9e7bb326f65ec4272",
+        )
+        .unwrap();
+        let list = List::BookmarkSet(String::from("articles"));
+        assert_eq!(list.kind(), Kind::BookmarkSet);
+        assert_eq!(
+            list.filter(public_key),
+            Filter::new()
+                .kind(Kind::BookmarkSet)
+                .author(public_key)
+                .limit(1)
+                .identifier("articles")
+        );
+    }
+}
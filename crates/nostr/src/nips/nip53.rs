@@ -14,19 +14,28 @@ use core::str::FromStr;
 use bitcoin::secp256k1::schnorr::Signature;
 use bitcoin::secp256k1::XOnlyPublicKey;
 
-use crate::{ImageDimensions, Tag, Timestamp, UncheckedUrl};
+use crate::{Event, ImageDimensions, Kind, Tag, Timestamp, UncheckedUrl};
 
 /// NIP53 Error
 #[derive(Debug)]
 pub enum Error {
     /// Unknown [`LiveEventMarker`]
     UnknownLiveEventMarker(String),
+    /// Not a [`Kind::LiveEvent`]
+    WrongKind,
+    /// Identifier (`d`) tag not found
+    IdentifierTagNotFound,
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::UnknownLiveEventMarker(u) => write!(f, "Unknown live event marker: {u}"),
+            Self::WrongKind => write!(f, "Wrong kind: expected live event"),
+            Self::IdentifierTagNotFound => write!(f, "Identifier tag not found"),
         }
     }
 }
@@ -104,6 +113,7 @@ where
 }
 
 /// Live Event Host
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LiveEventHost {
     /// Host public key
     pub public_key: XOnlyPublicKey,
@@ -114,6 +124,7 @@ pub struct LiveEventHost {
 }
 
 /// Live Event
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LiveEvent {
     /// Unique event ID
     pub id: String,
@@ -149,6 +160,86 @@ pub struct LiveEvent {
     pub participants: Vec<(XOnlyPublicKey, Option<UncheckedUrl>)>,
 }
 
+impl LiveEvent {
+    /// New live event, with only the unique ID
+    pub fn new<S>(id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            id: id.into(),
+            title: None,
+            summary: None,
+            image: None,
+            hashtags: Vec::new(),
+            streaming: None,
+            recording: None,
+            starts: None,
+            ends: None,
+            status: None,
+            current_participants: None,
+            total_participants: None,
+            relays: Vec::new(),
+            host: None,
+            speakers: Vec::new(),
+            participants: Vec::new(),
+        }
+    }
+
+    /// Parse a [`Kind::LiveEvent`]
+    pub fn from_event(event: &Event) -> Result<Self, Error> {
+        if event.kind != Kind::LiveEvent {
+            return Err(Error::WrongKind);
+        }
+
+        let id: String = event
+            .identifier()
+            .ok_or(Error::IdentifierTagNotFound)?
+            .to_string();
+        let mut live_event = Self::new(id);
+
+        for tag in event.tags.iter() {
+            match tag {
+                Tag::Title(title) => live_event.title = Some(title.clone()),
+                Tag::Summary(summary) => live_event.summary = Some(summary.clone()),
+                Tag::Image(url, dim) => live_event.image = Some((url.clone(), *dim)),
+                Tag::Hashtag(hashtag) => live_event.hashtags.push(hashtag.clone()),
+                Tag::Streaming(url) => live_event.streaming = Some(url.clone()),
+                Tag::Recording(url) => live_event.recording = Some(url.clone()),
+                Tag::Starts(timestamp) => live_event.starts = Some(*timestamp),
+                Tag::Ends(timestamp) => live_event.ends = Some(*timestamp),
+                Tag::LiveEventStatus(status) => live_event.status = Some(status.clone()),
+                Tag::CurrentParticipants(num) => live_event.current_participants = Some(*num),
+                Tag::TotalParticipants(num) => live_event.total_participants = Some(*num),
+                Tag::Relays(relays) => live_event.relays.extend(relays.iter().cloned()),
+                Tag::PubKeyLiveEvent {
+                    public_key,
+                    relay_url,
+                    marker,
+                    proof,
+                } => match marker {
+                    LiveEventMarker::Host => {
+                        live_event.host = Some(LiveEventHost {
+                            public_key: *public_key,
+                            relay_url: relay_url.clone(),
+                            proof: *proof,
+                        })
+                    }
+                    LiveEventMarker::Speaker => {
+                        live_event.speakers.push((*public_key, relay_url.clone()))
+                    }
+                    LiveEventMarker::Participant => live_event
+                        .participants
+                        .push((*public_key, relay_url.clone())),
+                },
+                _ => (),
+            }
+        }
+
+        Ok(live_event)
+    }
+}
+
 impl From<LiveEvent> for Vec<Tag> {
     fn from(live_event: LiveEvent) -> Self {
         let mut tags = Vec::new();
@@ -257,3 +348,35 @@ impl From<LiveEvent> for Vec<Tag> {
         tags
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, Keys};
+
+    #[test]
+    fn test_live_event_from_event() {
+        let keys = Keys::generate();
+        let speaker = Keys::generate().public_key();
+
+        let mut live_event = LiveEvent::new("podcast");
+        live_event.title = Some(String::from("Nostr talk"));
+        live_event.status = Some(LiveEventStatus::Live);
+        live_event.current_participants = Some(21);
+        live_event.hashtags = vec![String::from("nostr")];
+        live_event.speakers = vec![(speaker, None)];
+
+        let event = EventBuilder::live_event(live_event.clone())
+            .to_event(&keys)
+            .unwrap();
+        assert_eq!(LiveEvent::from_event(&event).unwrap(), live_event);
+
+        let note = EventBuilder::new_text_note("GM", [])
+            .to_event(&keys)
+            .unwrap();
+        assert!(matches!(
+            LiveEvent::from_event(&note),
+            Err(Error::WrongKind)
+        ));
+    }
+}
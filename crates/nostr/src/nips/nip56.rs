@@ -0,0 +1,95 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP56
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/56.md>
+
+use alloc::vec::Vec;
+
+use bitcoin::secp256k1::XOnlyPublicKey;
+
+use crate::{Event, EventId, Report, Tag};
+
+/// Target of a report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ReportTarget {
+    /// User
+    PublicKey(XOnlyPublicKey),
+    /// Event
+    Event {
+        /// Event ID
+        event_id: EventId,
+        /// Author of the event
+        author: XOnlyPublicKey,
+    },
+}
+
+impl From<XOnlyPublicKey> for ReportTarget {
+    fn from(public_key: XOnlyPublicKey) -> Self {
+        Self::PublicKey(public_key)
+    }
+}
+
+impl From<&Event> for ReportTarget {
+    fn from(event: &Event) -> Self {
+        Self::Event {
+            event_id: event.id,
+            author: event.pubkey,
+        }
+    }
+}
+
+impl ReportTarget {
+    /// Compose the tags of the report
+    ///
+    /// The report type is set in the `e` tag when reporting an event, in the `p` tag otherwise.
+    pub fn tags(&self, report: Report) -> Vec<Tag> {
+        match self {
+            Self::PublicKey(public_key) => vec![Tag::PubKeyReport(*public_key, report)],
+            Self::Event { event_id, author } => vec![
+                Tag::EventReport(*event_id, report),
+                Tag::public_key(*author),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_report_target_tags() {
+        let public_key = XOnlyPublicKey::from_str(
+            "68d81165918100b7da43fc28f7d1fc12554466e1115886b9e7bb326f65ec4272",
+        )
+        .unwrap();
+        let event_id = EventId::all_zeros();
+
+        let target = ReportTarget::from(public_key);
+        assert_eq!(
+            target.tags(Report::Spam),
+            vec![Tag::PubKeyReport(public_key, Report::Spam)]
+        );
+
+        let target = ReportTarget::Event {
+            event_id,
+            author: public_key,
+        };
+        assert_eq!(
+            target.tags(Report::Malware),
+            vec![
+                Tag::EventReport(event_id, Report::Malware),
+                Tag::public_key(public_key)
+            ]
+        );
+        assert_eq!(
+            target.tags(Report::Other)[0].as_vec(),
+            vec![String::from("e"), event_id.to_hex(), String::from("other")]
+        );
+    }
+}
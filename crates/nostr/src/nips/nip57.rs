@@ -19,7 +19,7 @@ use bitcoin::hashes::Hash;
 #[cfg(feature = "std")]
 use bitcoin::secp256k1::rand::rngs::OsRng;
 use bitcoin::secp256k1::rand::{CryptoRng, RngCore};
-use bitcoin::secp256k1::{self, Secp256k1, SecretKey, Signing, XOnlyPublicKey};
+use bitcoin::secp256k1::{self, Secp256k1, SecretKey, Signing, Verification, XOnlyPublicKey};
 use cbc::{Decryptor, Encryptor};
 
 use super::nip01::Coordinate;
@@ -28,10 +28,12 @@ use crate::key::Error as KeyError;
 #[cfg(feature = "std")]
 use crate::types::time::Instant;
 use crate::types::time::TimeSupplier;
+use crate::url::ParseError;
 #[cfg(feature = "std")]
 use crate::SECP256K1;
 use crate::{
     event, util, Event, EventBuilder, EventId, JsonUtil, Keys, Kind, Tag, Timestamp, UncheckedUrl,
+    Url,
 };
 
 type Aes256CbcEnc = Encryptor<Aes256>;
@@ -39,6 +41,7 @@ type Aes256CbcDec = Decryptor<Aes256>;
 
 const PRIVATE_ZAP_MSG_BECH32_PREFIX: &str = "pzap";
 const PRIVATE_ZAP_IV_BECH32_PREFIX: &str = "iv";
+const LNURL_BECH32_PREFIX: &str = "lnurl";
 
 #[allow(missing_docs)]
 #[derive(Debug)]
@@ -48,12 +51,19 @@ pub enum Error {
     Event(event::Error),
     Bech32(bech32::Error),
     Secp256k1(secp256k1::Error),
+    Url(ParseError),
     InvalidPrivateZapMessage,
     PrivateZapMessageNotFound,
     /// Wrong prefix or variant
     WrongBech32PrefixOrVariant,
     /// Wrong encryption block mode
     WrongBlockMode,
+    /// Invalid LNURL or lightning address
+    InvalidLnurl,
+    /// Invalid or missing bolt11 invoice amount
+    InvalidBolt11,
+    /// Zap receipt not issued by the LNURL server or not matching its zap request
+    InvalidZapReceipt,
 }
 
 #[cfg(feature = "std")]
@@ -67,6 +77,7 @@ impl fmt::Display for Error {
             Self::Event(e) => write!(f, "{e}"),
             Self::Bech32(e) => write!(f, "{e}"),
             Self::Secp256k1(e) => write!(f, "{e}"),
+            Self::Url(e) => write!(f, "{e}"),
             Self::InvalidPrivateZapMessage => write!(f, "Invalid private zap message"),
             Self::PrivateZapMessageNotFound => write!(f, "Private zap message not found"),
             Self::WrongBech32PrefixOrVariant => write!(f, "Wrong bech32 prefix or variant"),
@@ -74,6 +85,9 @@ impl fmt::Display for Error {
                 f,
                 "Wrong encryption block mode. The content must be encrypted using CBC mode!"
             ),
+            Self::InvalidLnurl => write!(f, "Invalid LNURL or lightning address"),
+            Self::InvalidBolt11 => write!(f, "Invalid or missing bolt11 invoice amount"),
+            Self::InvalidZapReceipt => write!(f, "Invalid zap receipt"),
         }
     }
 }
@@ -108,6 +122,12 @@ impl From<secp256k1::Error> for Error {
     }
 }
 
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Self::Url(e)
+    }
+}
+
 /* /// Zap Type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ZapType {
@@ -243,6 +263,45 @@ impl From<ZapRequestData> for Vec<Tag> {
     }
 }
 
+/// Get the LNURL pay endpoint from a `lud06` (bech32 encoded LNURL) or a `lud16` (lightning address)
+///
+/// <https://github.com/lnurl/luds/blob/luds/16.md>
+pub fn lnurl_pay_url<S>(lud: S) -> Result<Url, Error>
+where
+    S: AsRef<str>,
+{
+    let lud: &str = lud.as_ref().trim();
+    let lud: &str = lud.strip_prefix("lightning:").unwrap_or(lud);
+
+    // Lightning address
+    if let Some((name, domain)) = lud.split_once('@') {
+        if name.is_empty() || domain.is_empty() || domain.contains('/') {
+            return Err(Error::InvalidLnurl);
+        }
+        return Ok(Url::parse(&format!(
+            "https://{domain}/.well-known/lnurlp/{name}"
+        ))?);
+    }
+
+    // LNURL
+    let (hrp, data, checksum) = bech32::decode(lud)?;
+    if hrp != LNURL_BECH32_PREFIX || checksum != Variant::Bech32 {
+        return Err(Error::WrongBech32PrefixOrVariant);
+    }
+    let data: Vec<u8> = Vec::from_base32(&data)?;
+    let url: String = String::from_utf8(data).map_err(|_| Error::InvalidLnurl)?;
+    Ok(Url::parse(&url)?)
+}
+
+/// Encode the LNURL pay endpoint as bech32 (with the prefix `lnurl`)
+pub fn encode_lnurl(url: &Url) -> Result<String, Error> {
+    Ok(bech32::encode(
+        LNURL_BECH32_PREFIX,
+        url.as_str().as_bytes().to_base32(),
+        Variant::Bech32,
+    )?)
+}
+
 /// Create **anonymous** zap request
 #[cfg(feature = "std")]
 pub fn anonymous_zap_request(data: ZapRequestData) -> Result<Event, Error> {
@@ -388,6 +447,149 @@ pub fn decrypt_private_zap_message(
     Ok(Event::from_json(result)?)
 }
 
+/// Zap receipt verified against its zap request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZapReceipt {
+    /// Zap receipt ID
+    pub id: EventId,
+    /// Author of the zap request (a random key for anonymous and private zaps)
+    pub zapper: XOnlyPublicKey,
+    /// Zap recipient
+    pub recipient: XOnlyPublicKey,
+    /// Zapped event
+    pub event_id: Option<EventId>,
+    /// Amount paid (msat)
+    pub amount_msat: u64,
+    /// Message of the zap request
+    pub message: String,
+    /// Zap receipt timestamp
+    pub created_at: Timestamp,
+}
+
+impl ZapReceipt {
+    /// Verify the zap receipt issued by the LNURL server with `nostr_pubkey`
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/57.md#appendix-f-validating-zap-receipts>
+    #[cfg(feature = "std")]
+    pub fn from_event(receipt: &Event, nostr_pubkey: &XOnlyPublicKey) -> Result<Self, Error> {
+        Self::from_event_with_ctx(&SECP256K1, receipt, nostr_pubkey)
+    }
+
+    /// Verify the zap receipt issued by the LNURL server with `nostr_pubkey`
+    ///
+    /// <https://github.com/nostr-protocol/nips/blob/master/57.md#appendix-f-validating-zap-receipts>
+    pub fn from_event_with_ctx<C>(
+        secp: &Secp256k1<C>,
+        receipt: &Event,
+        nostr_pubkey: &XOnlyPublicKey,
+    ) -> Result<Self, Error>
+    where
+        C: Verification,
+    {
+        if receipt.kind != Kind::ZapReceipt || &receipt.pubkey != nostr_pubkey {
+            return Err(Error::InvalidZapReceipt);
+        }
+        receipt.verify_with_ctx(secp)?;
+
+        let bolt11: &str = receipt
+            .tags
+            .iter()
+            .find_map(|tag| match tag {
+                Tag::Bolt11(bolt11) => Some(bolt11.as_str()),
+                _ => None,
+            })
+            .ok_or(Error::InvalidZapReceipt)?;
+        let amount_msat: u64 = bolt11_amount_msat(bolt11).ok_or(Error::InvalidBolt11)?;
+
+        let description: &str = receipt
+            .tags
+            .iter()
+            .find_map(|tag| match tag {
+                Tag::Description(description) => Some(description.as_str()),
+                _ => None,
+            })
+            .ok_or(Error::InvalidZapReceipt)?;
+        let zap_request: Event = Event::from_json(description)?;
+        if zap_request.kind != Kind::ZapRequest {
+            return Err(Error::InvalidZapReceipt);
+        }
+        zap_request.verify_with_ctx(secp)?;
+
+        // The invoice must pay the requested amount
+        for tag in zap_request.tags.iter() {
+            if let Tag::Amount { millisats, .. } = tag {
+                if *millisats != amount_msat {
+                    return Err(Error::InvalidZapReceipt);
+                }
+            }
+        }
+
+        // The recipient and the zapped event must match the ones of the zap request
+        let recipient: XOnlyPublicKey =
+            zap_recipient(&zap_request).ok_or(Error::InvalidZapReceipt)?;
+        let event_id: Option<EventId> = zapped_event(&zap_request);
+        if zap_recipient(receipt).map_or(false, |p| p != recipient)
+            || zapped_event(receipt).map_or(false, |e| Some(e) != event_id)
+        {
+            return Err(Error::InvalidZapReceipt);
+        }
+
+        Ok(Self {
+            id: receipt.id,
+            zapper: zap_request.pubkey,
+            recipient,
+            event_id,
+            amount_msat,
+            message: zap_request.content,
+            created_at: receipt.created_at,
+        })
+    }
+}
+
+fn zap_recipient(event: &Event) -> Option<XOnlyPublicKey> {
+    event.tags.iter().find_map(|tag| match tag {
+        Tag::PublicKey {
+            public_key,
+            uppercase: false,
+            ..
+        } => Some(*public_key),
+        _ => None,
+    })
+}
+
+fn zapped_event(event: &Event) -> Option<EventId> {
+    event.tags.iter().find_map(|tag| match tag {
+        Tag::Event { event_id, .. } => Some(*event_id),
+        _ => None,
+    })
+}
+
+/// Get the amount (msat) of a bolt11 invoice
+///
+/// Return `None` if the invoice doesn't specify the amount.
+pub fn bolt11_amount_msat(invoice: &str) -> Option<u64> {
+    let invoice: String = invoice.to_lowercase();
+    let hrp: &str = &invoice[..invoice.rfind('1')?];
+    let hrp: &str = hrp.strip_prefix("ln")?;
+
+    // Skip the currency prefix (i.e. `bc`, `tb`, `bcrt`)
+    let amount: &str = &hrp[hrp.find(|c: char| c.is_ascii_digit())?..];
+    let (value, multiplier): (&str, Option<char>) = match amount.chars().last()? {
+        c if c.is_ascii_digit() => (amount, None),
+        c => (&amount[..amount.len() - c.len_utf8()], Some(c)),
+    };
+    let value: u64 = value.parse().ok()?;
+
+    match multiplier {
+        None => value.checked_mul(100_000_000_000),
+        Some('m') => value.checked_mul(100_000_000),
+        Some('u') => value.checked_mul(100_000),
+        Some('n') => value.checked_mul(100),
+        Some('p') if value % 10 == 0 => Some(value / 10),
+        _ => None,
+    }
+}
+
 #[cfg(feature = "std")]
 #[cfg(test)]
 mod tests {
@@ -417,4 +619,86 @@ mod tests {
 
         assert_eq!(msg, &private_zap_msg.content,)
     }
+
+    #[test]
+    fn test_lnurl_pay_url() {
+        let url = lnurl_pay_url("alice@example.com").unwrap();
+        assert_eq!(url.as_str(), "https://example.com/.well-known/lnurlp/alice");
+
+        // LUD06 example
+        let lnurl = "LNURL1DP68GURN8GHJ7UM9WFMXJCM99E3K7MF0V9CXJ0M385EKVCENXC6R2C35XVUKXEFCV5MKVV34X5EKZD3EV56NYD3HXQURZEPEXEJXXEPNXSCRVWFNV9NXZCN9XQ6XYEFHVGCXXCMYXYMNSERXFQ5FNS";
+        let url = lnurl_pay_url(lnurl).unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://service.com/api?q=3fc3645b439ce8e7f2553a69e5267081d96dcd340693afabe04be7b0ccd178df"
+        );
+        assert_eq!(encode_lnurl(&url).unwrap(), lnurl.to_lowercase());
+
+        assert!(lnurl_pay_url("@example.com").is_err());
+        assert!(
+            lnurl_pay_url("npub14f8usejl26twx0dhuxjh9cas7keav9vr0v8nvtwtrjqx3vycc76qqh9nsy")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_bolt11_amount_msat() {
+        assert_eq!(bolt11_amount_msat("lnbc2500u1pvjluez"), Some(250_000_000));
+        assert_eq!(bolt11_amount_msat("lnbc20m1pvjluez"), Some(2_000_000_000));
+        assert_eq!(bolt11_amount_msat("LNBC10N1PVJLUEZ"), Some(1_000));
+        assert_eq!(
+            bolt11_amount_msat("lnbc9678785340p1pwmna7l"),
+            Some(967_878_534)
+        );
+        assert_eq!(bolt11_amount_msat("lntb1m1pvjluez"), Some(100_000_000));
+        assert_eq!(bolt11_amount_msat("lnbcrt1u1pvjluez"), Some(100_000));
+        assert_eq!(bolt11_amount_msat("lnbc1pvjluez"), None);
+        assert_eq!(bolt11_amount_msat("lnbc1p1pvjluez"), None);
+        assert_eq!(bolt11_amount_msat("invoice"), None);
+        assert_eq!(bolt11_amount_msat("lnbc10é1pvjluez"), None);
+        assert_eq!(bolt11_amount_msat("lnbc2€1pvjluez"), None);
+        assert_eq!(bolt11_amount_msat("lnbcé1pvjluez"), None);
+    }
+
+    #[test]
+    fn test_zap_receipt() {
+        let zapper = Keys::generate();
+        let recipient = Keys::generate();
+        let server = Keys::generate();
+        let event_id = EventId::all_zeros();
+
+        let data = ZapRequestData::new(recipient.public_key(), [])
+            .amount(250_000_000)
+            .event_id(event_id)
+            .message("Great post!");
+        let zap_request = EventBuilder::public_zap_request(data)
+            .to_event(&zapper)
+            .unwrap();
+        let receipt = EventBuilder::new_zap_receipt("lnbc2500u1pvjluez", None, zap_request.clone())
+            .to_event(&server)
+            .unwrap();
+
+        let zap = ZapReceipt::from_event(&receipt, &server.public_key()).unwrap();
+        assert_eq!(zap.zapper, zapper.public_key());
+        assert_eq!(zap.recipient, recipient.public_key());
+        assert_eq!(zap.event_id, Some(event_id));
+        assert_eq!(zap.amount_msat, 250_000_000);
+        assert_eq!(zap.message, "Great post!");
+
+        // Not issued by the LNURL server
+        let forged = EventBuilder::new_zap_receipt("lnbc2500u1pvjluez", None, zap_request.clone())
+            .to_event(&zapper)
+            .unwrap();
+        assert!(ZapReceipt::from_event(&forged, &server.public_key()).is_err());
+        assert!(ZapReceipt::from_event(&forged, &zapper.public_key()).is_ok());
+
+        // Invoice amount not matching the requested one
+        let underpaid = EventBuilder::new_zap_receipt("lnbc10n1pvjluez", None, zap_request)
+            .to_event(&server)
+            .unwrap();
+        assert!(matches!(
+            ZapReceipt::from_event(&underpaid, &server.public_key()),
+            Err(Error::InvalidZapReceipt)
+        ));
+    }
 }
@@ -11,7 +11,11 @@ use core::fmt;
 
 use bitcoin::secp256k1::XOnlyPublicKey;
 
-use crate::{Event, Kind, Tag, UncheckedUrl};
+use crate::nips::nip01::Coordinate;
+use crate::{Event, EventId, Kind, Tag, UncheckedUrl};
+
+/// `d` tag identifier of the [`Kind::ProfileBadges`] event
+pub const PROFILE_BADGES_IDENTIFIER: &str = "profile_badges";
 
 #[derive(Debug)]
 /// [`BadgeAward`](crate::event::kind::Kind#variant.BadgeAward) error
@@ -39,8 +43,12 @@ impl fmt::Display for Error {
             Self::InvalidLength => write!(f, "invalid length"),
             Self::InvalidKind => write!(f, "invalid kind"),
             Self::IdentifierTagNotFound => write!(f, "identifier tag not found"),
-            Self::MismatchedBadgeDefinitionOrAward => write!(f, "mismatched badge definition/award"),
-            Self::BadgeAwardsLackAwardedPublicKey => write!(f, "badge award events lack the awarded public keybadge award events lack the awarded public key"),
+            Self::MismatchedBadgeDefinitionOrAward => {
+                write!(f, "mismatched badge definition/award")
+            }
+            Self::BadgeAwardsLackAwardedPublicKey => {
+                write!(f, "badge award events lack the awarded public key")
+            }
             Self::BadgeAwardMissingATag => write!(f, "badge award event lacks `a` tag"),
         }
     }
@@ -68,3 +76,65 @@ pub(crate) fn extract_awarded_public_key(
         _ => None,
     })
 }
+
+/// Extract the badges of a [`Kind::ProfileBadges`] event, in order
+///
+/// Each badge is an `a` tag (badge definition) immediately followed by an `e` tag (badge award):
+/// unpaired tags are ignored.
+pub fn extract_profile_badges(event: &Event) -> Vec<(Coordinate, EventId)> {
+    let mut badges: Vec<(Coordinate, EventId)> = Vec::new();
+    let mut tags = event.tags.iter().peekable();
+    while let Some(tag) = tags.next() {
+        if let Tag::A {
+            kind,
+            public_key,
+            identifier,
+            relay_url,
+        } = tag
+        {
+            if let Some(Tag::Event { event_id, .. }) = tags.peek() {
+                let mut coordinate = Coordinate::new(*kind, *public_key).identifier(identifier);
+                coordinate.relays = relay_url.iter().map(|u| u.to_string()).collect();
+                badges.push((coordinate, *event_id));
+                tags.next();
+            }
+        }
+    }
+    badges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, Keys};
+
+    #[test]
+    fn test_extract_profile_badges() {
+        let issuer = Keys::generate();
+        let awarded = Keys::generate();
+
+        let definition = EventBuilder::define_badge("bravery", None, None, None, None, Vec::new())
+            .to_event(&issuer)
+            .unwrap();
+        let award = EventBuilder::award_badge(&definition, [Tag::public_key(awarded.public_key())])
+            .unwrap()
+            .to_event(&issuer)
+            .unwrap();
+        let profile_badges = EventBuilder::profile_badges(
+            vec![definition],
+            vec![award.clone()],
+            &awarded.public_key(),
+        )
+        .unwrap()
+        .to_event(&awarded)
+        .unwrap();
+
+        let badges = extract_profile_badges(&profile_badges);
+        assert_eq!(badges.len(), 1);
+        let (coordinate, award_id) = &badges[0];
+        assert_eq!(coordinate.kind, Kind::BadgeDefinition);
+        assert_eq!(coordinate.pubkey, issuer.public_key());
+        assert_eq!(coordinate.identifier, "bravery");
+        assert_eq!(*award_id, award.id);
+    }
+}
@@ -0,0 +1,325 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP59
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/59.md>
+
+#[cfg(feature = "std")]
+use alloc::string::String;
+use core::fmt;
+use core::time::Duration;
+
+use bitcoin::secp256k1::XOnlyPublicKey;
+
+use super::nip44;
+#[cfg(feature = "std")]
+use super::nip44::Version;
+use crate::event::{self, unsigned};
+use crate::{key, Event, JsonUtil, Keys, Kind, UnsignedEvent};
+#[cfg(feature = "std")]
+use crate::{EventBuilder, Tag, Timestamp};
+
+/// Default window of the random `created_at` of the seals and gift wraps (2 days)
+///
+/// The timestamps are moved in the past to not leak when the message was sent.
+pub const DEFAULT_TIMESTAMP_TWEAK: Duration = Duration::from_secs(2 * 24 * 60 * 60);
+
+/// NIP59 error
+#[derive(Debug)]
+pub enum Error {
+    /// Key error
+    Key(key::Error),
+    /// Event error
+    Event(event::Error),
+    /// Unsigned event error
+    Unsigned(unsigned::Error),
+    /// NIP44 error
+    NIP44(nip44::Error),
+    /// Not a gift wrap
+    NotGiftWrap,
+    /// Not a seal
+    NotSeal,
+    /// The author of the rumor is not the author of the seal
+    SenderMismatch,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Key(e) => write!(f, "Key: {e}"),
+            Self::Event(e) => write!(f, "Event: {e}"),
+            Self::Unsigned(e) => write!(f, "Unsigned event: {e}"),
+            Self::NIP44(e) => write!(f, "NIP44: {e}"),
+            Self::NotGiftWrap => write!(f, "Not a gift wrap"),
+            Self::NotSeal => write!(f, "Not a seal"),
+            Self::SenderMismatch => write!(f, "Rumor and seal authors not match"),
+        }
+    }
+}
+
+impl From<key::Error> for Error {
+    fn from(e: key::Error) -> Self {
+        Self::Key(e)
+    }
+}
+
+impl From<event::Error> for Error {
+    fn from(e: event::Error) -> Self {
+        Self::Event(e)
+    }
+}
+
+impl From<unsigned::Error> for Error {
+    fn from(e: unsigned::Error) -> Self {
+        Self::Unsigned(e)
+    }
+}
+
+impl From<nip44::Error> for Error {
+    fn from(e: nip44::Error) -> Self {
+        Self::NIP44(e)
+    }
+}
+
+/// Unwrapped gift wrap
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnwrappedGift {
+    /// The author of the seal (and of the rumor)
+    pub sender: XOnlyPublicKey,
+    /// The rumor
+    pub rumor: UnsignedEvent,
+}
+
+/// Compose the `created_at` of a seal or gift wrap
+///
+/// If `tweak` is `None`, the current timestamp is used.
+#[cfg(feature = "std")]
+pub fn created_at(tweak: Option<Duration>) -> Timestamp {
+    match tweak {
+        Some(window) => Timestamp::tweaked_within(window),
+        None => Timestamp::now(),
+    }
+}
+
+/// Seal the `rumor`, NIP44 encrypting it to the `receiver`
+///
+/// The rumor must be authored by the `sender_keys`.
+/// The `created_at` is moved in the past by a random time within `tweak` (see [`DEFAULT_TIMESTAMP_TWEAK`]).
+#[cfg(feature = "std")]
+pub fn seal(
+    sender_keys: &Keys,
+    receiver: &XOnlyPublicKey,
+    rumor: UnsignedEvent,
+    tweak: Option<Duration>,
+) -> Result<Event, Error> {
+    let public_key: XOnlyPublicKey = sender_keys.public_key();
+    if rumor.pubkey != public_key {
+        return Err(Error::SenderMismatch);
+    }
+
+    let content: String = nip44::encrypt(
+        &sender_keys.secret_key()?,
+        receiver,
+        rumor.as_json(),
+        Version::V2,
+    )?;
+    Ok(EventBuilder::new(Kind::Seal, content, [])
+        .custom_created_at(created_at(tweak))
+        .to_unsigned_event(public_key)
+        .sign(sender_keys)?)
+}
+
+/// Gift wrap the `seal` for the `receiver`
+///
+/// The seal is NIP44 encrypted and signed with new ephemeral keys.
+/// The `created_at` is moved in the past by a random time within `tweak` (see [`DEFAULT_TIMESTAMP_TWEAK`]).
+#[cfg(feature = "std")]
+pub fn gift_wrap_from_seal(
+    receiver: &XOnlyPublicKey,
+    seal: &Event,
+    tweak: Option<Duration>,
+) -> Result<Event, Error> {
+    if seal.kind != Kind::Seal {
+        return Err(Error::NotSeal);
+    }
+
+    let keys: Keys = Keys::generate();
+    let content: String =
+        nip44::encrypt(&keys.secret_key()?, receiver, seal.as_json(), Version::V2)?;
+    Ok(
+        EventBuilder::new(Kind::GiftWrap, content, [Tag::public_key(*receiver)])
+            .custom_created_at(created_at(tweak))
+            .to_unsigned_event(keys.public_key())
+            .sign(&keys)?,
+    )
+}
+
+/// Seal the `rumor` with the `sender_keys` and gift wrap it for the `receiver`
+#[cfg(feature = "std")]
+pub fn gift_wrap(
+    sender_keys: &Keys,
+    receiver: &XOnlyPublicKey,
+    rumor: UnsignedEvent,
+    tweak: Option<Duration>,
+) -> Result<Event, Error> {
+    let seal: Event = seal(sender_keys, receiver, rumor, tweak)?;
+    gift_wrap_from_seal(receiver, &seal, tweak)
+}
+
+/// Parse and verify the decrypted content of a gift wrap
+///
+/// Return the seal, if its signature is valid.
+pub fn open_gift_wrap<T>(gift_wrap: &Event, decrypted_seal: T) -> Result<Event, Error>
+where
+    T: AsRef<[u8]>,
+{
+    if gift_wrap.kind != Kind::GiftWrap {
+        return Err(Error::NotGiftWrap);
+    }
+
+    let seal: Event = Event::from_json(decrypted_seal)?;
+    if seal.kind != Kind::Seal {
+        return Err(Error::NotSeal);
+    }
+    seal.verify()?;
+    Ok(seal)
+}
+
+/// Parse the decrypted content of a seal
+///
+/// The rumor must be authored by the signer of the seal.
+pub fn open_seal<T>(seal: &Event, decrypted_rumor: T) -> Result<UnwrappedGift, Error>
+where
+    T: AsRef<[u8]>,
+{
+    let rumor: UnsignedEvent = UnsignedEvent::from_json(decrypted_rumor)?;
+    if rumor.pubkey != seal.pubkey {
+        return Err(Error::SenderMismatch);
+    }
+    Ok(UnwrappedGift {
+        sender: seal.pubkey,
+        rumor,
+    })
+}
+
+/// Extract the rumor from a gift wrap addressed to `receiver_keys`
+pub fn extract_rumor(receiver_keys: &Keys, gift_wrap: &Event) -> Result<UnwrappedGift, Error> {
+    let secret_key = receiver_keys.secret_key()?;
+    let seal: String = nip44::decrypt(&secret_key, &gift_wrap.pubkey, &gift_wrap.content)?;
+    let seal: Event = open_gift_wrap(gift_wrap, seal)?;
+    let rumor: String = nip44::decrypt(&secret_key, &seal.pubkey, &seal.content)?;
+    open_seal(&seal, rumor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EventBuilder;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_extract_rumor() {
+        let sender_keys = Keys::generate();
+        let receiver_keys = Keys::generate();
+
+        let rumor: UnsignedEvent =
+            EventBuilder::private_msg_rumor(receiver_keys.public_key(), "Test", None)
+                .to_unsigned_event(sender_keys.public_key());
+        let gift_wrap: Event =
+            EventBuilder::gift_wrap(&sender_keys, &receiver_keys.public_key(), rumor.clone())
+                .unwrap();
+
+        assert_eq!(gift_wrap.kind, Kind::GiftWrap);
+        assert_ne!(gift_wrap.pubkey, sender_keys.public_key());
+
+        let unwrapped: UnwrappedGift = extract_rumor(&receiver_keys, &gift_wrap).unwrap();
+        assert_eq!(unwrapped.sender, sender_keys.public_key());
+        assert_eq!(unwrapped.rumor, rumor);
+
+        // Not addressed to a third party
+        assert!(extract_rumor(&Keys::generate(), &gift_wrap).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_seal_and_gift_wrap() {
+        let sender_keys = Keys::generate();
+        let receiver_keys = Keys::generate();
+        let receiver: XOnlyPublicKey = receiver_keys.public_key();
+
+        let rumor: UnsignedEvent = EventBuilder::private_msg_rumor(receiver, "Test", None)
+            .to_unsigned_event(sender_keys.public_key());
+        let sealed: Event = seal(&sender_keys, &receiver, rumor.clone(), None).unwrap();
+        assert_eq!(sealed.kind, Kind::Seal);
+        assert_eq!(sealed.pubkey, sender_keys.public_key());
+        assert!(sealed.tags.is_empty());
+        sealed.verify().unwrap();
+
+        let gift_wrap: Event =
+            gift_wrap_from_seal(&receiver, &sealed, Some(DEFAULT_TIMESTAMP_TWEAK)).unwrap();
+        assert_eq!(gift_wrap.kind, Kind::GiftWrap);
+        assert_eq!(gift_wrap.public_keys().next(), Some(&receiver));
+        gift_wrap.verify().unwrap();
+
+        // Random created_at in the past
+        let now = Timestamp::now();
+        assert!(sealed.created_at <= now);
+        assert!(gift_wrap.created_at <= now);
+        assert!(gift_wrap.created_at >= now - DEFAULT_TIMESTAMP_TWEAK);
+
+        // Ephemeral wrap keys
+        let other: Event =
+            gift_wrap_from_seal(&receiver, &sealed, Some(DEFAULT_TIMESTAMP_TWEAK)).unwrap();
+        assert_ne!(gift_wrap.pubkey, sender_keys.public_key());
+        assert_ne!(gift_wrap.pubkey, other.pubkey);
+
+        let unwrapped: UnwrappedGift = extract_rumor(&receiver_keys, &gift_wrap).unwrap();
+        assert_eq!(unwrapped.sender, sender_keys.public_key());
+        assert_eq!(unwrapped.rumor, rumor);
+
+        // Only seals can be wrapped
+        assert!(matches!(
+            gift_wrap_from_seal(&receiver, &gift_wrap, None),
+            Err(Error::NotSeal)
+        ));
+
+        // The rumor must be authored by the sender
+        let rumor: UnsignedEvent =
+            EventBuilder::private_msg_rumor(receiver, "Test", None).to_unsigned_event(receiver);
+        assert!(matches!(
+            seal(&sender_keys, &receiver, rumor, None),
+            Err(Error::SenderMismatch)
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_open_seal_sender_mismatch() {
+        let sender_keys = Keys::generate();
+        let receiver_keys = Keys::generate();
+
+        // Rumor authored by someone else
+        let rumor: UnsignedEvent =
+            EventBuilder::private_msg_rumor(receiver_keys.public_key(), "Test", None)
+                .to_unsigned_event(Keys::generate().public_key());
+        let seal: Event = EventBuilder::seal(&sender_keys, &receiver_keys.public_key(), rumor)
+            .unwrap()
+            .to_event(&sender_keys)
+            .unwrap();
+        let decrypted: String = nip44::decrypt(
+            &receiver_keys.secret_key().unwrap(),
+            &seal.pubkey,
+            &seal.content,
+        )
+        .unwrap();
+        assert!(matches!(
+            open_seal(&seal, decrypted),
+            Err(Error::SenderMismatch)
+        ));
+    }
+}
@@ -0,0 +1,359 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP84
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/84.md>
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use bitcoin::secp256k1::XOnlyPublicKey;
+
+use crate::nips::nip01::Coordinate;
+use crate::{Event, EventId, Kind, Tag, TagKind, UncheckedUrl};
+
+const CONTEXT: &str = "context";
+const COMMENT: &str = "comment";
+const AUTHOR: &str = "author";
+
+/// NIP84 Error
+#[derive(Debug)]
+pub enum Error {
+    /// Not a [`Kind::Highlight`]
+    WrongKind,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongKind => write!(f, "Wrong kind: expected highlight"),
+        }
+    }
+}
+
+/// Source of a highlight
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HighlightSource {
+    /// Event (`e` tag)
+    Event {
+        /// Event ID
+        event_id: EventId,
+        /// Author of the event (`p` tag)
+        author: Option<XOnlyPublicKey>,
+    },
+    /// Parameterized replaceable event, like a long-form article (`a` tag)
+    Coordinate(Coordinate),
+    /// Non-nostr content (`r` tag)
+    Url(String),
+}
+
+impl From<EventId> for HighlightSource {
+    fn from(event_id: EventId) -> Self {
+        Self::Event {
+            event_id,
+            author: None,
+        }
+    }
+}
+
+impl From<Coordinate> for HighlightSource {
+    fn from(coordinate: Coordinate) -> Self {
+        Self::Coordinate(coordinate)
+    }
+}
+
+impl From<&Event> for HighlightSource {
+    fn from(event: &Event) -> Self {
+        if event.kind.is_parameterized_replaceable() {
+            Self::Coordinate(
+                Coordinate::new(event.kind, event.pubkey)
+                    .identifier(event.identifier().unwrap_or_default()),
+            )
+        } else {
+            Self::Event {
+                event_id: event.id,
+                author: Some(event.pubkey),
+            }
+        }
+    }
+}
+
+impl HighlightSource {
+    /// Get the author of the source, if known
+    pub fn author(&self) -> Option<XOnlyPublicKey> {
+        match self {
+            Self::Event { author, .. } => *author,
+            Self::Coordinate(coordinate) => Some(coordinate.pubkey),
+            Self::Url(..) => None,
+        }
+    }
+}
+
+/// Highlight
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Highlight {
+    /// Highlighted text
+    pub content: String,
+    /// Source
+    pub source: Option<HighlightSource>,
+    /// Surrounding text of the highlight, if the highlight is a portion of a paragraph
+    pub context: Option<String>,
+    /// Comment (quote highlight)
+    pub comment: Option<String>,
+}
+
+impl Highlight {
+    /// New highlight
+    pub fn new<S>(content: S, source: Option<HighlightSource>) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            content: content.into(),
+            source,
+            context: None,
+            comment: None,
+        }
+    }
+
+    /// Set surrounding text
+    pub fn context<S>(self, context: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            context: Some(context.into()),
+            ..self
+        }
+    }
+
+    /// Set comment
+    pub fn comment<S>(self, comment: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            comment: Some(comment.into()),
+            ..self
+        }
+    }
+
+    /// Parse a [`Kind::Highlight`]
+    pub fn from_event(event: &Event) -> Result<Self, Error> {
+        if event.kind != Kind::Highlight {
+            return Err(Error::WrongKind);
+        }
+
+        let mut highlight = Self::new(event.content.clone(), None);
+        let mut author: Option<XOnlyPublicKey> = None;
+
+        for tag in event.tags.iter() {
+            match tag {
+                Tag::Event { event_id, .. } if highlight.source.is_none() => {
+                    highlight.source = Some(HighlightSource::from(*event_id));
+                }
+                Tag::A {
+                    kind,
+                    public_key,
+                    identifier,
+                    relay_url,
+                } => {
+                    let mut coordinate = Coordinate::new(*kind, *public_key).identifier(identifier);
+                    if let Some(relay_url) = relay_url {
+                        coordinate.relays.push(relay_url.to_string());
+                    }
+                    highlight.source = Some(HighlightSource::Coordinate(coordinate));
+                }
+                Tag::Reference(url) if highlight.source.is_none() => {
+                    highlight.source = Some(HighlightSource::Url(url.clone()));
+                }
+                Tag::PublicKey {
+                    public_key, alias, ..
+                } if author.is_none() || alias.as_deref() == Some(AUTHOR) => {
+                    author = Some(*public_key);
+                }
+                Tag::Generic(TagKind::Custom(kind), values) if kind == CONTEXT => {
+                    highlight.context = values.first().cloned();
+                }
+                Tag::Generic(TagKind::Custom(kind), values) if kind == COMMENT => {
+                    highlight.comment = values.first().cloned();
+                }
+                _ => (),
+            }
+        }
+
+        if let Some(HighlightSource::Event { author: a, .. }) = &mut highlight.source {
+            *a = author;
+        }
+
+        Ok(highlight)
+    }
+
+    /// Find the highlighted text in the `original` content of the source
+    ///
+    /// If the context is found in `original`, the highlight is searched inside it,
+    /// to disambiguate repeated text. Return the byte range of the highlight.
+    pub fn locate(&self, original: &str) -> Option<Range<usize>> {
+        if self.content.is_empty() {
+            return None;
+        }
+
+        if let Some(context) = &self.context {
+            if let Some(context_start) = original.find(context.as_str()) {
+                if let Some(start) = context.find(self.content.as_str()) {
+                    let start: usize = context_start + start;
+                    return Some(start..start + self.content.len());
+                }
+            }
+        }
+
+        let start: usize = original.find(self.content.as_str())?;
+        Some(start..start + self.content.len())
+    }
+}
+
+impl From<Highlight> for Vec<Tag> {
+    fn from(highlight: Highlight) -> Self {
+        let Highlight {
+            source,
+            context,
+            comment,
+            ..
+        } = highlight;
+
+        let mut tags: Vec<Tag> = Vec::new();
+
+        if let Some(source) = source {
+            let author: Option<XOnlyPublicKey> = source.author();
+            match source {
+                HighlightSource::Event { event_id, .. } => tags.push(Tag::event(event_id)),
+                HighlightSource::Coordinate(coordinate) => tags.push(coordinate.into()),
+                HighlightSource::Url(url) => tags.push(Tag::Reference(url)),
+            }
+            if let Some(author) = author {
+                tags.push(Tag::PublicKey {
+                    public_key: author,
+                    relay_url: Some(UncheckedUrl::empty()),
+                    alias: Some(AUTHOR.to_string()),
+                    uppercase: false,
+                });
+            }
+        }
+
+        if let Some(context) = context {
+            tags.push(Tag::Generic(
+                TagKind::Custom(CONTEXT.to_string()),
+                vec![context],
+            ));
+        }
+
+        if let Some(comment) = comment {
+            tags.push(Tag::Generic(
+                TagKind::Custom(COMMENT.to_string()),
+                vec![comment],
+            ));
+        }
+
+        tags
+    }
+}
+
+/// Find the highlights in the `original` content of their source, to render them
+///
+/// Return the sorted byte ranges of the highlighted text, with the overlapping ones merged.
+/// The highlights not found in `original` are skipped.
+pub fn locate_highlights<'a, I>(original: &str, highlights: I) -> Vec<Range<usize>>
+where
+    I: IntoIterator<Item = &'a Highlight>,
+{
+    let mut ranges: Vec<Range<usize>> = highlights
+        .into_iter()
+        .filter_map(|highlight| highlight.locate(original))
+        .collect();
+    ranges.sort_by_key(|range| range.start);
+
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges.into_iter() {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, Keys};
+
+    #[test]
+    fn test_locate() {
+        let original = "The cat sat. The cat ran away.";
+        let highlight = Highlight::new("The cat", None);
+        assert_eq!(highlight.locate(original), Some(0..7));
+
+        let highlight = highlight.context("The cat ran away.");
+        assert_eq!(highlight.locate(original), Some(13..20));
+
+        let highlight = Highlight::new("dog", None);
+        assert_eq!(highlight.locate(original), None);
+
+        let highlights = [
+            Highlight::new("cat sat", None),
+            Highlight::new("sat. The", None),
+            Highlight::new("away", None),
+        ];
+        assert_eq!(
+            locate_highlights(original, highlights.iter()),
+            vec![4..16, 25..29]
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_highlight_event() {
+        let keys = Keys::generate();
+        let article = EventBuilder::long_form_text_note(
+            "Lorem ipsum dolor sit amet",
+            [Tag::Identifier(String::from("lorem"))],
+        )
+        .to_event(&keys)
+        .unwrap();
+
+        let highlight = Highlight::new("dolor sit", Some(HighlightSource::from(&article)))
+            .context("Lorem ipsum dolor sit amet")
+            .comment("Nice");
+        let event = EventBuilder::highlight(highlight.clone())
+            .to_event(&keys)
+            .unwrap();
+        assert_eq!(event.kind, Kind::Highlight);
+        assert_eq!(event.content, "dolor sit");
+        assert_eq!(
+            event.tags[1].as_vec(),
+            vec![
+                String::from("p"),
+                keys.public_key().to_string(),
+                String::new(),
+                String::from("author")
+            ]
+        );
+        assert_eq!(Highlight::from_event(&event).unwrap(), highlight);
+
+        let note = EventBuilder::new_text_note("Hello world", [])
+            .to_event(&keys)
+            .unwrap();
+        let highlight = Highlight::new("world", Some(HighlightSource::from(&note)));
+        let event = EventBuilder::highlight(highlight.clone())
+            .to_event(&keys)
+            .unwrap();
+        assert_eq!(Highlight::from_event(&event).unwrap(), highlight);
+    }
+}
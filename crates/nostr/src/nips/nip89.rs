@@ -0,0 +1,392 @@
+// Copyright (c) 2022-2023 Yuki Kishimoto
+// Copyright (c) 2023-2024 Rust Nostr Developers
+// Distributed under the MIT software license
+
+//! NIP89
+//!
+//! <https://github.com/nostr-protocol/nips/blob/master/89.md>
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use bitcoin::secp256k1::XOnlyPublicKey;
+
+use crate::nips::nip01::Coordinate;
+use crate::{Event, JsonUtil, Kind, Metadata, Tag, TagKind};
+
+const KIND: &str = "k";
+const BECH32_PLACEHOLDER: &str = "<bech32>";
+
+/// NIP89 Error
+#[derive(Debug)]
+pub enum Error {
+    /// Not a [`Kind::HandlerInformation`] or [`Kind::HandlerRecommendation`]
+    WrongKind,
+    /// Identifier (`d`) tag not found
+    IdentifierTagNotFound,
+    /// The identifier of the recommendation is not a kind
+    InvalidRecommendedKind,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongKind => write!(
+                f,
+                "Wrong kind: expected handler information or recommendation"
+            ),
+            Self::IdentifierTagNotFound => write!(f, "Identifier tag not found"),
+            Self::InvalidRecommendedKind => write!(f, "Invalid recommended kind"),
+        }
+    }
+}
+
+/// URL of a handler for a platform (i.e. `web`, `ios`, `android`)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandlerPlatform {
+    /// Platform
+    pub platform: String,
+    /// URL, with the `<bech32>` placeholder
+    pub url: String,
+    /// NIP19 entity type to put in place of the placeholder (i.e. `nevent`, `nprofile`)
+    pub entity: Option<String>,
+}
+
+impl HandlerPlatform {
+    /// Compose the URL to open `bech32` entity with the handler
+    pub fn url_for(&self, bech32: &str) -> String {
+        self.url.replace(BECH32_PLACEHOLDER, bech32)
+    }
+}
+
+/// Application handler information (kind `31990`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandlerInformation {
+    /// Identifier (`d` tag)
+    pub identifier: String,
+    /// Handled kinds
+    pub kinds: Vec<Kind>,
+    /// Application metadata
+    ///
+    /// If `None`, the metadata of the handler author must be used.
+    pub metadata: Option<Metadata>,
+    /// Platform URLs
+    pub platforms: Vec<HandlerPlatform>,
+}
+
+impl HandlerInformation {
+    /// New handler information
+    pub fn new<S>(identifier: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            identifier: identifier.into(),
+            kinds: Vec::new(),
+            metadata: None,
+            platforms: Vec::new(),
+        }
+    }
+
+    /// Add handled kinds
+    pub fn kinds<I>(mut self, kinds: I) -> Self
+    where
+        I: IntoIterator<Item = Kind>,
+    {
+        self.kinds.extend(kinds);
+        self
+    }
+
+    /// Set application metadata
+    pub fn metadata(self, metadata: Metadata) -> Self {
+        Self {
+            metadata: Some(metadata),
+            ..self
+        }
+    }
+
+    /// Add a platform URL
+    ///
+    /// The `url` must contain the `<bech32>` placeholder.
+    pub fn platform<P, U, E>(mut self, platform: P, url: U, entity: Option<E>) -> Self
+    where
+        P: Into<String>,
+        U: Into<String>,
+        E: Into<String>,
+    {
+        self.platforms.push(HandlerPlatform {
+            platform: platform.into(),
+            url: url.into(),
+            entity: entity.map(|e| e.into()),
+        });
+        self
+    }
+
+    /// Check if the handler supports the `kind`
+    pub fn handles(&self, kind: Kind) -> bool {
+        self.kinds.contains(&kind)
+    }
+
+    /// Parse a [`Kind::HandlerInformation`]
+    ///
+    /// An invalid metadata is ignored.
+    pub fn from_event(event: &Event) -> Result<Self, Error> {
+        if event.kind != Kind::HandlerInformation {
+            return Err(Error::WrongKind);
+        }
+
+        let identifier: &str = event.identifier().ok_or(Error::IdentifierTagNotFound)?;
+        let mut info = Self::new(identifier);
+
+        if !event.content.is_empty() {
+            info.metadata = Metadata::from_json(&event.content).ok();
+        }
+
+        for tag in event.tags.iter() {
+            if let Tag::Generic(TagKind::Custom(tag_kind), values) = tag {
+                match values.first() {
+                    Some(value) if tag_kind == KIND => {
+                        if let Ok(kind) = Kind::from_str(value) {
+                            info.kinds.push(kind);
+                        }
+                    }
+                    Some(url) if url.contains(BECH32_PLACEHOLDER) => {
+                        info.platforms.push(HandlerPlatform {
+                            platform: tag_kind.clone(),
+                            url: url.clone(),
+                            entity: values.get(1).cloned(),
+                        });
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        Ok(info)
+    }
+
+    /// Get handler [`Coordinate`]
+    pub fn coordinate(&self, public_key: XOnlyPublicKey) -> Coordinate {
+        Coordinate::new(Kind::HandlerInformation, public_key).identifier(self.identifier.clone())
+    }
+}
+
+impl From<HandlerInformation> for Vec<Tag> {
+    fn from(info: HandlerInformation) -> Self {
+        let HandlerInformation {
+            identifier,
+            kinds,
+            platforms,
+            ..
+        } = info;
+
+        let mut tags: Vec<Tag> = vec![Tag::Identifier(identifier)];
+
+        for kind in kinds.into_iter() {
+            tags.push(Tag::Generic(
+                TagKind::Custom(KIND.to_string()),
+                vec![kind.as_u64().to_string()],
+            ));
+        }
+
+        for HandlerPlatform {
+            platform,
+            url,
+            entity,
+        } in platforms.into_iter()
+        {
+            let mut values: Vec<String> = vec![url];
+            values.extend(entity);
+            tags.push(Tag::Generic(TagKind::Custom(platform), values));
+        }
+
+        tags
+    }
+}
+
+/// Recommended handler
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RecommendedHandler {
+    /// Coordinate of the [`Kind::HandlerInformation`], with the relay hint
+    pub coordinate: Coordinate,
+    /// Platform of the handler
+    pub platform: Option<String>,
+}
+
+/// Application handler recommendation (kind `31989`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandlerRecommendation {
+    /// Kind
+    pub kind: Kind,
+    /// Recommended handlers
+    pub handlers: Vec<RecommendedHandler>,
+}
+
+impl HandlerRecommendation {
+    /// New recommendation of handlers for `kind`
+    pub fn new(kind: Kind) -> Self {
+        Self {
+            kind,
+            handlers: Vec::new(),
+        }
+    }
+
+    /// Add recommended handler
+    pub fn handler(mut self, coordinate: Coordinate, platform: Option<String>) -> Self {
+        self.handlers.push(RecommendedHandler {
+            coordinate,
+            platform,
+        });
+        self
+    }
+
+    /// Parse a [`Kind::HandlerRecommendation`]
+    pub fn from_event(event: &Event) -> Result<Self, Error> {
+        if event.kind != Kind::HandlerRecommendation {
+            return Err(Error::WrongKind);
+        }
+
+        let identifier: &str = event.identifier().ok_or(Error::IdentifierTagNotFound)?;
+        let kind: Kind = Kind::from_str(identifier).map_err(|_| Error::InvalidRecommendedKind)?;
+        let mut recommendation = Self::new(kind);
+
+        for tag in event.tags.iter() {
+            match tag {
+                Tag::A {
+                    kind,
+                    public_key,
+                    identifier,
+                    relay_url,
+                } => {
+                    let mut coordinate = Coordinate::new(*kind, *public_key).identifier(identifier);
+                    if let Some(relay_url) = relay_url {
+                        coordinate.relays.push(relay_url.to_string());
+                    }
+                    recommendation.handlers.push(RecommendedHandler {
+                        coordinate,
+                        platform: None,
+                    });
+                }
+                // `a` tag with the platform
+                Tag::Generic(TagKind::A, values) => {
+                    let coordinate: Option<Coordinate> = values
+                        .first()
+                        .and_then(|value| Coordinate::from_str(value).ok());
+                    if let Some(mut coordinate) = coordinate {
+                        if let Some(relay_url) = values.get(1).filter(|url| !url.is_empty()) {
+                            coordinate.relays.push(relay_url.clone());
+                        }
+                        recommendation.handlers.push(RecommendedHandler {
+                            coordinate,
+                            platform: values.get(2).cloned(),
+                        });
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        Ok(recommendation)
+    }
+}
+
+impl From<HandlerRecommendation> for Vec<Tag> {
+    fn from(recommendation: HandlerRecommendation) -> Self {
+        let HandlerRecommendation { kind, handlers } = recommendation;
+
+        let mut tags: Vec<Tag> = vec![Tag::Identifier(kind.as_u64().to_string())];
+
+        for RecommendedHandler {
+            coordinate,
+            platform,
+        } in handlers.into_iter()
+        {
+            match platform {
+                Some(platform) => tags.push(Tag::Generic(
+                    TagKind::A,
+                    vec![
+                        format!(
+                            "{}:{}:{}",
+                            coordinate.kind.as_u64(),
+                            coordinate.pubkey,
+                            coordinate.identifier
+                        ),
+                        coordinate.relays.first().cloned().unwrap_or_default(),
+                        platform,
+                    ],
+                )),
+                None => tags.push(coordinate.into()),
+            }
+        }
+
+        tags
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_handler_information() {
+        use crate::{EventBuilder, Keys};
+
+        let keys = Keys::generate();
+        let info = HandlerInformation::new("my-app")
+            .kinds([Kind::TextNote, Kind::LongFormTextNote])
+            .metadata(Metadata::new().name("My App"))
+            .platform("web", "https://example.com/a/<bech32>", Some("nevent"))
+            .platform("ios", String::from("myapp://<bech32>"), None::<String>);
+        let event = EventBuilder::handler_information(info.clone())
+            .to_event(&keys)
+            .unwrap();
+        assert_eq!(event.kind, Kind::HandlerInformation);
+        assert_eq!(HandlerInformation::from_event(&event).unwrap(), info);
+        assert!(info.handles(Kind::LongFormTextNote));
+        assert!(!info.handles(Kind::Metadata));
+        assert_eq!(
+            info.platforms[0].url_for("nevent1abc"),
+            "https://example.com/a/nevent1abc"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_handler_recommendation() {
+        use crate::{EventBuilder, Keys};
+
+        let keys = Keys::generate();
+        let mut coordinate =
+            Coordinate::new(Kind::HandlerInformation, keys.public_key()).identifier("my-app");
+        coordinate
+            .relays
+            .push(String::from("wss://relay.example.com"));
+        let recommendation = HandlerRecommendation::new(Kind::TextNote)
+            .handler(coordinate.clone(), Some(String::from("web")))
+            .handler(coordinate, None);
+        let event = EventBuilder::handler_recommendation(recommendation.clone())
+            .to_event(&keys)
+            .unwrap();
+        assert_eq!(event.kind, Kind::HandlerRecommendation);
+        assert_eq!(event.identifier(), Some("1"));
+        assert_eq!(
+            event.tags[1].as_vec(),
+            vec![
+                String::from("a"),
+                format!("31990:{}:my-app", keys.public_key()),
+                String::from("wss://relay.example.com"),
+                String::from("web"),
+            ]
+        );
+        assert_eq!(
+            HandlerRecommendation::from_event(&event).unwrap(),
+            recommendation
+        );
+    }
+}
@@ -34,6 +34,8 @@ pub use crate::{Result, SECP256K1};
 
 // NIPs
 pub use crate::nips::nip01::{self, *};
+#[cfg(feature = "nip03")]
+pub use crate::nips::nip03::{self, *};
 #[cfg(feature = "nip04")]
 pub use crate::nips::nip04::{self, *};
 #[cfg(all(feature = "std", feature = "nip05"))]
@@ -42,13 +44,19 @@ pub use crate::nips::nip05::{self, *};
 pub use crate::nips::nip06::{self, *};
 #[cfg(all(feature = "nip07", target_arch = "wasm32"))]
 pub use crate::nips::nip07::{self, *};
+pub use crate::nips::nip10::{self, *};
 #[cfg(all(feature = "std", feature = "nip11"))]
 pub use crate::nips::nip11::{self, *};
 pub use crate::nips::nip13::{self, *};
 pub use crate::nips::nip15::{self, *};
 pub use crate::nips::nip19::{self, *};
 pub use crate::nips::nip21::{self, *};
+pub use crate::nips::nip23::{self, *};
+pub use crate::nips::nip25::{self, *};
 pub use crate::nips::nip26::{self, *};
+pub use crate::nips::nip34::{self, *};
+pub use crate::nips::nip38::{self, *};
+pub use crate::nips::nip42::{self, *};
 #[cfg(feature = "nip44")]
 pub use crate::nips::nip44::{self, *};
 #[cfg(all(feature = "std", feature = "nip46"))]
@@ -56,10 +64,18 @@ pub use crate::nips::nip46::{self, *};
 #[cfg(feature = "nip47")]
 pub use crate::nips::nip47::{self, *};
 pub use crate::nips::nip48::{self, *};
+#[cfg(feature = "nip49")]
+pub use crate::nips::nip49::{self, *};
+pub use crate::nips::nip51::{self, *};
 pub use crate::nips::nip53::{self, *};
+pub use crate::nips::nip56::{self, *};
 #[cfg(feature = "nip57")]
 pub use crate::nips::nip57::{self, *};
+#[cfg(feature = "nip59")]
+pub use crate::nips::nip59::{self, *};
 pub use crate::nips::nip65::{self, *};
+pub use crate::nips::nip84::{self, *};
+pub use crate::nips::nip89::{self, *};
 pub use crate::nips::nip90::{self, *};
 pub use crate::nips::nip94::{self, *};
 pub use crate::nips::nip98::{self, *};
@@ -85,6 +85,23 @@ impl Timestamp {
         self.0 -= secs as i64;
     }
 
+    /// Get UNIX timestamp, moved in the past by a random number of seconds within `window`
+    #[cfg(feature = "std")]
+    pub fn tweaked_within(window: Duration) -> Self {
+        let mut now: Timestamp = Self::now();
+        now.tweak_within_with_rng(window, &mut OsRng);
+        now
+    }
+
+    /// Remove a random number of seconds within `window` from [`Timestamp`]
+    pub fn tweak_within_with_rng<R>(&mut self, window: Duration, rng: &mut R)
+    where
+        R: Rng,
+    {
+        let secs: u64 = rng.gen_range(0..=window.as_secs());
+        self.0 -= secs as i64;
+    }
+
     /// Get timestamp as [`u64`]
     pub fn as_u64(&self) -> u64 {
         if self.0 >= 0 {
@@ -256,4 +273,20 @@ mod tests {
             String::from("2023-04-21T07:04:45Z")
         );
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_tweak_within() {
+        let window = Duration::from_secs(2 * 24 * 60 * 60);
+        for _ in 0..100 {
+            let mut timestamp = Timestamp::from(1682060685);
+            timestamp.tweak_within_with_rng(window, &mut OsRng);
+            assert!(timestamp <= Timestamp::from(1682060685));
+            assert!(timestamp >= Timestamp::from(1682060685) - window);
+        }
+
+        let mut timestamp = Timestamp::from(1682060685);
+        timestamp.tweak_within_with_rng(Duration::ZERO, &mut OsRng);
+        assert_eq!(timestamp, Timestamp::from(1682060685));
+    }
 }